@@ -0,0 +1,43 @@
+/*
+ * C interface to pbn-to-pdf (src/ffi.rs), built with
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Strings are UTF-8 and NUL-terminated.
+ */
+
+#ifndef PBN_TO_PDF_H
+#define PBN_TO_PDF_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Render a PBN file's text to a PDF. `layout` is a --layout name such as
+ * "analysis" or "bidding-sheets"; NULL means "analysis". Returns the PDF and
+ * stores its length in `out_len`, or returns NULL on failure (see
+ * pbn_to_pdf_last_error). Release the PDF with pbn_to_pdf_free.
+ */
+uint8_t *pbn_to_pdf_render(const char *pbn_utf8, const char *layout, size_t *out_len);
+
+/* Release a PDF returned by pbn_to_pdf_render; NULL is ignored. */
+void pbn_to_pdf_free(uint8_t *pdf, size_t len);
+
+/*
+ * Why the last call on this thread failed, or NULL. Valid until the next
+ * failing call on the same thread; don't free it.
+ */
+const char *pbn_to_pdf_last_error(void);
+
+/* The library version, e.g. "0.17.0"; don't free it. */
+const char *pbn_to_pdf_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PBN_TO_PDF_H */
@@ -0,0 +1,138 @@
+//! Declarer-play analysis without a PDF (`pbn-to-pdf analyze`)
+//!
+//! Runs the same analysis the Declarer's Plan layout circles cards with
+//! (sure winners, winners by promotion and winners by length) on each board,
+//! seen from declarer's side with dummy opposite.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::model::analysis::{find_length_winners, find_promotable_winners, find_sure_winners};
+use crate::model::{Board, Card, Contract, Direction, DirectionExt};
+use crate::parser::validate::card_text;
+
+/// Cards in PBN notation, e.g. ["SA", "SK"]
+fn card_names(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|&card| card_text(card)).collect()
+}
+
+/// Winners that cost tricks first: honors knocked out, or tricks ducked
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Developed {
+    /// Cards given up to set the winners up
+    pub lost: Vec<String>,
+    pub winners: Vec<String>,
+}
+
+/// Declarer-play analysis of one board
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardAnalysis {
+    pub board: String,
+    pub contract: Option<String>,
+    pub declarer: String,
+    pub sure_winners: Vec<String>,
+    pub promotable: Developed,
+    pub length: Developed,
+}
+
+impl BoardAnalysis {
+    /// Analyze a board, or None if it has no deal
+    pub fn new(board: &Board) -> Option<Self> {
+        if board.deal.is_empty() {
+            return None;
+        }
+        // Like the Declarer's Plan layout, assume South declares without a contract
+        let declarer = board
+            .contract
+            .as_ref()
+            .map(|c| c.declarer)
+            .unwrap_or(Direction::South);
+        let declarer_hand = board.deal.hand(declarer);
+        let dummy = board.deal.hand(declarer.partner());
+
+        let promotion = find_promotable_winners(dummy, declarer_hand);
+        let length = find_length_winners(dummy, declarer_hand);
+        Some(Self {
+            board: board
+                .board_id
+                .clone()
+                .or_else(|| board.number.map(|n| n.to_string()))
+                .unwrap_or_else(|| "?".to_string()),
+            contract: board.contract.as_ref().map(Contract::to_pbn),
+            declarer: declarer.pbn_text().to_string(),
+            sure_winners: card_names(&find_sure_winners(dummy, declarer_hand)),
+            promotable: Developed {
+                lost: card_names(&promotion.spent),
+                winners: card_names(&promotion.winners),
+            },
+            length: Developed {
+                lost: card_names(&length.ducks),
+                winners: card_names(&length.winners),
+            },
+        })
+    }
+}
+
+/// "3: SA SK HA", or "0" for no cards
+fn card_list(cards: &[String]) -> String {
+    if cards.is_empty() {
+        "0".to_string()
+    } else {
+        format!("{}: {}", cards.len(), cards.join(" "))
+    }
+}
+
+impl fmt::Display for BoardAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.contract {
+            Some(contract) => {
+                writeln!(f, "Board {}: {} by {}", self.board, contract, self.declarer)?
+            }
+            None => writeln!(f, "Board {}: declarer {}", self.board, self.declarer)?,
+        }
+        writeln!(f, "  Sure winners     {}", card_list(&self.sure_winners))?;
+        write!(
+            f,
+            "  By promotion     {}",
+            card_list(&self.promotable.winners)
+        )?;
+        if !self.promotable.lost.is_empty() {
+            write!(f, " (knock out with {})", self.promotable.lost.join(" "))?;
+        }
+        writeln!(f)?;
+        write!(f, "  By length        {}", card_list(&self.length.winners))?;
+        if !self.length.lost.is_empty() {
+            write!(f, " (duck {})", self.length.lost.join(" "))?;
+        }
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Contract;
+    use crate::parser::deal::parse_deal;
+
+    #[test]
+    fn test_analysis_from_declarer_side() {
+        let mut board = Board::new().with_number(7);
+        board.deal =
+            parse_deal("N:AKQJ.T98.765.432 T987.AKQ.432.765 65432.J765.AK.A8 .432.QJT98.KQJT9")
+                .unwrap();
+        let mut contract = Contract::parse("4S").unwrap();
+        contract.declarer = Direction::North;
+        board.contract = Some(contract);
+
+        let analysis = BoardAnalysis::new(&board).unwrap();
+        assert_eq!(analysis.board, "7");
+        assert_eq!(analysis.contract.as_deref(), Some("4S"));
+        assert_eq!(analysis.declarer, "N");
+        assert!(analysis.sure_winners.contains(&"SA".to_string()));
+        assert!(analysis.sure_winners.contains(&"DA".to_string()));
+        assert!(analysis.to_string().starts_with("Board 7: 4S by N\n"));
+
+        assert!(BoardAnalysis::new(&Board::new()).is_none());
+    }
+}
@@ -1,37 +1,264 @@
-use clap::{Parser, ValueEnum};
-use std::path::PathBuf;
+use clap::builder::PossibleValue;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use super::split::DEFAULT_SPLIT_NAME;
+use super::values::{
+    AuctionHeaders, ColorTheme, CompressionLevel, Duplex, FontEmbedding, HiddenHandStyle, Language,
+    Layout, SuitStyle, TitleBlock,
+};
+use crate::config::profiles::{find_config_file, profile_args};
+use crate::error::ConfigError;
+use crate::model::metadata::{ColorSettings, Margins};
+use crate::model::{Board, Direction};
+use crate::parser::header::parse_pip_colors;
+
+/// Subcommands
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Check PBN files for errors (bad deals, insufficient bids, cards played
+    /// from the wrong hand) and print them with line numbers
+    Validate {
+        /// PBN files, directories or glob patterns ("-" reads standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+    },
+    /// Print one line per board (number, dealer, vulnerability, contract, HCP
+    /// split, commentary) to help choose a board range
+    List {
+        /// PBN files, directories or glob patterns ("-" reads standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+    },
+    /// Print statistics across all the boards (HCP per seat, contract strains,
+    /// vulnerability and dealer balance) to check a practice set is balanced
+    Stats {
+        /// PBN files, directories or glob patterns ("-" reads standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Print JSON instead of a text table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the declarer-play analysis of each board (sure winners, winners
+    /// by promotion and by length), as used by the Declarer's Plan layout
+    Analyze {
+        /// PBN files, directories or glob patterns ("-" reads standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Score a session from PBN results or a results CSV, and rank the pairs
+    Score {
+        /// PBN files with [Contract], [Declarer] and [Result] for each table,
+        /// or .csv results files ("-" reads PBN from standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Compare the results of each board by matchpoints or Butler IMPs
+        #[arg(long, value_enum, default_value = "matchpoints")]
+        method: ScoringMethod,
+
+        /// Mitchell keeps North-South and East-West pairs apart, so NS 1
+        /// and EW 1 are different pairs; Howell matches pairs by name
+        /// whichever direction they sat
+        #[arg(long, value_enum, default_value = "mitchell")]
+        movement: Movement,
+
+        /// Print the rankings as CSV instead of text
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Solve each board double dummy and write the PBN back with
+    /// [DoubleDummyTricks] and [OptimumScore] tags, so later renders need not
+    /// recompute them
+    Dd {
+        /// PBN file ("-" reads standard input)
+        file: PathBuf,
+
+        /// Output PBN file ("-" or omitted writes standard output)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge PBN files into one, dropping repeated boards and renumbering a
+    /// file whose board numbers clash with those before it
+    Merge {
+        /// PBN files, directories or glob patterns ("-" reads standard input)
+        #[arg(required = true, num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Output PBN file ("-" or omitted writes standard output)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Renumber the boards of a PBN file by an offset, from a start value or
+    /// from a mapping file
+    #[command(group(
+        clap::ArgGroup::new("numbering")
+            .required(true)
+            .args(["offset", "start", "map"])
+    ))]
+    Renumber {
+        /// PBN file ("-" reads standard input)
+        file: PathBuf,
+
+        /// Add to every board number (negative to number down)
+        #[arg(long, allow_negative_numbers = true)]
+        offset: Option<i64>,
+
+        /// Number the boards in file order from this value
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        start: Option<u32>,
+
+        /// File of "old new" board number pairs, one per line
+        #[arg(long)]
+        map: Option<PathBuf>,
+
+        /// Also set each board's dealer and vulnerability from its new number
+        #[arg(long)]
+        derive: bool,
+
+        /// Output PBN file ("-" or omitted writes standard output)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Copy a board range or the boards matching a filter into a new PBN file
+    Extract {
+        /// PBN file ("-" reads standard input)
+        file: PathBuf,
+
+        /// Board range to extract (e.g., "5-8", "1-16,!13" or "Ex 3")
+        #[arg(short = 'b', long)]
+        boards: Option<String>,
+
+        /// Only extract boards matching a condition, e.g. "contract=NT"
+        #[arg(long, value_name = "EXPR")]
+        filter: Option<String>,
+
+        /// Output PBN file ("-" or omitted writes standard output)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Turn the boards of a PBN file round the table so a chosen seat deals,
+    /// declares or sits South
+    #[command(group(
+        clap::ArgGroup::new("rotation")
+            .required(true)
+            .args(["dealer", "declarer", "south"])
+    ))]
+    Rotate {
+        /// PBN file ("-" reads standard input)
+        file: PathBuf,
+
+        /// Turn each board so this seat deals
+        #[arg(long, value_name = "SEAT", value_parser = parse_seat)]
+        dealer: Option<Direction>,
+
+        /// Turn each board so this seat declares
+        #[arg(long, value_name = "SEAT", value_parser = parse_seat)]
+        declarer: Option<Direction>,
+
+        /// Turn every board so this seat's hand goes South
+        #[arg(long, value_name = "SEAT", value_parser = parse_seat)]
+        south: Option<Direction>,
+
+        /// Output PBN file ("-" or omitted writes standard output)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Browse a file's boards in the terminal (hands, auction, commentary)
+    /// and mark the ones to print; confirming prints them as a --boards range
+    Preview {
+        /// PBN file ("-" reads standard input)
+        file: PathBuf,
+    },
+    /// Compare two PBN files board by board (deals, auctions, commentary) and
+    /// print what changed, to review a lesson's edits before reprinting
+    Diff {
+        /// The earlier version ("-" reads standard input)
+        old: PathBuf,
+
+        /// The later version
+        new: PathBuf,
+    },
+}
+
+/// How pairs are identified across boards (`score --movement`)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Movement {
+    /// Pairs stay North-South or East-West; a pair is its direction and name
+    #[default]
+    Mitchell,
+    /// Pairs change direction; a pair is its name alone
+    Howell,
+}
+
+/// How results on a board are compared (`score --method`)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ScoringMethod {
+    /// Matchpoints: pairs ranked by percentage
+    Matchpoints,
+    /// IMPs against the average score on each board (Butler)
+    Imps,
+}
 
 #[derive(Parser, Debug)]
 #[command(name = "pbn-to-pdf")]
 #[command(
     author,
     version,
-    about = "Convert PBN bridge files to PDF with Bridge Composer-style formatting"
+    about = "Convert PBN bridge files to PDF with Bridge Composer-style formatting",
+    args_override_self = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 pub struct Args {
-    /// Input PBN file path
-    #[arg(required = true)]
-    pub input: PathBuf,
+    /// Run a subcommand instead of converting files
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Input PBN files, directories or glob patterns ("-" reads standard input)
+    #[arg(required = true, num_args = 1..)]
+    pub inputs: Vec<PathBuf>,
 
-    /// Output PDF file path (defaults to input with .pdf extension)
+    /// Output PDF file path ("-" writes standard output; defaults to input with
+    /// .pdf extension, or standard output when reading standard input)
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
+    /// Directory for the PDFs (defaults to each input file's directory)
+    #[arg(long)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Number of files converted at once in batch mode (defaults to the number of CPUs)
+    #[arg(short = 'j', long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+
     /// Number of boards per page (1, 2, or 4)
     #[arg(short = 'n', long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..=4))]
     pub boards_per_page: u8,
 
-    /// Page size
-    #[arg(short = 's', long, value_enum, default_value = "letter")]
+    /// Page size: letter, a4, a5, legal, or "WxHmm" (e.g. "170x240mm")
+    #[arg(short = 's', long, default_value = "letter", value_parser = parse_page_size)]
     pub page_size: PageSize,
 
     /// Page orientation
     #[arg(long, value_enum, default_value = "portrait")]
     pub orientation: Orientation,
 
-    /// Output layout style
+    /// Double-sided printing: pad to whole sheets so fronts and backs line up
+    #[arg(long, value_enum)]
+    pub duplex: Option<Duplex>,
+
+    /// Output layout style; "all" writes every layout, each to its own PDF
     #[arg(short = 'l', long, value_enum, default_value = "analysis")]
-    pub layout: Layout,
+    pub layout: LayoutSelection,
 
     /// Hide bidding table
     #[arg(long)]
@@ -49,13 +276,99 @@ pub struct Args {
     #[arg(long)]
     pub no_hcp: bool,
 
-    /// Board range to include (e.g., "1-16" or "5,8,12")
+    /// Print each hand's HCP in the compass corners (also set by %BCOptions ShowHCP)
+    #[arg(long)]
+    pub hand_hcp: bool,
+
+    /// Add length points to the per-hand HCP, e.g. "12+1"
+    #[arg(long)]
+    pub distribution_points: bool,
+
+    /// Print the board number inside the compass and mark the dealer, instead of title lines
+    #[arg(long)]
+    pub board_in_compass: bool,
+
+    /// Shade the compass arms red/white by vulnerability, like a duplicate board
+    #[arg(long)]
+    pub vulnerable_compass: bool,
+
+    /// Underline the opening lead in the leader's hand (in addition to the "Lead:" line)
+    #[arg(long)]
+    pub lead_marker: bool,
+
+    /// Draw East and West rotated a quarter turn, suits running vertically
+    #[arg(long)]
+    pub rotate_east_west: bool,
+
+    /// Show double-dummy makeable tricks (from [DoubleDummyTricks]) beside the diagram
+    #[arg(long)]
+    pub dd_tricks: bool,
+
+    /// Show the double-dummy best opening leads (from [BestLeads]) below the contract
+    #[arg(long)]
+    pub best_leads: bool,
+
+    /// Describe the auction in words below the bidding table ("North opened 1NT, ...")
+    #[arg(long)]
+    pub auction_prose: bool,
+
+    /// Board range to include (e.g., "1-16", "5,8,12", "17-", "1-32,!13" or "Ex 3")
     #[arg(short = 'b', long)]
     pub boards: Option<String>,
 
-    /// Page margins (overrides PBN %Margins)
-    #[arg(short = 'm', long, value_enum)]
-    pub margins: Option<MarginPreset>,
+    /// Skip tags and commentary that can't be read, reporting each with its
+    /// line, instead of failing the whole file
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Only include boards matching a condition, e.g. "hcp(N)+hcp(S)>=25 && contract=NT"
+    #[arg(long, value_name = "EXPR")]
+    pub filter: Option<String>,
+
+    /// Order boards by a key instead of file order
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub sort_by: Option<SortKey>,
+
+    /// Reverse the --sort-by order
+    #[arg(long, requires = "sort_by")]
+    pub reverse: bool,
+
+    /// Write a separate PDF for every N boards
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub split_every: Option<u32>,
+
+    /// Write a separate PDF for each session (run of boards with the same [Event])
+    #[arg(long)]
+    pub split_by_session: bool,
+
+    /// File name of each split PDF; may use {stem}, {range}, {part} and {event}
+    #[arg(long, value_name = "TEMPLATE", default_value = DEFAULT_SPLIT_NAME)]
+    pub split_name: String,
+
+    /// Render N boards at a time and join them into one PDF, to save memory on
+    /// very large files; each chunk starts a new page
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub chunk_size: Option<u32>,
+
+    /// Keep running and regenerate the PDF whenever the input file changes,
+    /// redrawing only the chunks of boards that changed
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Report the time spent parsing, loading fonts, measuring, drawing,
+    /// saving and compressing, and on the slowest boards
+    #[arg(long)]
+    pub timings: bool,
+
+    /// Convention file mapping bidding sequences to meanings, footnoted in auctions
+    /// (one "1NT 2♣ = Stayman" per line)
+    #[arg(long, value_name = "FILE")]
+    pub conventions: Option<PathBuf>,
+
+    /// Page margins: narrow, standard, wide, or "top,right,bottom,left" in mm
+    /// (overrides PBN %Margins)
+    #[arg(short = 'm', long, value_parser = parse_margins)]
+    pub margins: Option<Margins>,
 
     /// Draw debug boxes around layout regions
     #[arg(long)]
@@ -73,18 +386,194 @@ pub struct Args {
     #[arg(long)]
     pub circle_length_winners: bool,
 
-    /// Title for bidding sheets banner. Overrides %HRTitleEvent.
+    /// Add fillable PDF form fields over fill-in blanks in auctions
+    #[arg(long)]
+    pub fillable: bool,
+
+    /// Put answers (auctions, contracts, leads, winner circles) on a toggleable PDF layer
+    #[arg(long)]
+    pub answers_layer: bool,
+
+    /// Suit colors as "#spades,#hearts,#diamonds,#clubs" (overrides %PipColors)
+    #[arg(long, value_name = "COLORS", value_parser = parse_suit_colors)]
+    pub suit_colors: Option<ColorSettings>,
+
+    /// Use a four-color deck: orange diamonds and green clubs
+    #[arg(long)]
+    pub four_color: bool,
+
+    /// Print in shades of gray with lighter banner fills, to save ink when photocopying
+    #[arg(long)]
+    pub grayscale: bool,
+
+    /// Color theme for suits, compass, banners and separators
+    #[arg(long, value_enum, default_value = "classic")]
+    pub theme: ColorTheme,
+
+    /// Show hidden hands as nothing, a shaded box or face-down cards
+    #[arg(long, value_enum, default_value = "omit")]
+    pub hidden_hands: HiddenHandStyle,
+
+    /// Draw suits as symbols or as letters (S/H/D/C)
+    #[arg(long, value_enum, default_value = "symbols")]
+    pub suit_style: SuitStyle,
+
+    /// Compress PDF streams: not at all (for post-processing), quickly, or as small as possible
+    #[arg(long, value_enum, default_value = "best")]
+    pub compress: CompressionLevel,
+
+    /// Embed only the glyphs used (subset), whole font files (full), or no font files beyond the suit symbols (builtin)
+    #[arg(long, value_enum, default_value = "subset")]
+    pub font_embedding: FontEmbedding,
+
+    /// Auction column headers: direction names, player names (from [West] etc.) or both
+    #[arg(long, value_enum, default_value = "directions")]
+    pub auction_headers: AuctionHeaders,
+
+    /// Continue auctions longer than this many rows of calls in a second block to the right
+    #[arg(long, value_name = "N")]
+    pub auction_max_rows: Option<usize>,
+
+    /// How Pass is written in auctions (default from --language, e.g. "Pass")
+    #[arg(long, value_name = "TEXT")]
+    pub pass_text: Option<String>,
+
+    /// How Double is written in auctions (e.g. "X"; default "Dbl", "X" on bidding sheets)
+    #[arg(long, value_name = "TEXT")]
+    pub double_text: Option<String>,
+
+    /// How Redouble is written in auctions (e.g. "XX"; default "Rdbl", "XX" on bidding sheets)
+    #[arg(long, value_name = "TEXT")]
+    pub redouble_text: Option<String>,
+
+    /// Wording that replaces the final three passes (default "All Pass")
+    #[arg(long, value_name = "TEXT")]
+    pub all_pass_text: Option<String>,
+
+    /// Wording for a passed-out auction (default "Passed Out")
+    #[arg(long, value_name = "TEXT")]
+    pub passed_out_text: Option<String>,
+
+    /// Hyphenate long words when wrapping commentary (patterns for --language)
+    #[arg(long)]
+    pub hyphenate: bool,
+
+    /// Show tens as "T" instead of "10" in diagrams, leads and commentary
+    #[arg(long)]
+    pub ten_as_t: bool,
+
+    /// Make card references in commentary clickable links to the diagram
+    #[arg(long)]
+    pub link_card_refs: bool,
+
+    /// Print a QR code per board linking to the deal in the BBO handviewer
+    #[arg(long)]
+    pub qr_codes: bool,
+
+    /// Page header as "left|center|right" (e.g. "{event}||{date}").
+    /// Fields may use {page}, {pages}, {boards}, {event}, {date}, {site},
+    /// {title} and {subtitle}.
+    #[arg(long, value_name = "SPEC")]
+    pub header: Option<String>,
+
+    /// Page footer as "left|center|right" (same variables as --header)
+    #[arg(long, value_name = "SPEC")]
+    pub footer: Option<String>,
+
+    /// Print "Page N of M" in the footer center (unless --footer sets it)
+    #[arg(long)]
+    pub page_numbers: bool,
+
+    /// TrueType/OpenType font for hand diagrams: "regular.ttf[,bold,italic,bold-italic]"
+    #[arg(long, value_name = "FILES")]
+    pub font_diagram: Option<String>,
+
+    /// TrueType/OpenType font for commentary (same format as --font-diagram)
+    #[arg(long, value_name = "FILES")]
+    pub font_commentary: Option<String>,
+
+    /// TrueType/OpenType font for board titles and auctions (same format as --font-diagram)
+    #[arg(long, value_name = "FILES")]
+    pub font_hand_record: Option<String>,
+
+    /// Language for generated labels ("Deals", "Lead:", "All Pass", ...)
+    #[arg(long, value_enum, default_value = "english")]
+    pub language: Language,
+
+    /// Title for bidding sheets banner and title block. Overrides %HRTitleEvent.
     /// Use --title with no value to hide the title.
     #[arg(short = 't', long, num_args = 0..=1, default_missing_value = "")]
     pub title: Option<String>,
 
+    /// Subtitle shown after the title in banners and as the second line of the
+    /// title block. Overrides the %HRTitleClub/Site/Date line.
+    /// Use --subtitle with no value to hide it.
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    pub subtitle: Option<String>,
+
+    /// Logo image (SVG, PNG or JPEG) drawn at the left of the page header.
+    /// Overrides %HRTitleLogo.
+    #[arg(long, value_name = "FILE")]
+    pub logo: Option<PathBuf>,
+
+    /// Layout template (TOML) adjusting margins, columns, diagram and auction
+    /// geometry, font sizes and the diagram side
+    #[arg(long, value_name = "FILE")]
+    pub template: Option<PathBuf>,
+
+    /// Letterhead drawn behind every page: a PDF (page N behind page N, the last
+    /// page repeating) or an SVG, PNG or JPEG image stretched to the page
+    #[arg(long, value_name = "FILE")]
+    pub background: Option<PathBuf>,
+
+    /// Downsample PNG and JPEG logos and backgrounds to at most this many
+    /// pixels per inch at the size they're drawn (card images are vector art)
+    #[arg(long, value_name = "DPI", value_parser = clap::value_parser!(u32).range(36..))]
+    pub image_dpi: Option<u32>,
+
+    /// Copyright line printed at the bottom of every page. Overrides %HRCopyright.
+    /// Use --copyright with no value to hide it.
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    pub copyright: Option<String>,
+
+    /// Print the %HRTitle block (event, club/site, date) above the first page or every page
+    #[arg(long, value_enum, default_value = "none")]
+    pub title_block: TitleBlock,
+
+    /// Named profile from the config file bundling a layout and options.
+    /// Options on the command line override the profile's.
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Config file with [profile.NAME] tables
+    /// (default: ./pbn-to-pdf.toml, then ~/.config/pbn-to-pdf/config.toml)
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
     /// Verbosity level (-v, -vv, -vvv)
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
     pub verbose: u8,
+
+    /// How warnings and errors are reported on standard error: text, or one
+    /// JSON object per line for editor plugins and CI checks
+    #[arg(long, value_enum, default_value = "text", global = true)]
+    pub diagnostics: DiagnosticsFormat,
+}
+
+/// Format of warnings and errors on standard error (`--diagnostics`)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum DiagnosticsFormat {
+    /// Plain log lines
+    #[default]
+    Text,
+    /// One JSON object per line with severity, message and, where known,
+    /// file and line
+    Json,
 }
 
 /// Preset margin sizes
-#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum MarginPreset {
     /// Narrow margins (1/4 inch = 6.35mm)
     Narrow,
@@ -105,11 +594,14 @@ impl MarginPreset {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PageSize {
     Letter,
     A4,
+    A5,
     Legal,
+    /// Width and height in mm (portrait)
+    Custom(f32, f32),
 }
 
 impl PageSize {
@@ -117,82 +609,304 @@ impl PageSize {
         match self {
             PageSize::Letter => (215.9, 279.4),
             PageSize::A4 => (210.0, 297.0),
+            PageSize::A5 => (148.0, 210.0),
             PageSize::Legal => (215.9, 355.6),
+            PageSize::Custom(width, height) => (*width, *height),
         }
     }
 }
 
+/// Parse a seat: N, E, S, W or the full name, in any case
+pub fn parse_seat(spec: &str) -> Result<Direction, String> {
+    let spec = spec.trim().to_ascii_lowercase();
+    let seat = match spec.as_str() {
+        "n" | "north" => Direction::North,
+        "e" | "east" => Direction::East,
+        "s" | "south" => Direction::South,
+        "w" | "west" => Direction::West,
+        _ => return Err(format!("Invalid seat: {} (expected N, E, S or W)", spec)),
+    };
+    Ok(seat)
+}
+
+/// Parse --page-size: letter, a4, a5, legal, or "WxH" in mm (e.g. "170x240mm")
+pub fn parse_page_size(spec: &str) -> Result<PageSize, String> {
+    let spec = spec.trim().to_ascii_lowercase();
+    match spec.as_str() {
+        "letter" => return Ok(PageSize::Letter),
+        "a4" => return Ok(PageSize::A4),
+        "a5" => return Ok(PageSize::A5),
+        "legal" => return Ok(PageSize::Legal),
+        _ => {}
+    }
+
+    let invalid = || {
+        format!(
+            "Invalid page size: {} (expected letter, a4, a5, legal or WxHmm)",
+            spec
+        )
+    };
+    let (width, height) = spec
+        .strip_suffix("mm")
+        .unwrap_or(&spec)
+        .split_once('x')
+        .ok_or_else(invalid)?;
+    let width: f32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: f32 = height.trim().parse().map_err(|_| invalid())?;
+    if width <= 0.0 || height <= 0.0 {
+        return Err(invalid());
+    }
+    Ok(PageSize::Custom(width, height))
+}
+
+/// Parse --margins: a preset (narrow, standard, wide) or "top,right,bottom,left" in mm
+pub fn parse_margins(spec: &str) -> Result<Margins, String> {
+    if let Ok(preset) = MarginPreset::from_str(spec.trim(), true) {
+        let m = preset.size_mm();
+        return Ok(Margins {
+            left: m,
+            right: m,
+            top: m,
+            bottom: m,
+        });
+    }
+
+    let sides = spec
+        .split(',')
+        .map(|side| side.trim().parse::<f32>().ok().filter(|m| *m >= 0.0))
+        .collect::<Option<Vec<_>>>();
+    match sides.as_deref() {
+        Some(&[top, right, bottom, left]) => Ok(Margins {
+            left,
+            right,
+            top,
+            bottom,
+        }),
+        _ => Err(format!(
+            "Invalid margins: {} (expected narrow, standard, wide or top,right,bottom,left in mm)",
+            spec
+        )),
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Orientation {
     Portrait,
     Landscape,
 }
 
-/// Output layout style
-#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Default)]
-pub enum Layout {
-    /// Standard analysis layout with hand diagram, bidding, and commentary
-    #[default]
-    Analysis,
-    /// Bidding practice sheets for face-to-face practice
-    BiddingSheets,
-    /// Declarer's plan - 1 deal per page (full size)
-    #[value(name = "declarers-plan-1up")]
-    DeclarersPlan1up,
-    /// Declarer's plan - 2 deals per page (rotated 90°)
-    #[value(name = "declarers-plan-2up")]
-    DeclarersPlan2up,
-    /// Declarer's plan practice sheets (4 deals per page)
-    DeclarersPlan,
-    /// Dealer summary showing board, dealer, contract, declarer, and lead (6 per page)
-    DealerSummary,
-}
-
-impl Layout {
-    /// Get the suffix to append to output filename (without extension)
-    pub fn output_suffix(&self) -> Option<&'static str> {
+/// Key for reordering boards (`--sort-by`)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// Board number
+    Number,
+    /// Dealer, clockwise from North
+    Dealer,
+    /// Contract strain (notrump first), then level
+    Contract,
+    /// North-South combined high card points
+    Hcp,
+    /// Theme of the deal, from its [Event] tag
+    Theme,
+}
+
+/// `--layout`: one layout, or all of them
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutSelection {
+    One(Layout),
+    All,
+}
+
+impl Default for LayoutSelection {
+    fn default() -> Self {
+        LayoutSelection::One(Layout::default())
+    }
+}
+
+impl LayoutSelection {
+    /// The layouts to write
+    pub fn layouts(&self) -> Vec<Layout> {
         match self {
-            Layout::Analysis => None,
-            Layout::BiddingSheets => Some(" - Bidding Sheets"),
-            Layout::DeclarersPlan1up => Some(" - Declarers Plan"),
-            Layout::DeclarersPlan2up => Some(" - Declarers Plan 2up"),
-            Layout::DeclarersPlan => Some(" - Declarers Plan 4up"),
-            Layout::DealerSummary => Some(" - Dealer Summary"),
+            LayoutSelection::One(layout) => vec![*layout],
+            LayoutSelection::All => Layout::ALL.to_vec(),
         }
     }
 
-    /// Returns true if this is any declarer's plan variant
-    pub fn is_declarers_plan(&self) -> bool {
-        matches!(
-            self,
-            Layout::DeclarersPlan | Layout::DeclarersPlan1up | Layout::DeclarersPlan2up
-        )
+    /// The layout whose output name and settings are the defaults; Analysis
+    /// for `all`, whose output has no suffix
+    pub fn primary(&self) -> Layout {
+        match self {
+            LayoutSelection::One(layout) => *layout,
+            LayoutSelection::All => Layout::Analysis,
+        }
+    }
+}
+
+impl From<Layout> for LayoutSelection {
+    fn from(layout: Layout) -> Self {
+        LayoutSelection::One(layout)
+    }
+}
+
+impl ValueEnum for LayoutSelection {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            LayoutSelection::One(Layout::Analysis),
+            LayoutSelection::One(Layout::BiddingSheets),
+            LayoutSelection::One(Layout::DeclarersPlan1up),
+            LayoutSelection::One(Layout::DeclarersPlan2up),
+            LayoutSelection::One(Layout::DeclarersPlan),
+            LayoutSelection::One(Layout::DealerSummary),
+            LayoutSelection::All,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            LayoutSelection::One(layout) => layout.to_possible_value(),
+            LayoutSelection::All => {
+                Some(PossibleValue::new("all").help("Every layout, each to its own PDF"))
+            }
+        }
+    }
+}
+
+/// Where `--layout all` writes `layout`: `output` with the layout's suffix
+/// added to its name
+pub fn layout_output_path(output: &Path, layout: Layout) -> PathBuf {
+    let stem = output.file_stem().unwrap_or_default().to_string_lossy();
+    let name = format!("{}{}.pdf", stem, layout.output_suffix().unwrap_or(""));
+    output.with_file_name(name)
+}
+
+/// Path standing for standard input or output
+pub const STDIO_PATH: &str = "-";
+
+/// Whether a path is `-`, standard input or output
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
+/// Whether an input is a glob pattern (`lessons/*.pbn`) rather than a path
+pub fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// Expand the command-line inputs into the PBN files to convert.
+///
+/// Directories contribute their `.pbn` files, glob patterns the files they
+/// match; both are sorted by name. Other paths (and `-`) are kept as given.
+pub fn expand_inputs(inputs: &[PathBuf]) -> Result<Vec<PathBuf>, ConfigError> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let mut matched: Vec<PathBuf> = if input.is_dir() {
+            std::fs::read_dir(input)
+                .map_err(|e| ConfigError::InvalidInput(format!("{}: {}", input.display(), e)))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file() && has_pbn_extension(path))
+                .collect()
+        } else if is_glob_pattern(input) {
+            let pattern = input.to_string_lossy();
+            glob::glob(&pattern)
+                .map_err(|e| ConfigError::InvalidInput(format!("{}: {}", pattern, e)))?
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect()
+        } else {
+            files.push(input.clone());
+            continue;
+        };
+        if matched.is_empty() {
+            return Err(ConfigError::InvalidInput(format!(
+                "{}: no PBN files found",
+                input.display()
+            )));
+        }
+        matched.sort();
+        files.append(&mut matched);
     }
+    Ok(files)
+}
+
+fn has_pbn_extension(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pbn"))
 }
 
 impl Args {
-    /// Get the output path, defaulting to input with layout-specific suffix
-    pub fn output_path(&self) -> PathBuf {
+    /// Parse the command line, expanding `--profile` from the config file.
+    ///
+    /// The profile's options go in front of the real command line, so options
+    /// given there override the profile's.
+    pub fn parse_with_profile() -> Result<Self, ConfigError> {
+        let args = Self::parse();
+        let Some(ref name) = args.profile else {
+            return Ok(args);
+        };
+        let path = args
+            .config
+            .clone()
+            .or_else(find_config_file)
+            .ok_or_else(|| {
+                ConfigError::InvalidConfig(format!("no config file found for profile \"{}\"", name))
+            })?;
+        let config = std::fs::read_to_string(&path)
+            .map_err(|e| ConfigError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+
+        let mut argv: Vec<OsString> = std::env::args_os().collect();
+        let profile = profile_args(&config, name)?;
+        argv.splice(1..1, profile.into_iter().map(OsString::from));
+        Ok(Self::parse_from(argv))
+    }
+
+    /// Whether the PBN is read from standard input (`-`)
+    pub fn reads_stdin(&self) -> bool {
+        matches!(&self.inputs[..], [input] if is_stdio(input))
+    }
+
+    /// Whether several files are converted: more than one input, a directory or
+    /// glob pattern, or an `--out-dir`
+    pub fn is_batch(&self) -> bool {
+        self.inputs.len() > 1
+            || self.out_dir.is_some()
+            || self
+                .inputs
+                .iter()
+                .any(|input| input.is_dir() || is_glob_pattern(input))
+    }
+
+    /// Get the output path for an input file, defaulting to the input with a
+    /// layout-specific suffix, in `--out-dir` if given (`-`, standard output,
+    /// when reading standard input)
+    pub fn output_path(&self, input: &Path) -> PathBuf {
         self.output.clone().unwrap_or_else(|| {
+            if is_stdio(input) {
+                return PathBuf::from(STDIO_PATH);
+            }
+
             // Get the input file stem (name without extension)
-            let stem = self.input.file_stem().unwrap_or_default().to_string_lossy();
+            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
 
             // Add layout-specific suffix if applicable
-            let new_name = if let Some(suffix) = self.layout.output_suffix() {
+            let new_name = if let Some(suffix) = self.layout.primary().output_suffix() {
                 format!("{}{}.pdf", stem, suffix)
             } else {
                 format!("{}.pdf", stem)
             };
 
-            // Keep the same directory as the input file
-            if let Some(parent) = self.input.parent() {
-                parent.join(new_name)
-            } else {
-                PathBuf::from(new_name)
+            // Keep the same directory as the input file unless --out-dir is given
+            match self.out_dir.as_deref().or_else(|| input.parent()) {
+                Some(dir) => dir.join(new_name),
+                None => PathBuf::from(new_name),
             }
         })
     }
 
+    /// Whether each file's boards are written to several PDFs
+    pub fn splits_output(&self) -> bool {
+        self.split_every.is_some() || self.split_by_session
+    }
+
     /// Get page dimensions in mm (width, height) accounting for orientation
     pub fn page_dimensions(&self) -> (f32, f32) {
         let (w, h) = self.page_size.dimensions_mm();
@@ -223,91 +937,458 @@ impl Args {
     }
 }
 
+/// Parse a --suit-colors value, in the same format as %PipColors
+pub fn parse_suit_colors(spec: &str) -> Result<ColorSettings, String> {
+    parse_pip_colors(spec).ok_or_else(|| {
+        format!(
+            "Invalid suit colors: '{}' (expected \"#rrggbb,#rrggbb,#rrggbb,#rrggbb\")",
+            spec
+        )
+    })
+}
+
+/// Boards selected by `--boards`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardRange {
+    include: Vec<RangePart>,
+    exclude: Vec<RangePart>,
+}
+
+/// One comma-separated part of a board range
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RangePart {
+    /// Boards `start` to `end` inclusive (`None` for an open range like "17-")
+    Numbers(u32, Option<u32>),
+    /// A board whose [Board] tag isn't a plain number, e.g. "Ex 3"
+    Id(String),
+}
+
+impl RangePart {
+    fn matches(&self, board: &Board) -> bool {
+        match self {
+            RangePart::Numbers(start, end) => board
+                .number
+                .is_some_and(|n| n >= *start && end.map_or(true, |end| n <= end)),
+            RangePart::Id(id) => board.board_id.as_deref() == Some(id.as_str()),
+        }
+    }
+}
+
+impl BoardRange {
+    /// Whether a board is selected: it matches an included part (or there are
+    /// only exclusions) and no excluded part
+    pub fn contains(&self, board: &Board) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|part| part.matches(board)))
+            && !self.exclude.iter().any(|part| part.matches(board))
+    }
+}
+
 /// Parse a board range specification
-pub fn parse_board_range(spec: &str) -> Result<Vec<u32>, String> {
-    let mut boards = Vec::new();
+///
+/// Parts are separated by commas: single boards ("7"), ranges ("1-16"), open
+/// ranges ("17-"), board IDs for boards whose [Board] tag isn't a number
+/// ("Ex 3"), and exclusions of any of these ("!13", "!9-12").
+pub fn parse_board_range(spec: &str) -> Result<BoardRange, String> {
+    let mut range = BoardRange::default();
 
     for part in spec.split(',') {
         let part = part.trim();
+        let (part, excluded) = match part.strip_prefix('!') {
+            Some(rest) => (rest.trim(), true),
+            None => (part, false),
+        };
+        if part.is_empty() {
+            return Err(format!("Empty board in range: {}", spec));
+        }
 
-        if part.contains('-') {
-            // Range: "1-16"
-            let parts: Vec<&str> = part.split('-').collect();
-            if parts.len() != 2 {
-                return Err(format!("Invalid range: {}", part));
+        let parsed = match part.split_once('-') {
+            // Range: "1-16" or "17-"
+            Some((start, end)) if is_board_number(start.trim()) => {
+                let start: u32 = start
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid number: {}", start))?;
+                let end = end.trim();
+                let end = if end.is_empty() {
+                    None
+                } else if is_board_number(end) {
+                    let end: u32 = end
+                        .parse()
+                        .map_err(|_| format!("Invalid number: {}", end))?;
+                    if start > end {
+                        return Err(format!("Invalid range: {} > {}", start, end));
+                    }
+                    Some(end)
+                } else {
+                    return Err(format!("Invalid range: {}", part));
+                };
+                RangePart::Numbers(start, end)
             }
-
-            let start: u32 = parts[0]
-                .trim()
-                .parse()
-                .map_err(|_| format!("Invalid number: {}", parts[0]))?;
-            let end: u32 = parts[1]
-                .trim()
-                .parse()
-                .map_err(|_| format!("Invalid number: {}", parts[1]))?;
-
-            if start > end {
-                return Err(format!("Invalid range: {} > {}", start, end));
+            // Single number
+            _ if is_board_number(part) => {
+                let num: u32 = part
+                    .parse()
+                    .map_err(|_| format!("Invalid number: {}", part))?;
+                RangePart::Numbers(num, Some(num))
             }
+            // Anything else names a board by its [Board] tag
+            _ => RangePart::Id(part.to_string()),
+        };
 
-            for i in start..=end {
-                boards.push(i);
-            }
+        if excluded {
+            range.exclude.push(parsed);
         } else {
-            // Single number
-            let num: u32 = part
-                .parse()
-                .map_err(|_| format!("Invalid number: {}", part))?;
-            boards.push(num);
+            range.include.push(parsed);
         }
     }
 
-    Ok(boards)
+    Ok(range)
+}
+
+fn is_board_number(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Boards 1-40 selected by a range
+    fn selected(range: &BoardRange) -> Vec<u32> {
+        (1..=40)
+            .filter(|&n| range.contains(&Board::new().with_number(n)))
+            .collect()
+    }
+
+    fn named(id: &str) -> Board {
+        let mut board = Board::new();
+        board.board_id = Some(id.to_string());
+        board
+    }
+
     #[test]
     fn test_parse_single_board() {
         let result = parse_board_range("5").unwrap();
-        assert_eq!(result, vec![5]);
+        assert_eq!(selected(&result), vec![5]);
     }
 
     #[test]
     fn test_parse_range() {
         let result = parse_board_range("1-4").unwrap();
-        assert_eq!(result, vec![1, 2, 3, 4]);
+        assert_eq!(selected(&result), vec![1, 2, 3, 4]);
     }
 
     #[test]
     fn test_parse_mixed() {
         let result = parse_board_range("1-3, 7, 10-12").unwrap();
-        assert_eq!(result, vec![1, 2, 3, 7, 10, 11, 12]);
+        assert_eq!(selected(&result), vec![1, 2, 3, 7, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_parse_exclusions_and_open_ranges() {
+        let result = parse_board_range("1-32,!13").unwrap();
+        assert_eq!(selected(&result).len(), 31);
+        assert!(!selected(&result).contains(&13));
+
+        let result = parse_board_range("37-").unwrap();
+        assert_eq!(selected(&result), vec![37, 38, 39, 40]);
+
+        let result = parse_board_range("!2-39").unwrap();
+        assert_eq!(selected(&result), vec![1, 40]);
+
+        assert!(parse_board_range("4-2").is_err());
+        assert!(parse_board_range("1,,2").is_err());
+        assert!(parse_board_range("3-x").is_err());
+    }
+
+    #[test]
+    fn test_parse_board_ids() {
+        let result = parse_board_range("Ex 1, 2").unwrap();
+        assert!(result.contains(&named("Ex 1")));
+        assert!(!result.contains(&named("Ex 2")));
+        assert_eq!(selected(&result), vec![2]);
+
+        let result = parse_board_range("!Ex 1").unwrap();
+        assert!(!result.contains(&named("Ex 1")));
+        assert!(result.contains(&named("Ex 2")));
+    }
+
+    #[test]
+    fn test_parse_page_size() {
+        assert_eq!(parse_page_size("A5"), Ok(PageSize::A5));
+        assert_eq!(
+            parse_page_size("170x240mm"),
+            Ok(PageSize::Custom(170.0, 240.0))
+        );
+        assert_eq!(
+            parse_page_size("100.5x200"),
+            Ok(PageSize::Custom(100.5, 200.0))
+        );
+        assert!(parse_page_size("tabloid").is_err());
+        assert!(parse_page_size("0x200mm").is_err());
+    }
+
+    #[test]
+    fn test_parse_seat() {
+        assert_eq!(parse_seat("S"), Ok(Direction::South));
+        assert_eq!(parse_seat("west"), Ok(Direction::West));
+        assert!(parse_seat("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_margins() {
+        let wide = parse_margins("wide").unwrap();
+        assert_eq!((wide.top, wide.left), (25.4, 25.4));
+
+        let custom = parse_margins("10, 12, 14, 16").unwrap();
+        assert_eq!(
+            (custom.top, custom.right, custom.bottom, custom.left),
+            (10.0, 12.0, 14.0, 16.0)
+        );
+        assert!(parse_margins("10,12").is_err());
+        assert!(parse_margins("10,12,-1,4").is_err());
+    }
+
+    #[test]
+    fn test_parse_suit_colors() {
+        let colors = parse_suit_colors("#000000,#cc0000,#ff8000,#008000").unwrap();
+        assert_eq!(colors.diamonds, (255, 128, 0));
+        assert_eq!(colors.clubs, (0, 128, 0));
+        assert!(parse_suit_colors("#000000,#cc0000").is_err());
+    }
+
+    #[test]
+    fn test_stdin_input_defaults_to_stdout() {
+        let args = Args::try_parse_from(["pbn-to-pdf", "-"]).unwrap();
+        assert!(args.reads_stdin());
+        assert!(is_stdio(&args.output_path(&args.inputs[0])));
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "-", "-o", "out.pdf"]).unwrap();
+        assert_eq!(args.output_path(&args.inputs[0]), PathBuf::from("out.pdf"));
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "hands.pbn", "-o", "-"]).unwrap();
+        assert!(!args.reads_stdin());
+        assert!(is_stdio(&args.output_path(&args.inputs[0])));
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let args = Args::try_parse_from(["pbn-to-pdf", "validate", "a.pbn", "b.pbn"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Validate {
+                files: vec![PathBuf::from("a.pbn"), PathBuf::from("b.pbn")]
+            })
+        );
+        assert!(Args::try_parse_from(["pbn-to-pdf", "validate"]).is_err());
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "list", "-"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::List {
+                files: vec![PathBuf::from("-")]
+            })
+        );
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "stats", "sets/", "--json"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Stats {
+                files: vec![PathBuf::from("sets/")],
+                json: true
+            })
+        );
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "score", "results.csv", "--method", "imps"])
+            .unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Score {
+                method: ScoringMethod::Imps,
+                csv: false,
+                ..
+            })
+        ));
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "hands.pbn"]).unwrap();
+        assert_eq!(args.command, None);
+    }
+
+    #[test]
+    fn test_expand_directory_and_glob_inputs() {
+        let fixtures = PathBuf::from("tests/fixtures");
+        let from_dir = expand_inputs(&[fixtures.clone()]).unwrap();
+        assert!(from_dir.contains(&fixtures.join("Stayman.pbn")));
+        assert!(from_dir.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let from_glob = expand_inputs(&[fixtures.join("Stay*.pbn")]).unwrap();
+        assert_eq!(from_glob, vec![fixtures.join("Stayman.pbn")]);
+
+        assert!(expand_inputs(&[fixtures.join("*.nothing")]).is_err());
+        assert_eq!(
+            expand_inputs(&[PathBuf::from("-")]).unwrap(),
+            vec![PathBuf::from("-")]
+        );
+    }
+
+    #[test]
+    fn test_batch_outputs_go_to_out_dir() {
+        let args = Args::try_parse_from([
+            "pbn-to-pdf",
+            "lessons/a.pbn",
+            "lessons/b.pbn",
+            "--layout",
+            "bidding-sheets",
+            "--out-dir",
+            "pdfs",
+        ])
+        .unwrap();
+        assert!(args.is_batch());
+        assert_eq!(
+            args.output_path(&args.inputs[1]),
+            PathBuf::from("pdfs/b - Bidding Sheets.pdf")
+        );
+
+        let args = Args::try_parse_from(["pbn-to-pdf", "lessons/a.pbn"]).unwrap();
+        assert!(!args.is_batch());
+        assert_eq!(
+            args.output_path(&args.inputs[0]),
+            PathBuf::from("lessons/a.pdf")
+        );
+    }
+
+    #[test]
+    fn test_command_line_overrides_profile_options() {
+        // Profile options are placed before the user's command line
+        let args = Args::try_parse_from([
+            "pbn-to-pdf",
+            "--layout=bidding-sheets",
+            "--boards-per-page=2",
+            "input.pbn",
+            "--layout",
+            "analysis",
+        ])
+        .unwrap();
+        assert_eq!(args.layout, LayoutSelection::One(Layout::Analysis));
+        assert_eq!(args.boards_per_page, 2);
+    }
+
+    #[test]
+    fn test_font_embedding() {
+        let args = Args::try_parse_from(["pbn-to-pdf", "input.pbn"]).unwrap();
+        assert_eq!(args.font_embedding, FontEmbedding::Subset);
+        let args = Args::try_parse_from(["pbn-to-pdf", "--font-embedding", "builtin", "input.pbn"])
+            .unwrap();
+        assert_eq!(args.font_embedding, FontEmbedding::Builtin);
+    }
+
+    #[test]
+    fn test_compress() {
+        let args = Args::try_parse_from(["pbn-to-pdf", "input.pbn"]).unwrap();
+        assert_eq!(args.compress, CompressionLevel::Best);
+        let args = Args::try_parse_from(["pbn-to-pdf", "--compress", "none", "input.pbn"]).unwrap();
+        assert_eq!(args.compress, CompressionLevel::None);
+    }
+
+    #[test]
+    fn test_layout_all() {
+        let args = Args::try_parse_from(["pbn-to-pdf", "--layout", "all", "hands.pbn"]).unwrap();
+        assert_eq!(args.layout, LayoutSelection::All);
+        assert_eq!(args.layout.layouts().len(), Layout::ALL.len());
+        assert_eq!(args.layout.primary(), Layout::Analysis);
+        assert_eq!(
+            layout_output_path(Path::new("out/hands.pdf"), Layout::BiddingSheets),
+            PathBuf::from("out/hands - Bidding Sheets.pdf")
+        );
+        assert_eq!(
+            layout_output_path(Path::new("out/hands.pdf"), Layout::Analysis),
+            PathBuf::from("out/hands.pdf")
+        );
     }
 
     #[test]
     fn test_page_dimensions() {
         let args = Args {
-            input: PathBuf::from("test.pbn"),
+            command: None,
+            inputs: vec![PathBuf::from("test.pbn")],
             output: None,
+            out_dir: None,
+            jobs: None,
             boards_per_page: 1,
             page_size: PageSize::Letter,
             orientation: Orientation::Portrait,
-            layout: Layout::Analysis,
+            duplex: None,
+            layout: LayoutSelection::default(),
             no_bidding: false,
             no_play: false,
             no_commentary: false,
             no_hcp: false,
+            hand_hcp: false,
+            distribution_points: false,
+            board_in_compass: false,
+            vulnerable_compass: false,
+            lead_marker: false,
+            rotate_east_west: false,
+            dd_tricks: false,
+            best_leads: false,
+            auction_prose: false,
             boards: None,
+            lenient: false,
+            filter: None,
+            sort_by: None,
+            reverse: false,
+            split_every: None,
+            split_by_session: false,
+            split_name: DEFAULT_SPLIT_NAME.to_string(),
+            chunk_size: None,
+            watch: false,
+            timings: false,
+            conventions: None,
             margins: None,
             debug_boxes: false,
             circle_sure_winners: false,
             circle_promotable_winners: false,
             circle_length_winners: false,
+            fillable: false,
+            answers_layer: false,
+            suit_colors: None,
+            four_color: false,
+            theme: ColorTheme::Classic,
+            grayscale: false,
+            hidden_hands: HiddenHandStyle::Omit,
+            suit_style: SuitStyle::Symbols,
+            compress: CompressionLevel::Best,
+            font_embedding: FontEmbedding::Subset,
+            auction_headers: AuctionHeaders::Directions,
+            auction_max_rows: None,
+            pass_text: None,
+            double_text: None,
+            redouble_text: None,
+            all_pass_text: None,
+            passed_out_text: None,
+            hyphenate: false,
+            ten_as_t: false,
+            link_card_refs: false,
+            qr_codes: false,
+            header: None,
+            footer: None,
+            page_numbers: false,
+            font_diagram: None,
+            font_commentary: None,
+            font_hand_record: None,
+            language: Language::English,
             title: None,
+            subtitle: None,
+            template: None,
+            logo: None,
+            background: None,
+            image_dpi: None,
+            copyright: None,
+            title_block: TitleBlock::None,
+            profile: None,
+            config: None,
             verbose: 0,
+            diagnostics: DiagnosticsFormat::Text,
         };
 
         let (w, h) = args.page_dimensions();
@@ -0,0 +1,128 @@
+//! Double-dummy annotation of PBN files (`dd` subcommand)
+//!
+//! The PBN text is rewritten rather than re-serialized, so everything but the
+//! `DoubleDummyTricks` and `OptimumScore` tags of solved boards is kept as is.
+
+use crate::model::scoring::par_score;
+use crate::model::solver::solve_deal;
+use crate::model::{Board, Direction, DoubleDummyTricks};
+use crate::parser::tags::parse_tag_pair;
+
+/// Double-dummy tricks and par score of one board
+#[derive(Debug, Clone)]
+pub struct DoubleDummyResult {
+    pub tricks: DoubleDummyTricks,
+    /// Par score for North-South (negative when East-West score)
+    pub par: i32,
+}
+
+impl DoubleDummyResult {
+    /// Solve a board, or None if its deal is incomplete
+    pub fn solve(board: &Board) -> Option<Self> {
+        let tricks = solve_deal(&board.deal)?;
+        // Without a [Dealer] tag, North is assumed to deal
+        let dealer = board.dealer.unwrap_or(Direction::North);
+        let par = par_score(&tricks, board.vulnerable, dealer);
+        Some(Self { tricks, par })
+    }
+}
+
+/// Write the results into PBN content, one per game in the order `parse_pbn`
+/// returns boards. The tags go right after each solved game's `[Deal]` tag,
+/// replacing any it had; games without a result are left untouched.
+pub fn annotate_pbn(content: &str, results: &[Option<DoubleDummyResult>]) -> String {
+    let mut output = String::with_capacity(content.len());
+    // Index of the current game, found the same way as the parser does
+    let mut game: Option<usize> = None;
+    let mut in_commentary = false;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if in_commentary || trimmed.starts_with('{') {
+            in_commentary = !line.contains('}');
+            output.push_str(line);
+            continue;
+        }
+        let Ok((_, tag)) = parse_tag_pair(trimmed) else {
+            output.push_str(line);
+            continue;
+        };
+
+        match tag.name.as_str() {
+            "Event" => game = Some(game.map_or(0, |g| g + 1)),
+            "Board" if game.is_none() => game = Some(0),
+            _ => {}
+        }
+        let result = game
+            .and_then(|g| results.get(g))
+            .and_then(|result| result.as_ref());
+
+        match (tag.name.as_str(), result) {
+            ("DoubleDummyTricks" | "OptimumScore", Some(_)) => {}
+            ("Deal", Some(result)) => {
+                output.push_str(line);
+                let newline = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+                if !line.ends_with('\n') {
+                    output.push_str(newline);
+                }
+                output.push_str(&format!(
+                    "[DoubleDummyTricks \"{}\"]{newline}",
+                    result.tricks.to_pbn()
+                ));
+                output.push_str(&format!("[OptimumScore \"NS {}\"]{newline}", result.par));
+            }
+            _ => output.push_str(line),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_pbn;
+
+    const PBN: &str = "[Event \"Club\"]\r\n\
+        [Board \"1\"]\r\n\
+        [Dealer \"N\"]\r\n\
+        [Vulnerable \"None\"]\r\n\
+        [Deal \"N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432\"]\r\n\
+        [OptimumScore \"NS 0\"]\r\n\
+        {[Deal \"N:- - - -\"] in commentary}\r\n\
+        [Event \"Club\"]\r\n\
+        [Board \"2\"]\r\n\
+        [Deal \"N:- - - -\"]\r\n\
+        [DoubleDummyTricks \"00000000000000000000\"]\r\n";
+
+    #[test]
+    fn test_annotate_pbn() {
+        let boards = parse_pbn(PBN).unwrap().boards;
+        let results: Vec<_> = boards.iter().map(DoubleDummyResult::solve).collect();
+        assert!(results[0].is_some());
+        assert!(results[1].is_none());
+
+        let annotated = annotate_pbn(PBN, &results);
+        let expected = PBN.replace(
+            "[OptimumScore \"NS 0\"]\r\n",
+            "[DoubleDummyTricks \"0d0d00d0d000d0d00d0d\"]\r\n[OptimumScore \"NS 1510\"]\r\n",
+        );
+        assert_eq!(annotated, expected);
+
+        let board = &parse_pbn(&annotated).unwrap().boards[0];
+        assert_eq!(
+            board.double_dummy.as_ref().unwrap().to_pbn(),
+            "0d0d00d0d000d0d00d0d"
+        );
+    }
+
+    #[test]
+    fn test_annotate_pbn_without_line_ending() {
+        let pbn = "[Event \"\"]\n[Deal \"N:AQ... K4... 32... 65...\"]";
+        let boards = parse_pbn(pbn).unwrap().boards;
+        let results: Vec<_> = boards.iter().map(DoubleDummyResult::solve).collect();
+        let annotated = annotate_pbn(pbn, &results);
+        assert!(annotated.ends_with(
+            "...\"]\n[DoubleDummyTricks \"22222111111111111111\"]\n[OptimumScore \"NS 0\"]\n"
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! Board-by-board comparison of two PBN files (`pbn-to-pdf diff`)
+//!
+//! Boards are matched by their `[Board]` number, so a reordered file only
+//! reports the boards that changed: deal, dealer, vulnerability, auction,
+//! contract and commentary.
+
+use std::fmt;
+
+use crate::model::{Auction, Board, CallExt, Direction, DirectionExt};
+
+/// Longest commentary excerpt shown for a changed block
+const EXCERPT_CHARS: usize = 60;
+
+/// How one board differs between the two files
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardDiff {
+    /// The board is only in the first file
+    Removed(String),
+    /// The board is only in the second file
+    Added(String),
+    /// The board is in both files; one line per difference
+    Changed(String, Vec<String>),
+}
+
+impl fmt::Display for BoardDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardDiff::Removed(board) => writeln!(f, "Board {}: only in the first file", board),
+            BoardDiff::Added(board) => writeln!(f, "Board {}: only in the second file", board),
+            BoardDiff::Changed(board, changes) => {
+                writeln!(f, "Board {}:", board)?;
+                for change in changes {
+                    writeln!(f, "  {}", change)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The board's identifier, or its position in the file if it has none
+fn board_key(board: &Board, index: usize) -> String {
+    board
+        .board_id
+        .clone()
+        .or_else(|| board.number.map(|n| n.to_string()))
+        .unwrap_or_else(|| format!("#{}", index + 1))
+}
+
+/// The calls of an auction, followed by its notes in number order
+fn auction_text(auction: &Auction) -> String {
+    let mut words: Vec<String> = auction
+        .calls
+        .iter()
+        .enumerate()
+        .map(|(index, annotated)| {
+            let alert = if auction.is_alerted(index) { "!" } else { "" };
+            let call = format!("{}{}", annotated.call.pbn_text(), alert);
+            match &annotated.annotation {
+                Some(note) => format!("{} ={}=", call, note),
+                None => call,
+            }
+        })
+        .collect();
+    let mut notes: Vec<_> = auction.notes.iter().collect();
+    notes.sort();
+    for (number, text) in notes {
+        words.push(format!("[{}: {}]", number, text));
+    }
+    words.join(" ")
+}
+
+/// The start of a commentary block's text, on one line
+fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > EXCERPT_CHARS {
+        let start: String = text.chars().take(EXCERPT_CHARS).collect();
+        format!("\"{}...\"", start)
+    } else {
+        format!("\"{}\"", text)
+    }
+}
+
+/// Record "name: old -> new" when the two values differ
+fn compare(changes: &mut Vec<String>, name: &str, old: String, new: String) {
+    if old != new {
+        changes.push(format!("{}: {} -> {}", name, old, new));
+    }
+}
+
+/// Differences between two versions of a board, one line each
+fn board_changes(old: &Board, new: &Board) -> Vec<String> {
+    let mut changes = Vec::new();
+    let optional = |text: Option<String>| text.unwrap_or_else(|| "-".to_string());
+
+    compare(
+        &mut changes,
+        "dealer",
+        optional(old.dealer.map(|d| d.pbn_text().to_string())),
+        optional(new.dealer.map(|d| d.pbn_text().to_string())),
+    );
+    compare(
+        &mut changes,
+        "vulnerable",
+        old.vulnerable.to_string(),
+        new.vulnerable.to_string(),
+    );
+    for seat in [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ] {
+        compare(
+            &mut changes,
+            &format!("deal {}", seat.pbn_text()),
+            old.deal.hand(seat).to_string(),
+            new.deal.hand(seat).to_string(),
+        );
+    }
+    compare(
+        &mut changes,
+        "auction",
+        optional(old.auction.as_ref().map(auction_text)),
+        optional(new.auction.as_ref().map(auction_text)),
+    );
+    let contract = |board: &Board| {
+        board
+            .contract
+            .as_ref()
+            .map(|contract| match board.declarer {
+                Some(declarer) => {
+                    format!("{} {}", contract.to_pbn(), declarer.pbn_text())
+                }
+                None => contract.to_pbn(),
+            })
+    };
+    compare(
+        &mut changes,
+        "contract",
+        optional(contract(old)),
+        optional(contract(new)),
+    );
+
+    let old_text: Vec<String> = old
+        .commentary
+        .iter()
+        .map(|block| block.content.to_plain_text())
+        .collect();
+    let new_text: Vec<String> = new
+        .commentary
+        .iter()
+        .map(|block| block.content.to_plain_text())
+        .collect();
+    for index in 0..old_text.len().max(new_text.len()) {
+        let excerpt_at = |texts: &[String]| {
+            texts
+                .get(index)
+                .map(|text| excerpt(text))
+                .unwrap_or_else(|| "(none)".to_string())
+        };
+        if old_text.get(index) != new_text.get(index) {
+            changes.push(format!(
+                "commentary {}: {} -> {}",
+                index + 1,
+                excerpt_at(&old_text),
+                excerpt_at(&new_text)
+            ));
+        }
+    }
+    changes
+}
+
+/// Compare the boards of two files; boards with the same number are compared
+/// with each other, and unchanged boards are left out
+pub fn diff_boards(old: &[Board], new: &[Board]) -> Vec<BoardDiff> {
+    let mut unmatched: Vec<Option<(String, &Board)>> = new
+        .iter()
+        .enumerate()
+        .map(|(index, board)| Some((board_key(board, index), board)))
+        .collect();
+
+    let mut diffs = Vec::new();
+    for (index, old_board) in old.iter().enumerate() {
+        let key = board_key(old_board, index);
+        let found = unmatched
+            .iter_mut()
+            .find(|entry| entry.as_ref().is_some_and(|(k, _)| *k == key))
+            .and_then(Option::take);
+        match found {
+            Some((_, new_board)) => {
+                let changes = board_changes(old_board, new_board);
+                if !changes.is_empty() {
+                    diffs.push(BoardDiff::Changed(key, changes));
+                }
+            }
+            None => diffs.push(BoardDiff::Removed(key)),
+        }
+    }
+    diffs.extend(
+        unmatched
+            .into_iter()
+            .flatten()
+            .map(|(key, _)| BoardDiff::Added(key)),
+    );
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_pbn;
+
+    fn boards(pbn: &str) -> Vec<Board> {
+        parse_pbn(pbn).unwrap().boards
+    }
+
+    #[test]
+    fn test_identical_files() {
+        let pbn =
+            "[Event \"\"]\n[Board \"1\"]\n[Deal \"N:AKQ.JT9.876.5432 - - -\"]\n{Lead a spade.}\n";
+        assert!(diff_boards(&boards(pbn), &boards(pbn)).is_empty());
+    }
+
+    #[test]
+    fn test_changed_boards() {
+        let old = boards(
+            "[Event \"\"]\n[Board \"1\"]\n[Dealer \"N\"]\n[Deal \"N:AKQ.JT9.876.5432 - - -\"]\n\
+             [Auction \"N\"]\n1NT Pass 3NT AP\n{Lead a spade.}\n\
+             [Event \"\"]\n[Board \"2\"]\n",
+        );
+        let new = boards(
+            "[Event \"\"]\n[Board \"3\"]\n\
+             [Event \"\"]\n[Board \"1\"]\n[Dealer \"N\"]\n[Deal \"N:AKJ.JT9.876.5432 - - -\"]\n\
+             [Auction \"N\"]\n1NT Pass 3NT AP\n{Lead a heart.}\n{Count your winners.}\n",
+        );
+
+        let diffs = diff_boards(&old, &new);
+        assert_eq!(
+            diffs,
+            vec![
+                BoardDiff::Changed(
+                    "1".to_string(),
+                    vec![
+                        format!("deal N: {} -> {}", old[0].deal.north, new[1].deal.north),
+                        "commentary 1: \"Lead a spade.\" -> \"Lead a heart.\"".to_string(),
+                        "commentary 2: (none) -> \"Count your winners.\"".to_string(),
+                    ]
+                ),
+                BoardDiff::Removed("2".to_string()),
+                BoardDiff::Added("3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auction_text() {
+        let board = &boards(
+            "[Event \"\"]\n[Board \"1\"]\n[Auction \"N\"]\n1NT =1= X! AP\n[Note \"1:15-17\"]\n",
+        )[0];
+        assert_eq!(
+            auction_text(board.auction.as_ref().unwrap()),
+            "1NT =1= X! Pass Pass Pass [1: 15-17]"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Board extraction into a new PBN file (`pbn-to-pdf extract`)
+//!
+//! Selects boards with the same `--boards` ranges and `--filter` expressions
+//! as conversion, and copies their games (see [`PbnRecords`]) unchanged.
+
+use super::args::BoardRange;
+use super::filter::BoardFilter;
+use crate::model::Board;
+use crate::parser::PbnRecords;
+
+/// The file's header and the games whose boards are in the range and match
+/// the filter. `boards` are the file's parsed boards, one per game.
+pub fn extract_records(
+    records: &PbnRecords,
+    boards: &[Board],
+    range: Option<&BoardRange>,
+    filter: Option<&BoardFilter>,
+) -> PbnRecords {
+    let games = records
+        .games
+        .iter()
+        .zip(boards)
+        .filter(|(_, board)| range.map_or(true, |range| range.contains(board)))
+        .filter(|(_, board)| filter.map_or(true, |filter| filter.matches(board)))
+        .map(|(game, _)| game.clone())
+        .collect();
+    PbnRecords {
+        header: records.header.clone(),
+        games,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::parse_board_range;
+    use crate::parser::parse_pbn;
+
+    #[test]
+    fn test_extract_records() {
+        let pbn: String = (1..=10)
+            .map(|n| {
+                let dealer = ["N", "E", "S", "W"][(n - 1) % 4];
+                format!("[Event \"\"]\n[Board \"{}\"]\n[Dealer \"{}\"]\n", n, dealer)
+            })
+            .collect();
+        let pbn = format!("%HRTitleEvent Lesson\n{}", pbn);
+        let records = PbnRecords::parse(&pbn);
+        let boards = parse_pbn(&pbn).unwrap().boards;
+
+        let range = parse_board_range("5-8").unwrap();
+        let subset = extract_records(&records, &boards, Some(&range), None);
+        assert_eq!(subset.header, ["%HRTitleEvent Lesson"]);
+        let numbers: Vec<_> = subset.games.iter().filter_map(|g| g.tag("Board")).collect();
+        assert_eq!(numbers, ["5", "6", "7", "8"]);
+
+        let filter = BoardFilter::parse("dealer=N").unwrap();
+        let subset = extract_records(&records, &boards, Some(&range), Some(&filter));
+        let numbers: Vec<_> = subset.games.iter().filter_map(|g| g.tag("Board")).collect();
+        assert_eq!(numbers, ["5"]);
+    }
+}
@@ -0,0 +1,502 @@
+//! Board filter expressions (`--filter`)
+//!
+//! A filter is a condition evaluated against each board; only boards that
+//! match are rendered:
+//!
+//! ```text
+//! hcp(N)+hcp(S)>=25 && contract=NT
+//! length(S,H)>=5 || dealer=N
+//! !(vul=None) && board<=16
+//! ```
+//!
+//! Values:
+//! - `board` - board number
+//! - `hcp(SEATS)` - high card points of one or more seats, e.g. `hcp(NS)`
+//! - `length(SEAT,SUIT)` - number of cards a seat holds in a suit
+//! - `level` - contract level
+//! - `contract` - the contract, e.g. `3NT` or `4SX`; compared with a strain
+//!   (`NT`, `S`, `H`, `D`, `C`) it matches every contract in that strain
+//! - `declarer`, `dealer` - a seat (`N`, `E`, `S`, `W`)
+//! - `vul` - `None`, `NS`, `EW` or `All` (as PBN writes it; `Both` also works)
+//!
+//! Numbers combine with `+` and `-` and compare with `=`, `!=`, `<`, `<=`,
+//! `>`, `>=`; other values compare with `=` and `!=`. Conditions combine with
+//! `&&`, `||`, `!` and parentheses. A condition on a value the board doesn't
+//! have (such as `level` for a board without a contract) is false.
+
+use crate::error::ConfigError;
+use crate::model::{Board, Direction, DirectionExt, StrainExt, Suit, Vulnerability};
+
+/// A parsed `--filter` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardFilter {
+    expr: Expr,
+}
+
+impl BoardFilter {
+    /// Parse a filter expression
+    pub fn parse(spec: &str) -> Result<Self, ConfigError> {
+        let tokens = tokenize(spec)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or_expr()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(invalid(format!("unexpected {:?}", token)));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Whether a board satisfies the filter
+    pub fn matches(&self, board: &Board) -> bool {
+        self.expr.eval(board)
+    }
+}
+
+fn invalid(message: String) -> ConfigError {
+    ConfigError::InvalidFilter(message)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(i32),
+    Word(String),
+    LParen,
+    RParen,
+    Comma,
+    Plus,
+    Minus,
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn tokenize(spec: &str) -> Result<Vec<Token>, ConfigError> {
+    let mut tokens = Vec::new();
+    let mut chars = spec.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut followed_by = |next: char| chars.next_if_eq(&next).is_some();
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '=' => {
+                followed_by('=');
+                Token::Cmp(CmpOp::Eq)
+            }
+            '!' if followed_by('=') => Token::Cmp(CmpOp::Ne),
+            '!' => Token::Not,
+            '<' if followed_by('=') => Token::Cmp(CmpOp::Le),
+            '<' => Token::Cmp(CmpOp::Lt),
+            '>' if followed_by('=') => Token::Cmp(CmpOp::Ge),
+            '>' => Token::Cmp(CmpOp::Gt),
+            '&' if followed_by('&') => Token::And,
+            '|' if followed_by('|') => Token::Or,
+            c if c.is_ascii_alphanumeric() => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(char::is_ascii_alphanumeric) {
+                    word.push(c);
+                }
+                match word.parse() {
+                    Ok(n) => Token::Num(n),
+                    Err(_) => Token::Word(word),
+                }
+            }
+            c => return Err(invalid(format!("unexpected character '{}'", c))),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Sum, CmpOp, Sum),
+}
+
+/// Terms added or subtracted; `true` marks a subtracted term
+#[derive(Debug, Clone, PartialEq)]
+struct Sum(Vec<(bool, Term)>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Num(i32),
+    Text(String),
+    Board,
+    Hcp(Vec<Direction>),
+    Length(Direction, Suit),
+    Level,
+    Contract,
+    Declarer,
+    Dealer,
+    Vul,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Num(i32),
+    Text(String),
+    /// Contract as written ("4SX") and its strain ("S")
+    Contract(String, String),
+    Vul(Vulnerability),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ConfigError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(invalid(format!("expected {:?}", token)))
+        }
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, ConfigError> {
+        let mut expr = self.and_expr()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and_expr()?));
+        }
+        Ok(expr)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, ConfigError> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ConfigError> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        // Parentheses group conditions; numbers are only ever added up
+        if self.eat(&Token::LParen) {
+            let expr = self.or_expr()?;
+            self.expect(Token::RParen)?;
+            return Ok(expr);
+        }
+        let left = self.sum()?;
+        let op = match self.next() {
+            Some(Token::Cmp(op)) => op,
+            _ => return Err(invalid("expected a comparison".to_string())),
+        };
+        let right = self.sum()?;
+        Ok(Expr::Compare(left, op, right))
+    }
+
+    fn sum(&mut self) -> Result<Sum, ConfigError> {
+        let mut terms = vec![(self.eat(&Token::Minus), self.term()?)];
+        loop {
+            if self.eat(&Token::Plus) {
+                terms.push((false, self.term()?));
+            } else if self.eat(&Token::Minus) {
+                terms.push((true, self.term()?));
+            } else {
+                return Ok(Sum(terms));
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, ConfigError> {
+        let word = match self.next() {
+            Some(Token::Num(n)) => return Ok(Term::Num(n)),
+            Some(Token::Word(word)) => word,
+            other => return Err(invalid(format!("expected a value, found {:?}", other))),
+        };
+
+        if self.eat(&Token::LParen) {
+            let mut args = vec![self.word_arg()?];
+            while self.eat(&Token::Comma) {
+                args.push(self.word_arg()?);
+            }
+            self.expect(Token::RParen)?;
+            return function(&word, &args);
+        }
+
+        Ok(match word.to_ascii_lowercase().as_str() {
+            "board" => Term::Board,
+            "level" => Term::Level,
+            "contract" => Term::Contract,
+            "declarer" => Term::Declarer,
+            "dealer" => Term::Dealer,
+            "vul" => Term::Vul,
+            _ => Term::Text(word.to_ascii_uppercase()),
+        })
+    }
+
+    fn word_arg(&mut self) -> Result<String, ConfigError> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word.to_ascii_uppercase()),
+            other => Err(invalid(format!(
+                "expected a seat or suit, found {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Build a function term such as `hcp(NS)` or `length(S,H)`
+fn function(name: &str, args: &[String]) -> Result<Term, ConfigError> {
+    let seats = |arg: &str| {
+        arg.chars()
+            .map(|c| {
+                Direction::from_char(c).ok_or_else(|| invalid(format!("unknown seat '{}'", c)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    match (name.to_ascii_lowercase().as_str(), args) {
+        ("hcp", args) => {
+            let mut directions = Vec::new();
+            for arg in args {
+                directions.extend(seats(arg)?);
+            }
+            Ok(Term::Hcp(directions))
+        }
+        ("length" | "len", [seat, suit]) => {
+            let seat = match seats(seat)?[..] {
+                [seat] => seat,
+                _ => return Err(invalid(format!("length needs one seat, not {}", seat))),
+            };
+            let suit = suit
+                .chars()
+                .next()
+                .filter(|_| suit.len() == 1)
+                .and_then(Suit::from_char)
+                .ok_or_else(|| invalid(format!("unknown suit '{}'", suit)))?;
+            Ok(Term::Length(seat, suit))
+        }
+        (name, _) => Err(invalid(format!("unknown function {}()", name))),
+    }
+}
+
+impl Term {
+    fn value(&self, board: &Board) -> Option<Value> {
+        let num = |n: u8| Value::Num(n as i32);
+        Some(match self {
+            Term::Num(n) => Value::Num(*n),
+            Term::Text(text) => Value::Text(text.clone()),
+            Term::Board => Value::Num(board.number? as i32),
+            Term::Hcp(directions) => Value::Num(
+                directions
+                    .iter()
+                    .map(|&d| board.deal.hand(d).total_hcp() as i32)
+                    .sum(),
+            ),
+            Term::Length(direction, suit) => {
+                Value::Num(board.deal.hand(*direction).holding(*suit).len() as i32)
+            }
+            Term::Level => num(board.contract.as_ref()?.level),
+            Term::Contract => {
+                let contract = board.contract.as_ref()?;
+                Value::Contract(contract.to_pbn(), contract.suit.pbn_text().to_string())
+            }
+            Term::Declarer => Value::Text(board.declarer?.pbn_text().to_string()),
+            Term::Dealer => Value::Text(board.dealer?.pbn_text().to_string()),
+            Term::Vul => Value::Vul(board.vulnerable),
+        })
+    }
+}
+
+impl Sum {
+    fn value(&self, board: &Board) -> Option<Value> {
+        if let [(false, term)] = &self.0[..] {
+            return term.value(board);
+        }
+        let mut total = 0;
+        for (negated, term) in &self.0 {
+            match term.value(board)? {
+                Value::Num(n) if *negated => total -= n,
+                Value::Num(n) => total += n,
+                _ => return None,
+            }
+        }
+        Some(Value::Num(total))
+    }
+}
+
+impl Expr {
+    fn eval(&self, board: &Board) -> bool {
+        match self {
+            Expr::Or(a, b) => a.eval(board) || b.eval(board),
+            Expr::And(a, b) => a.eval(board) && b.eval(board),
+            Expr::Not(a) => !a.eval(board),
+            Expr::Compare(left, op, right) => {
+                let (Some(left), Some(right)) = (left.value(board), right.value(board)) else {
+                    return false;
+                };
+                compare(&left, *op, &right)
+            }
+        }
+    }
+}
+
+fn compare(left: &Value, op: CmpOp, right: &Value) -> bool {
+    let equal = match (left, right) {
+        (Value::Num(a), Value::Num(b)) => {
+            return match op {
+                CmpOp::Eq => a == b,
+                CmpOp::Ne => a != b,
+                CmpOp::Lt => a < b,
+                CmpOp::Le => a <= b,
+                CmpOp::Gt => a > b,
+                CmpOp::Ge => a >= b,
+            }
+        }
+        (Value::Contract(full, strain), Value::Text(text))
+        | (Value::Text(text), Value::Contract(full, strain)) => text == full || text == strain,
+        (Value::Vul(vul), Value::Text(text)) | (Value::Text(text), Value::Vul(vul)) => {
+            vulnerability(text) == Some(*vul)
+        }
+        (Value::Text(a), Value::Text(b)) => a == b,
+        (Value::Vul(a), Value::Vul(b)) => a == b,
+        (Value::Contract(a, _), Value::Contract(b, _)) => a == b,
+        _ => false,
+    };
+    match op {
+        CmpOp::Eq => equal,
+        CmpOp::Ne => !equal,
+        _ => false,
+    }
+}
+
+/// A vulnerability named in a filter (upper case): PBN's `All` or `Both`
+fn vulnerability(text: &str) -> Option<Vulnerability> {
+    match text {
+        "NONE" => Some(Vulnerability::None),
+        "NS" => Some(Vulnerability::NorthSouth),
+        "EW" => Some(Vulnerability::EastWest),
+        "ALL" | "BOTH" => Some(Vulnerability::Both),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Contract, Deal, Hand, Holding, Rank};
+
+    fn board() -> Board {
+        let holding = |ranks: &[Rank]| Holding::from_ranks(ranks.iter().copied());
+        let mut deal = Deal::new();
+        // North: AKQ of spades (9 HCP), South: AKJ of hearts (8 HCP)
+        deal.set_hand(
+            Direction::North,
+            Hand::from_holdings(
+                holding(&[Rank::Ace, Rank::King, Rank::Queen]),
+                Holding::new(),
+                Holding::new(),
+                Holding::new(),
+            ),
+        );
+        deal.set_hand(
+            Direction::South,
+            Hand::from_holdings(
+                Holding::new(),
+                holding(&[Rank::Ace, Rank::King, Rank::Jack]),
+                Holding::new(),
+                Holding::new(),
+            ),
+        );
+        let mut board = Board::new()
+            .with_number(7)
+            .with_dealer(Direction::South)
+            .with_deal(deal);
+        board.contract = Contract::parse("3NTX");
+        board.declarer = Some(Direction::North);
+        board
+    }
+
+    fn matches(spec: &str) -> bool {
+        BoardFilter::parse(spec).unwrap().matches(&board())
+    }
+
+    #[test]
+    fn test_numeric_conditions() {
+        assert!(matches("hcp(N)+hcp(S)>=17"));
+        assert!(!matches("hcp(N)+hcp(S)>=25"));
+        assert!(matches("hcp(NS) = 17"));
+        assert!(matches("hcp(N) - hcp(S) == 1"));
+        assert!(matches("length(N,S) = 3 && len(S,S) = 0"));
+        assert!(matches("board <= 16 && level = 3"));
+    }
+
+    #[test]
+    fn test_text_conditions() {
+        assert!(matches("contract=NT"));
+        assert!(matches("contract = 3NTX"));
+        assert!(!matches("contract = S"));
+        assert!(matches("declarer = N && dealer = s"));
+        assert!(matches("vul = none"));
+        assert!(!matches("vul = All"));
+    }
+
+    #[test]
+    fn test_vul_spellings() {
+        let mut board = board();
+        board.vulnerable = Vulnerability::Both;
+        for spec in ["vul = All", "vul = both", "vul != NS"] {
+            assert!(
+                BoardFilter::parse(spec).unwrap().matches(&board),
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn test_logic_and_grouping() {
+        assert!(matches("contract=S || (board=7 && !(dealer=N))"));
+        assert!(!matches("!(contract=NT)"));
+        assert!(matches("contract != H"));
+    }
+
+    #[test]
+    fn test_missing_values_are_false() {
+        let filter = BoardFilter::parse("level >= 1").unwrap();
+        assert!(!filter.matches(&Board::new()));
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        assert!(BoardFilter::parse("hcp(N) >=").is_err());
+        assert!(BoardFilter::parse("hcp(X) > 3").is_err());
+        assert!(BoardFilter::parse("tricks(N) > 3").is_err());
+        assert!(BoardFilter::parse("board = 1 &&").is_err());
+        assert!(BoardFilter::parse("board # 1").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Board listing (`pbn-to-pdf list`)
+//!
+//! One line per board, so a file can be surveyed before choosing `--boards`
+//! or `--filter`: number, dealer, vulnerability, contract, how the high card
+//! points are split, and whether the board has commentary.
+
+use crate::model::{Board, Direction, DirectionExt, Vulnerability};
+
+pub(crate) const HEADER: [&str; 6] = ["Board", "Dealer", "Vul", "Contract", "HCP N-E-S-W", "Notes"];
+
+/// The columns of one board's line
+pub(crate) fn board_row(board: &Board) -> [String; 6] {
+    let number = board
+        .board_id
+        .clone()
+        .or_else(|| board.number.map(|n| n.to_string()))
+        .unwrap_or_else(|| "?".to_string());
+    let dealer = board
+        .dealer
+        .map(|d| d.pbn_text().to_string())
+        .unwrap_or_default();
+    let vul = match board.vulnerable {
+        Vulnerability::None => "None",
+        Vulnerability::NorthSouth => "NS",
+        Vulnerability::EastWest => "EW",
+        Vulnerability::Both => "Both",
+    };
+    let contract = match (&board.contract, board.declarer) {
+        (Some(contract), Some(declarer)) => {
+            format!("{} {}", contract.to_pbn(), declarer.pbn_text())
+        }
+        (Some(contract), None) => contract.to_pbn(),
+        (None, _) => "-".to_string(),
+    };
+    let hcp = if board.deal.is_empty() {
+        "-".to_string()
+    } else {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .iter()
+        .map(|&d| board.deal.hand(d).total_hcp().to_string())
+        .collect::<Vec<_>>()
+        .join("-")
+    };
+    let notes = if board.commentary.is_empty() {
+        "n"
+    } else {
+        "y"
+    };
+
+    [
+        number,
+        dealer,
+        vul.to_string(),
+        contract,
+        hcp,
+        notes.to_string(),
+    ]
+}
+
+/// A table of the boards, one line each, with aligned columns
+pub fn board_table(boards: &[Board]) -> String {
+    let rows: Vec<[String; 6]> = std::iter::once(HEADER.map(str::to_string))
+        .chain(boards.iter().map(board_row))
+        .collect();
+
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{CommentaryBlock, Contract, FormattedText};
+    use crate::parser::deal::parse_deal;
+
+    #[test]
+    fn test_board_table() {
+        let mut first = Board::new().with_number(1);
+        first.dealer = Some(Direction::North);
+        first.deal =
+            parse_deal("N:AKQJ.T98.765.432 T987.AKQ.432.765 65432.J765.AK.A8 .432.QJT98.KQJT9")
+                .unwrap();
+        first.contract = Contract::parse("4SX");
+        first.declarer = Some(Direction::South);
+        first
+            .commentary
+            .push(CommentaryBlock::new(FormattedText::new()));
+
+        let mut second = Board::new().with_number(12);
+        second.dealer = Some(Direction::West);
+        second.vulnerable = Vulnerability::Both;
+
+        assert_eq!(
+            board_table(&[first, second]),
+            "Board  Dealer  Vul   Contract  HCP N-E-S-W  Notes\n\
+             1      N       None  4SX S     10-9-12-9    y\n\
+             12     W       Both  -         -            n\n"
+        );
+    }
+}
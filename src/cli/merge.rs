@@ -0,0 +1,117 @@
+//! Combining PBN files into one (`pbn-to-pdf merge`)
+//!
+//! Works on the text of each game (see [`PbnRecords`]), so the merged file
+//! keeps every tag and comment of its sources.
+
+use crate::parser::{GameRecord, PbnRecords};
+
+/// A game's board number, if it is a plain number
+fn board_number(game: &GameRecord) -> Option<u32> {
+    game.tag("Board")?.trim().parse().ok()
+}
+
+/// The directive a header line sets, e.g. "%HRTitleEvent"; None for comments
+fn directive(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let name = line.split_whitespace().next()?;
+    (name.len() > 1 && name.starts_with('%')).then_some(name)
+}
+
+/// Merge files into one. Header directives are kept from the first file that
+/// sets them; boards identical to one already merged (apart from the number)
+/// are dropped; and when a file's board numbers clash with those already
+/// merged, its boards are renumbered to follow the highest of them.
+pub fn merge_records(files: &[PbnRecords]) -> PbnRecords {
+    let mut merged = PbnRecords::default();
+
+    for records in files {
+        for line in &records.header {
+            let known = merged.header.iter().any(|kept| {
+                kept == line || directive(kept).is_some_and(|name| directive(line) == Some(name))
+            });
+            if !known && !line.trim().is_empty() {
+                merged.header.push(line.clone());
+            }
+        }
+
+        let games: Vec<&GameRecord> = records
+            .games
+            .iter()
+            .filter(|game| !merged.games.iter().any(|kept| kept.same_board(game)))
+            .collect();
+        let used: Vec<u32> = merged.games.iter().filter_map(board_number).collect();
+        let offset = if games
+            .iter()
+            .filter_map(|game| board_number(game))
+            .any(|number| used.contains(&number))
+        {
+            used.iter().copied().max().unwrap_or(0)
+        } else {
+            0
+        };
+
+        for game in games {
+            if merged.games.iter().any(|kept| kept.same_board(game)) {
+                continue;
+            }
+            let mut game = game.clone();
+            if let Some(number) = board_number(&game).filter(|_| offset > 0) {
+                game.set_tag("Board", &(number + offset).to_string());
+            }
+            merged.games.push(game);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(board: u32, deal: &str) -> String {
+        format!("[Event \"\"]\n[Board \"{}\"]\n[Deal \"{}\"]\n", board, deal)
+    }
+
+    #[test]
+    fn test_merge_records() {
+        let first = PbnRecords::parse(&format!(
+            "% lesson 1\n%HRTitleEvent Lesson 1\n{}{}",
+            game(1, "N:AKQ.JT9.876.5432 - - -"),
+            game(2, "N:AKJ.JT9.876.5432 - - -")
+        ));
+        let second = PbnRecords::parse(&format!(
+            "% lesson 2\n%HRTitleEvent Lesson 2\n%BoardsPerPage 2\n{}{}",
+            game(1, "N:AKJ.JT9.876.5432 - - -"),
+            game(2, "N:AKT.JT9.876.5432 - - -")
+        ));
+
+        let merged = merge_records(&[first, second]);
+        assert_eq!(
+            merged.header,
+            [
+                "% lesson 1",
+                "%HRTitleEvent Lesson 1",
+                "% lesson 2",
+                "%BoardsPerPage 2"
+            ]
+        );
+        // The second file's first board repeats board 2; its other board
+        // clashes with board 2 and is renumbered after it
+        let numbers: Vec<_> = merged.games.iter().filter_map(board_number).collect();
+        assert_eq!(numbers, [1, 2, 4]);
+        assert_eq!(
+            merged.games[2].tag("Deal").as_deref(),
+            Some("N:AKT.JT9.876.5432 - - -")
+        );
+    }
+
+    #[test]
+    fn test_merge_without_clashes() {
+        let first = PbnRecords::parse(&game(1, "N:AKQ.JT9.876.5432 - - -"));
+        let second = PbnRecords::parse(&game(2, "N:AKJ.JT9.876.5432 - - -"));
+        let merged = merge_records(&[first.clone(), second]);
+        let numbers: Vec<_> = merged.games.iter().filter_map(board_number).collect();
+        assert_eq!(numbers, [1, 2]);
+        assert_eq!(merge_records(&[first.clone(), first.clone()]), first);
+    }
+}
@@ -1,3 +1,74 @@
+#[cfg(feature = "cli")]
+pub mod analyze;
+#[cfg(feature = "cli")]
 pub mod args;
+#[cfg(feature = "cli")]
+pub mod dd;
+#[cfg(feature = "cli")]
+pub mod diff;
+#[cfg(feature = "cli")]
+pub mod extract;
+#[cfg(feature = "cli")]
+pub mod filter;
+#[cfg(feature = "cli")]
+pub mod list;
+#[cfg(feature = "cli")]
+pub mod merge;
+#[cfg(feature = "cli")]
+pub mod preview;
+#[cfg(feature = "cli")]
+pub mod progress;
+#[cfg(feature = "cli")]
+pub mod renumber;
+#[cfg(feature = "cli")]
+pub mod rotate;
+#[cfg(feature = "cli")]
+pub mod score;
+#[cfg(feature = "cli")]
+pub mod sort;
+#[cfg(feature = "cli")]
+pub mod split;
+#[cfg(feature = "cli")]
+pub mod stats;
+pub mod values;
 
-pub use args::{parse_board_range, Args, Layout, MarginPreset, Orientation, PageSize};
+#[cfg(feature = "cli")]
+pub use analyze::BoardAnalysis;
+#[cfg(feature = "cli")]
+pub use args::{
+    expand_inputs, is_stdio, layout_output_path, parse_board_range, parse_seat, Args, BoardRange,
+    Command, DiagnosticsFormat, LayoutSelection, MarginPreset, Movement, Orientation, PageSize,
+    ScoringMethod, SortKey,
+};
+#[cfg(feature = "cli")]
+pub use dd::{annotate_pbn, DoubleDummyResult};
+#[cfg(feature = "cli")]
+pub use diff::{diff_boards, BoardDiff};
+#[cfg(feature = "cli")]
+pub use extract::extract_records;
+#[cfg(feature = "cli")]
+pub use filter::BoardFilter;
+#[cfg(feature = "cli")]
+pub use list::board_table;
+#[cfg(feature = "cli")]
+pub use merge::merge_records;
+#[cfg(feature = "cli")]
+pub use preview::{board_detail, run_preview, Preview};
+#[cfg(feature = "cli")]
+pub use progress::{clear_progress_bar, progress_bar, progress_line};
+#[cfg(feature = "cli")]
+pub use renumber::{parse_board_map, renumber_records, Renumbering};
+#[cfg(feature = "cli")]
+pub use rotate::{rotate_records, Rotation};
+#[cfg(feature = "cli")]
+pub use score::{parse_results_csv, results_from_boards, ScoreReport};
+#[cfg(feature = "cli")]
+pub use sort::sort_boards;
+#[cfg(feature = "cli")]
+pub use split::{split_boards, split_file_name, DEFAULT_SPLIT_NAME};
+#[cfg(feature = "cli")]
+pub use stats::BoardStats;
+pub use values::{
+    AuctionHeaders, ColorTheme, CompressionLevel, Duplex, FontEmbedding, HiddenHandStyle, Language,
+    Layout, SuitStyle, TitleBlock,
+};
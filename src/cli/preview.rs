@@ -0,0 +1,355 @@
+//! Interactive board preview (`pbn-to-pdf preview`)
+//!
+//! A terminal view of a file's boards: the columns of `list` on the left and
+//! the selected board's hands, auction and commentary on the right. Boards
+//! are marked for printing (all of them to begin with); confirming prints
+//! the marked boards as a `--boards` range, so a conversion can render just
+//! those:
+//!
+//! ```text
+//! pbn-to-pdf hands.pbn --boards "$(pbn-to-pdf preview hands.pbn)"
+//! ```
+//!
+//! The view is drawn on standard error, leaving standard output for the range.
+
+use std::io::{self, Stderr};
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+
+use super::list::board_row;
+use crate::model::{Board, CallExt, Direction, DirectionExt, SUITS_DISPLAY_ORDER};
+
+/// Width of each hand in the diagram
+const HAND_COLUMN: usize = 16;
+
+/// Width of each seat's column in the auction
+const CALL_COLUMN: usize = 7;
+
+/// Width of the board list, borders included
+const LIST_WIDTH: u16 = 34;
+
+/// Rows moved by Page Up and Page Down
+const PAGE: isize = 10;
+
+const KEYS: &str = "↑/↓ move  space mark  a mark all  enter print range  q quit";
+
+/// The boards being previewed, which of them are marked, and the selection
+#[derive(Debug, Clone)]
+pub struct Preview {
+    boards: Vec<Board>,
+    marked: Vec<bool>,
+    selected: usize,
+}
+
+impl Preview {
+    /// A preview with every board marked and the first one selected
+    pub fn new(boards: Vec<Board>) -> Self {
+        let marked = vec![true; boards.len()];
+        Self {
+            boards,
+            marked,
+            selected: 0,
+        }
+    }
+
+    /// Move the selection, stopping at the first and last boards
+    pub fn move_by(&mut self, rows: isize) {
+        let last = self.boards.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(rows).min(last);
+    }
+
+    /// Mark or unmark the selected board
+    pub fn toggle(&mut self) {
+        if let Some(marked) = self.marked.get_mut(self.selected) {
+            *marked = !*marked;
+        }
+    }
+
+    /// Unmark every board if all are marked, otherwise mark them all
+    pub fn toggle_all(&mut self) {
+        let mark = !self.marked.iter().all(|&marked| marked);
+        self.marked.fill(mark);
+    }
+
+    /// The marked boards as a `--boards` range, with consecutive numbers
+    /// joined ("1-4,7,Ex 3"); None if no board is marked
+    pub fn board_range(&self) -> Option<String> {
+        let mut parts: Vec<(Option<(u32, u32)>, String)> = Vec::new();
+        for (board, _) in self
+            .boards
+            .iter()
+            .zip(&self.marked)
+            .filter(|(_, marked)| **marked)
+        {
+            if let (Some(number), Some((Some((_, end)), _))) = (board.number, parts.last_mut()) {
+                if *end + 1 == number {
+                    *end = number;
+                    continue;
+                }
+            }
+            match (board.number, &board.board_id) {
+                (Some(number), _) => parts.push((Some((number, number)), String::new())),
+                (None, Some(id)) => parts.push((None, id.clone())),
+                (None, None) => {}
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = parts
+            .into_iter()
+            .map(|part| match part {
+                (Some((start, end)), _) if start == end => start.to_string(),
+                (Some((start, end)), _) => format!("{}-{}", start, end),
+                (None, id) => id,
+            })
+            .collect();
+        Some(parts.join(","))
+    }
+
+    /// Show the preview until the user confirms (returning the board range)
+    /// or quits (returning None)
+    fn run(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stderr>>,
+    ) -> io::Result<Option<String>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => {
+                    if let Some(range) = self.board_range() {
+                        return Ok(Some(range));
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+                KeyCode::PageUp => self.move_by(-PAGE),
+                KeyCode::PageDown => self.move_by(PAGE),
+                KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+                KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX),
+                KeyCode::Char(' ') => self.toggle(),
+                KeyCode::Char('a') => self.toggle_all(),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list, detail] =
+            Layout::horizontal([Constraint::Length(LIST_WIDTH), Constraint::Min(0)]).areas(main);
+
+        let items: Vec<ListItem> = self
+            .boards
+            .iter()
+            .zip(&self.marked)
+            .map(|(board, &marked)| {
+                let [number, dealer, vul, contract, _, _] = board_row(board);
+                ListItem::new(format!(
+                    "[{}] {:<5} {:<1} {:<4} {}",
+                    if marked { "x" } else { " " },
+                    number,
+                    dealer,
+                    vul,
+                    contract
+                ))
+            })
+            .collect();
+        let marked = self.marked.iter().filter(|&&marked| marked).count();
+        let title = format!("Boards ({} of {} marked)", marked, self.boards.len());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::bordered().title(title))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            list,
+            &mut state,
+        );
+
+        if let Some(board) = self.boards.get(self.selected) {
+            let [number, ..] = board_row(board);
+            frame.render_widget(
+                Paragraph::new(board_detail(board).join("\n"))
+                    .block(Block::bordered().title(format!("Board {}", number)))
+                    .wrap(Wrap { trim: false }),
+                detail,
+            );
+        }
+        frame.render_widget(Paragraph::new(KEYS), help);
+    }
+}
+
+/// One seat's hand, a suit per line
+fn hand_lines(board: &Board, seat: Direction) -> Vec<String> {
+    let hand = board.deal.hand(seat);
+    SUITS_DISPLAY_ORDER
+        .iter()
+        .map(|&suit| format!("{} {}", suit.symbol(), hand.holding(suit)))
+        .collect()
+}
+
+/// A board as text: dealer, vulnerability and contract, the hands round the
+/// table, the auction in seat columns, then the commentary
+pub fn board_detail(board: &Board) -> Vec<String> {
+    let [_, dealer, vul, contract, hcp, _] = board_row(board);
+    let mut lines = vec![format!(
+        "Dealer {}   Vul {}   Contract {}   HCP {}",
+        if dealer.is_empty() { "-" } else { &dealer },
+        vul,
+        contract,
+        hcp
+    )];
+
+    if !board.deal.is_empty() {
+        lines.push(String::new());
+        for line in hand_lines(board, Direction::North) {
+            lines.push(format!("{:width$}{}", "", line, width = HAND_COLUMN));
+        }
+        let west = hand_lines(board, Direction::West);
+        let east = hand_lines(board, Direction::East);
+        for (west, east) in west.iter().zip(&east) {
+            lines.push(format!("{:<width$}{}", west, east, width = 2 * HAND_COLUMN));
+        }
+        for line in hand_lines(board, Direction::South) {
+            lines.push(format!("{:width$}{}", "", line, width = HAND_COLUMN));
+        }
+    }
+
+    if let Some(ref auction) = board.auction {
+        lines.push(String::new());
+        let mut cells: Vec<String> = ["West", "North", "East", "South"]
+            .map(str::to_string)
+            .to_vec();
+        cells.extend(vec![String::new(); auction.dealer.table_position()]);
+        cells.extend(auction.calls.iter().enumerate().map(|(index, annotated)| {
+            let call = annotated.call.pbn_text();
+            let alert = if auction.is_alerted(index) { "!" } else { "" };
+            match annotated.annotation {
+                Some(_) => format!("{}{}*", call, alert),
+                None => format!("{}{}", call, alert),
+            }
+        }));
+        for row in cells.chunks(4) {
+            let row: String = row
+                .iter()
+                .map(|cell| format!("{:<width$}", cell, width = CALL_COLUMN))
+                .collect();
+            lines.push(row.trim_end().to_string());
+        }
+        let mut notes: Vec<_> = auction.notes.iter().collect();
+        notes.sort();
+        for (number, text) in notes {
+            lines.push(format!("{}: {}", number, text));
+        }
+    }
+
+    for block in &board.commentary {
+        lines.push(String::new());
+        lines.push(block.content.to_plain_text());
+    }
+    lines
+}
+
+/// Preview boards in the terminal. Returns the `--boards` range of the
+/// marked boards, or None if the user quit without confirming.
+pub fn run_preview(boards: Vec<Board>) -> io::Result<Option<String>> {
+    let mut preview = Preview::new(boards);
+
+    enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen)?;
+    let result = Terminal::new(CrosstermBackend::new(io::stderr()))
+        .and_then(|mut terminal| preview.run(&mut terminal));
+    // Put the terminal back even if drawing failed
+    disable_raw_mode()?;
+    execute!(io::stderr(), LeaveAlternateScreen)?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_pbn;
+
+    const PBN: &str = "[Event \"\"]\n\
+        [Board \"1\"]\n\
+        [Dealer \"E\"]\n\
+        [Vulnerable \"None\"]\n\
+        [Deal \"N:AKQ.JT9.876.5432 JT9.AKQ.5432.876 876.5432.AKQ.JT9 5432.876.JT9.AKQ\"]\n\
+        [Contract \"1NT\"]\n\
+        [Declarer \"E\"]\n\
+        [Auction \"E\"]\n\
+        1NT =1= Pass Pass Pass\n\
+        [Note \"1:15-17\"]\n\
+        {Count your winners.}\n";
+
+    fn boards(ids: &[&str]) -> Vec<Board> {
+        ids.iter()
+            .map(|id| {
+                let mut board = Board::new();
+                board.number = id.parse().ok();
+                board.board_id = Some(id.to_string());
+                board
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_board_detail() {
+        let board = &parse_pbn(PBN).unwrap().boards[0];
+        let lines = board_detail(board);
+        assert_eq!(
+            lines[0],
+            "Dealer E   Vul None   Contract 1NT E   HCP 10-10-10-10"
+        );
+        assert_eq!(lines[2], format!("{:16}♠ AKQ", ""));
+        assert_eq!(lines[6], format!("{:<32}♠ JT9", "♠ 5432"));
+        assert_eq!(lines[15], "West   North  East   South");
+        assert_eq!(lines[16], "              1NT*   Pass");
+        assert_eq!(lines[17], "Pass   Pass");
+        assert_eq!(lines[18], "1: 15-17");
+        assert_eq!(lines.last().unwrap(), "Count your winners.");
+    }
+
+    #[test]
+    fn test_board_range() {
+        let mut preview = Preview::new(boards(&["1", "2", "3", "5", "Ex 3", "6"]));
+        assert_eq!(preview.board_range().as_deref(), Some("1-3,5,Ex 3,6"));
+
+        preview.move_by(1);
+        preview.toggle();
+        preview.move_by(3);
+        preview.toggle();
+        assert_eq!(preview.board_range().as_deref(), Some("1,3,5-6"));
+
+        preview.toggle_all();
+        assert_eq!(preview.board_range().as_deref(), Some("1-3,5,Ex 3,6"));
+        preview.toggle_all();
+        assert_eq!(preview.board_range(), None);
+    }
+
+    #[test]
+    fn test_move_stops_at_ends() {
+        let mut preview = Preview::new(boards(&["1", "2", "3"]));
+        preview.move_by(-1);
+        assert_eq!(preview.selected, 0);
+        preview.move_by(isize::MAX);
+        assert_eq!(preview.selected, 2);
+    }
+}
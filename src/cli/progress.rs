@@ -0,0 +1,76 @@
+//! Progress bar for conversions, drawn on standard error
+//!
+//! Shown while a single file renders, when standard error is a terminal and
+//! nothing else (log output, JSON diagnostics) is being written there.
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::render::{ProgressCallback, RenderProgress, RenderStage};
+
+/// Width of the bar, in characters
+const BAR_WIDTH: usize = 30;
+
+/// The progress bar's text for one report
+pub fn progress_line(progress: RenderProgress) -> String {
+    match progress.stage {
+        RenderStage::Boards => {
+            let filled = (BAR_WIDTH * progress.done)
+                .checked_div(progress.total)
+                .unwrap_or(BAR_WIDTH)
+                .min(BAR_WIDTH);
+            format!(
+                "[{}{}] {}/{} boards",
+                "#".repeat(filled),
+                " ".repeat(BAR_WIDTH - filled),
+                progress.done,
+                progress.total
+            )
+        }
+        RenderStage::Saving => format!("[{}] saving PDF", "#".repeat(BAR_WIDTH)),
+    }
+}
+
+/// A progress callback redrawing the bar in place on standard error
+pub fn progress_bar() -> ProgressCallback {
+    Arc::new(|progress| {
+        let mut stderr = io::stderr().lock();
+        // A broken progress bar isn't worth failing the conversion for
+        let _ = write!(stderr, "\r\x1b[2K{}", progress_line(progress));
+        let _ = stderr.flush();
+    })
+}
+
+/// Remove the progress bar, leaving the cursor at the start of its line
+pub fn clear_progress_bar() {
+    eprint!("\r\x1b[2K");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_line() {
+        let line = progress_line(RenderProgress {
+            stage: RenderStage::Boards,
+            done: 10,
+            total: 40,
+        });
+        assert_eq!(line, format!("[{:<30}] 10/40 boards", "#######"));
+
+        let line = progress_line(RenderProgress {
+            stage: RenderStage::Boards,
+            done: 0,
+            total: 0,
+        });
+        assert!(line.starts_with(&format!("[{}]", "#".repeat(30))));
+
+        let line = progress_line(RenderProgress {
+            stage: RenderStage::Saving,
+            done: 40,
+            total: 40,
+        });
+        assert!(line.ends_with("] saving PDF"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Board renumbering (`pbn-to-pdf renumber`)
+//!
+//! Rewrites the `[Board]` tags of a PBN file (see [`PbnRecords`]) and, when
+//! asked, the `[Dealer]` and `[Vulnerable]` tags that follow from the new
+//! numbers.
+
+use std::collections::HashMap;
+
+use crate::error::ConfigError;
+use crate::model::scoring::{board_dealer, board_vulnerability};
+use crate::model::{DirectionExt, VulnerabilityExt};
+use crate::parser::PbnRecords;
+
+/// How boards get their new numbers
+#[derive(Debug, Clone, PartialEq)]
+pub enum Renumbering {
+    /// Add to every board number (negative to number down)
+    Offset(i64),
+    /// Number the boards in file order from this value
+    Start(u32),
+    /// New board numbers by old board number; boards not listed keep theirs
+    Map(HashMap<String, String>),
+}
+
+/// Parse a board mapping file: one "old new" pair per line, separated by
+/// spaces or a comma, with `#` starting a comment line
+pub fn parse_board_map(text: &str) -> Result<HashMap<String, String>, ConfigError> {
+    let mut map = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |message: String| {
+            ConfigError::InvalidInput(format!("board map line {}: {}", index + 1, message))
+        };
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let [old, new] = fields[..] else {
+            return Err(invalid(format!("expected \"old new\", got \"{}\"", line)));
+        };
+        if map.insert(old.to_string(), new.to_string()).is_some() {
+            return Err(invalid(format!("board {} is mapped twice", old)));
+        }
+    }
+    Ok(map)
+}
+
+/// Renumber the boards of a file. With `derive`, each renumbered board also
+/// gets the dealer and vulnerability of its new number. Returns warnings for
+/// boards whose auction no longer starts with the dealer.
+pub fn renumber_records(
+    records: &mut PbnRecords,
+    renumbering: &Renumbering,
+    derive: bool,
+) -> Result<Vec<String>, ConfigError> {
+    let mut warnings = Vec::new();
+    let mut next = match renumbering {
+        Renumbering::Start(start) => start.saturating_sub(1),
+        _ => 0,
+    };
+
+    for game in &mut records.games {
+        let Some(old) = game.tag("Board") else {
+            continue;
+        };
+        let old = old.trim().to_string();
+        let new = match renumbering {
+            Renumbering::Offset(offset) => {
+                let Ok(number) = old.parse::<i64>() else {
+                    continue;
+                };
+                let new = number + offset;
+                if new < 1 {
+                    return Err(ConfigError::InvalidInput(format!(
+                        "board {} would be numbered {}",
+                        old, new
+                    )));
+                }
+                new.to_string()
+            }
+            Renumbering::Start(_) => {
+                next += 1;
+                next.to_string()
+            }
+            Renumbering::Map(map) => match map.get(&old) {
+                Some(new) => new.clone(),
+                None => continue,
+            },
+        };
+        game.set_tag("Board", &new);
+
+        let Some(number) = new.parse::<u32>().ok().filter(|_| derive) else {
+            continue;
+        };
+        let dealer = board_dealer(number).pbn_text();
+        game.set_tag("Dealer", dealer);
+        game.set_tag("Vulnerable", board_vulnerability(number).pbn_text());
+        if let Some(first) = game.tag("Auction") {
+            if !first.trim().eq_ignore_ascii_case(dealer) {
+                warnings.push(format!(
+                    "board {}: the auction starts with {} but {} now deals",
+                    new,
+                    first.trim(),
+                    dealer
+                ));
+            }
+        }
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PBN: &str = "[Event \"\"]\n[Board \"1\"]\n[Dealer \"N\"]\n[Vulnerable \"None\"]\n\
+        [Event \"\"]\n[Board \"2\"]\n[Auction \"E\"]\nPass AP\n\
+        [Event \"\"]\n[Board \"x\"]\n";
+
+    fn numbers(records: &PbnRecords) -> Vec<String> {
+        records
+            .games
+            .iter()
+            .filter_map(|game| game.tag("Board"))
+            .collect()
+    }
+
+    #[test]
+    fn test_offset_and_start() {
+        let mut records = PbnRecords::parse(PBN);
+        renumber_records(&mut records, &Renumbering::Offset(8), false).unwrap();
+        assert_eq!(numbers(&records), ["9", "10", "x"]);
+        assert_eq!(records.games[0].tag("Dealer").as_deref(), Some("N"));
+
+        assert!(renumber_records(&mut records, &Renumbering::Offset(-9), false).is_err());
+
+        renumber_records(&mut records, &Renumbering::Start(5), false).unwrap();
+        assert_eq!(numbers(&records), ["5", "6", "7"]);
+    }
+
+    #[test]
+    fn test_map_and_derive() {
+        let map = parse_board_map("# old new\n1 6\n2,3\n").unwrap();
+        let mut records = PbnRecords::parse(PBN);
+        let warnings = renumber_records(&mut records, &Renumbering::Map(map), true).unwrap();
+        assert_eq!(numbers(&records), ["6", "3", "x"]);
+        assert_eq!(records.games[0].tag("Dealer").as_deref(), Some("E"));
+        assert_eq!(records.games[0].tag("Vulnerable").as_deref(), Some("EW"));
+        assert_eq!(records.games[1].tag("Dealer").as_deref(), Some("S"));
+        assert_eq!(records.games[1].tag("Vulnerable").as_deref(), Some("EW"));
+        assert_eq!(
+            warnings,
+            ["board 3: the auction starts with E but S now deals"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_board_map() {
+        assert!(parse_board_map("1 2 3").is_err());
+        assert!(parse_board_map("1 2\n1 3").is_err());
+    }
+}
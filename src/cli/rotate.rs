@@ -0,0 +1,254 @@
+//! Deal rotation (`pbn-to-pdf rotate`)
+//!
+//! Turns boards round the table so a chosen seat deals, declares or sits
+//! South. Everything tied to a seat turns with the hands: player names,
+//! dealer, declarer, the auction and play, hidden hands, vulnerability,
+//! scores, the double-dummy table and seat-specific commentary (`{N: ...}`).
+//! Seats named in commentary text are left as written.
+
+use crate::model::{
+    Board, Direction, DirectionExt, DoubleDummyTricks, Vulnerability, VulnerabilityExt,
+};
+use crate::parser::{GameRecord, PbnRecords};
+
+/// Where each board is turned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// Turn each board so this seat deals
+    Dealer(Direction),
+    /// Turn each board so this seat declares
+    Declarer(Direction),
+    /// Turn every board so this seat's hand goes South
+    South(Direction),
+}
+
+/// Player name tags, in seat order
+const PLAYER_TAGS: [&str; 4] = ["North", "East", "South", "West"];
+
+fn seat_index(seat: Direction) -> usize {
+    match seat {
+        Direction::North => 0,
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3,
+    }
+}
+
+/// The seat `turns` places clockwise from `seat`
+fn turn(seat: Direction, turns: usize) -> Direction {
+    (0..turns % 4).fold(seat, |seat, _| seat.next())
+}
+
+/// A seat letter turned, or any other character unchanged
+fn turn_char(c: char, turns: usize) -> char {
+    match Direction::from_char(c).filter(|_| c.is_ascii_uppercase()) {
+        Some(seat) => turn(seat, turns).pbn_text().chars().next().unwrap_or(c),
+        None => c,
+    }
+}
+
+/// Turn a tag's seat letters: all of them, or just the first
+fn turn_tag(game: &mut GameRecord, name: &str, turns: usize, first_only: bool) {
+    if let Some(value) = game.tag(name) {
+        let value: String = value
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if first_only && i > 0 {
+                    c
+                } else {
+                    turn_char(c, turns)
+                }
+            })
+            .collect();
+        game.set_tag(name, &value);
+    }
+}
+
+/// Turn one game `turns` places clockwise: North's hand goes East for one turn
+pub fn rotate_game(game: &mut GameRecord, turns: usize) {
+    let turns = turns % 4;
+    if turns == 0 {
+        return;
+    }
+
+    // Tags whose value starts with a seat: "N", "^S", "E:AK..."
+    for name in ["Dealer", "Auction", "Play", "Deal"] {
+        turn_tag(game, name, turns, true);
+    }
+    turn_tag(game, "Declarer", turns, false);
+    turn_tag(game, "Hidden", turns, false);
+
+    let players = PLAYER_TAGS.map(|name| game.tag(name));
+    for (seat, player) in players.into_iter().enumerate() {
+        let name = PLAYER_TAGS[(seat + turns) % 4];
+        match player {
+            Some(player) => game.set_tag(name, &player),
+            None => game.remove_tag(name),
+        }
+    }
+
+    if let Some(dd) = game
+        .tag("DoubleDummyTricks")
+        .and_then(|value| DoubleDummyTricks::from_pbn(&value))
+    {
+        let tricks = DoubleDummyTricks::DECLARERS.map(|declarer| {
+            DoubleDummyTricks::STRAINS.map(|strain| dd.tricks(turn(declarer, 4 - turns), strain))
+        });
+        game.set_tag(
+            "DoubleDummyTricks",
+            &DoubleDummyTricks::new(tricks).to_pbn(),
+        );
+    }
+
+    // An odd number of turns swaps the partnerships
+    if turns % 2 == 1 {
+        if let Some(vulnerable) = game
+            .tag("Vulnerable")
+            .and_then(|value| Vulnerability::from_pbn(&value))
+        {
+            let swapped = match vulnerable {
+                Vulnerability::NorthSouth => Vulnerability::EastWest,
+                Vulnerability::EastWest => Vulnerability::NorthSouth,
+                other => other,
+            };
+            game.set_tag("Vulnerable", swapped.pbn_text());
+        }
+        for name in ["Score", "OptimumScore"] {
+            let Some(value) = game.tag(name) else {
+                continue;
+            };
+            let swapped = if let Some(score) = value.strip_prefix("NS") {
+                format!("EW{}", score)
+            } else if let Some(score) = value.strip_prefix("EW") {
+                format!("NS{}", score)
+            } else {
+                continue;
+            };
+            game.set_tag(name, &swapped);
+        }
+    }
+
+    // Seat prefixes of commentary blocks
+    let mut in_commentary = false;
+    for line in &mut game.lines {
+        if !in_commentary {
+            if let Some(rest) = line.trim_start().strip_prefix('{') {
+                let body = rest.trim_start();
+                let mut chars = body.chars();
+                if let (Some(c), Some(':')) = (chars.next(), chars.next()) {
+                    let offset = line.len() - body.len();
+                    let turned = turn_char(c, turns);
+                    if turned != c {
+                        line.replace_range(offset..offset + 1, turned.encode_utf8(&mut [0; 4]));
+                    }
+                }
+                in_commentary = true;
+            }
+        }
+        if in_commentary && line.contains('}') {
+            in_commentary = false;
+        }
+    }
+}
+
+/// Rotate the boards of a file. `boards` are the file's parsed boards, one
+/// per game. Returns warnings for boards without the seat to rotate by.
+pub fn rotate_records(
+    records: &mut PbnRecords,
+    boards: &[Board],
+    rotation: Rotation,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (index, (game, board)) in records.games.iter_mut().zip(boards).enumerate() {
+        let (from, to, what) = match rotation {
+            Rotation::Dealer(seat) => (board.dealer, seat, "dealer"),
+            Rotation::Declarer(seat) => (
+                board
+                    .declarer
+                    .or_else(|| board.contract.as_ref().map(|c| c.declarer)),
+                seat,
+                "declarer",
+            ),
+            Rotation::South(seat) => (Some(seat), Direction::South, "seat"),
+        };
+        match from {
+            Some(from) => rotate_game(game, seat_index(to) + 4 - seat_index(from)),
+            None => {
+                let id = board
+                    .board_id
+                    .clone()
+                    .unwrap_or_else(|| format!("#{}", index + 1));
+                warnings.push(format!("board {}: no {}, left as it was", id, what));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_pbn;
+
+    const PBN: &str = "[Event \"\"]\n\
+        [Board \"1\"]\n\
+        [North \"Ann\"]\n\
+        [South \"Bob\"]\n\
+        [Dealer \"N\"]\n\
+        [Vulnerable \"NS\"]\n\
+        [Deal \"N:AKQ.JT9.876.5432 - - -\"]\n\
+        [Declarer \"E\"]\n\
+        [Contract \"4S\"]\n\
+        [OptimumScore \"NS 100\"]\n\
+        [DoubleDummyTricks \"123451234567890a0b0c\"]\n\
+        [Auction \"N\"]\n\
+        Pass 1S Pass 4S\n\
+        AP\n\
+        {W: Lead a heart.}\n";
+
+    #[test]
+    fn test_rotate_declarer_south() {
+        let mut records = PbnRecords::parse(PBN);
+        let boards = parse_pbn(PBN).unwrap().boards;
+        let warnings = rotate_records(&mut records, &boards, Rotation::Declarer(Direction::South));
+        assert!(warnings.is_empty());
+
+        let game = &records.games[0];
+        assert_eq!(game.tag("Dealer").as_deref(), Some("E"));
+        assert_eq!(game.tag("Declarer").as_deref(), Some("S"));
+        assert_eq!(game.tag("Auction").as_deref(), Some("E"));
+        assert_eq!(game.tag("Vulnerable").as_deref(), Some("EW"));
+        assert_eq!(game.tag("OptimumScore").as_deref(), Some("EW 100"));
+        assert_eq!(game.tag("North"), None);
+        assert_eq!(game.tag("East").as_deref(), Some("Ann"));
+        assert_eq!(game.tag("West").as_deref(), Some("Bob"));
+        assert!(game.lines.contains(&"{N: Lead a heart.}".to_string()));
+
+        let board = &parse_pbn(&records.to_pbn()).unwrap().boards[0];
+        assert_eq!(board.deal.east.spades.len(), 3);
+        let dd = board.double_dummy.unwrap();
+        // East's row is now South's, West's is North's, and so on
+        assert_eq!(dd.to_pbn(), "a0b0c678901234512345");
+        assert_eq!(
+            board
+                .auction
+                .as_ref()
+                .unwrap()
+                .final_contract()
+                .unwrap()
+                .declarer,
+            Direction::South
+        );
+    }
+
+    #[test]
+    fn test_rotate_without_dealer() {
+        let pbn = "[Event \"\"]\n[Board \"2\"]\n";
+        let mut records = PbnRecords::parse(pbn);
+        let boards = parse_pbn(pbn).unwrap().boards;
+        let warnings = rotate_records(&mut records, &boards, Rotation::Dealer(Direction::North));
+        assert_eq!(warnings, ["board 2: no dealer, left as it was"]);
+        assert_eq!(records, PbnRecords::parse(pbn));
+    }
+}
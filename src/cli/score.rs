@@ -0,0 +1,484 @@
+//! Post-game scoring (`pbn-to-pdf score`)
+//!
+//! Results come from PBN records with `[Contract]`, `[Declarer]` and
+//! `[Result]` (one record per table), from each record's `[ScoreTable]`
+//! (every table's result), or from a CSV file with a header row:
+//!
+//! ```text
+//! board,ns,ew,contract,declarer,tricks
+//! 1,1,1,3NT,S,10
+//! 1,2,2,4S,N,9
+//! ```
+//!
+//! Each board's results are compared as matchpoints or Butler IMPs, and the
+//! pairs ranked by their total. In a Mitchell movement a pair is its
+//! direction and name, so NS pair 1 and EW pair 1 above are two pairs; in a
+//! Howell movement a pair that sits North-South on some boards and East-West
+//! on others keeps one total under its name.
+
+use std::collections::BTreeMap;
+
+use super::args::{Movement, ScoringMethod};
+use crate::error::ConfigError;
+use crate::model::scoring::{board_vulnerability, butler_imps, matchpoints, TableResult};
+use crate::model::{Board, Contract, Direction, DirectionExt};
+
+/// CSV columns of a results file, in any order
+const CSV_COLUMNS: [&str; 6] = ["board", "ns", "ew", "contract", "declarer", "tricks"];
+
+/// Read results from a CSV file; vulnerability follows the board number
+pub fn parse_results_csv(text: &str) -> Result<Vec<TableResult>, ConfigError> {
+    let invalid = |line: usize, message: String| {
+        ConfigError::InvalidInput(format!("results line {}: {}", line, message))
+    };
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header.split(',').map(|h| h.trim().to_lowercase()).collect();
+    let mut columns = [0; 6];
+    for (column, name) in columns.iter_mut().zip(CSV_COLUMNS) {
+        *column = header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| invalid(1, format!("missing \"{}\" column", name)))?;
+    }
+
+    let mut results = Vec::new();
+    for (index, line) in lines {
+        let number = index + 1;
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [board, ns, ew, contract, declarer, tricks] =
+            columns.map(|c| fields.get(c).copied().unwrap_or_default());
+
+        let board: u32 = board
+            .parse()
+            .map_err(|_| invalid(number, format!("invalid board \"{}\"", board)))?;
+        let contract = Contract::parse_tag(contract)
+            .ok_or_else(|| invalid(number, format!("invalid contract \"{}\"", contract)))?;
+        let contract = if let Some(mut parsed) = contract {
+            parsed.declarer = declarer
+                .chars()
+                .next()
+                .and_then(Direction::from_char)
+                .ok_or_else(|| invalid(number, format!("invalid declarer \"{}\"", declarer)))?;
+            Some(parsed)
+        } else {
+            None
+        };
+        let tricks = match contract {
+            None => 0,
+            Some(_) => tricks
+                .parse()
+                .ok()
+                .filter(|&t| t <= 13)
+                .ok_or_else(|| invalid(number, format!("invalid tricks \"{}\"", tricks)))?,
+        };
+
+        results.push(TableResult {
+            board,
+            ns: ns.to_string(),
+            ew: ew.to_string(),
+            vulnerable: board_vulnerability(board),
+            contract,
+            tricks,
+        });
+    }
+    Ok(results)
+}
+
+/// Results of PBN records that have a score table, or else a contract and
+/// a result, or were passed out
+///
+/// Pairs are named after the players. A record without player names is
+/// still scored, but its pairs are left unnamed and count toward no total,
+/// since nothing says which pairs played it.
+pub fn results_from_boards(boards: &[Board]) -> Vec<TableResult> {
+    let (with_tables, records): (Vec<&Board>, Vec<&Board>) =
+        boards.iter().partition(|board| board.score_table.is_some());
+    let from_tables = with_tables.into_iter().flat_map(|board| {
+        board
+            .score_table
+            .iter()
+            .flat_map(|table| table.results(board))
+    });
+    records
+        .into_iter()
+        .filter_map(|board| {
+            let number = board.number?;
+            // A passed-out board needs no [Result]
+            let (contract, tricks) = if board.passed_out {
+                (None, 0)
+            } else {
+                let contract = board.contract.clone()?;
+                (Some(contract), u8::try_from(board.result?).ok()?)
+            };
+            let pair = |first: &Option<String>, second: &Option<String>| match (first, second) {
+                (Some(a), Some(b)) => format!("{} & {}", a, b),
+                (Some(a), None) | (None, Some(a)) => a.clone(),
+                (None, None) => String::new(),
+            };
+            Some(TableResult {
+                board: number,
+                ns: pair(&board.players.north, &board.players.south),
+                ew: pair(&board.players.east, &board.players.west),
+                vulnerable: board.vulnerable,
+                contract,
+                tricks,
+            })
+        })
+        .chain(from_tables)
+        .collect()
+}
+
+/// A table's result with its score and the points it earned each side
+#[derive(Debug, Clone)]
+pub struct ScoredResult {
+    pub result: TableResult,
+    pub ns_score: i32,
+    pub ns_points: f32,
+    pub ew_points: f32,
+}
+
+/// The direction a pair sat in a Mitchell movement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Side {
+    NorthSouth,
+    EastWest,
+}
+
+impl Side {
+    fn label(self) -> &'static str {
+        match self {
+            Side::NorthSouth => "NS",
+            Side::EastWest => "EW",
+        }
+    }
+}
+
+/// A pair's total across the boards it played
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairTotal {
+    pub pair: String,
+    /// The pair's direction, or None when pairs are matched by name alone
+    pub side: Option<Side>,
+    pub boards: usize,
+    /// Matchpoints or IMPs
+    pub points: f32,
+    /// Most matchpoints the pair could have scored (0 for IMPs)
+    pub top: f32,
+}
+
+impl PairTotal {
+    /// The pair as shown in the rankings, e.g. "NS 1"
+    pub fn name(&self) -> String {
+        match self.side {
+            Some(side) => format!("{} {}", side.label(), self.pair),
+            None => self.pair.clone(),
+        }
+    }
+
+    /// Matchpoint percentage
+    pub fn percentage(&self) -> f32 {
+        if self.top > 0.0 {
+            self.points / self.top * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Scored results and pair rankings for a session
+#[derive(Debug, Clone)]
+pub struct ScoreReport {
+    pub method: ScoringMethod,
+    /// Results grouped by board, in board order
+    pub results: Vec<ScoredResult>,
+    /// Pairs from best to worst
+    pub rankings: Vec<PairTotal>,
+}
+
+impl ScoreReport {
+    pub fn new(mut results: Vec<TableResult>, method: ScoringMethod, movement: Movement) -> Self {
+        results.sort_by_key(|r| r.board);
+
+        let mut scored = Vec::new();
+        let mut totals: BTreeMap<(Option<Side>, String), PairTotal> = BTreeMap::new();
+        for board in results.chunk_by(|a, b| a.board == b.board) {
+            let scores: Vec<i32> = board.iter().map(TableResult::ns_score).collect();
+            let top = (scores.len() - 1) as f32;
+            let ns_points: Vec<f32> = match method {
+                ScoringMethod::Matchpoints => matchpoints(&scores),
+                ScoringMethod::Imps => butler_imps(&scores).into_iter().map(|i| i as f32).collect(),
+            };
+
+            for ((result, ns_score), ns_points) in board.iter().zip(scores).zip(ns_points) {
+                let ew_points = match method {
+                    ScoringMethod::Matchpoints => top - ns_points,
+                    ScoringMethod::Imps => -ns_points,
+                };
+                for (pair, side, points) in [
+                    (&result.ns, Side::NorthSouth, ns_points),
+                    (&result.ew, Side::EastWest, ew_points),
+                ] {
+                    if pair.is_empty() {
+                        continue;
+                    }
+                    let side = match movement {
+                        Movement::Mitchell => Some(side),
+                        Movement::Howell => None,
+                    };
+                    let total = totals
+                        .entry((side, pair.clone()))
+                        .or_insert_with(|| PairTotal {
+                            pair: pair.clone(),
+                            side,
+                            boards: 0,
+                            points: 0.0,
+                            top: 0.0,
+                        });
+                    total.boards += 1;
+                    total.points += points;
+                    if method == ScoringMethod::Matchpoints {
+                        total.top += top;
+                    }
+                }
+                scored.push(ScoredResult {
+                    result: result.clone(),
+                    ns_score,
+                    ns_points,
+                    ew_points,
+                });
+            }
+        }
+
+        let mut rankings: Vec<PairTotal> = totals.into_values().collect();
+        rankings.sort_by(|a, b| match method {
+            ScoringMethod::Matchpoints => b.percentage().total_cmp(&a.percentage()),
+            ScoringMethod::Imps => b.points.total_cmp(&a.points),
+        });
+
+        Self {
+            method,
+            results: scored,
+            rankings,
+        }
+    }
+
+    /// Score of a ranked pair as shown: a percentage or an IMP total
+    fn pair_score(&self, total: &PairTotal) -> String {
+        match self.method {
+            ScoringMethod::Matchpoints => format!("{:.2}", total.percentage()),
+            ScoringMethod::Imps => format!("{:+}", total.points),
+        }
+    }
+
+    /// Board-by-board results followed by the rankings
+    pub fn to_text(&self) -> String {
+        let unit = match self.method {
+            ScoringMethod::Matchpoints => "MP",
+            ScoringMethod::Imps => "IMPs",
+        };
+        let mut text = String::new();
+        for board in self
+            .results
+            .chunk_by(|a, b| a.result.board == b.result.board)
+        {
+            text.push_str(&format!("Board {}\n", board[0].result.board));
+            for scored in board {
+                let result = &scored.result;
+                let contract = match &result.contract {
+                    Some(c) => {
+                        format!("{} {} {}", c.to_pbn(), c.declarer.pbn_text(), result.tricks)
+                    }
+                    None => "Pass".to_string(),
+                };
+                text.push_str(&format!(
+                    "  {:<16} {:<16} {:<10} {:>6}  {:>5} {:>5} {}\n",
+                    if result.ns.is_empty() {
+                        "-"
+                    } else {
+                        &result.ns
+                    },
+                    if result.ew.is_empty() {
+                        "-"
+                    } else {
+                        &result.ew
+                    },
+                    contract,
+                    scored.ns_score,
+                    scored.ns_points,
+                    scored.ew_points,
+                    unit
+                ));
+            }
+        }
+
+        text.push_str("\nRankings\n");
+        for (rank, total) in self.rankings.iter().enumerate() {
+            text.push_str(&format!(
+                "  {:>3}  {:<24} {:>8}  ({} boards)\n",
+                rank + 1,
+                total.name(),
+                self.pair_score(total),
+                total.boards
+            ));
+        }
+        text
+    }
+
+    /// Rankings as CSV
+    pub fn to_csv(&self) -> String {
+        let score = match self.method {
+            ScoringMethod::Matchpoints => "percentage",
+            ScoringMethod::Imps => "imps",
+        };
+        let mut csv = format!("rank,pair,{},boards\n", score);
+        for (rank, total) in self.rankings.iter().enumerate() {
+            let pair = total.name();
+            let pair = if pair.contains([',', '"']) {
+                format!("\"{}\"", pair.replace('"', "\"\""))
+            } else {
+                pair
+            };
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                rank + 1,
+                pair,
+                self.pair_score(total),
+                total.boards
+            ));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESULTS: &str = "board,ns,ew,contract,declarer,tricks\n\
+        1,A,B,3NT,S,10\n\
+        1,C,D,4S,N,9\n\
+        2,A,D,2H,E,8\n\
+        2,C,B,Pass,,\n";
+
+    #[test]
+    fn test_parse_results_csv() {
+        let results = parse_results_csv(RESULTS).unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].ns_score(), 430);
+        assert_eq!(results[1].ns_score(), -50);
+        assert_eq!(results[2].ns_score(), -110);
+        assert!(results[3].contract.is_none());
+
+        assert!(parse_results_csv("board,ns,ew,contract,tricks\n").is_err());
+        assert!(parse_results_csv("board,ns,ew,contract,declarer,tricks\n1,A,B,3NT,S,14").is_err());
+    }
+
+    #[test]
+    fn test_matchpoint_rankings() {
+        let report = ScoreReport::new(
+            parse_results_csv(RESULTS).unwrap(),
+            ScoringMethod::Matchpoints,
+            Movement::Howell,
+        );
+        let ranking: Vec<(&str, f32)> = report
+            .rankings
+            .iter()
+            .map(|t| (t.pair.as_str(), t.percentage()))
+            .collect();
+        // A: top and bottom; B: bottom and bottom; C: bottom and top; D: top and top
+        assert_eq!(ranking[0], ("D", 100.0));
+        assert_eq!(ranking[3], ("B", 0.0));
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("rank,pair,percentage,boards\n1,D,100.00,2\n"));
+    }
+
+    #[test]
+    fn test_imp_rankings() {
+        let report = ScoreReport::new(
+            parse_results_csv(RESULTS).unwrap(),
+            ScoringMethod::Imps,
+            Movement::Howell,
+        );
+        // Board 1 datum 190: A +6, C -6; board 2 datum -60: A -2, C +2
+        let a = report.rankings.iter().find(|t| t.pair == "A").unwrap();
+        assert_eq!(a.points, 4.0);
+        assert!(report.to_text().contains("Board 2\n"));
+    }
+
+    #[test]
+    fn test_mitchell_keeps_directions_apart() {
+        let results = "board,ns,ew,contract,declarer,tricks\n\
+            1,1,1,3NT,S,10\n\
+            1,2,2,4S,N,9\n\
+            2,1,2,2H,E,8\n\
+            2,2,1,Pass,,\n";
+        let percentage = |report: &ScoreReport, name: &str| {
+            report
+                .rankings
+                .iter()
+                .find(|t| t.name() == name)
+                .map(PairTotal::percentage)
+        };
+
+        let mitchell = ScoreReport::new(
+            parse_results_csv(results).unwrap(),
+            ScoringMethod::Matchpoints,
+            Movement::Mitchell,
+        );
+        assert_eq!(mitchell.rankings.len(), 4);
+        assert_eq!(percentage(&mitchell, "NS 1"), Some(50.0));
+        assert_eq!(percentage(&mitchell, "EW 1"), Some(0.0));
+        assert_eq!(mitchell.rankings[0].name(), "EW 2");
+        assert!(mitchell.to_csv().contains("1,EW 2,100.00,2\n"));
+
+        let howell = ScoreReport::new(
+            parse_results_csv(results).unwrap(),
+            ScoringMethod::Matchpoints,
+            Movement::Howell,
+        );
+        assert_eq!(howell.rankings.len(), 2);
+        assert_eq!(percentage(&howell, "1"), Some(25.0));
+    }
+
+    #[test]
+    fn test_unnamed_records_count_toward_no_pair() {
+        let board = |result| Board {
+            number: Some(1),
+            contract: Contract::parse("3NT"),
+            result: Some(result),
+            ..Default::default()
+        };
+        let results = results_from_boards(&[board(9), board(10)]);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].ns.is_empty() && results[0].ew.is_empty());
+        let report = ScoreReport::new(results, ScoringMethod::Matchpoints, Movement::Mitchell);
+        assert_eq!(report.results.len(), 2);
+        assert!(report.rankings.is_empty());
+    }
+
+    #[test]
+    fn test_results_from_score_table() {
+        let mut table = crate::model::ScoreTable::from_pbn(
+            "PairId_NS\\2R;PairId_EW\\2R;Contract\\5L;Declarer\\1R;Result\\2R",
+        );
+        table.add_row(" 1  4 3NT   S 10");
+        table.add_row(" 2  3 4S    N  9");
+        let board = Board {
+            number: Some(1),
+            score_table: Some(table),
+            ..Default::default()
+        };
+        let results = results_from_boards(&[board]);
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[1].ns.as_str(), results[1].ew.as_str()), ("2", "3"));
+        assert_eq!(results[1].ns_score(), -50);
+    }
+}
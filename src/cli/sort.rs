@@ -0,0 +1,91 @@
+//! Board ordering (`--sort-by`, `--reverse`)
+
+use std::cmp::Ordering;
+
+use super::args::SortKey;
+use crate::model::{Board, Direction, Strain};
+
+/// Reorder boards by a key. The sort is stable, so boards with the same key
+/// keep their file order; boards without the key (no contract, say) go last.
+pub fn sort_boards(boards: &mut [Board], key: SortKey, reverse: bool) {
+    boards.sort_by(|a, b| match (sort_value(a, key), sort_value(b, key)) {
+        (Some(a), Some(b)) if reverse => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
+
+/// Value compared for a board: a number, then text (used by `Theme` only)
+fn sort_value(board: &Board, key: SortKey) -> Option<(u32, String)> {
+    let number = |n: u32| Some((n, String::new()));
+    match key {
+        SortKey::Number => number(board.number?),
+        // Clockwise from North
+        SortKey::Dealer => number(match board.dealer? {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
+        }),
+        // Grouped by strain (notrump first), then by level
+        SortKey::Contract => {
+            let contract = board.contract.as_ref()?;
+            let strain = match contract.suit {
+                Strain::NoTrump => 0,
+                Strain::Spades => 1,
+                Strain::Hearts => 2,
+                Strain::Diamonds => 3,
+                Strain::Clubs => 4,
+            };
+            number(strain * 10 + contract.level as u32)
+        }
+        SortKey::Hcp => number(
+            [Direction::North, Direction::South]
+                .iter()
+                .map(|&d| board.deal.hand(d).total_hcp() as u32)
+                .sum(),
+        ),
+        SortKey::Theme => Some((0, board.event.clone().unwrap_or_default())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Contract;
+
+    fn board(number: u32, contract: Option<&str>) -> Board {
+        let mut board = Board::new().with_number(number);
+        board.contract = contract.and_then(Contract::parse);
+        board
+    }
+
+    fn numbers(boards: &[Board]) -> Vec<u32> {
+        boards.iter().filter_map(|b| b.number).collect()
+    }
+
+    #[test]
+    fn test_sort_by_contract_groups_strains() {
+        let mut boards = vec![
+            board(1, Some("4S")),
+            board(2, Some("3NT")),
+            board(3, None),
+            board(4, Some("1NT")),
+            board(5, Some("2S")),
+        ];
+        sort_boards(&mut boards, SortKey::Contract, false);
+        assert_eq!(numbers(&boards), vec![4, 2, 5, 1, 3]);
+
+        sort_boards(&mut boards, SortKey::Contract, true);
+        assert_eq!(numbers(&boards), vec![1, 5, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_sort_by_number_reversed() {
+        let mut boards = vec![board(2, None), board(10, None), board(1, None)];
+        sort_boards(&mut boards, SortKey::Number, true);
+        assert_eq!(numbers(&boards), vec![10, 2, 1]);
+    }
+}
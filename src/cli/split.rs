@@ -0,0 +1,104 @@
+//! Output splitting (`--split-every`, `--split-by-session`)
+//!
+//! A long PBN file can be written as several PDFs: one per N boards, one per
+//! session (a run of boards with the same `[Event]`), or both, in which case
+//! each session is split again every N boards. Each part is named from a
+//! template such as `{stem}-{range}.pdf`.
+
+use crate::model::Board;
+use crate::render::components::header_footer::board_range;
+
+/// Default file name template for split output
+pub const DEFAULT_SPLIT_NAME: &str = "{stem}-{range}.pdf";
+
+/// Split boards into the parts written to separate files
+pub fn split_boards(boards: Vec<Board>, every: Option<usize>, by_session: bool) -> Vec<Vec<Board>> {
+    let mut sessions: Vec<Vec<Board>> = Vec::new();
+    for board in boards {
+        match sessions.last_mut() {
+            Some(session) if !by_session || session[0].event == board.event => session.push(board),
+            _ => sessions.push(vec![board]),
+        }
+    }
+
+    match every {
+        Some(n) if n > 0 => sessions
+            .into_iter()
+            .flat_map(|session| {
+                let mut parts = Vec::new();
+                let mut boards = session.into_iter().peekable();
+                while boards.peek().is_some() {
+                    parts.push(boards.by_ref().take(n).collect());
+                }
+                parts
+            })
+            .collect(),
+        _ => sessions,
+    }
+}
+
+/// File name of one part of split output
+///
+/// The template may use `{stem}` (the unsplit output file name without
+/// extension), `{range}` (first and last board, e.g. "1-8"), `{part}` (1-based
+/// part number) and `{event}`.
+pub fn split_file_name(template: &str, stem: &str, part: usize, boards: &[Board]) -> String {
+    let refs: Vec<&Board> = boards.iter().collect();
+    let event = boards
+        .first()
+        .and_then(|b| b.event.as_deref())
+        .unwrap_or_default();
+    template
+        .replace("{stem}", stem)
+        .replace("{range}", &board_range(&refs))
+        .replace("{part}", &part.to_string())
+        .replace("{event}", event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(id: u32, event: &str) -> Board {
+        let mut board = Board::new().with_number(id);
+        board.board_id = Some(id.to_string());
+        board.event = Some(event.to_string());
+        board
+    }
+
+    fn ids(parts: &[Vec<Board>]) -> Vec<Vec<u32>> {
+        parts
+            .iter()
+            .map(|part| part.iter().filter_map(|b| b.number).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_every_and_by_session() {
+        let boards: Vec<Board> = (1..=5)
+            .map(|n| board(n, if n <= 3 { "Monday" } else { "Tuesday" }))
+            .collect();
+
+        let parts = split_boards(boards.clone(), Some(2), false);
+        assert_eq!(ids(&parts), vec![vec![1, 2], vec![3, 4], vec![5]]);
+
+        let parts = split_boards(boards.clone(), None, true);
+        assert_eq!(ids(&parts), vec![vec![1, 2, 3], vec![4, 5]]);
+
+        let parts = split_boards(boards, Some(2), true);
+        assert_eq!(ids(&parts), vec![vec![1, 2], vec![3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_split_file_name() {
+        let boards: Vec<Board> = (9..=16).map(|n| board(n, "Club Game")).collect();
+        assert_eq!(
+            split_file_name(DEFAULT_SPLIT_NAME, "hands", 2, &boards),
+            "hands-9-16.pdf"
+        );
+        assert_eq!(
+            split_file_name("{event} part {part}.pdf", "hands", 2, &boards),
+            "Club Game part 2.pdf"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Statistics across a set of boards (`pbn-to-pdf stats`)
+//!
+//! Shows whether a generated practice set is balanced: how the high card
+//! points fall to each seat, which strains the contracts are in, and how
+//! evenly dealer and vulnerability rotate.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::model::{Board, Direction, DirectionExt, Strain, StrainExt, Vulnerability};
+
+const SEATS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+const STRAINS: [Strain; 5] = [
+    Strain::NoTrump,
+    Strain::Spades,
+    Strain::Hearts,
+    Strain::Diamonds,
+    Strain::Clubs,
+];
+
+const VULNERABILITIES: [(Vulnerability, &str); 4] = [
+    (Vulnerability::None, "None"),
+    (Vulnerability::NorthSouth, "NS"),
+    (Vulnerability::EastWest, "EW"),
+    (Vulnerability::Both, "Both"),
+];
+
+/// HCP ranges counted for each seat, as (label, lowest, highest)
+const HCP_RANGES: [(&str, u8, u8); 5] = [
+    ("0-5", 0, 5),
+    ("6-10", 6, 10),
+    ("11-15", 11, 15),
+    ("16-20", 16, 20),
+    ("21+", 21, 40),
+];
+
+/// How many boards have a value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Count {
+    pub name: String,
+    pub count: usize,
+}
+
+/// High card points held by one seat across the boards with a deal
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeatHcp {
+    pub seat: String,
+    pub min: u8,
+    pub max: u8,
+    pub average: f32,
+    /// Boards per HCP range (0-5, 6-10, 11-15, 16-20, 21+)
+    pub ranges: Vec<Count>,
+}
+
+/// Statistics for a set of boards
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardStats {
+    pub boards: usize,
+    /// Boards with a deal, which the HCP figures are taken from
+    pub deals: usize,
+    pub hcp: Vec<SeatHcp>,
+    /// Contracts by strain; boards without a contract count as "none"
+    pub contracts: Vec<Count>,
+    pub vulnerability: Vec<Count>,
+    /// Boards by dealer; boards without one count as "none"
+    pub dealer: Vec<Count>,
+}
+
+/// Count the boards for each named value, adding "none" for boards without one
+fn tally<T: Copy + PartialEq>(
+    boards: &[Board],
+    values: impl IntoIterator<Item = (T, String)>,
+    key: impl Fn(&Board) -> Option<T>,
+) -> Vec<Count> {
+    let mut counts: Vec<Count> = values
+        .into_iter()
+        .map(|(value, name)| Count {
+            name,
+            count: boards.iter().filter(|b| key(b) == Some(value)).count(),
+        })
+        .collect();
+    let missing = boards.iter().filter(|b| key(b).is_none()).count();
+    if missing > 0 {
+        counts.push(Count {
+            name: "none".to_string(),
+            count: missing,
+        });
+    }
+    counts
+}
+
+impl BoardStats {
+    /// Gather statistics for the boards
+    pub fn new(boards: &[Board]) -> Self {
+        let deals: Vec<&Board> = boards.iter().filter(|b| !b.deal.is_empty()).collect();
+
+        let hcp = SEATS
+            .iter()
+            .map(|&seat| {
+                let points: Vec<u8> = deals
+                    .iter()
+                    .map(|b| b.deal.hand(seat).total_hcp())
+                    .collect();
+                let total: u32 = points.iter().map(|&p| p as u32).sum();
+                SeatHcp {
+                    seat: seat.pbn_text().to_string(),
+                    min: points.iter().copied().min().unwrap_or(0),
+                    max: points.iter().copied().max().unwrap_or(0),
+                    average: if points.is_empty() {
+                        0.0
+                    } else {
+                        total as f32 / points.len() as f32
+                    },
+                    ranges: HCP_RANGES
+                        .iter()
+                        .map(|&(name, low, high)| Count {
+                            name: name.to_string(),
+                            count: points
+                                .iter()
+                                .filter(|&&p| (low..=high).contains(&p))
+                                .count(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            boards: boards.len(),
+            deals: deals.len(),
+            hcp,
+            contracts: tally(
+                boards,
+                STRAINS.map(|s| (s, s.pbn_text().to_string())),
+                |b| b.contract.as_ref().map(|c| c.suit),
+            ),
+            vulnerability: tally(
+                boards,
+                VULNERABILITIES.map(|(v, name)| (v, name.to_string())),
+                |b| Some(b.vulnerable),
+            ),
+            dealer: tally(boards, SEATS.map(|d| (d, d.pbn_text().to_string())), |b| {
+                b.dealer
+            }),
+        }
+    }
+}
+
+/// Counts on one line, e.g. "NT 5, S 4, none 1"
+fn counts_line(counts: &[Count]) -> String {
+    counts
+        .iter()
+        .map(|c| format!("{} {}", c.name, c.count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for BoardStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Boards: {} ({} with a deal)", self.boards, self.deals)?;
+        writeln!(f)?;
+
+        write!(f, "HCP    Min  Avg   Max")?;
+        for (name, _, _) in HCP_RANGES {
+            write!(f, "  {:>5}", name)?;
+        }
+        writeln!(f)?;
+        for seat in &self.hcp {
+            write!(
+                f,
+                "{:<5}  {:>3}  {:>4.1}  {:>3}",
+                seat.seat, seat.min, seat.average, seat.max
+            )?;
+            for range in &seat.ranges {
+                write!(f, "  {:>5}", range.count)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Contracts:     {}", counts_line(&self.contracts))?;
+        writeln!(f, "Vulnerability: {}", counts_line(&self.vulnerability))?;
+        writeln!(f, "Dealer:        {}", counts_line(&self.dealer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Contract;
+    use crate::parser::deal::parse_deal;
+
+    #[test]
+    fn test_board_stats() {
+        let mut first = Board::new().with_number(1);
+        first.dealer = Some(Direction::North);
+        first.deal =
+            parse_deal("N:AKQJ.T98.765.432 T987.AKQ.432.765 65432.J765.AK.A8 .432.QJT98.KQJT9")
+                .unwrap();
+        first.contract = Contract::parse("4S");
+
+        let mut second = Board::new().with_number(2);
+        second.dealer = Some(Direction::East);
+        second.vulnerable = Vulnerability::NorthSouth;
+        second.contract = Contract::parse("3NTX");
+
+        let stats = BoardStats::new(&[first, second]);
+        assert_eq!(stats.boards, 2);
+        assert_eq!(stats.deals, 1);
+
+        let north = &stats.hcp[0];
+        assert_eq!((north.seat.as_str(), north.min, north.max), ("N", 10, 10));
+        assert_eq!(north.ranges[1].count, 1);
+        assert_eq!(stats.hcp[2].ranges[2].count, 1);
+
+        assert_eq!(counts_line(&stats.contracts), "NT 1, S 1, H 0, D 0, C 0");
+        assert_eq!(
+            counts_line(&stats.vulnerability),
+            "None 1, NS 1, EW 0, Both 0"
+        );
+        assert_eq!(counts_line(&stats.dealer), "N 1, E 1, S 0, W 0");
+    }
+}
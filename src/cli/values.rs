@@ -0,0 +1,229 @@
+//! Option values the renderer itself uses
+//!
+//! These are the values of command-line options that are also fields of
+//! [Settings](crate::config::Settings) and
+//! [RenderOptions](crate::options::RenderOptions), so they're compiled without
+//! the `cli` feature; with it they double as clap value enums.
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Language for text the renderer generates itself
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Dutch,
+    Polish,
+}
+
+/// Named color theme
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorTheme {
+    /// Bridge Composer colors (suit colors from %PipColors)
+    #[default]
+    Classic,
+    /// Black lines and saturated colors for projectors and poor printers
+    HighContrast,
+    /// Soft colors for handouts
+    Pastel,
+    /// Navy and gold club colors
+    ClubBranded,
+}
+
+/// How suits are drawn
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SuitStyle {
+    /// Suit symbols (♠ ♥ ♦ ♣)
+    #[default]
+    Symbols,
+    /// Letters (S H D C), bridge magazine style
+    Letters,
+}
+
+/// How hard PDF streams are compressed
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionLevel {
+    /// Leave streams uncompressed, for tools that post-process the PDF
+    None,
+    /// Fastest compression
+    Fast,
+    /// Smallest files
+    #[default]
+    Best,
+}
+
+/// How fonts are embedded in the PDF
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FontEmbedding {
+    /// Only the glyphs the document uses
+    #[default]
+    Subset,
+    /// Whole font files, so the text can be edited later
+    Full,
+    /// PDF builtin fonts in place of font files; only the small suit
+    /// symbol font is embedded, as a subset
+    Builtin,
+}
+
+/// How hidden hands ([Hidden] tag) are shown in diagrams
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HiddenHandStyle {
+    /// Leave the hand out
+    #[default]
+    Omit,
+    /// Shaded box where the hand would be
+    Placeholder,
+    /// Row of face-down cards
+    CardBacks,
+}
+
+/// Where the %HRTitle block is printed
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TitleBlock {
+    /// No title block
+    #[default]
+    None,
+    /// At the top of the first page
+    First,
+    /// At the top of every page
+    Every,
+}
+
+/// What the bidding table's column headers show
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuctionHeaders {
+    /// Direction names, with player names on a second row when present
+    #[default]
+    Directions,
+    /// Player names in place of direction names (directions for missing names)
+    Names,
+    /// Player name followed by the compass letter, e.g. "Alice (W)"
+    Both,
+}
+
+/// Double-sided printing (`--duplex`)
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Duplex {
+    /// Single-sided: no blank pages, bidding sheet answers printed once
+    None,
+    /// Sheets turn over on the long edge; documents end on a back page
+    Long,
+    /// Sheets turn over on the short edge; back pages are turned upside down
+    Short,
+}
+
+/// Output layout style
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// Standard analysis layout with hand diagram, bidding, and commentary
+    #[default]
+    Analysis,
+    /// Bidding practice sheets for face-to-face practice
+    BiddingSheets,
+    /// Declarer's plan - 1 deal per page (full size)
+    #[cfg_attr(feature = "cli", value(name = "declarers-plan-1up"))]
+    #[serde(rename = "declarers-plan-1up")]
+    DeclarersPlan1up,
+    /// Declarer's plan - 2 deals per page (rotated 90°)
+    #[cfg_attr(feature = "cli", value(name = "declarers-plan-2up"))]
+    #[serde(rename = "declarers-plan-2up")]
+    DeclarersPlan2up,
+    /// Declarer's plan practice sheets (4 deals per page)
+    DeclarersPlan,
+    /// Dealer summary showing board, dealer, contract, declarer, and lead (6 per page)
+    DealerSummary,
+}
+
+impl Layout {
+    /// Every layout, in the order `--layout all` writes them
+    pub const ALL: [Layout; 6] = [
+        Layout::Analysis,
+        Layout::BiddingSheets,
+        Layout::DeclarersPlan1up,
+        Layout::DeclarersPlan2up,
+        Layout::DeclarersPlan,
+        Layout::DealerSummary,
+    ];
+
+    /// The layout's name, as `--layout` and config files spell it
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Analysis => "analysis",
+            Layout::BiddingSheets => "bidding-sheets",
+            Layout::DeclarersPlan1up => "declarers-plan-1up",
+            Layout::DeclarersPlan2up => "declarers-plan-2up",
+            Layout::DeclarersPlan => "declarers-plan",
+            Layout::DealerSummary => "dealer-summary",
+        }
+    }
+
+    /// The layout with this name, in any case
+    pub fn from_name(name: &str) -> Option<Layout> {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Get the suffix to append to output filename (without extension)
+    pub fn output_suffix(&self) -> Option<&'static str> {
+        match self {
+            Layout::Analysis => None,
+            Layout::BiddingSheets => Some(" - Bidding Sheets"),
+            Layout::DeclarersPlan1up => Some(" - Declarers Plan"),
+            Layout::DeclarersPlan2up => Some(" - Declarers Plan 2up"),
+            Layout::DeclarersPlan => Some(" - Declarers Plan 4up"),
+            Layout::DealerSummary => Some(" - Dealer Summary"),
+        }
+    }
+
+    /// Returns true if this is any declarer's plan variant
+    pub fn is_declarers_plan(&self) -> bool {
+        matches!(
+            self,
+            Layout::DeclarersPlan | Layout::DeclarersPlan1up | Layout::DeclarersPlan2up
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_names_match_serde() {
+        for layout in Layout::ALL {
+            let quoted = serde_json::to_string(&layout).unwrap();
+            assert_eq!(quoted, format!("\"{}\"", layout.name()));
+            assert_eq!(Layout::from_name(layout.name()), Some(layout));
+        }
+        assert_eq!(
+            Layout::from_name("Bidding-Sheets"),
+            Some(Layout::BiddingSheets)
+        );
+        assert_eq!(Layout::from_name("all"), None);
+    }
+}
@@ -16,6 +16,9 @@ pub const DEFAULT_LINE_HEIGHT: f32 = 5.0;
 /// Default title font size in points
 pub const DEFAULT_TITLE_FONT_SIZE: f32 = 14.0;
 
+/// Height of the %HRTitle block (`--title-block`) in mm, added to the top margin
+pub const TITLE_BLOCK_HEIGHT: f32 = 14.0;
+
 /// Default header font size in points
 pub const DEFAULT_HEADER_FONT_SIZE: f32 = 11.0;
 
@@ -0,0 +1,318 @@
+//! Localized strings for generated text
+//!
+//! Labels the renderer writes itself ("Deals", "Lead:", "All Pass", ...) come
+//! from the `Locale` for the language chosen with `--language`. Text taken from
+//! the PBN file, such as commentary and player names, is never translated.
+//!
+//! Templates use `%` as the placeholder, like `board_label_format`.
+
+use crate::cli::Language;
+use crate::model::{Direction, Vulnerability};
+
+/// Generated strings for one language
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    /// Board label template, e.g. "Board %"
+    pub board_label: &'static str,
+    pub board: &'static str,
+    /// Dealer line template in board titles, e.g. "% Deals"
+    pub deals: &'static str,
+    pub dealer: &'static str,
+    pub vul: &'static str,
+    pub contract: &'static str,
+    pub lead: &'static str,
+    pub best_leads: &'static str,
+    pub pass: &'static str,
+    pub double: &'static str,
+    pub redouble: &'static str,
+    pub all_pass: &'static str,
+    pub passed_out: &'static str,
+    pub hcp: &'static str,
+    /// Bidding sheet banner template, e.g. "% hands"
+    pub hands: &'static str,
+    pub both_hands: &'static str,
+    pub practice_page: &'static str,
+    pub answers_page: &'static str,
+    /// Full direction names: North, East, South, West
+    pub directions: [&'static str; 4],
+    /// Compass letters: N, E, S, W
+    pub compass: [&'static str; 4],
+    /// Vulnerability: None, North-South, East-West, Both
+    pub vulnerability: [&'static str; 4],
+    /// Rooms of a teams match: Open, Closed
+    pub rooms: [&'static str; 2],
+    /// Title of the event ranking page
+    pub ranking: &'static str,
+    /// Ranking columns: Rank, Pair, Score, Percent
+    pub ranking_columns: [&'static str; 4],
+}
+
+static ENGLISH: Locale = Locale {
+    board_label: "Board %",
+    board: "Board",
+    deals: "% Deals",
+    dealer: "Dealer",
+    vul: "Vul",
+    contract: "Contract",
+    lead: "Lead",
+    best_leads: "Best leads",
+    pass: "Pass",
+    double: "Dbl",
+    redouble: "Rdbl",
+    all_pass: "All Pass",
+    passed_out: "Passed Out",
+    hcp: "HCP",
+    hands: "% hands",
+    both_hands: "Both hands",
+    practice_page: "Practice Page",
+    answers_page: "Answers Page",
+    directions: ["North", "East", "South", "West"],
+    compass: ["N", "E", "S", "W"],
+    vulnerability: ["None", "N-S", "E-W", "Both"],
+    rooms: ["Open Room", "Closed Room"],
+    ranking: "Ranking",
+    ranking_columns: ["Rank", "Pair", "Score", "%"],
+};
+
+static FRENCH: Locale = Locale {
+    board_label: "Donne %",
+    board: "Donne",
+    deals: "Donneur %",
+    dealer: "Donneur",
+    vul: "Vuln",
+    contract: "Contrat",
+    lead: "Entame",
+    best_leads: "Meilleures entames",
+    pass: "Passe",
+    double: "Contre",
+    redouble: "Surcontre",
+    all_pass: "Tous passent",
+    passed_out: "Donne passée",
+    hcp: "PH",
+    hands: "Main de %",
+    both_hands: "Les deux mains",
+    practice_page: "Page d'entraînement",
+    answers_page: "Page des réponses",
+    directions: ["Nord", "Est", "Sud", "Ouest"],
+    compass: ["N", "E", "S", "O"],
+    vulnerability: ["Personne", "N-S", "E-O", "Tous"],
+    rooms: ["Salle ouverte", "Salle fermée"],
+    ranking: "Classement",
+    ranking_columns: ["Rang", "Paire", "Score", "%"],
+};
+
+static GERMAN: Locale = Locale {
+    board_label: "Board %",
+    board: "Board",
+    deals: "Geber %",
+    dealer: "Geber",
+    vul: "Gefahr",
+    contract: "Kontrakt",
+    lead: "Ausspiel",
+    best_leads: "Beste Ausspiele",
+    pass: "Passe",
+    double: "Kontra",
+    redouble: "Rekontra",
+    all_pass: "Alle passen",
+    passed_out: "Eingepasst",
+    hcp: "FP",
+    hands: "Hand %",
+    both_hands: "Beide Hände",
+    practice_page: "Übungsseite",
+    answers_page: "Lösungsseite",
+    directions: ["Nord", "Ost", "Süd", "West"],
+    compass: ["N", "O", "S", "W"],
+    vulnerability: ["Keiner", "N-S", "O-W", "Alle"],
+    rooms: ["Offener Raum", "Geschlossener Raum"],
+    ranking: "Rangliste",
+    ranking_columns: ["Rang", "Paar", "Ergebnis", "%"],
+};
+
+static DUTCH: Locale = Locale {
+    board_label: "Spel %",
+    board: "Spel",
+    deals: "Gever %",
+    dealer: "Gever",
+    vul: "Kwetsbaar",
+    contract: "Contract",
+    lead: "Uitkomst",
+    best_leads: "Beste uitkomsten",
+    pass: "Pas",
+    double: "Doublet",
+    redouble: "Redoublet",
+    all_pass: "Allen passen",
+    passed_out: "Rondgepast",
+    hcp: "HCP",
+    hands: "Hand %",
+    both_hands: "Beide handen",
+    practice_page: "Oefenpagina",
+    answers_page: "Antwoordpagina",
+    directions: ["Noord", "Oost", "Zuid", "West"],
+    compass: ["N", "O", "Z", "W"],
+    vulnerability: ["Niemand", "N-Z", "O-W", "Allen"],
+    rooms: ["Open zaal", "Gesloten zaal"],
+    ranking: "Uitslag",
+    ranking_columns: ["Plaats", "Paar", "Score", "%"],
+};
+
+// Polish needs a custom font (--font-*): "ł", "ą" etc. aren't in the builtin fonts
+static POLISH: Locale = Locale {
+    board_label: "Rozdanie %",
+    board: "Rozdanie",
+    deals: "Rozdaje %",
+    dealer: "Rozdający",
+    vul: "Założenia",
+    contract: "Kontrakt",
+    lead: "Wist",
+    best_leads: "Najlepsze wisty",
+    pass: "Pas",
+    double: "Kontra",
+    redouble: "Rekontra",
+    all_pass: "Pas, pas, pas",
+    passed_out: "Rozdanie spasowane",
+    hcp: "PC",
+    hands: "Ręka %",
+    both_hands: "Obie ręce",
+    practice_page: "Strona do ćwiczeń",
+    answers_page: "Strona z odpowiedziami",
+    directions: ["Północ", "Wschód", "Południe", "Zachód"],
+    compass: ["N", "E", "S", "W"],
+    vulnerability: ["Nikt", "NS", "WE", "Obie"],
+    rooms: ["Pokój otwarty", "Pokój zamknięty"],
+    ranking: "Klasyfikacja",
+    ranking_columns: ["Miejsce", "Para", "Wynik", "%"],
+};
+
+impl Locale {
+    /// Get the strings for a language
+    pub fn for_language(language: Language) -> &'static Locale {
+        match language {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+            Language::German => &GERMAN,
+            Language::Dutch => &DUTCH,
+            Language::Polish => &POLISH,
+        }
+    }
+
+    /// Full name of a direction
+    pub fn direction(&self, direction: Direction) -> &'static str {
+        self.directions[direction_index(direction)]
+    }
+
+    /// Compass letter for a direction
+    pub fn compass_letter(&self, direction: Direction) -> &'static str {
+        self.compass[direction_index(direction)]
+    }
+
+    /// Vulnerability as shown in board titles
+    pub fn vulnerability(&self, vulnerability: Vulnerability) -> &'static str {
+        match vulnerability {
+            Vulnerability::None => self.vulnerability[0],
+            Vulnerability::NorthSouth => self.vulnerability[1],
+            Vulnerability::EastWest => self.vulnerability[2],
+            Vulnerability::Both => self.vulnerability[3],
+        }
+    }
+
+    /// Dealer line for board titles, e.g. "North Deals"
+    pub fn deals_line(&self, dealer: Direction) -> String {
+        self.deals.replace('%', self.direction(dealer))
+    }
+
+    /// Bidding sheet banner for a player, e.g. "North hands"
+    pub fn hands_banner(&self, player: Direction) -> String {
+        self.hands.replace('%', self.direction(player))
+    }
+
+    /// Label followed by a colon and space, e.g. "Lead: "
+    pub fn label(text: &str) -> String {
+        format!("{}: ", text)
+    }
+
+    /// Every string of the locale
+    pub fn strings(&self) -> Vec<&'static str> {
+        let mut strings = vec![
+            self.board_label,
+            self.board,
+            self.deals,
+            self.dealer,
+            self.vul,
+            self.contract,
+            self.lead,
+            self.best_leads,
+            self.pass,
+            self.double,
+            self.redouble,
+            self.all_pass,
+            self.passed_out,
+            self.hcp,
+            self.hands,
+            self.both_hands,
+            self.practice_page,
+            self.answers_page,
+            self.ranking,
+        ];
+        strings.extend(self.directions);
+        strings.extend(self.compass);
+        strings.extend(self.vulnerability);
+        strings.extend(self.rooms);
+        strings.extend(self.ranking_columns);
+        strings
+    }
+
+    /// Whether the builtin PDF fonts (Latin-1) have every letter of the
+    /// locale. Labels in other locales lose their accents unless drawn in an
+    /// embedded font ("Północ" is drawn as "Pólnoc").
+    pub fn fits_builtin_fonts(&self) -> bool {
+        self.strings()
+            .iter()
+            .all(|s| s.chars().all(|c| c <= '\u{FF}'))
+    }
+}
+
+fn direction_index(direction: Direction) -> usize {
+    match direction {
+        Direction::North => 0,
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_strings() {
+        let locale = Locale::for_language(Language::English);
+        assert_eq!(locale.deals_line(Direction::North), "North Deals");
+        assert_eq!(locale.vulnerability(Vulnerability::EastWest), "E-W");
+        assert_eq!(Locale::label(locale.lead), "Lead: ");
+    }
+
+    #[test]
+    fn test_german_compass_uses_ost() {
+        let locale = Locale::for_language(Language::German);
+        assert_eq!(locale.compass_letter(Direction::East), "O");
+        assert_eq!(locale.deals_line(Direction::South), "Geber Süd");
+    }
+
+    #[test]
+    fn test_builtin_font_coverage() {
+        assert!(Locale::for_language(Language::German).fits_builtin_fonts());
+        assert!(!Locale::for_language(Language::Polish).fits_builtin_fonts());
+    }
+
+    #[test]
+    fn test_board_label_follows_language() {
+        let mut settings = crate::config::Settings {
+            language: Language::Polish,
+            ..Default::default()
+        };
+        assert_eq!(settings.board_label_format(), "Rozdanie %");
+        settings.board_label_format = Some("%)".to_string());
+        assert_eq!(settings.board_label_format(), "%)");
+    }
+}
@@ -1,4 +1,12 @@
 pub mod defaults;
+pub mod locale;
+pub mod overrides;
+#[cfg(feature = "cli")]
+pub mod profiles;
 pub mod settings;
+pub mod template;
 
-pub use settings::Settings;
+pub use locale::Locale;
+pub use overrides::{BoardOverrides, BoardSettings};
+pub use settings::{CallText, Settings};
+pub use template::{DiagramSide, LayoutTemplate};
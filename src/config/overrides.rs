@@ -0,0 +1,183 @@
+//! Per-board settings overrides
+//!
+//! Most of a document shares one [Settings], but a handout sometimes wants a
+//! board drawn differently from the rest: the auction hidden on a quiz board,
+//! the HCP shown on a teaching board, or a different label. A
+//! [BoardOverrides] maps board ids (the [Board] tag) to the settings that
+//! differ for that board; everything left unset keeps the document's value.
+//!
+//! Overrides apply to the layouts that draw whole boards one at a time:
+//! Analysis and the Declarer's Plan layouts.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Settings;
+use crate::cli::HiddenHandStyle;
+use crate::model::Board;
+
+/// Settings that can differ for one board
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BoardSettings {
+    pub show_bidding: Option<bool>,
+    pub show_play: Option<bool>,
+    pub show_commentary: Option<bool>,
+    pub show_hcp: Option<bool>,
+    pub hand_hcp: Option<bool>,
+    pub distribution_points: Option<bool>,
+    pub board_in_compass: Option<bool>,
+    pub vulnerable_compass: Option<bool>,
+    pub lead_marker: Option<bool>,
+    pub show_dd_tricks: Option<bool>,
+    pub show_best_leads: Option<bool>,
+    pub show_auction_prose: Option<bool>,
+    pub circle_sure_winners: Option<bool>,
+    pub circle_promotable_winners: Option<bool>,
+    pub circle_length_winners: Option<bool>,
+    pub hidden_hand_style: Option<HiddenHandStyle>,
+    pub ten_as_t: Option<bool>,
+    /// Board label format, "%" standing for the board number
+    pub board_label_format: Option<String>,
+}
+
+impl BoardSettings {
+    /// Apply the overrides to the settings
+    pub fn apply(&self, settings: &mut Settings) {
+        fn set<T: Copy>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+
+        set(&mut settings.show_bidding, self.show_bidding);
+        set(&mut settings.show_play, self.show_play);
+        set(&mut settings.show_commentary, self.show_commentary);
+        set(&mut settings.show_hcp, self.show_hcp);
+        set(&mut settings.hand_hcp, self.hand_hcp);
+        set(&mut settings.distribution_points, self.distribution_points);
+        set(&mut settings.board_in_compass, self.board_in_compass);
+        set(&mut settings.vulnerable_compass, self.vulnerable_compass);
+        set(&mut settings.lead_marker, self.lead_marker);
+        set(&mut settings.show_dd_tricks, self.show_dd_tricks);
+        set(&mut settings.show_best_leads, self.show_best_leads);
+        set(&mut settings.show_auction_prose, self.show_auction_prose);
+        set(&mut settings.circle_sure_winners, self.circle_sure_winners);
+        set(
+            &mut settings.circle_promotable_winners,
+            self.circle_promotable_winners,
+        );
+        set(
+            &mut settings.circle_length_winners,
+            self.circle_length_winners,
+        );
+        set(&mut settings.hidden_hand_style, self.hidden_hand_style);
+        set(&mut settings.ten_as_t, self.ten_as_t);
+        if let Some(ref format) = self.board_label_format {
+            settings.board_label_format = Some(format.clone());
+        }
+    }
+}
+
+/// Board settings keyed by board id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BoardOverrides(BTreeMap<String, BoardSettings>);
+
+impl BoardOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `settings` for the board with id `board` (its [Board] tag, or
+    /// its number), replacing any overrides it already had
+    pub fn insert(&mut self, board: impl Into<String>, settings: BoardSettings) {
+        self.0.insert(board.into(), settings);
+    }
+
+    /// Builder form of [BoardOverrides::insert]
+    pub fn with(mut self, board: impl Into<String>, settings: BoardSettings) -> Self {
+        self.insert(board, settings);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The overrides for `board`, if it has any
+    pub fn get(&self, board: &Board) -> Option<&BoardSettings> {
+        if let Some(found) = board.board_id.as_ref().and_then(|id| self.0.get(id)) {
+            return Some(found);
+        }
+        board
+            .number
+            .and_then(|number| self.0.get(&number.to_string()))
+    }
+
+    /// `settings` with `board`'s overrides applied; borrowed unchanged when
+    /// the board has none
+    pub fn settings_for<'a>(&self, board: &Board, settings: &'a Settings) -> Cow<'a, Settings> {
+        match self.get(board) {
+            Some(overrides) => {
+                let mut settings = settings.clone();
+                overrides.apply(&mut settings);
+                Cow::Owned(settings)
+            }
+            None => Cow::Borrowed(settings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(id: &str) -> Board {
+        Board {
+            board_id: Some(id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_settings_for_board() {
+        let overrides = BoardOverrides::new().with(
+            "3",
+            BoardSettings {
+                show_bidding: Some(false),
+                board_label_format: Some("Quiz %".to_string()),
+                ..Default::default()
+            },
+        );
+        let settings = Settings::default();
+
+        let quiz = overrides.settings_for(&board("3"), &settings);
+        assert!(!quiz.show_bidding);
+        assert_eq!(quiz.board_label_format(), "Quiz %");
+        assert_eq!(quiz.show_commentary, settings.show_commentary);
+
+        let other = overrides.settings_for(&board("4"), &settings);
+        assert!(matches!(other, Cow::Borrowed(_)));
+        assert!(other.show_bidding);
+    }
+
+    #[test]
+    fn test_board_number_key() {
+        let overrides = BoardOverrides::new().with(
+            "7",
+            BoardSettings {
+                show_hcp: Some(true),
+                ..Default::default()
+            },
+        );
+        let numbered = Board {
+            number: Some(7),
+            ..Default::default()
+        };
+        assert!(overrides.get(&numbered).is_some());
+        assert!(overrides.get(&Board::default()).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//! Named profiles in the config file (`--profile`)
+//!
+//! A profile bundles command-line options for a recurring job. The config file
+//! is TOML with one table per profile, keyed by long option names:
+//!
+//! ```toml
+//! [profile.club-handrecord]
+//! boards-per-page = 2
+//! hand-hcp = true
+//! header = "{event}||{date}"
+//! page-numbers = true
+//!
+//! [profile.lesson]
+//! layout = "bidding-sheets"
+//! margins = "wide"
+//! ```
+//!
+//! The profile is turned back into options (`--boards-per-page=2 --hand-hcp`)
+//! placed before the real command line, so clap validates them and options given
+//! on the command line win.
+
+use std::path::PathBuf;
+
+use crate::error::ConfigError;
+
+/// Config file name looked up in the current directory
+pub const CONFIG_FILE_NAME: &str = "pbn-to-pdf.toml";
+
+/// Options that can't be set from a profile
+const RESERVED_KEYS: &[&str] = &["profile", "config"];
+
+/// Find the config file: `./pbn-to-pdf.toml`, then `~/.config/pbn-to-pdf/config.toml`
+pub fn find_config_file() -> Option<PathBuf> {
+    let local = PathBuf::from(CONFIG_FILE_NAME);
+    if local.is_file() {
+        return Some(local);
+    }
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    let user = config_dir.join("pbn-to-pdf").join("config.toml");
+    user.is_file().then_some(user)
+}
+
+/// Command-line options for the named profile in config file text
+pub fn profile_args(config: &str, name: &str) -> Result<Vec<String>, ConfigError> {
+    let table: toml::Table =
+        toml::from_str(config).map_err(|e| ConfigError::InvalidConfig(e.to_string()))?;
+    let profile = table
+        .get("profile")
+        .and_then(|profiles| profiles.get(name))
+        .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))?
+        .as_table()
+        .ok_or_else(|| ConfigError::InvalidConfig(format!("profile.{} is not a table", name)))?;
+
+    let mut args = Vec::new();
+    for (key, value) in profile {
+        let option = key.replace('_', "-");
+        if RESERVED_KEYS.contains(&option.as_str()) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "profile.{}: \"{}\" can't be set in a profile",
+                name, key
+            )));
+        }
+        match value {
+            toml::Value::Boolean(true) => args.push(format!("--{}", option)),
+            toml::Value::Boolean(false) => {}
+            toml::Value::String(s) => args.push(format!("--{}={}", option, s)),
+            toml::Value::Integer(n) => args.push(format!("--{}={}", option, n)),
+            toml::Value::Float(n) => args.push(format!("--{}={}", option, n)),
+            _ => {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "profile.{}.{}: expected a string, number or boolean",
+                    name, key
+                )))
+            }
+        }
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [profile.club-handrecord]
+        boards-per-page = 2
+        hand_hcp = true
+        no-play = false
+        header = "{event}||{date}"
+
+        [profile.lesson]
+        layout = "bidding-sheets"
+    "#;
+
+    #[test]
+    fn test_profile_becomes_options() {
+        let args = profile_args(CONFIG, "club-handrecord").unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--boards-per-page=2",
+                "--hand-hcp",
+                "--header={event}||{date}"
+            ]
+        );
+        assert_eq!(
+            profile_args(CONFIG, "lesson").unwrap(),
+            vec!["--layout=bidding-sheets"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_profile_and_bad_values() {
+        assert!(matches!(
+            profile_args(CONFIG, "missing"),
+            Err(ConfigError::UnknownProfile(_))
+        ));
+        assert!(profile_args("[profile.x]\nboards = [1, 2]", "x").is_err());
+        assert!(profile_args("[profile.x]\nprofile = \"y\"", "x").is_err());
+    }
+}
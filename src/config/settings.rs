@@ -1,8 +1,28 @@
-use crate::cli::{Args, Layout, MarginPreset};
-use crate::model::{FontSettings, PbnMetadata};
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::locale::Locale;
+use super::overrides::BoardOverrides;
+use super::template::DiagramSide;
+#[cfg(feature = "cli")]
+use crate::cli::Args;
+use crate::cli::{
+    AuctionHeaders, ColorTheme, CompressionLevel, Duplex, FontEmbedding, HiddenHandStyle, Language,
+    Layout, SuitStyle, TitleBlock,
+};
+use crate::model::metadata::{ColorSettings, Margins};
+use crate::model::{Board, FontSettings, PbnMetadata};
 
 use super::defaults::*;
 
+/// Separator between the club, site and date in the derived subtitle
+const SUBTITLE_SEPARATOR: &str = "  \u{2022}  ";
+
+/// Separator between the title and subtitle in a banner
+const BANNER_TITLE_SEPARATOR: &str = " \u{2013} ";
+
 /// Standard margin for bidding sheets (1/2 inch)
 const BIDDING_SHEETS_MARGIN: f32 = 12.7;
 
@@ -10,8 +30,135 @@ const BIDDING_SHEETS_MARGIN: f32 = 12.7;
 const DECLARERS_PLAN_MARGIN_LR: f32 = 12.7; // 1/2 inch left/right
 const DECLARERS_PLAN_MARGIN_TB: f32 = 25.4; // 1 inch top/bottom
 
+/// Four-color deck: black spades, red hearts, orange diamonds, green clubs
+pub(crate) const FOUR_COLOR_DECK: ColorSettings = ColorSettings {
+    spades: (0, 0, 0),
+    hearts: (204, 0, 0),
+    diamonds: (255, 128, 0),
+    clubs: (0, 128, 0),
+};
+
+/// Suit colors bundled with a theme (None keeps the default or %PipColors colors)
+#[cfg(feature = "cli")]
+fn theme_suit_colors(theme: ColorTheme) -> Option<ColorSettings> {
+    match theme {
+        ColorTheme::Classic | ColorTheme::ClubBranded => None,
+        ColorTheme::HighContrast => Some(ColorSettings {
+            spades: (0, 0, 0),
+            hearts: (230, 0, 0),
+            diamonds: (230, 0, 0),
+            clubs: (0, 0, 0),
+        }),
+        ColorTheme::Pastel => Some(ColorSettings {
+            spades: (60, 60, 90),
+            hearts: (214, 90, 110),
+            diamonds: (214, 90, 110),
+            clubs: (60, 60, 90),
+        }),
+    }
+}
+
+/// Where a document's pages fall in a longer one rendered in chunks (see
+/// `Settings::chunk_size`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunk {
+    /// Number of the chunk's first page in the whole document
+    pub first_page: usize,
+    /// Pages in the whole document
+    pub total_pages: usize,
+    /// Whether this is the document's last chunk
+    pub last: bool,
+}
+
+/// Left, center and right fields of a page header or footer
+///
+/// Each field is a template; see `render::components::header_footer` for the
+/// variables it can use.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageFields {
+    pub left: String,
+    pub center: String,
+    pub right: String,
+}
+
+impl PageFields {
+    /// Parse a "left|center|right" spec; missing trailing fields are empty
+    pub fn parse(spec: &str) -> Self {
+        let mut parts = spec.splitn(3, '|').map(|s| s.trim().to_string());
+        Self {
+            left: parts.next().unwrap_or_default(),
+            center: parts.next().unwrap_or_default(),
+            right: parts.next().unwrap_or_default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.center.is_empty() && self.right.is_empty()
+    }
+}
+
+/// Custom font files for each kind of text
+///
+/// Each list holds up to four files: regular, bold, italic, bold-italic.
+/// An empty list keeps the builtin fonts chosen from the PBN font settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomFonts {
+    pub diagram: Vec<PathBuf>,
+    pub commentary: Vec<PathBuf>,
+    pub hand_record: Vec<PathBuf>,
+}
+
+impl CustomFonts {
+    /// Parse a comma-separated list of font file paths
+    pub fn parse_paths(spec: &str) -> Vec<PathBuf> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    }
+}
+
+/// How calls and auction endings are written in auctions
+///
+/// Unset strings fall back to the `Locale` ("Pass", "Dbl", "All Pass", ...),
+/// except that bidding sheets write doubles as "X" and "XX" by default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CallText {
+    pub pass: Option<String>,
+    pub double: Option<String>,
+    pub redouble: Option<String>,
+    pub all_pass: Option<String>,
+    pub passed_out: Option<String>,
+}
+
+/// Suit colors from --suit-colors, the four-color preset from --four-color,
+/// or the colors of the --theme
+#[cfg(feature = "cli")]
+fn suit_colors_from_args(args: &Args) -> Option<ColorSettings> {
+    args.suit_colors
+        .clone()
+        .or_else(|| args.four_color.then_some(FOUR_COLOR_DECK))
+        .or_else(|| theme_suit_colors(args.theme))
+}
+
+/// Footer from --footer, with --page-numbers filling an empty center field
+#[cfg(feature = "cli")]
+fn footer_from_args(args: &Args) -> PageFields {
+    let mut footer = args
+        .footer
+        .as_deref()
+        .map(PageFields::parse)
+        .unwrap_or_default();
+    if args.page_numbers && footer.center.is_empty() {
+        footer.center = "Page {page} of {pages}".to_string();
+    }
+    footer
+}
+
 /// Runtime settings for PDF generation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     // Page dimensions
     pub page_width: f32,
@@ -22,9 +169,17 @@ pub struct Settings {
     pub margin_left: f32,
     pub margin_right: f32,
     pub boards_per_page: u8,
+    /// Double-sided printing; None keeps each layout's usual page order
+    pub duplex: Option<Duplex>,
+    /// Render this many boards at a time and join the PDFs, so only one
+    /// chunk's pages are held in memory
+    pub chunk_size: Option<usize>,
+    /// Set while rendering one chunk of a longer document
+    #[serde(skip)]
+    pub chunk: Option<Chunk>,
 
     // CLI margin override (if specified)
-    margin_preset: Option<MarginPreset>,
+    margins_override: Option<Margins>,
 
     // Layout style
     pub layout: Layout,
@@ -34,6 +189,24 @@ pub struct Settings {
     pub show_play: bool,
     pub show_commentary: bool,
     pub show_hcp: bool,
+    /// Print each hand's HCP in the compass corners
+    pub hand_hcp: bool,
+    /// Include length points in the per-hand HCP
+    pub distribution_points: bool,
+    /// Print the board number and dealer mark in the compass instead of the title
+    pub board_in_compass: bool,
+    /// Shade the compass arms by vulnerability
+    pub vulnerable_compass: bool,
+    /// Underline the opening lead in the leader's hand
+    pub lead_marker: bool,
+    /// Draw East and West rotated in full-deal diagrams
+    pub rotate_east_west: bool,
+    /// Show the double-dummy makeable tricks table in diagrams
+    pub show_dd_tricks: bool,
+    /// Show the double-dummy best opening leads below the contract
+    pub show_best_leads: bool,
+    /// Describe the auction in words below the bidding table
+    pub show_auction_prose: bool,
     pub justify: bool,
     pub debug_boxes: bool,
     /// Circle sure winners on declarer's plan layouts
@@ -42,22 +215,76 @@ pub struct Settings {
     pub circle_promotable_winners: bool,
     /// Circle length winners on declarer's plan layouts
     pub circle_length_winners: bool,
+    /// Emit PDF form text fields over fill-in blanks (`_` calls)
+    pub fillable_forms: bool,
+    /// Draw answers on an optional content group that viewers can hide
+    pub answers_layer: bool,
+    /// Color theme for compass, banners, separators and debug boxes
+    pub theme: ColorTheme,
+    /// Convert all colors to grays and lighten dark fills (ink saver)
+    pub grayscale: bool,
+    /// How hidden hands are shown in diagrams
+    pub hidden_hand_style: HiddenHandStyle,
+    /// Draw suits as symbols or letters
+    pub suit_style: SuitStyle,
+    /// Subset, fully embed, or avoid font files
+    pub font_embedding: FontEmbedding,
+    /// How hard streams are compressed, if at all
+    pub compression: CompressionLevel,
+    /// What the auction column headers show
+    pub auction_headers: AuctionHeaders,
+    /// Rows of calls after which a long auction continues in a block to the right
+    pub auction_max_rows: Option<usize>,
+    /// Hyphenate long words when wrapping commentary
+    pub hyphenate: bool,
+    /// Show tens as "T" instead of "10"
+    pub ten_as_t: bool,
+    /// Link commentary card references to the card in the diagram
+    pub link_card_refs: bool,
+    /// Draw a QR code per board encoding its BBO handviewer URL
+    pub show_qr_codes: bool,
+    /// QR code size in mm (square)
+    pub qr_code_size: f32,
+    /// Header line drawn in the top margin of every page
+    pub header: PageFields,
+    /// Footer line drawn in the bottom margin of every page
+    pub footer: PageFields,
     /// Multi-column layout mode (1 = single column, 2+ = multi-column)
     pub column_count: u8,
     /// Two-column auctions mode (show uncontested auctions in 2 columns)
     pub two_col_auctions: bool,
     /// Center layout mode (commentary first, board info centered below)
     pub center: bool,
+    /// Side of the page the deal is drawn on (one board per page), from --template
+    pub diagram_side: DiagramSide,
 
     /// Title override from CLI (None = use metadata, Some("") = hide, Some(x) = use x)
     pub title_override: Option<String>,
     /// Title from metadata (HRTitleEvent)
     pub title_from_metadata: Option<String>,
+    /// Logo drawn at the left of the header line (--logo or %HRTitleLogo)
+    pub logo: Option<PathBuf>,
+    /// Letterhead PDF or image drawn behind every page
+    pub background: Option<PathBuf>,
+    /// Pixels per inch bitmap logos and backgrounds are downsampled to
+    pub image_dpi: Option<u32>,
+    /// Copyright override from CLI (None = use metadata, Some("") = hide, Some(x) = use x)
+    pub copyright_override: Option<String>,
+    /// Copyright line from metadata (%HRCopyright)
+    pub copyright_from_metadata: Option<String>,
+    /// Where the %HRTitle block is printed
+    pub title_block: TitleBlock,
+    /// Second line of the title block: club, site and date from metadata
+    pub title_details: Vec<String>,
+    /// Subtitle override from CLI (None = use title_details, Some("") = hide, Some(x) = use x)
+    pub subtitle_override: Option<String>,
 
     /// Board label format from %Translate directive
     /// Format string where "%" is replaced with the board number
-    /// Default is "Board %" -> "Board 1", can be "%)" -> "1)"
-    pub board_label_format: String,
+    /// None uses the language's label ("Board %" -> "Board 1"); can be "%)" -> "1)"
+    pub board_label_format: Option<String>,
+    /// Language for generated labels
+    pub language: Language,
 
     // Layout dimensions (in mm)
     pub hand_width: f32,
@@ -75,14 +302,29 @@ pub struct Settings {
 
     // Font specifications from PBN (for font family selection)
     pub fonts: FontSettings,
+    /// Custom font files that replace the builtin fonts
+    pub custom_fonts: CustomFonts,
 
     // Bidding table
     pub bid_column_width: f32,
     pub bid_row_height: f32,
+    /// Wording for Pass/Double/Redouble and "All Pass"/"Passed Out"
+    pub call_text: CallText,
 
     // Colors (RGB 0.0-1.0)
+    /// Spade color (and black text in two-color decks)
     pub black_color: (f32, f32, f32),
+    /// Heart color
     pub red_color: (f32, f32, f32),
+    /// Diamond color (same as hearts unless using a four-color deck)
+    pub diamond_color: (f32, f32, f32),
+    /// Club color (same as spades unless using a four-color deck)
+    pub club_color: (f32, f32, f32),
+    // CLI suit color override (if specified), wins over %PipColors
+    suit_colors_override: Option<ColorSettings>,
+
+    /// Settings that differ for particular boards
+    pub board_overrides: BoardOverrides,
 }
 
 impl Default for Settings {
@@ -96,7 +338,10 @@ impl Default for Settings {
             margin_left: DEFAULT_PAGE_MARGIN,
             margin_right: DEFAULT_PAGE_MARGIN,
             boards_per_page: 1,
-            margin_preset: None,
+            duplex: None,
+            chunk_size: None,
+            chunk: None,
+            margins_override: None,
 
             layout: Layout::Analysis,
 
@@ -104,17 +349,53 @@ impl Default for Settings {
             show_play: true,
             show_commentary: true,
             show_hcp: false,
+            hand_hcp: false,
+            distribution_points: false,
+            board_in_compass: false,
+            vulnerable_compass: false,
+            lead_marker: false,
+            rotate_east_west: false,
+            show_dd_tricks: false,
+            show_best_leads: false,
+            show_auction_prose: false,
             justify: false,
             debug_boxes: false,
             circle_sure_winners: false,
             circle_promotable_winners: false,
             circle_length_winners: false,
+            fillable_forms: false,
+            answers_layer: false,
+            theme: ColorTheme::Classic,
+            grayscale: false,
+            hidden_hand_style: HiddenHandStyle::Omit,
+            suit_style: SuitStyle::Symbols,
+            font_embedding: FontEmbedding::Subset,
+            compression: CompressionLevel::Best,
+            auction_headers: AuctionHeaders::Directions,
+            auction_max_rows: None,
+            hyphenate: false,
+            ten_as_t: false,
+            link_card_refs: false,
+            show_qr_codes: false,
+            qr_code_size: 20.0,
+            header: PageFields::default(),
+            footer: PageFields::default(),
             column_count: 1,
             two_col_auctions: false,
             center: false,
+            diagram_side: DiagramSide::Left,
             title_override: None,
             title_from_metadata: None,
-            board_label_format: "Board %".to_string(),
+            logo: None,
+            background: None,
+            image_dpi: None,
+            copyright_override: None,
+            copyright_from_metadata: None,
+            title_block: TitleBlock::None,
+            title_details: Vec::new(),
+            subtitle_override: None,
+            board_label_format: None,
+            language: Language::English,
 
             hand_width: DEFAULT_HAND_WIDTH,
             hand_height: DEFAULT_HAND_HEIGHT,
@@ -129,57 +410,215 @@ impl Default for Settings {
             commentary_font_size: 12.0, // Commentary default
 
             fonts: FontSettings::default(),
+            custom_fonts: CustomFonts::default(),
 
             bid_column_width: DEFAULT_BID_COLUMN_WIDTH,
             bid_row_height: DEFAULT_BID_ROW_HEIGHT,
+            call_text: CallText::default(),
 
             black_color: BLACK_SUIT_COLOR,
             red_color: RED_SUIT_COLOR,
+            diamond_color: RED_SUIT_COLOR,
+            club_color: BLACK_SUIT_COLOR,
+            suit_colors_override: None,
+
+            board_overrides: BoardOverrides::default(),
         }
     }
 }
 
 impl Settings {
     /// Create settings from CLI arguments
+    #[cfg(feature = "cli")]
     pub fn from_args(args: &Args) -> Self {
+        Self::from_args_for(args, args.layout.primary())
+    }
+
+    /// Create settings from CLI arguments for one layout of `--layout all`
+    #[cfg(feature = "cli")]
+    pub fn from_args_for(args: &Args, layout: Layout) -> Self {
         let (page_width, page_height) = args.page_dimensions();
 
         // Determine initial margins based on layout and CLI override
-        let (margin_lr, margin_tb) = if let Some(preset) = args.margins {
-            let m = preset.size_mm();
-            (m, m)
-        } else if args.layout == Layout::BiddingSheets {
-            // Bidding sheets use standard margins by default
-            (BIDDING_SHEETS_MARGIN, BIDDING_SHEETS_MARGIN)
-        } else if args.layout.is_declarers_plan() {
-            // Declarer's plan uses 0.5" left/right, 1.0" top/bottom
-            (DECLARERS_PLAN_MARGIN_LR, DECLARERS_PLAN_MARGIN_TB)
-        } else {
-            (DEFAULT_PAGE_MARGIN, DEFAULT_PAGE_MARGIN)
-        };
+        let margins = args.margins.unwrap_or_else(|| {
+            let (margin_lr, margin_tb) = if layout == Layout::BiddingSheets {
+                // Bidding sheets use standard margins by default
+                (BIDDING_SHEETS_MARGIN, BIDDING_SHEETS_MARGIN)
+            } else if layout.is_declarers_plan() {
+                // Declarer's plan uses 0.5" left/right, 1.0" top/bottom
+                (DECLARERS_PLAN_MARGIN_LR, DECLARERS_PLAN_MARGIN_TB)
+            } else {
+                (DEFAULT_PAGE_MARGIN, DEFAULT_PAGE_MARGIN)
+            };
+            Margins {
+                left: margin_lr,
+                right: margin_lr,
+                top: margin_tb,
+                bottom: margin_tb,
+            }
+        });
 
-        Self {
+        let mut settings = Self {
             page_width,
             page_height,
-            margin: margin_lr,
-            margin_top: margin_tb,
-            margin_bottom: margin_tb,
-            margin_left: margin_lr,
-            margin_right: margin_lr,
+            margin: margins.left,
+            margin_top: margins.top,
+            margin_bottom: margins.bottom,
+            margin_left: margins.left,
+            margin_right: margins.right,
             boards_per_page: args.boards_per_page,
-            margin_preset: args.margins,
-            layout: args.layout,
+            duplex: args.duplex,
+            chunk_size: args.chunk_size.map(|n| n as usize),
+            chunk: None,
+            margins_override: args.margins,
+            layout,
             show_bidding: args.show_bidding(),
             show_play: args.show_play(),
             show_commentary: args.show_commentary(),
             show_hcp: args.show_hcp(),
+            hand_hcp: args.hand_hcp,
+            distribution_points: args.distribution_points,
+            board_in_compass: args.board_in_compass,
+            vulnerable_compass: args.vulnerable_compass,
+            lead_marker: args.lead_marker,
+            rotate_east_west: args.rotate_east_west,
+            show_dd_tricks: args.dd_tricks,
+            show_best_leads: args.best_leads,
+            show_auction_prose: args.auction_prose,
             debug_boxes: args.debug_boxes,
             circle_sure_winners: args.circle_sure_winners,
             circle_promotable_winners: args.circle_promotable_winners,
             circle_length_winners: args.circle_length_winners,
+            fillable_forms: args.fillable,
+            answers_layer: args.answers_layer,
+            suit_colors_override: suit_colors_from_args(args),
+            theme: args.theme,
+            grayscale: args.grayscale,
+            hidden_hand_style: args.hidden_hands,
+            suit_style: args.suit_style,
+            font_embedding: args.font_embedding,
+            compression: args.compress,
+            auction_headers: args.auction_headers,
+            auction_max_rows: args.auction_max_rows,
+            hyphenate: args.hyphenate,
+            ten_as_t: args.ten_as_t,
+            link_card_refs: args.link_card_refs,
+            show_qr_codes: args.qr_codes,
+            header: args
+                .header
+                .as_deref()
+                .map(PageFields::parse)
+                .unwrap_or_default(),
+            footer: footer_from_args(args),
             title_override: args.title.clone(),
+            subtitle_override: args.subtitle.clone(),
+            logo: args.logo.clone(),
+            background: args.background.clone(),
+            image_dpi: args.image_dpi,
+            copyright_override: args.copyright.clone(),
+            title_block: args.title_block,
+            language: args.language,
+            custom_fonts: CustomFonts {
+                diagram: args
+                    .font_diagram
+                    .as_deref()
+                    .map(CustomFonts::parse_paths)
+                    .unwrap_or_default(),
+                commentary: args
+                    .font_commentary
+                    .as_deref()
+                    .map(CustomFonts::parse_paths)
+                    .unwrap_or_default(),
+                hand_record: args
+                    .font_hand_record
+                    .as_deref()
+                    .map(CustomFonts::parse_paths)
+                    .unwrap_or_default(),
+            },
+            call_text: CallText {
+                pass: args.pass_text.clone(),
+                double: args.double_text.clone(),
+                redouble: args.redouble_text.clone(),
+                all_pass: args.all_pass_text.clone(),
+                passed_out: args.passed_out_text.clone(),
+            },
             ..Default::default()
+        };
+        if let Some(colors) = settings.suit_colors_override {
+            settings.apply_suit_colors(&colors);
         }
+        settings.margin_top += settings.title_block_height();
+        settings
+    }
+
+    /// Set all four suit colors
+    pub fn apply_suit_colors(&mut self, colors: &ColorSettings) {
+        let scale =
+            |(r, g, b): (u8, u8, u8)| (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        self.black_color = scale(colors.spades);
+        self.red_color = scale(colors.hearts);
+        self.diamond_color = scale(colors.diamonds);
+        self.club_color = scale(colors.clubs);
+    }
+
+    /// Strings for generated labels in the selected language
+    pub fn locale(&self) -> &'static Locale {
+        Locale::for_language(self.language)
+    }
+
+    /// Board label template: the %Translate format, or the language's own
+    pub fn board_label_format(&self) -> &str {
+        self.board_label_format
+            .as_deref()
+            .unwrap_or(self.locale().board_label)
+    }
+
+    /// How Pass is written in auctions
+    pub fn pass_text(&self) -> &str {
+        self.call_text.pass.as_deref().unwrap_or(self.locale().pass)
+    }
+
+    /// How Double is written in auctions
+    pub fn double_text(&self) -> &str {
+        self.call_text
+            .double
+            .as_deref()
+            .unwrap_or(self.locale().double)
+    }
+
+    /// How Redouble is written in auctions
+    pub fn redouble_text(&self) -> &str {
+        self.call_text
+            .redouble
+            .as_deref()
+            .unwrap_or(self.locale().redouble)
+    }
+
+    /// Ending shown in place of the final three passes
+    pub fn all_pass_text(&self) -> &str {
+        self.call_text
+            .all_pass
+            .as_deref()
+            .unwrap_or(self.locale().all_pass)
+    }
+
+    /// Shown in place of a passed-out auction
+    pub fn passed_out_text(&self) -> &str {
+        self.call_text
+            .passed_out
+            .as_deref()
+            .unwrap_or(self.locale().passed_out)
+    }
+
+    /// Number of the first page drawn: 1, or later for a chunk of a longer
+    /// document
+    pub fn first_page(&self) -> usize {
+        self.chunk.map_or(1, |chunk| chunk.first_page)
+    }
+
+    /// The settings for drawing `board`, with its overrides applied
+    pub fn for_board(&self, board: &Board) -> Cow<'_, Settings> {
+        self.board_overrides.settings_for(board, self)
     }
 
     /// Create settings for a specific layout with appropriate defaults
@@ -213,9 +652,9 @@ impl Settings {
         // Apply PBN margins only if:
         // 1. No CLI margin override was specified, AND
         // 2. Layout is Analysis (bidding sheets and declarer's plan ignore embedded margins)
-        if self.margin_preset.is_none() && self.layout == Layout::Analysis {
+        if self.margins_override.is_none() && self.layout == Layout::Analysis {
             if let Some(ref margins) = metadata.layout.margins {
-                self.margin_top = margins.top;
+                self.margin_top = margins.top + self.title_block_height();
                 self.margin_bottom = margins.bottom;
                 self.margin_left = margins.left;
                 self.margin_right = margins.right;
@@ -230,18 +669,9 @@ impl Settings {
         self.compass_font_size = metadata.fonts.card_table_size();
         self.commentary_font_size = metadata.fonts.commentary_size();
 
-        // Apply colors from metadata
-        let scale = |v: u8| v as f32 / 255.0;
-        self.black_color = (
-            scale(metadata.colors.spades.0),
-            scale(metadata.colors.spades.1),
-            scale(metadata.colors.spades.2),
-        );
-        self.red_color = (
-            scale(metadata.colors.hearts.0),
-            scale(metadata.colors.hearts.1),
-            scale(metadata.colors.hearts.2),
-        );
+        // Apply colors from metadata, unless overridden on the command line
+        let colors = self.suit_colors_override.unwrap_or(metadata.colors);
+        self.apply_suit_colors(&colors);
 
         // Store font settings for font family selection
         self.fonts = metadata.fonts.clone();
@@ -249,6 +679,7 @@ impl Settings {
         // Apply display options from PBN metadata
         if metadata.layout.show_hcp {
             self.show_hcp = true;
+            self.hand_hcp = true;
         }
         if metadata.layout.justify {
             self.justify = true;
@@ -265,10 +696,22 @@ impl Settings {
 
         // Store title from metadata (HRTitleEvent)
         self.title_from_metadata = metadata.title_event.clone();
+        self.copyright_from_metadata = metadata.copyright.clone();
+        // Bridge Composer writes "" or 0 for unset title fields
+        self.title_details = [
+            &metadata.title_club,
+            &metadata.title_site,
+            &metadata.title_date,
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|value| !value.is_empty() && value.as_str() != "0")
+        .cloned()
+        .collect();
 
         // Apply board label format from %Translate directive
         if let Some(ref fmt) = metadata.layout.board_label_format {
-            self.board_label_format = fmt.clone();
+            self.board_label_format = Some(fmt.clone());
         }
 
         self
@@ -284,6 +727,48 @@ impl Settings {
         }
     }
 
+    /// Get the subtitle: --subtitle, or the club, site and date from metadata
+    /// (same rules as `effective_title`)
+    pub fn effective_subtitle(&self) -> Option<String> {
+        let subtitle = match &self.subtitle_override {
+            Some(s) => s.clone(),
+            None => self.title_details.join(SUBTITLE_SEPARATOR),
+        };
+        Some(subtitle).filter(|s| !s.is_empty())
+    }
+
+    /// Title and subtitle on one line, as shown in banners
+    pub fn banner_title(&self) -> Option<String> {
+        match (self.effective_title(), self.subtitle_override.as_deref()) {
+            (Some(title), Some(subtitle)) if !subtitle.is_empty() => {
+                Some(format!("{}{}{}", title, BANNER_TITLE_SEPARATOR, subtitle))
+            }
+            (Some(title), _) => Some(title.to_string()),
+            (None, Some(subtitle)) if !subtitle.is_empty() => Some(subtitle.to_string()),
+            (None, _) => None,
+        }
+    }
+
+    /// Get the copyright line to print, if any (same rules as `effective_title`)
+    pub fn effective_copyright(&self) -> Option<&str> {
+        let copyright = match &self.copyright_override {
+            Some(c) => Some(c.as_str()),
+            None => self.copyright_from_metadata.as_deref(),
+        };
+        copyright.filter(|c| !c.is_empty())
+    }
+
+    /// Space reserved above the content for the %HRTitle block
+    ///
+    /// Reserved on every page even with `--title-block first`, so boards
+    /// start at the same height throughout the document.
+    pub fn title_block_height(&self) -> f32 {
+        match self.title_block {
+            TitleBlock::None => 0.0,
+            TitleBlock::First | TitleBlock::Every => TITLE_BLOCK_HEIGHT,
+        }
+    }
+
     /// Get the usable content area width
     pub fn content_width(&self) -> f32 {
         self.page_width - self.margin_left - self.margin_right
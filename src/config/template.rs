@@ -0,0 +1,202 @@
+//! Layout template files (`--template`)
+//!
+//! A template is a TOML file that adjusts the Analysis layout's geometry
+//! without recompiling: page margins and columns, the size of the hand diagram
+//! and auction grid, font sizes, and which side of the page the diagram sits
+//! on. Every key is optional; anything left out keeps its usual value.
+//!
+//! ```toml
+//! line_height = 4.5
+//!
+//! [page]
+//! margins = { top = 12.0, bottom = 12.0, left = 15.0, right = 15.0 }
+//! columns = 2
+//!
+//! [diagram]
+//! side = "right"
+//! hand_width = 40.0
+//!
+//! [auction]
+//! column_width = 18.0
+//!
+//! [fonts]
+//! commentary = 11.0
+//! ```
+//!
+//! A template is applied after the PBN header directives, so it overrides them.
+
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::Settings;
+use crate::error::ConfigError;
+
+/// Which side of the page the deal (title, diagram, auction) is drawn on in
+/// the one-board-per-page Analysis layout; commentary floats on the other side
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramSide {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Geometry overrides read from a template file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutTemplate {
+    /// Baseline-to-baseline distance for diagram, title and commentary lines in mm
+    pub line_height: Option<f32>,
+    pub page: PageTemplate,
+    pub diagram: DiagramTemplate,
+    pub auction: AuctionTemplate,
+    pub fonts: FontTemplate,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PageTemplate {
+    pub margins: Option<MarginTemplate>,
+    /// Number of board columns (1 = one board per page)
+    pub columns: Option<u8>,
+}
+
+/// Page margins in mm
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarginTemplate {
+    pub top: f32,
+    pub bottom: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiagramTemplate {
+    pub side: Option<DiagramSide>,
+    pub hand_width: Option<f32>,
+    pub hand_height: Option<f32>,
+    pub compass_gap: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuctionTemplate {
+    pub column_width: Option<f32>,
+    pub row_height: Option<f32>,
+}
+
+/// Font sizes in points
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FontTemplate {
+    pub title: Option<f32>,
+    pub body: Option<f32>,
+    pub cards: Option<f32>,
+    pub compass: Option<f32>,
+    pub commentary: Option<f32>,
+}
+
+impl LayoutTemplate {
+    /// Read a template file
+    #[cfg(feature = "fs")]
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::InvalidTemplate(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text)
+            .map_err(|e| ConfigError::InvalidTemplate(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse template text
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        toml::from_str(text).map_err(|e| ConfigError::InvalidTemplate(e.to_string()))
+    }
+
+    /// Apply the template's overrides to the settings
+    pub fn apply(&self, settings: &mut Settings) {
+        fn set<T: Copy>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+
+        set(&mut settings.line_height, self.line_height);
+
+        if let Some(margins) = self.page.margins {
+            // The title block lives in the top margin, so keep its space
+            settings.margin_top = margins.top + settings.title_block_height();
+            settings.margin_bottom = margins.bottom;
+            settings.margin_left = margins.left;
+            settings.margin_right = margins.right;
+            settings.margin = margins.left;
+        }
+        set(
+            &mut settings.column_count,
+            self.page.columns.map(|c| c.max(1)),
+        );
+
+        set(&mut settings.diagram_side, self.diagram.side);
+        set(&mut settings.hand_width, self.diagram.hand_width);
+        set(&mut settings.hand_height, self.diagram.hand_height);
+        set(&mut settings.compass_gap, self.diagram.compass_gap);
+
+        set(&mut settings.bid_column_width, self.auction.column_width);
+        set(&mut settings.bid_row_height, self.auction.row_height);
+
+        set(&mut settings.title_font_size, self.fonts.title);
+        set(&mut settings.body_font_size, self.fonts.body);
+        set(&mut settings.card_font_size, self.fonts.cards);
+        set(&mut settings.compass_font_size, self.fonts.compass);
+        set(&mut settings.commentary_font_size, self.fonts.commentary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_apply_template() {
+        let template = LayoutTemplate::parse(
+            r#"
+            line_height = 4.5
+
+            [page]
+            margins = { top = 10.0, bottom = 11.0, left = 12.0, right = 13.0 }
+
+            [diagram]
+            side = "right"
+            hand_width = 40.0
+
+            [fonts]
+            commentary = 10.5
+            "#,
+        )
+        .unwrap();
+
+        let mut settings = Settings::default();
+        let bid_column_width = settings.bid_column_width;
+        template.apply(&mut settings);
+
+        assert_eq!(settings.line_height, 4.5);
+        assert_eq!(settings.margin_top, 10.0);
+        assert_eq!(settings.margin_right, 13.0);
+        assert_eq!(settings.diagram_side, DiagramSide::Right);
+        assert_eq!(settings.hand_width, 40.0);
+        assert_eq!(settings.commentary_font_size, 10.5);
+        assert_eq!(settings.bid_column_width, bid_column_width);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(LayoutTemplate::parse("[diagram]\nsides = \"right\"").is_err());
+        assert!(LayoutTemplate::parse("[diagram]\nside = \"top\"").is_err());
+        assert_eq!(
+            LayoutTemplate::parse("").unwrap(),
+            LayoutTemplate::default()
+        );
+    }
+}
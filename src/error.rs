@@ -1,27 +1,147 @@
+use std::fmt;
+
 use thiserror::Error;
 
+/// Where in the input a problem is: a 1-based line and column, the length of
+/// the offending text, and the whole line it is on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub snippet: String,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, length: usize, snippet: impl Into<String>) -> Self {
+        Self {
+            line,
+            column: column.max(1),
+            length: length.max(1),
+            snippet: snippet.into(),
+        }
+    }
+
+    /// The span of `part` in `text`, line `line` of the input; the whole
+    /// line if `part` isn't in it
+    pub fn of(line: usize, text: &str, part: &str) -> Self {
+        match text.find(part) {
+            Some(offset) => Self::new(
+                line,
+                text[..offset].chars().count() + 1,
+                part.chars().count(),
+                text,
+            ),
+            None => Self::new(line, 1, text.chars().count(), text),
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    /// The line with carets under the offending text:
+    ///
+    /// ```text
+    ///   |
+    /// 3 | 1NT 2Q = Stayman
+    ///   |     ^^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Keep tabs so the carets line up with the text above them
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.snippet)?;
+        write!(f, "{} | {}{}", gutter, indent, "^".repeat(self.length))
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum PbnError {
-    #[error("Invalid tag pair at line {line}: {message}")]
-    InvalidTagPair { line: usize, message: String },
+    #[error("line {}: invalid tag pair: {message}", .span.line)]
+    InvalidTagPair { message: String, span: Span },
+
+    #[error("line {}: invalid deal notation: {message}", .span.line)]
+    InvalidDeal { message: String, span: Span },
+
+    #[error("line {}: invalid card: {message}", .span.line)]
+    InvalidCard { message: String, span: Span },
+
+    #[error("line {}: invalid call: {message}", .span.line)]
+    InvalidCall { message: String, span: Span },
+
+    #[error("line {}: unclosed commentary brace", .span.line)]
+    UnclosedComment { span: Span },
+
+    #[error("line {}: unknown formatting tag: {message}", .span.line)]
+    UnknownFormatTag { message: String, span: Span },
 
-    #[error("Invalid deal notation: {0}")]
-    InvalidDeal(String),
+    #[error("line {}: {message}", .span.line)]
+    ParseError { message: String, span: Span },
+}
+
+impl PbnError {
+    /// Where the problem is
+    pub fn span(&self) -> &Span {
+        match self {
+            PbnError::InvalidTagPair { span, .. }
+            | PbnError::InvalidDeal { span, .. }
+            | PbnError::InvalidCard { span, .. }
+            | PbnError::InvalidCall { span, .. }
+            | PbnError::UnclosedComment { span }
+            | PbnError::UnknownFormatTag { span, .. }
+            | PbnError::ParseError { span, .. } => span,
+        }
+    }
 
-    #[error("Invalid card: {0}")]
-    InvalidCard(String),
+    /// The message with the offending line and carets under the problem, for
+    /// showing to users; `source` names the input (usually its path)
+    pub fn render(&self, source: &str) -> String {
+        let span = self.span();
+        let gutter = " ".repeat(span.line.to_string().len());
+        format!(
+            "{}\n{}--> {}:{}:{}\n{}",
+            self, gutter, source, span.line, span.column, span
+        )
+    }
+}
 
-    #[error("Invalid call in auction: {0}")]
-    InvalidCall(String),
+/// Something in a PBN file that was recognized but not applied
+///
+/// Parsing still succeeds; callers decide whether to report these.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PbnWarning {
+    #[error("Unsupported %BCOptions flag: {0}")]
+    UnsupportedBCOption(String),
+}
 
-    #[error("Unclosed commentary brace")]
-    UnclosedComment,
+/// Something that rendered, but not as the PBN file asked
+///
+/// The PDF is still produced; callers decide whether to report these.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RenderWarning {
+    #[error(
+        "Board {0}: commentary has Chinese/Japanese/Korean text, which the builtin fonts \
+         can't show; use --font-commentary with a CJK font"
+    )]
+    MissingGlyphs(String),
 
-    #[error("Unknown formatting tag: {0}")]
-    UnknownFormatTag(String),
+    #[error("Title cut short to fit the banner: {0}")]
+    TruncatedTitle(String),
 
-    #[error("Parse error: {0}")]
-    ParseError(String),
+    #[error("Board {0} is taller than a column and runs off the page")]
+    BoardOverflow(String),
+
+    #[error("Board {0} has nothing to show and was skipped")]
+    EmptyBoard(String),
+
+    #[error("{0} is not used by this layout, which draws that text in the builtin fonts")]
+    UnusedFont(String),
 }
 
 #[derive(Error, Debug)]
@@ -40,6 +160,12 @@ pub enum RenderError {
 
     #[error("Card asset loading error: {0}")]
     CardAsset(String),
+
+    #[error("Failed to load image: {0}")]
+    Image(String),
+
+    #[error("Rendering was cancelled")]
+    Cancelled,
 }
 
 #[derive(Error, Debug)]
@@ -49,4 +175,19 @@ pub enum ConfigError {
 
     #[error("Invalid color specification: {0}")]
     InvalidColor(String),
+
+    #[error("Invalid layout template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Invalid config file: {0}")]
+    InvalidConfig(String),
+
+    #[error("Unknown profile: {0}")]
+    UnknownProfile(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
 }
@@ -0,0 +1,168 @@
+//! C interface, for programs written in other languages
+//!
+//! Built with the `ffi` feature as a C dynamic library:
+//!
+//! ```bash
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! `include/pbn_to_pdf.h` declares the functions. Strings are UTF-8 and
+//! NUL-terminated. A failed call returns NULL and leaves a message for
+//! [pbn_to_pdf_last_error]; no panic crosses into the caller.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use crate::{parse_pbn, render_with_settings, settings_from_metadata, Layout, RenderOptions};
+
+thread_local! {
+    /// Message from the last failed call on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Render a PBN file to a PDF.
+///
+/// `pbn_utf8` is the file's text. `layout` is a `--layout` name such as
+/// "analysis" or "bidding-sheets"; NULL means "analysis". On success the
+/// PDF's length is stored in `out_len` and the PDF returned, to be released
+/// with [pbn_to_pdf_free]; on failure NULL is returned.
+///
+/// # Safety
+///
+/// `pbn_utf8` must be a valid NUL-terminated string, `layout` one or NULL,
+/// and `out_len` must point to writable memory for a `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn pbn_to_pdf_render(
+    pbn_utf8: *const c_char,
+    layout: *const c_char,
+    out_len: *mut usize,
+) -> *mut u8 {
+    if pbn_utf8.is_null() || out_len.is_null() {
+        set_last_error("pbn_utf8 and out_len must not be NULL".to_string());
+        return ptr::null_mut();
+    }
+    let pbn = CStr::from_ptr(pbn_utf8);
+    let layout = (!layout.is_null()).then(|| CStr::from_ptr(layout));
+
+    match panic::catch_unwind(AssertUnwindSafe(|| render(pbn, layout))) {
+        Ok(Ok(pdf)) => {
+            *out_len = pdf.len();
+            Box::into_raw(pdf.into_boxed_slice()) as *mut u8
+        }
+        Ok(Err(message)) => {
+            set_last_error(message);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_last_error("internal error while rendering".to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Release a PDF returned by [pbn_to_pdf_render]
+///
+/// # Safety
+///
+/// `pdf` and `len` must be a pointer and length returned together by
+/// [pbn_to_pdf_render], not yet freed; NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn pbn_to_pdf_free(pdf: *mut u8, len: usize) {
+    if !pdf.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(pdf, len)));
+    }
+}
+
+/// Why the last call on this thread failed, or NULL if none has. The string
+/// stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn pbn_to_pdf_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// The library version, e.g. "0.17.0"
+#[no_mangle]
+pub extern "C" fn pbn_to_pdf_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+fn render(pbn: &CStr, layout: Option<&CStr>) -> Result<Vec<u8>, String> {
+    let pbn = pbn
+        .to_str()
+        .map_err(|e| format!("PBN is not UTF-8: {}", e))?;
+    let layout = match layout {
+        Some(name) => {
+            let name = name
+                .to_str()
+                .map_err(|e| format!("layout is not UTF-8: {}", e))?;
+            Layout::from_name(name).ok_or_else(|| format!("unknown layout: {}", name))?
+        }
+        None => Layout::Analysis,
+    };
+
+    let pbn_file = parse_pbn(pbn).map_err(|e| e.to_string())?;
+    let settings = settings_from_metadata(&pbn_file.metadata, layout, RenderOptions::default());
+    let never = Arc::new(AtomicBool::new(false));
+    render_with_settings(&pbn_file.boards, settings, Arc::new(|_| {}), never)
+        .map(|report| report.into_pdf())
+        .map_err(|e| e.to_string())
+}
+
+fn set_last_error(message: String) {
+    // A message can't hold a NUL; keep the text before it
+    let message = match CString::new(message) {
+        Ok(message) => message,
+        Err(e) => {
+            let end = e.nul_position();
+            CString::new(&e.into_vec()[..end]).unwrap_or_default()
+        }
+    };
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PBN: &str = "[Board \"1\"]\n[Dealer \"N\"]\n[Vulnerable \"None\"]\n\
+        [Deal \"N:AKQ2.J54.T9.8732 T98.T98.QJ87.T96 J43.AKQ.AK54.AKQ 765.7632.632.J54\"]\n";
+
+    #[test]
+    fn test_render_and_free() {
+        let pbn = CString::new(PBN).unwrap();
+        let layout = CString::new("bidding-sheets").unwrap();
+        let mut len = 0;
+        unsafe {
+            let pdf = pbn_to_pdf_render(pbn.as_ptr(), layout.as_ptr(), &mut len);
+            assert!(!pdf.is_null());
+            assert!(std::slice::from_raw_parts(pdf, len).starts_with(b"%PDF"));
+            pbn_to_pdf_free(pdf, len);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let pbn = CString::new(PBN).unwrap();
+        let layout = CString::new("no-such-layout").unwrap();
+        let mut len = 0;
+        unsafe {
+            let pdf = pbn_to_pdf_render(pbn.as_ptr(), layout.as_ptr(), &mut len);
+            assert!(pdf.is_null());
+            let message = CStr::from_ptr(pbn_to_pdf_last_error());
+            assert_eq!(message.to_str().unwrap(), "unknown layout: no-such-layout");
+
+            assert!(pbn_to_pdf_render(ptr::null(), ptr::null(), &mut len).is_null());
+        }
+        let version = unsafe { CStr::from_ptr(pbn_to_pdf_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
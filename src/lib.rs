@@ -1,38 +1,27 @@
 pub mod cli;
 pub mod config;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod model;
+pub mod options;
 pub mod parser;
 pub mod render;
 
 pub use cli::Layout;
-pub use config::Settings;
-pub use error::{PbnError, RenderError};
+pub use config::{BoardOverrides, BoardSettings, Settings};
+pub use error::{PbnError, PbnWarning, RenderError, RenderWarning};
 pub use model::Board;
-pub use parser::{parse_pbn, PbnFile};
-pub use render::generate_pdf;
+pub use options::{RenderOptions, RenderOptionsBuilder};
+pub use parser::{parse_pbn, parse_pbn_lenient, PbnFile, PbnReader};
+pub use render::{generate_pdf, LayoutRenderer, RenderProgress, RenderReport, RenderStage};
 
-use parser::header::parse_headers;
-use render::{
-    BiddingSheetsRenderer, DealerSummaryRenderer, DeclarersPlan1UpRenderer,
-    DeclarersPlan2UpRenderer, DeclarersPlanRenderer,
-};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
-/// Optional rendering flags passed through from library consumers.
-///
-/// Currently used by the declarer's plan layouts (1-up, 2-up, 4-up) to
-/// highlight analysis-identified cards with colored circles. When multiple
-/// analyses identify the same card the highest-priority color wins
-/// (sure > promotable > length).
-#[derive(Debug, Default, Clone, Copy)]
-pub struct RenderOptions {
-    /// Circle sure winners in red (priority 1, highest)
-    pub circle_sure_winners: bool,
-    /// Circle promotable winners in green (priority 2)
-    pub circle_promotable_winners: bool,
-    /// Circle length winners in blue (priority 3)
-    pub circle_length_winners: bool,
-}
+use model::PbnMetadata;
+use parser::header::parse_headers;
+use render::{renderer_for, ProgressCallback};
 
 /// High-level API for rendering boards to PDF.
 ///
@@ -81,38 +70,297 @@ pub fn render_boards(
     layout: Layout,
     options: RenderOptions,
 ) -> Result<Vec<u8>, RenderError> {
-    // Parse metadata from raw comment lines
+    render_boards_with_progress(boards, metadata_comments, layout, options, |_| {})
+}
+
+/// [`render_boards`], reporting progress as it goes.
+///
+/// `progress` is called as boards are laid out (boards done and the total)
+/// and once more when the PDF is being saved, on the calling thread, so a
+/// caller can drive a progress bar from it.
+///
+/// # Example
+///
+/// ```no_run
+/// use pbn_to_pdf::{parse_pbn, render_boards_with_progress, Layout, RenderOptions};
+///
+/// let pbn_file = parse_pbn(&std::fs::read_to_string("hands.pbn").unwrap()).unwrap();
+/// let pdf_bytes = render_boards_with_progress(
+///     &pbn_file.boards,
+///     &[],
+///     Layout::Analysis,
+///     RenderOptions::default(),
+///     |progress| eprintln!("{:?}: {} of {}", progress.stage, progress.done, progress.total),
+/// )
+/// .unwrap();
+/// ```
+pub fn render_boards_with_progress(
+    boards: &[Board],
+    metadata_comments: &[String],
+    layout: Layout,
+    options: RenderOptions,
+    progress: impl Fn(RenderProgress) + Send + Sync + 'static,
+) -> Result<Vec<u8>, RenderError> {
+    let never = Arc::new(AtomicBool::new(false));
+    render_boards_cancellable(boards, metadata_comments, layout, options, progress, never)
+}
+
+/// [`render_boards_with_progress`], stopping early when `cancel` is set.
+///
+/// Another thread (a GUI's cancel button, a server dropping a request) sets
+/// `cancel` to abort the render; it stops at the next page with
+/// [`RenderError::Cancelled`] and nothing is written.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use pbn_to_pdf::{parse_pbn, render_boards_cancellable, Layout, RenderOptions};
+///
+/// let pbn_file = parse_pbn(&std::fs::read_to_string("hands.pbn").unwrap()).unwrap();
+/// let cancel = Arc::new(AtomicBool::new(false));
+///
+/// let handle = {
+///     let cancel = cancel.clone();
+///     std::thread::spawn(move || {
+///         render_boards_cancellable(
+///             &pbn_file.boards,
+///             &[],
+///             Layout::Analysis,
+///             RenderOptions::default(),
+///             |_| {},
+///             cancel,
+///         )
+///     })
+/// };
+///
+/// // The user changed their mind
+/// cancel.store(true, Ordering::Relaxed);
+/// let _ = handle.join().unwrap();
+/// ```
+pub fn render_boards_cancellable(
+    boards: &[Board],
+    metadata_comments: &[String],
+    layout: Layout,
+    options: RenderOptions,
+    progress: impl Fn(RenderProgress) + Send + Sync + 'static,
+    cancel: Arc<AtomicBool>,
+) -> Result<Vec<u8>, RenderError> {
+    render_boards_report(boards, metadata_comments, layout, options, progress, cancel)
+        .map(RenderReport::into_pdf)
+}
+
+/// [`render_boards_cancellable`], returning the warnings with the PDF.
+///
+/// The other `render_boards` functions send warnings (missing glyphs,
+/// truncated titles, boards that overflow or have nothing to show) to the
+/// log; this returns them in the [`RenderReport`] instead, so callers can
+/// show them to users.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::Arc;
+/// use pbn_to_pdf::{parse_pbn, render_boards_report, Layout, RenderOptions};
+///
+/// let pbn_file = parse_pbn(&std::fs::read_to_string("hands.pbn").unwrap()).unwrap();
+/// let report = render_boards_report(
+///     &pbn_file.boards,
+///     &[],
+///     Layout::Analysis,
+///     RenderOptions::default(),
+///     |_| {},
+///     Arc::new(AtomicBool::new(false)),
+/// )
+/// .unwrap();
+/// for warning in &report.warnings {
+///     eprintln!("warning: {}", warning);
+/// }
+/// std::fs::write("output.pdf", report.pdf).unwrap();
+/// ```
+pub fn render_boards_report(
+    boards: &[Board],
+    metadata_comments: &[String],
+    layout: Layout,
+    options: RenderOptions,
+    progress: impl Fn(RenderProgress) + Send + Sync + 'static,
+    cancel: Arc<AtomicBool>,
+) -> Result<RenderReport, RenderError> {
+    let settings = layout_settings(metadata_comments, layout, options);
+    render_with_settings(boards, settings, Arc::new(progress), cancel)
+}
+
+/// [`render_boards`], with settings that differ for particular boards.
+///
+/// `overrides` maps board ids (the `[Board]` tag) to the settings to change
+/// for that board, such as hiding the auction on a quiz board or a different
+/// label format; every other board uses the document's settings. Overrides
+/// apply to the Analysis and Declarer's Plan layouts, which draw boards one
+/// at a time.
+///
+/// # Example
+///
+/// ```no_run
+/// use pbn_to_pdf::{
+///     parse_pbn, render_boards_with_overrides, BoardOverrides, BoardSettings, Layout,
+///     RenderOptions,
+/// };
+///
+/// let pbn_file = parse_pbn(&std::fs::read_to_string("hands.pbn").unwrap()).unwrap();
+/// let overrides = BoardOverrides::new().with(
+///     "3",
+///     BoardSettings {
+///         show_bidding: Some(false),
+///         board_label_format: Some("Quiz %".to_string()),
+///         ..Default::default()
+///     },
+/// );
+/// let pdf_bytes = render_boards_with_overrides(
+///     &pbn_file.boards,
+///     &[],
+///     Layout::Analysis,
+///     RenderOptions::default(),
+///     overrides,
+/// )
+/// .unwrap();
+/// ```
+pub fn render_boards_with_overrides(
+    boards: &[Board],
+    metadata_comments: &[String],
+    layout: Layout,
+    options: RenderOptions,
+    overrides: BoardOverrides,
+) -> Result<Vec<u8>, RenderError> {
+    let mut settings = layout_settings(metadata_comments, layout, options);
+    settings.board_overrides = overrides;
+    let never = Arc::new(AtomicBool::new(false));
+    render_with_settings(boards, settings, Arc::new(|_| {}), never).map(RenderReport::into_pdf)
+}
+
+/// Render the same boards in several layouts in one call.
+///
+/// The `%` directives in `metadata_comments` are read once and each layout
+/// gets its own page defaults, so the result is the same as calling
+/// [`render_boards`] once per layout. Each PDF embeds its own fonts and card
+/// images, as a PDF must. The PDFs come back in the order of `layouts`.
+///
+/// # Example
+///
+/// ```no_run
+/// use pbn_to_pdf::{parse_pbn, render_layouts, Layout, RenderOptions};
+///
+/// let pbn_file = parse_pbn(&std::fs::read_to_string("hands.pbn").unwrap()).unwrap();
+/// let pdfs = render_layouts(
+///     &pbn_file.boards,
+///     &[],
+///     &[Layout::Analysis, Layout::BiddingSheets, Layout::DeclarersPlan],
+///     RenderOptions::default(),
+/// )
+/// .unwrap();
+/// for (layout, pdf_bytes) in pdfs {
+///     let name = format!("hands{}.pdf", layout.output_suffix().unwrap_or(""));
+///     std::fs::write(name, pdf_bytes).unwrap();
+/// }
+/// ```
+pub fn render_layouts(
+    boards: &[Board],
+    metadata_comments: &[String],
+    layouts: &[Layout],
+    options: RenderOptions,
+) -> Result<Vec<(Layout, Vec<u8>)>, RenderError> {
+    let metadata = parse_metadata(metadata_comments);
+    let never = Arc::new(AtomicBool::new(false));
+    layouts
+        .iter()
+        .map(|&layout| {
+            let settings = settings_from_metadata(&metadata, layout, options);
+            let report = render_with_settings(boards, settings, Arc::new(|_| {}), never.clone())?;
+            Ok((layout, report.into_pdf()))
+        })
+        .collect()
+}
+
+/// [`render_boards`] with a layout from outside this crate.
+///
+/// `make_renderer` builds the [`LayoutRenderer`] from the settings read from
+/// `metadata_comments` and `options` (with the Analysis layout's page
+/// defaults), so a custom layout honours the same `%` directives as the
+/// builtin ones.
+///
+/// # Example
+///
+/// ```no_run
+/// use pbn_to_pdf::{
+///     render_boards_with_layout, Board, LayoutRenderer, RenderError, RenderOptions,
+///     RenderReport, Settings,
+/// };
+///
+/// struct Blank(Settings);
+///
+/// impl LayoutRenderer for Blank {
+///     fn name(&self) -> &str {
+///         "blank"
+///     }
+///
+///     fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+///         Ok(boards.len())
+///     }
+///
+///     fn render_report(&self, _boards: &[Board]) -> Result<RenderReport, RenderError> {
+///         unimplemented!("draw the pages with printpdf")
+///     }
+/// }
+///
+/// let pdf_bytes =
+///     render_boards_with_layout(&[], &[], RenderOptions::default(), Blank).unwrap();
+/// ```
+pub fn render_boards_with_layout<R: LayoutRenderer>(
+    boards: &[Board],
+    metadata_comments: &[String],
+    options: RenderOptions,
+    make_renderer: impl FnOnce(Settings) -> R,
+) -> Result<Vec<u8>, RenderError> {
+    let settings = layout_settings(metadata_comments, Layout::Analysis, options);
+    make_renderer(settings).render(boards)
+}
+
+/// Settings for `layout`, from the PBN header comments and render options
+fn layout_settings(
+    metadata_comments: &[String],
+    layout: Layout,
+    options: RenderOptions,
+) -> Settings {
+    settings_from_metadata(&parse_metadata(metadata_comments), layout, options)
+}
+
+/// Parse metadata from raw comment lines
+fn parse_metadata(metadata_comments: &[String]) -> PbnMetadata {
     let comment_refs: Vec<&str> = metadata_comments.iter().map(|s| s.as_str()).collect();
-    let metadata = parse_headers(&comment_refs);
+    parse_headers(&comment_refs)
+}
 
+fn settings_from_metadata(
+    metadata: &PbnMetadata,
+    layout: Layout,
+    options: RenderOptions,
+) -> Settings {
     // Create settings with layout-appropriate defaults, then apply metadata
-    let mut settings = Settings::for_layout(layout).with_metadata(&metadata);
-    settings.circle_sure_winners = options.circle_sure_winners;
-    settings.circle_promotable_winners = options.circle_promotable_winners;
-    settings.circle_length_winners = options.circle_length_winners;
+    let mut settings = Settings::for_layout(layout).with_metadata(metadata);
+    // Explicit options win over the file's directives
+    options.apply(&mut settings);
+    settings
+}
 
-    // Route to the appropriate renderer based on layout
-    match layout {
-        Layout::Analysis => generate_pdf(boards, &settings),
-        Layout::BiddingSheets => {
-            let renderer = BiddingSheetsRenderer::new(settings);
-            renderer.render(boards)
-        }
-        Layout::DeclarersPlan1up => {
-            let renderer = DeclarersPlan1UpRenderer::new(settings);
-            renderer.render(boards)
-        }
-        Layout::DeclarersPlan2up => {
-            let renderer = DeclarersPlan2UpRenderer::new(settings);
-            renderer.render(boards)
-        }
-        Layout::DeclarersPlan => {
-            let renderer = DeclarersPlanRenderer::new(settings);
-            renderer.render(boards)
-        }
-        Layout::DealerSummary => {
-            let renderer = DealerSummaryRenderer::new(settings);
-            renderer.render(boards)
-        }
-    }
+fn render_with_settings(
+    boards: &[Board],
+    settings: Settings,
+    progress: ProgressCallback,
+    cancel: Arc<AtomicBool>,
+) -> Result<RenderReport, RenderError> {
+    let mut renderer = renderer_for(settings);
+    renderer.set_progress(progress);
+    renderer.set_cancel(cancel);
+    renderer.render_report(boards)
 }
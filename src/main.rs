@@ -1,107 +1,938 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use std::fmt;
 use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
-use pbn_to_pdf::cli::{parse_board_range, Args, Layout};
-use pbn_to_pdf::config::Settings;
-use pbn_to_pdf::parser::parse_pbn;
+use pbn_to_pdf::cli::{
+    annotate_pbn, board_table, clear_progress_bar, diff_boards, expand_inputs, extract_records,
+    is_stdio, layout_output_path, merge_records, parse_board_map, parse_board_range,
+    parse_results_csv, progress_bar, renumber_records, results_from_boards, rotate_records,
+    run_preview, sort_boards, split_boards, split_file_name, Args, BoardAnalysis, BoardFilter,
+    BoardStats, Command, DiagnosticsFormat, DoubleDummyResult, Layout, LayoutSelection, Movement,
+    Renumbering, Rotation, ScoreReport, ScoringMethod,
+};
+use pbn_to_pdf::config::{LayoutTemplate, Locale, Settings};
+use pbn_to_pdf::error::PbnError;
+use pbn_to_pdf::model::{merge_rooms, Board, PbnMetadata};
+use pbn_to_pdf::parser::{
+    decode_pbn, parse_conventions, parse_pbn, parse_pbn_lenient, validate_pbn, Encoding, PbnFile,
+    PbnRecords,
+};
+use pbn_to_pdf::render::helpers::timings;
 use pbn_to_pdf::render::{
-    generate_pdf, BiddingSheetsRenderer, DealerSummaryRenderer, DeclarersPlan1UpRenderer,
-    DeclarersPlan2UpRenderer, DeclarersPlanRenderer,
+    renderer_for, ChunkCache, ChunkedRenderer, LayoutRenderer, ProgressCallback,
 };
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+fn main() -> ExitCode {
+    let args = match Args::parse_with_profile() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
 
     // Initialize logging
-    env_logger::Builder::new()
-        .filter_level(match args.verbose {
-            0 => log::LevelFilter::Warn,
-            1 => log::LevelFilter::Info,
-            _ => log::LevelFilter::Debug,
+    let mut logger = env_logger::Builder::new();
+    logger.filter_level(match args.verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        _ => log::LevelFilter::Debug,
+    });
+    if args.diagnostics == DiagnosticsFormat::Json {
+        logger.format(|buf, record| writeln!(buf, "{}", record_json(record)));
+    }
+    logger.init();
+
+    // Failures are reported like any other diagnostic, so JSON output stays
+    // JSON when the conversion itself fails
+    let diagnostics = args.diagnostics;
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            match diagnostics {
+                DiagnosticsFormat::Text => eprintln!("Error: {:?}", error),
+                DiagnosticsFormat::Json => eprintln!("{}", error_json(&error)),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A log record as a JSON diagnostic (`--diagnostics json`)
+fn record_json(record: &log::Record) -> serde_json::Value {
+    let severity = match record.level() {
+        log::Level::Error => "error",
+        log::Level::Warn => "warning",
+        log::Level::Info => "info",
+        log::Level::Debug | log::Level::Trace => "debug",
+    };
+    serde_json::json!({
+        "severity": severity,
+        "source": record.target(),
+        "message": record.args().to_string(),
+    })
+}
+
+/// A failure as a JSON diagnostic, with the file, line and column when a
+/// PBN file failed to parse
+fn error_json(error: &anyhow::Error) -> serde_json::Value {
+    let failure = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ParseFailure>());
+    match failure {
+        Some(failure) => {
+            let span = failure.error.span();
+            serde_json::json!({
+                "severity": "error",
+                "file": failure.file,
+                "line": span.line,
+                "column": span.column,
+                "message": failure.error.to_string(),
+            })
+        }
+        None => serde_json::json!({
+            "severity": "error",
+            "message": format!("{:#}", error),
+        }),
+    }
+}
+
+/// Run the command the arguments ask for
+fn run(args: Args) -> Result<()> {
+    match args.command {
+        Some(Command::Validate { ref files }) => {
+            return validate_files(&expand_inputs(files)?, args.diagnostics)
+        }
+        Some(Command::List { ref files }) => return list_files(&expand_inputs(files)?),
+        Some(Command::Stats { ref files, json }) => {
+            return print_stats(&expand_inputs(files)?, json)
+        }
+        Some(Command::Analyze { ref files, json }) => {
+            return print_analysis(&expand_inputs(files)?, json)
+        }
+        Some(Command::Score {
+            ref files,
+            method,
+            movement,
+            csv,
+        }) => return print_scores(&expand_inputs(files)?, method, movement, csv),
+        Some(Command::Dd {
+            ref file,
+            ref output,
+        }) => return annotate_file(file, output.as_deref()),
+        Some(Command::Renumber {
+            ref file,
+            offset,
+            start,
+            ref map,
+            derive,
+            ref output,
+        }) => {
+            let renumbering = match (offset, start, map) {
+                (Some(offset), _, _) => Renumbering::Offset(offset),
+                (_, Some(start), _) => Renumbering::Start(start),
+                (_, _, Some(map)) => {
+                    let text = fs::read_to_string(map)
+                        .with_context(|| format!("Failed to read board map: {}", map.display()))?;
+                    Renumbering::Map(parse_board_map(&text)?)
+                }
+                (None, None, None) => unreachable!("clap requires one numbering option"),
+            };
+            return renumber_file(file, &renumbering, derive, output.as_deref());
+        }
+        Some(Command::Extract {
+            ref file,
+            ref boards,
+            ref filter,
+            ref output,
+        }) => {
+            return extract_file(
+                file,
+                boards.as_deref(),
+                filter.as_deref(),
+                output.as_deref(),
+            )
+        }
+        Some(Command::Rotate {
+            ref file,
+            dealer,
+            declarer,
+            south,
+            ref output,
+        }) => {
+            let rotation = match (dealer, declarer, south) {
+                (Some(seat), _, _) => Rotation::Dealer(seat),
+                (_, Some(seat), _) => Rotation::Declarer(seat),
+                (_, _, Some(seat)) => Rotation::South(seat),
+                (None, None, None) => unreachable!("clap requires one rotation option"),
+            };
+            return rotate_file(file, rotation, output.as_deref());
+        }
+        Some(Command::Diff { ref old, ref new }) => return diff_files(old, new),
+        Some(Command::Preview { ref file }) => return preview_file(file),
+        Some(Command::Merge {
+            ref files,
+            ref output,
+        }) => return merge_files(&expand_inputs(files)?, output.as_deref()),
+        None => {}
+    }
+
+    let custom_font = args.font_diagram.is_some()
+        || args.font_commentary.is_some()
+        || args.font_hand_record.is_some();
+    if !custom_font && !Locale::for_language(args.language).fits_builtin_fonts() {
+        log::warn!(
+            "The builtin PDF fonts lack some {:?} letters; labels are printed without those \
+             accents unless a --font-* file is given",
+            args.language
+        );
+    }
+
+    let files = expand_inputs(&args.inputs)?;
+    if args.is_batch() {
+        if args.watch {
+            anyhow::bail!("--watch works on a single input file");
+        }
+        start_timings(&args);
+        let result = convert_batch(&args, &files);
+        print_timings();
+        return result;
+    }
+
+    let input = &files[0];
+    let output_path = args.output_path(input);
+    if args.watch {
+        return watch_file(&args, input, &output_path);
+    }
+
+    // Nothing is listed for "-", so standard output holds only the PDF
+    start_timings(&args);
+    let written = convert_file(&args, input, &output_path, None);
+    print_timings();
+    for path in written? {
+        println!("Successfully wrote PDF to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Boards listed by `--timings`
+const SLOWEST_BOARDS: usize = 10;
+
+/// Start timing stages and boards for `--timings`
+fn start_timings(args: &Args) {
+    if args.timings {
+        timings::enable();
+    }
+}
+
+/// Print the `--timings` report, if timing was started
+fn print_timings() {
+    if let Some(log) = timings::take() {
+        eprintln!("{}", log.report(SLOWEST_BOARDS));
+    }
+}
+
+/// Convert every input file, several at a time, and report how each one went
+fn convert_batch(args: &Args, files: &[PathBuf]) -> Result<()> {
+    if args.output.is_some() {
+        anyhow::bail!("--output names a single file; use --out-dir with several inputs");
+    }
+    if files.iter().any(|file| is_stdio(file)) {
+        anyhow::bail!("Standard input (-) can't be combined with other inputs");
+    }
+    if let Some(ref dir) = args.out_dir {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+    }
+
+    let jobs = args
+        .jobs
+        .map(usize::from)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .min(files.len());
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(files.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = files.get(index) else {
+                    break;
+                };
+                let result = convert_file(args, input, &args.output_path(input), None);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut failed = 0;
+    for (index, result) in &results {
+        match result {
+            Ok(written) => {
+                let outputs: Vec<String> =
+                    written.iter().map(|p| p.display().to_string()).collect();
+                println!(
+                    "ok      {} -> {}",
+                    files[*index].display(),
+                    outputs.join(", ")
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                match args.diagnostics {
+                    DiagnosticsFormat::Text => {
+                        eprintln!("FAILED  {}: {:#}", files[*index].display(), e)
+                    }
+                    DiagnosticsFormat::Json => {
+                        let mut json = error_json(e);
+                        if json.get("file").is_none() {
+                            json["file"] = files[*index].display().to_string().into();
+                        }
+                        eprintln!("{}", json)
+                    }
+                }
+            }
+        }
+    }
+    println!(
+        "Converted {} of {} files ({} failed)",
+        files.len() - failed,
+        files.len(),
+        failed
+    );
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} files failed", failed, files.len());
+    }
+    Ok(())
+}
+
+/// Read an input file, or standard input for "-", as UTF-8 whatever encoding
+/// it was saved in
+fn read_pbn(input: &Path) -> Result<String> {
+    let bytes = if is_stdio(input) {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .with_context(|| "Failed to read PBN from standard input")?;
+        bytes
+    } else {
+        fs::read(input)
+            .with_context(|| format!("Failed to read input file: {}", input.display()))?
+    };
+    let (content, encoding) = decode_pbn(&bytes);
+    if encoding != Encoding::Utf8 {
+        log::info!("Read {} as {}", input.display(), encoding);
+    }
+    Ok(content)
+}
+
+/// Check PBN files and print their problems (`validate`); fails if any file
+/// has errors
+fn validate_files(files: &[PathBuf], format: DiagnosticsFormat) -> Result<()> {
+    let mut errors = 0;
+    let mut warnings = 0;
+    for file in files {
+        let name = if is_stdio(file) {
+            "<stdin>".to_string()
+        } else {
+            file.display().to_string()
+        };
+        for diagnostic in validate_pbn(&read_pbn(file)?) {
+            if diagnostic.is_error() {
+                errors += 1;
+            } else {
+                warnings += 1;
+            }
+            match format {
+                DiagnosticsFormat::Text => println!(
+                    "{}:{}: {}: {}",
+                    name, diagnostic.line, diagnostic.severity, diagnostic.message
+                ),
+                DiagnosticsFormat::Json => {
+                    let mut json = serde_json::to_value(&diagnostic)?;
+                    json["file"] = name.clone().into();
+                    eprintln!("{}", json);
+                }
+            }
+        }
+    }
+    println!(
+        "Checked {} files: {} errors, {} warnings",
+        files.len(),
+        errors,
+        warnings
+    );
+
+    if errors > 0 {
+        anyhow::bail!("{} errors found", errors);
+    }
+    Ok(())
+}
+
+/// Print a table of the boards in each file (`list`)
+fn list_files(files: &[PathBuf]) -> Result<()> {
+    for (index, file) in files.iter().enumerate() {
+        let pbn_file = parse_pbn(&read_pbn(file)?).map_err(|error| parse_failure(error, file))?;
+        if files.len() > 1 {
+            if index > 0 {
+                println!();
+            }
+            println!("{}:", file.display());
+        }
+        print!("{}", board_table(&pbn_file.boards));
+    }
+    Ok(())
+}
+
+/// Print statistics across the boards of all the files (`stats`)
+fn print_stats(files: &[PathBuf], json: bool) -> Result<()> {
+    let mut boards = Vec::new();
+    for file in files {
+        let pbn_file = parse_pbn(&read_pbn(file)?).map_err(|error| parse_failure(error, file))?;
+        boards.extend(pbn_file.boards);
+    }
+
+    let stats = BoardStats::new(&boards);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", stats);
+    }
+    Ok(())
+}
+
+/// Print the declarer-play analysis of every board (`analyze`)
+fn print_analysis(files: &[PathBuf], json: bool) -> Result<()> {
+    let mut analyses = Vec::new();
+    for file in files {
+        let pbn_file = parse_pbn(&read_pbn(file)?).map_err(|error| parse_failure(error, file))?;
+        analyses.extend(pbn_file.boards.iter().filter_map(BoardAnalysis::new));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&analyses)?);
+    } else {
+        for (index, analysis) in analyses.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            print!("{}", analysis);
+        }
+    }
+    Ok(())
+}
+
+/// Score the results in PBN and CSV files and rank the pairs (`score`)
+fn print_scores(
+    files: &[PathBuf],
+    method: ScoringMethod,
+    movement: Movement,
+    csv: bool,
+) -> Result<()> {
+    let mut results = Vec::new();
+    for file in files {
+        let content = read_pbn(file)?;
+        if file
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        {
+            results.extend(parse_results_csv(&content)?);
+        } else {
+            let pbn_file = parse_pbn(&content).map_err(|error| parse_failure(error, file))?;
+            results.extend(results_from_boards(&pbn_file.boards));
+        }
+    }
+    if results.is_empty() {
+        anyhow::bail!("No results to score");
+    }
+
+    let report = ScoreReport::new(results, method, movement);
+    if csv {
+        print!("{}", report.to_csv());
+    } else {
+        print!("{}", report.to_text());
+    }
+    Ok(())
+}
+
+/// Solve every board of a PBN file double dummy and write the file back with
+/// the results (`dd`)
+fn annotate_file(input: &Path, output: Option<&Path>) -> Result<()> {
+    let content = read_pbn(input)?;
+    let pbn_file = parse_pbn(&content).map_err(|error| parse_failure(error, input))?;
+
+    let total = pbn_file.boards.len();
+    let results: Vec<_> = pbn_file
+        .boards
+        .iter()
+        .enumerate()
+        .map(|(index, board)| {
+            let result = DoubleDummyResult::solve(board);
+            let id = board.board_id.as_deref().unwrap_or("?");
+            match result {
+                Some(_) => log::info!("Solved board {} ({} of {})", id, index + 1, total),
+                None => log::info!("Skipped board {}: the deal is incomplete", id),
+            }
+            result
         })
-        .init();
+        .collect();
+    write_pbn(output, &annotate_pbn(&content, &results))
+}
+
+/// Merge PBN files into one (`merge`)
+fn merge_files(files: &[PathBuf], output: Option<&Path>) -> Result<()> {
+    let records = files
+        .iter()
+        .map(|file| read_pbn(file).map(|content| PbnRecords::parse(&content)))
+        .collect::<Result<Vec<_>>>()?;
+    let merged = merge_records(&records);
+
+    let total: usize = records.iter().map(|r| r.games.len()).sum();
+    log::info!(
+        "Merged {} boards from {} files ({} repeated boards dropped)",
+        merged.games.len(),
+        files.len(),
+        total - merged.games.len()
+    );
+    write_pbn(output, &merged.to_pbn())
+}
+
+/// Renumber the boards of a PBN file (`renumber`)
+fn renumber_file(
+    input: &Path,
+    renumbering: &Renumbering,
+    derive: bool,
+    output: Option<&Path>,
+) -> Result<()> {
+    let mut records = PbnRecords::parse(&read_pbn(input)?);
+    for warning in renumber_records(&mut records, renumbering, derive)? {
+        log::warn!("{}", warning);
+    }
+    write_pbn(output, &records.to_pbn())
+}
+
+/// Copy the selected boards of a PBN file into a new one (`extract`)
+fn extract_file(
+    input: &Path,
+    boards: Option<&str>,
+    filter: Option<&str>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let content = read_pbn(input)?;
+    let pbn_file = parse_pbn(&content).map_err(|error| parse_failure(error, input))?;
+    let range = boards
+        .map(parse_board_range)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid board range: {}", e))?;
+    let filter = filter.map(BoardFilter::parse).transpose()?;
+
+    let subset = extract_records(
+        &PbnRecords::parse(&content),
+        &pbn_file.boards,
+        range.as_ref(),
+        filter.as_ref(),
+    );
+    if subset.games.is_empty() {
+        anyhow::bail!("No boards to extract");
+    }
+    log::info!("Extracted {} boards", subset.games.len());
+    write_pbn(output, &subset.to_pbn())
+}
+
+/// Turn the boards of a PBN file round the table (`rotate`)
+fn rotate_file(input: &Path, rotation: Rotation, output: Option<&Path>) -> Result<()> {
+    let content = read_pbn(input)?;
+    let pbn_file = parse_pbn(&content).map_err(|error| parse_failure(error, input))?;
+
+    let mut records = PbnRecords::parse(&content);
+    for warning in rotate_records(&mut records, &pbn_file.boards, rotation) {
+        log::warn!("{}", warning);
+    }
+    write_pbn(output, &records.to_pbn())
+}
+
+/// Write PBN text to a file, or to standard output when there is no file or
+/// it is "-"
+fn write_pbn(output: Option<&Path>, content: &str) -> Result<()> {
+    match output {
+        Some(path) if !is_stdio(path) => fs::write(path, content)
+            .with_context(|| format!("Failed to write output file: {}", path.display())),
+        _ => {
+            let mut stdout = io::stdout().lock();
+            stdout
+                .write_all(content.as_bytes())
+                .and_then(|_| stdout.flush())
+                .with_context(|| "Failed to write PBN to standard output")
+        }
+    }
+}
+
+/// Print the differences between two PBN files (`diff`)
+fn diff_files(old: &Path, new: &Path) -> Result<()> {
+    let parse = |file: &Path| -> Result<Vec<Board>> {
+        let content = read_pbn(file)?;
+        let pbn_file = parse_pbn(&content).map_err(|error| parse_failure(error, file))?;
+        Ok(pbn_file.boards)
+    };
+    let diffs = diff_boards(&parse(old)?, &parse(new)?);
+
+    if diffs.is_empty() {
+        println!("No differences");
+    }
+    for diff in &diffs {
+        print!("{}", diff);
+    }
+    Ok(())
+}
+
+/// Browse a file's boards and print the range of the marked ones (`preview`)
+fn preview_file(file: &Path) -> Result<()> {
+    let pbn_file = parse_pbn(&read_pbn(file)?).map_err(|error| parse_failure(error, file))?;
+    if pbn_file.boards.is_empty() {
+        anyhow::bail!("No boards to preview");
+    }
+    if let Some(range) =
+        run_preview(pbn_file.boards).with_context(|| "Failed to run the terminal preview")?
+    {
+        println!("{}", range);
+    }
+    Ok(())
+}
+
+/// Parse the boards to convert; with `--lenient`, what can't be read is
+/// skipped and reported instead of failing the file
+fn parse_input(args: &Args, content: &str, input: &Path) -> Result<PbnFile> {
+    if !args.lenient {
+        return parse_pbn(content).map_err(|error| parse_failure(error, input));
+    }
+    let (pbn_file, diagnostics) = parse_pbn_lenient(content);
+    for diagnostic in diagnostics {
+        log::warn!("{}: {}", input.display(), diagnostic);
+    }
+    Ok(pbn_file)
+}
+
+/// A parse error showing the offending line of `source`
+fn parse_failure(error: PbnError, source: &Path) -> anyhow::Error {
+    ParseFailure {
+        file: source.display().to_string(),
+        error,
+    }
+    .into()
+}
+
+/// A PBN file that couldn't be parsed, shown with the offending line
+#[derive(Debug)]
+struct ParseFailure {
+    file: String,
+    error: PbnError,
+}
+
+impl fmt::Display for ParseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error.render(&self.file))
+    }
+}
+
+impl std::error::Error for ParseFailure {}
 
-    // Read input file
-    let pbn_content = fs::read_to_string(&args.input)
-        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+/// How often `--watch` checks whether the input has changed
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Convert `input` now and again whenever it's saved, until interrupted.
+/// Chunks of unchanged boards are kept between conversions and reused.
+fn watch_file(args: &Args, input: &Path, output_path: &Path) -> Result<()> {
+    if is_stdio(input) || is_stdio(output_path) {
+        anyhow::bail!("--watch needs an input file and an output file, not standard input/output");
+    }
+    let cache = ChunkCache::new();
+    let mut last_modified = None;
+    loop {
+        let modified = fs::metadata(input).and_then(|m| m.modified()).ok();
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+            // A broken edit is reported and the previous PDF left in place
+            start_timings(args);
+            let result = convert_file(args, input, output_path, Some(&cache));
+            print_timings();
+            match result {
+                Ok(written) => {
+                    for path in written {
+                        println!("Successfully wrote PDF to {}", path.display());
+                    }
+                }
+                Err(error) => eprintln!("Error: {:#}", error),
+            }
+            cache.sweep();
+            println!("Watching {} for changes (Ctrl-C to stop)", input.display());
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Convert one PBN file (or standard input) to a PDF at `output_path`, or to
+/// several PDFs next to it when splitting; returns the files written. With a
+/// cache (`--watch`), chunks rendered by an earlier conversion are reused.
+fn convert_file(
+    args: &Args,
+    input: &Path,
+    output_path: &Path,
+    cache: Option<&ChunkCache>,
+) -> Result<Vec<PathBuf>> {
+    let pbn_content = read_pbn(input)?;
 
     // Parse PBN
-    let pbn_file = parse_pbn(&pbn_content).with_context(|| "Failed to parse PBN content")?;
+    let pbn_file = timings::time("parse", || parse_input(args, &pbn_content, input))?;
 
     log::info!("Parsed {} boards from PBN file", pbn_file.boards.len());
+    for warning in &pbn_file.warnings {
+        log::warn!("{}", warning);
+    }
+
+    // Vugraph files record each room as its own game; show them as one board
+    let boards = merge_rooms(pbn_file.boards);
 
     // Filter boards if range specified
-    let boards = if let Some(ref range_spec) = args.boards {
+    let mut boards: Vec<_> = if let Some(ref range_spec) = args.boards {
         let allowed_boards = parse_board_range(range_spec)
             .map_err(|e| anyhow::anyhow!("Invalid board range: {}", e))?;
 
-        pbn_file
-            .boards
+        boards
             .into_iter()
-            .filter(|b| {
-                b.number
-                    .map(|n| allowed_boards.contains(&n))
-                    .unwrap_or(false)
-            })
+            .filter(|b| allowed_boards.contains(b))
             .collect()
     } else {
-        pbn_file.boards
+        boards
     };
 
+    if let Some(ref spec) = args.filter {
+        let filter = BoardFilter::parse(spec)?;
+        boards.retain(|board| filter.matches(board));
+    }
+
+    if let Some(key) = args.sort_by {
+        sort_boards(&mut boards, key, args.reverse);
+    }
+
     if boards.is_empty() {
         anyhow::bail!("No boards to process");
     }
 
     log::info!("Processing {} boards", boards.len());
 
-    // Build settings from CLI args and PBN metadata
-    let settings = Settings::from_args(&args).with_metadata(&pbn_file.metadata);
-
-    // Generate PDF
-    let output_path = args.output_path();
-
-    let pdf_data = match settings.layout {
-        Layout::Analysis => {
-            generate_pdf(&boards, &settings).with_context(|| "Failed to generate PDF")?
-        }
-        Layout::BiddingSheets => {
-            let renderer = BiddingSheetsRenderer::new(settings);
-            renderer
-                .render(&boards)
-                .with_context(|| "Failed to generate bidding sheets PDF")?
-        }
-        Layout::DeclarersPlan1up => {
-            let renderer = DeclarersPlan1UpRenderer::new(settings);
-            renderer
-                .render(&boards)
-                .with_context(|| "Failed to generate declarer's plan 1-up PDF")?
-        }
-        Layout::DeclarersPlan2up => {
-            let renderer = DeclarersPlan2UpRenderer::new(settings);
-            renderer
-                .render(&boards)
-                .with_context(|| "Failed to generate declarer's plan 2-up PDF")?
-        }
-        Layout::DeclarersPlan => {
-            let renderer = DeclarersPlanRenderer::new(settings);
-            renderer
-                .render(&boards)
-                .with_context(|| "Failed to generate declarer's plan PDF")?
-        }
-        Layout::DealerSummary => {
-            let renderer = DealerSummaryRenderer::new(settings);
-            renderer
-                .render(&boards)
-                .with_context(|| "Failed to generate dealer summary PDF")?
+    // Footnote conventional calls from the convention file
+    if let Some(ref path) = args.conventions {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read convention file: {}", path.display()))?;
+        let conventions =
+            parse_conventions(&content).map_err(|error| parse_failure(error, path))?;
+        conventions.annotate_boards(&mut boards);
+    }
+
+    // A progress bar for a single conversion, when nothing else is writing
+    // to the terminal
+    let progress = (!args.is_batch()
+        && args.verbose == 0
+        && args.diagnostics == DiagnosticsFormat::Text
+        && io::stderr().is_terminal())
+    .then(progress_bar);
+
+    let LayoutSelection::All = args.layout else {
+        let settings = layout_settings(args, args.layout.primary(), input, &pbn_file.metadata)?;
+        return write_layout(args, boards, settings, output_path, progress, cache);
+    };
+
+    // --layout all: the boards are parsed once and each layout written next
+    // to the Analysis output, named with its suffix
+    if is_stdio(output_path) {
+        anyhow::bail!("--layout all can't be written to standard output");
+    }
+    let mut written = Vec::new();
+    for layout in Layout::ALL {
+        let settings = layout_settings(args, layout, input, &pbn_file.metadata)?;
+        let path = layout_output_path(output_path, layout);
+        written.extend(write_layout(
+            args,
+            boards.clone(),
+            settings,
+            &path,
+            progress.clone(),
+            cache,
+        )?);
+    }
+    Ok(written)
+}
+
+/// Settings for one layout, from CLI args and PBN metadata
+fn layout_settings(
+    args: &Args,
+    layout: Layout,
+    input: &Path,
+    metadata: &PbnMetadata,
+) -> Result<Settings> {
+    let mut settings = Settings::from_args_for(args, layout).with_metadata(metadata);
+    if let Some(ref path) = args.template {
+        LayoutTemplate::load(path)?.apply(&mut settings);
+    }
+
+    // %HRTitleLogo names a file next to the PBN file; skip it if it isn't there
+    if settings.logo.is_none() {
+        if let Some(ref logo) = metadata.title_logo {
+            let path = input.parent().unwrap_or_else(|| Path::new("")).join(logo);
+            if path.is_file() {
+                settings.logo = Some(path);
+            } else {
+                log::warn!("Logo from %HRTitleLogo not found: {}", path.display());
+            }
         }
+    }
+    Ok(settings)
+}
+
+/// Render one layout to `output_path`, or to one PDF per part when the
+/// output is split
+fn write_layout(
+    args: &Args,
+    boards: Vec<Board>,
+    settings: Settings,
+    output_path: &Path,
+    progress: Option<ProgressCallback>,
+    cache: Option<&ChunkCache>,
+) -> Result<Vec<PathBuf>> {
+    if !args.splits_output() {
+        let pdf_data = render_pdf(&boards, settings, progress, cache)?;
+        return Ok(write_pdf(output_path, &pdf_data)?.into_iter().collect());
+    }
+
+    // Split output: one PDF per part, named from the unsplit output's stem
+    if is_stdio(output_path) {
+        anyhow::bail!("Split output can't be written to standard output");
+    }
+    let stem = output_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let dir = output_path.parent().unwrap_or_else(|| Path::new(""));
+    let parts = split_boards(
+        boards,
+        args.split_every.map(|n| n as usize),
+        args.split_by_session,
+    );
+    let mut written = Vec::with_capacity(parts.len());
+    for (idx, part) in parts.iter().enumerate() {
+        let path = dir.join(split_file_name(&args.split_name, &stem, idx + 1, part));
+        let pdf_data = render_pdf(part, settings.clone(), progress.clone(), cache)?;
+        written.extend(write_pdf(&path, &pdf_data)?);
+    }
+    Ok(written)
+}
+
+/// Render boards with the layout chosen in the settings, reusing chunks from
+/// `cache` where the layout is rendered in chunks
+fn render_pdf(
+    boards: &[Board],
+    settings: Settings,
+    progress: Option<ProgressCallback>,
+    cache: Option<&ChunkCache>,
+) -> Result<Vec<u8>> {
+    let shows_progress = progress.is_some();
+    let chunk_size = cache.and_then(|_| watch_chunk_size(&settings));
+    let mut renderer: Box<dyn LayoutRenderer> = match (cache, chunk_size) {
+        (Some(cache), Some(chunk_size)) => {
+            let settings = Settings {
+                chunk_size: Some(chunk_size),
+                ..settings
+            };
+            Box::new(ChunkedRenderer::new(settings).with_cache(cache.clone()))
+        }
+        _ => renderer_for(settings),
     };
+    if let Some(progress) = progress {
+        renderer.set_progress(progress);
+    }
+    // Includes the fonts, measure, save and compress stages timed inside it
+    let stage = format!("render {}", renderer.name());
+    let pdf_data = timings::time(&stage, || renderer.render(boards))
+        .with_context(|| format!("Failed to generate {} PDF", renderer.name()));
+    if shows_progress {
+        clear_progress_bar();
+    }
 
-    // Write output
-    fs::write(&output_path, pdf_data)
+    pdf_data
+}
+
+/// Boards per chunk when watching: `--chunk-size`, or one for Analysis
+/// output that puts each board on its own page anyway
+fn watch_chunk_size(settings: &Settings) -> Option<usize> {
+    settings
+        .chunk_size
+        .or_else(|| (settings.layout == Layout::Analysis && settings.column_count < 2).then_some(1))
+}
+
+/// Write a PDF file, or standard output for "-"; returns the file written, if any
+fn write_pdf(output_path: &Path, pdf_data: &[u8]) -> Result<Option<PathBuf>> {
+    if is_stdio(output_path) {
+        let mut stdout = io::stdout().lock();
+        stdout
+            .write_all(pdf_data)
+            .and_then(|_| stdout.flush())
+            .with_context(|| "Failed to write PDF to standard output")?;
+        return Ok(None);
+    }
+    fs::write(output_path, pdf_data)
         .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+    Ok(Some(output_path.to_path_buf()))
+}
 
-    println!("Successfully wrote PDF to {}", output_path.display());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    #[test]
+    fn test_record_json() {
+        let record = log::Record::builder()
+            .args(format_args!("Logo not found"))
+            .level(log::Level::Warn)
+            .target("pbn_to_pdf")
+            .build();
+        assert_eq!(
+            record_json(&record),
+            serde_json::json!({
+                "severity": "warning",
+                "source": "pbn_to_pdf",
+                "message": "Logo not found",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_failure_json() {
+        let error = parse_pbn("[Event \"\"]\n  {Never closed\n").unwrap_err();
+        let failure =
+            parse_failure(error, Path::new("hands.pbn")).context("Failed to convert hands.pbn");
+        let json = error_json(&failure);
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["file"], "hands.pbn");
+        assert_eq!(json["line"], 2);
+        assert_eq!(json["column"], 3);
+        assert_eq!(json["message"], "line 2: unclosed commentary brace");
+
+        let json = error_json(&anyhow::anyhow!("No results to score"));
+        assert_eq!(json["message"], "No results to score");
+        assert!(json.get("file").is_none());
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Re-exports core types from bridge-types with display-oriented extensions.
 
+use std::collections::BTreeSet;
 use std::fmt;
 
 use super::deal::Direction;
@@ -15,6 +16,8 @@ pub type BidSuit = Strain;
 /// Extension trait for Call to add pbn-to-pdf specific functionality
 pub trait CallExt {
     fn from_pbn_ext(s: &str) -> Option<Call>;
+    /// The call as written in PBN ("1NT", "Pass", "X", ...)
+    fn pbn_text(&self) -> String;
 }
 
 impl CallExt for Call {
@@ -26,6 +29,35 @@ impl CallExt for Call {
         }
         Call::from_pbn(s)
     }
+
+    fn pbn_text(&self) -> String {
+        match self {
+            Call::Bid { level, strain } => format!("{}{}", level, strain.pbn_text()),
+            Call::Pass => "Pass".to_string(),
+            Call::Double => "X".to_string(),
+            Call::Redouble => "XX".to_string(),
+            Call::Continue => "+".to_string(),
+            Call::Blank => "-".to_string(),
+        }
+    }
+}
+
+/// Extension trait for Strain to write it as PBN does
+pub trait StrainExt {
+    /// The strain as written in PBN ("NT", "S", "H", "D", "C")
+    fn pbn_text(&self) -> &'static str;
+}
+
+impl StrainExt for Strain {
+    fn pbn_text(&self) -> &'static str {
+        match self {
+            Strain::Clubs => "C",
+            Strain::Diamonds => "D",
+            Strain::Hearts => "H",
+            Strain::Spades => "S",
+            Strain::NoTrump => "NT",
+        }
+    }
 }
 
 /// A complete auction (bidding sequence)
@@ -36,6 +68,9 @@ pub struct Auction {
     pub is_passed_out: bool,
     /// Notes/alerts referenced by =N= in the auction
     pub notes: std::collections::HashMap<u8, String>,
+    /// Positions in `calls` of alerted calls (a `!` after the call). Kept
+    /// apart from the `=N=` note references in each call's annotation.
+    pub alerts: BTreeSet<usize>,
 }
 
 impl Auction {
@@ -45,6 +80,7 @@ impl Auction {
             calls: Vec::new(),
             is_passed_out: false,
             notes: std::collections::HashMap::new(),
+            alerts: BTreeSet::new(),
         }
     }
 
@@ -64,6 +100,27 @@ impl Auction {
         }
     }
 
+    /// Mark the last call as alerted
+    pub fn alert_last(&mut self) {
+        if let Some(index) = self.calls.len().checked_sub(1) {
+            self.alerts.insert(index);
+        }
+    }
+
+    /// Whether the call at `index` was alerted
+    pub fn is_alerted(&self, index: usize) -> bool {
+        self.alerts.contains(&index)
+    }
+
+    /// Superscript after the call at `index`: `!` when alerted, then its
+    /// annotation (a note number or a `?`)
+    pub fn call_marks(&self, index: usize) -> Option<String> {
+        let annotation = self.calls.get(index)?.annotation.as_deref();
+        let alert = if self.is_alerted(index) { "!" } else { "" };
+        let marks = format!("{}{}", alert, annotation.unwrap_or(""));
+        (!marks.is_empty()).then_some(marks)
+    }
+
     /// Returns true if this is an uncontested auction (one pair only bids, opponents only pass)
     /// Returns the bidding pair: Some((Direction, Direction)) for the pair that bids
     pub fn uncontested_pair(&self) -> Option<(Direction, Direction)> {
@@ -123,6 +180,93 @@ impl Auction {
             declarer,
         })
     }
+
+    /// Meaning of an annotated call from the auction notes
+    fn note_for(&self, annotated: &AnnotatedCall) -> Option<&str> {
+        let number = annotated.annotation.as_ref()?.parse::<u8>().ok()?;
+        self.notes.get(&number).map(String::as_str)
+    }
+
+    /// Describe the auction in plain English, for beginner handouts, e.g.
+    /// "North opened 1NT, South responded 2♥ (transfer), North bid 2♠ and all passed."
+    ///
+    /// Passes after the opening bid are left out; note meanings follow their
+    /// call in parentheses.
+    pub fn to_prose(&self) -> String {
+        let has_bid = self
+            .calls
+            .iter()
+            .any(|a| matches!(a.call, Call::Bid { .. }));
+        if !has_bid {
+            return if self.calls.len() >= 4 {
+                "All four players passed.".to_string()
+            } else {
+                String::new()
+            };
+        }
+
+        let trailing_passes = self
+            .calls
+            .iter()
+            .rev()
+            .take_while(|a| a.call == Call::Pass)
+            .count();
+        let ends_all_pass = trailing_passes >= 3;
+        let described = if ends_all_pass {
+            self.calls.len() - trailing_passes
+        } else {
+            self.calls.len()
+        };
+
+        let mut clauses = Vec::new();
+        let mut opener: Option<Direction> = None;
+        let mut responded = false;
+        let mut overcalled = false;
+        let mut seat = self.dealer;
+
+        for annotated in &self.calls[..described] {
+            let call = &annotated.call;
+            let clause = match (call, opener) {
+                (Call::Pass, None) => Some(format!("{} passed", seat)),
+                (Call::Bid { .. }, None) => {
+                    opener = Some(seat);
+                    Some(format!("{} opened {}", seat, call))
+                }
+                (Call::Bid { .. }, Some(first)) if seat == first.partner() && !responded => {
+                    responded = true;
+                    Some(format!("{} responded {}", seat, call))
+                }
+                (Call::Bid { .. }, Some(first))
+                    if seat != first && seat != first.partner() && !overcalled =>
+                {
+                    overcalled = true;
+                    Some(format!("{} overcalled {}", seat, call))
+                }
+                (Call::Bid { .. }, Some(_)) => Some(format!("{} bid {}", seat, call)),
+                (Call::Double, _) => Some(format!("{} doubled", seat)),
+                (Call::Redouble, _) => Some(format!("{} redoubled", seat)),
+                (Call::Pass | Call::Continue | Call::Blank, _) => None,
+            };
+            if let Some(mut clause) = clause {
+                if let Some(meaning) = self.note_for(annotated) {
+                    clause.push_str(&format!(" ({})", meaning));
+                }
+                clauses.push(clause);
+            }
+            seat = seat.next();
+        }
+
+        if ends_all_pass {
+            clauses.push("all passed".to_string());
+        }
+        match clauses.split_last() {
+            Some((last, rest)) if !rest.is_empty() => {
+                format!("{} and {}.", rest.join(", "), last)
+            }
+            Some((last, _)) => format!("{}.", last),
+            None => String::new(),
+        }
+    }
 }
 
 /// The contract resulting from an auction
@@ -136,9 +280,14 @@ pub struct Contract {
 }
 
 impl Contract {
-    /// Parse a contract string like "1NT", "4S", "4HX", "3NTXX"
+    /// Parse a contract string like "1NT", "4S", "4HX", "3NTXX"; case and
+    /// spaces are ignored ("3nt x")
     pub fn parse(s: &str) -> Option<Self> {
-        let s = s.trim();
+        let s: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
         if s.is_empty() {
             return None;
         }
@@ -157,7 +306,10 @@ impl Contract {
             (rest, false, false)
         };
 
-        let suit = Strain::from_str(suit_part)?;
+        let suit = match suit_part {
+            "N" => Strain::NoTrump,
+            _ => Strain::from_str(suit_part)?,
+        };
 
         Some(Contract {
             level,
@@ -168,6 +320,25 @@ impl Contract {
         })
     }
 
+    /// Parse a [Contract] tag value: Some(None) for "Pass" (the board was
+    /// passed out), None when the value isn't a contract
+    pub fn parse_tag(s: &str) -> Option<Option<Self>> {
+        if s.trim().eq_ignore_ascii_case("pass") {
+            return Some(None);
+        }
+        Self::parse(s).map(Some)
+    }
+
+    /// The contract as written in PBN, e.g. "3NTX"
+    pub fn to_pbn(&self) -> String {
+        let doubles = match (self.doubled, self.redoubled) {
+            (_, true) => "XX",
+            (true, _) => "X",
+            _ => "",
+        };
+        format!("{}{}{}", self.level, self.suit.pbn_text(), doubles)
+    }
+
     /// Convert to bridge_types::FinalContract
     pub fn to_final_contract(&self) -> FinalContract {
         let mut fc = FinalContract::new(self.level, self.suit, self.declarer);
@@ -218,6 +389,16 @@ mod tests {
         assert_eq!(Call::from_pbn("+"), Some(Call::Continue));
     }
 
+    #[test]
+    fn test_pbn_text() {
+        for text in ["1C", "3NT", "Pass", "X", "XX"] {
+            assert_eq!(Call::from_pbn(text).unwrap().pbn_text(), text);
+        }
+        for text in ["3NT", "4SX", "7HXX"] {
+            assert_eq!(Contract::parse(text).unwrap().to_pbn(), text);
+        }
+    }
+
     #[test]
     fn test_bid_display() {
         assert_eq!(
@@ -230,6 +411,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_contract_tag_forms() {
+        let contract = |s| Contract::parse_tag(s).unwrap().unwrap();
+        let doubled = contract("3NTX");
+        assert_eq!((doubled.level, doubled.suit), (3, Strain::NoTrump));
+        assert!(doubled.doubled && !doubled.redoubled);
+        let redoubled = contract("4SXX");
+        assert_eq!(redoubled.suit, Strain::Spades);
+        assert!(redoubled.redoubled && !redoubled.doubled);
+        let undoubled = contract(" 2h ");
+        assert_eq!(undoubled.suit, Strain::Hearts);
+        assert!(!undoubled.doubled && !undoubled.redoubled);
+        assert!(contract("3nt x").doubled);
+        assert_eq!(contract("1N").suit, Strain::NoTrump);
+
+        assert!(Contract::parse_tag("Pass").unwrap().is_none());
+        assert!(Contract::parse_tag("PASS").unwrap().is_none());
+        for invalid in ["", "?", "8S", "4SXXX", "4Q"] {
+            assert!(Contract::parse_tag(invalid).is_none(), "{}", invalid);
+        }
+    }
+
     #[test]
     fn test_contract_display() {
         let contract = Contract {
@@ -242,6 +445,52 @@ mod tests {
         assert_eq!(contract.to_string(), "4♠X by South");
     }
 
+    #[test]
+    fn test_prose_summary() {
+        let mut auction = Auction::new(Direction::North);
+        for call in ["1NT", "Pass", "2H", "Pass", "2S", "Pass", "Pass", "Pass"] {
+            auction.add_call(Call::from_pbn(call).unwrap());
+        }
+        auction.calls[2].annotation = Some("1".to_string());
+        auction.add_note(1, "transfer".to_string());
+        assert_eq!(
+            auction.to_prose(),
+            "North opened 1NT, South responded 2♥ (transfer), North bid 2♠ and all passed."
+        );
+    }
+
+    #[test]
+    fn test_call_marks() {
+        let mut auction = Auction::new(Direction::North);
+        auction.add_call(Call::from_pbn("1C").unwrap());
+        auction.alert_last();
+        auction.add_annotated_call(Call::from_pbn("1H").unwrap(), Some("1".to_string()));
+        auction.alert_last();
+        auction.add_call(Call::Pass);
+        assert_eq!(auction.call_marks(0).as_deref(), Some("!"));
+        assert_eq!(auction.call_marks(1).as_deref(), Some("!1"));
+        assert_eq!(auction.call_marks(2), None);
+        assert!(auction.calls[0].annotation.is_none());
+    }
+
+    #[test]
+    fn test_prose_competitive_and_passed_out() {
+        let mut auction = Auction::new(Direction::West);
+        for call in ["Pass", "1H", "1S", "X", "Pass", "Pass", "Pass"] {
+            auction.add_call(Call::from_pbn(call).unwrap());
+        }
+        assert_eq!(
+            auction.to_prose(),
+            "West passed, North opened 1♥, East overcalled 1♠, South doubled and all passed."
+        );
+
+        let mut passed_out = Auction::new(Direction::South);
+        for _ in 0..4 {
+            passed_out.add_call(Call::Pass);
+        }
+        assert_eq!(passed_out.to_prose(), "All four players passed.");
+    }
+
     #[test]
     fn test_bidsuit_alias() {
         // BidSuit is now an alias for Strain
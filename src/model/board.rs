@@ -1,12 +1,31 @@
-use super::auction::{Auction, Contract};
+use super::auction::{Auction, Contract, Strain};
 use super::bcflags::BCFlags;
+use super::card::Card;
 use super::commentary::CommentaryBlock;
 use super::deal::{Deal, Direction};
 use super::play::PlaySequence;
+use super::score_table::ScoreTable;
 
 // Re-export types from bridge-types
 pub use bridge_types::{PlayerNames, Vulnerability};
 
+/// Extension trait for Vulnerability to write it as PBN does
+pub trait VulnerabilityExt {
+    /// The vulnerability as written in PBN ("None", "NS", "EW", "All")
+    fn pbn_text(&self) -> &'static str;
+}
+
+impl VulnerabilityExt for Vulnerability {
+    fn pbn_text(&self) -> &'static str {
+        match self {
+            Vulnerability::None => "None",
+            Vulnerability::NorthSouth => "NS",
+            Vulnerability::EastWest => "EW",
+            Vulnerability::Both => "All",
+        }
+    }
+}
+
 /// Tracks which hands should be hidden in display
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct HiddenHands {
@@ -49,6 +68,119 @@ impl HiddenHands {
     }
 }
 
+/// Double-dummy makeable tricks for every declarer and strain
+/// (from the [DoubleDummyTricks] PBN tag)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoubleDummyTricks {
+    /// Tricks indexed by declarer (N, S, E, W) then strain (NT, S, H, D, C), as in the tag
+    tricks: [[u8; 5]; 4],
+}
+
+impl DoubleDummyTricks {
+    /// Strains in tag (and display) order
+    pub const STRAINS: [Strain; 5] = [
+        Strain::NoTrump,
+        Strain::Spades,
+        Strain::Hearts,
+        Strain::Diamonds,
+        Strain::Clubs,
+    ];
+
+    /// Declarers in tag (and display) order
+    pub const DECLARERS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    /// Create from tricks indexed by declarer then strain, in tag order
+    pub fn new(tricks: [[u8; 5]; 4]) -> Self {
+        Self { tricks }
+    }
+
+    /// Parse from the PBN tag value: 20 hex digits, one per declarer and strain
+    /// (e.g., "a9a9ba9a9b3434334343"). "**" and other placeholders give None.
+    pub fn from_pbn(s: &str) -> Option<Self> {
+        let digits: Vec<u8> = s
+            .trim()
+            .chars()
+            .map(|c| c.to_digit(16).filter(|d| *d <= 13).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        if digits.len() != 20 {
+            return None;
+        }
+        let mut tricks = [[0; 5]; 4];
+        for (i, row) in tricks.iter_mut().enumerate() {
+            row.copy_from_slice(&digits[i * 5..i * 5 + 5]);
+        }
+        Some(Self { tricks })
+    }
+
+    /// The PBN tag value: 20 hex digits, one per declarer and strain
+    pub fn to_pbn(&self) -> String {
+        self.tricks
+            .iter()
+            .flatten()
+            .map(|t| char::from_digit(*t as u32, 16).unwrap_or('0'))
+            .collect()
+    }
+
+    /// Tricks the declarer makes in the strain with best play all round
+    pub fn tricks(&self, declarer: Direction, strain: Strain) -> u8 {
+        let row = Self::DECLARERS
+            .iter()
+            .position(|d| *d == declarer)
+            .unwrap_or(0);
+        let col = Self::STRAINS.iter().position(|s| *s == strain).unwrap_or(0);
+        self.tricks[row][col]
+    }
+}
+
+/// Room of a teams match a board was played in (from the [Room] tag)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Room {
+    Open,
+    Closed,
+}
+
+impl Room {
+    /// Parse from the PBN tag value ("Open" or "Closed")
+    pub fn from_pbn(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "open" => Some(Room::Open),
+            "closed" => Some(Room::Closed),
+            _ => None,
+        }
+    }
+}
+
+/// How a board went at another table of the match: vugraph files record
+/// each room as its own game, and [merge_rooms] folds them into one board
+#[derive(Debug, Clone, Default)]
+pub struct TableResult {
+    pub room: Option<Room>,
+    pub table: Option<String>,
+    pub players: PlayerNames,
+    pub auction: Option<Auction>,
+    pub contract: Option<Contract>,
+    pub declarer: Option<Direction>,
+    pub play: Option<PlaySequence>,
+    pub result: Option<i8>,
+}
+
+impl TableResult {
+    /// Label for the table: the room's name from `rooms` (open, closed), or
+    /// else its [Table] tag
+    pub fn label(&self, rooms: [&str; 2]) -> Option<String> {
+        match (self.room, &self.table) {
+            (Some(Room::Open), _) => Some(rooms[0].to_string()),
+            (Some(Room::Closed), _) => Some(rooms[1].to_string()),
+            (None, table) => table.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Board {
     // Identification
@@ -58,6 +190,10 @@ pub struct Board {
     pub event: Option<String>,
     pub site: Option<String>,
     pub date: Option<String>,
+    /// Teams match details (from the [Room], [Stage] and [Table] tags)
+    pub room: Option<Room>,
+    pub stage: Option<String>,
+    pub table: Option<String>,
 
     // Setup
     pub dealer: Option<Direction>,
@@ -70,6 +206,8 @@ pub struct Board {
     // Bidding
     pub auction: Option<Auction>,
     pub contract: Option<Contract>,
+    /// The [Contract] tag was "Pass": all four players passed
+    pub passed_out: bool,
     pub declarer: Option<Direction>,
 
     // Play
@@ -84,6 +222,18 @@ pub struct Board {
 
     // Hidden hands (from [Hidden] tag)
     pub hidden: HiddenHands,
+
+    // Double-dummy analysis (from [DoubleDummyTricks] tag)
+    pub double_dummy: Option<DoubleDummyTricks>,
+    /// Opening leads that hold the contract double dummy (from [BestLeads] tag)
+    pub best_leads: Vec<Card>,
+
+    /// The other rooms' auctions and results, once rooms are merged
+    pub other_tables: Vec<TableResult>,
+    /// Results from every table (from the [ScoreTable] section)
+    pub score_table: Option<ScoreTable>,
+    /// The event's final ranking (from the [TotalScoreTable] section)
+    pub total_score_table: Option<ScoreTable>,
 }
 
 impl Board {
@@ -111,6 +261,20 @@ impl Board {
         self
     }
 
+    /// How the board went at its own table
+    pub fn table_result(&self) -> TableResult {
+        TableResult {
+            room: self.room,
+            table: self.table.clone(),
+            players: self.players.clone(),
+            auction: self.auction.clone(),
+            contract: self.contract.clone(),
+            declarer: self.declarer,
+            play: self.play.clone(),
+            result: self.result,
+        }
+    }
+
     pub fn title(&self) -> String {
         let mut parts = Vec::new();
 
@@ -131,11 +295,54 @@ impl Board {
         !self.commentary.is_empty()
     }
 
+    /// Non-blank commentary meant for everyone, i.e. without `{N: ...}` seat blocks
+    pub fn general_commentary(&self) -> impl Iterator<Item = &CommentaryBlock> {
+        self.commentary
+            .iter()
+            .filter(|block| block.is_general() && !block.is_blank())
+    }
+
     pub fn opening_lead_direction(&self) -> Option<Direction> {
         self.declarer.map(|d| d.next())
     }
 }
 
+/// Fold boards played at several tables of a match (the same board of the
+/// same event and stage, with the same deal, in another room or at another
+/// table) into the first of them, with the others in its `other_tables`.
+/// Boards without a room or table, and everything else, are kept in order.
+pub fn merge_rooms(boards: Vec<Board>) -> Vec<Board> {
+    let mut merged: Vec<Board> = Vec::with_capacity(boards.len());
+    for board in boards {
+        let at_table = board.room.is_some() || board.table.is_some();
+        let same_board = |other: &Board| {
+            other.board_id == board.board_id
+                && other.event == board.event
+                && other.stage == board.stage
+                && other.deal == board.deal
+                && (other.room, &other.table) != (board.room, &board.table)
+                && other
+                    .other_tables
+                    .iter()
+                    .all(|t| (t.room, &t.table) != (board.room, &board.table))
+        };
+        match merged
+            .iter_mut()
+            .find(|other| at_table && same_board(other))
+        {
+            Some(first) => {
+                first.other_tables.push(board.table_result());
+                first.commentary.extend(board.commentary);
+                first.score_table = first.score_table.take().or(board.score_table);
+                first.total_score_table =
+                    first.total_score_table.take().or(board.total_score_table);
+            }
+            None => merged.push(board),
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,6 +369,47 @@ mod tests {
         assert!(!Vulnerability::NorthSouth.is_vulnerable(Direction::East));
     }
 
+    #[test]
+    fn test_double_dummy_tricks_parsing() {
+        let dd = DoubleDummyTricks::from_pbn("a9a9ba9a9b3434334343").unwrap();
+        assert_eq!(dd.tricks(Direction::North, Strain::NoTrump), 10);
+        assert_eq!(dd.tricks(Direction::South, Strain::Clubs), 11);
+        assert_eq!(dd.tricks(Direction::East, Strain::Spades), 4);
+        assert_eq!(dd.tricks(Direction::West, Strain::Clubs), 3);
+
+        assert_eq!(dd.to_pbn(), "a9a9ba9a9b3434334343");
+        assert!(DoubleDummyTricks::from_pbn("**********").is_none());
+        assert!(DoubleDummyTricks::from_pbn("a9a9b").is_none());
+        assert!(DoubleDummyTricks::from_pbn("e9a9b3434399a9a93434").is_none());
+    }
+
+    #[test]
+    fn test_merge_rooms() {
+        let board = |number: u32, room: Room, declarer: Direction| Board {
+            number: Some(number),
+            board_id: Some(number.to_string()),
+            room: Some(room),
+            declarer: Some(declarer),
+            ..Default::default()
+        };
+        let boards = vec![
+            board(1, Room::Open, Direction::North),
+            board(2, Room::Open, Direction::East),
+            board(1, Room::Closed, Direction::South),
+            Board::new().with_number(3),
+            board(2, Room::Closed, Direction::West),
+        ];
+        let merged = merge_rooms(boards);
+        let numbers: Vec<_> = merged.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, [Some(1), Some(2), Some(3)]);
+        assert_eq!(merged[0].declarer, Some(Direction::North));
+        assert_eq!(merged[0].other_tables.len(), 1);
+        assert_eq!(merged[0].other_tables[0].room, Some(Room::Closed));
+        assert_eq!(merged[0].other_tables[0].declarer, Some(Direction::South));
+        assert_eq!(merged[1].other_tables[0].declarer, Some(Direction::West));
+        assert!(merged[2].other_tables.is_empty());
+    }
+
     #[test]
     fn test_board_title() {
         let board = Board::new()
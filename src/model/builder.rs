@@ -0,0 +1,277 @@
+//! Building boards in code, for deals that were never PBN text
+//!
+//! ```
+//! use pbn_to_pdf::model::{BoardBuilder, Direction};
+//!
+//! let board = BoardBuilder::new(1)
+//!     .north("AKQ2.J54.T9.8732")
+//!     .south("J43.AKQ.AK54.AKQ")
+//!     .hidden("EW")
+//!     .auction("1C-P-1S-P-3NT-AP")
+//!     .commentary("Declarer has **nine** top tricks; *don't* finesse.")
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(board.dealer, Some(Direction::North));
+//! ```
+//!
+//! Everything is checked when the board is built, so the setters can be
+//! chained in any order.
+
+use crate::error::ConfigError;
+use crate::parser::auction::parse_auction;
+use crate::parser::commentary::parse_commentary;
+use crate::parser::deal::{parse_deal, parse_hand};
+
+use super::scoring::{board_dealer, board_vulnerability};
+use super::{Board, Direction, HiddenHands, Vulnerability};
+
+/// Builds a [Board] from compact strings; see the module docs
+#[derive(Debug, Clone, Default)]
+pub struct BoardBuilder {
+    board: Board,
+    deal: Option<String>,
+    hands: Vec<(Direction, String)>,
+    auction: Option<String>,
+    commentary: Vec<String>,
+}
+
+impl BoardBuilder {
+    /// A board numbered `number`, with the dealer and vulnerability of that
+    /// number in the usual 16-board cycle
+    pub fn new(number: u32) -> Self {
+        let board = Board {
+            number: Some(number),
+            board_id: Some(number.to_string()),
+            dealer: Some(board_dealer(number)),
+            vulnerable: board_vulnerability(number),
+            ..Default::default()
+        };
+        Self {
+            board,
+            ..Default::default()
+        }
+    }
+
+    pub fn dealer(mut self, dealer: Direction) -> Self {
+        self.board.dealer = Some(dealer);
+        self
+    }
+
+    pub fn vulnerable(mut self, vulnerable: Vulnerability) -> Self {
+        self.board.vulnerable = vulnerable;
+        self
+    }
+
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.board.event = Some(event.into());
+        self
+    }
+
+    /// All four hands in PBN notation: "N:AKQ.JT9.876.5432 ..."
+    pub fn deal(mut self, deal: impl Into<String>) -> Self {
+        self.deal = Some(deal.into());
+        self
+    }
+
+    /// One hand as spades.hearts.diamonds.clubs, e.g. "AKQ2.J54.T9.8732";
+    /// replaces that seat's hand from [BoardBuilder::deal]
+    pub fn hand(mut self, seat: Direction, hand: impl Into<String>) -> Self {
+        self.hands.push((seat, hand.into()));
+        self
+    }
+
+    pub fn north(self, hand: impl Into<String>) -> Self {
+        self.hand(Direction::North, hand)
+    }
+
+    pub fn east(self, hand: impl Into<String>) -> Self {
+        self.hand(Direction::East, hand)
+    }
+
+    pub fn south(self, hand: impl Into<String>) -> Self {
+        self.hand(Direction::South, hand)
+    }
+
+    pub fn west(self, hand: impl Into<String>) -> Self {
+        self.hand(Direction::West, hand)
+    }
+
+    /// Seats whose hands are hidden in diagrams, e.g. "EW"
+    pub fn hidden(mut self, seats: &str) -> Self {
+        self.board.hidden = HiddenHands::from_pbn(seats);
+        self
+    }
+
+    /// The auction from the dealer, calls separated by spaces, dashes or
+    /// commas: "1N-P-2C-P-2H-P-3N-AP". Passes are "P", doubles "X" and
+    /// "XX", and "AP" ends the auction with three passes. The contract and
+    /// declarer are taken from it.
+    pub fn auction(mut self, auction: impl Into<String>) -> Self {
+        self.auction = Some(auction.into());
+        self
+    }
+
+    /// A commentary block in Markdown: `**bold**`, `*italic*` and `_italic_`,
+    /// with blank lines between paragraphs starting new blocks. Suits can be
+    /// written as symbols or as PBN escapes (`\S`, `\H`, `\D`, `\C`).
+    pub fn commentary(mut self, markdown: impl Into<String>) -> Self {
+        self.commentary.push(markdown.into());
+        self
+    }
+
+    /// The board, or the first string that didn't parse
+    pub fn build(self) -> Result<Board, ConfigError> {
+        let invalid = |what: &str, error: String| {
+            ConfigError::InvalidInput(format!("board {}: {}: {}", self.label(), what, error))
+        };
+        let mut board = self.board.clone();
+
+        if let Some(ref deal) = self.deal {
+            board.deal = parse_deal(deal).map_err(|e| invalid("deal", e))?;
+        }
+        for (seat, hand) in &self.hands {
+            let hand = parse_hand(hand).map_err(|e| invalid("hand", e))?;
+            board.deal.set_hand(*seat, hand);
+        }
+
+        if let Some(ref auction) = self.auction {
+            let dealer = board.dealer.unwrap_or(Direction::North);
+            let auction = parse_auction(dealer, &compact_auction(auction))
+                .map_err(|e| invalid("auction", e))?;
+            if let Some(contract) = auction.final_contract() {
+                board.declarer = Some(contract.declarer);
+                board.contract = Some(contract);
+            }
+            board.auction = Some(auction);
+        }
+
+        for markdown in &self.commentary {
+            for paragraph in markdown.split("\n\n").filter(|p| !p.trim().is_empty()) {
+                let block = parse_commentary(&markdown_to_pbn(paragraph))
+                    .map_err(|e| invalid("commentary", e))?;
+                board.commentary.push(block);
+            }
+        }
+
+        Ok(board)
+    }
+
+    fn label(&self) -> String {
+        self.board
+            .board_id
+            .clone()
+            .unwrap_or_else(|| "?".to_string())
+    }
+}
+
+/// PBN auction text from the compact form: "1n-p-3n-ap" becomes "1NT Pass 3NT AP"
+fn compact_auction(auction: &str) -> String {
+    auction
+        .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let upper = token.to_uppercase();
+            match upper.as_str() {
+                "P" | "PASS" => "Pass".to_string(),
+                "D" | "DBL" => "X".to_string(),
+                "R" | "RDBL" => "XX".to_string(),
+                _ => match upper.strip_suffix('N') {
+                    Some(level) if level.len() == 1 => format!("{}NT", level),
+                    _ => upper,
+                },
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// PBN commentary markup from a paragraph of Markdown
+fn markdown_to_pbn(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut bold = false;
+    let mut italic = false;
+    let mut chars = markdown.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '_' if chars.peek() == Some(&c) => {
+                chars.next();
+                text.push_str(if bold { "</b>" } else { "<b>" });
+                bold = !bold;
+            }
+            '*' | '_' => {
+                text.push_str(if italic { "</i>" } else { "<i>" });
+                italic = !italic;
+            }
+            // Markdown joins the lines of a paragraph
+            '\n' => text.push(' '),
+            _ => text.push(c),
+        }
+    }
+    // Close anything left open rather than failing on a stray marker
+    if italic {
+        text.push_str("</i>");
+    }
+    if bold {
+        text.push_str("</b>");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Call, Strain, TextSpan};
+
+    #[test]
+    fn test_build_board() {
+        let board = BoardBuilder::new(2)
+            .north("AKQ2.J54.T9.8732")
+            .south("J43.AKQ.AK54.AKQ")
+            .auction("p 3n ap")
+            .build()
+            .unwrap();
+
+        assert_eq!(board.dealer, Some(Direction::East));
+        assert_eq!(board.vulnerable, Vulnerability::NorthSouth);
+        assert_eq!(board.deal.north.card_count(), 13);
+        assert_eq!(board.deal.east.card_count(), 0);
+
+        let auction = board.auction.unwrap();
+        assert_eq!(auction.calls.len(), 5);
+        assert_eq!(auction.calls[0].call, Call::Pass);
+        let contract = board.contract.unwrap();
+        assert_eq!((contract.level, contract.suit), (3, Strain::NoTrump));
+        assert_eq!(board.declarer, Some(Direction::South));
+    }
+
+    #[test]
+    fn test_invalid_hand() {
+        let error = BoardBuilder::new(5).west("AKQ.JT9").build().unwrap_err();
+        assert!(error.to_string().contains("board 5: hand"));
+    }
+
+    #[test]
+    fn test_markdown_commentary() {
+        assert_eq!(
+            markdown_to_pbn("Take **nine** tricks,\n*then* _stop_"),
+            "Take <b>nine</b> tricks, <i>then</i> <i>stop</i>"
+        );
+        assert_eq!(markdown_to_pbn("**unclosed"), "<b>unclosed</b>");
+
+        let board = BoardBuilder::new(1)
+            .commentary("First **block**.\n\nSecond block.")
+            .build()
+            .unwrap();
+        assert_eq!(board.commentary.len(), 2);
+        assert!(board.commentary[0]
+            .content
+            .spans
+            .contains(&TextSpan::Bold("block".to_string())));
+    }
+
+    #[test]
+    fn test_compact_auction() {
+        assert_eq!(compact_auction("1n-p-2c,x 2h"), "1NT Pass 2C X 2H");
+        assert_eq!(compact_auction("1S D R AP"), "1S X XX AP");
+    }
+}
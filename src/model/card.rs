@@ -46,6 +46,8 @@ pub trait RankExt {
     fn hcp_value(&self) -> u8;
     /// Display string for rendering: "10" for Ten, single char for others
     fn display_str(&self) -> &'static str;
+    /// Display string with the Ten shown as "T" when `ten_as_t` is set
+    fn display_str_with(&self, ten_as_t: bool) -> &'static str;
 }
 
 impl RankExt for Rank {
@@ -82,6 +84,14 @@ impl RankExt for Rank {
             Rank::Two => "2",
         }
     }
+
+    /// Display string with the Ten shown as "T" when `ten_as_t` is set
+    fn display_str_with(&self, ten_as_t: bool) -> &'static str {
+        match self {
+            Rank::Ten if ten_as_t => "T",
+            _ => self.display_str(),
+        }
+    }
 }
 
 /// Compare ranks in display order (Ace > King > ... > Two)
@@ -148,4 +158,11 @@ mod tests {
             Ordering::Greater
         ); // Two sorts after Three
     }
+
+    #[test]
+    fn test_ten_display_styles() {
+        assert_eq!(Rank::Ten.display_str_with(false), "10");
+        assert_eq!(Rank::Ten.display_str_with(true), "T");
+        assert_eq!(Rank::Jack.display_str_with(true), "J");
+    }
 }
@@ -1,4 +1,5 @@
 use super::card::{Rank, RankExt, Suit};
+use super::deal::Direction;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TextSpan {
@@ -19,7 +20,19 @@ pub enum TextSpan {
         suit: Suit,
         rank: Rank,
     },
+    /// Hyperlink, from `<a href="...">...</a>` or a bare URL in the text
+    Link {
+        text: String,
+        url: String,
+    },
+    /// Raised text, from `<sup>...</sup>` (note references, exponents)
+    Superscript(String),
+    /// Lowered text, from `<sub>...</sub>`
+    Subscript(String),
     LineBreak,
+    /// Column separator in a table row, from a tab or `<td>` table markup.
+    /// Consecutive lines containing tabs are laid out as one table.
+    Tab,
 }
 
 impl TextSpan {
@@ -58,6 +71,21 @@ impl TextSpan {
             rgb,
         }
     }
+
+    pub fn superscript(s: impl Into<String>) -> Self {
+        TextSpan::Superscript(s.into())
+    }
+
+    pub fn subscript(s: impl Into<String>) -> Self {
+        TextSpan::Subscript(s.into())
+    }
+
+    pub fn link(s: impl Into<String>, url: impl Into<String>) -> Self {
+        TextSpan::Link {
+            text: s.into(),
+            url: url.into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -89,9 +117,11 @@ impl FormattedText {
             | TextSpan::Bold(s)
             | TextSpan::Italic(s)
             | TextSpan::BoldItalic(s)
-            | TextSpan::Underline(s) => s.trim().is_empty(),
-            TextSpan::Colored { text, .. } => text.trim().is_empty(),
-            TextSpan::LineBreak => true,
+            | TextSpan::Underline(s)
+            | TextSpan::Superscript(s)
+            | TextSpan::Subscript(s) => s.trim().is_empty(),
+            TextSpan::Colored { text, .. } | TextSpan::Link { text, .. } => text.trim().is_empty(),
+            TextSpan::LineBreak | TextSpan::Tab => true,
             // Suit symbols and card refs are not whitespace
             TextSpan::SuitSymbol(_) | TextSpan::CardRef { .. } => false,
         })
@@ -105,10 +135,12 @@ impl FormattedText {
                 | TextSpan::Bold(s)
                 | TextSpan::Italic(s)
                 | TextSpan::BoldItalic(s)
-                | TextSpan::Underline(s) => {
+                | TextSpan::Underline(s)
+                | TextSpan::Superscript(s)
+                | TextSpan::Subscript(s) => {
                     result.push_str(s);
                 }
-                TextSpan::Colored { text, .. } => {
+                TextSpan::Colored { text, .. } | TextSpan::Link { text, .. } => {
                     result.push_str(text);
                 }
                 TextSpan::SuitSymbol(suit) => {
@@ -121,20 +153,67 @@ impl FormattedText {
                 TextSpan::LineBreak => {
                     result.push('\n');
                 }
+                TextSpan::Tab => {
+                    result.push('\t');
+                }
             }
         }
         result
     }
 }
 
+/// Where a commentary block sits in the board record
+///
+/// PBN commentary belongs to the tag or section before it, and Bridge Composer
+/// uses that to tell its commentary kinds apart (and to show or hide them via
+/// `[BCFlags]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentaryPlacement {
+    /// Before the `[Deal]` tag, e.g. after `[Event]` or `[Date]`
+    Event,
+    /// Right after the `[Deal]` tag
+    Diagram,
+    /// After the auction section and its notes
+    Auction,
+    /// After the result tags or the play section
+    #[default]
+    Final,
+}
+
 #[derive(Debug, Clone)]
 pub struct CommentaryBlock {
     pub content: FormattedText,
+    /// Position in the board record, which decides where layouts show the block
+    pub placement: CommentaryPlacement,
+    /// Seat the block is meant for, from a `{N: ...}` prefix. Such blocks are
+    /// shown on that seat's bidding practice page only.
+    pub seat: Option<Direction>,
 }
 
 impl CommentaryBlock {
     pub fn new(content: FormattedText) -> Self {
-        Self { content }
+        Self {
+            content,
+            placement: CommentaryPlacement::default(),
+            seat: None,
+        }
+    }
+
+    /// Set where the block sits in the board record
+    pub fn with_placement(mut self, placement: CommentaryPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Restrict the block to one seat
+    pub fn for_seat(mut self, seat: Direction) -> Self {
+        self.seat = Some(seat);
+        self
+    }
+
+    /// Whether the block is meant for everyone rather than one seat
+    pub fn is_general(&self) -> bool {
+        self.seat.is_none()
     }
 
     pub fn is_empty(&self) -> bool {
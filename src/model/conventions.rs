@@ -0,0 +1,158 @@
+//! Convention annotations from a system definition file
+//!
+//! A `Conventions` list maps bidding sequences to their meaning ("1NT" = "15-17",
+//! "1NT 2♣" = "Stayman"). Applied to an auction, every call that completes a
+//! listed sequence gets a footnote, so the same explanation doesn't have to be
+//! written as a note in every board.
+//!
+//! Sequences list the calls made so far by both sides, ignoring passes: "2♦"
+//! is an opening 2♦, "1NT 2♣" is a 2♣ response (or overcall) to 1NT.
+
+use super::auction::{Auction, Call};
+use super::board::Board;
+
+/// One sequence and its meaning
+#[derive(Debug, Clone, PartialEq)]
+pub struct Convention {
+    pub sequence: Vec<Call>,
+    pub meaning: String,
+}
+
+/// Conventions to footnote automatically
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conventions {
+    pub entries: Vec<Convention>,
+}
+
+impl Conventions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sequence (later entries for the same sequence are ignored)
+    pub fn add(&mut self, sequence: Vec<Call>, meaning: impl Into<String>) {
+        self.entries.push(Convention {
+            sequence,
+            meaning: meaning.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Meaning of the call that completes `sequence`, if listed
+    pub fn meaning(&self, sequence: &[Call]) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|c| c.sequence == sequence)
+            .map(|c| c.meaning.as_str())
+    }
+
+    /// Footnote every call in the auction that completes a listed sequence.
+    ///
+    /// Calls that already carry an annotation are left alone. Notes are
+    /// numbered after the auction's own notes, and a meaning used twice in the
+    /// same auction shares one note.
+    pub fn annotate(&self, auction: &mut Auction) {
+        // Find the matching calls first, then attach notes
+        let mut sequence: Vec<&Call> = Vec::new();
+        let mut matches = Vec::new();
+        for (i, annotated) in auction.calls.iter().enumerate() {
+            if matches!(annotated.call, Call::Pass | Call::Continue) {
+                continue;
+            }
+            sequence.push(&annotated.call);
+            if annotated.annotation.is_some() {
+                continue;
+            }
+            if let Some(convention) = self
+                .entries
+                .iter()
+                .find(|c| c.sequence.iter().eq(sequence.iter().copied()))
+            {
+                matches.push((i, convention.meaning.clone()));
+            }
+        }
+
+        let mut next_note = auction
+            .notes
+            .keys()
+            .max()
+            .map_or(1, |n| n.saturating_add(1));
+        for (i, meaning) in matches {
+            let existing = auction
+                .notes
+                .iter()
+                .find(|(_, text)| **text == meaning)
+                .map(|(num, _)| *num);
+            let num = match existing {
+                Some(num) => num,
+                None => {
+                    let num = next_note;
+                    next_note = next_note.saturating_add(1);
+                    auction.add_note(num, meaning);
+                    num
+                }
+            };
+            auction.calls[i].annotation = Some(num.to_string());
+        }
+    }
+
+    /// Footnote the auctions of all boards
+    pub fn annotate_boards(&self, boards: &mut [Board]) {
+        if self.is_empty() {
+            return;
+        }
+        for auction in boards.iter_mut().filter_map(|b| b.auction.as_mut()) {
+            self.annotate(auction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Direction, Strain};
+
+    fn bid(level: u8, strain: Strain) -> Call {
+        Call::Bid { level, strain }
+    }
+
+    #[test]
+    fn test_annotate_opening_and_response() {
+        let mut conventions = Conventions::new();
+        conventions.add(vec![bid(1, Strain::NoTrump)], "15-17");
+        conventions.add(
+            vec![bid(1, Strain::NoTrump), bid(2, Strain::Clubs)],
+            "Stayman",
+        );
+
+        let mut auction = Auction::new(Direction::North);
+        auction.add_call(bid(1, Strain::NoTrump));
+        auction.add_call(Call::Pass);
+        auction.add_call(bid(2, Strain::Clubs));
+        auction.add_call(Call::Pass);
+        conventions.annotate(&mut auction);
+
+        assert_eq!(auction.calls[0].annotation.as_deref(), Some("1"));
+        assert_eq!(auction.calls[2].annotation.as_deref(), Some("2"));
+        assert_eq!(auction.notes.get(&2).map(String::as_str), Some("Stayman"));
+    }
+
+    #[test]
+    fn test_existing_notes_are_kept() {
+        let mut conventions = Conventions::new();
+        conventions.add(vec![bid(2, Strain::Diamonds)], "Multi");
+
+        let mut auction = Auction::new(Direction::North);
+        auction.add_annotated_call(bid(1, Strain::Spades), Some("1".to_string()));
+        auction.add_note(1, "Five-card major".to_string());
+        auction.add_call(bid(2, Strain::Diamonds));
+        conventions.annotate(&mut auction);
+
+        // 2♦ here is not an opening bid
+        assert!(auction.calls[1].annotation.is_none());
+        assert_eq!(auction.notes.len(), 1);
+    }
+}
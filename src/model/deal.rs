@@ -10,6 +10,8 @@ pub trait DirectionExt {
     fn all() -> [Direction; 4];
     /// Returns the table position (0-3) for bidding display (West=0, North=1, East=2, South=3)
     fn table_position(&self) -> usize;
+    /// The seat as written in PBN ("N", "E", "S", "W")
+    fn pbn_text(&self) -> &'static str;
 }
 
 impl DirectionExt for Direction {
@@ -25,6 +27,15 @@ impl DirectionExt for Direction {
             Direction::South => 3,
         }
     }
+
+    fn pbn_text(&self) -> &'static str {
+        match self {
+            Direction::North => "N",
+            Direction::East => "E",
+            Direction::South => "S",
+            Direction::West => "W",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
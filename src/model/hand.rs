@@ -1,22 +1,28 @@
 use std::fmt;
 
-use super::card::{rank_display_cmp, Rank, RankExt, Suit, SUITS_DISPLAY_ORDER};
+use super::card::{
+    rank_display_cmp, Rank, RankExt, Suit, RANKS_DISPLAY_ORDER, SUITS_DISPLAY_ORDER,
+};
 
 /// Cards held in a single suit, stored in display order (Ace first)
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Holding {
     /// Ranks stored in display order (Ace, King, Queen, ... Two)
     pub ranks: Vec<Rank>,
+    /// Spot cards whose rank doesn't matter (`x` in the deal), shown after
+    /// the ranks
+    pub spots: usize,
 }
 
 impl Holding {
     pub fn new() -> Self {
-        Self { ranks: Vec::new() }
+        Self::default()
     }
 
     pub fn from_ranks(ranks: impl IntoIterator<Item = Rank>) -> Self {
         let mut holding = Self {
             ranks: ranks.into_iter().collect(),
+            spots: 0,
         };
         holding.sort_display_order();
         holding
@@ -34,12 +40,17 @@ impl Holding {
         }
     }
 
+    /// Add a spot card placeholder (`x`)
+    pub fn add_spot(&mut self) {
+        self.spots += 1;
+    }
+
     pub fn len(&self) -> usize {
-        self.ranks.len()
+        self.ranks.len() + self.spots
     }
 
     pub fn is_empty(&self) -> bool {
-        self.ranks.is_empty()
+        self.len() == 0
     }
 
     pub fn hcp(&self) -> u8 {
@@ -47,23 +58,45 @@ impl Holding {
     }
 
     pub fn is_void(&self) -> bool {
-        self.ranks.is_empty()
+        self.is_empty()
     }
 
     pub fn contains(&self, rank: &Rank) -> bool {
         self.ranks.contains(rank)
     }
+
+    /// Each card as shown in a diagram: the ranks high to low, then an "x"
+    /// for each spot card
+    pub fn card_texts(&self, ten_as_t: bool) -> impl Iterator<Item = &'static str> + '_ {
+        self.ranks
+            .iter()
+            .map(move |rank| rank.display_str_with(ten_as_t))
+            .chain(std::iter::repeat("x").take(self.spots))
+    }
+
+    /// Ranks to draw as card images, high to low: the known ranks, then for
+    /// each spot card the lowest rank not held here and not in `taken`
+    pub fn card_ranks(&self, taken: &[Rank]) -> Vec<Rank> {
+        let mut spares: Vec<Rank> = RANKS_DISPLAY_ORDER
+            .into_iter()
+            .rev()
+            .filter(|rank| !self.ranks.contains(rank) && !taken.contains(rank))
+            .take(self.spots)
+            .collect();
+        spares.reverse();
+        self.ranks.iter().copied().chain(spares).collect()
+    }
 }
 
 impl fmt::Display for Holding {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.ranks.is_empty() {
+        if self.is_empty() {
             write!(f, "-")
         } else {
             for rank in &self.ranks {
                 write!(f, "{}", rank.display_str())?;
             }
-            Ok(())
+            write!(f, "{}", "x".repeat(self.spots))
         }
     }
 }
@@ -190,6 +223,28 @@ mod tests {
         assert_eq!(holding.to_string(), "AK2");
     }
 
+    #[test]
+    fn test_spot_cards() {
+        let mut holding = Holding::from_ranks([Rank::Queen, Rank::Ace]);
+        holding.add_spot();
+        holding.add_spot();
+        assert_eq!(holding.len(), 4);
+        assert_eq!(holding.hcp(), 6);
+        assert_eq!(holding.to_string(), "AQxx");
+        assert_eq!(
+            holding.card_texts(false).collect::<Vec<_>>(),
+            ["A", "Q", "x", "x"]
+        );
+        assert_eq!(
+            holding.card_ranks(&[]),
+            [Rank::Ace, Rank::Queen, Rank::Three, Rank::Two]
+        );
+        assert_eq!(
+            holding.card_ranks(&[Rank::Two]),
+            [Rank::Ace, Rank::Queen, Rank::Four, Rank::Three]
+        );
+    }
+
     #[test]
     fn test_hand_shape() {
         let mut hand = Hand::new();
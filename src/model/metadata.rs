@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Layout settings parsed from PBN % header directives
 #[derive(Debug, Clone, Default)]
 pub struct LayoutSettings {
@@ -20,7 +22,8 @@ pub struct LayoutSettings {
     pub two_col_auctions: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Page margins in mm
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Margins {
     pub left: f32,
     pub right: f32,
@@ -58,7 +61,7 @@ impl PaperSize {
 }
 
 /// Single font specification from PBN
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FontSpec {
     pub family: String,
     pub size: f32,
@@ -87,7 +90,7 @@ impl FontSpec {
 }
 
 /// Font settings parsed from PBN header
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FontSettings {
     pub card_table: Option<FontSpec>,
     pub commentary: Option<FontSpec>,
@@ -130,7 +133,7 @@ impl FontSettings {
 }
 
 /// Color settings for suits
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ColorSettings {
     pub spades: (u8, u8, u8),
     pub hearts: (u8, u8, u8),
@@ -157,6 +160,12 @@ pub struct PbnMetadata {
     pub created: Option<String>,
     pub title_event: Option<String>,
     pub title_date: Option<String>,
+    pub title_site: Option<String>,
+    pub title_club: Option<String>,
+    /// Logo file name (%HRTitleLogo), relative to the PBN file
+    pub title_logo: Option<String>,
+    /// Attribution printed at the bottom of every page (%HRCopyright)
+    pub copyright: Option<String>,
     pub layout: LayoutSettings,
     pub fonts: FontSettings,
     pub colors: ColorSettings,
@@ -2,21 +2,32 @@ pub mod analysis;
 pub mod auction;
 pub mod bcflags;
 pub mod board;
+pub mod builder;
 pub mod card;
 pub mod commentary;
+pub mod conventions;
 pub mod deal;
 pub mod hand;
 pub mod metadata;
 pub mod play;
+pub mod score_table;
+pub mod scoring;
+pub mod solver;
 
 pub use auction::{
-    AnnotatedCall, Auction, BidSuit, Call, CallExt, Contract, FinalContract, Strain,
+    AnnotatedCall, Auction, BidSuit, Call, CallExt, Contract, FinalContract, Strain, StrainExt,
 };
 pub use bcflags::BCFlags;
-pub use board::{Board, HiddenHands, PlayerNames, Vulnerability};
+pub use board::{
+    merge_rooms, Board, DoubleDummyTricks, HiddenHands, PlayerNames, Room, TableResult,
+    Vulnerability, VulnerabilityExt,
+};
+pub use builder::BoardBuilder;
 pub use card::{Card, Rank, RankExt, Suit, SuitExt, RANKS_DISPLAY_ORDER, SUITS_DISPLAY_ORDER};
-pub use commentary::{CommentaryBlock, FormattedText, TextSpan};
+pub use commentary::{CommentaryBlock, CommentaryPlacement, FormattedText, TextSpan};
+pub use conventions::{Convention, Conventions};
 pub use deal::{Deal, Direction, DirectionExt};
 pub use hand::{Hand, Holding};
 pub use metadata::{FontSettings, FontSpec, PbnMetadata};
 pub use play::{PlaySequence, Trick};
+pub use score_table::{ScoreTable, Standing};
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::card::Card;
 use super::deal::Direction;
 
@@ -6,6 +8,8 @@ pub struct Trick {
     pub leader: Direction,
     pub cards: [Option<Card>; 4],
     pub winner: Option<Direction>,
+    /// Note referenced by =N= after each card
+    pub notes: [Option<u8>; 4],
 }
 
 impl Trick {
@@ -14,6 +18,7 @@ impl Trick {
             leader,
             cards: [None, None, None, None],
             winner: None,
+            notes: [None; 4],
         }
     }
 
@@ -26,12 +31,20 @@ impl Trick {
             self.cards[position] = Some(card);
         }
     }
+
+    pub fn set_note(&mut self, position: usize, number: u8) {
+        if position < 4 {
+            self.notes[position] = Some(number);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PlaySequence {
     pub opening_leader: Direction,
     pub tricks: Vec<Trick>,
+    /// Notes referenced by =N= in the play, from [Note] tags after [Play]
+    pub notes: HashMap<u8, String>,
 }
 
 impl PlaySequence {
@@ -39,9 +52,34 @@ impl PlaySequence {
         Self {
             opening_leader,
             tricks: Vec::new(),
+            notes: HashMap::new(),
         }
     }
 
+    pub fn add_note(&mut self, number: u8, text: String) {
+        self.notes.insert(number, text);
+    }
+
+    /// Notes in number order, with the cards that reference them, for
+    /// footnotes under the play
+    pub fn footnotes(&self) -> Vec<(u8, Vec<Card>, &str)> {
+        let mut numbers: Vec<&u8> = self.notes.keys().collect();
+        numbers.sort();
+        numbers
+            .into_iter()
+            .map(|number| {
+                let cards = self
+                    .tricks
+                    .iter()
+                    .flat_map(|trick| trick.cards.iter().zip(&trick.notes))
+                    .filter(|(_, note)| **note == Some(*number))
+                    .filter_map(|(card, _)| *card)
+                    .collect();
+                (*number, cards, self.notes[number].as_str())
+            })
+            .collect()
+    }
+
     pub fn add_trick(&mut self, trick: Trick) {
         self.tricks.push(trick);
     }
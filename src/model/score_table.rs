@@ -0,0 +1,233 @@
+//! Results from every table of a board (the PBN `[ScoreTable]` section)
+//!
+//! The tag value names the columns, each with its width and alignment
+//! (`"PairId_NS\2R;PairId_EW\2R;Contract\5L;Declarer\1R;Result\2R"`), and one
+//! line per table follows:
+//!
+//! ```text
+//! [ScoreTable "PairId_NS\2R;PairId_EW\2R;Contract\5L;Declarer\1R;Result\2R"]
+//!  1  4 3NT   S 10
+//!  2  3 "4S"  N  9
+//!  3  1 Pass  - -
+//! ```
+//!
+//! Values may be quoted, and `-` leaves a field empty. `[TotalScoreTable]`
+//! uses the same format for the event's final ranking, one line per pair.
+
+use super::auction::Contract;
+use super::board::Board;
+use super::deal::Direction;
+use super::scoring::{matchpoints, TableResult};
+
+/// The columns and rows of a `[ScoreTable]` or `[TotalScoreTable]` section
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoreTable {
+    /// Column names, without their width and alignment
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ScoreTable {
+    /// An empty table with the columns named in a `[ScoreTable]` tag value
+    pub fn from_pbn(value: &str) -> Self {
+        let columns = value
+            .split(';')
+            .map(|column| column.split('\\').next().unwrap_or_default().trim())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a line of the section as a row
+    pub fn add_row(&mut self, line: &str) {
+        let fields = split_fields(line);
+        if !fields.is_empty() {
+            self.rows.push(fields);
+        }
+    }
+
+    /// A row's value in the named column (case-insensitive), unless empty
+    /// or `-`
+    pub fn value(&self, row: usize, column: &str) -> Option<&str> {
+        let index = self
+            .columns
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(column))?;
+        let value = self.rows.get(row)?.get(index)?.as_str();
+        (!value.is_empty() && value != "-").then_some(value)
+    }
+
+    /// Each table's result on `board`, for scoring. Pairs are named by the
+    /// `Names_` columns, else the `PairId_` ones, else "NS 1", "EW 1" and so
+    /// on; rows without a readable contract or result are left out.
+    pub fn results(&self, board: &Board) -> Vec<TableResult> {
+        let Some(number) = board.number else {
+            return Vec::new();
+        };
+        (0..self.rows.len())
+            .filter_map(|row| {
+                let pair = |side: &str| {
+                    self.value(row, &format!("Names_{}", side))
+                        .or_else(|| self.value(row, &format!("PairId_{}", side)))
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("{} {}", side, row + 1))
+                };
+                let contract = self.contract(row)?;
+                let tricks = match contract {
+                    None => 0,
+                    Some(_) => self
+                        .value(row, "Result")?
+                        .parse()
+                        .ok()
+                        .filter(|&t| t <= 13)?,
+                };
+                Some(TableResult {
+                    board: number,
+                    ns: pair("NS"),
+                    ew: pair("EW"),
+                    vulnerable: board.vulnerable,
+                    contract,
+                    tricks,
+                })
+            })
+            .collect()
+    }
+
+    /// North-South matchpoints of each result from [ScoreTable::results]
+    pub fn matchpoints(&self, board: &Board) -> Vec<f32> {
+        let scores: Vec<i32> = self
+            .results(board)
+            .iter()
+            .map(TableResult::ns_score)
+            .collect();
+        matchpoints(&scores)
+    }
+
+    /// The pairs' standings in a `[TotalScoreTable]`, in the table's order
+    ///
+    /// Pairs are named by their `PairId` and `Names`; the score is the first
+    /// `TotalScore` column (`TotalScoreMP`, `TotalScoreIMP`, ...).
+    pub fn standings(&self) -> Vec<Standing> {
+        let score_column = self
+            .columns
+            .iter()
+            .find(|name| name.to_ascii_lowercase().starts_with("totalscore"));
+        (0..self.rows.len())
+            .map(|row| {
+                let pair = match (self.value(row, "PairId"), self.value(row, "Names")) {
+                    (Some(id), Some(names)) => format!("{} {}", id, names),
+                    (Some(text), None) | (None, Some(text)) => text.to_string(),
+                    (None, None) => (row + 1).to_string(),
+                };
+                Standing {
+                    rank: self.value(row, "Rank").map(str::to_string),
+                    pair,
+                    score: score_column
+                        .and_then(|column| self.value(row, column))
+                        .map(str::to_string),
+                    percent: self.value(row, "TotalPercentage").map(str::to_string),
+                }
+            })
+            .collect()
+    }
+
+    /// A row's contract: Some(None) when passed out, None when unreadable
+    fn contract(&self, row: usize) -> Option<Option<Contract>> {
+        let Some(mut contract) = Contract::parse_tag(self.value(row, "Contract")?)? else {
+            return Some(None);
+        };
+        contract.declarer = self
+            .value(row, "Declarer")?
+            .chars()
+            .next()
+            .and_then(Direction::from_char)?;
+        Some(Some(contract))
+    }
+}
+
+/// A pair's place in the event's final ranking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub rank: Option<String>,
+    pub pair: String,
+    pub score: Option<String>,
+    pub percent: Option<String>,
+}
+
+/// Whitespace-separated fields, with double quotes around fields that
+/// contain spaces
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let (field, after) = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => rest.split_once(char::is_whitespace).unwrap_or((rest, "")),
+        };
+        fields.push(field.to_string());
+        rest = after.trim_start();
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Vulnerability;
+
+    fn table() -> ScoreTable {
+        let mut table = ScoreTable::from_pbn(
+            "PairId_NS\\2R;PairId_EW\\2R;Contract\\5L;Declarer\\1R;Result\\2R",
+        );
+        for line in [" 1  4 3NT   S 10", " 2  3 \"4S\"  N  9", " 3  1 Pass  - -"] {
+            table.add_row(line);
+        }
+        table
+    }
+
+    #[test]
+    fn test_score_table_columns_and_values() {
+        let table = table();
+        assert_eq!(
+            table.columns,
+            ["PairId_NS", "PairId_EW", "Contract", "Declarer", "Result"]
+        );
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(table.value(1, "contract"), Some("4S"));
+        assert_eq!(table.value(2, "Declarer"), None);
+        assert_eq!(table.value(0, "Score_NS"), None);
+    }
+
+    #[test]
+    fn test_score_table_results_and_matchpoints() {
+        let board = Board {
+            number: Some(1),
+            vulnerable: Vulnerability::None,
+            ..Default::default()
+        };
+        let results = table().results(&board);
+        let scores: Vec<i32> = results.iter().map(TableResult::ns_score).collect();
+        assert_eq!(scores, [430, -50, 0]);
+        assert_eq!((results[0].ns.as_str(), results[0].ew.as_str()), ("1", "4"));
+        assert_eq!(table().matchpoints(&board), [2.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_total_score_table_standings() {
+        let mut table = ScoreTable::from_pbn(
+            "Rank\\2R;PairId\\2R;TotalScoreMP\\5R;TotalPercentage\\5R;Names\\20L",
+        );
+        table.add_row(" 1  4  31.0 64.58 \"Smith - Jones\"");
+        table.add_row(" 2  1  -    -     -");
+        let standings = table.standings();
+        assert_eq!(standings[0].pair, "4 Smith - Jones");
+        assert_eq!(standings[0].score.as_deref(), Some("31.0"));
+        assert_eq!(standings[0].percent.as_deref(), Some("64.58"));
+        assert_eq!(standings[1].pair, "1");
+        assert_eq!(standings[1].score, None);
+    }
+}
@@ -0,0 +1,359 @@
+//! Duplicate bridge scoring
+//!
+//! Scores a contract from the number of tricks declarer took, then compares
+//! the results of each board across the field as matchpoints or IMPs. The
+//! par score of a deal comes from its double-dummy tricks.
+
+use super::auction::{Contract, Strain};
+use super::board::{DoubleDummyTricks, Vulnerability};
+use super::deal::Direction;
+
+/// Upper bounds of the score differences for 0, 1, 2, ... 23 IMPs
+const IMP_BOUNDS: [i32; 24] = [
+    10, 40, 80, 120, 160, 210, 260, 310, 360, 420, 490, 590, 740, 890, 1090, 1290, 1490, 1740,
+    1990, 2240, 2490, 2990, 3490, 3990,
+];
+
+/// One table's result on a board
+#[derive(Debug, Clone)]
+pub struct TableResult {
+    pub board: u32,
+    /// North-South pair
+    pub ns: String,
+    /// East-West pair
+    pub ew: String,
+    pub vulnerable: Vulnerability,
+    /// The contract, or None if the board was passed out
+    pub contract: Option<Contract>,
+    /// Tricks taken by declarer
+    pub tricks: u8,
+}
+
+impl TableResult {
+    /// Score for North-South (negative when East-West scored)
+    pub fn ns_score(&self) -> i32 {
+        let Some(contract) = &self.contract else {
+            return 0;
+        };
+        let score = contract_score(
+            contract,
+            is_vulnerable(self.vulnerable, contract.declarer),
+            self.tricks,
+        );
+        match contract.declarer {
+            Direction::North | Direction::South => score,
+            Direction::East | Direction::West => -score,
+        }
+    }
+}
+
+/// Whether a seat is vulnerable
+pub fn is_vulnerable(vulnerable: Vulnerability, seat: Direction) -> bool {
+    match vulnerable {
+        Vulnerability::None => false,
+        Vulnerability::Both => true,
+        Vulnerability::NorthSouth => matches!(seat, Direction::North | Direction::South),
+        Vulnerability::EastWest => matches!(seat, Direction::East | Direction::West),
+    }
+}
+
+/// Dealer of a board number: North deals board 1, East board 2, and so on
+pub fn board_dealer(board: u32) -> Direction {
+    match (board.max(1) - 1) % 4 {
+        0 => Direction::North,
+        1 => Direction::East,
+        2 => Direction::South,
+        _ => Direction::West,
+    }
+}
+
+/// Vulnerability of a board number in the standard 16-board cycle
+pub fn board_vulnerability(board: u32) -> Vulnerability {
+    // Vulnerability rotates every 4 boards, shifted one seat in each group
+    let index = (board.max(1) - 1) % 16;
+    let group = index / 4;
+    match (index + group) % 4 {
+        0 => Vulnerability::None,
+        1 => Vulnerability::NorthSouth,
+        2 => Vulnerability::EastWest,
+        _ => Vulnerability::Both,
+    }
+}
+
+/// Duplicate score for declarer's side (negative when the contract failed)
+pub fn contract_score(contract: &Contract, vulnerable: bool, tricks: u8) -> i32 {
+    let level = contract.level as i32;
+    let tricks = tricks as i32;
+    let multiplier = if contract.redoubled {
+        4
+    } else if contract.doubled {
+        2
+    } else {
+        1
+    };
+
+    if tricks < level + 6 {
+        let down = level + 6 - tricks;
+        if multiplier == 1 {
+            return -down * if vulnerable { 100 } else { 50 };
+        }
+        // Doubled: 100, 200, 200, then 300 each (200, 300, 300... vulnerable)
+        let doubled: i32 = (1..=down)
+            .map(|n| match (vulnerable, n) {
+                (false, 1) => 100,
+                (false, 2 | 3) => 200,
+                (false, _) => 300,
+                (true, 1) => 200,
+                (true, _) => 300,
+            })
+            .sum();
+        return -doubled * multiplier / 2;
+    }
+
+    let per_trick = match contract.suit {
+        Strain::Clubs | Strain::Diamonds => 20,
+        _ => 30,
+    };
+    let notrump_bonus = if contract.suit == Strain::NoTrump {
+        10
+    } else {
+        0
+    };
+    let trick_score = (per_trick * level + notrump_bonus) * multiplier;
+
+    let mut score = trick_score;
+    score += match (trick_score >= 100, vulnerable) {
+        (true, false) => 300,
+        (true, true) => 500,
+        (false, _) => 50,
+    };
+    score += match (level, vulnerable) {
+        (6, false) => 500,
+        (6, true) => 750,
+        (7, false) => 1000,
+        (7, true) => 1500,
+        _ => 0,
+    };
+    score += match multiplier {
+        4 => 100,
+        2 => 50,
+        _ => 0,
+    };
+
+    let overtricks = tricks - level - 6;
+    score += overtricks
+        * match (multiplier, vulnerable) {
+            (1, _) => per_trick,
+            (2, false) => 100,
+            (2, true) => 200,
+            (_, false) => 200,
+            (_, true) => 400,
+        };
+    score
+}
+
+/// IMPs for a score difference (negative for a negative difference)
+pub fn imps(difference: i32) -> i32 {
+    let imps = IMP_BOUNDS
+        .iter()
+        .take_while(|&&bound| difference.abs() > bound)
+        .count() as i32;
+    imps * difference.signum()
+}
+
+/// Matchpoints for each score on a board: 1 for every score beaten and ½ for
+/// every tie, so the top is one less than the number of scores
+pub fn matchpoints(scores: &[i32]) -> Vec<f32> {
+    scores
+        .iter()
+        .map(|&score| {
+            scores
+                .iter()
+                .map(|&other| match score.cmp(&other) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                })
+                .sum::<f32>()
+                - 0.5
+        })
+        .collect()
+}
+
+/// Butler IMPs for each score on a board: IMPs against the average score,
+/// rounded to the nearest 10, leaving out the highest and lowest scores when
+/// there are at least five
+pub fn butler_imps(scores: &[i32]) -> Vec<i32> {
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable();
+    let counted = if sorted.len() >= 5 {
+        &sorted[1..sorted.len() - 1]
+    } else {
+        &sorted[..]
+    };
+    if counted.is_empty() {
+        return Vec::new();
+    }
+    let average = counted.iter().sum::<i32>() as f32 / counted.len() as f32;
+    let datum = (average / 10.0).round() as i32 * 10;
+    scores.iter().map(|&score| imps(score - datum)).collect()
+}
+
+/// Par score for North-South (negative when East-West score): the result
+/// when each side in turn, starting with the dealer's, may outbid the other
+/// with a contract that makes or a doubled sacrifice, knowing the
+/// double-dummy tricks
+pub fn par_score(dd: &DoubleDummyTricks, vulnerable: Vulnerability, dealer: Direction) -> i32 {
+    const STRAINS: [Strain; 5] = [
+        Strain::Clubs,
+        Strain::Diamonds,
+        Strain::Hearts,
+        Strain::Spades,
+        Strain::NoTrump,
+    ];
+    const SIDES: [[Direction; 2]; 2] = [
+        [Direction::North, Direction::South],
+        [Direction::East, Direction::West],
+    ];
+
+    // North-South score if `side` plays contract `index` (in bidding order),
+    // doubled when it fails
+    let score = |index: usize, side: usize| {
+        let suit = STRAINS[index % 5];
+        let declarer = SIDES[side]
+            .into_iter()
+            .max_by_key(|d| dd.tricks(*d, suit))
+            .unwrap_or(Direction::North);
+        let tricks = dd.tricks(declarer, suit);
+        let level = (index / 5 + 1) as u8;
+        let contract = Contract {
+            level,
+            suit,
+            doubled: tricks < level + 6,
+            redoubled: false,
+            declarer,
+        };
+        let score = contract_score(&contract, is_vulnerable(vulnerable, declarer), tricks);
+        if side == 0 {
+            score
+        } else {
+            -score
+        }
+    };
+    // The side to bid takes whichever result is best for it
+    let best = |side: usize, results: &mut dyn Iterator<Item = i32>| {
+        if side == 0 {
+            results.max().unwrap_or(0)
+        } else {
+            results.min().unwrap_or(0)
+        }
+    };
+
+    // outcome[index][side]: the result once `side` has bid contract `index`
+    // and the other side is to call
+    let mut outcome = [[0; 2]; 35];
+    for index in (0..35).rev() {
+        for side in 0..2 {
+            let other = 1 - side;
+            let mut results = std::iter::once(score(index, side))
+                .chain((index + 1..35).map(|i| outcome[i][other]));
+            outcome[index][side] = best(other, &mut results);
+        }
+    }
+
+    let first = usize::from(matches!(dealer, Direction::East | Direction::West));
+    let second = 1 - first;
+    let passed = best(
+        second,
+        &mut std::iter::once(0).chain((0..35).map(|i| outcome[i][second])),
+    );
+    best(
+        first,
+        &mut std::iter::once(passed).chain((0..35).map(|i| outcome[i][first])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(contract: &str, declarer: Direction, vulnerable: bool, tricks: u8) -> i32 {
+        let mut contract = Contract::parse(contract).unwrap();
+        contract.declarer = declarer;
+        contract_score(&contract, vulnerable, tricks)
+    }
+
+    #[test]
+    fn test_contract_scores() {
+        assert_eq!(score("3NT", Direction::South, false, 9), 400);
+        assert_eq!(score("3NT", Direction::South, true, 10), 630);
+        assert_eq!(score("2H", Direction::South, false, 8), 110);
+        assert_eq!(score("1NTX", Direction::South, false, 7), 180);
+        assert_eq!(score("4SX", Direction::South, true, 11), 990);
+        assert_eq!(score("6C", Direction::South, false, 12), 920);
+        assert_eq!(score("7NTXX", Direction::South, true, 13), 2980);
+        assert_eq!(score("4S", Direction::South, true, 8), -200);
+        assert_eq!(score("3NTX", Direction::South, false, 5), -800);
+        assert_eq!(score("3NTX", Direction::South, true, 6), -800);
+        assert_eq!(score("2DXX", Direction::South, false, 7), -200);
+    }
+
+    #[test]
+    fn test_ns_score_and_vulnerability() {
+        let mut contract = Contract::parse("4H").unwrap();
+        contract.declarer = Direction::East;
+        let result = TableResult {
+            board: 2,
+            ns: "1".to_string(),
+            ew: "2".to_string(),
+            vulnerable: board_vulnerability(2),
+            contract: Some(contract),
+            tricks: 10,
+        };
+        assert_eq!(result.vulnerable, Vulnerability::NorthSouth);
+        assert_eq!(result.ns_score(), -420);
+        assert_eq!(board_vulnerability(16), Vulnerability::EastWest);
+        assert_eq!(board_vulnerability(17), Vulnerability::None);
+        assert_eq!(board_dealer(1), Direction::North);
+        assert_eq!(board_dealer(16), Direction::West);
+        assert_eq!(board_dealer(18), Direction::East);
+    }
+
+    #[test]
+    fn test_imps_and_matchpoints() {
+        assert_eq!(imps(0), 0);
+        assert_eq!(imps(20), 1);
+        assert_eq!(imps(-230), -6);
+        assert_eq!(imps(620 - 170), 10);
+        assert_eq!(imps(5000), 24);
+
+        assert_eq!(matchpoints(&[420, 170, 420, -50]), vec![2.5, 1.0, 2.5, 0.0]);
+        assert_eq!(butler_imps(&[420, 170]), vec![3, -4]);
+    }
+
+    #[test]
+    fn test_par_score() {
+        // North-South make 4S; East-West make only seven tricks in hearts
+        let dd = DoubleDummyTricks::from_pbn("7a6667a6666377763777").unwrap();
+        assert_eq!(par_score(&dd, Vulnerability::None, Direction::North), 420);
+        assert_eq!(
+            par_score(&dd, Vulnerability::NorthSouth, Direction::East),
+            620
+        );
+
+        // With nine tricks in hearts, 5H doubled (down two) costs less than
+        // the vulnerable game
+        let dd = DoubleDummyTricks::from_pbn("7a6667a6666397763977").unwrap();
+        assert_eq!(
+            par_score(&dd, Vulnerability::NorthSouth, Direction::North),
+            300
+        );
+        assert_eq!(
+            par_score(&dd, Vulnerability::EastWest, Direction::North),
+            420
+        );
+
+        let dd = DoubleDummyTricks::from_pbn("66666666667777777777").unwrap();
+        assert_eq!(par_score(&dd, Vulnerability::None, Direction::East), -90);
+    }
+}
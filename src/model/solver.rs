@@ -0,0 +1,642 @@
+//! Double-dummy solver
+//!
+//! Counts the tricks each declarer makes in each strain when all four hands
+//! are known and every card is played perfectly. Each count comes from a few
+//! null-window searches ("can North-South take at least N of the remaining
+//! tricks?") sharing a table of trick bounds for the positions at the start
+//! of each trick. Every result records the ranks it depended on, so an entry
+//! also answers for positions that differ only in lower cards (partition
+//! search), and sure tricks cut the search short.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+
+use super::auction::Strain;
+use super::board::DoubleDummyTricks;
+use super::card::{RANKS_DISPLAY_ORDER, SUITS_DISPLAY_ORDER};
+use super::deal::{Deal, Direction};
+
+/// Seats in playing order; North-South are the even indices
+const SEATS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+/// A card as (suit index, rank bit); suits follow `SUITS_DISPLAY_ORDER` and
+/// the two is bit 0, the ace bit 12
+type PlayedCard = (usize, u16);
+
+/// Ranks, per suit, that a result depends on; lower cards of the suit could
+/// be exchanged between the hands without changing it
+type Ranks = [u16; 4];
+
+const NO_RANKS: Ranks = [0; 4];
+
+/// Cards played so far to the current trick
+struct Trick {
+    leader: usize,
+    cards: [PlayedCard; 4],
+    len: usize,
+}
+
+impl Trick {
+    fn new(leader: usize) -> Self {
+        Self {
+            leader,
+            cards: [(0, 0); 4],
+            len: 0,
+        }
+    }
+
+    /// Index (from the leader) of the card winning the trick so far
+    fn winning(&self, trump: Option<usize>) -> usize {
+        let mut best = 0;
+        for i in 1..self.len {
+            let (suit, rank) = self.cards[i];
+            let (best_suit, best_rank) = self.cards[best];
+            if (suit == best_suit && rank > best_rank)
+                || (Some(suit) == trump && Some(best_suit) != trump)
+            {
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+/// Hasher for table keys, which are already well mixed
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0.rotate_left(5) ^ *byte as u64).wrapping_mul(0x517c_c1b7_2722_0a95);
+        }
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.0 = ((n as u64) ^ ((n >> 64) as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+
+/// Trick bounds for the positions that share the owners of the top cards of
+/// each suit
+struct Entry {
+    /// Number of top cards of each suit that must match
+    depth: [u8; 4],
+    /// Owners of the cards of each suit from the top down, two bits each
+    owners: [u32; 4],
+    low: u8,
+    high: u8,
+}
+
+/// Search state for one deal and strain
+struct Solver {
+    /// Rank bits held by each seat (in `SEATS` order) in each suit
+    hands: [[u16; 4]; 4],
+    trump: Option<usize>,
+    /// Bounds on North-South's tricks from positions at the start of a
+    /// trick, by leader and suit lengths
+    table: HashMap<u128, Vec<Entry>, BuildHasherDefault<KeyHasher>>,
+}
+
+impl Solver {
+    fn new(hands: [[u16; 4]; 4], trump: Option<usize>) -> Self {
+        Self {
+            hands,
+            trump,
+            table: HashMap::default(),
+        }
+    }
+
+    /// Tricks North-South take with `leader` on lead, by bisection or by
+    /// stepping from a guess (such as the count with another leader)
+    fn ns_tricks(&mut self, leader: usize, guess: Option<u8>) -> u8 {
+        let left = self.tricks_left(leader);
+        let Some(guess) = guess else {
+            let (mut low, mut high) = (0, left);
+            while low < high {
+                let target = (low + high + 1) / 2;
+                if self.ns_can_take(leader, target).0 {
+                    low = target;
+                } else {
+                    high = target - 1;
+                }
+            }
+            return low;
+        };
+
+        let mut tricks = guess.min(left);
+        if self.ns_can_take(leader, tricks).0 {
+            while tricks < left && self.ns_can_take(leader, tricks + 1).0 {
+                tricks += 1;
+            }
+        } else {
+            tricks -= 1;
+            while !self.ns_can_take(leader, tricks).0 {
+                tricks -= 1;
+            }
+        }
+        tricks
+    }
+
+    /// Cards (so tricks) left in a seat's hand
+    fn tricks_left(&self, seat: usize) -> u8 {
+        self.hands[seat].iter().map(|h| h.count_ones()).sum::<u32>() as u8
+    }
+
+    /// Cards of each suit not yet played
+    fn left(&self) -> [u16; 4] {
+        std::array::from_fn(|suit| self.hands.iter().fold(0, |all, h| all | h[suit]))
+    }
+
+    /// Whether North-South can take `target` of the remaining tricks, with
+    /// `leader` about to lead to a new trick, and the ranks that decided it
+    fn ns_can_take(&mut self, leader: usize, target: u8) -> (bool, Ranks) {
+        let left = self.tricks_left(leader);
+        if target == 0 {
+            return (true, NO_RANKS);
+        }
+        if target > left {
+            return (false, NO_RANKS);
+        }
+        let ((ns_sure, ns_ranks), (ew_sure, ew_ranks)) = self.sure_tricks(leader, target);
+        if target <= ns_sure {
+            return (true, ns_ranks);
+        }
+        if target > left - ew_sure {
+            return (false, ew_ranks);
+        }
+
+        let cards = self.left();
+        let owners = self.owners(&cards);
+        let key = self.key(leader);
+        if let Some(entries) = self.table.get(&key) {
+            for entry in entries {
+                let decided = if entry.low >= target {
+                    true
+                } else if entry.high < target {
+                    false
+                } else {
+                    continue;
+                };
+                if entry.matches(&owners) {
+                    return (decided, top_ranks(&cards, &entry.depth));
+                }
+            }
+        }
+
+        let (result, ranks) = self.play(&mut Trick::new(leader), target);
+
+        let depth = std::array::from_fn(|suit| {
+            let lowest = ranks[suit] & ranks[suit].wrapping_neg();
+            if lowest == 0 {
+                0
+            } else {
+                (cards[suit] & !(lowest - 1)).count_ones() as u8
+            }
+        });
+        let entries = self.table.entry(key).or_default();
+        let index = match entries
+            .iter()
+            .position(|e| e.depth == depth && e.matches(&owners))
+        {
+            Some(index) => index,
+            None => {
+                entries.push(Entry {
+                    depth,
+                    owners,
+                    low: 0,
+                    high: left,
+                });
+                entries.len() - 1
+            }
+        };
+        let entry = &mut entries[index];
+        if result {
+            entry.low = entry.low.max(target);
+        } else {
+            entry.high = entry.high.min(target - 1);
+        }
+        (result, ranks)
+    }
+
+    /// Tricks each side is sure of with `leader` on lead, and the cards that
+    /// make them sure: winners the leader (or partner, after an entry at
+    /// notrump) can cash from the top, and the top trumps in one hand. Only
+    /// the cards for `target` tricks (North-South) or enough to stop it
+    /// (East-West) are kept.
+    fn sure_tricks(&self, leader: usize, target: u8) -> ((u8, Ranks), (u8, Ranks)) {
+        let partner = (leader + 2) % 4;
+        let cards = self.left();
+        let top = |seat: usize, suit: usize| {
+            let mut left = cards[suit];
+            let mut run = 0;
+            while left != 0 {
+                let top = 1 << (15 - left.leading_zeros());
+                if self.hands[seat][suit] & top == 0 {
+                    break;
+                }
+                run |= top;
+                left &= !top;
+            }
+            run
+        };
+        let count = |ranks: &Ranks| ranks.iter().map(|r| r.count_ones()).sum::<u32>() as u8;
+
+        // Suit that must keep its top card, as partner's entry
+        let mut entry = None;
+        let mut leading = NO_RANKS;
+        let mut other = NO_RANKS;
+        match self.trump {
+            None => {
+                let own: Ranks = std::array::from_fn(|suit| top(leader, suit));
+                let partners: Ranks = std::array::from_fn(|suit| top(partner, suit));
+                entry = (0..4).find(|&suit| self.hands[leader][suit] != 0 && partners[suit] != 0);
+                leading = if entry.is_some() && count(&partners) > count(&own) {
+                    partners
+                } else {
+                    entry = None;
+                    own
+                };
+            }
+            Some(trump) => {
+                let length = |seat: usize, suit: usize| self.hands[seat][suit].count_ones();
+                for suit in 0..4 {
+                    // A side suit runs only while every other hand with a
+                    // trump still follows
+                    let mut run = top(leader, suit);
+                    if suit != trump {
+                        for seat in [(leader + 1) % 4, partner, (leader + 3) % 4] {
+                            if self.hands[seat][trump] != 0 {
+                                while run.count_ones() > length(seat, suit) {
+                                    run &= run - 1;
+                                }
+                            }
+                        }
+                    }
+                    leading[suit] = run;
+                }
+                if let Some(holder) = (0..4).find(|&seat| top(seat, trump) != 0) {
+                    let mut trumps = NO_RANKS;
+                    trumps[trump] = top(holder, trump);
+                    if holder % 2 != leader % 2 {
+                        other = trumps;
+                    } else if count(&trumps) > count(&leading) {
+                        leading = trumps;
+                    }
+                }
+            }
+        }
+
+        let left = self.tricks_left(leader);
+        let (leading_needed, other_needed) = if leader % 2 == 0 {
+            (target, left + 1 - target)
+        } else {
+            (left + 1 - target, target)
+        };
+        let leading = (count(&leading), fewest(leading, leading_needed, entry));
+        let other = (count(&other), fewest(other, other_needed, None));
+        if leader % 2 == 0 {
+            (leading, other)
+        } else {
+            (other, leading)
+        }
+    }
+
+    /// Play the next card to the trick; North-South look for a card that
+    /// reaches the target, East-West for one that stops it
+    fn play(&mut self, trick: &mut Trick, target: u8) -> (bool, Ranks) {
+        let seat = (trick.leader + trick.len) % 4;
+        let ns_to_play = seat % 2 == 0;
+        let mut all_ranks = NO_RANKS;
+
+        let (moves, count) = self.moves(seat, trick);
+        for &(_, (suit, rank)) in &moves[..count] {
+            self.hands[seat][suit] &= !rank;
+            trick.cards[trick.len] = (suit, rank);
+            trick.len += 1;
+
+            let (reached, mut ranks) = if trick.len == 4 {
+                let (win_suit, win_rank) = trick.cards[trick.winning(self.trump)];
+                let winner = (trick.leader + trick.winning(self.trump)) % 4;
+                let target = if winner % 2 == 0 { target - 1 } else { target };
+                let (reached, mut ranks) = self.ns_can_take(winner, target);
+                // The winner matters if it beat another card of its suit
+                if trick.cards.iter().filter(|(s, _)| *s == win_suit).count() > 1 {
+                    ranks[win_suit] |= win_rank;
+                }
+                (reached, ranks)
+            } else {
+                self.play(trick, target)
+            };
+
+            trick.len -= 1;
+            self.hands[seat][suit] |= rank;
+            if reached == ns_to_play {
+                return (reached, ranks);
+            }
+            for (all, ranks) in all_ranks.iter_mut().zip(ranks.iter()) {
+                *all |= *ranks;
+            }
+        }
+        (!ns_to_play, all_ranks)
+    }
+
+    /// Cards `seat` may play, most promising first, and their count
+    fn moves(&self, seat: usize, trick: &Trick) -> ([(i32, PlayedCard); 13], usize) {
+        let hand = self.hands[seat];
+        let led = (trick.len > 0).then(|| trick.cards[0].0);
+        let must_follow = led.filter(|&suit| hand[suit] != 0);
+        let current = (trick.len > 0).then(|| trick.cards[trick.winning(self.trump)]);
+        let partner_winning = trick.len >= 2 && trick.winning(self.trump) == trick.len - 2;
+
+        let mut moves = [(0, (0, 0)); 13];
+        let mut count = 0;
+        for suit in 0..4 {
+            if hand[suit] == 0 || must_follow.is_some_and(|led| led != suit) {
+                continue;
+            }
+            let mut left = self.hands.iter().fold(0, |all, h| all | h[suit]);
+            for &(played_suit, rank) in &trick.cards[..trick.len] {
+                if played_suit == suit {
+                    left |= rank;
+                }
+            }
+
+            let mut cards = hand[suit];
+            while cards != 0 {
+                let rank = 1 << (15 - cards.leading_zeros());
+                cards &= !rank;
+
+                let above = left & !(rank | (rank - 1));
+
+                let height = rank.trailing_zeros() as i32;
+                let is_trump = Some(suit) == self.trump;
+                let top_partner =
+                    self.hands[(seat + 2) % 4][suit] & (1 << (15 - left.leading_zeros()));
+                let priority = match current {
+                    None if above == 0 => 100 + height,
+                    None if top_partner != 0 => 90 - height,
+                    None => hand[suit].count_ones() as i32 - height,
+                    Some(_) if partner_winning => -height - if is_trump { 50 } else { 0 },
+                    Some((best_suit, best_rank)) => {
+                        let beats = (suit == best_suit && rank > best_rank)
+                            || (is_trump && Some(best_suit) != self.trump);
+                        if beats {
+                            100 - height
+                        } else {
+                            -height - if is_trump { 50 } else { 0 }
+                        }
+                    }
+                };
+                moves[count] = (priority, (suit, rank));
+                count += 1;
+            }
+        }
+        moves[..count].sort_unstable_by_key(|(priority, _)| -priority);
+        (moves, count)
+    }
+
+    /// Owners of the remaining cards of each suit, from the top down
+    fn owners(&self, cards: &[u16; 4]) -> [u32; 4] {
+        std::array::from_fn(|suit| {
+            let mut left = cards[suit];
+            let mut owners = 0;
+            let mut shift = 0;
+            while left != 0 {
+                let bit = 15 - left.leading_zeros();
+                let seat = (self.hands[1][suit] >> bit & 1)
+                    | (self.hands[2][suit] >> bit & 1) * 2
+                    | (self.hands[3][suit] >> bit & 1) * 3;
+                owners |= (seat as u32) << shift;
+                shift += 2;
+                left &= !(1 << bit);
+            }
+            owners
+        })
+    }
+
+    /// Table key: the leader and the length of each hand in each suit
+    fn key(&self, leader: usize) -> u128 {
+        let mut key = leader as u128;
+        for hand in &self.hands {
+            for holding in hand {
+                key = key << 4 | holding.count_ones() as u128;
+            }
+        }
+        key
+    }
+}
+
+impl Entry {
+    fn matches(&self, owners: &[u32; 4]) -> bool {
+        (0..4).all(|suit| {
+            let mask = (1u32 << (2 * self.depth[suit])).wrapping_sub(1);
+            (owners[suit] ^ self.owners[suit]) & mask == 0
+        })
+    }
+}
+
+/// The top cards of each suit's runs, enough for `needed` tricks, always
+/// keeping the top card of `entry`
+fn fewest(runs: Ranks, needed: u8, entry: Option<usize>) -> Ranks {
+    let mut kept = NO_RANKS;
+    let mut needed = needed as u32;
+    let order = entry
+        .into_iter()
+        .chain((0..4).filter(|&suit| Some(suit) != entry));
+    for suit in order {
+        let mut run = runs[suit];
+        while run != 0 && (needed > 0 || (Some(suit) == entry && kept[suit] == 0)) {
+            let top = 1 << (15 - run.leading_zeros());
+            kept[suit] |= top;
+            run &= !top;
+            needed = needed.saturating_sub(1);
+        }
+    }
+    kept
+}
+
+/// The top `depth` remaining cards of each suit
+fn top_ranks(cards: &[u16; 4], depth: &[u8; 4]) -> Ranks {
+    std::array::from_fn(|suit| {
+        let mut left = cards[suit];
+        let mut ranks = 0;
+        for _ in 0..depth[suit] {
+            let top = 1 << (15 - left.leading_zeros());
+            ranks |= top;
+            left &= !top;
+        }
+        ranks
+    })
+}
+
+/// Double-dummy tricks for every declarer and strain, or None unless the four
+/// hands hold the same (non-zero) number of cards and no card twice. The
+/// strains are solved in parallel where threads are available.
+pub fn solve_deal(deal: &Deal) -> Option<DoubleDummyTricks> {
+    let mut hands = [[0u16; 4]; 4];
+    for (seat, direction) in SEATS.iter().enumerate() {
+        let hand = deal.hand(*direction);
+        for (suit, s) in SUITS_DISPLAY_ORDER.iter().enumerate() {
+            for rank in &hand.holding(*s).ranks {
+                let position = RANKS_DISPLAY_ORDER.iter().position(|r| r == rank)?;
+                hands[seat][suit] |= 1 << (12 - position);
+            }
+        }
+    }
+    let cards = deal.north.card_count();
+    let distinct: u32 = (0..4)
+        .map(|suit| hands.iter().fold(0u16, |all, h| all | h[suit]).count_ones())
+        .sum();
+    if cards == 0
+        || SEATS.iter().any(|d| deal.hand(*d).card_count() != cards)
+        || distinct as usize != 4 * cards
+    {
+        return None;
+    }
+
+    let columns = solve_strains(hands);
+
+    let mut tricks = [[0u8; 5]; 4];
+    for (col, column) in columns.iter().enumerate() {
+        for (row, declarer_tricks) in column.iter().enumerate() {
+            tricks[row][col] = *declarer_tricks;
+        }
+    }
+    Some(DoubleDummyTricks::new(tricks))
+}
+
+/// Trump suit index for each of `DoubleDummyTricks::STRAINS`
+fn strain_trumps() -> impl Iterator<Item = Option<usize>> {
+    DoubleDummyTricks::STRAINS
+        .iter()
+        .map(|strain| match strain {
+            Strain::NoTrump => None,
+            Strain::Spades => Some(0),
+            Strain::Hearts => Some(1),
+            Strain::Diamonds => Some(2),
+            Strain::Clubs => Some(3),
+        })
+}
+
+/// The strains' columns, solved on a thread each
+#[cfg(not(target_arch = "wasm32"))]
+fn solve_strains(hands: [[u16; 4]; 4]) -> Vec<[u8; 4]> {
+    thread::scope(|scope| {
+        let solvers: Vec<_> = strain_trumps()
+            .map(|trump| scope.spawn(move || solve_strain(hands, trump)))
+            .collect();
+        solvers
+            .into_iter()
+            .map(|solver| solver.join().unwrap())
+            .collect()
+    })
+}
+
+/// The strains' columns, one after another: wasm32 has no threads
+#[cfg(target_arch = "wasm32")]
+fn solve_strains(hands: [[u16; 4]; 4]) -> Vec<[u8; 4]> {
+    strain_trumps()
+        .map(|trump| solve_strain(hands, trump))
+        .collect()
+}
+
+/// Tricks for each declarer (in `DoubleDummyTricks::DECLARERS` order) in one
+/// strain
+fn solve_strain(hands: [[u16; 4]; 4], trump: Option<usize>) -> [u8; 4] {
+    let mut solver = Solver::new(hands, trump);
+    let cards = solver.tricks_left(0);
+    let mut guess = None;
+    DoubleDummyTricks::DECLARERS.map(|declarer| {
+        let seat = SEATS.iter().position(|d| *d == declarer).unwrap_or(0);
+        let ns_tricks = solver.ns_tricks((seat + 1) % 4, guess);
+        guess = Some(ns_tricks);
+        if seat % 2 == 0 {
+            ns_tricks
+        } else {
+            cards - ns_tricks
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::deal::parse_deal;
+
+    #[test]
+    fn test_solve_one_suit_each() {
+        // North holds the spades, East the hearts, South the diamonds and
+        // West the clubs: whoever can ruff East's heart lead takes them all
+        let deal =
+            parse_deal("N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432")
+                .unwrap();
+        let dd = solve_deal(&deal).unwrap();
+        assert_eq!(dd.tricks(Direction::North, Strain::NoTrump), 0);
+        assert_eq!(dd.tricks(Direction::North, Strain::Spades), 13);
+        assert_eq!(dd.tricks(Direction::North, Strain::Hearts), 0);
+        assert_eq!(dd.tricks(Direction::North, Strain::Diamonds), 13);
+        assert_eq!(dd.tricks(Direction::North, Strain::Clubs), 0);
+        // South declaring, West leads a club
+        assert_eq!(dd.tricks(Direction::South, Strain::NoTrump), 0);
+        assert_eq!(dd.tricks(Direction::East, Strain::Hearts), 13);
+    }
+
+    #[test]
+    fn test_solve_finesse() {
+        // The spade finesse works only when East has to lead away from the king
+        let deal = parse_deal("N:AQ... K4... 32... 65...").unwrap();
+        let dd = solve_deal(&deal).unwrap();
+        assert_eq!(dd.tricks(Direction::North, Strain::NoTrump), 2);
+        assert_eq!(dd.tricks(Direction::South, Strain::NoTrump), 1);
+        assert_eq!(dd.tricks(Direction::East, Strain::Spades), 1);
+        assert_eq!(dd.tricks(Direction::West, Strain::Hearts), 1);
+    }
+
+    #[test]
+    fn test_solve_full_deals() {
+        // Practice deals from tests/fixtures, with the tables (declarers N, S,
+        // E, W by NT, S, H, D, C) of a separate exhaustive solver
+        let deals = [
+            (
+                "N:AQJT.A86.J9.A853 K842.T953.A5.QJT 3.742.QT8763.964 9765.KQJ.K42.K72",
+                "55797557978864688646",
+            ),
+            (
+                "E:Q9753.AJ.T962.K9 AK.K8752.A3.T865 JT82.T6.KQJ5.AQJ 64.Q943.874.7432",
+                "63838638386a5a56a5a5",
+            ),
+            (
+                "N:AKQJT6.K3.42.AJT 7.JT5.J9653.6532 4.AQ92.KT87.K987 98532.8764.AQ.Q4",
+                "bbbabcccac0003000030",
+            ),
+            (
+                "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765",
+                "67555675556687766877",
+            ),
+        ];
+        for (pbn, tricks) in deals {
+            let dd = solve_deal(&parse_deal(pbn).unwrap()).unwrap();
+            assert_eq!(dd.to_pbn(), tricks, "{}", pbn);
+        }
+    }
+
+    #[test]
+    fn test_solve_incomplete_deal() {
+        assert!(solve_deal(&parse_deal("N:AQ... K4... 3... 65...").unwrap()).is_none());
+        assert!(solve_deal(&parse_deal("N:AQ... K4... 32... 6A...").unwrap()).is_none());
+        assert!(solve_deal(&Deal::new()).is_none());
+    }
+}
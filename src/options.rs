@@ -0,0 +1,335 @@
+//! Rendering options for library consumers
+//!
+//! [RenderOptions] holds the handful of choices most programs make (paper,
+//! margins, font sizes, suit colors, what to show) without exposing the full
+//! [Settings] struct. Anything left unset keeps the layout's default or the
+//! value from the PBN file's `%` directives; anything set wins over both.
+
+use crate::cli::{CompressionLevel, FontEmbedding};
+use crate::config::settings::FOUR_COLOR_DECK;
+use crate::config::Settings;
+use crate::error::ConfigError;
+use crate::model::metadata::{ColorSettings, Margins, PaperSize};
+
+/// Optional rendering flags passed through from library consumers.
+///
+/// The circle flags are used by the declarer's plan layouts (1-up, 2-up,
+/// 4-up) to highlight analysis-identified cards with colored circles. When
+/// multiple analyses identify the same card the highest-priority color wins
+/// (sure > promotable > length).
+///
+/// `fillable_forms` applies to every layout that renders auctions with
+/// fill-in blanks; `answers_layer` to every layout that draws answers.
+///
+/// The `Option` fields override the layout defaults and the PBN file's `%`
+/// directives when set. [RenderOptions::builder] sets them with validation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RenderOptions {
+    /// Circle sure winners in red (priority 1, highest)
+    pub circle_sure_winners: bool,
+    /// Circle promotable winners in green (priority 2)
+    pub circle_promotable_winners: bool,
+    /// Circle length winners in blue (priority 3)
+    pub circle_length_winners: bool,
+    /// Add fillable PDF form fields over fill-in blanks
+    pub fillable_forms: bool,
+    /// Draw answers on a PDF layer that can be toggled in the viewer
+    pub answers_layer: bool,
+    /// Page width and height in mm
+    pub page_size: Option<(f32, f32)>,
+    /// Page margins in mm
+    pub margins: Option<Margins>,
+    /// Body text size in points
+    pub body_font_size: Option<f32>,
+    /// Card symbol and rank size in points
+    pub card_font_size: Option<f32>,
+    /// Commentary text size in points
+    pub commentary_font_size: Option<f32>,
+    /// Suit symbol colors
+    pub suit_colors: Option<ColorSettings>,
+    pub show_bidding: Option<bool>,
+    pub show_play: Option<bool>,
+    pub show_commentary: Option<bool>,
+    pub show_hcp: Option<bool>,
+    /// Subset, fully embed, or avoid font files
+    pub font_embedding: Option<FontEmbedding>,
+    /// How hard streams are compressed, if at all
+    pub compression: Option<CompressionLevel>,
+}
+
+impl RenderOptions {
+    /// Start building options, checked when built
+    ///
+    /// ```
+    /// use pbn_to_pdf::model::metadata::PaperSize;
+    /// use pbn_to_pdf::RenderOptions;
+    ///
+    /// let options = RenderOptions::builder()
+    ///     .paper(PaperSize::A4)
+    ///     .margin(12.0)
+    ///     .commentary_font_size(11.0)
+    ///     .show_bidding(false)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(options.page_size, Some((210.0, 297.0)));
+    /// ```
+    pub fn builder() -> RenderOptionsBuilder {
+        RenderOptionsBuilder::default()
+    }
+
+    /// Apply the options to settings already holding the PBN file's directives
+    pub fn apply(&self, settings: &mut Settings) {
+        fn set<T: Copy>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+
+        settings.circle_sure_winners = self.circle_sure_winners;
+        settings.circle_promotable_winners = self.circle_promotable_winners;
+        settings.circle_length_winners = self.circle_length_winners;
+        settings.fillable_forms = self.fillable_forms;
+        settings.answers_layer = self.answers_layer;
+
+        if let Some((width, height)) = self.page_size {
+            settings.page_width = width;
+            settings.page_height = height;
+        }
+        if let Some(margins) = self.margins {
+            // The title block lives in the top margin, so keep its space
+            settings.margin_top = margins.top + settings.title_block_height();
+            settings.margin_bottom = margins.bottom;
+            settings.margin_left = margins.left;
+            settings.margin_right = margins.right;
+            settings.margin = margins.left;
+        }
+        set(&mut settings.body_font_size, self.body_font_size);
+        set(&mut settings.card_font_size, self.card_font_size);
+        set(
+            &mut settings.commentary_font_size,
+            self.commentary_font_size,
+        );
+        if let Some(ref colors) = self.suit_colors {
+            settings.apply_suit_colors(colors);
+        }
+        set(&mut settings.show_bidding, self.show_bidding);
+        set(&mut settings.show_play, self.show_play);
+        set(&mut settings.show_commentary, self.show_commentary);
+        set(&mut settings.show_hcp, self.show_hcp);
+        set(&mut settings.font_embedding, self.font_embedding);
+        set(&mut settings.compression, self.compression);
+    }
+}
+
+/// Fluent construction of [RenderOptions]; see [RenderOptions::builder]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RenderOptionsBuilder {
+    options: RenderOptions,
+}
+
+impl RenderOptionsBuilder {
+    /// Use a standard paper size
+    pub fn paper(self, paper: PaperSize) -> Self {
+        let (width, height) = paper.dimensions_mm();
+        self.page_size(width, height)
+    }
+
+    /// Use a custom page size, in mm
+    pub fn page_size(mut self, width: f32, height: f32) -> Self {
+        self.options.page_size = Some((width, height));
+        self
+    }
+
+    /// The same margin on all four sides, in mm
+    pub fn margin(self, margin: f32) -> Self {
+        self.margins(Margins {
+            left: margin,
+            right: margin,
+            top: margin,
+            bottom: margin,
+        })
+    }
+
+    pub fn margins(mut self, margins: Margins) -> Self {
+        self.options.margins = Some(margins);
+        self
+    }
+
+    pub fn body_font_size(mut self, points: f32) -> Self {
+        self.options.body_font_size = Some(points);
+        self
+    }
+
+    pub fn card_font_size(mut self, points: f32) -> Self {
+        self.options.card_font_size = Some(points);
+        self
+    }
+
+    pub fn commentary_font_size(mut self, points: f32) -> Self {
+        self.options.commentary_font_size = Some(points);
+        self
+    }
+
+    pub fn suit_colors(mut self, colors: ColorSettings) -> Self {
+        self.options.suit_colors = Some(colors);
+        self
+    }
+
+    /// Orange diamonds and green clubs, as `--four-color`
+    pub fn four_color_deck(self) -> Self {
+        self.suit_colors(FOUR_COLOR_DECK)
+    }
+
+    pub fn show_bidding(mut self, show: bool) -> Self {
+        self.options.show_bidding = Some(show);
+        self
+    }
+
+    pub fn show_play(mut self, show: bool) -> Self {
+        self.options.show_play = Some(show);
+        self
+    }
+
+    pub fn show_commentary(mut self, show: bool) -> Self {
+        self.options.show_commentary = Some(show);
+        self
+    }
+
+    pub fn show_hcp(mut self, show: bool) -> Self {
+        self.options.show_hcp = Some(show);
+        self
+    }
+
+    pub fn circle_sure_winners(mut self, circle: bool) -> Self {
+        self.options.circle_sure_winners = circle;
+        self
+    }
+
+    pub fn circle_promotable_winners(mut self, circle: bool) -> Self {
+        self.options.circle_promotable_winners = circle;
+        self
+    }
+
+    pub fn circle_length_winners(mut self, circle: bool) -> Self {
+        self.options.circle_length_winners = circle;
+        self
+    }
+
+    pub fn fillable_forms(mut self, fillable: bool) -> Self {
+        self.options.fillable_forms = fillable;
+        self
+    }
+
+    pub fn answers_layer(mut self, answers: bool) -> Self {
+        self.options.answers_layer = answers;
+        self
+    }
+
+    /// How hard streams are compressed; [CompressionLevel::None] leaves
+    /// them readable for post-processing
+    pub fn compression(mut self, level: CompressionLevel) -> Self {
+        self.options.compression = Some(level);
+        self
+    }
+
+    /// How fonts are embedded; [FontEmbedding::Builtin] gives the smallest files
+    pub fn font_embedding(mut self, embedding: FontEmbedding) -> Self {
+        self.options.font_embedding = Some(embedding);
+        self
+    }
+
+    /// The options, once the sizes are checked: positive page and font
+    /// sizes, and margins that leave room on the page
+    pub fn build(self) -> Result<RenderOptions, ConfigError> {
+        let options = self.options;
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+
+        if let Some((width, height)) = options.page_size {
+            if !positive(width) || !positive(height) {
+                return Err(ConfigError::InvalidInput(format!(
+                    "page size must be positive: {}x{}mm",
+                    width, height
+                )));
+            }
+        }
+        if let Some(margins) = options.margins {
+            let sides = [margins.left, margins.right, margins.top, margins.bottom];
+            if sides.iter().any(|side| !side.is_finite() || *side < 0.0) {
+                return Err(ConfigError::InvalidInput(
+                    "margins must not be negative".to_string(),
+                ));
+            }
+            let (width, height) = options
+                .page_size
+                .unwrap_or_else(|| PaperSize::default().dimensions_mm());
+            if margins.left + margins.right >= width || margins.top + margins.bottom >= height {
+                return Err(ConfigError::InvalidInput(format!(
+                    "margins leave no room on a {}x{}mm page",
+                    width, height
+                )));
+            }
+        }
+        for (name, size) in [
+            ("body", options.body_font_size),
+            ("card", options.card_font_size),
+            ("commentary", options.commentary_font_size),
+        ] {
+            if let Some(size) = size.filter(|size| !positive(*size)) {
+                return Err(ConfigError::InvalidInput(format!(
+                    "{} font size must be positive: {}",
+                    name, size
+                )));
+            }
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_applies_over_settings() {
+        let options = RenderOptions::builder()
+            .paper(PaperSize::A4)
+            .body_font_size(9.0)
+            .four_color_deck()
+            .show_hcp(true)
+            .fillable_forms(true)
+            .font_embedding(FontEmbedding::Builtin)
+            .build()
+            .unwrap();
+
+        let mut settings = Settings::default();
+        let show_bidding = settings.show_bidding;
+        options.apply(&mut settings);
+
+        assert_eq!((settings.page_width, settings.page_height), (210.0, 297.0));
+        assert_eq!(settings.body_font_size, 9.0);
+        assert_eq!(settings.club_color, (0.0, 128.0 / 255.0, 0.0));
+        assert!(settings.show_hcp);
+        assert!(settings.fillable_forms);
+        assert_eq!(settings.font_embedding, FontEmbedding::Builtin);
+        assert_eq!(settings.show_bidding, show_bidding);
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(RenderOptions::builder()
+            .page_size(0.0, 297.0)
+            .build()
+            .is_err());
+        assert!(RenderOptions::builder().margin(-1.0).build().is_err());
+        assert!(RenderOptions::builder()
+            .page_size(100.0, 100.0)
+            .margin(50.0)
+            .build()
+            .is_err());
+        assert!(RenderOptions::builder()
+            .commentary_font_size(f32::NAN)
+            .build()
+            .is_err());
+        assert!(RenderOptions::builder().margin(10.0).build().is_ok());
+    }
+}
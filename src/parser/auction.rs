@@ -50,11 +50,15 @@ pub fn parse_auction(dealer: Direction, input: &str) -> Result<Auction, String>
         }
 
         // Try to parse as a call
-        // Extract call and any inline annotation
-        let (clean_token, annotation) = extract_annotation(token);
+        // Extract call, alert and any inline annotation
+        let (token, alerted) = split_alert(token);
+        let (clean_token, annotation) = extract_annotation(&token);
 
         if let Some(call) = Call::from_pbn(&clean_token) {
             auction.add_annotated_call(call, annotation);
+            if alerted {
+                auction.alert_last();
+            }
         } else if clean_token.is_empty() && token.starts_with('$') {
             // Standalone $N NAG marker — attach as annotation to previous call
             // PBN standard: $1 = "!", $2 = "?", $3 = "!!", $4 = "??"
@@ -89,7 +93,7 @@ pub fn parse_auction(dealer: Direction, input: &str) -> Result<Auction, String>
 }
 
 /// Parse a note reference like "=1=" and return the note number
-fn parse_note_reference(token: &str) -> Option<u8> {
+pub(crate) fn parse_note_reference(token: &str) -> Option<u8> {
     if token.starts_with('=') && token.ends_with('=') && token.len() >= 3 {
         let inner = &token[1..token.len() - 1];
         inner.parse::<u8>().ok()
@@ -98,12 +102,27 @@ fn parse_note_reference(token: &str) -> Option<u8> {
     }
 }
 
+/// Split the alert marker off a call token
+/// e.g., "1C!" -> ("1C", true)
+/// e.g., "2H!=1=" -> ("2H=1=", true)
+/// e.g., "3NT!!" -> ("3NT!!", false), a "very good call" suffix annotation
+pub(crate) fn split_alert(token: &str) -> (String, bool) {
+    let call_end = token
+        .find(|c| matches!(c, '!' | '?' | '=' | '$'))
+        .unwrap_or(token.len());
+    let (call, suffix) = token.split_at(call_end);
+    match suffix.strip_prefix('!') {
+        Some(rest) if !rest.starts_with(['!', '?']) => (format!("{}{}", call, rest), true),
+        _ => (token.to_string(), false),
+    }
+}
+
 /// Extract annotation from a call token
 /// Returns (clean_call, optional_annotation)
 /// e.g., "1C!" -> ("1C", Some("!"))
 /// e.g., "2H=1=" -> ("2H", Some("1"))
 /// e.g., "3NT" -> ("3NT", None)
-fn extract_annotation(token: &str) -> (String, Option<String>) {
+pub(crate) fn extract_annotation(token: &str) -> (String, Option<String>) {
     // Check for =N= annotation at the end
     if let Some(eq_pos) = token.find('=') {
         let before = &token[..eq_pos];
@@ -173,7 +192,7 @@ mod tests {
 
     #[test]
     fn test_auction_with_annotations() {
-        let auction = parse_auction(Direction::West, "1C! 1H 2C$1 Pass").unwrap();
+        let auction = parse_auction(Direction::West, "1C! 1H 2C$1 Pass 2S? 3C!=1=").unwrap();
         assert_eq!(auction.calls.len(), 4);
         assert_eq!(
             auction.calls[0].call,
@@ -182,10 +201,17 @@ mod tests {
                 strain: Strain::Clubs
             }
         );
-        // Check that ! annotation is preserved
-        assert_eq!(auction.calls[0].annotation, Some("!".to_string()));
+        // ! alerts the call rather than annotating it
+        assert!(auction.is_alerted(0));
+        assert_eq!(auction.calls[0].annotation, None);
+        assert!(!auction.is_alerted(1));
         // $1 NAG marker should not produce an annotation
         assert_eq!(auction.calls[2].annotation, None);
+        assert!(!auction.is_alerted(2));
+        assert_eq!(auction.calls[4].annotation, Some("?".to_string()));
+        // An alert and a note reference together
+        assert!(auction.is_alerted(5));
+        assert_eq!(auction.calls[5].annotation, Some("1".to_string()));
     }
 
     #[test]
@@ -233,6 +259,14 @@ mod tests {
         assert_eq!(extract_annotation("Pass$1"), ("Pass".to_string(), None));
     }
 
+    #[test]
+    fn test_split_alert() {
+        assert_eq!(split_alert("1C!"), ("1C".to_string(), true));
+        assert_eq!(split_alert("2H!=1="), ("2H=1=".to_string(), true));
+        assert_eq!(split_alert("3NT!!"), ("3NT!!".to_string(), false));
+        assert_eq!(split_alert("Pass"), ("Pass".to_string(), false));
+    }
+
     #[test]
     fn test_empty_auction() {
         let auction = parse_auction(Direction::East, "").unwrap();
@@ -1,16 +1,176 @@
-use crate::model::{CommentaryBlock, FormattedText, Rank, Suit, TextSpan};
+use crate::model::{CommentaryBlock, Direction, FormattedText, Rank, Suit, TextSpan};
 
 /// Parse commentary text from PBN, handling formatting codes
 /// Commentary is enclosed in braces: { ... }
 /// Supports: <b>bold</b>, <i>italic</i>, \S \H \D \C for suits
+/// A leading seat prefix (`{N: ...}`) makes the block for that seat only
 pub fn parse_commentary(input: &str) -> Result<CommentaryBlock, String> {
-    let content = parse_formatted_text(input)?;
-    Ok(CommentaryBlock::new(content))
+    let (seat, input) = split_seat_prefix(input);
+    let block = CommentaryBlock::new(parse_formatted_text(input)?);
+    Ok(match seat {
+        Some(seat) => block.for_seat(seat),
+        None => block,
+    })
+}
+
+/// Split a leading `N:`/`E:`/`S:`/`W:` seat prefix off commentary
+fn split_seat_prefix(input: &str) -> (Option<Direction>, &str) {
+    let trimmed = input.trim_start();
+    let mut chars = trimmed.chars();
+    if let (Some(c), Some(':')) = (chars.next(), chars.next()) {
+        if c.is_ascii_uppercase() {
+            if let Some(seat) = Direction::from_char(c) {
+                return (Some(seat), chars.as_str().trim_start());
+            }
+        }
+    }
+    (None, input)
+}
+
+/// A Bridge Composer (RTF-style) control word such as `\emdash` or `\fs24`
+struct ControlWord<'a> {
+    word: &'a str,
+    param: Option<i32>,
+    /// Bytes after the backslash, including a delimiting space
+    len: usize,
+}
+
+/// Parse a control word at the start of `input` (just after the backslash)
+///
+/// Words starting with a suit letter or `n` are left to the single-letter
+/// escapes (`\s`, `\n`, ...).
+fn parse_control_word(input: &str) -> Option<ControlWord<'_>> {
+    let word_len = input
+        .find(|c: char| !c.is_ascii_lowercase())
+        .unwrap_or(input.len());
+    if word_len == 0 || input.starts_with(['s', 'h', 'd', 'c', 'n']) {
+        return None;
+    }
+    let rest = &input[word_len..];
+    let digits = rest.strip_prefix('-').unwrap_or(rest);
+    let digit_len = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let param_len = if digit_len > 0 {
+        rest.len() - digits.len() + digit_len
+    } else {
+        0
+    };
+    // A space after a control word only ends it
+    let space_len = usize::from(rest[param_len..].starts_with(' '));
+    Some(ControlWord {
+        word: &input[..word_len],
+        param: rest[..param_len].parse().ok(),
+        len: word_len + param_len + space_len,
+    })
+}
+
+/// Text for a control word or symbol that stands for characters
+fn control_text(word: &str, param: Option<i32>) -> Option<String> {
+    let text = match word {
+        "emdash" => "—",
+        "endash" => "–",
+        "bullet" => "•",
+        "lquote" => "‘",
+        "rquote" => "’",
+        "ldblquote" => "“",
+        "rdblquote" => "”",
+        "tab" => "\t",
+        // Control symbols: non-breaking space, optional hyphen, non-breaking hyphen
+        "~" => "\u{a0}",
+        "-" => "",
+        "_" => "\u{2011}",
+        "{" => "{",
+        "}" => "}",
+        "u" => {
+            // Unicode character; negative values wrap as in RTF
+            let code = param?;
+            let code = if code < 0 { code + 65536 } else { code };
+            return char::from_u32(code as u32).map(String::from);
+        }
+        _ => return None,
+    };
+    Some(text.to_string())
+}
+
+/// Translate Bridge Composer control words into the characters and tags the
+/// commentary parser understands (`\ul` .. `\ul0` becomes `<u>` .. `</u>`).
+///
+/// With `tags` off (note text) formatting words are dropped. Font size changes
+/// are ignored, and unknown control words are dropped with a warning rather
+/// than printed. Suit escapes, `\n` and `\\` are left for the caller.
+fn translate_control_words(input: &str, tags: bool) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut open_tags: Vec<&str> = Vec::new();
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('\\') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let symbol = after.chars().next().filter(|c| "~-_{}".contains(*c));
+
+        if after.starts_with('\\') {
+            // Escaped backslash
+            result.push_str("\\\\");
+            rest = &after[1..];
+        } else if let Some(symbol) = symbol {
+            result.push_str(&control_text(&symbol.to_string(), None).unwrap_or_default());
+            rest = &after[1..];
+        } else if let Some(control) = parse_control_word(after) {
+            rest = &after[control.len..];
+            let turns_off = control.param == Some(0);
+            match control.word {
+                "ul" | "ulnone" | "b" | "i" => {
+                    let tag = if control.word == "ulnone" {
+                        "u"
+                    } else {
+                        &control.word[..1]
+                    };
+                    let off = turns_off || control.word == "ulnone";
+                    let is_open = open_tags.contains(&tag);
+                    if tags && off && is_open {
+                        open_tags.retain(|open| *open != tag);
+                        result.push_str(&format!("</{}>", tag));
+                    } else if tags && !off && !is_open {
+                        open_tags.push(tag);
+                        result.push_str(&format!("<{}>", tag));
+                    }
+                }
+                "line" | "par" => result.push_str("\\n"),
+                "fs" | "plain" => {
+                    log::debug!("Ignoring commentary font code \\{}", control.word);
+                }
+                word => match control_text(word, control.param) {
+                    Some(text) => {
+                        result.push_str(&text);
+                        // RTF follows \uN with a fallback character
+                        if word == "u" {
+                            rest = rest.strip_prefix('?').unwrap_or(rest);
+                        }
+                    }
+                    None => log::warn!("Unsupported commentary code \\{}", word),
+                },
+            }
+        } else {
+            // Single-letter escape (\S, \n, ...), handled by the caller
+            result.push('\\');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+
+    // Close formatting that was never switched off
+    while let Some(tag) = open_tags.pop() {
+        result.push_str(&format!("</{}>", tag));
+    }
+    result
 }
 
 /// Replace suit escape sequences (\S, \H, \D, \C) with Unicode symbols.
 /// Used to process text where we need to convert backslash codes to symbols.
+/// Bridge Composer control words such as `\emdash` are translated too.
 pub fn replace_suit_escapes(input: &str) -> String {
+    let input = translate_control_words(input, false);
     let mut result = String::with_capacity(input.len());
     let mut chars = input.chars().peekable();
 
@@ -18,6 +178,10 @@ pub fn replace_suit_escapes(input: &str) -> String {
         if c == '\\' {
             if let Some(&next) = chars.peek() {
                 match next {
+                    '\\' => {
+                        result.push('\\');
+                        chars.next();
+                    }
                     'S' | 's' => {
                         result.push('♠');
                         chars.next();
@@ -113,30 +277,74 @@ fn parse_italic_with_nested_bold(content: &str, text: &mut FormattedText) {
     }
 }
 
-/// Parse a `<span style=color:HEX>` opening tag.
-/// Returns `Some((rgb, byte_offset_past_closing_angle_bracket))` if matched, else `None`.
+/// Opening tags of colored text, with the attribute holding the color and the closing tag
+const COLOR_TAGS: [(&str, &str, &str); 2] = [
+    ("<span", "color:", "</span>"),
+    ("<font", "color=", "</font>"),
+];
+
+/// Position of the first colored-text opening tag (`<span` or `<font`) in `text`
+fn find_color_open(text: &str) -> Option<usize> {
+    COLOR_TAGS
+        .iter()
+        .filter_map(|(open, _, _)| text.find(open))
+        .min()
+}
+
+/// Parse a `<span style=color:HEX>` or `<font color=HEX>` opening tag.
+/// Returns `Some((rgb, byte_offset_past_closing_angle_bracket, closing_tag))` if
+/// matched, else `None`.
 ///
-/// Accepts both shorthand (#RGB) and full (#RRGGBB) hex colors, with optional
-/// quotes around the style value and tolerant of extra whitespace.
-fn parse_span_open(remaining: &str) -> Option<((u8, u8, u8), usize)> {
-    if !remaining.starts_with("<span") {
-        return None;
-    }
+/// Accepts shorthand (#RGB) and full (#RRGGBB) hex colors as well as HTML color
+/// names, with optional quotes around the value and tolerant of extra whitespace.
+fn parse_color_open(remaining: &str) -> Option<((u8, u8, u8), usize, &'static str)> {
+    let (_, attribute, close_tag) = COLOR_TAGS
+        .iter()
+        .find(|(open, _, _)| remaining.starts_with(open))?;
     // Find the closing '>' of the opening tag
     let close_offset = remaining.find('>')?;
     let tag = &remaining[..close_offset];
-    // Look for `color:` (case-insensitive prefix not required for our PBN inputs).
+    // Look for `color:` / `color=` (case-insensitive)
     let lower = tag.to_ascii_lowercase();
-    let color_idx = lower.find("color:")?;
-    let hex_start = color_idx + "color:".len();
-    let after_prefix = &tag[hex_start..];
-    // Skip a leading '#' if present
-    let trimmed = after_prefix.trim_start();
-    let hex = trimmed.strip_prefix('#').unwrap_or(trimmed);
-    // Take hex digits only (stops at quote, semicolon, whitespace, or '>')
-    let hex_digits: String = hex.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
-    let rgb = parse_hex_color(&hex_digits)?;
-    Some((rgb, close_offset + 1))
+    let color_idx = lower.find(attribute)?;
+    let value = tag[color_idx + attribute.len()..].trim_start();
+    let value = value.trim_start_matches(['"', '\'']).trim_start();
+    // Take the color only (stops at quote, semicolon, whitespace, or '>')
+    let color: String = value
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '#')
+        .collect();
+    let rgb = match color.strip_prefix('#') {
+        Some(hex) => parse_hex_color(hex)?,
+        None => named_color(&color).or_else(|| parse_hex_color(&color))?,
+    };
+    Some((rgb, close_offset + 1, close_tag))
+}
+
+/// RGB value of an HTML color name
+fn named_color(name: &str) -> Option<(u8, u8, u8)> {
+    let rgb = match name.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "maroon" => (128, 0, 0),
+        "green" => (0, 128, 0),
+        "lime" => (0, 255, 0),
+        "blue" => (0, 0, 255),
+        "navy" => (0, 0, 128),
+        "yellow" => (255, 255, 0),
+        "olive" => (128, 128, 0),
+        "purple" => (128, 0, 128),
+        "fuchsia" | "magenta" => (255, 0, 255),
+        "teal" => (0, 128, 128),
+        "aqua" | "cyan" => (0, 255, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "orange" => (255, 165, 0),
+        "brown" => (165, 42, 42),
+        _ => return None,
+    };
+    Some(rgb)
 }
 
 /// Parse a CSS-style hex color (`#RGB` or `#RRGGBB`, with leading `#` already stripped).
@@ -159,11 +367,63 @@ fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
     }
 }
 
+/// Parse an `<a href="URL">` opening tag.
+/// Returns `Some((url, byte_offset_past_closing_angle_bracket))` if matched, else `None`.
+fn parse_anchor_open(remaining: &str) -> Option<(String, usize)> {
+    if !remaining.starts_with("<a ") {
+        return None;
+    }
+    let close_offset = remaining.find('>')?;
+    let tag = &remaining[..close_offset];
+    let href_idx = tag.to_ascii_lowercase().find("href=")?;
+    let value = tag[href_idx + "href=".len()..].trim_start();
+    let url = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or(""),
+        _ => value.split_whitespace().next().unwrap_or(""),
+    };
+    if url.is_empty() {
+        return None;
+    }
+    Some((url.to_string(), close_offset + 1))
+}
+
+/// Length in bytes of a bare URL at the start of `remaining`, if there is one.
+///
+/// Recognizes `http://`, `https://` and `www.` prefixes. Trailing punctuation
+/// that usually belongs to the sentence (e.g. a final period) is not included.
+fn bare_url_len(remaining: &str) -> Option<usize> {
+    let lower_prefix: String = remaining
+        .chars()
+        .take(8)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if !(lower_prefix.starts_with("http://")
+        || lower_prefix.starts_with("https://")
+        || lower_prefix.starts_with("www."))
+    {
+        return None;
+    }
+    let end = remaining
+        .find(|c: char| c.is_whitespace() || c == '<' || c == '"')
+        .unwrap_or(remaining.len());
+    let url = remaining[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+    Some(url.len())
+}
+
+/// Turn link text into a URL a viewer can open (`www.` links get a scheme)
+fn url_for(text: &str) -> String {
+    if text.to_ascii_lowercase().starts_with("www.") {
+        format!("https://{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
 /// Parse italic content that contains a nested `<span style=color:...>` tag.
 /// Splits into italic spans and italic-colored spans.
 fn parse_italic_with_nested_span(content: &str, text: &mut FormattedText) {
     let mut remaining = content;
-    while let Some(span_start) = remaining.find("<span") {
+    while let Some(span_start) = find_color_open(remaining) {
         // Push italic text before the <span> tag
         let before = &remaining[..span_start];
         if !before.is_empty() {
@@ -172,7 +432,7 @@ fn parse_italic_with_nested_span(content: &str, text: &mut FormattedText) {
 
         // Parse the span opening
         let after_open = &remaining[span_start..];
-        let (rgb, open_len) = match parse_span_open(after_open) {
+        let (rgb, open_len, close_tag) = match parse_color_open(after_open) {
             Some(v) => v,
             None => {
                 // Malformed span — treat the rest as italic and bail
@@ -181,13 +441,13 @@ fn parse_italic_with_nested_span(content: &str, text: &mut FormattedText) {
             }
         };
         let body_and_rest = &after_open[open_len..];
-        // Find the closing </span>
-        if let Some(end_idx) = body_and_rest.find("</span>") {
+        // Find the closing </span> or </font>
+        if let Some(end_idx) = body_and_rest.find(close_tag) {
             let body = &body_and_rest[..end_idx];
             text.push(TextSpan::italic_colored(replace_suit_escapes(body), rgb));
-            remaining = &body_and_rest[end_idx + "</span>".len()..];
+            remaining = &body_and_rest[end_idx + close_tag.len()..];
         } else {
-            // Unclosed </span> — treat the rest as italic-colored
+            // Unclosed tag — treat the rest as italic-colored
             text.push(TextSpan::italic_colored(
                 replace_suit_escapes(body_and_rest),
                 rgb,
@@ -231,18 +491,61 @@ fn strip_empty_italic_tags(input: &str) -> String {
     result
 }
 
+/// Table markup tags, turned into tabs and line breaks
+const TABLE_TAGS: [&str; 8] = [
+    "<table>", "</table>", "<tr>", "</tr>", "<td>", "</td>", "<th>", "</th>",
+];
+
+/// Handle a table markup tag: cells end with a tab and rows with a line break,
+/// so the renderer lines the cells up in columns like tab-separated text
+fn push_table_tag(tag: &str, text: &mut FormattedText) {
+    match tag {
+        "<table>" => {
+            // Start the table on its own line
+            if !text.is_empty() && text.spans.last() != Some(&TextSpan::LineBreak) {
+                text.push(TextSpan::LineBreak);
+            }
+        }
+        "</td>" | "</th>" => text.push(TextSpan::Tab),
+        "</tr>" => {
+            // The last cell needs no separator
+            if text.spans.last() == Some(&TextSpan::Tab) {
+                text.spans.pop();
+            }
+            text.push(TextSpan::LineBreak);
+        }
+        _ => {}
+    }
+}
+
 /// Parse formatted text with HTML-like tags and suit symbols
 pub fn parse_formatted_text(input: &str) -> Result<FormattedText, String> {
     // Pre-process: strip empty or whitespace-only italic tags like <i> </i>
     // These are sometimes used in PBN files for formatting around punctuation
     let input = strip_empty_italic_tags(input);
+    let input = translate_control_words(&input, true);
 
     let mut text = FormattedText::new();
     let mut remaining = input.as_str();
     let mut plain_buffer = String::new();
 
     while !remaining.is_empty() {
-        if remaining.starts_with("<b>") {
+        if let Some(tag) = TABLE_TAGS.iter().find(|tag| remaining.starts_with(**tag)) {
+            // Table markup: whitespace between tags is layout, not text
+            if !plain_buffer.trim().is_empty() {
+                text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
+            }
+            plain_buffer.clear();
+            push_table_tag(tag, &mut text);
+            remaining = &remaining[tag.len()..];
+        } else if remaining.starts_with('\t') {
+            // Tab = column separator
+            if !plain_buffer.is_empty() {
+                text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
+            }
+            text.push(TextSpan::Tab);
+            remaining = &remaining[1..];
+        } else if remaining.starts_with("<b>") {
             // Flush plain buffer
             if !plain_buffer.is_empty() {
                 text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
@@ -282,8 +585,8 @@ pub fn parse_formatted_text(input: &str) -> Result<FormattedText, String> {
             } else if italic_content.contains("<u>") {
                 // Nested <u> tags inside <i>: split into italic and underline spans
                 parse_italic_with_nested_underline(italic_content, &mut text);
-            } else if italic_content.contains("<span") {
-                // Nested <span> color tags inside <i>: split into italic and italic-colored spans
+            } else if find_color_open(italic_content).is_some() {
+                // Nested <span>/<font> color tags inside <i>: split into italic and italic-colored spans
                 parse_italic_with_nested_span(italic_content, &mut text);
             } else {
                 text.push(TextSpan::italic(replace_suit_escapes(italic_content)));
@@ -300,29 +603,76 @@ pub fn parse_formatted_text(input: &str) -> Result<FormattedText, String> {
             let underline_content = &remaining[3..end];
             text.push(TextSpan::underline(replace_suit_escapes(underline_content)));
             remaining = &remaining[end + 4..];
-        } else if remaining.starts_with("<span") {
-            // Color span: <span style=color:HEX>...</span>
-            if let Some((rgb, open_len)) = parse_span_open(remaining) {
+        } else if remaining.starts_with("<sup>") || remaining.starts_with("<sub>") {
+            // Flush plain buffer
+            if !plain_buffer.is_empty() {
+                text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
+            }
+
+            // Find closing tag
+            let superscript = remaining.starts_with("<sup>");
+            let close = if superscript { "</sup>" } else { "</sub>" };
+            let end = remaining
+                .find(close)
+                .ok_or_else(|| format!("Unclosed {} tag", &remaining[..5]))?;
+            let content = replace_suit_escapes(&remaining[5..end]);
+            text.push(if superscript {
+                TextSpan::superscript(content)
+            } else {
+                TextSpan::subscript(content)
+            });
+            remaining = &remaining[end + close.len()..];
+        } else if remaining.starts_with("<span") || remaining.starts_with("<font") {
+            // Color span: <span style=color:HEX>...</span> or <font color=HEX>...</font>
+            if let Some((rgb, open_len, close_tag)) = parse_color_open(remaining) {
                 // Flush plain buffer
                 if !plain_buffer.is_empty() {
                     text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
                 }
                 let body_and_rest = &remaining[open_len..];
-                if let Some(end_idx) = body_and_rest.find("</span>") {
+                if let Some(end_idx) = body_and_rest.find(close_tag) {
                     let body = &body_and_rest[..end_idx];
                     text.push(TextSpan::colored(replace_suit_escapes(body), rgb));
-                    remaining = &body_and_rest[end_idx + "</span>".len()..];
+                    remaining = &body_and_rest[end_idx + close_tag.len()..];
                 } else {
-                    // Unclosed span — treat rest as colored
+                    // Unclosed tag — treat rest as colored
                     text.push(TextSpan::colored(replace_suit_escapes(body_and_rest), rgb));
                     remaining = "";
                 }
             } else {
-                // Malformed <span ...> — fall through and consume one char
+                // Malformed <span ...> or <font ...> — fall through and consume one char
                 let c = remaining.chars().next().unwrap();
                 plain_buffer.push(c);
                 remaining = &remaining[c.len_utf8()..];
             }
+        } else if let Some((url, open_len)) = parse_anchor_open(remaining) {
+            // Hyperlink: <a href="URL">text</a>
+            if !plain_buffer.is_empty() {
+                text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
+            }
+            let body_and_rest = &remaining[open_len..];
+            let (body, rest) = match body_and_rest.find("</a>") {
+                Some(end_idx) => (&body_and_rest[..end_idx], &body_and_rest[end_idx + 4..]),
+                None => (body_and_rest, ""),
+            };
+            let body = replace_suit_escapes(body);
+            let link_text = if body.trim().is_empty() {
+                url.clone()
+            } else {
+                body
+            };
+            text.push(TextSpan::link(link_text, url));
+            remaining = rest;
+        } else if let Some(url_len) = bare_url_len(remaining)
+            .filter(|_| plain_buffer.is_empty() || plain_buffer.ends_with([' ', '(']))
+        {
+            // Bare URL in running text
+            if !plain_buffer.is_empty() {
+                text.push(TextSpan::plain(std::mem::take(&mut plain_buffer)));
+            }
+            let url_text = &remaining[..url_len];
+            text.push(TextSpan::link(url_text, url_for(url_text)));
+            remaining = &remaining[url_len..];
         } else if remaining.starts_with('\\') && remaining.len() >= 2 {
             // Check for suit symbol escape
             let next_char = remaining.chars().nth(1).unwrap();
@@ -414,12 +764,17 @@ pub fn parse_formatted_text(input: &str) -> Result<FormattedText, String> {
                     text.push(TextSpan::LineBreak);
                     remaining = &remaining[2..];
                 }
-                _ => {
-                    // Unknown escape, keep as-is
+                '\\' => {
+                    // Escaped backslash
                     plain_buffer.push('\\');
-                    plain_buffer.push(next_char);
                     remaining = &remaining[2..];
                 }
+                _ => {
+                    // Unknown escape: keep the character, not the backslash
+                    log::warn!("Unsupported commentary escape \\{}", next_char);
+                    plain_buffer.push(next_char);
+                    remaining = &remaining[1 + next_char.len_utf8()..];
+                }
             }
         } else if remaining.starts_with("\n\n") || remaining.starts_with("\r\n\r\n") {
             // Blank line = paragraph break (double line break for spacing)
@@ -615,6 +970,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_superscript_and_subscript() {
+        let text = parse_formatted_text(r"1NT<sup>1</sup> and 4\S<sub>-1</sub>").unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::plain("1NT"),
+                TextSpan::superscript("1"),
+                TextSpan::plain(" and 4"),
+                TextSpan::SuitSymbol(Suit::Spades),
+                TextSpan::subscript("-1"),
+            ]
+        );
+        assert!(parse_formatted_text("x<sup>2").is_err());
+    }
+
     #[test]
     fn test_strip_empty_italic_tags() {
         // Test case from real PBN: <b>Exercise One<i> </i>—<i> </i>Ruffing Losers</b>
@@ -694,6 +1065,68 @@ mod tests {
         assert_eq!(text.spans[2], TextSpan::Italic(" first".to_string()));
     }
 
+    #[test]
+    fn test_control_words() {
+        let text = parse_formatted_text(r"Win\emdash then\~lose \u8212? x \foo y").unwrap();
+        assert_eq!(text.to_plain_text(), "Win—then\u{a0}lose — x y");
+
+        let text = parse_formatted_text(r"\fs28 Bid \ul Pass\ul0  now \b bold").unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::plain("Bid "),
+                TextSpan::underline("Pass"),
+                TextSpan::plain(" now "),
+                TextSpan::bold("bold"),
+            ]
+        );
+
+        let text = parse_formatted_text(r"C:\\dir \Q").unwrap();
+        assert_eq!(text.to_plain_text(), r"C:\dir Q");
+
+        assert_eq!(replace_suit_escapes(r"1\S\endash 2\H"), "1♠–2♥");
+        assert_eq!(replace_suit_escapes(r"\ul A\ul0"), "A");
+    }
+
+    #[test]
+    fn test_seat_prefix() {
+        let block = parse_commentary("N: Partner opened 1\\S").unwrap();
+        assert_eq!(block.seat, Some(Direction::North));
+        assert_eq!(block.content.to_plain_text(), "Partner opened 1♠");
+
+        let block = parse_commentary("S:Think about slam").unwrap();
+        assert_eq!(block.seat, Some(Direction::South));
+
+        // Only a single capital seat letter counts
+        assert!(parse_commentary("Note: none").unwrap().is_general());
+        assert!(parse_commentary("n: lowercase").unwrap().is_general());
+    }
+
+    #[test]
+    fn test_font_color_tag_and_named_colors() {
+        let text = parse_formatted_text(
+            r#"<font color="red">Stop</font> and <span style="color: Navy">go</span>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::colored("Stop", (255, 0, 0)),
+                TextSpan::plain(" and "),
+                TextSpan::colored("go", (0, 0, 128)),
+            ]
+        );
+
+        let text = parse_formatted_text(r#"<i>Read <font color=#080>this</font></i>"#).unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::italic("Read "),
+                TextSpan::italic_colored("this", (0, 0x88, 0)),
+            ]
+        );
+    }
+
     #[test]
     fn test_strip_empty_italic_tags_no_content() {
         // Empty italic tags with no content at all
@@ -701,4 +1134,76 @@ mod tests {
         assert_eq!(text.spans.len(), 1);
         assert_eq!(text.spans[0], TextSpan::Plain("BeforeAfter".to_string()));
     }
+
+    #[test]
+    fn test_anchor_link() {
+        let text =
+            parse_formatted_text(r#"See <a href="https://example.com/squeeze">this article</a>."#)
+                .unwrap();
+        assert_eq!(text.spans.len(), 3);
+        assert_eq!(text.spans[0], TextSpan::Plain("See ".to_string()));
+        assert_eq!(
+            text.spans[1],
+            TextSpan::link("this article", "https://example.com/squeeze")
+        );
+        assert_eq!(text.spans[2], TextSpan::Plain(".".to_string()));
+    }
+
+    #[test]
+    fn test_bare_url_excludes_trailing_punctuation() {
+        let text = parse_formatted_text("Watch www.example.com/video.").unwrap();
+        assert_eq!(text.spans.len(), 3);
+        assert_eq!(
+            text.spans[1],
+            TextSpan::link("www.example.com/video", "https://www.example.com/video")
+        );
+        assert_eq!(text.spans[2], TextSpan::Plain(".".to_string()));
+    }
+
+    #[test]
+    fn test_url_inside_word_is_not_linked() {
+        let text = parse_formatted_text("xhttp://example.com").unwrap();
+        assert_eq!(text.spans.len(), 1);
+    }
+
+    #[test]
+    fn test_tab_separated_row() {
+        let text = parse_formatted_text("Suit\tTricks\\n\\S\t5").unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::plain("Suit"),
+                TextSpan::Tab,
+                TextSpan::plain("Tricks"),
+                TextSpan::LineBreak,
+                TextSpan::SuitSymbol(Suit::Spades),
+                TextSpan::Tab,
+                TextSpan::plain("5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_markup_becomes_tabs_and_rows() {
+        let text = parse_formatted_text(
+            "Plans:<table>\n<tr><td>A</td><td><b>B</b></td></tr>\n<tr><td>C</td><td>D</td></tr>\n</table>Done",
+        )
+        .unwrap();
+        assert_eq!(
+            text.spans,
+            vec![
+                TextSpan::plain("Plans:"),
+                TextSpan::LineBreak,
+                TextSpan::plain("A"),
+                TextSpan::Tab,
+                TextSpan::bold("B"),
+                TextSpan::LineBreak,
+                TextSpan::plain("C"),
+                TextSpan::Tab,
+                TextSpan::plain("D"),
+                TextSpan::LineBreak,
+                TextSpan::plain("Done"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,108 @@
+//! Parser for convention definition files
+//!
+//! One sequence per line, calls separated by spaces, then `=` and the meaning:
+//!
+//! ```text
+//! # Our system
+//! 1NT = 15-17
+//! 2♦ = Multi
+//! 1NT 2♣ = Stayman
+//! ```
+//!
+//! Suits may be written as symbols or letters. Blank lines and lines starting
+//! with `#` are ignored.
+
+use crate::error::{PbnError, Span};
+use crate::model::{Call, Conventions};
+
+/// Parse a convention definition file
+pub fn parse_conventions(content: &str) -> Result<Conventions, PbnError> {
+    let mut conventions = Conventions::new();
+
+    for (index, text) in content.lines().enumerate() {
+        let line = text.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (sequence, meaning) = line.split_once('=').ok_or_else(|| PbnError::ParseError {
+            message: "expected \"calls = meaning\"".to_string(),
+            span: Span::of(index + 1, text, line),
+        })?;
+
+        let calls = sequence
+            .split_whitespace()
+            .map(|token| {
+                parse_convention_call(token).ok_or_else(|| PbnError::InvalidCall {
+                    message: token.to_string(),
+                    span: Span::of(index + 1, text, token),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if calls.is_empty() {
+            return Err(PbnError::ParseError {
+                message: "no calls before \"=\"".to_string(),
+                span: Span::of(index + 1, text, "="),
+            });
+        }
+
+        conventions.add(calls, meaning.trim());
+    }
+
+    Ok(conventions)
+}
+
+/// Parse one call, accepting suit symbols as well as PBN letters ("2♦", "2D")
+fn parse_convention_call(token: &str) -> Option<Call> {
+    let pbn: String = token
+        .chars()
+        .map(|c| match c {
+            '♠' => 'S',
+            '♥' => 'H',
+            '♦' => 'D',
+            '♣' => 'C',
+            other => other.to_ascii_uppercase(),
+        })
+        .collect();
+    Call::from_pbn(&pbn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Strain;
+
+    #[test]
+    fn test_parse_conventions() {
+        let content = "# System\n1NT = 15-17\n\n2♦ = Multi\n1nt 2c = Stayman\n";
+        let conventions = parse_conventions(content).unwrap();
+
+        assert_eq!(conventions.entries.len(), 3);
+        assert_eq!(
+            conventions.meaning(&[Call::Bid {
+                level: 2,
+                strain: Strain::Diamonds
+            }]),
+            Some("Multi")
+        );
+        assert_eq!(conventions.entries[2].sequence.len(), 2);
+        assert_eq!(conventions.entries[2].meaning, "Stayman");
+    }
+
+    #[test]
+    fn test_parse_conventions_errors() {
+        assert!(parse_conventions("1NT 15-17").is_err());
+        assert!(parse_conventions("9Z = Nonsense").is_err());
+        assert!(parse_conventions(" = Nothing").is_err());
+    }
+
+    #[test]
+    fn test_error_span() {
+        let error = parse_conventions("1NT = 15-17\n  1NT 2Q = Stayman\n").unwrap_err();
+        assert_eq!(error.span(), &Span::new(2, 7, 2, "  1NT 2Q = Stayman"));
+        assert_eq!(
+            error.render("system.txt"),
+            "line 2: invalid call: 2Q\n --> system.txt:2:7\n  |\n2 |   1NT 2Q = Stayman\n  |       ^^"
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::model::{Deal, Direction, Hand, Holding, Rank};
 
 /// Parse a deal notation string: "N:AKQ.JT9.876.5432 QJ.AK.QT9.87654 ..."
+///
+/// Hands left off the end (a deal giving only the first one to three hands)
+/// are empty, like hands given as "-".
 pub fn parse_deal(input: &str) -> Result<Deal, String> {
     let input = input.trim();
 
@@ -19,7 +22,7 @@ pub fn parse_deal(input: &str) -> Result<Deal, String> {
     let hands_str: String = chars.collect();
     let hand_notations: Vec<&str> = hands_str.split_whitespace().collect();
 
-    if hand_notations.len() != 4 {
+    if hand_notations.is_empty() || hand_notations.len() > 4 {
         return Err(format!("Expected 4 hands, got {}", hand_notations.len()));
     }
 
@@ -42,7 +45,7 @@ pub fn parse_deal(input: &str) -> Result<Deal, String> {
 }
 
 /// Parse a single hand notation: "AKQ.JT9.876.5432" or "-" for unknown/empty hand
-fn parse_hand(input: &str) -> Result<Hand, String> {
+pub(crate) fn parse_hand(input: &str) -> Result<Hand, String> {
     // A single dash means unknown/not dealt hand (PBN spec)
     if input == "-" {
         return Ok(Hand::new());
@@ -75,6 +78,11 @@ fn parse_holding(input: &str) -> Result<Holding, String> {
         if c == '-' {
             continue;
         }
+        // A spot card whose rank doesn't matter
+        if c == 'x' || c == 'X' {
+            holding.add_spot();
+            continue;
+        }
 
         let rank = Rank::from_char(c).ok_or_else(|| format!("Invalid rank character: {}", c))?;
         holding.add(rank);
@@ -106,6 +114,28 @@ mod tests {
         assert!(holding.is_void());
     }
 
+    #[test]
+    fn test_parse_deal_with_missing_hands() {
+        let deal = parse_deal("N:AKQ.JT9.876.5432 QJ.AK.QT9.87654").unwrap();
+        assert_eq!(deal.north.card_count(), 13);
+        assert_eq!(deal.east.card_count(), 13);
+        assert_eq!(deal.south.card_count(), 0);
+        assert_eq!(deal.west.card_count(), 0);
+        assert!(parse_deal("N:").is_err());
+    }
+
+    #[test]
+    fn test_parse_spot_cards() {
+        let holding = parse_holding("AQxx").unwrap();
+        assert_eq!(holding.ranks, [Rank::Ace, Rank::Queen]);
+        assert_eq!(holding.spots, 2);
+
+        let deal = parse_deal("N:AKxx.Qx.xxx.xxxx - QJ9x.xxx.Kxx.AKx -").unwrap();
+        assert_eq!(deal.north.card_count(), 13);
+        assert_eq!(deal.north.spades.to_string(), "AKxx");
+        assert_eq!(deal.south.clubs.spots, 1);
+    }
+
     #[test]
     fn test_parse_hand() {
         let hand = parse_hand("AKQ.JT9.876.5432").unwrap();
@@ -0,0 +1,144 @@
+//! Character encoding of PBN files
+//!
+//! PBN is meant to be ASCII or UTF-8, but files from older Windows tools are
+//! usually Windows-1252 (a superset of Latin-1), and some editors save UTF-16.
+//! [decode_pbn] sniffs the encoding from a byte order mark or the bytes
+//! themselves and returns the text as UTF-8, ready for `parse_pbn`.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// An encoding a PBN file was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Utf8 => write!(f, "UTF-8"),
+            Encoding::Utf16Le => write!(f, "UTF-16LE"),
+            Encoding::Utf16Be => write!(f, "UTF-16BE"),
+            Encoding::Windows1252 => write!(f, "Windows-1252"),
+        }
+    }
+}
+
+/// Windows-1252 characters for bytes 0x80-0x9F; the five bytes it leaves
+/// undefined map to the C1 control with the same value, as browsers do
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// Decode the bytes of a PBN file, and the encoding they were found to be in
+///
+/// A UTF-8 or UTF-16 byte order mark decides the encoding (and is dropped).
+/// Without one, text starting with a zero byte beside an ASCII one is taken
+/// as UTF-16, valid UTF-8 as UTF-8, and anything else as Windows-1252, which
+/// every byte decodes in. Invalid UTF-16 is replaced with U+FFFD.
+///
+/// ```
+/// use pbn_to_pdf::parser::{decode_pbn, Encoding};
+///
+/// let (text, encoding) = decode_pbn(b"{Gr\xfcezi}");
+/// assert_eq!(text, "{Grüezi}");
+/// assert_eq!(encoding, Encoding::Windows1252);
+/// ```
+pub fn decode_pbn(bytes: &[u8]) -> (String, Encoding) {
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return decode_pbn(rest);
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        return (decode_utf16(rest, u16::from_le_bytes), Encoding::Utf16Le);
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        return (decode_utf16(rest, u16::from_be_bytes), Encoding::Utf16Be);
+    }
+    match bytes {
+        [first, 0, ..] if *first != 0 && first.is_ascii() => {
+            return (decode_utf16(bytes, u16::from_le_bytes), Encoding::Utf16Le)
+        }
+        [0, second, ..] if *second != 0 && second.is_ascii() => {
+            return (decode_utf16(bytes, u16::from_be_bytes), Encoding::Utf16Be)
+        }
+        _ => {}
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (text.to_string(), Encoding::Utf8),
+        Err(_) => (decode_windows_1252(bytes), Encoding::Windows1252),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Decode one line of a PBN file read a line at a time: UTF-8 if it's valid,
+/// otherwise Windows-1252
+pub(crate) fn decode_line(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => Cow::Owned(decode_windows_1252(bytes)),
+    }
+}
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+            // The rest of Windows-1252 is Latin-1, whose bytes are code points
+            _ => char::from(byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_with_and_without_bom() {
+        let text = "[Event \"Café\"]";
+        assert_eq!(
+            decode_pbn(text.as_bytes()),
+            (text.to_string(), Encoding::Utf8)
+        );
+        let with_bom = [&b"\xEF\xBB\xBF"[..], text.as_bytes()].concat();
+        assert_eq!(decode_pbn(&with_bom), (text.to_string(), Encoding::Utf8));
+    }
+
+    #[test]
+    fn test_windows_1252() {
+        let (text, encoding) = decode_pbn(b"{\x93Caf\xe9\x94 \x80 1\x96 2}");
+        assert_eq!(text, "{“Café” € 1– 2}");
+        assert_eq!(encoding, Encoding::Windows1252);
+    }
+
+    #[test]
+    fn test_utf16() {
+        let text = "[Event \"♠ Café\"]";
+        let le: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+
+        assert_eq!(
+            decode_pbn(&[&b"\xFF\xFE"[..], &le[..]].concat()),
+            (text.to_string(), Encoding::Utf16Le)
+        );
+        assert_eq!(
+            decode_pbn(&[&b"\xFE\xFF"[..], &be[..]].concat()),
+            (text.to_string(), Encoding::Utf16Be)
+        );
+        // No byte order mark: told apart by where the zero bytes are
+        assert_eq!(decode_pbn(&le), (text.to_string(), Encoding::Utf16Le));
+        assert_eq!(decode_pbn(&be), (text.to_string(), Encoding::Utf16Be));
+    }
+}
@@ -1,5 +1,22 @@
+use crate::error::PbnWarning;
 use crate::model::metadata::{ColorSettings, FontSpec, Margins, PaperSize, PbnMetadata};
 
+/// %BCOptions flags that are recognized but don't change the output:
+/// - Float, GutterH, GutterV: commentary always floats beside the diagram and
+///   separator lines are always drawn between boards and columns
+/// - NoHRStats, STBorder, STShade: hand record statistics and score tables
+///   aren't rendered
+/// - PageHeader: page headers come from `--header`
+const PASSIVE_BC_OPTIONS: &[&str] = &[
+    "Float",
+    "GutterH",
+    "GutterV",
+    "NoHRStats",
+    "PageHeader",
+    "STBorder",
+    "STShade",
+];
+
 /// Parse a PBN header line starting with %
 pub fn parse_header_line(line: &str) -> Option<HeaderDirective> {
     let line = line.trim();
@@ -72,6 +89,26 @@ pub fn parse_header_line(line: &str) -> Option<HeaderDirective> {
         return Some(HeaderDirective::TitleDate(date));
     }
 
+    if let Some(stripped) = content.strip_prefix("HRTitleSite ") {
+        let site = stripped.trim().trim_matches('"').to_string();
+        return Some(HeaderDirective::TitleSite(site));
+    }
+
+    if let Some(stripped) = content.strip_prefix("HRCopyright ") {
+        let copyright = stripped.trim().trim_matches('"').to_string();
+        return Some(HeaderDirective::Copyright(copyright));
+    }
+
+    if let Some(stripped) = content.strip_prefix("HRTitleLogo ") {
+        let logo = stripped.trim().trim_matches('"').to_string();
+        return Some(HeaderDirective::TitleLogo(logo));
+    }
+
+    if let Some(stripped) = content.strip_prefix("HRTitleClub ") {
+        let club = stripped.trim().trim_matches('"').to_string();
+        return Some(HeaderDirective::TitleClub(club));
+    }
+
     if content.starts_with("ShowHCP") {
         return Some(HeaderDirective::ShowHcp(true));
     }
@@ -116,6 +153,8 @@ pub struct BCOptions {
     pub float: bool,
     pub center: bool,
     pub two_col_auctions: bool,
+    /// Flags this renderer doesn't know, in file order
+    pub unsupported: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -130,6 +169,12 @@ pub enum HeaderDirective {
     PipColors(ColorSettings),
     TitleEvent(String),
     TitleDate(String),
+    TitleSite(String),
+    TitleClub(String),
+    /// Logo file from %HRTitleLogo, relative to the PBN file
+    TitleLogo(String),
+    /// Attribution line from %HRCopyright
+    Copyright(String),
     ShowHcp(bool),
     ShowCardTable(bool),
     ShowBoardLabels(bool),
@@ -235,7 +280,7 @@ fn parse_font_directive(content: &str) -> Option<(String, FontSpec)> {
     ))
 }
 
-fn parse_pip_colors(value: &str) -> Option<ColorSettings> {
+pub fn parse_pip_colors(value: &str) -> Option<ColorSettings> {
     // Format: "#000000,#ff0000,#ff0000,#000000" (spades, hearts, diamonds, clubs)
     let parts: Vec<&str> = value.split(',').collect();
     if parts.len() != 4 {
@@ -295,7 +340,8 @@ fn parse_bc_options(value: &str) -> BCOptions {
             "Float" => options.float = true,
             "Center" => options.center = true,
             "TwoColAuctions" => options.two_col_auctions = true,
-            _ => {} // Ignore unknown options like NoHRStats, STBorder, STShade, GutterH, GutterV, PageHeader
+            _ if PASSIVE_BC_OPTIONS.contains(&word) => {}
+            _ => options.unsupported.push(word.to_string()),
         }
     }
 
@@ -304,7 +350,14 @@ fn parse_bc_options(value: &str) -> BCOptions {
 
 /// Parse all header lines and build metadata
 pub fn parse_headers(lines: &[&str]) -> PbnMetadata {
+    parse_headers_with_warnings(lines).0
+}
+
+/// Parse all header lines, also returning warnings for directives that were
+/// recognized but can't be applied
+pub fn parse_headers_with_warnings(lines: &[&str]) -> (PbnMetadata, Vec<PbnWarning>) {
     let mut metadata = PbnMetadata::default();
+    let mut warnings = Vec::new();
 
     for line in lines {
         if let Some(directive) = parse_header_line(line) {
@@ -332,6 +385,11 @@ pub fn parse_headers(lines: &[&str]) -> PbnMetadata {
                 HeaderDirective::PipColors(c) => metadata.colors = c,
                 HeaderDirective::TitleEvent(t) => metadata.title_event = Some(t),
                 HeaderDirective::TitleDate(d) => metadata.title_date = Some(d),
+                HeaderDirective::TitleSite(s) => metadata.title_site = Some(s),
+                HeaderDirective::TitleClub(c) => metadata.title_club = Some(c),
+                HeaderDirective::Copyright(c) => metadata.copyright = Some(c),
+                HeaderDirective::TitleLogo(l) if !l.is_empty() => metadata.title_logo = Some(l),
+                HeaderDirective::TitleLogo(_) => {}
                 HeaderDirective::ShowHcp(v) => metadata.layout.show_hcp = v,
                 HeaderDirective::ShowCardTable(v) => metadata.layout.show_card_table = v,
                 HeaderDirective::ShowBoardLabels(v) => metadata.layout.show_board_labels = v,
@@ -348,6 +406,11 @@ pub fn parse_headers(lines: &[&str]) -> PbnMetadata {
                     if opts.two_col_auctions {
                         metadata.layout.two_col_auctions = true;
                     }
+                    warnings.extend(
+                        opts.unsupported
+                            .into_iter()
+                            .map(PbnWarning::UnsupportedBCOption),
+                    );
                 }
                 HeaderDirective::BoardLabelFormat(fmt) => {
                     metadata.layout.board_label_format = Some(fmt);
@@ -357,7 +420,7 @@ pub fn parse_headers(lines: &[&str]) -> PbnMetadata {
         }
     }
 
-    metadata
+    (metadata, warnings)
 }
 
 #[cfg(test)]
@@ -479,6 +542,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bc_options_fixture_flags_are_all_recognized() {
+        let lines = vec![
+            "%BCOptions Center GutterH GutterV Justify NoHRStats PageHeader STBorder STShade TwoColAuctions",
+            "%BCOptions Float Justify NoHRStats  STBorder STShade ShowHCP",
+        ];
+        let (metadata, warnings) = parse_headers_with_warnings(&lines);
+        assert!(warnings.is_empty(), "Unexpected warnings: {:?}", warnings);
+        assert!(metadata.layout.show_hcp);
+        assert!(metadata.layout.center);
+    }
+
+    #[test]
+    fn test_unknown_bc_options_become_warnings() {
+        let lines = vec!["%BCOptions Justify ShowLosers Float BigCards"];
+        let (metadata, warnings) = parse_headers_with_warnings(&lines);
+        assert!(metadata.layout.justify);
+        assert_eq!(
+            warnings,
+            vec![
+                PbnWarning::UnsupportedBCOption("ShowLosers".to_string()),
+                PbnWarning::UnsupportedBCOption("BigCards".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_hr_title_headers() {
+        let lines = vec![
+            "%HRTitleEvent \"ABS2-2 Developing Tricks\"",
+            "%HRTitleSite \"Stoneridge Creek Pleasanton\"",
+            "%HRTitleClub \"Tri-Valley Bridge Club\"",
+            "%HRTitleDate 2016.08.05",
+        ];
+        let metadata = parse_headers(&lines);
+        assert_eq!(
+            metadata.title_event.as_deref(),
+            Some("ABS2-2 Developing Tricks")
+        );
+        assert_eq!(
+            metadata.title_site.as_deref(),
+            Some("Stoneridge Creek Pleasanton")
+        );
+        assert_eq!(
+            metadata.title_club.as_deref(),
+            Some("Tri-Valley Bridge Club")
+        );
+        assert_eq!(metadata.title_date.as_deref(), Some("2016.08.05"));
+    }
+
+    #[test]
+    fn test_parse_title_logo() {
+        let metadata = parse_headers(&["%HRTitleLogo \"logo.png\""]);
+        assert_eq!(metadata.title_logo.as_deref(), Some("logo.png"));
+        let metadata = parse_headers(&["%HRTitleLogo \"\""]);
+        assert_eq!(metadata.title_logo, None);
+    }
+
+    #[test]
+    fn test_parse_copyright() {
+        let lines = vec!["%HRCopyright \"© 2024 Jane Teacher, used with permission\""];
+        let metadata = parse_headers(&lines);
+        assert_eq!(
+            metadata.copyright.as_deref(),
+            Some("© 2024 Jane Teacher, used with permission")
+        );
+    }
+
     #[test]
     fn test_parse_translate_board_label() {
         let directive = parse_header_line("%Translate \"Board %\" \"%)\"").unwrap();
@@ -1,10 +1,18 @@
 pub mod auction;
 pub mod commentary;
+pub mod conventions;
 pub mod deal;
+pub mod encoding;
 pub mod header;
 pub mod pbn;
 pub mod play;
+pub mod records;
 pub mod tags;
+pub mod validate;
 
 pub use commentary::replace_suit_escapes;
-pub use pbn::{parse_pbn, PbnFile};
+pub use conventions::parse_conventions;
+pub use encoding::{decode_pbn, Encoding};
+pub use pbn::{parse_pbn, parse_pbn_lenient, PbnFile, PbnReader};
+pub use records::{GameRecord, PbnRecords};
+pub use validate::{validate_pbn, Diagnostic, Severity};
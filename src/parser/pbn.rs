@@ -1,12 +1,19 @@
-use crate::error::PbnError;
-use crate::model::{BCFlags, Board, Contract, Direction, HiddenHands, PbnMetadata, Vulnerability};
+use std::io::BufRead;
+
+use crate::error::{PbnError, PbnWarning, Span};
+use crate::model::{
+    BCFlags, Board, CommentaryPlacement, Contract, Direction, DoubleDummyTricks, HiddenHands,
+    PbnMetadata, Room, ScoreTable, Vulnerability,
+};
 
 use super::auction::parse_auction;
 use super::commentary::{extract_commentary, parse_commentary};
 use super::deal::parse_deal;
-use super::header::parse_headers;
-use super::play::parse_play;
-use super::tags::{parse_tag_pair, TagPair};
+use super::encoding::decode_line;
+use super::header::parse_headers_with_warnings;
+use super::play::{parse_card_list, parse_play};
+use super::tags::{parse_tag_pair, TagHistory, TagPair};
+use super::validate::Diagnostic;
 
 /// Parse a note value in format "N:text" where N is the note number
 /// Returns (note_number, note_text) if successful
@@ -23,10 +30,35 @@ fn parse_note_value(value: &str) -> Option<(u8, String)> {
 pub struct PbnFile {
     pub metadata: PbnMetadata,
     pub boards: Vec<Board>,
+    /// Header directives that were recognized but not applied
+    pub warnings: Vec<PbnWarning>,
 }
 
 /// Parse a complete PBN file
 pub fn parse_pbn(content: &str) -> Result<PbnFile, PbnError> {
+    parse(content, BoardParser::new()).map(|(file, _)| file)
+}
+
+/// Parse a PBN file, leaving out what can't be read instead of failing
+///
+/// Malformed tags and commentary missing its closing brace (ended at the
+/// next board) are skipped, and a board with an invalid deal is kept without
+/// it, as `parse_pbn` does; each gets a diagnostic giving its line and the
+/// rest of the file parses as usual.
+///
+/// ```
+/// use pbn_to_pdf::parser::parse_pbn_lenient;
+///
+/// let pbn = "[Board \"1\"]\n[Deal \"N:AKQ\"]\n\n[Event \"\"]\n[Board \"2\"]\n";
+/// let (file, diagnostics) = parse_pbn_lenient(pbn);
+/// assert_eq!(file.boards.len(), 2);
+/// assert_eq!(diagnostics[0].line, 2);
+/// ```
+pub fn parse_pbn_lenient(content: &str) -> (PbnFile, Vec<Diagnostic>) {
+    parse(content, BoardParser::lenient()).expect("lenient parsing doesn't fail")
+}
+
+fn parse(content: &str, parser: BoardParser) -> Result<(PbnFile, Vec<Diagnostic>), PbnError> {
     let lines: Vec<&str> = content.lines().collect();
 
     // Extract header lines (starting with %)
@@ -36,146 +68,399 @@ pub fn parse_pbn(content: &str) -> Result<PbnFile, PbnError> {
         .copied()
         .collect();
 
-    let metadata = parse_headers(&header_lines);
+    let (metadata, warnings) = parse_headers_with_warnings(&header_lines);
 
     // Parse boards
-    let boards = parse_boards(&lines)?;
+    let (boards, diagnostics) = parse_boards(&lines, parser)?;
+
+    let file = PbnFile {
+        metadata,
+        boards,
+        warnings,
+    };
+    Ok((file, diagnostics))
+}
 
-    Ok(PbnFile { metadata, boards })
+/// Boards read one at a time from a PBN source, for archives too large to
+/// hold in memory at once
+///
+/// ```
+/// use pbn_to_pdf::parser::PbnReader;
+///
+/// let pbn = "[Board \"1\"]\n[Dealer \"N\"]\n\n[Board \"2\"]\n[Dealer \"E\"]\n";
+/// let numbers: Vec<u32> = PbnReader::new(pbn.as_bytes())
+///     .filter_map(Result::ok)
+///     .filter_map(|board| board.number)
+///     .collect();
+/// assert_eq!(numbers, [1, 2]);
+/// ```
+///
+/// Any `BufRead` works: a `BufReader` over a file, or the bytes of a
+/// memory-mapped one. A board is yielded once the next one starts (or the
+/// input ends), and reading stops at the first error. Each line is read as
+/// UTF-8, or as Windows-1252 if it isn't valid UTF-8; unlike [decode_pbn]
+/// there's no UTF-16 detection, so convert such files first.
+///
+/// [decode_pbn]: super::decode_pbn
+pub struct PbnReader<R> {
+    reader: R,
+    /// None once the input is finished or has failed
+    parser: Option<BoardParser>,
+    line_number: usize,
+    header_lines: Vec<String>,
+    buffer: Vec<u8>,
 }
 
-/// Parse all board records from the file
-fn parse_boards(lines: &[&str]) -> Result<Vec<Board>, PbnError> {
+impl<R: BufRead> PbnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: Some(BoardParser::new()),
+            line_number: 0,
+            header_lines: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// The `%` header directives read so far. They usually all come before
+    /// the first board, and are complete once the boards have been read.
+    pub fn metadata(&self) -> PbnMetadata {
+        self.headers().0
+    }
+
+    /// Header directives read so far that were recognized but not applied
+    pub fn warnings(&self) -> Vec<PbnWarning> {
+        self.headers().1
+    }
+
+    fn headers(&self) -> (PbnMetadata, Vec<PbnWarning>) {
+        let lines: Vec<&str> = self.header_lines.iter().map(String::as_str).collect();
+        parse_headers_with_warnings(&lines)
+    }
+}
+
+impl<R: BufRead> Iterator for PbnReader<R> {
+    type Item = Result<Board, PbnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let parser = self.parser.as_mut()?;
+            if !parser.ready.is_empty() {
+                return Some(Ok(parser.ready.remove(0)));
+            }
+
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => return self.parser.take()?.finish().transpose(),
+                Ok(_) => {
+                    self.line_number += 1;
+                    let mut bytes = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+                    bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+                    if self.line_number == 1 {
+                        bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+                    }
+                    let line = decode_line(bytes);
+                    let line = line.as_ref();
+                    if line.trim().starts_with('%') {
+                        self.header_lines.push(line.to_string());
+                    }
+                    if let Err(e) = parser.line(self.line_number, line) {
+                        self.parser = None;
+                        return Some(Err(e));
+                    }
+                }
+                Err(e) => {
+                    self.parser = None;
+                    return Some(Err(PbnError::ParseError {
+                        message: format!("failed to read input: {}", e),
+                        span: Span::new(self.line_number + 1, 1, 1, ""),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Placement of commentary that follows a tag (or the section it starts)
+fn placement_after_tag(name: &str) -> CommentaryPlacement {
+    match name {
+        "Event" | "Site" | "Date" | "Board" | "West" | "North" | "East" | "South" | "Dealer"
+        | "Vulnerable" | "Room" | "Stage" | "Table" => CommentaryPlacement::Event,
+        "Deal" => CommentaryPlacement::Diagram,
+        "Auction" | "Note" => CommentaryPlacement::Auction,
+        _ => CommentaryPlacement::Final,
+    }
+}
+
+/// Parse all board records from the file, with the problems a lenient
+/// parser skipped
+fn parse_boards(
+    lines: &[&str],
+    mut parser: BoardParser,
+) -> Result<(Vec<Board>, Vec<Diagnostic>), PbnError> {
     let mut boards = Vec::new();
-    let mut current_board: Option<Board> = None;
-    let mut in_auction = false;
-    let mut auction_dealer: Option<Direction> = None;
-    let mut auction_lines = Vec::new();
-    let mut in_play = false;
-    let mut play_leader: Option<Direction> = None;
-    let mut play_lines = Vec::new();
-    let mut in_commentary = false;
-    let mut commentary_lines: Vec<&str> = Vec::new();
-
-    for line in lines {
+    for (index, line) in lines.iter().enumerate() {
+        parser.line(index + 1, line)?;
+        boards.append(&mut parser.ready);
+    }
+    boards.extend(parser.finish()?);
+    Ok((boards, parser.diagnostics))
+}
+
+/// Whether a line is a tag that starts a new board
+fn starts_board(trimmed: &str) -> bool {
+    parse_tag_pair(trimmed).is_ok_and(|(_, tag)| tag.name == "Event" || tag.name == "Board")
+}
+
+/// A score table section of a board record
+#[derive(Debug, Clone, Copy)]
+enum ScoreSection {
+    /// `[ScoreTable]`: every table's result
+    Results,
+    /// `[TotalScoreTable]`: the event's final ranking
+    Ranking,
+}
+
+/// Board records parsed a line at a time. Boards are moved to `ready` once
+/// the next one starts, so a caller can take them while reading on.
+#[derive(Default)]
+struct BoardParser {
+    /// Finished boards not yet taken
+    ready: Vec<Board>,
+    current_board: Option<Board>,
+    in_auction: bool,
+    auction_dealer: Option<Direction>,
+    auction_lines: Vec<String>,
+    in_play: bool,
+    play_leader: Option<Direction>,
+    play_lines: Vec<String>,
+    /// [Note] tags belong to the play, not the auction, until the next
+    /// [Auction] or board
+    notes_for_play: bool,
+    /// The current board's score table that lines belong to
+    score_section: Option<ScoreSection>,
+    in_commentary: bool,
+    commentary_lines: Vec<String>,
+    /// Line number where the open commentary started
+    commentary_start: usize,
+    placement: CommentaryPlacement,
+    /// Values `#` tags copy
+    history: TagHistory,
+    /// Skip what can't be read, noting it in `diagnostics`, instead of failing
+    lenient: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl BoardParser {
+    fn new() -> Self {
+        Self {
+            placement: CommentaryPlacement::Event,
+            ..Default::default()
+        }
+    }
+
+    fn lenient() -> Self {
+        Self {
+            lenient: true,
+            ..Self::new()
+        }
+    }
+
+    /// Feed line `number` (1-based) of the file
+    fn line(&mut self, number: usize, line: &str) -> Result<(), PbnError> {
         let trimmed = line.trim();
 
         // Skip empty lines and comments (but not if we're in commentary)
-        if !in_commentary
+        if !self.in_commentary
             && (trimmed.is_empty() || trimmed.starts_with('%') || trimmed.starts_with(';'))
         {
-            continue;
+            return Ok(());
         }
 
-        // Handle multi-line commentary
-        if in_commentary {
-            commentary_lines.push(*line);
+        // Handle multi-line commentary. When lenient, the next board ends
+        // commentary whose closing brace is missing.
+        if self.in_commentary && !(self.lenient && starts_board(trimmed)) {
+            self.commentary_lines.push(line.to_string());
             if line.contains('}') {
                 // End of commentary block
-                in_commentary = false;
-                if let Some(ref mut board) = current_board {
-                    let full_text = commentary_lines.join("\n");
-                    if let Some((commentary_text, _)) = extract_commentary(&full_text) {
-                        if let Ok(block) = parse_commentary(commentary_text) {
-                            board.commentary.push(block);
-                        }
-                    }
-                }
-                commentary_lines.clear();
+                self.in_commentary = false;
+                let full_text = self.commentary_lines.join("\n");
+                self.add_commentary(&full_text);
+                self.commentary_lines.clear();
             }
-            continue;
+            return Ok(());
+        }
+        if self.in_commentary {
+            self.drop_commentary();
         }
 
         // Check for tag pairs
         if trimmed.starts_with('[') {
-            // Finish any ongoing auction section
-            if in_auction && !auction_lines.is_empty() {
-                if let (Some(ref mut board), Some(dealer)) = (&mut current_board, auction_dealer) {
-                    let auction_text = auction_lines.join(" ");
-                    if let Ok(auction) = parse_auction(dealer, &auction_text) {
-                        board.auction = Some(auction);
-                    }
+            // Finish any ongoing auction, play and score table sections
+            self.finish_auction();
+            self.finish_play();
+            self.score_section = None;
+
+            // Parse the tag pair
+            let Ok((_, mut tag)) = parse_tag_pair(trimmed) else {
+                if self.lenient {
+                    self.diagnostics
+                        .push(Diagnostic::warning(number, "malformed tag skipped"));
+                }
+                return Ok(());
+            };
+            self.history.resolve(&mut tag);
+            if self.lenient && tag.name == "Deal" && self.current_board.is_some() {
+                if let Err(e) = parse_deal(&tag.value) {
+                    self.diagnostics.push(Diagnostic::warning(
+                        number,
+                        format!("invalid deal ({}); board kept without it", e),
+                    ));
+                    return Ok(());
                 }
-                auction_lines.clear();
-                in_auction = false;
             }
-
-            // Finish any ongoing play section
-            if in_play && !play_lines.is_empty() {
-                if let (Some(ref mut board), Some(leader)) = (&mut current_board, play_leader) {
-                    let play_text = play_lines.join(" ");
-                    if let Ok(play) = parse_play(leader, &play_text) {
-                        board.play = Some(play);
+            self.placement = placement_after_tag(&tag.name);
+            match tag.name.as_str() {
+                "Play" => self.notes_for_play = true,
+                "Auction" | "Event" => self.notes_for_play = false,
+                "Note" if self.notes_for_play => {
+                    if let Some(ref mut board) = self.current_board {
+                        if let (Some(play), Some((num, text))) =
+                            (board.play.as_mut(), parse_note_value(&tag.value))
+                        {
+                            play.add_note(num, text);
+                        }
                     }
+                    self.placement = CommentaryPlacement::Final;
+                    return Ok(());
                 }
-                play_lines.clear();
-                in_play = false;
+                _ => {}
             }
-
-            // Parse the tag pair
-            if let Ok((_, tag)) = parse_tag_pair(trimmed) {
-                process_tag(
-                    &mut current_board,
-                    &mut boards,
-                    tag,
-                    &mut in_auction,
-                    &mut auction_dealer,
-                    &mut in_play,
-                    &mut play_leader,
-                )?;
+            let section = match tag.name.as_str() {
+                "ScoreTable" => Some(ScoreSection::Results),
+                "TotalScoreTable" => Some(ScoreSection::Ranking),
+                _ => None,
+            };
+            if let Some(section) = section {
+                if let Some(ref mut board) = self.current_board {
+                    let table = Some(ScoreTable::from_pbn(&tag.value));
+                    match section {
+                        ScoreSection::Results => board.score_table = table,
+                        ScoreSection::Ranking => board.total_score_table = table,
+                    }
+                    self.score_section = Some(section);
+                    self.in_auction = false;
+                    self.in_play = false;
+                }
+                return Ok(());
             }
+            process_tag(
+                &mut self.current_board,
+                &mut self.ready,
+                tag,
+                &mut self.in_auction,
+                &mut self.auction_dealer,
+                &mut self.in_play,
+                &mut self.play_leader,
+            )?;
         } else if trimmed.starts_with('{') {
             // Start of commentary block
-            commentary_lines.push(*line);
             if line.contains('}') {
                 // Single-line commentary
-                if let Some(ref mut board) = current_board {
-                    if let Some((commentary_text, _)) = extract_commentary(line) {
-                        if let Ok(block) = parse_commentary(commentary_text) {
-                            board.commentary.push(block);
-                        }
-                    }
-                }
-                commentary_lines.clear();
+                self.add_commentary(line);
             } else {
                 // Multi-line commentary
-                in_commentary = true;
+                self.commentary_lines.push(line.to_string());
+                self.in_commentary = true;
+                self.commentary_start = number;
             }
-        } else if in_auction {
+        } else if self.in_auction {
             // Continuation of auction section
-            auction_lines.push(trimmed);
-        } else if in_play {
+            self.auction_lines.push(trimmed.to_string());
+        } else if self.in_play {
             // Continuation of play section
-            play_lines.push(trimmed);
+            self.play_lines.push(trimmed.to_string());
+        } else if let Some(section) = self.score_section {
+            // A table's result, or a pair's standing
+            let table = self.current_board.as_mut().and_then(|board| match section {
+                ScoreSection::Results => board.score_table.as_mut(),
+                ScoreSection::Ranking => board.total_score_table.as_mut(),
+            });
+            if let Some(table) = table {
+                table.add_row(trimmed);
+            }
         }
+        Ok(())
     }
 
-    // Finish any final auction section
-    if in_auction && !auction_lines.is_empty() {
-        if let (Some(ref mut board), Some(dealer)) = (&mut current_board, auction_dealer) {
-            let auction_text = auction_lines.join(" ");
-            if let Ok(auction) = parse_auction(dealer, &auction_text) {
-                board.auction = Some(auction);
-            }
+    /// The last board, once the whole file has been fed
+    fn finish(&mut self) -> Result<Option<Board>, PbnError> {
+        // Commentary still open here has swallowed the rest of the file
+        if self.in_commentary && !self.lenient {
+            let line = &self.commentary_lines[0];
+            return Err(PbnError::UnclosedComment {
+                span: Span::of(self.commentary_start, line, "{"),
+            });
+        }
+        if self.in_commentary {
+            self.drop_commentary();
         }
+
+        // Finish any final auction and play sections
+        self.finish_auction();
+        self.finish_play();
+
+        Ok(self.current_board.take())
+    }
+
+    /// Forget commentary that was never closed, noting where it started
+    fn drop_commentary(&mut self) {
+        self.diagnostics.push(Diagnostic::error(
+            self.commentary_start,
+            "unclosed commentary brace; commentary skipped",
+        ));
+        self.in_commentary = false;
+        self.commentary_lines.clear();
     }
 
-    // Finish any final play section
-    if in_play && !play_lines.is_empty() {
-        if let (Some(ref mut board), Some(leader)) = (&mut current_board, play_leader) {
-            let play_text = play_lines.join(" ");
-            if let Ok(play) = parse_play(leader, &play_text) {
-                board.play = Some(play);
+    fn add_commentary(&mut self, text: &str) {
+        if let Some(ref mut board) = self.current_board {
+            if let Some((commentary_text, _)) = extract_commentary(text) {
+                if let Ok(block) = parse_commentary(commentary_text) {
+                    board.commentary.push(block.with_placement(self.placement));
+                }
             }
         }
     }
 
-    // Save the last board
-    if let Some(board) = current_board {
-        boards.push(board);
+    fn finish_auction(&mut self) {
+        if self.in_auction && !self.auction_lines.is_empty() {
+            if let (Some(ref mut board), Some(dealer)) =
+                (&mut self.current_board, self.auction_dealer)
+            {
+                let auction_text = self.auction_lines.join(" ");
+                if let Ok(auction) = parse_auction(dealer, &auction_text) {
+                    board.auction = Some(auction);
+                }
+            }
+            self.auction_lines.clear();
+            self.in_auction = false;
+        }
     }
 
-    Ok(boards)
+    fn finish_play(&mut self) {
+        if self.in_play && !self.play_lines.is_empty() {
+            if let (Some(ref mut board), Some(leader)) = (&mut self.current_board, self.play_leader)
+            {
+                let play_text = self.play_lines.join(" ");
+                if let Ok(play) = parse_play(leader, &play_text) {
+                    board.play = Some(play);
+                }
+            }
+            self.play_lines.clear();
+            self.in_play = false;
+        }
+    }
 }
 
 /// Process a single tag pair
@@ -214,6 +499,25 @@ fn process_tag(
                 }
             }
         }
+        "Room" => {
+            if let Some(ref mut board) = current_board {
+                board.room = Room::from_pbn(&tag.value);
+            }
+        }
+        "Stage" => {
+            if let Some(ref mut board) = current_board {
+                if !tag.value.is_empty() {
+                    board.stage = Some(tag.value);
+                }
+            }
+        }
+        "Table" => {
+            if let Some(ref mut board) = current_board {
+                if !tag.value.is_empty() {
+                    board.table = Some(tag.value);
+                }
+            }
+        }
         "Board" => {
             // BridgeComposer sometimes omits [Event] before the first board.
             // If no current board exists, start a new one so the board's data
@@ -267,12 +571,20 @@ fn process_tag(
         }
         "Contract" => {
             if let Some(ref mut board) = current_board {
-                if let Some(mut contract) = Contract::parse(&tag.value) {
-                    // If declarer was already set, use it
-                    if let Some(declarer) = board.declarer {
-                        contract.declarer = declarer;
+                match Contract::parse_tag(&tag.value) {
+                    Some(Some(mut contract)) => {
+                        // If declarer was already set, use it
+                        if let Some(declarer) = board.declarer {
+                            contract.declarer = declarer;
+                        }
+                        board.contract = Some(contract);
+                        board.passed_out = false;
+                    }
+                    Some(None) => {
+                        board.contract = None;
+                        board.passed_out = true;
                     }
-                    board.contract = Some(contract);
+                    None => {}
                 }
             }
         }
@@ -315,6 +627,17 @@ fn process_tag(
                 }
             }
         }
+        "BestLeads" => {
+            // Double-dummy best opening leads, e.g. "S4 DK"
+            if let Some(ref mut board) = current_board {
+                board.best_leads = parse_card_list(&tag.value);
+            }
+        }
+        "DoubleDummyTricks" => {
+            if let Some(ref mut board) = current_board {
+                board.double_dummy = DoubleDummyTricks::from_pbn(&tag.value);
+            }
+        }
         "Hidden" => {
             // Hidden hands (e.g., "NS", "ESW", "NESW")
             if let Some(ref mut board) = current_board {
@@ -453,4 +776,289 @@ mod tests {
         assert!(board.hidden.south);
         assert!(!board.hidden.west);
     }
+
+    #[test]
+    fn test_parse_double_dummy_tricks() {
+        let content = r#"[Event "Test"]
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ.JT9.876.5432 JT9.AKQ.543.8765 876.543.AKQ.JT98 543.876.JT9.AKQ6"]
+[DoubleDummyTricks "6767867678767676767"]
+[Board "2"]
+[Dealer "E"]
+[Vulnerable "None"]
+[Deal "N:AKQ.JT9.876.5432 JT9.AKQ.543.8765 876.543.AKQ.JT98 543.876.JT9.AKQ6"]
+[DoubleDummyTricks "67678676787676767676"]
+"#;
+
+        let result = parse_pbn(content).unwrap();
+        assert!(result.boards[0].double_dummy.is_none());
+
+        let dd = result.boards[1].double_dummy.unwrap();
+        assert_eq!(
+            dd.tricks(Direction::North, crate::model::Strain::NoTrump),
+            6
+        );
+        assert_eq!(dd.tricks(Direction::South, crate::model::Strain::Clubs), 8);
+    }
+
+    #[test]
+    fn test_commentary_placement() {
+        let content = r#"[Event "Test"]
+[Date ""]
+{Event}
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ.JT9.876.5432 JT9.AKQ.543.8765 876.543.AKQ.JT98 543.876.JT9.AKQ6"]
+{Diagram}
+[Result ""]
+{Final}
+[Auction "N"]
+1NT Pass 3NT AP
+{Auction}
+[Play "E"]
+D2 D6 DA D3
+{Play}
+"#;
+
+        let result = parse_pbn(content).unwrap();
+        let placements: Vec<_> = result.boards[0]
+            .commentary
+            .iter()
+            .map(|block| (block.content.to_plain_text(), block.placement))
+            .collect();
+        let expected = [
+            ("Event", CommentaryPlacement::Event),
+            ("Diagram", CommentaryPlacement::Diagram),
+            ("Final", CommentaryPlacement::Final),
+            ("Auction", CommentaryPlacement::Auction),
+            ("Play", CommentaryPlacement::Final),
+        ];
+        assert_eq!(placements.len(), expected.len());
+        for ((text, placement), (expected_text, expected_placement)) in
+            placements.iter().zip(expected)
+        {
+            assert_eq!(text, expected_text);
+            assert_eq!(*placement, expected_placement);
+        }
+    }
+
+    #[test]
+    fn test_unclosed_commentary() {
+        let content = "[Event \"\"]\n[Board \"1\"]\n  {Never closed\n[Board \"2\"]\n";
+        let error = parse_pbn(content).unwrap_err();
+        assert!(matches!(error, PbnError::UnclosedComment { .. }));
+        assert_eq!(error.span(), &Span::new(3, 3, 1, "  {Never closed"));
+
+        let mut reader = PbnReader::new(content.as_bytes());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(PbnError::UnclosedComment { .. }))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_lenient_recovers_unclosed_commentary() {
+        let content =
+            "[Event \"\"]\n[Board \"1\"]\n  {Never closed\n[Board \"2\"]\n[Dealer \"E\"]\n";
+        let (file, diagnostics) = parse_pbn_lenient(content);
+        assert_eq!(file.boards.len(), 1);
+        assert_eq!(file.boards[0].number, Some(2));
+        assert_eq!(file.boards[0].dealer, Some(Direction::East));
+        assert!(file.boards[0].commentary.is_empty());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 3);
+        assert!(diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn test_lenient_skips_bad_tags_and_deals() {
+        let content = r#"[Event ""]
+[Board "1"]
+[Deal "N:AKQ.JT9.876.5432 JT9.AKQ.543.8765 876.543.AKQ.JT98 543.876.JT9.AKQ6"]
+
+[Event ""]
+[Board "2"]
+[Deal "N:AKQ.JT9.876 JT9.AKQ.543.8765"]
+[Contract "4S"]
+
+[Event ""]
+[Board "3"]
+[Dealer "S"
+[Deal "N:AKQ.JT9.876.5432 JT9.AKQ.543.8765 876.543.AKQ.JT98 543.876.JT9.AKQ6"]
+"#;
+        // Both modes keep board 2 without its deal
+        assert_eq!(parse_pbn(content).unwrap().boards.len(), 3);
+
+        let (file, diagnostics) = parse_pbn_lenient(content);
+        let numbers: Vec<_> = file.boards.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, [Some(1), Some(2), Some(3)]);
+        assert!(file.boards[1].contract.is_some());
+        assert_eq!(file.boards[1].deal.north.card_count(), 0);
+        assert_eq!(file.boards[2].dealer, None);
+        let found: Vec<_> = diagnostics.iter().map(|d| (d.line, d.is_error())).collect();
+        assert_eq!(found, [(7, false), (12, false)]);
+    }
+
+    #[test]
+    fn test_room_stage_and_table() {
+        let content = r#"[Event "Final"]
+[Stage "Segment 2"]
+[Room "Closed"]
+[Table "3"]
+[Board "9"]
+"#;
+        let board = &parse_pbn(content).unwrap().boards[0];
+        assert_eq!(board.room, Some(Room::Closed));
+        assert_eq!(board.stage.as_deref(), Some("Segment 2"));
+        assert_eq!(board.table.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_contract_tag_forms() {
+        let content = r#"[Event ""]
+[Board "1"]
+[Declarer "W"]
+[Contract "3NTX"]
+[Event ""]
+[Board "2"]
+[Contract "4SXX"]
+[Declarer "N"]
+[Event ""]
+[Board "3"]
+[Contract "Pass"]
+[Event ""]
+[Board "4"]
+[Contract "?"]
+"#;
+        let boards = parse_pbn(content).unwrap().boards;
+        let contract = boards[0].contract.as_ref().unwrap();
+        assert!(contract.doubled && !contract.redoubled);
+        assert_eq!(contract.declarer, Direction::West);
+        let contract = boards[1].contract.as_ref().unwrap();
+        assert!(contract.redoubled && !contract.doubled);
+        assert_eq!(contract.declarer, Direction::North);
+        assert!(boards[2].contract.is_none() && boards[2].passed_out);
+        assert!(boards[3].contract.is_none() && !boards[3].passed_out);
+    }
+
+    #[test]
+    fn test_score_table() {
+        let content = r#"[Event ""]
+[Board "1"]
+[Auction "N"]
+1NT Pass 3NT AP
+[ScoreTable "PairId_NS\2R;PairId_EW\2R;Contract\5L;Declarer\1R;Result\2R"]
+ 1  4 3NT   S 10
+ 2  3 4S    N  9
+{After the table}
+[Event ""]
+[Board "2"]
+"#;
+        let boards = parse_pbn(content).unwrap().boards;
+        assert_eq!(boards[0].auction.as_ref().unwrap().calls.len(), 4);
+        let table = boards[0].score_table.as_ref().unwrap();
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.value(1, "Contract"), Some("4S"));
+        assert_eq!(boards[0].commentary.len(), 1);
+        assert!(boards[1].score_table.is_none());
+    }
+
+    #[test]
+    fn test_total_score_table() {
+        let content = r#"[Event ""]
+[Board "1"]
+[ScoreTable "PairId_NS\2R;PairId_EW\2R;Contract\5L;Declarer\1R;Result\2R"]
+ 1  2 3NT   S 10
+[TotalScoreTable "Rank\2R;PairId\2R;TotalScoreMP\5R;TotalPercentage\5R"]
+ 1  1  1.0 100.00
+ 2  2  0.0   0.00
+"#;
+        let board = &parse_pbn(content).unwrap().boards[0];
+        assert_eq!(board.score_table.as_ref().unwrap().rows.len(), 1);
+        let standings = board.total_score_table.as_ref().unwrap().standings();
+        assert_eq!(standings.len(), 2);
+        assert_eq!(standings[1].percent.as_deref(), Some("0.00"));
+    }
+
+    #[test]
+    fn test_play_notes() {
+        let content = r#"[Event ""]
+[Board "1"]
+[Auction "N"]
+1NT =1= Pass 3NT AP
+[Note "1:15-17"]
+[Play "E"]
+S2 =1= SA S3 S4
+HK=2= H2 H3 H4
+[Note "1:Fourth best"]
+[Note "2:Asks for unblock"]
+{After the play}
+"#;
+        let board = &parse_pbn(content).unwrap().boards[0];
+        let auction = board.auction.as_ref().unwrap();
+        assert_eq!(auction.notes.len(), 1);
+        assert_eq!(auction.notes[&1], "15-17");
+        let play = board.play.as_ref().unwrap();
+        assert_eq!(play.notes[&1], "Fourth best");
+        assert_eq!(play.tricks[0].notes, [Some(1), None, None, None]);
+        assert_eq!(play.tricks[1].notes[0], Some(2));
+        assert_eq!(play.footnotes()[1].2, "Asks for unblock");
+        assert_eq!(board.commentary[0].placement, CommentaryPlacement::Final);
+    }
+
+    #[test]
+    fn test_inherited_tag_values() {
+        let content = r###"[Event "Club Night"]
+[Site "Town Hall"]
+[Board "1"]
+[Dealer "N"]
+
+[Event "#"]
+[Site "##"]
+[Board "2"]
+[Dealer "#"]
+"###;
+        let result = parse_pbn(content).unwrap();
+        let second = &result.boards[1];
+        assert_eq!(second.event.as_deref(), Some("Club Night"));
+        assert_eq!(second.site.as_deref(), Some("Town Hall"));
+        assert_eq!(second.dealer, Some(Direction::North));
+        assert_eq!(second.number, Some(2));
+    }
+
+    #[test]
+    fn test_reader_matches_parse_pbn() {
+        let content = "%HRTitleEvent Club Night\r\n\
+            [Event \"\"]\r\n[Board \"1\"]\r\n[Dealer \"N\"]\r\n\
+            [Auction \"N\"]\r\n1NT Pass\r\n3NT AP\r\n{Easy\r\ngame}\r\n\r\n\
+            [Event \"\"]\r\n[Board \"2\"]\r\n[Dealer \"E\"]\r\n";
+        let parsed = parse_pbn(content).unwrap();
+
+        let mut reader = PbnReader::new(content.as_bytes());
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(reader.metadata().title_event.as_deref(), Some("Club Night"));
+        let rest: Vec<Board> = reader.map(Result::unwrap).collect();
+
+        assert_eq!(rest.len(), 1);
+        assert_eq!(first.number, parsed.boards[0].number);
+        assert_eq!(
+            first.auction.map(|a| a.calls.len()),
+            parsed.boards[0].auction.as_ref().map(|a| a.calls.len())
+        );
+        assert_eq!(first.commentary.len(), 1);
+        assert_eq!(rest[0].dealer, Some(Direction::East));
+    }
+
+    #[test]
+    fn test_reader_decodes_windows_1252() {
+        let content = b"\xEF\xBB\xBF[Event \"Caf\xc3\xa9\"]\n[Board \"1\"]\n\n\
+            [Event \"Caf\xe9 \x93Bridge\x94\"]\n[Board \"2\"]\n";
+        let boards: Vec<Board> = PbnReader::new(&content[..]).map(Result::unwrap).collect();
+        assert_eq!(boards[0].event.as_deref(), Some("Café"));
+        assert_eq!(boards[1].event.as_deref(), Some("Café “Bridge”"));
+    }
 }
@@ -1,8 +1,10 @@
+use super::auction::{extract_annotation, parse_note_reference};
 use crate::model::{Card, Direction, PlaySequence, Rank, Suit, Trick};
 
 /// Parse a play section from PBN
 /// The play starts after [Play "X"] where X is the opening leader
-/// Cards are in format SR where S is suit (S/H/D/C) and R is rank
+/// Cards are in format SR where S is suit (S/H/D/C) and R is rank, each
+/// optionally followed by a note reference (=N=), attached or on its own
 pub fn parse_play(opening_leader: Direction, input: &str) -> Result<PlaySequence, String> {
     let mut play = PlaySequence::new(opening_leader);
     let input = input.trim();
@@ -28,6 +30,16 @@ pub fn parse_play(opening_leader: Direction, input: &str) -> Result<PlaySequence
             break;
         }
 
+        // A note reference for the card before it
+        if let Some(number) = parse_note_reference(token) {
+            if card_index > 0 {
+                current_trick.set_note(card_index - 1, number);
+            } else if let Some(trick) = play.tricks.last_mut() {
+                trick.set_note(3, number);
+            }
+            continue;
+        }
+
         // Handle dashes (card not played / unknown)
         if token == "-" {
             card_index += 1;
@@ -40,8 +52,12 @@ pub fn parse_play(opening_leader: Direction, input: &str) -> Result<PlaySequence
         }
 
         // Parse card
-        if let Some(card) = parse_card(token) {
+        let (card_token, annotation) = extract_annotation(token);
+        if let Some(card) = parse_card(&card_token) {
             current_trick.set_card(card_index, card);
+            if let Some(number) = annotation.and_then(|a| a.parse().ok()) {
+                current_trick.set_note(card_index, number);
+            }
             card_index += 1;
 
             if card_index >= 4 {
@@ -62,8 +78,24 @@ pub fn parse_play(opening_leader: Direction, input: &str) -> Result<PlaySequence
     Ok(play)
 }
 
+/// Parse a list of cards separated by spaces or commas, e.g. "S4, DK"
+/// Unrecognized tokens are skipped.
+pub fn parse_card_list(input: &str) -> Vec<Card> {
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .filter_map(|token| {
+            let card = parse_card(token);
+            if card.is_none() {
+                log::debug!("Skipping unrecognized card: {}", token);
+            }
+            card
+        })
+        .collect()
+}
+
 /// Parse a card notation: "SQ" = Queen of Spades, "HA" = Ace of Hearts
-fn parse_card(input: &str) -> Option<Card> {
+pub(crate) fn parse_card(input: &str) -> Option<Card> {
     let mut chars = input.chars();
 
     let suit_char = chars.next()?;
@@ -94,6 +126,18 @@ mod tests {
         assert_eq!(card.rank, Rank::Ten);
     }
 
+    #[test]
+    fn test_parse_card_list() {
+        let cards = parse_card_list("S4, DK  HT");
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[0], Card::new(Suit::Spades, Rank::Four));
+        assert_eq!(cards[1], Card::new(Suit::Diamonds, Rank::King));
+        assert_eq!(cards[2], Card::new(Suit::Hearts, Rank::Ten));
+
+        assert!(parse_card_list("").is_empty());
+        assert_eq!(parse_card_list("S4 ?? DK").len(), 2);
+    }
+
     #[test]
     fn test_parse_play_single_trick() {
         let play = parse_play(Direction::West, "SQ SK S8 S4").unwrap();
@@ -116,6 +160,15 @@ mod tests {
         assert!(!play.tricks[0].is_complete());
     }
 
+    #[test]
+    fn test_parse_play_note_references() {
+        let play = parse_play(Direction::West, "SQ =1= SK S8 S4=2= HA").unwrap();
+
+        assert_eq!(play.tricks[0].notes, [Some(1), None, None, Some(2)]);
+        assert_eq!(play.tricks[0].cards[3].unwrap().rank, Rank::Four);
+        assert_eq!(play.tricks[1].notes, [None; 4]);
+    }
+
     #[test]
     fn test_parse_empty_play() {
         let play = parse_play(Direction::North, "").unwrap();
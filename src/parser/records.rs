@@ -0,0 +1,212 @@
+//! PBN files as text: the header and one record per game
+//!
+//! Subcommands that write PBN (`merge`, `renumber`, `extract`) edit the text
+//! of each game rather than re-serializing parsed boards, so commentary
+//! markup and tags this crate doesn't read survive unchanged. Games are found
+//! the same way `parse_pbn` finds boards, so record `i` holds board `i`.
+
+use super::tags::{parse_tag_pair, TagPair};
+
+/// One game's lines, from the tag that starts it up to the next game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub lines: Vec<String>,
+}
+
+impl GameRecord {
+    /// The tag lines (not those quoted in commentary) and their line indexes
+    fn tags(&self) -> impl Iterator<Item = (usize, TagPair)> + '_ {
+        let mut in_commentary = false;
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, line)| {
+                let trimmed = line.trim();
+                if in_commentary || trimmed.starts_with('{') {
+                    in_commentary = !line.contains('}');
+                    return None;
+                }
+                parse_tag_pair(trimmed).ok().map(|(_, tag)| (index, tag))
+            })
+    }
+
+    /// Value of the first tag with this name
+    pub fn tag(&self, name: &str) -> Option<String> {
+        self.tags()
+            .find(|(_, tag)| tag.name == name)
+            .map(|(_, tag)| tag.value)
+    }
+
+    /// Replace the value of a tag, or add the tag after `[Board]` (or after
+    /// the game's first tag when it has no board number)
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        let line = TagPair::new(name, value).to_string();
+        let tags: Vec<_> = self.tags().collect();
+        let mut position = None;
+        for (index, tag) in tags {
+            if tag.name == name {
+                self.lines[index] = line;
+                return;
+            }
+            if tag.name == "Board" || position.is_none() {
+                position = Some(index + 1);
+            }
+        }
+        self.lines.insert(position.unwrap_or(0), line);
+    }
+
+    /// Remove every tag with this name
+    pub fn remove_tag(&mut self, name: &str) {
+        let lines: Vec<usize> = self
+            .tags()
+            .filter(|(_, tag)| tag.name == name)
+            .map(|(index, _)| index)
+            .collect();
+        for index in lines.into_iter().rev() {
+            self.lines.remove(index);
+        }
+    }
+
+    /// Whether two records hold the same board: the same lines, ignoring
+    /// indentation, blank lines and the board number
+    pub fn same_board(&self, other: &GameRecord) -> bool {
+        let content = |record: &GameRecord| {
+            let numbers: Vec<usize> = record
+                .tags()
+                .filter(|(_, tag)| tag.name == "Board")
+                .map(|(index, _)| index)
+                .collect();
+            record
+                .lines
+                .iter()
+                .enumerate()
+                .filter(|(index, line)| !numbers.contains(index) && !line.trim().is_empty())
+                .map(|(_, line)| line.trim().to_string())
+                .collect::<Vec<_>>()
+        };
+        content(self) == content(other)
+    }
+}
+
+/// The text of a PBN file, split into games
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PbnRecords {
+    /// Lines before the first game: `%` directives and comments
+    pub header: Vec<String>,
+    pub games: Vec<GameRecord>,
+}
+
+impl PbnRecords {
+    /// Split PBN content into its header and games
+    pub fn parse(content: &str) -> Self {
+        let mut records = Self::default();
+        let mut in_commentary = false;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let starts_game = if in_commentary || trimmed.starts_with('{') {
+                in_commentary = !line.contains('}');
+                false
+            } else {
+                match parse_tag_pair(trimmed) {
+                    Ok((_, tag)) if tag.name == "Event" => true,
+                    Ok((_, tag)) if tag.name == "Board" => records.games.is_empty(),
+                    _ => false,
+                }
+            };
+
+            if starts_game {
+                records.games.push(GameRecord { lines: Vec::new() });
+            }
+            match records.games.last_mut() {
+                Some(game) => game.lines.push(line.to_string()),
+                None => records.header.push(line.to_string()),
+            }
+        }
+        records
+    }
+
+    /// The PBN text: the header, then the games separated by blank lines
+    pub fn to_pbn(&self) -> String {
+        let mut sections = Vec::new();
+        let header = trim_blank_lines(&self.header);
+        if !header.is_empty() {
+            sections.push(header.join("\n"));
+        }
+        for game in &self.games {
+            sections.push(trim_blank_lines(&game.lines).join("\n"));
+        }
+
+        let mut pbn = sections.join("\n\n");
+        pbn.push('\n');
+        pbn
+    }
+}
+
+/// Lines without the blank lines at either end
+fn trim_blank_lines(lines: &[String]) -> &[String] {
+    let start = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(start, |index| index + 1);
+    &lines[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PBN: &str = "% PBN 2.1\n\
+        %HRTitleEvent Club night\n\
+        \n\
+        [Event \"Club\"]\n\
+        [Board \"1\"]\n\
+        [Deal \"N:AKQ.JT9.876.5432 - - -\"]\n\
+        {[Event \"quoted\"] in commentary}\n\
+        \n\
+        [Event \"Club\"]\n\
+        [Board \"2\"]\n\
+        [Dealer \"E\"]\n";
+
+    #[test]
+    fn test_split_records() {
+        let records = PbnRecords::parse(PBN);
+        assert_eq!(
+            records.header,
+            ["% PBN 2.1", "%HRTitleEvent Club night", ""]
+        );
+        assert_eq!(records.games.len(), 2);
+        assert_eq!(records.games[0].lines.len(), 5);
+        assert_eq!(records.games[0].tag("Board").as_deref(), Some("1"));
+        assert_eq!(records.games[1].tag("Dealer").as_deref(), Some("E"));
+        assert_eq!(records.games[1].tag("Deal"), None);
+        assert_eq!(records.to_pbn(), PBN);
+    }
+
+    #[test]
+    fn test_set_tag() {
+        let mut records = PbnRecords::parse(PBN);
+        let game = &mut records.games[1];
+        game.set_tag("Board", "12");
+        game.set_tag("Vulnerable", "Both");
+        game.remove_tag("Event");
+        assert_eq!(
+            game.lines,
+            ["[Board \"12\"]", "[Vulnerable \"Both\"]", "[Dealer \"E\"]"]
+        );
+    }
+
+    #[test]
+    fn test_same_board() {
+        let records = PbnRecords::parse(PBN);
+        let mut renumbered = records.games[0].clone();
+        renumbered.set_tag("Board", "9");
+        renumbered.lines.push(String::new());
+        assert!(records.games[0].same_board(&renumbered));
+        assert!(!records.games[0].same_board(&records.games[1]));
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use nom::{
     bytes::complete::take_while1,
     character::complete::{char, space0},
@@ -20,6 +23,37 @@ impl TagPair {
     }
 }
 
+impl fmt::Display for TagPair {
+    /// The tag as written in PBN, escaping quotes and backslashes
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.value.replace('\\', "\\\\").replace('"', "\\\"");
+        write!(f, "[{} \"{}\"]", self.name, value)
+    }
+}
+
+/// Tag values of earlier games, for PBN's shorthand of writing `#` (or `##`)
+/// for a value that is the same as in the previous game
+#[derive(Debug, Clone, Default)]
+pub struct TagHistory {
+    values: HashMap<String, String>,
+}
+
+impl TagHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace a `#` value with the one it stands for (empty when no earlier
+    /// game has the tag), or remember the value for the games that follow
+    pub fn resolve(&mut self, tag: &mut TagPair) {
+        if tag.value == "#" || tag.value == "##" {
+            tag.value = self.values.get(&tag.name).cloned().unwrap_or_default();
+        } else {
+            self.values.insert(tag.name.clone(), tag.value.clone());
+        }
+    }
+}
+
 fn is_tag_name_char(c: char) -> bool {
     c.is_alphanumeric() || c == '_'
 }
@@ -140,6 +174,16 @@ mod tests {
         assert_eq!(pair.value, r#"He said "hello""#);
     }
 
+    #[test]
+    fn test_tag_pair_round_trip() {
+        for value in ["1", "", "He said \"hi\"", "\\S led"] {
+            let tag = TagPair::new("Event", value);
+            let (_, parsed) = parse_tag_pair(&tag.to_string()).unwrap();
+            assert_eq!(parsed, tag);
+        }
+        assert_eq!(TagPair::new("Board", "7").to_string(), "[Board \"7\"]");
+    }
+
     #[test]
     fn test_suit_escape_preserved() {
         let input = r#"[Note "Open 1\S"]"#;
@@ -147,6 +191,24 @@ mod tests {
         assert_eq!(pair.value, r"Open 1\S");
     }
 
+    #[test]
+    fn test_tag_history() {
+        let mut history = TagHistory::new();
+        let mut first = TagPair::new("Site", "#");
+        history.resolve(&mut first);
+        assert_eq!(first.value, "");
+
+        history.resolve(&mut TagPair::new("Site", "Club"));
+        for value in ["#", "##"] {
+            let mut tag = TagPair::new("Site", value);
+            history.resolve(&mut tag);
+            assert_eq!(tag.value, "Club");
+        }
+        let mut other = TagPair::new("Event", "#");
+        history.resolve(&mut other);
+        assert_eq!(other.value, "");
+    }
+
     #[test]
     fn test_multiple_tag_pairs() {
         let input = r#"[Event "Test"][Site "Location"][Date "2024.01.01"]"#;
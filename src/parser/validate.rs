@@ -0,0 +1,591 @@
+//! PBN lint (`pbn-to-pdf validate`)
+//!
+//! The board parser is forgiving: it skips tags, calls and cards it can't
+//! read so a slightly broken file still renders. The validator reads the same
+//! file strictly and reports every problem with the line it was found on.
+//! Errors are things that make a board wrong (a card dealt twice, an
+//! insufficient bid, a card played from the wrong hand); warnings are things
+//! worth a look that still render (a contract tag that disagrees with the
+//! auction, an unsupported header option).
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::auction::{extract_annotation, parse_auction, split_alert};
+use super::deal::parse_deal;
+use super::header::parse_headers_with_warnings;
+use super::play::parse_card;
+use super::tags::{parse_tag_pair, TagHistory};
+use crate::model::{
+    Call, Card, Contract, Deal, Direction, Strain, Suit, RANKS_DISPLAY_ORDER, SUITS_DISPLAY_ORDER,
+};
+
+/// How serious a problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a PBN file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// 1-based line number
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn warning(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.severity, self.message)
+    }
+}
+
+/// A tag value and the line it was on
+#[derive(Debug, Clone)]
+struct Located {
+    line: usize,
+    value: String,
+}
+
+/// An auction or play section: the tag value and the tokens that follow it
+#[derive(Debug, Clone, Default)]
+struct Section {
+    line: usize,
+    value: String,
+    tokens: Vec<(usize, String)>,
+}
+
+/// The tags and sections of one board, with line numbers
+#[derive(Debug, Default)]
+struct Record {
+    line: usize,
+    board: Option<Located>,
+    dealer: Option<Located>,
+    deal: Option<Located>,
+    contract: Option<Located>,
+    declarer: Option<Located>,
+    auction: Option<Section>,
+    play: Option<Section>,
+}
+
+/// Which section continuation lines belong to
+enum Open {
+    None,
+    Auction,
+    Play,
+}
+
+/// Check a PBN file, returning its problems in line order
+pub fn validate_pbn(content: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut records: Vec<Record> = Vec::new();
+    let mut open = Open::None;
+    let mut commentary_start: Option<usize> = None;
+    let mut history = TagHistory::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim();
+
+        if commentary_start.is_some() {
+            if trimmed.contains('}') {
+                commentary_start = None;
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with(';') {
+            continue;
+        }
+        if trimmed.starts_with('%') {
+            for warning in parse_headers_with_warnings(&[trimmed]).1 {
+                diagnostics.push(Diagnostic::warning(number, warning.to_string()));
+            }
+            continue;
+        }
+        if trimmed.starts_with('{') {
+            if !trimmed.contains('}') {
+                commentary_start = Some(number);
+            }
+            continue;
+        }
+
+        if trimmed.starts_with('[') {
+            open = Open::None;
+            let Ok((rest, mut tag)) = parse_tag_pair(trimmed) else {
+                diagnostics.push(Diagnostic::error(number, "malformed tag"));
+                continue;
+            };
+            history.resolve(&mut tag);
+            let located = Located {
+                line: number,
+                value: tag.value.clone(),
+            };
+
+            let starts_board = match tag.name.as_str() {
+                "Event" => true,
+                "Board" => records.last().map_or(true, |r| r.board.is_some()),
+                _ => records.is_empty(),
+            };
+            if starts_board {
+                records.push(Record {
+                    line: number,
+                    ..Record::default()
+                });
+            }
+            let record = records.last_mut().expect("a board was just started");
+
+            match tag.name.as_str() {
+                "Board" => record.board = Some(located),
+                "Dealer" => record.dealer = Some(located),
+                "Deal" => record.deal = Some(located),
+                "Contract" => record.contract = Some(located),
+                "Declarer" => record.declarer = Some(located),
+                "Auction" | "Play" => {
+                    let mut section = Section {
+                        line: number,
+                        value: tag.value,
+                        tokens: Vec::new(),
+                    };
+                    section.tokens.extend(tokens(number, rest));
+                    if tag.name == "Auction" {
+                        record.auction = Some(section);
+                        open = Open::Auction;
+                    } else {
+                        record.play = Some(section);
+                        open = Open::Play;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let section = records.last_mut().and_then(|record| match open {
+            Open::Auction => record.auction.as_mut(),
+            Open::Play => record.play.as_mut(),
+            Open::None => None,
+        });
+        match section {
+            Some(section) => section.tokens.extend(tokens(number, trimmed)),
+            None => diagnostics.push(Diagnostic::warning(
+                number,
+                format!("unexpected text outside a tag: {}", trimmed),
+            )),
+        }
+    }
+
+    if let Some(line) = commentary_start {
+        diagnostics.push(Diagnostic::error(
+            line,
+            "commentary is never closed with '}'",
+        ));
+    }
+    for record in &records {
+        validate_record(record, &mut diagnostics);
+    }
+
+    diagnostics.sort_by_key(|d| d.line);
+    diagnostics
+}
+
+/// Whitespace-separated tokens of a section line, skipping `{...}` comments
+fn tokens(line: usize, text: &str) -> Vec<(usize, String)> {
+    let text = match text.find('{') {
+        Some(start) => &text[..start],
+        None => text,
+    };
+    text.split_whitespace()
+        .map(|token| (line, token.to_string()))
+        .collect()
+}
+
+fn validate_record(record: &Record, diagnostics: &mut Vec<Diagnostic>) {
+    let dealer = record.dealer.as_ref().and_then(|tag| {
+        let direction = single_direction(&tag.value);
+        if direction.is_none() {
+            diagnostics.push(Diagnostic::error(
+                tag.line,
+                format!("invalid dealer \"{}\"", tag.value),
+            ));
+        }
+        direction
+    });
+
+    let deal = match &record.deal {
+        Some(tag) => validate_deal(tag, diagnostics),
+        None => {
+            diagnostics.push(Diagnostic::warning(record.line, "board has no [Deal] tag"));
+            None
+        }
+    };
+
+    let declarer = record.declarer.as_ref().and_then(|tag| {
+        let value = tag.value.trim_start_matches('^');
+        if value.is_empty() || value == "?" {
+            return None;
+        }
+        let direction = single_direction(value);
+        if direction.is_none() {
+            diagnostics.push(Diagnostic::error(
+                tag.line,
+                format!("invalid declarer \"{}\"", tag.value),
+            ));
+        }
+        direction
+    });
+
+    let contract = record.contract.as_ref().and_then(|tag| {
+        let value = tag.value.trim();
+        if value.is_empty() || value == "?" {
+            return None;
+        }
+        let contract = Contract::parse_tag(value);
+        if contract.is_none() {
+            diagnostics.push(Diagnostic::error(
+                tag.line,
+                format!("invalid contract \"{}\"", tag.value),
+            ));
+        }
+        contract.flatten()
+    });
+
+    if let Some(section) = &record.auction {
+        let auction_dealer = single_direction(&section.value);
+        match (auction_dealer, dealer) {
+            (None, _) => diagnostics.push(Diagnostic::error(
+                section.line,
+                format!("invalid auction dealer \"{}\"", section.value),
+            )),
+            (Some(auction_dealer), Some(dealer)) if auction_dealer != dealer => {
+                diagnostics.push(Diagnostic::error(
+                    section.line,
+                    format!(
+                        "auction starts with {:?} but [Dealer] is {:?}",
+                        auction_dealer, dealer
+                    ),
+                ))
+            }
+            _ => {}
+        }
+        if let Some(auction_dealer) = auction_dealer {
+            validate_auction(section, auction_dealer, contract.as_ref(), diagnostics);
+        }
+    }
+
+    if let Some(section) = &record.play {
+        match single_direction(&section.value) {
+            Some(leader) => {
+                if let Some(declarer) = declarer {
+                    if leader != declarer.next() {
+                        diagnostics.push(Diagnostic::warning(
+                            section.line,
+                            format!(
+                                "opening leader {:?} is not on declarer {:?}'s left",
+                                leader, declarer
+                            ),
+                        ));
+                    }
+                }
+                validate_play(section, leader, deal.as_ref(), diagnostics);
+            }
+            None => diagnostics.push(Diagnostic::error(
+                section.line,
+                format!("invalid opening leader \"{}\"", section.value),
+            )),
+        }
+    }
+}
+
+fn single_direction(value: &str) -> Option<Direction> {
+    let mut chars = value.trim().chars();
+    let direction = Direction::from_char(chars.next()?)?;
+    chars.next().is_none().then_some(direction)
+}
+
+/// Check the deal for bad notation, cards dealt twice and hands that aren't
+/// 13 cards; returns the deal if it could be read
+fn validate_deal(tag: &Located, diagnostics: &mut Vec<Diagnostic>) -> Option<Deal> {
+    let deal = match parse_deal(&tag.value) {
+        Ok(deal) => deal,
+        Err(e) => {
+            diagnostics.push(Diagnostic::error(tag.line, format!("invalid deal: {}", e)));
+            return None;
+        }
+    };
+
+    let mut seen = HashSet::new();
+    for direction in Direction::ALL {
+        let hand = deal.hand(direction);
+        for suit in SUITS_DISPLAY_ORDER {
+            for rank in RANKS_DISPLAY_ORDER {
+                if hand.contains(suit, rank) && !seen.insert((suit, rank)) {
+                    diagnostics.push(Diagnostic::error(
+                        tag.line,
+                        format!(
+                            "{} is dealt more than once",
+                            card_text(Card::new(suit, rank))
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    if !deal.is_fragment() {
+        for direction in Direction::ALL {
+            let count = deal.hand(direction).card_count();
+            if count != 0 && count != 13 {
+                diagnostics.push(Diagnostic::error(
+                    tag.line,
+                    format!("{:?} has {} cards", direction, count),
+                ));
+            }
+        }
+    }
+    Some(deal)
+}
+
+/// Rank of a bid, so later bids must be higher
+fn bid_rank(level: u8, strain: Strain) -> u8 {
+    let strain = match strain {
+        Strain::Clubs => 0,
+        Strain::Diamonds => 1,
+        Strain::Hearts => 2,
+        Strain::Spades => 3,
+        Strain::NoTrump => 4,
+    };
+    level * 5 + strain
+}
+
+fn validate_auction(
+    section: &Section,
+    dealer: Direction,
+    contract: Option<&Contract>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut last_bid = None;
+    let mut text = Vec::new();
+    for (line, token) in &section.tokens {
+        text.push(token.as_str());
+        let upper = token.to_uppercase();
+        if upper == "AP" || upper == "*" {
+            break;
+        }
+        let (token, _) = split_alert(token);
+        let (call, _) = extract_annotation(&token);
+        if call.is_empty() {
+            // A standalone note reference (=1=) or NAG ($1)
+            continue;
+        }
+        match Call::from_pbn(&call) {
+            Some(Call::Bid { level, strain }) => {
+                let rank = bid_rank(level, strain);
+                if last_bid.is_some_and(|last| rank <= last) {
+                    diagnostics.push(Diagnostic::error(
+                        *line,
+                        format!("insufficient bid {}", token),
+                    ));
+                }
+                last_bid = Some(rank);
+            }
+            Some(_) => {}
+            None => diagnostics.push(Diagnostic::error(
+                *line,
+                format!("unrecognized call \"{}\"", token),
+            )),
+        }
+    }
+
+    let Some(contract) = contract else {
+        return;
+    };
+    let Ok(auction) = parse_auction(dealer, &text.join(" ")) else {
+        return;
+    };
+    if let Some(last) = auction.final_contract() {
+        let agrees = last.level == contract.level
+            && last.suit == contract.suit
+            && last.doubled == contract.doubled
+            && last.redoubled == contract.redoubled;
+        if !agrees {
+            diagnostics.push(Diagnostic::warning(
+                section.line,
+                "auction ends in a different contract than [Contract]",
+            ));
+        }
+    }
+}
+
+fn validate_play(
+    section: &Section,
+    leader: Direction,
+    deal: Option<&Deal>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // Each play column belongs to one seat, starting with the opening leader
+    let mut seat = leader;
+    let mut played = HashSet::new();
+    for (line, token) in &section.tokens {
+        if token == "*" {
+            break;
+        }
+        let (card, _) = extract_annotation(token);
+        if card.is_empty() {
+            continue;
+        }
+        if card != "-" {
+            let card = Some(card.as_str())
+                .filter(|card| card.len() == 2)
+                .and_then(parse_card);
+            match card {
+                None => diagnostics.push(Diagnostic::error(
+                    *line,
+                    format!("unrecognized card \"{}\"", token),
+                )),
+                Some(card) => {
+                    if !played.insert((card.suit, card.rank)) {
+                        diagnostics.push(Diagnostic::error(
+                            *line,
+                            format!("{} is played twice", card_text(card)),
+                        ));
+                    } else if let Some(deal) = deal.filter(|d| !d.is_fragment()) {
+                        let hand = deal.hand(seat);
+                        if hand.card_count() == 13 && !hand.contains(card.suit, card.rank) {
+                            diagnostics.push(Diagnostic::error(
+                                *line,
+                                format!(
+                                    "{:?} plays {}, which it doesn't hold",
+                                    seat,
+                                    card_text(card)
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        seat = seat.next();
+    }
+}
+
+/// A card in PBN notation, e.g. "SA"
+pub(crate) fn card_text(card: Card) -> String {
+    let suit = match card.suit {
+        Suit::Spades => 'S',
+        Suit::Hearts => 'H',
+        Suit::Diamonds => 'D',
+        Suit::Clubs => 'C',
+    };
+    format!("{}{}", suit, card.rank.to_char())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"[Event "Club"]
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQJ.T98.765.432 T987.AKQ.432.765 65432.J765.AK.A8 .432.QJT98.KQJT9"]
+[Declarer "S"]
+[Contract "4S"]
+[Auction "N"]
+1S Pass 3S Pass
+4S AP
+[Play "W"]
+D8 D5 D2 DA
+"#;
+
+    #[test]
+    fn test_clean_board_has_no_diagnostics() {
+        assert_eq!(validate_pbn(BOARD), vec![]);
+    }
+
+    #[test]
+    fn test_errors_have_line_numbers() {
+        let text = BOARD
+            .replace("3S Pass", "1H Pass")
+            .replace("D8 D5", "D8 HA")
+            .replace("[Contract \"4S\"]", "[Contract \"4S\"");
+        let diagnostics = validate_pbn(&text);
+        let found: Vec<(usize, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (7, "malformed tag"),
+                (9, "insufficient bid 1H"),
+                (12, "North plays HA, which it doesn't hold"),
+            ]
+        );
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+    }
+
+    #[test]
+    fn test_bad_deal_and_warnings() {
+        let text = BOARD
+            .replace("N:AKQJ.", "N:AKQJ9.")
+            .replace("[Contract \"4S\"]", "[Contract \"3S\"]");
+        let diagnostics = validate_pbn(&text);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.line == 5 && d.message == "S9 is dealt more than once"));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.line == 5 && d.message == "North has 14 cards"));
+        assert!(diagnostics.iter().any(|d| d.line == 8
+            && d.severity == Severity::Warning
+            && d.message.contains("[Contract]")));
+    }
+
+    #[test]
+    fn test_unclosed_commentary() {
+        let text = format!("{}{{This never ends\n", BOARD);
+        let diagnostics = validate_pbn(&text);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 13);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "line 13: error: commentary is never closed with '}'"
+        );
+    }
+}
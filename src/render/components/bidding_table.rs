@@ -1,23 +1,101 @@
+use crate::cli::AuctionHeaders;
 use crate::config::Settings;
 use crate::model::{AnnotatedCall, Auction, BidSuit, Call, Direction, DirectionExt, PlayerNames};
 use crate::parser::replace_suit_escapes;
-use printpdf::{BuiltinFont, Color, FontId, Mm};
+use printpdf::{Color, FontId, Mm};
+use std::collections::HashMap;
 
 use crate::render::helpers::colors::{SuitColors, BLACK};
+use crate::render::helpers::fonts::TextFont;
+use crate::render::helpers::forms::TextField;
 use crate::render::helpers::layer::LayerBuilder;
-use crate::render::helpers::text_metrics;
+use crate::render::helpers::text_metrics::{self, TextMeasure};
 
 /// Size ratio for superscript text relative to body font
 const SUPERSCRIPT_RATIO: f32 = 0.65;
 /// Vertical offset for superscript as fraction of font size
 const SUPERSCRIPT_RISE: f32 = 0.4;
+/// Gap kept after a column header that sets the column width (mm)
+const HEADER_GAP: f32 = 2.0;
+/// Gap between the blocks of a long auction split side by side (mm)
+const BLOCK_GAP: f32 = 4.0;
+
+/// Layout of the call rows, which a long auction splits into side-by-side
+/// blocks of at most `settings.auction_max_rows` rows, each under its own header
+struct RowBlocks {
+    /// Row of the first call (after the spacing, header and player names rows)
+    first_row: usize,
+    /// Rows of calls in the whole auction
+    call_rows: usize,
+    rows_per_block: Option<usize>,
+    /// Horizontal distance from one block to the next
+    block_width: f32,
+    columns: usize,
+}
+
+impl RowBlocks {
+    fn new(
+        auction: &Auction,
+        players: Option<&PlayerNames>,
+        settings: &Settings,
+        two_col_auctions: bool,
+    ) -> Self {
+        let columns = if two_col_auctions && auction.uncontested_pair().is_some() {
+            2
+        } else {
+            4
+        };
+        let has_player_names = BiddingTableRenderer::has_player_name_row(players, settings);
+        Self {
+            first_row: if has_player_names { 3 } else { 2 },
+            call_rows: BiddingTableRenderer::call_rows(auction, two_col_auctions),
+            rows_per_block: settings.auction_max_rows.filter(|&rows| rows > 0),
+            block_width: columns as f32 * settings.bid_column_width + BLOCK_GAP,
+            columns,
+        }
+    }
+
+    /// Rows of calls in the tallest (first) block
+    fn rows_in_block(&self) -> usize {
+        self.rows_per_block
+            .map_or(self.call_rows, |rows| rows.min(self.call_rows))
+    }
+
+    fn count(&self) -> usize {
+        match self.rows_per_block {
+            Some(rows) if self.call_rows > rows => self.call_rows.div_ceil(rows),
+            _ => 1,
+        }
+    }
+
+    /// Horizontal offset and row within its block for a table row
+    fn place(&self, row: usize) -> (f32, usize) {
+        match self.rows_per_block {
+            Some(rows) if row >= self.first_row => {
+                let index = row - self.first_row;
+                let block = index / rows;
+                (
+                    block as f32 * self.block_width,
+                    self.first_row + index % rows,
+                )
+            }
+            _ => (0.0, row),
+        }
+    }
+
+    /// Width of all blocks together
+    fn width(&self, column_width: f32) -> f32 {
+        let table_width = self.columns as f32 * column_width;
+        table_width + (self.count() - 1) as f32 * self.block_width
+    }
+}
 
 /// Renderer for bidding tables
 pub struct BiddingTableRenderer<'a> {
-    font: BuiltinFont,
+    font: TextFont,
     #[allow(dead_code)]
-    bold_font: BuiltinFont,
-    italic_font: BuiltinFont,
+    bold_font: TextFont,
+    italic_font: TextFont,
     symbol_font: &'a FontId, // Font with Unicode suit symbols (DejaVu Sans)
     colors: SuitColors,
     settings: &'a Settings,
@@ -26,9 +104,9 @@ pub struct BiddingTableRenderer<'a> {
 
 impl<'a> BiddingTableRenderer<'a> {
     pub fn new(
-        font: BuiltinFont,
-        bold_font: BuiltinFont,
-        italic_font: BuiltinFont,
+        font: impl Into<TextFont>,
+        bold_font: impl Into<TextFont>,
+        italic_font: impl Into<TextFont>,
         symbol_font: &'a FontId,
         settings: &'a Settings,
     ) -> Self {
@@ -41,23 +119,23 @@ impl<'a> BiddingTableRenderer<'a> {
             .unwrap_or(false);
 
         Self {
-            font,
-            bold_font,
-            italic_font,
+            font: font.into(),
+            bold_font: bold_font.into(),
+            italic_font: italic_font.into(),
             symbol_font,
-            colors: SuitColors::new(settings.black_color, settings.red_color),
+            colors: SuitColors::from_settings(settings),
             settings,
             use_sans_measurer,
         }
     }
 
     /// Get the appropriate text measurer based on font type
-    fn get_measurer(&self) -> &'static text_metrics::BuiltinFontMeasurer {
-        if self.use_sans_measurer {
+    fn get_measurer(&self) -> &dyn TextMeasure {
+        self.font.measurer_or(if self.use_sans_measurer {
             text_metrics::get_helvetica_measurer()
         } else {
             text_metrics::get_times_measurer()
-        }
+        })
     }
 
     /// Calculate the height of the bidding table without rendering
@@ -75,17 +153,9 @@ impl<'a> BiddingTableRenderer<'a> {
         Self::measure_height_static(auction, players, self.settings, notes_max_width)
     }
 
-    /// Calculate the height of the bidding table with all options (static version)
-    /// This can be called without creating a renderer instance, useful for layout measurement
-    pub fn measure_height_static(
-        auction: &Auction,
-        players: Option<&PlayerNames>,
-        settings: &Settings,
-        notes_max_width: Option<f32>,
-    ) -> f32 {
-        let row_height = settings.bid_row_height;
-        let two_col_auctions = settings.two_col_auctions;
-
+    /// Number of rows of calls (including "All Pass" or "Passed Out"), not
+    /// counting the header rows or any split into blocks
+    fn call_rows(auction: &Auction, two_col_auctions: bool) -> usize {
         let calls = &auction.calls;
 
         // Check if we should use two-column mode for this auction
@@ -102,8 +172,11 @@ impl<'a> BiddingTableRenderer<'a> {
         // If any trailing pass has an annotation, show passes individually
         let trailing_passes = calls
             .iter()
+            .enumerate()
             .rev()
-            .take_while(|a| a.call == Call::Pass && a.annotation.is_none())
+            .take_while(|(i, a)| {
+                a.call == Call::Pass && a.annotation.is_none() && !auction.is_alerted(*i)
+            })
             .count();
         let show_all_pass = !is_passed_out && trailing_passes >= 3;
         let calls_to_render = if is_passed_out || show_all_pass {
@@ -112,11 +185,8 @@ impl<'a> BiddingTableRenderer<'a> {
             calls.len()
         };
 
-        // Start counting rows: spacing + header + optional player names row
-        // Row 0 is spacing, Row 1 is header, Row 2 is player names (if present)
-        // After counting, `row` will be one past the last content row
-        let has_player_names = players.is_some_and(|p| p.has_any());
-        let mut row = if has_player_names { 3 } else { 2 };
+        // After counting, `row` is the number of rows used
+        let mut row = 0;
 
         // Handle passed out auction
         if is_passed_out {
@@ -175,6 +245,25 @@ impl<'a> BiddingTableRenderer<'a> {
             // If col == 0 and calls_to_render > 0, row was already incremented in the loop
         }
 
+        row
+    }
+
+    /// Calculate the height of the bidding table with all options (static version)
+    /// This can be called without creating a renderer instance, useful for layout measurement
+    pub fn measure_height_static(
+        auction: &Auction,
+        players: Option<&PlayerNames>,
+        settings: &Settings,
+        notes_max_width: Option<f32>,
+    ) -> f32 {
+        let row_height = settings.bid_row_height;
+
+        // Rows: spacing + header + optional player names row, then the calls
+        // (only the first block's worth when a long auction is split)
+        // `row` is one past the last content row
+        let blocks = RowBlocks::new(auction, players, settings, settings.two_col_auctions);
+        let row = blocks.first_row + blocks.rows_in_block();
+
         // Calculate table height: extend to last row's text descenders, not a full row below
         // row is one past the last content row, so last baseline is at (row-1) * row_height
         let measurer = text_metrics::get_times_measurer();
@@ -183,64 +272,104 @@ impl<'a> BiddingTableRenderer<'a> {
 
         // Account for notes (with word wrapping if max_width specified)
         // Note: render_notes adds one line_height of spacing before the first note
-        if !auction.notes.is_empty() {
-            let note_font_size = settings.body_font_size; // Same font size as auction
-            let note_line_height = note_font_size * 1.3 * 0.352778; // Convert pt to mm
-
-            // Count note content lines (not including initial spacing)
-            let note_content_lines = if let Some(max_w) = notes_max_width {
-                // Calculate wrapped line count
-                let measurer = text_metrics::get_times_measurer();
-                let space_width = measurer.measure_width_mm(" ", note_font_size);
-                let mut total_lines = 0;
-
-                for (num, text) in &auction.notes {
-                    // Convert suit escape codes for accurate width measurement
-                    let converted_text = replace_suit_escapes(text);
-                    let prefix = format!("{}. ", num);
-                    let prefix_width = measurer.measure_width_mm(&prefix, note_font_size);
-                    let available_width = max_w - prefix_width;
-
-                    let words: Vec<&str> = converted_text.split_whitespace().collect();
-                    if words.is_empty() {
-                        total_lines += 1;
-                        continue;
-                    }
-
-                    let mut line_count = 1;
-                    let mut current_line_width = 0.0;
-
-                    for word in words {
-                        let word_width = measurer.measure_width_mm(word, note_font_size);
-
-                        if current_line_width == 0.0 {
-                            current_line_width = word_width;
-                        } else if current_line_width + space_width + word_width <= available_width {
-                            current_line_width += space_width + word_width;
-                        } else {
-                            line_count += 1;
-                            current_line_width = word_width;
-                        }
-                    }
-                    total_lines += line_count;
-                }
-                total_lines
-            } else {
-                // Original: one line per note
-                auction.notes.len()
-            };
-
+        let height = if !auction.notes.is_empty() {
             // Notes height = initial spacing line + note content lines
             // (matching render_notes which starts at oy.0 - line_height)
-            let notes_height = (1 + note_content_lines) as f32 * note_line_height;
+            let notes_height =
+                Self::measure_footnotes_static(&auction.notes, settings, notes_max_width)
+                    + Self::note_line_height(settings);
 
             // Return actual combined height (no rounding)
             table_height + notes_height
         } else {
             table_height
+        };
+
+        // Prose summary below the table and notes
+        let prose_lines = Self::prose_lines(
+            auction,
+            settings,
+            notes_max_width.unwrap_or(blocks.width(settings.bid_column_width)),
+            text_metrics::get_times_measurer(),
+        );
+        if prose_lines.is_empty() {
+            height
+        } else {
+            let line_height = settings.body_font_size * 1.3 * 0.352778;
+            height + (1 + prose_lines.len()) as f32 * line_height
         }
     }
 
+    /// Line height of the numbered notes under an auction (mm)
+    fn note_line_height(settings: &Settings) -> f32 {
+        settings.body_font_size * 1.3 * 0.352778 // Convert pt to mm
+    }
+
+    /// Distance from the origin down to the last baseline of numbered notes
+    /// drawn by `render_footnotes` (wrapped when max_width is specified)
+    pub fn measure_footnotes_static(
+        notes: &HashMap<u8, String>,
+        settings: &Settings,
+        max_width: Option<f32>,
+    ) -> f32 {
+        let note_font_size = settings.body_font_size; // Same font size as auction
+
+        // Count note content lines
+        let note_content_lines = if let Some(max_w) = max_width {
+            // Calculate wrapped line count
+            let measurer = text_metrics::get_times_measurer();
+            let space_width = measurer.measure_width_mm(" ", note_font_size);
+            let mut total_lines = 0;
+
+            for (num, text) in notes {
+                // Convert suit escape codes for accurate width measurement
+                let converted_text = replace_suit_escapes(text);
+                let prefix = format!("{}. ", num);
+                let prefix_width = measurer.measure_width_mm(&prefix, note_font_size);
+                let available_width = max_w - prefix_width;
+
+                let words: Vec<&str> = converted_text.split_whitespace().collect();
+                if words.is_empty() {
+                    total_lines += 1;
+                    continue;
+                }
+
+                let mut line_count = 1;
+                let mut current_line_width = 0.0;
+
+                for word in words {
+                    let word_width = measurer.measure_width_mm(word, note_font_size);
+
+                    if current_line_width == 0.0 {
+                        current_line_width = word_width;
+                    } else if current_line_width + space_width + word_width <= available_width {
+                        current_line_width += space_width + word_width;
+                    } else {
+                        line_count += 1;
+                        current_line_width = word_width;
+                    }
+                }
+                total_lines += line_count;
+            }
+            total_lines
+        } else {
+            // Original: one line per note
+            notes.len()
+        };
+
+        note_content_lines as f32 * Self::note_line_height(settings)
+    }
+
+    /// Width of the bidding table, including the extra blocks of a long auction
+    pub fn table_width_static(
+        auction: &Auction,
+        players: Option<&PlayerNames>,
+        settings: &Settings,
+    ) -> f32 {
+        RowBlocks::new(auction, players, settings, settings.two_col_auctions)
+            .width(settings.bid_column_width)
+    }
+
     /// Render the bidding table and return the height used
     pub fn render(&self, layer: &mut LayerBuilder, auction: &Auction, origin: (Mm, Mm)) -> f32 {
         self.render_with_options(layer, auction, origin, None, false, None)
@@ -292,6 +421,70 @@ impl<'a> BiddingTableRenderer<'a> {
         players: Option<&PlayerNames>,
         two_col_auctions: bool,
         notes_max_width: Option<f32>,
+    ) -> f32 {
+        layer.begin_answers();
+        let height = self.render_table_and_notes(
+            layer,
+            auction,
+            origin,
+            players,
+            two_col_auctions,
+            notes_max_width,
+        );
+        layer.end_answers();
+        height
+    }
+
+    /// Whether player names get their own row below the direction headers
+    fn has_player_name_row(players: Option<&PlayerNames>, settings: &Settings) -> bool {
+        settings.auction_headers == AuctionHeaders::Directions
+            && players.is_some_and(|p| p.has_any())
+    }
+
+    /// Column header for a direction, following `settings.auction_headers`
+    pub fn header_label(
+        direction: Direction,
+        players: Option<&PlayerNames>,
+        settings: &Settings,
+    ) -> String {
+        let locale = settings.locale();
+        let name = players
+            .and_then(|p| p.get(direction))
+            .filter(|name| !name.is_empty());
+        match (settings.auction_headers, name) {
+            (AuctionHeaders::Names, Some(name)) => name.to_string(),
+            (AuctionHeaders::Both, Some(name)) => {
+                format!("{} ({})", name, locale.compass_letter(direction))
+            }
+            _ => locale.direction(direction).to_string(),
+        }
+    }
+
+    /// Bid column width needed to fit the column headers: the configured
+    /// width, widened when player names are used as headers
+    pub fn column_width_static(players: Option<&PlayerNames>, settings: &Settings) -> f32 {
+        if settings.auction_headers == AuctionHeaders::Directions {
+            return settings.bid_column_width;
+        }
+        let measurer = text_metrics::get_times_measurer();
+        Direction::all()
+            .into_iter()
+            .map(|dir| {
+                let label = Self::header_label(dir, players, settings);
+                measurer.measure_width_mm(&label, settings.header_font_size) + HEADER_GAP
+            })
+            .fold(settings.bid_column_width, f32::max)
+    }
+
+    /// Render the header, calls and notes of the bidding table
+    fn render_table_and_notes(
+        &self,
+        layer: &mut LayerBuilder,
+        auction: &Auction,
+        origin: (Mm, Mm),
+        players: Option<&PlayerNames>,
+        two_col_auctions: bool,
+        notes_max_width: Option<f32>,
     ) -> f32 {
         let (ox, oy) = origin;
         let col_width = self.settings.bid_column_width;
@@ -331,34 +524,39 @@ impl<'a> BiddingTableRenderer<'a> {
         // Add spacing before header row to separate from content above
         let header_y = Mm(oy.0 - row_height);
 
-        for (i, dir) in directions.iter().enumerate() {
-            let x = ox.0 + (i as f32 * col_width);
-            layer.use_text_builtin(
-                format!("{}", dir), // Use Display trait for full name
-                self.settings.header_font_size,
-                Mm(x),
-                header_y,
-                self.italic_font, // Use italic font for direction names (Bridge Composer style)
-            );
-        }
-
-        // Render player names below direction headers if provided
-        let has_player_names = players.is_some_and(|p| p.has_any());
-        if has_player_names {
-            let players = players.unwrap();
-            let name_y = Mm(header_y.0 - row_height);
+        // A long auction continues in blocks to the right, each with the headers repeated
+        let blocks = RowBlocks::new(auction, players, self.settings, two_col_auctions);
+        for block in 0..blocks.count() {
+            let block_x = ox.0 + block as f32 * blocks.block_width;
 
             for (i, dir) in directions.iter().enumerate() {
-                if let Some(name) = players.get(*dir) {
-                    if !name.is_empty() {
-                        let x = ox.0 + (i as f32 * col_width);
-                        layer.use_text_builtin(
-                            name,
-                            self.settings.header_font_size,
-                            Mm(x),
-                            name_y,
-                            self.italic_font,
-                        );
+                let x = block_x + (i as f32 * col_width);
+                layer.use_text_font(
+                    &Self::header_label(*dir, players, self.settings), // Direction and/or player name
+                    self.settings.header_font_size,
+                    Mm(x),
+                    header_y,
+                    &self.italic_font, // Use italic font for direction names (Bridge Composer style)
+                );
+            }
+
+            // Render player names below direction headers if provided
+            if Self::has_player_name_row(players, self.settings) {
+                let players = players.unwrap();
+                let name_y = Mm(header_y.0 - row_height);
+
+                for (i, dir) in directions.iter().enumerate() {
+                    if let Some(name) = players.get(*dir) {
+                        if !name.is_empty() {
+                            let x = block_x + (i as f32 * col_width);
+                            layer.use_text_font(
+                                name,
+                                self.settings.header_font_size,
+                                Mm(x),
+                                name_y,
+                                &self.italic_font,
+                            );
+                        }
                     }
                 }
             }
@@ -373,8 +571,11 @@ impl<'a> BiddingTableRenderer<'a> {
         // If any trailing pass has an annotation, show passes individually
         let trailing_passes = calls
             .iter()
+            .enumerate()
             .rev()
-            .take_while(|a| a.call == Call::Pass && a.annotation.is_none())
+            .take_while(|(i, a)| {
+                a.call == Call::Pass && a.annotation.is_none() && !auction.is_alerted(*i)
+            })
             .count();
         let show_all_pass = !is_passed_out && trailing_passes >= 3;
         let calls_to_render = if is_passed_out || show_all_pass {
@@ -386,7 +587,7 @@ impl<'a> BiddingTableRenderer<'a> {
         // Start row accounting for spacing + header + optional player names row
         // Row 0 is spacing, Row 1 is header, Row 2 is player names (if present)
         // After counting, `row` will be one past the last content row
-        let mut row = if has_player_names { 3 } else { 2 };
+        let mut row = blocks.first_row;
 
         // Handle passed out auction
         if is_passed_out {
@@ -396,16 +597,17 @@ impl<'a> BiddingTableRenderer<'a> {
             } else {
                 auction.dealer.table_position()
             };
-            let x = ox.0 + (col as f32 * col_width);
-            let y = oy.0 - (row as f32 * row_height);
+            let (block_x, block_row) = blocks.place(row);
+            let x = ox.0 + block_x + (col as f32 * col_width);
+            let y = oy.0 - (block_row as f32 * row_height);
 
             layer.set_fill_color(Color::Rgb(BLACK));
-            layer.use_text_builtin(
-                "Passed Out",
+            layer.use_text_font(
+                self.settings.passed_out_text(),
                 self.settings.body_font_size,
                 Mm(x),
                 Mm(y),
-                self.font,
+                &self.font,
             );
             row += 1;
         } else if let Some(pair) = uncontested_pair {
@@ -414,7 +616,7 @@ impl<'a> BiddingTableRenderer<'a> {
             let mut last_col: Option<usize> = None; // Track last column rendered
             let mut current_player = auction.dealer;
 
-            for annotated in calls.iter().take(calls_to_render) {
+            for (index, annotated) in calls.iter().enumerate().take(calls_to_render) {
                 // Only render calls from the bidding pair
                 if current_player == d1 || current_player == d2 {
                     // Determine which column (0 or 1) based on which player in the pair
@@ -427,10 +629,16 @@ impl<'a> BiddingTableRenderer<'a> {
                         row += 1;
                     }
 
-                    let x = ox.0 + (display_col as f32 * col_width);
-                    let y = oy.0 - (row as f32 * row_height);
+                    let (block_x, block_row) = blocks.place(row);
+                    let x = ox.0 + block_x + (display_col as f32 * col_width);
+                    let y = oy.0 - (block_row as f32 * row_height);
 
-                    self.render_annotated_call(layer, annotated, (Mm(x), Mm(y)));
+                    self.render_annotated_call(
+                        layer,
+                        annotated,
+                        auction.call_marks(index).as_deref(),
+                        (Mm(x), Mm(y)),
+                    );
 
                     last_col = Some(display_col);
                 }
@@ -441,16 +649,17 @@ impl<'a> BiddingTableRenderer<'a> {
             if show_all_pass {
                 // Always move to next row for "All Pass"
                 row += 1;
-                let x = ox.0;
-                let y = oy.0 - (row as f32 * row_height);
+                let (block_x, block_row) = blocks.place(row);
+                let x = ox.0 + block_x;
+                let y = oy.0 - (block_row as f32 * row_height);
 
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin(
-                    "All Pass",
+                layer.use_text_font(
+                    self.settings.all_pass_text(),
                     self.settings.body_font_size,
                     Mm(x),
                     Mm(y),
-                    self.font,
+                    &self.font,
                 );
                 row += 1;
             } else if last_col.is_some() {
@@ -463,11 +672,17 @@ impl<'a> BiddingTableRenderer<'a> {
             let mut col = start_col;
 
             // Render regular calls (excluding trailing passes if we'll show "All Pass")
-            for annotated in calls.iter().take(calls_to_render) {
-                let x = ox.0 + (col as f32 * col_width);
-                let y = oy.0 - (row as f32 * row_height);
-
-                self.render_annotated_call(layer, annotated, (Mm(x), Mm(y)));
+            for (index, annotated) in calls.iter().enumerate().take(calls_to_render) {
+                let (block_x, block_row) = blocks.place(row);
+                let x = ox.0 + block_x + (col as f32 * col_width);
+                let y = oy.0 - (block_row as f32 * row_height);
+
+                self.render_annotated_call(
+                    layer,
+                    annotated,
+                    auction.call_marks(index).as_deref(),
+                    (Mm(x), Mm(y)),
+                );
 
                 col += 1;
                 if col >= 4 {
@@ -482,16 +697,17 @@ impl<'a> BiddingTableRenderer<'a> {
                 if col > 0 {
                     row += 1;
                 }
-                let x = ox.0;
-                let y = oy.0 - (row as f32 * row_height);
+                let (block_x, block_row) = blocks.place(row);
+                let x = ox.0 + block_x;
+                let y = oy.0 - (block_row as f32 * row_height);
 
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin(
-                    "All Pass",
+                layer.use_text_font(
+                    self.settings.all_pass_text(),
                     self.settings.body_font_size,
                     Mm(x),
                     Mm(y),
-                    self.font,
+                    &self.font,
                 );
                 row += 1;
             } else if col > 0 {
@@ -503,12 +719,14 @@ impl<'a> BiddingTableRenderer<'a> {
 
         // Calculate table height: extend to last row's text descenders, not a full row below
         // row is one past the last content row, so last baseline is at (row-1) * row_height
+        // (a split auction is as tall as its first block)
+        let row = row.min(blocks.first_row + blocks.rows_in_block());
         let measurer = self.get_measurer();
         let descender = measurer.descender_mm(self.settings.body_font_size);
         let table_height = (row - 1) as f32 * row_height + descender;
 
         // Render notes if present and return combined height
-        if !auction.notes.is_empty() {
+        let height = if !auction.notes.is_empty() {
             let notes_height = self.render_notes(
                 layer,
                 auction,
@@ -519,30 +737,88 @@ impl<'a> BiddingTableRenderer<'a> {
             table_height + notes_height
         } else {
             table_height
+        };
+
+        // Prose summary below the table and notes
+        let prose_lines = Self::prose_lines(
+            auction,
+            self.settings,
+            notes_max_width.unwrap_or(blocks.width(col_width)),
+            self.get_measurer(),
+        );
+        if prose_lines.is_empty() {
+            return height;
+        }
+        let line_height = self.settings.body_font_size * 1.3 * 0.352778; // Convert pt to mm
+        let mut current_y = oy.0 - height - line_height;
+        for line in &prose_lines {
+            self.render_text_with_suits(layer, line, self.settings.body_font_size, ox.0, current_y);
+            current_y -= line_height;
+        }
+        oy.0 - current_y
+    }
+
+    /// Wrapped lines of the prose auction summary (empty unless `show_auction_prose`)
+    fn prose_lines(
+        auction: &Auction,
+        settings: &Settings,
+        max_width: f32,
+        measurer: &dyn TextMeasure,
+    ) -> Vec<String> {
+        if !settings.show_auction_prose {
+            return Vec::new();
+        }
+        let prose = replace_suit_escapes(&auction.to_prose());
+        let font_size = settings.body_font_size;
+        let space_width = measurer.measure_text(" ", font_size);
+
+        let mut lines = Vec::new();
+        let mut current_line = String::new();
+        let mut current_line_width = 0.0;
+        for word in prose.split_whitespace() {
+            let word_width = measurer.measure_text(word, font_size);
+            if current_line.is_empty() {
+                current_line = word.to_string();
+                current_line_width = word_width;
+            } else if current_line_width + space_width + word_width <= max_width {
+                current_line.push(' ');
+                current_line.push_str(word);
+                current_line_width += space_width + word_width;
+            } else {
+                lines.push(std::mem::take(&mut current_line));
+                current_line = word.to_string();
+                current_line_width = word_width;
+            }
+        }
+        if !current_line.is_empty() {
+            lines.push(current_line);
         }
+        lines
     }
 
-    /// Render an annotated call (call with optional superscript annotation)
+    /// Render an annotated call (call with its alert and annotation as a
+    /// superscript, from [Auction::call_marks])
     fn render_annotated_call(
         &self,
         layer: &mut LayerBuilder,
         annotated: &AnnotatedCall,
+        marks: Option<&str>,
         pos: (Mm, Mm),
     ) {
         let call_width = self.render_call(layer, &annotated.call, pos);
 
-        // If there's an annotation, render it
-        if let Some(ref annotation) = annotated.annotation {
+        // If there's an alert or annotation, render it
+        if let Some(annotation) = marks {
             if annotated.call == Call::Blank {
                 // For blanks, render annotation at normal size after the line
                 let text_x = Mm(pos.0 .0 + call_width + 0.5);
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin(
+                layer.use_text_font(
                     annotation,
                     self.settings.body_font_size,
                     text_x,
                     pos.1,
-                    self.font,
+                    &self.font,
                 );
             } else {
                 // For other calls, render as superscript
@@ -552,7 +828,7 @@ impl<'a> BiddingTableRenderer<'a> {
                 let sup_size = self.settings.body_font_size * SUPERSCRIPT_RATIO;
 
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin(annotation, sup_size, sup_x, sup_y, self.font);
+                layer.use_text_font(annotation, sup_size, sup_x, sup_y, &self.font);
             }
         }
     }
@@ -565,18 +841,21 @@ impl<'a> BiddingTableRenderer<'a> {
         match call {
             Call::Pass => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("Pass", self.settings.body_font_size, x, y, self.font);
-                measurer.measure_width_mm("Pass", self.settings.body_font_size)
+                let text = self.settings.pass_text();
+                layer.use_text_font(text, self.settings.body_font_size, x, y, &self.font);
+                measurer.measure_text(text, self.settings.body_font_size)
             }
             Call::Double => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("Dbl", self.settings.body_font_size, x, y, self.font);
-                measurer.measure_width_mm("Dbl", self.settings.body_font_size)
+                let text = self.settings.double_text();
+                layer.use_text_font(text, self.settings.body_font_size, x, y, &self.font);
+                measurer.measure_text(text, self.settings.body_font_size)
             }
             Call::Redouble => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("Rdbl", self.settings.body_font_size, x, y, self.font);
-                measurer.measure_width_mm("Rdbl", self.settings.body_font_size)
+                let text = self.settings.redouble_text();
+                layer.use_text_font(text, self.settings.body_font_size, x, y, &self.font);
+                measurer.measure_text(text, self.settings.body_font_size)
             }
             Call::Bid {
                 level,
@@ -585,11 +864,10 @@ impl<'a> BiddingTableRenderer<'a> {
                 // Render level
                 layer.set_fill_color(Color::Rgb(BLACK));
                 let level_str = level.to_string();
-                layer.use_text_builtin(&level_str, self.settings.body_font_size, x, y, self.font);
+                layer.use_text_font(&level_str, self.settings.body_font_size, x, y, &self.font);
 
                 // Render suit symbol immediately after level (no gap)
-                let level_width =
-                    measurer.measure_width_mm(&level_str, self.settings.body_font_size);
+                let level_width = measurer.measure_text(&level_str, self.settings.body_font_size);
                 let suit_x = Mm(x.0 + level_width);
                 let suit_width = self.render_bid_suit(layer, *suit, (suit_x, y));
                 level_width + suit_width
@@ -597,8 +875,8 @@ impl<'a> BiddingTableRenderer<'a> {
             Call::Continue => {
                 // "+" in PBN becomes "?" in display (student fills in next bid)
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("?", self.settings.body_font_size, x, y, self.font);
-                measurer.measure_width_mm("?", self.settings.body_font_size)
+                layer.use_text_font("?", self.settings.body_font_size, x, y, &self.font);
+                measurer.measure_text("?", self.settings.body_font_size)
             }
             Call::Blank => {
                 // Underscore sequences in PBN become a horizontal line for students to write answers
@@ -615,6 +893,21 @@ impl<'a> BiddingTableRenderer<'a> {
                     Mm(x.0 + line_width),
                     Mm(y.0 - baseline_offset),
                 );
+
+                // Optionally overlay a form field so the blank can be filled in on screen
+                if self.settings.fillable_forms {
+                    let field_height = self.settings.body_font_size * 0.352778;
+                    layer.add_text_field(
+                        TextField::new(
+                            "call",
+                            x.0,
+                            y.0 - baseline_offset,
+                            line_width,
+                            field_height,
+                        )
+                        .with_font_size(self.settings.body_font_size * 0.8),
+                    );
+                }
                 line_width
             }
         }
@@ -627,24 +920,39 @@ impl<'a> BiddingTableRenderer<'a> {
         auction: &Auction,
         origin: (Mm, Mm),
         max_width: Option<f32>,
+    ) -> f32 {
+        // Total height, including the line below the last note
+        self.render_footnotes(layer, &auction.notes, origin, max_width)
+            + Self::note_line_height(self.settings)
+    }
+
+    /// Render numbered notes ("1. text"), one below the other starting a line
+    /// below `origin`, with word wrapping when max_width is specified.
+    /// Returns the distance from the origin down to the last note's baseline.
+    pub fn render_footnotes(
+        &self,
+        layer: &mut LayerBuilder,
+        notes: &HashMap<u8, String>,
+        origin: (Mm, Mm),
+        max_width: Option<f32>,
     ) -> f32 {
         let (ox, oy) = origin;
         let note_font_size = self.settings.body_font_size; // Same font size as auction
-        let line_height = note_font_size * 1.3 * 0.352778; // Convert pt to mm
+        let line_height = Self::note_line_height(self.settings);
         let measurer = self.get_measurer();
 
         // Get sorted note numbers
-        let mut note_nums: Vec<&u8> = auction.notes.keys().collect();
+        let mut note_nums: Vec<&u8> = notes.keys().collect();
         note_nums.sort();
 
         let mut current_y = oy.0 - line_height; // Start below the origin with some spacing
 
         for num in note_nums {
-            if let Some(text) = auction.notes.get(num) {
+            if let Some(text) = notes.get(num) {
                 // Convert suit escape codes (\S, \H, \D, \C) to Unicode symbols
                 let converted_text = replace_suit_escapes(text);
                 let prefix = format!("{}. ", num);
-                let prefix_width = measurer.measure_width_mm(&prefix, note_font_size);
+                let prefix_width = measurer.measure_text(&prefix, note_font_size);
 
                 // If max_width is specified, wrap the text
                 if let Some(max_w) = max_width {
@@ -654,13 +962,7 @@ impl<'a> BiddingTableRenderer<'a> {
                     if words.is_empty() {
                         // Empty note - just render prefix
                         layer.set_fill_color(Color::Rgb(BLACK));
-                        layer.use_text_builtin(
-                            &prefix,
-                            note_font_size,
-                            ox,
-                            Mm(current_y),
-                            self.font,
-                        );
+                        layer.use_text_font(&prefix, note_font_size, ox, Mm(current_y), &self.font);
                         current_y -= line_height;
                         continue;
                     }
@@ -669,10 +971,10 @@ impl<'a> BiddingTableRenderer<'a> {
                     let mut lines: Vec<String> = Vec::new();
                     let mut current_line = String::new();
                     let mut current_line_width = 0.0;
-                    let space_width = measurer.measure_width_mm(" ", note_font_size);
+                    let space_width = measurer.measure_text(" ", note_font_size);
 
                     for word in words {
-                        let word_width = measurer.measure_width_mm(word, note_font_size);
+                        let word_width = measurer.measure_text(word, note_font_size);
 
                         if current_line.is_empty() {
                             // First word on line
@@ -700,12 +1002,12 @@ impl<'a> BiddingTableRenderer<'a> {
                         if i == 0 {
                             // First line: render prefix then text with suit symbols
                             layer.set_fill_color(Color::Rgb(BLACK));
-                            layer.use_text_builtin(
+                            layer.use_text_font(
                                 &prefix,
                                 note_font_size,
                                 ox,
                                 Mm(current_y),
-                                self.font,
+                                &self.font,
                             );
                             self.render_text_with_suits(
                                 layer,
@@ -730,7 +1032,7 @@ impl<'a> BiddingTableRenderer<'a> {
                     // No max width - render as single line (original behavior)
                     // Render prefix then text with suit symbols
                     layer.set_fill_color(Color::Rgb(BLACK));
-                    layer.use_text_builtin(&prefix, note_font_size, ox, Mm(current_y), self.font);
+                    layer.use_text_font(&prefix, note_font_size, ox, Mm(current_y), &self.font);
                     self.render_text_with_suits(
                         layer,
                         &converted_text,
@@ -743,8 +1045,8 @@ impl<'a> BiddingTableRenderer<'a> {
             }
         }
 
-        // Return total height used
-        (oy.0 - current_y).max(0.0)
+        // Return the height down to the last note's baseline
+        (oy.0 - current_y - line_height).max(0.0)
     }
 
     /// Render a bid suit symbol and return width used
@@ -752,25 +1054,21 @@ impl<'a> BiddingTableRenderer<'a> {
         let (x, y) = pos;
         let measurer = self.get_measurer();
 
-        let (text, is_red, use_symbol_font) = match suit {
-            BidSuit::Clubs => ("♣", false, true),
-            BidSuit::Diamonds => ("♦", true, true),
-            BidSuit::Hearts => ("♥", true, true),
-            BidSuit::Spades => ("♠", false, true),
-            BidSuit::NoTrump => ("NT", false, false),
+        let (text, use_symbol_font) = match suit {
+            BidSuit::Clubs => ("♣", true),
+            BidSuit::Diamonds => ("♦", true),
+            BidSuit::Hearts => ("♥", true),
+            BidSuit::Spades => ("♠", true),
+            BidSuit::NoTrump => ("NT", false),
         };
 
-        if is_red {
-            layer.set_fill_color(Color::Rgb(self.colors.hearts.clone()));
-        } else {
-            layer.set_fill_color(Color::Rgb(BLACK));
-        }
+        layer.set_fill_color(Color::Rgb(self.colors.for_bid_suit(suit)));
 
         // Use symbol font for suit symbols, regular font for "NT"
         if use_symbol_font {
             layer.use_text(text, self.settings.body_font_size, x, y, self.symbol_font);
         } else {
-            layer.use_text_builtin(text, self.settings.body_font_size, x, y, self.font);
+            layer.use_text_font(text, self.settings.body_font_size, x, y, &self.font);
         }
 
         // Measure width (use Helvetica for symbols, Times for NT)
@@ -778,7 +1076,7 @@ impl<'a> BiddingTableRenderer<'a> {
             let sans_measurer = text_metrics::get_helvetica_measurer();
             sans_measurer.measure_width_mm(text, self.settings.body_font_size)
         } else {
-            measurer.measure_width_mm(text, self.settings.body_font_size)
+            measurer.measure_text(text, self.settings.body_font_size)
         }
     }
 
@@ -803,18 +1101,13 @@ impl<'a> BiddingTableRenderer<'a> {
                     // Flush any accumulated regular text
                     if !buffer.is_empty() {
                         layer.set_fill_color(Color::Rgb(BLACK));
-                        layer.use_text_builtin(&buffer, font_size, Mm(current_x), Mm(y), self.font);
-                        current_x += measurer.measure_width_mm(&buffer, font_size);
+                        layer.use_text_font(&buffer, font_size, Mm(current_x), Mm(y), &self.font);
+                        current_x += measurer.measure_text(&buffer, font_size);
                         buffer.clear();
                     }
 
                     // Render suit symbol with appropriate color
-                    let is_red = ch == '♥' || ch == '♦';
-                    if is_red {
-                        layer.set_fill_color(Color::Rgb(self.colors.hearts.clone()));
-                    } else {
-                        layer.set_fill_color(Color::Rgb(BLACK));
-                    }
+                    layer.set_fill_color(Color::Rgb(self.colors.for_symbol(ch)));
 
                     let symbol = ch.to_string();
                     layer.use_text(&symbol, font_size, Mm(current_x), Mm(y), self.symbol_font);
@@ -829,8 +1122,8 @@ impl<'a> BiddingTableRenderer<'a> {
         // Flush any remaining regular text
         if !buffer.is_empty() {
             layer.set_fill_color(Color::Rgb(BLACK));
-            layer.use_text_builtin(&buffer, font_size, Mm(current_x), Mm(y), self.font);
-            current_x += measurer.measure_width_mm(&buffer, font_size);
+            layer.use_text_font(&buffer, font_size, Mm(current_x), Mm(y), &self.font);
+            current_x += measurer.measure_text(&buffer, font_size);
         }
 
         // Return total width used
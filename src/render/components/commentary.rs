@@ -1,13 +1,17 @@
 use crate::config::Settings;
 use crate::model::{CommentaryBlock, FormattedText, Suit, TextSpan};
-use printpdf::{BuiltinFont, Color, FontId, Mm};
+use printpdf::{Color, FontId, Mm};
+use std::collections::HashMap;
 
 use crate::model::card::{Rank, RankExt};
-use crate::render::helpers::colors::{SuitColors, BLACK};
+use crate::render::helpers::colors::{SuitColors, BLACK, BLUE};
+use crate::render::helpers::fonts::TextFont;
+use crate::render::helpers::hyphenate::split_to_fit;
 use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::links::{Link, LinkTarget};
 use crate::render::helpers::text_metrics::{
     get_helvetica_bold_measurer, get_helvetica_measurer, get_times_bold_measurer,
-    get_times_measurer, BuiltinFontMeasurer,
+    get_times_measurer, BuiltinFontMeasurer, TextMeasure,
 };
 
 /// Check if a character is a Unicode suit symbol and return the corresponding Suit
@@ -47,10 +51,10 @@ pub struct FloatRenderResult {
 
 /// Renderer for commentary text
 pub struct CommentaryRenderer<'a> {
-    font: BuiltinFont,
-    bold_font: BuiltinFont,
-    italic_font: BuiltinFont,
-    bold_italic_font: BuiltinFont,
+    font: TextFont,
+    bold_font: TextFont,
+    italic_font: TextFont,
+    bold_italic_font: TextFont,
     symbol_font: &'a FontId, // Font with Unicode suit symbols (DejaVu Sans)
     colors: SuitColors,
     settings: &'a Settings,
@@ -71,6 +75,71 @@ enum RenderFragment {
         suit: Suit,
         rank: Rank,
     },
+    /// One word of hyperlink text
+    Link {
+        text: String,
+        url: String,
+    },
+    /// Superscript or subscript text, attached to the preceding word
+    Script {
+        text: String,
+        position: ScriptPosition,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptPosition {
+    Super,
+    Sub,
+}
+
+/// Size of superscript/subscript text relative to the body text
+const SCRIPT_SCALE: f32 = 0.65;
+
+impl ScriptPosition {
+    /// Baseline shift (mm) for body text with the given cap height
+    fn baseline_shift(self, cap_height: f32) -> f32 {
+        match self {
+            ScriptPosition::Super => cap_height * 0.5,
+            ScriptPosition::Sub => -cap_height * 0.3,
+        }
+    }
+}
+
+/// Split text into runs of ordinary characters and single suit symbols
+fn suit_runs(text: &str) -> Vec<(&str, Option<Suit>)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if let Some(suit) = suit_from_symbol(c) {
+            if start < i {
+                runs.push((&text[start..i], None));
+            }
+            runs.push((&text[i..i + c.len_utf8()], Some(suit)));
+            start = i + c.len_utf8();
+        }
+    }
+    if start < text.len() {
+        runs.push((&text[start..], None));
+    }
+    runs
+}
+
+/// Width of superscript/subscript text; suit symbols use the symbol font
+fn script_width(
+    text: &str,
+    font_size: f32,
+    measurer: &dyn TextMeasure,
+    symbol_measurer: &BuiltinFontMeasurer,
+) -> f32 {
+    let size = font_size * SCRIPT_SCALE;
+    suit_runs(text)
+        .into_iter()
+        .map(|(run, suit)| match suit {
+            Some(_) => symbol_measurer.measure_width_mm(run, size),
+            None => measurer.measure_text(run, size),
+        })
+        .sum()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -94,7 +163,85 @@ struct WordGroup {
 enum RenderToken {
     WordGroup(WordGroup),
     Space,
+    /// A line may wrap here, but no space is drawn (e.g. between CJK characters)
+    Break,
     LineBreak,
+    /// Move to the next table column
+    Tab,
+}
+
+/// Space that never wraps (`\~` in Bridge Composer commentary)
+const NO_BREAK_SPACE: char = '\u{a0}';
+
+/// Gap between table columns (mm)
+const TABLE_COLUMN_GAP: f32 = 3.0;
+
+/// Column positions for table rows (lines containing tabs), keyed by the
+/// index of the row's first token
+///
+/// Consecutive rows form one table: each column starts after the widest
+/// cell of the column before it. Table rows are never wrapped.
+fn table_columns(tokens: &[RenderToken], space_width: f32) -> HashMap<usize, Vec<f32>> {
+    // (first token, cell widths) for each line; cell widths only for table rows
+    let mut lines: Vec<(usize, Option<Vec<f32>>)> = Vec::new();
+    let mut line_start = 0;
+    let mut cells = vec![0.0];
+    let mut has_tab = false;
+    let mut pending_spaces = 0;
+    let mut pending_break = false;
+
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            RenderToken::WordGroup(group) => {
+                let cell = cells.last_mut().unwrap();
+                if *cell > 0.0 {
+                    *cell += space_width * space_units(pending_spaces, pending_break) as f32;
+                }
+                *cell += group.width;
+                pending_spaces = 0;
+                pending_break = false;
+            }
+            RenderToken::Space => pending_spaces += 1,
+            RenderToken::Break => pending_break = true,
+            RenderToken::Tab => {
+                has_tab = true;
+                cells.push(0.0);
+                pending_spaces = 0;
+                pending_break = false;
+            }
+            RenderToken::LineBreak => {
+                let cells = std::mem::replace(&mut cells, vec![0.0]);
+                lines.push((line_start, has_tab.then_some(cells)));
+                line_start = idx + 1;
+                has_tab = false;
+                pending_spaces = 0;
+                pending_break = false;
+            }
+        }
+    }
+    lines.push((line_start, has_tab.then_some(cells)));
+
+    let mut columns = HashMap::new();
+    for table in lines.split(|(_, cells)| cells.is_none()) {
+        let mut widths: Vec<f32> = Vec::new();
+        for (_, cells) in table {
+            for (i, width) in cells.iter().flatten().enumerate() {
+                if i < widths.len() {
+                    widths[i] = widths[i].max(*width);
+                } else {
+                    widths.push(*width);
+                }
+            }
+        }
+        let mut stops = vec![0.0];
+        for width in &widths[..widths.len().saturating_sub(1)] {
+            stops.push(stops.last().unwrap() + width + TABLE_COLUMN_GAP);
+        }
+        for (start, _) in table {
+            columns.insert(*start, stops.clone());
+        }
+    }
+    columns
 }
 
 /// Check if a character is a valid card rank or placeholder in card lists.
@@ -137,13 +284,66 @@ fn is_rank_char(c: char) -> bool {
     is_card_char(c)
 }
 
+/// Check if a character is Chinese, Japanese or Korean script.
+///
+/// These scripts don't separate words with spaces, so a line may wrap
+/// between any two of their characters.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x11FF         // Hangul Jamo
+            | 0x2E80..=0x2FDF   // CJK and Kangxi radicals
+            | 0x3000..=0x30FF   // CJK punctuation, Hiragana, Katakana
+            | 0x3100..=0x31FF   // Bopomofo, Hangul compatibility Jamo
+            | 0x3400..=0x4DBF   // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+            | 0xAC00..=0xD7AF   // Hangul syllables
+            | 0xF900..=0xFAFF   // CJK Compatibility Ideographs
+            | 0xFF00..=0xFFEF   // Fullwidth forms
+            | 0x20000..=0x2FFFF // CJK Unified Ideographs Extensions B+
+    )
+}
+
+/// CJK punctuation that must not start a line
+fn is_cjk_closing(c: char) -> bool {
+    "、。，．：；！？）」』】〕〉》ー々".contains(c)
+}
+
+/// CJK punctuation that must not end a line
+fn is_cjk_opening(c: char) -> bool {
+    "（「『【〔〈《".contains(c)
+}
+
+/// Check if text contains CJK characters, which the builtin PDF fonts can't show
+pub fn contains_cjk(text: &str) -> bool {
+    text.chars().any(is_cjk)
+}
+
+/// Check if a line may wrap between two adjacent non-space characters
+fn is_cjk_break(prev: char, next: char) -> bool {
+    (is_cjk(prev) || is_cjk(next)) && !is_cjk_opening(prev) && !is_cjk_closing(next)
+}
+
+/// Number of spaces drawn before a word group.
+///
+/// Adjacent groups normally get at least one space; a `Break` with no
+/// spaces (between CJK characters) joins them directly.
+fn space_units(pending_spaces: usize, pending_break: bool) -> usize {
+    if pending_break && pending_spaces == 0 {
+        0
+    } else {
+        pending_spaces.max(1)
+    }
+}
+
 /// Parse spans into render tokens, grouping fragments that should stay together
 fn tokenize_spans(
     spans: &[TextSpan],
     font_size: f32,
-    regular_measurer: &BuiltinFontMeasurer,
-    bold_measurer: &BuiltinFontMeasurer,
+    regular_measurer: &dyn TextMeasure,
+    bold_measurer: &dyn TextMeasure,
     symbol_measurer: &BuiltinFontMeasurer,
+    ten_as_t: bool,
 ) -> Vec<RenderToken> {
     let mut tokens: Vec<RenderToken> = Vec::new();
     let mut current_group: Vec<RenderFragment> = Vec::new();
@@ -202,13 +402,13 @@ fn tokenize_spans(
 
                 while i < chars.len() {
                     let c = chars[i];
-                    if c.is_whitespace() {
+                    if c.is_whitespace() && c != NO_BREAK_SPACE {
                         // Flush any accumulated word fragment
                         if !current_word.is_empty() {
                             // Check if this word is a rank character to update card list state
                             let is_rank = current_word.len() == 1
                                 && is_rank_char(current_word.chars().next().unwrap());
-                            let w = measurer.measure_width_mm(&current_word, font_size);
+                            let w = measurer.measure_text(&current_word, font_size);
                             current_group.push(RenderFragment::Text {
                                 text: std::mem::take(&mut current_word),
                                 style,
@@ -237,7 +437,7 @@ fn tokenize_spans(
 
                         if should_stay_in_card_list {
                             // Keep space in the group - add it as a text fragment
-                            let space_w = regular_measurer.measure_width_mm(" ", font_size);
+                            let space_w = regular_measurer.measure_text(" ", font_size);
                             current_group.push(RenderFragment::Text {
                                 text: " ".to_string(),
                                 style,
@@ -259,7 +459,7 @@ fn tokenize_spans(
                         // Unicode suit symbol - handle specially for correct coloring
                         // First flush any accumulated word
                         if !current_word.is_empty() {
-                            let w = measurer.measure_width_mm(&current_word, font_size);
+                            let w = measurer.measure_text(&current_word, font_size);
                             current_group.push(RenderFragment::Text {
                                 text: std::mem::take(&mut current_word),
                                 style,
@@ -273,7 +473,29 @@ fn tokenize_spans(
                         current_group_width += symbol_w;
                         in_card_list = true;
                     } else {
-                        current_word.push(c);
+                        let breaks_before =
+                            i > 0 && !chars[i - 1].is_whitespace() && is_cjk_break(chars[i - 1], c);
+                        if breaks_before {
+                            // CJK text has no spaces, so wrap between characters
+                            if !current_word.is_empty() {
+                                let w = measurer.measure_text(&current_word, font_size);
+                                current_group.push(RenderFragment::Text {
+                                    text: std::mem::take(&mut current_word),
+                                    style,
+                                    color,
+                                });
+                                current_group_width += w;
+                            }
+                            flush_group(
+                                &mut tokens,
+                                &mut current_group,
+                                &mut current_group_width,
+                                &mut in_card_list,
+                            );
+                            tokens.push(RenderToken::Break);
+                        }
+                        // A no-break space is drawn as a space inside the word
+                        current_word.push(if c == NO_BREAK_SPACE { ' ' } else { c });
                     }
                     i += 1;
                 }
@@ -283,7 +505,7 @@ fn tokenize_spans(
                     // Check if this is a rank character
                     let is_rank = current_word.len() == 1
                         && is_rank_char(current_word.chars().next().unwrap());
-                    let w = measurer.measure_width_mm(&current_word, font_size);
+                    let w = measurer.measure_text(&current_word, font_size);
                     current_group.push(RenderFragment::Text {
                         text: current_word,
                         style,
@@ -308,7 +530,8 @@ fn tokenize_spans(
             TextSpan::CardRef { suit, rank } => {
                 let symbol_w =
                     symbol_measurer.measure_width_mm(&suit.symbol().to_string(), font_size);
-                let rank_w = regular_measurer.measure_width_mm(rank.display_str(), font_size);
+                let rank_w =
+                    regular_measurer.measure_text(rank.display_str_with(ten_as_t), font_size);
                 current_group.push(RenderFragment::CardRef {
                     suit: *suit,
                     rank: *rank,
@@ -317,6 +540,44 @@ fn tokenize_spans(
                 // Start card list mode
                 in_card_list = true;
             }
+            TextSpan::Link { text, url } => {
+                // Each word of the link text is its own fragment so the link can wrap
+                let mut words = text.split(' ').peekable();
+                while let Some(word) = words.next() {
+                    if !word.is_empty() {
+                        current_group_width += regular_measurer.measure_text(word, font_size);
+                        current_group.push(RenderFragment::Link {
+                            text: word.to_string(),
+                            url: url.clone(),
+                        });
+                    }
+                    if words.peek().is_some() {
+                        flush_group(
+                            &mut tokens,
+                            &mut current_group,
+                            &mut current_group_width,
+                            &mut in_card_list,
+                        );
+                        tokens.push(RenderToken::Space);
+                    }
+                }
+                in_card_list = false;
+            }
+            TextSpan::Superscript(s) | TextSpan::Subscript(s) => {
+                // No flush: the script stays with the word before it
+                let position = if matches!(span, TextSpan::Superscript(_)) {
+                    ScriptPosition::Super
+                } else {
+                    ScriptPosition::Sub
+                };
+                current_group_width +=
+                    script_width(s, font_size, regular_measurer, symbol_measurer);
+                current_group.push(RenderFragment::Script {
+                    text: s.clone(),
+                    position,
+                });
+                in_card_list = false;
+            }
             TextSpan::LineBreak => {
                 flush_group(
                     &mut tokens,
@@ -326,6 +587,15 @@ fn tokenize_spans(
                 );
                 tokens.push(RenderToken::LineBreak);
             }
+            TextSpan::Tab => {
+                flush_group(
+                    &mut tokens,
+                    &mut current_group,
+                    &mut current_group_width,
+                    &mut in_card_list,
+                );
+                tokens.push(RenderToken::Tab);
+            }
         }
     }
 
@@ -340,12 +610,25 @@ fn tokenize_spans(
     tokens
 }
 
+/// Replace a word group with its two hyphenated parts, which may wrap between them
+fn split_hyphenated(tokens: &mut Vec<RenderToken>, idx: usize, parts: (WordGroup, WordGroup)) {
+    let (first, rest) = parts;
+    tokens.splice(
+        idx..=idx,
+        [
+            RenderToken::WordGroup(first),
+            RenderToken::Break,
+            RenderToken::WordGroup(rest),
+        ],
+    );
+}
+
 impl<'a> CommentaryRenderer<'a> {
     pub fn new(
-        font: BuiltinFont,
-        bold_font: BuiltinFont,
-        italic_font: BuiltinFont,
-        bold_italic_font: BuiltinFont,
+        font: impl Into<TextFont>,
+        bold_font: impl Into<TextFont>,
+        italic_font: impl Into<TextFont>,
+        bold_italic_font: impl Into<TextFont>,
         symbol_font: &'a FontId,
         settings: &'a Settings,
     ) -> Self {
@@ -358,33 +641,63 @@ impl<'a> CommentaryRenderer<'a> {
             .unwrap_or(false);
 
         Self {
-            font,
-            bold_font,
-            italic_font,
-            bold_italic_font,
+            font: font.into(),
+            bold_font: bold_font.into(),
+            italic_font: italic_font.into(),
+            bold_italic_font: bold_italic_font.into(),
             symbol_font,
-            colors: SuitColors::new(settings.black_color, settings.red_color),
+            colors: SuitColors::from_settings(settings),
             settings,
             use_sans_measurer,
         }
     }
 
     /// Get the appropriate text measurer for regular text
-    fn get_regular_measurer(&self) -> &'static BuiltinFontMeasurer {
-        if self.use_sans_measurer {
+    fn get_regular_measurer(&self) -> &dyn TextMeasure {
+        self.font.measurer_or(if self.use_sans_measurer {
             get_helvetica_measurer()
         } else {
             get_times_measurer()
-        }
+        })
     }
 
     /// Get the appropriate text measurer for bold text
-    fn get_bold_measurer(&self) -> &'static BuiltinFontMeasurer {
-        if self.use_sans_measurer {
+    fn get_bold_measurer(&self) -> &dyn TextMeasure {
+        self.bold_font.measurer_or(if self.use_sans_measurer {
             get_helvetica_bold_measurer()
         } else {
             get_times_bold_measurer()
+        })
+    }
+
+    /// Split a one-word group at a hyphenation point so the first part fits in
+    /// `available` (only with `--hyphenate`)
+    fn hyphenate_group(&self, group: &WordGroup, available: f32) -> Option<(WordGroup, WordGroup)> {
+        if !self.settings.hyphenate {
+            return None;
         }
+        let [RenderFragment::Text { text, style, color }] = group.fragments.as_slice() else {
+            return None;
+        };
+        let font_size = self.settings.commentary_font_size;
+        let measurer = match style {
+            TextStyle::Plain | TextStyle::Italic | TextStyle::Underline => {
+                self.get_regular_measurer()
+            }
+            TextStyle::Bold | TextStyle::BoldItalic => self.get_bold_measurer(),
+        };
+        let (first, rest) = split_to_fit(text, self.settings.language, available, |s| {
+            measurer.measure_text(s, font_size)
+        })?;
+        let part = |text: String| WordGroup {
+            width: measurer.measure_text(&text, font_size),
+            fragments: vec![RenderFragment::Text {
+                text,
+                style: *style,
+                color: *color,
+            }],
+        };
+        Some((part(first), part(rest)))
     }
 
     /// Measure the height of a commentary block without rendering
@@ -401,26 +714,44 @@ impl<'a> CommentaryRenderer<'a> {
         let bold_measurer = self.get_bold_measurer();
         let symbol_measurer = get_helvetica_measurer();
 
-        let base_space_width = regular_measurer.measure_width_mm(" ", font_size);
+        let base_space_width = regular_measurer.measure_text(" ", font_size);
 
         // Tokenize the spans into word groups and spaces
-        let tokens = tokenize_spans(
+        let mut tokens = tokenize_spans(
             &text.spans,
             font_size,
             regular_measurer,
             bold_measurer,
             symbol_measurer,
+            self.settings.ten_as_t,
         );
 
+        let mut table_rows = table_columns(&tokens, base_space_width);
+
         // Count lines by simulating the line-wrapping logic
         let mut token_idx = 0;
         let mut line_count = 0;
 
         while token_idx < tokens.len() {
+            // Table rows take one line each
+            if table_rows.contains_key(&token_idx) {
+                while token_idx < tokens.len() {
+                    token_idx += 1;
+                    if matches!(tokens[token_idx - 1], RenderToken::LineBreak) {
+                        break;
+                    }
+                }
+                line_count += 1;
+                continue;
+            }
+
             // Collect word groups for the current line
+            let line_start_idx = token_idx;
             let mut line_groups: Vec<&WordGroup> = Vec::new();
             let mut line_width: f32 = 0.0;
             let mut pending_spaces: usize = 0;
+            let mut pending_break = false;
+            let mut hyphenated = None;
 
             while token_idx < tokens.len() {
                 match &tokens[token_idx] {
@@ -428,15 +759,28 @@ impl<'a> CommentaryRenderer<'a> {
                         let space_needed = if line_groups.is_empty() {
                             0.0
                         } else {
-                            base_space_width * pending_spaces.max(1) as f32
+                            base_space_width * space_units(pending_spaces, pending_break) as f32
                         };
                         let new_width = line_width + space_needed + group.width;
 
-                        if line_groups.is_empty() || new_width <= max_width {
+                        if new_width <= max_width {
+                            line_groups.push(group);
+                            line_width = new_width;
+                            token_idx += 1;
+                            pending_spaces = 0;
+                            pending_break = false;
+                        } else if let Some(parts) =
+                            self.hyphenate_group(group, max_width - line_width - space_needed)
+                        {
+                            hyphenated = Some((token_idx, parts));
+                            break;
+                        } else if line_groups.is_empty() {
+                            // Word longer than the line: it overflows
                             line_groups.push(group);
                             line_width = new_width;
                             token_idx += 1;
                             pending_spaces = 0;
+                            pending_break = false;
                         } else {
                             break;
                         }
@@ -445,6 +789,15 @@ impl<'a> CommentaryRenderer<'a> {
                         pending_spaces += 1;
                         token_idx += 1;
                     }
+                    RenderToken::Break => {
+                        pending_break = true;
+                        token_idx += 1;
+                    }
+                    // Tabs outside table rows can't happen; treat as a space
+                    RenderToken::Tab => {
+                        pending_spaces += 1;
+                        token_idx += 1;
+                    }
                     RenderToken::LineBreak => {
                         token_idx += 1;
                         break;
@@ -452,6 +805,14 @@ impl<'a> CommentaryRenderer<'a> {
                 }
             }
 
+            // Split a hyphenated word and lay out the line again
+            if let Some((idx, parts)) = hyphenated {
+                split_hyphenated(&mut tokens, idx, parts);
+                table_rows = table_columns(&tokens, base_space_width);
+                token_idx = line_start_idx;
+                continue;
+            }
+
             // Count this line (even if empty due to consecutive line breaks)
             line_count += 1;
         }
@@ -512,7 +873,7 @@ impl<'a> CommentaryRenderer<'a> {
         let bold_measurer = self.get_bold_measurer();
         let symbol_measurer = get_helvetica_measurer();
 
-        let base_space_width = regular_measurer.measure_width_mm(" ", font_size);
+        let base_space_width = regular_measurer.measure_text(" ", font_size);
 
         // Track current layout state
         let mut current_line_start = ox.0;
@@ -521,14 +882,17 @@ impl<'a> CommentaryRenderer<'a> {
         let mut y = oy.0;
 
         // Tokenize the spans into word groups and spaces
-        let tokens = tokenize_spans(
+        let mut tokens = tokenize_spans(
             &text.spans,
             font_size,
             regular_measurer,
             bold_measurer,
             symbol_measurer,
+            self.settings.ten_as_t,
         );
 
+        let mut table_rows = table_columns(&tokens, base_space_width);
+
         // Process tokens and render lines on-the-fly
         // This allows us to handle width changes dynamically
         let mut token_idx = 0;
@@ -547,29 +911,74 @@ impl<'a> CommentaryRenderer<'a> {
             }
 
             // Collect word groups for the current line using current max_width
-            // Track (word_group, preceding_space_count) for each word
-            let mut line_groups: Vec<(&WordGroup, usize)> = Vec::new();
+            // Track (word_group, preceding_space_count, table column start) for each word
+            let line_start_idx = token_idx;
+            let mut hyphenated = None;
+            let mut line_groups: Vec<(&WordGroup, usize, Option<f32>)> = Vec::new();
             let mut line_width: f32 = 0.0;
             let mut is_paragraph_end = false;
             let mut pending_spaces: usize = 0;
+            let mut pending_break = false;
+
+            // A table row is laid out on one line, each cell at its column
+            let row_columns = table_rows.get(&token_idx);
+            if let Some(columns) = row_columns {
+                let mut column = Some(columns[0]);
+                let mut column_idx = 0;
+                while token_idx < tokens.len() {
+                    token_idx += 1;
+                    match &tokens[token_idx - 1] {
+                        RenderToken::WordGroup(group) => {
+                            let spaces = space_units(pending_spaces, pending_break);
+                            line_groups.push((group, spaces, column.take()));
+                            pending_spaces = 0;
+                            pending_break = false;
+                        }
+                        RenderToken::Space => pending_spaces += 1,
+                        RenderToken::Break => pending_break = true,
+                        RenderToken::Tab => {
+                            column_idx += 1;
+                            column = columns.get(column_idx).copied();
+                            pending_spaces = 0;
+                            pending_break = false;
+                        }
+                        RenderToken::LineBreak => break,
+                    }
+                }
+            }
 
-            while token_idx < tokens.len() {
+            while row_columns.is_none() && token_idx < tokens.len() {
                 match &tokens[token_idx] {
                     RenderToken::WordGroup(group) => {
                         // Calculate width if we add this word
+                        let spaces = space_units(pending_spaces, pending_break);
                         let space_needed = if line_groups.is_empty() {
                             0.0
                         } else {
-                            base_space_width * pending_spaces.max(1) as f32
+                            base_space_width * spaces as f32
                         };
                         let new_width = line_width + space_needed + group.width;
 
-                        if line_groups.is_empty() || new_width <= max_width {
+                        if new_width <= max_width {
                             // Word fits on this line
-                            line_groups.push((group, pending_spaces));
+                            line_groups.push((group, spaces, None));
                             line_width = new_width;
                             token_idx += 1;
                             pending_spaces = 0;
+                            pending_break = false;
+                        } else if let Some(parts) =
+                            self.hyphenate_group(group, max_width - line_width - space_needed)
+                        {
+                            // Hyphenate the word at the end of this line
+                            hyphenated = Some((token_idx, parts));
+                            break;
+                        } else if line_groups.is_empty() {
+                            // Word longer than the line: it overflows
+                            line_groups.push((group, spaces, None));
+                            line_width = new_width;
+                            token_idx += 1;
+                            pending_spaces = 0;
+                            pending_break = false;
                         } else {
                             // Word doesn't fit, break line here (don't consume this token)
                             break;
@@ -580,6 +989,15 @@ impl<'a> CommentaryRenderer<'a> {
                         pending_spaces += 1;
                         token_idx += 1;
                     }
+                    RenderToken::Break => {
+                        pending_break = true;
+                        token_idx += 1;
+                    }
+                    // Tabs outside table rows can't happen; treat as a space
+                    RenderToken::Tab => {
+                        pending_spaces += 1;
+                        token_idx += 1;
+                    }
                     RenderToken::LineBreak => {
                         // Explicit line break - end the current line as a paragraph end
                         is_paragraph_end = true;
@@ -589,35 +1007,47 @@ impl<'a> CommentaryRenderer<'a> {
                 }
             }
 
+            // Split a hyphenated word and lay out the line again
+            if let Some((idx, parts)) = hyphenated {
+                split_hyphenated(&mut tokens, idx, parts);
+                table_rows = table_columns(&tokens, base_space_width);
+                token_idx = line_start_idx;
+                continue;
+            }
+
             // If we collected no words (e.g., multiple consecutive line breaks), just move to next line
             if line_groups.is_empty() {
                 y -= line_height;
                 continue;
             }
 
-            // Check if this is the last line (paragraph end)
-            if token_idx >= tokens.len() {
+            // Check if this is the last line (paragraph end); table rows aren't justified
+            if token_idx >= tokens.len() || row_columns.is_some() {
                 is_paragraph_end = true;
             }
 
             // Calculate total space units needed (sum of all space counts between words)
-            let total_space_units: usize = line_groups
+            let total_space_units: usize =
+                line_groups.iter().skip(1).map(|(_, count, _)| *count).sum();
+
+            // Stretch units for justification: one per space, and one for each
+            // wrap point between CJK characters (which has no space to widen)
+            let stretch_units: usize = line_groups
                 .iter()
                 .skip(1)
-                .map(|(_, count)| (*count).max(1))
+                .map(|(_, count, _)| (*count).max(1))
                 .sum();
 
-            // Calculate space width for justification
-            let space_width = if justify && !is_paragraph_end && total_space_units > 0 {
-                // Calculate total content width (word groups only, no spaces)
-                let total_word_width: f32 = line_groups.iter().map(|(g, _)| g.width).sum();
-                // Available space for distribution (divided by total space units)
-                let available_space = max_width - total_word_width;
-                // Each space unit gets this width
-                available_space / total_space_units as f32
+            // Extra width per stretch unit, so a justified line fills max_width
+            let stretch = if justify && !is_paragraph_end && stretch_units > 0 {
+                // Calculate natural width (word groups plus base spaces)
+                let total_word_width: f32 = line_groups.iter().map(|(g, _, _)| g.width).sum();
+                let natural_width = total_word_width + base_space_width * total_space_units as f32;
+                // Distribute the remaining space over the stretch units
+                ((max_width - natural_width) / stretch_units as f32).max(0.0)
             } else {
-                // Use base space width for paragraph-ending lines or when not justifying
-                base_space_width
+                // Paragraph-ending lines and ragged-right text keep base spacing
+                0.0
             };
 
             // Render the line
@@ -647,15 +1077,22 @@ impl<'a> CommentaryRenderer<'a> {
                 }
             };
 
-            for (i, (group, space_count)) in line_groups.iter().enumerate() {
+            for (i, (group, space_count, column)) in line_groups.iter().enumerate() {
                 // Check if this group starts with underlined content
                 let group_starts_underlined =
                     group.fragments.first().map(is_underlined).unwrap_or(false);
 
-                // Add space before word (except first word)
-                if i > 0 {
-                    let num_spaces = (*space_count).max(1);
-                    let space_advance = space_width * num_spaces as f32;
+                if let Some(column_x) = column {
+                    // First word of a table cell starts at its column
+                    if underline_start_x.is_some() {
+                        draw_underline(layer, underline_start_x, x);
+                        underline_start_x = None;
+                    }
+                    x = current_line_start + column_x;
+                } else if i > 0 {
+                    // Add space before word (except first word)
+                    let space_advance = base_space_width * *space_count as f32
+                        + stretch * (*space_count).max(1) as f32;
 
                     // If we're continuing an underline into this group, include the space
                     // If we're ending an underline (next group not underlined), draw it before space
@@ -681,10 +1118,10 @@ impl<'a> CommentaryRenderer<'a> {
                             color,
                         } => {
                             let font = match style {
-                                TextStyle::Plain | TextStyle::Underline => self.font,
-                                TextStyle::Bold => self.bold_font,
-                                TextStyle::Italic => self.italic_font,
-                                TextStyle::BoldItalic => self.bold_italic_font,
+                                TextStyle::Plain | TextStyle::Underline => &self.font,
+                                TextStyle::Bold => &self.bold_font,
+                                TextStyle::Italic => &self.italic_font,
+                                TextStyle::BoldItalic => &self.bold_italic_font,
                             };
                             let measurer = match style {
                                 TextStyle::Plain | TextStyle::Italic | TextStyle::Underline => {
@@ -692,7 +1129,7 @@ impl<'a> CommentaryRenderer<'a> {
                                 }
                                 TextStyle::Bold | TextStyle::BoldItalic => &bold_measurer,
                             };
-                            let width = measurer.measure_width_mm(txt, font_size);
+                            let width = measurer.measure_text(txt, font_size);
 
                             // Check underline state transitions
                             let is_underline = *style == TextStyle::Underline;
@@ -713,7 +1150,7 @@ impl<'a> CommentaryRenderer<'a> {
                                 None => BLACK,
                             };
                             layer.set_fill_color(Color::Rgb(fill));
-                            layer.use_text_builtin(txt, font_size, Mm(x), Mm(y), font);
+                            layer.use_text_font(txt, font_size, Mm(x), Mm(y), font);
 
                             x += width;
                         }
@@ -741,9 +1178,9 @@ impl<'a> CommentaryRenderer<'a> {
 
                             let symbol = suit.symbol().to_string();
                             let symbol_width = symbol_measurer.measure_width_mm(&symbol, font_size);
-                            let rank_str = rank.display_str().to_string();
-                            let rank_width =
-                                regular_measurer.measure_width_mm(&rank_str, font_size);
+                            let rank_str =
+                                rank.display_str_with(self.settings.ten_as_t).to_string();
+                            let rank_width = regular_measurer.measure_text(&rank_str, font_size);
 
                             // Render suit symbol with color
                             let color = self.colors.for_suit(suit);
@@ -753,8 +1190,86 @@ impl<'a> CommentaryRenderer<'a> {
 
                             // Render rank in black
                             layer.set_fill_color(Color::Rgb(BLACK));
-                            layer.use_text_builtin(&rank_str, font_size, Mm(x), Mm(y), self.font);
+                            layer.use_text_font(&rank_str, font_size, Mm(x), Mm(y), &self.font);
                             x += rank_width;
+
+                            if self.settings.link_card_refs {
+                                let descender = regular_measurer.descender_mm(font_size);
+                                let height = descender + regular_measurer.cap_height_mm(font_size);
+                                let width = symbol_width + rank_width;
+                                layer.add_card_link(
+                                    *suit,
+                                    *rank,
+                                    x - width,
+                                    y - descender,
+                                    width,
+                                    height,
+                                );
+                            }
+                        }
+                        RenderFragment::Link { text: txt, url } => {
+                            // Links draw their own underline
+                            if underline_start_x.is_some() {
+                                draw_underline(layer, underline_start_x, x);
+                                underline_start_x = None;
+                            }
+
+                            let width = regular_measurer.measure_text(txt, font_size);
+                            layer.set_fill_color(Color::Rgb(BLUE));
+                            layer.use_text_font(txt, font_size, Mm(x), Mm(y), &self.font);
+                            layer.set_outline_color(Color::Rgb(BLUE));
+                            layer.set_outline_thickness(0.3);
+                            layer.add_line(Mm(x), Mm(underline_y), Mm(x + width), Mm(underline_y));
+
+                            let descender = regular_measurer.descender_mm(font_size);
+                            layer.add_link(Link {
+                                x,
+                                y: y - descender,
+                                width,
+                                height: descender + regular_measurer.cap_height_mm(font_size),
+                                target: LinkTarget::Uri(url.clone()),
+                            });
+                            x += width;
+                        }
+                        RenderFragment::Script {
+                            text: txt,
+                            position,
+                        } => {
+                            if underline_start_x.is_some() {
+                                draw_underline(layer, underline_start_x, x);
+                                underline_start_x = None;
+                            }
+
+                            let size = font_size * SCRIPT_SCALE;
+                            let script_y = y + position
+                                .baseline_shift(regular_measurer.cap_height_mm(font_size));
+                            for (run, suit) in suit_runs(txt) {
+                                match suit {
+                                    Some(suit) => {
+                                        let color = self.colors.for_suit(&suit);
+                                        layer.set_fill_color(Color::Rgb(color));
+                                        layer.use_text(
+                                            run,
+                                            size,
+                                            Mm(x),
+                                            Mm(script_y),
+                                            self.symbol_font,
+                                        );
+                                        x += symbol_measurer.measure_width_mm(run, size);
+                                    }
+                                    None => {
+                                        layer.set_fill_color(Color::Rgb(BLACK));
+                                        layer.use_text_font(
+                                            run,
+                                            size,
+                                            Mm(x),
+                                            Mm(script_y),
+                                            &self.font,
+                                        );
+                                        x += regular_measurer.measure_text(run, size);
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -780,3 +1295,77 @@ impl<'a> CommentaryRenderer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::commentary::parse_commentary;
+    use printpdf::BuiltinFont;
+
+    /// Tokens of plain text as a string: word groups as their text, `|` for a
+    /// break with no space and `_` for a space
+    fn tokens(text: &str) -> String {
+        let tokens = tokenize_spans(
+            &[TextSpan::Plain(text.to_string())],
+            10.0,
+            get_times_measurer(),
+            get_times_bold_measurer(),
+            get_helvetica_measurer(),
+            false,
+        );
+        tokens
+            .iter()
+            .map(|token| match token {
+                RenderToken::WordGroup(group) => group
+                    .fragments
+                    .iter()
+                    .map(|fragment| match fragment {
+                        RenderFragment::Text { text, .. } => text.as_str(),
+                        _ => "?",
+                    })
+                    .collect(),
+                RenderToken::Space => "_".to_string(),
+                RenderToken::Break => "|".to_string(),
+                RenderToken::LineBreak => "\n".to_string(),
+                RenderToken::Tab => "\t".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cjk_breaks_between_characters() {
+        assert_eq!(tokens("北家开叫"), "北|家|开|叫");
+        // Latin words keep their spaces and don't break inside
+        assert_eq!(tokens("Stayman 问叫"), "Stayman_问|叫");
+        assert_eq!(tokens("Stayman问叫"), "Stayman|问|叫");
+        assert_eq!(tokens("Hanako さん"), "Hanako_さ|ん");
+    }
+
+    #[test]
+    fn test_cjk_punctuation_stays_with_its_word() {
+        // Closing punctuation never starts a line, opening never ends one
+        assert_eq!(tokens("无将。南家"), "无|将。|南|家");
+        assert_eq!(tokens("叫「二方块」"), "叫|「二|方|块」");
+        assert_eq!(tokens("一，二"), "一，|二");
+    }
+
+    #[test]
+    fn test_cjk_text_wraps_without_spaces() {
+        let settings = Settings::default();
+        let symbol_font = FontId::new();
+        let renderer = CommentaryRenderer::new(
+            BuiltinFont::TimesRoman,
+            BuiltinFont::TimesBold,
+            BuiltinFont::TimesItalic,
+            BuiltinFont::TimesBoldItalic,
+            &symbol_font,
+            &settings,
+        );
+        let block =
+            parse_commentary("北家开叫一无将南家用斯台曼问叫询问高花北家没有四张高花").unwrap();
+
+        let one_line = renderer.measure_height(&block, 500.0);
+        let wrapped = renderer.measure_height(&block, 4.0);
+        assert!(wrapped > one_line * 3.0, "{} vs {}", wrapped, one_line);
+    }
+}
@@ -17,7 +17,7 @@ use crate::render::components::{
     DummyRenderer, FanRenderer, LosersTableRenderer, WinnersTableRenderer,
 };
 use crate::render::helpers::card_assets::{CardAssets, CARD_HEIGHT_MM};
-use crate::render::helpers::colors::{SuitColors, BLACK};
+use crate::render::helpers::colors::{SuitColors, Theme, BLACK};
 use crate::render::helpers::layer::LayerBuilder;
 use crate::render::helpers::text_metrics;
 
@@ -48,14 +48,6 @@ const TABLE_RAISE: f32 = 11.5;
 /// Font size for opening lead box (larger for visibility)
 const LEAD_BOX_FONT_SIZE: f32 = 12.5;
 
-/// Mild yellow background color for opening lead box
-const MILD_YELLOW: Rgb = Rgb {
-    r: 1.0,
-    g: 1.0,
-    b: 0.7,
-    icc_profile: None,
-};
-
 /// Renderer for a small declarer's plan layout (one quadrant of a page)
 pub struct DeclarersPlanSmallRenderer<'a> {
     card_assets: &'a CardAssets,
@@ -63,6 +55,8 @@ pub struct DeclarersPlanSmallRenderer<'a> {
     bold_font: BuiltinFont,
     symbol_font: &'a FontId,
     colors: SuitColors,
+    /// Colors for table headers and the opening lead box
+    theme: Theme,
     /// Scale factor for card rendering
     card_scale: f32,
     /// Scale factor for non-card elements (text, tables, spacing)
@@ -73,6 +67,8 @@ pub struct DeclarersPlanSmallRenderer<'a> {
     dummy_overlap: f32,
     /// Whether to show debug bounding boxes
     show_bounds: bool,
+    /// Show tens as "T" instead of "10"
+    ten_as_t: bool,
     /// Cards to circle (highlight) with their colors
     circled_cards: HashMap<Card, Rgb>,
 }
@@ -92,15 +88,23 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
             bold_font,
             symbol_font,
             colors,
+            theme: Theme::default(),
             card_scale: 0.35,
             layout_scale: 1.0,
             fan_arc: 30.0,
             dummy_overlap: 0.18, // Show some suit symbol on clipped cards
             show_bounds: false,
+            ten_as_t: false,
             circled_cards: HashMap::new(),
         }
     }
 
+    /// Set the color theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Set the card scale factor
     pub fn card_scale(mut self, scale: f32) -> Self {
         self.card_scale = scale;
@@ -131,6 +135,12 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
         self
     }
 
+    /// Set whether tens are shown as "T" instead of "10"
+    pub fn ten_as_t(mut self, ten_as_t: bool) -> Self {
+        self.ten_as_t = ten_as_t;
+        self
+    }
+
     /// Set which cards should be circled (highlighted) with their colors
     ///
     /// The ellipse appears around the rank/suit indicator in the top-left corner of the card.
@@ -281,6 +291,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
         .col_width(16.0 * s)
         .row_height(8.0 * s)
         .header_height(6.0 * s)
+        .theme(self.theme.clone())
     }
 
     /// Create the losers table renderer, scaled by layout_scale
@@ -296,6 +307,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
         .col_width(16.0 * s)
         .row_height(8.0 * s)
         .header_height(6.0 * s)
+        .theme(self.theme.clone())
     }
 
     /// Render the declarer's plan layout from a Board
@@ -485,7 +497,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
     }
 
     /// Render contract string with colored suit symbol
-    /// Hearts and diamonds are rendered in red, spades and clubs in black
+    /// The suit symbol is drawn in its suit color
     fn render_colored_contract(
         &self,
         layer: &mut LayerBuilder,
@@ -498,8 +510,8 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
         // Use serif measurer for text width
         let measurer = text_metrics::get_times_measurer();
 
-        // Determine if trump suit is red
-        let is_red = trump.map(|t| t.is_red()).unwrap_or(false);
+        // Trump suit color (black for NT or an unknown trump)
+        let suit_color = trump.map(|t| self.colors.for_bid_suit(&t)).unwrap_or(BLACK);
 
         // Split contract into level and symbol
         // Contract format is like "4♥" or "3NT"
@@ -514,11 +526,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
             layer.use_text_builtin(level, font_size, x, y, self.bold_font);
 
             // Render symbol in appropriate color
-            if is_red {
-                layer.set_fill_color(Color::Rgb(self.colors.hearts.clone()));
-            } else {
-                layer.set_fill_color(Color::Rgb(BLACK));
-            }
+            layer.set_fill_color(Color::Rgb(suit_color));
 
             // Use builtin font for NT, symbol font for suit symbols
             let is_nt = trump
@@ -554,7 +562,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
         // Build the text components: "Lead: " + suit symbol + rank
         let label = "Lead: ";
         let suit_symbol = card.suit.symbol().to_string();
-        let rank_str = card.rank.display_str().to_string();
+        let rank_str = card.rank.display_str_with(self.ten_as_t).to_string();
 
         // Measure widths
         let label_width = measurer.measure_width_mm(label, font_size);
@@ -575,7 +583,7 @@ impl<'a> DeclarersPlanSmallRenderer<'a> {
 
         // Draw mild yellow background rectangle (filled, no stroke)
         // add_rect takes (x1, y1, x2, y2) - lower-left and upper-right corners
-        layer.set_fill_color(Color::Rgb(MILD_YELLOW));
+        layer.set_fill_color(Color::Rgb(self.theme.lead_box.clone()));
         layer.add_rect(
             Mm(box_x),
             Mm(box_bottom),
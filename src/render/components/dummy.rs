@@ -214,8 +214,8 @@ impl<'a> DummyRenderer<'a> {
                 continue;
             }
 
-            // Get ranks sorted high to low (BTreeSet already gives this order)
-            let ranks: Vec<Rank> = holding.ranks.to_vec();
+            // Ranks high to low, with low cards standing in for spot cards
+            let ranks: Vec<Rank> = holding.card_ranks(&[]);
 
             // Render cards from top (highest rank) to bottom (lowest rank)
             // so that lower cards render on top and naturally cover the cards above.
@@ -266,6 +266,7 @@ impl<'a> DummyRenderer<'a> {
         let ellipse_radius_x = ellipse_radius_y * ELLIPSE_WIDTH_RATIO; // horizontal radius (30% narrower)
 
         // Draw the ellipse with the specified color
+        layer.begin_answers();
         layer.set_outline_color(printpdf::Color::Rgb(color.clone()));
         layer.set_outline_thickness(1.5);
         layer.add_ellipse(
@@ -275,5 +276,29 @@ impl<'a> DummyRenderer<'a> {
             Mm(ellipse_radius_y),
             PaintMode::Stroke,
         );
+        layer.end_answers();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::deal::parse_hand;
+    use printpdf::{Op, PdfDocument};
+
+    #[test]
+    fn test_spot_cards_are_drawn() {
+        let mut doc = PdfDocument::new("Test");
+        let assets = CardAssets::load(&mut doc).unwrap();
+        let hand = parse_hand("AKxx.Q..").unwrap();
+        let mut layer = LayerBuilder::new();
+        DummyRenderer::new(&assets, 0.5).render(&mut layer, &hand, (Mm(0.0), Mm(100.0)));
+        let cards = layer
+            .into_ops()
+            .iter()
+            .filter(|op| matches!(op, Op::UseXobject { .. }))
+            .count();
+        assert_eq!(cards, hand.card_count());
+        assert_eq!(cards, 5);
     }
 }
@@ -482,8 +482,9 @@ impl<'a> FanRenderer<'a> {
         let mut cards: Vec<(Suit, Rank)> = Vec::new();
         for suit in self.suit_order() {
             let holding = hand.holding(suit);
-            for rank in holding.ranks.iter() {
-                cards.push((suit, *rank));
+            // Low cards stand in for spot cards
+            for rank in holding.card_ranks(&[]) {
+                cards.push((suit, rank));
             }
         }
 
@@ -633,6 +634,7 @@ impl<'a> FanRenderer<'a> {
         };
 
         // Draw the rotated ellipse with the specified color
+        layer.begin_answers();
         layer.set_outline_color(printpdf::Color::Rgb(color.clone()));
         layer.set_outline_thickness(1.5);
         layer.add_rotated_ellipse(
@@ -643,5 +645,29 @@ impl<'a> FanRenderer<'a> {
             rotation,
             PaintMode::Stroke,
         );
+        layer.end_answers();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::deal::parse_hand;
+    use printpdf::{Op, PdfDocument};
+
+    #[test]
+    fn test_spot_cards_are_drawn() {
+        let mut doc = PdfDocument::new("Test");
+        let assets = CardAssets::load(&mut doc).unwrap();
+        let hand = parse_hand("AKxx.Q..").unwrap();
+        let mut layer = LayerBuilder::new();
+        FanRenderer::new(&assets, 0.5).render(&mut layer, &hand, (Mm(0.0), Mm(100.0)));
+        let cards = layer
+            .into_ops()
+            .iter()
+            .filter(|op| matches!(op, Op::UseXobject { .. }))
+            .count();
+        assert_eq!(cards, hand.card_count());
+        assert_eq!(cards, 5);
     }
 }
@@ -1,25 +1,35 @@
+use crate::cli::HiddenHandStyle;
 use crate::config::Settings;
 use crate::model::card::RankExt;
-use crate::model::{Deal, Direction, Hand, HiddenHands, Suit, SUITS_DISPLAY_ORDER};
-use printpdf::{BuiltinFont, Color, FontId, Mm, PaintMode, Rgb};
+use crate::model::{
+    Card, Deal, Direction, DoubleDummyTricks, Hand, HiddenHands, Strain, Suit, Vulnerability,
+    SUITS_DISPLAY_ORDER,
+};
+use printpdf::{BuiltinFont, Color, CurTransMat, FontId, Mm, PaintMode, XObjectId};
 
-use crate::render::helpers::colors::{self, SuitColors};
+use crate::render::helpers::card_assets::{card_transform, CARD_HEIGHT_MM, CARD_WIDTH_MM};
+use crate::render::helpers::colors::{self, SuitColors, Theme};
+use crate::render::helpers::fonts::TextFont;
 use crate::render::helpers::layer::LayerBuilder;
-use crate::render::helpers::text_metrics;
-
-/// Light gray color for debug boxes
-const DEBUG_BOX_COLOR: Rgb = Rgb {
-    r: 0.7,
-    g: 0.7,
-    b: 0.7,
-    icc_profile: None,
-};
+use crate::render::helpers::text_metrics::{self, TextMeasure};
+
+/// Width of a hidden hand's placeholder or card backs, relative to the hand width
+const HIDDEN_HAND_WIDTH_RATIO: f32 = 0.75;
+
+/// Size of the double-dummy table text and rows, relative to the cards
+const DD_TABLE_SCALE: f32 = 0.7;
+
+/// Gap between the compass and a rotated East/West hand
+const ROTATED_HAND_GAP: f32 = 2.0;
+
+const MM_TO_PT: f32 = 2.834_645_7;
 
 /// Display options for diagram rendering, computed by the layout layer
 /// This centralizes all visibility decisions in one place
 #[derive(Debug, Clone, Default)]
 pub struct DiagramDisplayOptions {
-    /// Which hands are hidden (from [Hidden] PBN tag)
+    /// Which hands are hidden (from [Hidden] PBN tag, and hands the deal
+    /// leaves out)
     pub hidden: HiddenHands,
     /// Hide the compass box (implied when only one hand is visible)
     pub hide_compass: bool,
@@ -31,14 +41,42 @@ pub struct DiagramDisplayOptions {
     pub suits_present: Vec<Suit>,
     /// Whether to show suit symbols (false for single-suit fragments)
     pub show_suit_symbols: bool,
+    /// How hidden hands are drawn
+    pub hidden_style: HiddenHandStyle,
+    /// Print each visible hand's HCP in the compass corners
+    pub show_hand_hcp: bool,
+    /// Add length points to the per-hand HCP ("12+1")
+    pub show_distribution_points: bool,
+    /// Board number printed in the middle of the compass
+    pub compass_board: Option<String>,
+    /// Dealer whose compass letter is underlined
+    pub compass_dealer: Option<Direction>,
+    /// Shade the compass arms by vulnerability
+    pub compass_vulnerability: Option<Vulnerability>,
+    /// Opening leader and lead card to underline in the leader's hand
+    pub lead: Option<(Direction, Card)>,
+    /// Draw East and West rotated so their suits run vertically (full deals only)
+    pub rotate_east_west: bool,
+    /// Double-dummy makeable tricks, shown in the corner below East
+    pub double_dummy: Option<DoubleDummyTricks>,
 }
 
 impl DiagramDisplayOptions {
     /// Compute display options from a deal and hidden hands
-    /// This applies the implied hiding rules documented in docs/BCFlags.md
+    /// This applies the implied hiding rules documented in docs/BCFlags.md.
+    /// Hands the deal leaves out ("-") are hidden too, so a deal giving only
+    /// a pair's hands draws just those two.
     pub fn from_deal(deal: &Deal, hidden: &HiddenHands) -> Self {
         let suits_present = deal.suits_present();
         let is_fragment = suits_present.len() < 4;
+        let absent =
+            |direction: Direction| !deal.is_empty() && deal.hand(direction).card_count() == 0;
+        let hidden = &HiddenHands {
+            north: hidden.north || absent(Direction::North),
+            east: hidden.east || absent(Direction::East),
+            south: hidden.south || absent(Direction::South),
+            west: hidden.west || absent(Direction::West),
+        };
 
         // Determine which hands are visible
         let visible: Vec<Direction> = [
@@ -68,51 +106,150 @@ impl DiagramDisplayOptions {
             is_fragment,
             suits_present,
             show_suit_symbols,
+            hidden_style: HiddenHandStyle::Omit,
+            show_hand_hcp: false,
+            show_distribution_points: false,
+            compass_board: None,
+            compass_dealer: None,
+            compass_vulnerability: None,
+            lead: None,
+            rotate_east_west: false,
+            double_dummy: None,
         }
     }
+
+    /// Underline the opening lead in the leader's hand
+    pub fn with_lead(mut self, leader: Direction, card: Card) -> Self {
+        self.lead = Some((leader, card));
+        self
+    }
+
+    /// Show a double-dummy makeable tricks table in the free corner below East
+    pub fn with_double_dummy(mut self, double_dummy: Option<DoubleDummyTricks>) -> Self {
+        self.double_dummy = double_dummy;
+        self
+    }
+
+    /// Draw East and West rotated a quarter turn, newspaper style
+    pub fn with_rotated_east_west(mut self, rotate: bool) -> Self {
+        self.rotate_east_west = rotate;
+        self
+    }
+
+    /// Shade the compass arms red (vulnerable) or white (not vulnerable)
+    pub fn with_vulnerability(mut self, vulnerability: Vulnerability) -> Self {
+        self.compass_vulnerability = Some(vulnerability);
+        self
+    }
+
+    /// Print the board number in the compass and underline the dealer's letter,
+    /// like the face of a duplicate board
+    pub fn with_board_in_compass(
+        mut self,
+        board: Option<String>,
+        dealer: Option<Direction>,
+    ) -> Self {
+        self.compass_board = board;
+        self.compass_dealer = dealer;
+        self
+    }
+
+    /// Print each visible hand's HCP (optionally with length points) by the compass
+    pub fn with_hand_hcp(mut self, show: bool, distribution_points: bool) -> Self {
+        self.show_hand_hcp = show;
+        self.show_distribution_points = show && distribution_points;
+        self
+    }
+
+    /// Whether a hand leaves its place in the diagram empty: hidden, with
+    /// hidden hands omitted
+    pub fn omits(&self, direction: Direction) -> bool {
+        self.hidden.is_hidden(direction) && self.hidden_style == HiddenHandStyle::Omit
+    }
+
+    /// Draw hidden hands in the given style.
+    ///
+    /// Unless hidden hands are omitted, the compass is always shown so the
+    /// diagram keeps the table geometry even with a single visible hand.
+    pub fn with_hidden_style(mut self, style: HiddenHandStyle) -> Self {
+        self.hidden_style = style;
+        if style != HiddenHandStyle::Omit {
+            self.hide_compass = false;
+            self.single_visible_hand = None;
+        }
+        self
+    }
 }
 
 /// Renderer for hand diagrams
 pub struct HandDiagramRenderer<'a> {
-    font: BuiltinFont,
-    bold_font: BuiltinFont,
+    font: TextFont,
+    bold_font: TextFont,
     compass_font: BuiltinFont,
     symbol_font: &'a FontId, // Font with Unicode suit symbols (DejaVu Sans)
     colors: SuitColors,
+    theme: Theme,
     settings: &'a Settings,
+    /// Card back image for hidden hands (see `HiddenHandStyle::CardBacks`)
+    card_back: Option<&'a XObjectId>,
     debug_boxes: bool,
 }
 
 impl<'a> HandDiagramRenderer<'a> {
     pub fn new(
-        font: BuiltinFont,
-        bold_font: BuiltinFont,
+        font: impl Into<TextFont>,
+        bold_font: impl Into<TextFont>,
         compass_font: BuiltinFont,
         symbol_font: &'a FontId,
         settings: &'a Settings,
     ) -> Self {
         Self {
-            font,
-            bold_font,
+            font: font.into(),
+            bold_font: bold_font.into(),
             compass_font,
             symbol_font,
-            colors: SuitColors::new(settings.black_color, settings.red_color),
+            colors: SuitColors::from_settings(settings),
+            theme: Theme::from_settings(settings),
             settings,
+            card_back: None,
             debug_boxes: false, // Disable debug boxes for production
         }
     }
 
+    /// Set the card back image used for hidden hands
+    pub fn card_back(mut self, card_back: Option<&'a XObjectId>) -> Self {
+        self.card_back = card_back;
+        self
+    }
+
     /// Draw a debug outline box
     fn draw_debug_box(&self, layer: &mut LayerBuilder, x: f32, y: f32, w: f32, h: f32) {
         if !self.debug_boxes {
             return;
         }
         // y is top of box, draw from bottom-left to top-right
-        layer.set_outline_color(Color::Rgb(DEBUG_BOX_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.debug_box.clone()));
         layer.set_outline_thickness(0.25);
         layer.add_rect(Mm(x), Mm(y - h), Mm(x + w), Mm(y), PaintMode::Stroke);
     }
 
+    /// Measurer for card text: an embedded diagram font's own metrics, otherwise
+    /// the Times metrics the diagram layout is tuned to
+    fn card_measurer(&self) -> &dyn TextMeasure {
+        self.font.measurer_or(text_metrics::get_times_measurer())
+    }
+
+    /// Width of a suit line: the suit symbol (drawn in the symbol font), a space and the cards
+    fn suit_line_width(&self, suit: Suit, cards_str: &str) -> f32 {
+        let font_size = self.settings.card_font_size;
+        let symbol_width = text_metrics::get_times_measurer()
+            .measure_width_mm(&suit.symbol().to_string(), font_size);
+        symbol_width
+            + self
+                .card_measurer()
+                .measure_text(&format!(" {}", cards_str), font_size)
+    }
+
     /// Calculate the actual height of a hand block based on font metrics
     fn actual_hand_height(&self) -> f32 {
         self.hand_height_for_suits(4)
@@ -120,7 +257,7 @@ impl<'a> HandDiagramRenderer<'a> {
 
     /// Calculate hand height for a specific number of suits
     fn hand_height_for_suits(&self, num_suits: usize) -> f32 {
-        let measurer = text_metrics::get_times_measurer();
+        let measurer = self.card_measurer();
         let line_height = self.settings.line_height;
         let cap_height = measurer.cap_height_mm(self.settings.card_font_size);
         let descender = measurer.descender_mm(self.settings.card_font_size);
@@ -135,9 +272,6 @@ impl<'a> HandDiagramRenderer<'a> {
 
     /// Calculate the actual width of a hand by measuring all suit lines
     fn actual_hand_width(&self, hand: &Hand) -> f32 {
-        let measurer = text_metrics::get_times_measurer();
-        let font_size = self.settings.card_font_size;
-
         SUITS_DISPLAY_ORDER
             .iter()
             .map(|suit| {
@@ -146,15 +280,12 @@ impl<'a> HandDiagramRenderer<'a> {
                     "-".to_string()
                 } else {
                     holding
-                        .ranks
-                        .iter()
-                        .map(|r| r.display_str().to_string())
+                        .card_texts(self.settings.ten_as_t)
                         .collect::<Vec<_>>()
                         .join(" ")
                 };
                 // Full line: "♠ A K Q J T 9 8 7 6 5" (symbol + space + spaced cards)
-                let line = format!("{} {}", suit.symbol(), cards_str);
-                measurer.measure_width_mm(&line, font_size)
+                self.suit_line_width(*suit, &cards_str)
             })
             .fold(0.0_f32, |max, w| max.max(w))
     }
@@ -181,7 +312,53 @@ impl<'a> HandDiagramRenderer<'a> {
         // = oy.0 - (north_y - hand_h - hand_h - 2.0 - hand_h)
         // = oy.0 - oy.0 + 3*hand_h + 2.0
         // = 3*hand_h + 2.0
-        3.0 * hand_h + 2.0
+        // (the middle row is taller than hand_h when East/West are rotated,
+        // and the North and South rows are left out when empty)
+        let south_row = if self.has_south_row(options) {
+            hand_h + 2.0
+        } else {
+            0.0
+        };
+        self.north_row_height(options, hand_h) + self.middle_row_height(options) + south_row
+    }
+
+    /// Height of the North row: none when North is left out (East-West only)
+    fn north_row_height(&self, options: &DiagramDisplayOptions, hand_h: f32) -> f32 {
+        if options.omits(Direction::North) {
+            0.0
+        } else {
+            hand_h
+        }
+    }
+
+    /// Whether a full deal has a South row: South, the HCP box or the
+    /// double-dummy table
+    fn has_south_row(&self, options: &DiagramDisplayOptions) -> bool {
+        !options.omits(Direction::South) || self.settings.show_hcp || options.double_dummy.is_some()
+    }
+
+    /// Height of the West | Compass | East row of a full deal.
+    /// Rotated hands get the full hand width, like North and South do across.
+    fn middle_row_height(&self, options: &DiagramDisplayOptions) -> f32 {
+        if options.rotate_east_west {
+            self.settings.hand_width.max(self.compass_box_size())
+        } else {
+            self.actual_hand_height()
+        }
+    }
+
+    /// Vertical extent of a rotated East/West hand (its unrotated width)
+    fn rotated_hand_length(
+        &self,
+        hand: &Hand,
+        hidden: bool,
+        options: &DiagramDisplayOptions,
+    ) -> f32 {
+        match (hidden, options.hidden_style) {
+            (false, _) => self.actual_hand_width(hand),
+            (true, HiddenHandStyle::Omit) => 0.0,
+            (true, _) => self.hidden_hand_width(),
+        }
     }
 
     /// Measure the height of a fragment deal diagram
@@ -207,7 +384,13 @@ impl<'a> HandDiagramRenderer<'a> {
         // south_y = west_y - hand_h - compass_center_offset - compass_hand_gap
         // height = oy.0 - (south_y - hand_h)
         // = 3*hand_h + 2*compass_center_offset + compass_hand_gap
-        3.0 * hand_h + 2.0 * compass_center_offset + compass_hand_gap
+        // (less the North and South rows when they're left out)
+        let south_row = if options.omits(Direction::South) {
+            0.0
+        } else {
+            compass_hand_gap + hand_h
+        };
+        self.north_row_height(options, hand_h) + hand_h + 2.0 * compass_center_offset + south_row
     }
 
     /// Render a complete deal with compass rose - Bridge Composer style
@@ -272,25 +455,49 @@ impl<'a> HandDiagramRenderer<'a> {
         if !options.hidden.north {
             self.draw_debug_box(layer, north_x, north_y, north_w, hand_h);
             self.render_hand_cards(layer, &deal.north, (Mm(north_x), Mm(north_y)));
+            self.render_lead_marker(
+                layer,
+                &deal.north,
+                Direction::North,
+                (Mm(north_x), Mm(north_y)),
+                options,
+            );
+        } else {
+            self.render_hidden_hand(layer, &deal.north, (north_x, north_y), hand_h, options);
         }
 
         // Row 2: West hand | Compass | East hand (immediately below North)
-        let row2_y = north_y - hand_h; // No extra gap
-
-        // West hand - left side
-        let west_x = ox.0;
-        if !options.hidden.west {
-            self.draw_debug_box(layer, west_x, row2_y, west_w, hand_h);
-            self.render_hand_cards(layer, &deal.west, (Mm(west_x), Mm(row2_y)));
-        }
+        let row2_y = north_y - self.north_row_height(options, hand_h); // No extra gap
+        let row2_h = self.middle_row_height(options);
 
         // Compass rose - vertically centered with West/East hands
         // Left edge of compass aligns with right edge of suit symbols (suit symbols are ~5mm wide)
         let suit_symbol_width = 5.0;
         let half_char_adjust = 1.5; // Fine-tune alignment
         let compass_center_x = north_x + suit_symbol_width + compass_size / 2.0 - half_char_adjust;
-        let compass_y = row2_y - hand_h / 2.0; // Center vertically with West/East
-                                               // Debug box for compass (centered)
+        let compass_y = row2_y - row2_h / 2.0; // Center vertically with West/East
+
+        // West hand - left side
+        let west_x = ox.0;
+        if options.rotate_east_west {
+            let length = self.rotated_hand_length(&deal.west, options.hidden.west, options);
+            let x = compass_center_x - compass_size / 2.0 - ROTATED_HAND_GAP - hand_h;
+            let origin = (x, compass_y + length / 2.0);
+            self.render_rotated_hand(layer, &deal.west, Direction::West, origin, options);
+        } else if !options.hidden.west {
+            self.draw_debug_box(layer, west_x, row2_y, west_w, hand_h);
+            self.render_hand_cards(layer, &deal.west, (Mm(west_x), Mm(row2_y)));
+            self.render_lead_marker(
+                layer,
+                &deal.west,
+                Direction::West,
+                (Mm(west_x), Mm(row2_y)),
+                options,
+            );
+        } else {
+            self.render_hidden_hand(layer, &deal.west, (west_x, row2_y), hand_h, options);
+        }
+        // Debug box for compass (centered)
         self.draw_debug_box(
             layer,
             compass_center_x - compass_size / 2.0,
@@ -298,19 +505,34 @@ impl<'a> HandDiagramRenderer<'a> {
             compass_size,
             compass_size,
         );
-        self.render_compass(layer, (Mm(compass_center_x), Mm(compass_y)));
+        self.render_compass(layer, (Mm(compass_center_x), Mm(compass_y)), options);
+        self.render_hand_hcp(layer, deal, (Mm(compass_center_x), Mm(compass_y)), options);
 
         // East hand - to the right of compass
         let east_x = compass_center_x + compass_size / 2.0 + 3.5;
-        if !options.hidden.east {
+        if options.rotate_east_west {
+            let length = self.rotated_hand_length(&deal.east, options.hidden.east, options);
+            let x = compass_center_x + compass_size / 2.0 + ROTATED_HAND_GAP;
+            let origin = (x, compass_y + length / 2.0);
+            self.render_rotated_hand(layer, &deal.east, Direction::East, origin, options);
+        } else if !options.hidden.east {
             self.draw_debug_box(layer, east_x, row2_y, east_w, hand_h);
             self.render_hand_cards(layer, &deal.east, (Mm(east_x), Mm(row2_y)));
+            self.render_lead_marker(
+                layer,
+                &deal.east,
+                Direction::East,
+                (Mm(east_x), Mm(row2_y)),
+                options,
+            );
+        } else {
+            self.render_hidden_hand(layer, &deal.east, (east_x, row2_y), hand_h, options);
         }
 
         // Row 3: HCP box (below West) and South hand (next to HCP box)
         let hcp_box_size = compass_size;
         let hcp_box_x = west_x;
-        let hcp_box_y = row2_y - hand_h - 2.0; // Small gap below West hand
+        let hcp_box_y = row2_y - row2_h - 2.0; // Small gap below West hand
 
         if self.settings.show_hcp {
             self.render_hcp_box(layer, deal, (Mm(hcp_box_x), Mm(hcp_box_y)), hcp_box_size);
@@ -321,10 +543,28 @@ impl<'a> HandDiagramRenderer<'a> {
         if !options.hidden.south {
             self.draw_debug_box(layer, north_x, south_y, south_w, hand_h);
             self.render_hand_cards(layer, &deal.south, (Mm(north_x), Mm(south_y)));
+            self.render_lead_marker(
+                layer,
+                &deal.south,
+                Direction::South,
+                (Mm(north_x), Mm(south_y)),
+                options,
+            );
+        } else {
+            self.render_hidden_hand(layer, &deal.south, (north_x, south_y), hand_h, options);
+        }
+
+        // Double-dummy table in the free corner: below East, level with South
+        if let Some(dd) = &options.double_dummy {
+            self.render_dd_table(layer, dd, (east_x, south_y));
         }
 
         // Return total height used
-        oy.0 - (south_y - hand_h)
+        if self.has_south_row(options) {
+            oy.0 - (south_y - hand_h)
+        } else {
+            oy.0 - (row2_y - row2_h)
+        }
     }
 
     /// Render a deal fragment with pre-computed display options
@@ -388,10 +628,12 @@ impl<'a> HandDiagramRenderer<'a> {
                 suits_present,
                 show_suit_symbol,
             );
+        } else {
+            self.render_hidden_hand(layer, &deal.north, (north_x, north_y), hand_h, options);
         }
 
         // Row 2: West hand | Compass | East hand
-        let row2_y = north_y - hand_h;
+        let row2_y = north_y - self.north_row_height(options, hand_h);
 
         // Compass positioning
         let compass_y = row2_y - hand_h / 2.0 - compass_center_offset;
@@ -413,6 +655,9 @@ impl<'a> HandDiagramRenderer<'a> {
                 suits_present,
                 show_suit_symbol,
             );
+        } else {
+            let hidden_x = compass_left - hand_compass_gap - self.hidden_hand_width();
+            self.render_hidden_hand(layer, &deal.west, (hidden_x, west_y), hand_h, options);
         }
 
         // Render compass
@@ -423,7 +668,8 @@ impl<'a> HandDiagramRenderer<'a> {
             compass_size,
             compass_size,
         );
-        self.render_compass(layer, (Mm(compass_center_x), Mm(compass_y)));
+        self.render_compass(layer, (Mm(compass_center_x), Mm(compass_y)), options);
+        self.render_hand_hcp(layer, deal, (Mm(compass_center_x), Mm(compass_y)), options);
 
         // East hand - left edge near compass right edge
         let east_x = compass_right + hand_compass_gap;
@@ -436,6 +682,8 @@ impl<'a> HandDiagramRenderer<'a> {
                 suits_present,
                 show_suit_symbol,
             );
+        } else {
+            self.render_hidden_hand(layer, &deal.east, (east_x, west_y), hand_h, options);
         }
 
         // Row 3: South hand (below compass, centered)
@@ -456,10 +704,16 @@ impl<'a> HandDiagramRenderer<'a> {
                 suits_present,
                 show_suit_symbol,
             );
+        } else {
+            self.render_hidden_hand(layer, &deal.south, (south_x, south_y), hand_h, options);
         }
 
         // Return total height used
-        oy.0 - (south_y - hand_h)
+        if options.omits(Direction::South) {
+            oy.0 - (west_y - hand_h - compass_center_offset)
+        } else {
+            oy.0 - (south_y - hand_h)
+        }
     }
 
     /// Calculate the width of a hand for fragment display
@@ -469,7 +723,7 @@ impl<'a> HandDiagramRenderer<'a> {
         suits_present: &[Suit],
         show_suit_symbol: bool,
     ) -> f32 {
-        let measurer = text_metrics::get_times_measurer();
+        let measurer = self.card_measurer();
         let font_size = self.settings.card_font_size;
 
         suits_present
@@ -480,17 +734,14 @@ impl<'a> HandDiagramRenderer<'a> {
                     "-".to_string()
                 } else {
                     holding
-                        .ranks
-                        .iter()
-                        .map(|r| r.display_str().to_string())
+                        .card_texts(self.settings.ten_as_t)
                         .collect::<Vec<_>>()
                         .join(" ")
                 };
                 if show_suit_symbol {
-                    let line = format!("{} {}", suit.symbol(), cards_str);
-                    measurer.measure_width_mm(&line, font_size)
+                    self.suit_line_width(*suit, &cards_str)
                 } else {
-                    measurer.measure_width_mm(&cards_str, font_size)
+                    measurer.measure_text(&cards_str, font_size)
                 }
             })
             .fold(0.0_f32, |max, w| max.max(w))
@@ -508,7 +759,7 @@ impl<'a> HandDiagramRenderer<'a> {
         let (ox, oy) = origin;
         let line_height = self.settings.line_height;
 
-        let measurer = text_metrics::get_times_measurer();
+        let measurer = self.card_measurer();
         let cap_height = measurer.cap_height_mm(self.settings.card_font_size);
 
         let first_baseline = oy.0 - cap_height;
@@ -538,14 +789,12 @@ impl<'a> HandDiagramRenderer<'a> {
             "-".to_string()
         } else {
             holding
-                .ranks
-                .iter()
-                .map(|r| r.display_str().to_string())
+                .card_texts(self.settings.ten_as_t)
                 .collect::<Vec<_>>()
                 .join(" ")
         };
 
-        layer.use_text_builtin(&cards_str, self.settings.card_font_size, ox, oy, self.font);
+        layer.use_text_font(&cards_str, self.settings.card_font_size, ox, oy, &self.font);
     }
 
     /// Render a single hand without compass (when only one hand is visible)
@@ -578,6 +827,9 @@ impl<'a> HandDiagramRenderer<'a> {
 
         self.draw_debug_box(layer, hand_x, oy.0, hand_width, hand_h);
         self.render_hand_cards(layer, hand, (Mm(hand_x), oy));
+        if let Some(direction) = options.single_visible_hand {
+            self.render_lead_marker(layer, hand, direction, (Mm(hand_x), oy), options);
+        }
 
         // Return just the height used - layout handles spacing
         hand_h
@@ -634,6 +886,98 @@ impl<'a> HandDiagramRenderer<'a> {
         hand_h
     }
 
+    /// Width of a hidden hand's placeholder or card backs
+    fn hidden_hand_width(&self) -> f32 {
+        self.settings.hand_width * HIDDEN_HAND_WIDTH_RATIO
+    }
+
+    /// Draw a hidden hand as a shaded box or face-down cards.
+    /// Origin is the top-left of the hand's bounding box.
+    fn render_hidden_hand(
+        &self,
+        layer: &mut LayerBuilder,
+        hand: &Hand,
+        origin: (f32, f32),
+        hand_h: f32,
+        options: &DiagramDisplayOptions,
+    ) {
+        let (x, y) = origin;
+        let width = self.hidden_hand_width();
+
+        match (options.hidden_style, self.card_back) {
+            (HiddenHandStyle::Omit, _) => {}
+            (HiddenHandStyle::CardBacks, Some(card_back)) => {
+                // Overlapping row of backs, one per card (a full hand if the deal omits it)
+                let count = match hand.card_count() {
+                    0 => 13,
+                    n => n,
+                };
+                let scale = hand_h / CARD_HEIGHT_MM;
+                let card_w = CARD_WIDTH_MM * scale;
+                let step = if count > 1 {
+                    ((width - card_w) / (count - 1) as f32).max(0.0)
+                } else {
+                    0.0
+                };
+                for i in 0..count {
+                    let card_x = x + i as f32 * step;
+                    layer.use_xobject(
+                        card_back.clone(),
+                        card_transform(card_x, y - hand_h, scale, 0.0),
+                    );
+                }
+            }
+            // Placeholder, or card backs requested without a back image
+            _ => {
+                layer.set_fill_color(Color::Rgb(colors::LIGHT_GRAY));
+                layer.add_rect(Mm(x), Mm(y - hand_h), Mm(x + width), Mm(y), PaintMode::Fill);
+                layer.set_fill_color(Color::Rgb(colors::BLACK));
+            }
+        }
+    }
+
+    /// Draw West or East turned a quarter turn, suits running vertically with
+    /// spades next to the compass. West reads top to bottom, East bottom to top.
+    /// Origin is the top-left of the rotated hand's bounding box.
+    fn render_rotated_hand(
+        &self,
+        layer: &mut LayerBuilder,
+        hand: &Hand,
+        direction: Direction,
+        origin: (f32, f32),
+        options: &DiagramDisplayOptions,
+    ) {
+        let (x, y) = origin;
+        let hand_h = self.actual_hand_height();
+        let hidden = match direction {
+            Direction::West => options.hidden.west,
+            _ => options.hidden.east,
+        };
+        let length = self.rotated_hand_length(hand, hidden, options);
+        self.draw_debug_box(layer, x, y, hand_h, length);
+
+        // The hand is drawn at (0, 0) and mapped onto the box:
+        //   West (clockwise):        x' = y + right,   y' = top - x
+        //   East (counter-clockwise): x' = left - y,   y' = x + bottom
+        let (a, b, c, d, e, f) = match direction {
+            Direction::West => (0.0, -1.0, 1.0, 0.0, x + hand_h, y),
+            _ => (0.0, 1.0, -1.0, 0.0, x, y - length),
+        };
+        let anchors_start = layer.card_anchor_count();
+        layer.save_graphics_state();
+        layer.set_transform(CurTransMat::Raw([a, b, c, d, e * MM_TO_PT, f * MM_TO_PT]));
+        if hidden {
+            self.render_hidden_hand(layer, hand, (0.0, 0.0), hand_h, options);
+        } else {
+            self.render_hand_cards(layer, hand, (Mm(0.0), Mm(0.0)));
+            self.render_lead_marker(layer, hand, direction, (Mm(0.0), Mm(0.0)), options);
+        }
+        layer.restore_graphics_state();
+        layer.map_card_anchors_from(anchors_start, |ax, ay| {
+            (a * ax + c * ay + e, b * ax + d * ay + f)
+        });
+    }
+
     /// Render a single hand (used for backward compatibility)
     pub fn render_hand(
         &self,
@@ -652,7 +996,7 @@ impl<'a> HandDiagramRenderer<'a> {
         let line_height = self.settings.line_height;
 
         // Use actual font metrics to get cap-height
-        let measurer = text_metrics::get_times_measurer();
+        let measurer = self.card_measurer();
         let cap_height = measurer.cap_height_mm(self.settings.card_font_size);
 
         // First baseline is below the top by cap-height
@@ -697,22 +1041,145 @@ impl<'a> HandDiagramRenderer<'a> {
             "-".to_string()
         } else {
             holding
-                .ranks
-                .iter()
-                .map(|r| r.display_str().to_string())
+                .card_texts(self.settings.ten_as_t)
                 .collect::<Vec<_>>()
                 .join(" ")
         };
 
         // Offset for cards (after suit symbol)
         let cards_x = Mm(ox.0 + 5.0);
-        layer.use_text_builtin(
+        layer.use_text_font(
             &cards_str,
             self.settings.card_font_size,
             cards_x,
             oy,
-            self.font,
+            &self.font,
         );
+
+        // Record card positions so commentary card references can link to them
+        if self.settings.link_card_refs {
+            let measurer = self.font.measurer();
+            let font_size = self.settings.card_font_size;
+            let top_y = oy.0 + measurer.cap_height_mm(font_size);
+            let mut x = cards_x.0;
+            for rank in &holding.ranks {
+                layer.add_card_anchor(suit, *rank, x, top_y);
+                x += measurer.measure_text(
+                    &format!("{} ", rank.display_str_with(self.settings.ten_as_t)),
+                    font_size,
+                );
+            }
+        }
+    }
+
+    /// Underline the opening lead in a full hand drawn by `render_hand_cards`.
+    /// Drawn on the answers layer, like the "Lead:" line.
+    fn render_lead_marker(
+        &self,
+        layer: &mut LayerBuilder,
+        hand: &Hand,
+        direction: Direction,
+        origin: (Mm, Mm),
+        options: &DiagramDisplayOptions,
+    ) {
+        let Some((leader, card)) = options.lead else {
+            return;
+        };
+        let holding = hand.holding(card.suit);
+        if leader != direction || !holding.ranks.contains(&card.rank) {
+            return;
+        }
+
+        let (ox, oy) = origin;
+        let font_size = self.settings.card_font_size;
+        let measurer = self.font.measurer();
+        let cap_height = self.card_measurer().cap_height_mm(font_size);
+        let line = SUITS_DISPLAY_ORDER
+            .iter()
+            .position(|s| *s == card.suit)
+            .unwrap_or(0);
+        let baseline = oy.0 - cap_height - line as f32 * self.settings.line_height;
+
+        // Same card positions as the card anchors in render_suit_line
+        let mut x = ox.0 + 5.0;
+        for rank in holding.ranks.iter().take_while(|r| **r != card.rank) {
+            x += measurer.measure_text(
+                &format!("{} ", rank.display_str_with(self.settings.ten_as_t)),
+                font_size,
+            );
+        }
+        let width = measurer.measure_text(
+            card.rank.display_str_with(self.settings.ten_as_t),
+            font_size,
+        );
+        let y = baseline - cap_height * 0.25;
+
+        layer.begin_answers();
+        layer.set_outline_color(Color::Rgb(colors::BLACK));
+        layer.set_outline_thickness(1.0);
+        layer.add_line(Mm(x), Mm(y), Mm(x + width), Mm(y));
+        layer.end_answers();
+    }
+
+    /// Compact double-dummy table: declarers down, strains (NT ♠ ♥ ♦ ♣) across.
+    /// Set smaller than the cards so all five rows fit in one hand's height.
+    /// Origin is the top-left of the table.
+    fn render_dd_table(
+        &self,
+        layer: &mut LayerBuilder,
+        dd: &DoubleDummyTricks,
+        origin: (f32, f32),
+    ) {
+        let (ox, oy) = origin;
+        let font_size = self.settings.card_font_size * DD_TABLE_SCALE;
+        let row_height = self.settings.line_height * DD_TABLE_SCALE;
+        let measurer = self.card_measurer();
+        let cap_height = measurer.cap_height_mm(font_size);
+        let label_width = measurer.measure_text("W ", font_size);
+        let column_width = measurer.measure_text("NT ", font_size);
+        let column_x = |i: usize| ox + label_width + i as f32 * column_width;
+
+        // Header: strain symbols in suit colors
+        let header_y = oy - cap_height;
+        for (i, strain) in DoubleDummyTricks::STRAINS.iter().enumerate() {
+            layer.set_fill_color(Color::Rgb(self.colors.for_bid_suit(strain)));
+            let symbol = match strain {
+                Strain::Spades => "♠",
+                Strain::Hearts => "♥",
+                Strain::Diamonds => "♦",
+                Strain::Clubs => "♣",
+                Strain::NoTrump => "NT",
+            };
+            if *strain == Strain::NoTrump {
+                layer.use_text_font(symbol, font_size, Mm(column_x(i)), Mm(header_y), &self.font);
+            } else {
+                layer.use_text(
+                    symbol,
+                    font_size,
+                    Mm(column_x(i)),
+                    Mm(header_y),
+                    self.symbol_font,
+                );
+            }
+        }
+
+        // One row per declarer
+        layer.set_fill_color(Color::Rgb(colors::BLACK));
+        let locale = self.settings.locale();
+        for (row, declarer) in DoubleDummyTricks::DECLARERS.iter().enumerate() {
+            let y = header_y - (row + 1) as f32 * row_height;
+            layer.use_text_font(
+                locale.compass_letter(*declarer),
+                font_size,
+                Mm(ox),
+                Mm(y),
+                &self.font,
+            );
+            for (i, strain) in DoubleDummyTricks::STRAINS.iter().enumerate() {
+                let tricks = dd.tricks(*declarer, *strain).to_string();
+                layer.use_text_font(tricks, font_size, Mm(column_x(i)), Mm(y), &self.font);
+            }
+        }
     }
 
     /// Calculate compass box size based on font metrics
@@ -736,7 +1203,12 @@ impl<'a> HandDiagramRenderer<'a> {
     }
 
     /// Render compass rose with green filled box and white letters
-    fn render_compass(&self, layer: &mut LayerBuilder, center: (Mm, Mm)) {
+    fn render_compass(
+        &self,
+        layer: &mut LayerBuilder,
+        center: (Mm, Mm),
+        options: &DiagramDisplayOptions,
+    ) {
         let (cx, cy) = center;
         let measurer = text_metrics::get_times_measurer();
         let font_size = self.settings.compass_font_size;
@@ -746,12 +1218,13 @@ impl<'a> HandDiagramRenderer<'a> {
 
         // Get font metrics for positioning
         let cap_height = measurer.cap_height_mm(font_size);
-        let n_width = measurer.measure_width_mm("N", font_size);
-        let s_width = measurer.measure_width_mm("S", font_size);
-        let e_width = measurer.measure_width_mm("E", font_size);
+        let [north, east, south, west] = self.settings.locale().compass;
+        let n_width = measurer.measure_width_mm(north, font_size);
+        let s_width = measurer.measure_width_mm(south, font_size);
+        let e_width = measurer.measure_width_mm(east, font_size);
 
-        // Draw filled green rectangle
-        layer.set_fill_color(Color::Rgb(colors::GREEN));
+        // Draw filled compass rectangle (green in the classic theme)
+        layer.set_fill_color(Color::Rgb(self.theme.compass.clone()));
         layer.add_rect(
             Mm(cx.0 - half_box),
             Mm(cy.0 - half_box),
@@ -760,14 +1233,61 @@ impl<'a> HandDiagramRenderer<'a> {
             PaintMode::Fill,
         );
 
-        // Draw white letters using compass font size
-        layer.set_fill_color(Color::Rgb(colors::WHITE));
-
         let padding = 1.5;
 
+        // Vulnerability arms: red where vulnerable, white where not, like a
+        // duplicate board. Corners and the middle keep the compass color.
+        if let Some(vulnerability) = options.compass_vulnerability {
+            let letter_size = measurer.measure_width_mm("W", font_size).max(cap_height);
+            let band = padding * 2.0 + letter_size;
+            let (left, right) = (cx.0 - half_box, cx.0 + half_box);
+            let (bottom, top) = (cy.0 - half_box, cy.0 + half_box);
+            for (direction, x1, y1, x2, y2) in [
+                (Direction::North, left + band, top - band, right - band, top),
+                (
+                    Direction::South,
+                    left + band,
+                    bottom,
+                    right - band,
+                    bottom + band,
+                ),
+                (
+                    Direction::West,
+                    left,
+                    bottom + band,
+                    left + band,
+                    top - band,
+                ),
+                (
+                    Direction::East,
+                    right - band,
+                    bottom + band,
+                    right,
+                    top - band,
+                ),
+            ] {
+                let arm = if vulnerability.is_vulnerable(direction) {
+                    self.theme.vulnerable.clone()
+                } else {
+                    colors::WHITE
+                };
+                layer.set_fill_color(Color::Rgb(arm));
+                layer.add_rect(Mm(x1), Mm(y1), Mm(x2), Mm(y2), PaintMode::Fill);
+            }
+        }
+
+        // Letters are white, except on the white arms of a non-vulnerable side
+        let letter_color = |direction: Direction| match options.compass_vulnerability {
+            Some(vulnerability) if !vulnerability.is_vulnerable(direction) => {
+                self.theme.compass.clone()
+            }
+            _ => colors::WHITE,
+        };
+
         // N (top center) - baseline positioned so cap-height reaches near top edge
+        layer.set_fill_color(Color::Rgb(letter_color(Direction::North)));
         layer.use_text_builtin(
-            "N",
+            north,
             font_size,
             Mm(cx.0 - n_width / 2.0),
             Mm(cy.0 + half_box - padding - cap_height),
@@ -775,8 +1295,9 @@ impl<'a> HandDiagramRenderer<'a> {
         );
 
         // S (bottom center) - baseline near bottom edge
+        layer.set_fill_color(Color::Rgb(letter_color(Direction::South)));
         layer.use_text_builtin(
-            "S",
+            south,
             font_size,
             Mm(cx.0 - s_width / 2.0),
             Mm(cy.0 - half_box + padding),
@@ -784,8 +1305,9 @@ impl<'a> HandDiagramRenderer<'a> {
         );
 
         // W (left center) - vertically centered
+        layer.set_fill_color(Color::Rgb(letter_color(Direction::West)));
         layer.use_text_builtin(
-            "W",
+            west,
             font_size,
             Mm(cx.0 - half_box + padding),
             Mm(cy.0 - cap_height / 2.0),
@@ -793,13 +1315,118 @@ impl<'a> HandDiagramRenderer<'a> {
         );
 
         // E (right center) - vertically centered
+        layer.set_fill_color(Color::Rgb(letter_color(Direction::East)));
         layer.use_text_builtin(
-            "E",
+            east,
             font_size,
             Mm(cx.0 + half_box - padding - e_width),
             Mm(cy.0 - cap_height / 2.0),
             self.compass_font,
         );
+
+        // Board number in the middle, shrunk to fit between W and E
+        if let Some(board) = &options.compass_board {
+            layer.set_fill_color(Color::Rgb(colors::WHITE));
+            let inner_width =
+                box_size - 2.0 * (padding + measurer.measure_width_mm("W", font_size));
+            let width = measurer.measure_width_mm(board, font_size);
+            let board_size = if width > inner_width {
+                font_size * inner_width / width
+            } else {
+                font_size
+            };
+            let board_width = measurer.measure_width_mm(board, board_size);
+            let board_cap = measurer.cap_height_mm(board_size);
+            layer.use_text_builtin(
+                board.as_str(),
+                board_size,
+                Mm(cx.0 - board_width / 2.0),
+                Mm(cy.0 - board_cap / 2.0),
+                self.compass_font,
+            );
+        }
+
+        // Underline the dealer's letter
+        if let Some(dealer) = options.compass_dealer {
+            let (letter, left, baseline) = match dealer {
+                Direction::North => (
+                    north,
+                    cx.0 - n_width / 2.0,
+                    cy.0 + half_box - padding - cap_height,
+                ),
+                Direction::South => (south, cx.0 - s_width / 2.0, cy.0 - half_box + padding),
+                Direction::West => (west, cx.0 - half_box + padding, cy.0 - cap_height / 2.0),
+                Direction::East => (
+                    east,
+                    cx.0 + half_box - padding - e_width,
+                    cy.0 - cap_height / 2.0,
+                ),
+            };
+            let width = measurer.measure_width_mm(letter, font_size);
+            let y = baseline - cap_height * 0.2;
+            layer.set_outline_color(Color::Rgb(letter_color(dealer)));
+            layer.set_outline_thickness((font_size * 0.06).max(0.5));
+            layer.add_line(Mm(left), Mm(y), Mm(left + width), Mm(y));
+            layer.set_outline_color(Color::Rgb(colors::BLACK));
+        }
+    }
+
+    /// Label for a hand's points: HCP, plus length points when requested
+    fn hand_points_label(hand: &Hand, distribution_points: bool) -> String {
+        let length = hand.length_points();
+        if distribution_points && length > 0 {
+            format!("{}+{}", hand.total_hcp(), length)
+        } else {
+            hand.total_hcp().to_string()
+        }
+    }
+
+    /// Print each visible hand's points in a compass corner, clockwise after its
+    /// letter: North top-left, East top-right, South bottom-right, West bottom-left
+    fn render_hand_hcp(
+        &self,
+        layer: &mut LayerBuilder,
+        deal: &Deal,
+        center: (Mm, Mm),
+        options: &DiagramDisplayOptions,
+    ) {
+        if !options.show_hand_hcp {
+            return;
+        }
+
+        let (cx, cy) = center;
+        let measurer = text_metrics::get_builtin_measurer(self.compass_font);
+        let font_size = self.settings.compass_font_size * 0.55;
+        let cap_height = measurer.cap_height_mm(font_size);
+        let half_box = self.compass_box_size() / 2.0;
+        let padding = 1.0;
+
+        let left = cx.0 - half_box + padding;
+        let right = cx.0 + half_box - padding;
+        let top = cy.0 + half_box - padding - cap_height;
+        let bottom = cy.0 - half_box + padding;
+
+        layer.set_fill_color(Color::Rgb(colors::WHITE));
+        for (direction, hand, hidden) in [
+            (Direction::North, &deal.north, options.hidden.north),
+            (Direction::East, &deal.east, options.hidden.east),
+            (Direction::South, &deal.south, options.hidden.south),
+            (Direction::West, &deal.west, options.hidden.west),
+        ] {
+            if hidden || hand.card_count() == 0 {
+                continue;
+            }
+            let label = Self::hand_points_label(hand, options.show_distribution_points);
+            let width = measurer.measure_width_mm(&label, font_size);
+            let (x, y) = match direction {
+                Direction::North => (left, top),
+                Direction::East => (right - width, top),
+                Direction::South => (right - width, bottom),
+                Direction::West => (left, bottom),
+            };
+            layer.use_text_builtin(label, font_size, Mm(x), Mm(y), self.compass_font);
+        }
+        layer.set_fill_color(Color::Rgb(colors::BLACK));
     }
 
     /// Render HCP box with all four hands' point counts
@@ -830,49 +1457,367 @@ impl<'a> HandDiagramRenderer<'a> {
         let west_hcp = deal.west.total_hcp();
 
         // Use bold measurer for HCP values
-        let bold_measurer = text_metrics::get_times_bold_measurer();
+        let bold_measurer = self
+            .bold_font
+            .measurer_or(text_metrics::get_times_bold_measurer());
 
         // N (top center)
         let n_text = format!("{}", north_hcp);
-        let n_width = bold_measurer.measure_width_mm(&n_text, font_size);
-        layer.use_text_builtin(
+        let n_width = bold_measurer.measure_text(&n_text, font_size);
+        layer.use_text_font(
             &n_text,
             font_size,
             Mm(center_x - n_width / 2.0),
             Mm(center_y + half_box - 5.0),
-            self.bold_font,
+            &self.bold_font,
         );
 
         // S (bottom center)
         let s_text = format!("{}", south_hcp);
-        let s_width = bold_measurer.measure_width_mm(&s_text, font_size);
-        layer.use_text_builtin(
+        let s_width = bold_measurer.measure_text(&s_text, font_size);
+        layer.use_text_font(
             &s_text,
             font_size,
             Mm(center_x - s_width / 2.0),
             Mm(center_y - half_box + 2.0),
-            self.bold_font,
+            &self.bold_font,
         );
 
         // W (left center)
         let w_text = format!("{}", west_hcp);
-        layer.use_text_builtin(
+        layer.use_text_font(
             &w_text,
             font_size,
             Mm(ox.0 + 2.0),
             Mm(center_y - 1.5),
-            self.bold_font,
+            &self.bold_font,
         );
 
         // E (right center)
         let e_text = format!("{}", east_hcp);
-        let e_width = bold_measurer.measure_width_mm(&e_text, font_size);
-        layer.use_text_builtin(
+        let e_width = bold_measurer.measure_text(&e_text, font_size);
+        layer.use_text_font(
             &e_text,
             font_size,
             Mm(ox.0 + box_size - e_width - 2.0),
             Mm(center_y - 1.5),
-            self.bold_font,
+            &self.bold_font,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Rank;
+    use crate::parser::deal::parse_deal;
+    use printpdf::{Op, Rgb, TextItem};
+
+    const DEAL: &str = "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765";
+
+    fn options(hidden: HiddenHands) -> DiagramDisplayOptions {
+        DiagramDisplayOptions::from_deal(&parse_deal(DEAL).unwrap(), &hidden)
+    }
+
+    /// Ops drawing the test deal with `options`
+    fn draw(options: &DiagramDisplayOptions, card_back: Option<&XObjectId>) -> Vec<Op> {
+        let settings = Settings::default();
+        let symbol_font = FontId::new();
+        let renderer = HandDiagramRenderer::new(
+            BuiltinFont::TimesRoman,
+            BuiltinFont::TimesBold,
+            BuiltinFont::HelveticaBold,
+            &symbol_font,
+            &settings,
+        )
+        .card_back(card_back);
+        let mut layer = LayerBuilder::new();
+        let deal = parse_deal(DEAL).unwrap();
+        renderer.render_deal_with_options(&mut layer, &deal, (Mm(20.0), Mm(200.0)), options);
+        layer.into_ops()
+    }
+
+    /// A piece of text drawn: its font size and position in points
+    #[derive(Debug)]
+    struct Shown {
+        text: String,
+        size: f32,
+        x: f32,
+        y: f32,
+    }
+
+    fn shown_text(ops: &[Op]) -> Vec<Shown> {
+        let (mut size, mut x, mut y) = (0.0, 0.0, 0.0);
+        let mut shown = Vec::new();
+        for op in ops {
+            match op {
+                Op::SetTextCursor { pos } => (x, y) = (pos.x.0, pos.y.0),
+                Op::SetFont { size: pt, .. } => size = pt.0,
+                Op::ShowText { items } => {
+                    for item in items {
+                        if let TextItem::Text(text) = item {
+                            let text = text.clone();
+                            shown.push(Shown { text, size, x, y });
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        shown
+    }
+
+    /// Stroked straight lines as (x1, y1, x2, y2) in points
+    fn stroked_lines(ops: &[Op]) -> Vec<(f32, f32, f32, f32)> {
+        ops.iter()
+            .filter_map(|op| match op {
+                Op::DrawPolygon { polygon } if matches!(polygon.mode, PaintMode::Stroke) => {
+                    match &polygon.rings[..] {
+                        [ring] => match &ring.points[..] {
+                            [a, b] => Some((a.p.x.0, a.p.y.0, b.p.x.0, b.p.y.0)),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Fill color of each filled rectangle, in drawing order
+    fn rect_fills(ops: &[Op]) -> Vec<Rgb> {
+        let mut fill = None;
+        let mut fills = Vec::new();
+        for op in ops {
+            match op {
+                Op::SetFillColor {
+                    col: Color::Rgb(rgb),
+                } => fill = Some(rgb.clone()),
+                Op::DrawPolygon { polygon }
+                    if matches!(polygon.mode, PaintMode::Fill)
+                        && polygon.rings.iter().all(|ring| ring.points.len() == 4) =>
+                {
+                    fills.extend(fill.clone());
+                }
+                _ => {}
+            }
+        }
+        fills
+    }
+
+    #[test]
+    fn test_hidden_hand_card_backs() {
+        let back = XObjectId::new();
+        let east = HiddenHands {
+            east: true,
+            ..Default::default()
+        };
+        let backs = |style| {
+            draw(&options(east).with_hidden_style(style), Some(&back))
+                .iter()
+                .filter(|op| matches!(op, Op::UseXobject { id, .. } if *id == back))
+                .count()
+        };
+        assert_eq!(backs(HiddenHandStyle::CardBacks), 13);
+        assert_eq!(backs(HiddenHandStyle::Placeholder), 0);
+        assert_eq!(backs(HiddenHandStyle::Omit), 0);
+
+        // Without a back image the placeholder box stands in
+        let ops = draw(
+            &options(east).with_hidden_style(HiddenHandStyle::CardBacks),
+            None,
+        );
+        assert!(!ops.iter().any(|op| matches!(op, Op::UseXobject { .. })));
+        assert!(ops.iter().any(|op| matches!(
+            op,
+            Op::SetFillColor { col: Color::Rgb(rgb) } if *rgb == colors::LIGHT_GRAY
+        )));
+    }
+
+    #[test]
+    fn test_hand_hcp_in_compass_corners() {
+        let size = Settings::default().compass_font_size * 0.55;
+        let points = |options: DiagramDisplayOptions| -> Vec<Shown> {
+            let ops = draw(&options, None);
+            shown_text(&ops)
+                .into_iter()
+                .filter(|shown| shown.size == size)
+                .collect()
+        };
+        assert!(points(options(HiddenHands::default())).is_empty());
+
+        let shown = points(options(HiddenHands::default()).with_hand_hcp(true, false));
+        let labels: Vec<&str> = shown.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(labels, ["10", "21", "9", "0"]);
+        // Clockwise after each letter: N top-left, E top-right, S bottom-right,
+        // W bottom-left
+        let [north, east, south, west] = &shown[..] else {
+            unreachable!()
+        };
+        assert!(north.x < east.x && north.y == east.y);
+        assert!(west.x < south.x && west.y == south.y);
+        assert!(north.y > south.y);
+        assert_eq!(north.x, west.x);
+
+        // A hidden hand's points stay secret
+        let east_hidden = HiddenHands {
+            east: true,
+            ..Default::default()
+        };
+        let shown = points(options(east_hidden).with_hand_hcp(true, false));
+        let labels: Vec<&str> = shown.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(labels, ["10", "9", "0"]);
+    }
+
+    #[test]
+    fn test_board_number_in_compass() {
+        let size = Settings::default().compass_font_size;
+        let compass = |board: &str, dealer: Option<Direction>| {
+            let options = options(HiddenHands::default())
+                .with_board_in_compass(Some(board.to_string()), dealer);
+            draw(&options, None)
+        };
+        let find = |shown: &[Shown], text: &str| -> (f32, f32, f32) {
+            let s = shown.iter().find(|s| s.text == text).unwrap();
+            (s.size, s.x, s.y)
+        };
+
+        let ops = compass("12", None);
+        let shown = shown_text(&ops);
+        let (board_size, board_x, board_y) = find(&shown, "12");
+        let (_, north_x, north_y) = find(&shown, "N");
+        let (_, east_x, _) = find(&shown, "E");
+        let (_, south_x, south_y) = find(&shown, "S");
+        let (_, west_x, _) = find(&shown, "W");
+        assert_eq!(board_size, size);
+        assert!(west_x < board_x && board_x < east_x);
+        assert!(south_y < board_y && board_y < north_y);
+        assert!(board_x < north_x);
+
+        // A long board number shrinks to fit between W and E
+        let shown = shown_text(&compass("2024-117", None));
+        let (long_size, long_x, _) = find(&shown, "2024-117");
+        assert!(long_size < size);
+        assert!(west_x < long_x);
+
+        // The dealer's letter is underlined
+        let dealt = compass("12", Some(Direction::South));
+        let undealt = stroked_lines(&ops);
+        let underlines: Vec<_> = stroked_lines(&dealt)
+            .into_iter()
+            .filter(|line| !undealt.contains(line))
+            .collect();
+        let [(x1, y1, x2, y2)] = underlines[..] else {
+            panic!("expected one dealer underline, got {underlines:?}");
+        };
+        assert_eq!(x1, south_x);
+        assert!(x2 > x1 && y1 == y2 && y1 < south_y);
+    }
+
+    #[test]
+    fn test_vulnerable_arms_fill_color() {
+        let theme = Theme::from_settings(&Settings::default());
+        let fills = |options: DiagramDisplayOptions| rect_fills(&draw(&options, None));
+        // Whether each arm (N, S, W, E) is shaded as vulnerable
+        let arms = |vulnerability| -> Vec<bool> {
+            let fills = fills(options(HiddenHands::default()).with_vulnerability(vulnerability));
+            let compass = fills.iter().position(|c| *c == theme.compass).unwrap();
+            fills[compass + 1..compass + 5]
+                .iter()
+                .map(|arm| {
+                    assert!(*arm == theme.vulnerable || *arm == colors::WHITE);
+                    *arm == theme.vulnerable
+                })
+                .collect()
+        };
+        assert_eq!(arms(Vulnerability::None), [false; 4]);
+        assert_eq!(arms(Vulnerability::NorthSouth), [true, true, false, false]);
+        assert_eq!(arms(Vulnerability::EastWest), [false, false, true, true]);
+        assert_eq!(arms(Vulnerability::Both), [true; 4]);
+
+        // Without a vulnerability the compass is one plain box
+        let plain = fills(options(HiddenHands::default()));
+        assert!(plain.contains(&theme.compass));
+        assert!(!plain.contains(&theme.vulnerable));
+    }
+
+    #[test]
+    fn test_lead_underline() {
+        let plain = stroked_lines(&draw(&options(HiddenHands::default()), None));
+        // The lines a lead adds, and the text of the leader's spades
+        let lead = |rank| {
+            let card = Card::new(Suit::Spades, rank);
+            let options = options(HiddenHands::default()).with_lead(Direction::West, card);
+            let ops = draw(&options, None);
+            let underlines: Vec<_> = stroked_lines(&ops)
+                .into_iter()
+                .filter(|line| !plain.contains(line))
+                .collect();
+            let spades = shown_text(&ops).into_iter().find(|s| s.text == "4 3");
+            (underlines, spades.unwrap())
+        };
+
+        let (four, spades) = lead(Rank::Four);
+        let [(x1, y1, x2, y2)] = four[..] else {
+            panic!("expected one lead underline, got {four:?}");
+        };
+        assert_eq!(x1, spades.x);
+        assert!(x2 > x1 && y1 == y2);
+        assert!(y1 < spades.y && spades.y - y1 < spades.size);
+
+        // Lower cards are underlined further along the holding
+        let (three, _) = lead(Rank::Three);
+        let [(three_x1, three_y, _, _)] = three[..] else {
+            panic!("expected one lead underline, got {three:?}");
+        };
+        assert!(three_x1 > x2);
+        assert_eq!(three_y, y1);
+
+        // A card the leader doesn't hold is not underlined
+        assert!(lead(Rank::Ace).0.is_empty());
+    }
+
+    #[test]
+    fn test_rotated_east_west_matrices() {
+        // Each transformation matrix with the text drawn under it
+        let rotated = |rotate: bool| {
+            let options = options(HiddenHands::default()).with_rotated_east_west(rotate);
+            let mut rotated: Vec<([f32; 6], Vec<String>)> = Vec::new();
+            let mut transformed = false;
+            for op in draw(&options, None) {
+                match op {
+                    Op::SetTransformationMatrix {
+                        matrix: CurTransMat::Raw(matrix),
+                    } => {
+                        rotated.push((matrix, Vec::new()));
+                        transformed = true;
+                    }
+                    Op::RestoreGraphicsState => transformed = false,
+                    Op::ShowText { items } if transformed => {
+                        let texts = &mut rotated.last_mut().unwrap().1;
+                        texts.extend(items.into_iter().filter_map(|item| match item {
+                            TextItem::Text(text) => Some(text),
+                            _ => None,
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+            rotated
+        };
+        assert!(rotated(false).is_empty());
+
+        let rotated = rotated(true);
+        let [(west, west_text), (east, east_text)] = &rotated[..] else {
+            panic!("expected West and East rotations, got {rotated:?}");
+        };
+        // West turns clockwise, East counter-clockwise
+        assert_eq!(west[..4], [0.0, -1.0, 1.0, 0.0]);
+        assert_eq!(east[..4], [0.0, 1.0, -1.0, 0.0]);
+        assert!(west[4] < east[4]);
+        // Each hand's own cards are drawn inside its rotation
+        assert!(west_text.iter().any(|text| text == "4 3"));
+        assert!(east_text.iter().any(|text| text == "J 9 8"));
+    }
+}
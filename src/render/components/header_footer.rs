@@ -0,0 +1,293 @@
+//! Page header and footer renderer
+//!
+//! Draws the optional header and footer lines shared by all layouts. Each line
+//! has left, center and right fields whose text is a template expanded per page:
+//!
+//! - `{page}` - current page number
+//! - `{pages}` - total page count
+//! - `{boards}` - boards on the page (e.g. "1-6", or "7" for a single board)
+//! - `{event}`, `{date}`, `{site}` - tags of the first board on the page
+//! - `{title}`, `{subtitle}` - the document title and subtitle (`--title`,
+//!   `--subtitle`, or the `%HRTitle` directives)
+//!
+//! A copyright line (`%HRCopyright` or `--copyright`) is centered in a smaller
+//! font at the bottom of the page, below the footer when there is one.
+//!
+//! A logo (`--logo` or `%HRTitleLogo`) is drawn at the left end of the header
+//! line, which moves the left header field over to make room.
+//!
+//! The lines sit in the top and bottom margins, so page content is unaffected.
+//! They are added once all pages are laid out, as `{pages}` isn't known before.
+
+use printpdf::{BuiltinFont, Color, Mm, PdfDocument, PdfPage};
+
+use crate::config::settings::PageFields;
+use crate::config::Settings;
+use crate::error::RenderError;
+use crate::model::Board;
+use crate::render::helpers::colors::BLACK;
+use crate::render::helpers::image_file::ImageFile;
+use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::text_metrics::get_times_measurer;
+
+/// Font size for header and footer text in points
+const FONT_SIZE: f32 = 9.0;
+
+/// Font size for the copyright line in points
+const COPYRIGHT_FONT_SIZE: f32 = 7.0;
+
+/// Tallest a logo is drawn, in mm (less if the top margin is narrow)
+const LOGO_MAX_HEIGHT: f32 = 12.0;
+
+/// Space kept between the logo and the page edge/left header field, in mm
+const LOGO_GAP: f32 = 2.0;
+
+/// Values substituted into header/footer templates for one page
+#[derive(Debug, Clone, Default)]
+pub struct PageContext {
+    pub page: usize,
+    pub pages: usize,
+    pub boards: String,
+    pub event: String,
+    pub date: String,
+    pub site: String,
+    pub title: String,
+    pub subtitle: String,
+}
+
+impl PageContext {
+    /// Build the context for a page showing the given boards
+    pub fn new(page: usize, pages: usize, boards: &[&Board]) -> Self {
+        let first = boards.first().copied();
+        let tag =
+            |get: fn(&Board) -> Option<&String>| first.and_then(get).cloned().unwrap_or_default();
+
+        Self {
+            page,
+            pages,
+            boards: board_range(boards),
+            event: tag(|b| b.event.as_ref()),
+            date: tag(|b| b.date.as_ref()),
+            site: tag(|b| b.site.as_ref()),
+            title: String::new(),
+            subtitle: String::new(),
+        }
+    }
+
+    /// Expand template variables in a header/footer field
+    pub fn expand(&self, template: &str) -> String {
+        template
+            .replace("{page}", &self.page.to_string())
+            .replace("{pages}", &self.pages.to_string())
+            .replace("{boards}", &self.boards)
+            .replace("{event}", &self.event)
+            .replace("{date}", &self.date)
+            .replace("{site}", &self.site)
+            .replace("{title}", &self.title)
+            .replace("{subtitle}", &self.subtitle)
+    }
+}
+
+/// Describe the boards on a page as "first-last", or a single board id
+pub fn board_range(boards: &[&Board]) -> String {
+    let mut ids = boards.iter().filter_map(|b| b.board_id.as_deref());
+    match (ids.next(), ids.last()) {
+        (Some(first), Some(last)) if first != last => format!("{}-{}", first, last),
+        (Some(first), _) => first.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+/// Add the configured header, footer and logo to every page.
+///
+/// `page_boards[i]` holds the boards shown on page `i`.
+pub fn add_header_footer(
+    doc: &mut PdfDocument,
+    pages: &mut [PdfPage],
+    page_boards: &[Vec<&Board>],
+    settings: &Settings,
+) -> Result<(), RenderError> {
+    let logo = match &settings.logo {
+        Some(path) => Some(ImageFile::load(
+            doc,
+            path,
+            (f32::INFINITY, LOGO_MAX_HEIGHT),
+            settings.image_dpi,
+        )?),
+        None => None,
+    };
+    if settings.header.is_empty()
+        && settings.footer.is_empty()
+        && settings.effective_copyright().is_none()
+        && logo.is_none()
+    {
+        return Ok(());
+    }
+
+    // A chunk of a longer document numbers its pages within the whole
+    let first_page = settings.first_page();
+    let total = settings
+        .chunk
+        .map_or(pages.len(), |chunk| chunk.total_pages);
+    for (idx, page) in pages.iter_mut().enumerate() {
+        let boards = page_boards.get(idx).map(Vec::as_slice).unwrap_or(&[]);
+        let context = PageContext {
+            title: settings.effective_title().unwrap_or_default().to_string(),
+            subtitle: settings.effective_subtitle().unwrap_or_default(),
+            ..PageContext::new(first_page + idx, total, boards)
+        };
+
+        let mut layer = LayerBuilder::new();
+        render_header_footer(&mut layer, &context, settings, logo.as_ref());
+        page.ops.extend(layer.into_ops());
+    }
+    Ok(())
+}
+
+/// Render the header and footer lines for one page
+pub fn render_header_footer(
+    layer: &mut LayerBuilder,
+    context: &PageContext,
+    settings: &Settings,
+    logo: Option<&ImageFile>,
+) {
+    let measurer = get_times_measurer();
+
+    // Center each line vertically in its margin (above the title block, if any)
+    let cap_height = measurer.cap_height_mm(FONT_SIZE);
+    let header_margin = settings.margin_top - settings.title_block_height();
+    let header_y = settings.page_height - (header_margin + cap_height) / 2.0;
+    let footer_y = (settings.margin_bottom - cap_height) / 2.0;
+
+    // Logo centered on the header line, left header field moved past it
+    let mut header_indent = 0.0;
+    if let Some(logo) = logo {
+        let height = LOGO_MAX_HEIGHT.min(header_margin - 2.0 * LOGO_GAP);
+        if height > 0.0 {
+            let y = settings.page_height - (header_margin + height) / 2.0;
+            layer.use_xobject(logo.id(), logo.transform(settings.margin_left, y, height));
+            header_indent = logo.width_at(height) + LOGO_GAP;
+        }
+    }
+
+    layer.set_fill_color(Color::Rgb(BLACK));
+    render_line(
+        layer,
+        &settings.header,
+        header_y,
+        header_indent,
+        context,
+        settings,
+    );
+    render_line(layer, &settings.footer, footer_y, 0.0, context, settings);
+
+    if let Some(copyright) = settings.effective_copyright() {
+        // Centered in the margin, or halfway between the footer and the page edge
+        let copyright_cap = measurer.cap_height_mm(COPYRIGHT_FONT_SIZE);
+        let space = if settings.footer.is_empty() {
+            settings.margin_bottom
+        } else {
+            footer_y
+        };
+        let width = measurer.measure_width_mm(copyright, COPYRIGHT_FONT_SIZE);
+        layer.use_text_builtin(
+            copyright,
+            COPYRIGHT_FONT_SIZE,
+            Mm((settings.margin_left + settings.page_width - settings.margin_right - width) / 2.0),
+            Mm((space - copyright_cap) / 2.0),
+            BuiltinFont::TimesRoman,
+        );
+    }
+}
+
+/// Render one line's left, center and right fields at the given baseline,
+/// with the left field moved right by `indent` mm
+fn render_line(
+    layer: &mut LayerBuilder,
+    fields: &PageFields,
+    baseline: f32,
+    indent: f32,
+    context: &PageContext,
+    settings: &Settings,
+) {
+    let measurer = get_times_measurer();
+    let left_x = settings.margin_left;
+    let right_x = settings.page_width - settings.margin_right;
+
+    let left = context.expand(&fields.left);
+    if !left.is_empty() {
+        layer.use_text_builtin(
+            left,
+            FONT_SIZE,
+            Mm(left_x + indent),
+            Mm(baseline),
+            BuiltinFont::TimesRoman,
+        );
+    }
+
+    let center = context.expand(&fields.center);
+    if !center.is_empty() {
+        let width = measurer.measure_width_mm(&center, FONT_SIZE);
+        layer.use_text_builtin(
+            center,
+            FONT_SIZE,
+            Mm((left_x + right_x - width) / 2.0),
+            Mm(baseline),
+            BuiltinFont::TimesRoman,
+        );
+    }
+
+    let right = context.expand(&fields.right);
+    if !right.is_empty() {
+        let width = measurer.measure_width_mm(&right, FONT_SIZE);
+        layer.use_text_builtin(
+            right,
+            FONT_SIZE,
+            Mm(right_x - width),
+            Mm(baseline),
+            BuiltinFont::TimesRoman,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(id: &str) -> Board {
+        Board {
+            board_id: Some(id.to_string()),
+            event: Some("Club Game".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let boards = [board("1"), board("2"), board("3")];
+        let refs: Vec<&Board> = boards.iter().collect();
+        let context = PageContext::new(2, 5, &refs);
+        assert_eq!(
+            context.expand("{event}: boards {boards}, page {page} of {pages}"),
+            "Club Game: boards 1-3, page 2 of 5"
+        );
+    }
+
+    #[test]
+    fn test_expand_title_and_subtitle() {
+        let context = PageContext {
+            title: "Stayman".to_string(),
+            subtitle: "Lesson 3".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(context.expand("{title}: {subtitle}"), "Stayman: Lesson 3");
+    }
+
+    #[test]
+    fn test_board_range_single_and_empty() {
+        let boards = [board("7")];
+        let refs: Vec<&Board> = boards.iter().collect();
+        assert_eq!(board_range(&refs), "7");
+        assert_eq!(board_range(&[]), "");
+    }
+}
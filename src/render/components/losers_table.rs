@@ -6,32 +6,18 @@
 
 use printpdf::{BuiltinFont, Color, FontId, Mm, PaintMode, Rgb};
 
-use crate::render::helpers::colors::{SuitColors, BLACK, WHITE};
+use crate::model::Suit;
+use crate::render::helpers::colors::{SuitColors, Theme, BLACK, WHITE};
 use crate::render::helpers::layer::LayerBuilder;
 use crate::render::helpers::text_metrics;
 
-/// Light blue for "Losers" header background
-const HEADER_BLUE: Rgb = Rgb {
-    r: 0.678, // #ADC8E6 approximately
-    g: 0.784,
-    b: 0.902,
-    icc_profile: None,
-};
-
-/// Light green for "Techniques" header background
-const HEADER_GREEN: Rgb = Rgb {
-    r: 0.745, // #BED8BE approximately
-    g: 0.847,
-    b: 0.745,
-    icc_profile: None,
-};
-
 /// Renderer for the losers/techniques table
 pub struct LosersTableRenderer<'a> {
     font: BuiltinFont,
     bold_font: BuiltinFont,
     symbol_font: &'a FontId,
     colors: SuitColors,
+    theme: Theme,
     /// Font size for header text (e.g., "Losers", "Techniques")
     header_font_size: f32,
     /// Font size for column labels
@@ -59,6 +45,7 @@ impl<'a> LosersTableRenderer<'a> {
             bold_font,
             symbol_font,
             colors,
+            theme: Theme::default(),
             header_font_size: 14.0,
             label_font_size: 12.0,
             col_width: 16.0,
@@ -68,6 +55,12 @@ impl<'a> LosersTableRenderer<'a> {
         }
     }
 
+    /// Set the color theme for header fills
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Set font sizes
     pub fn font_sizes(mut self, header: f32, label: f32) -> Self {
         self.header_font_size = header;
@@ -117,7 +110,7 @@ impl<'a> LosersTableRenderer<'a> {
             current_y,
             width,
             "Count Fast and Slow Losers",
-            HEADER_BLUE,
+            self.theme.table_header.clone(),
         );
         current_y -= self.header_height;
 
@@ -138,7 +131,7 @@ impl<'a> LosersTableRenderer<'a> {
             current_y,
             width,
             "Decide How to Reduce Losers",
-            HEADER_GREEN,
+            self.theme.table_subheader.clone(),
         );
         current_y -= self.header_height;
 
@@ -216,10 +209,10 @@ impl<'a> LosersTableRenderer<'a> {
     /// Render the suit symbols row (♠ ♥ ♦ ♣ Total) - 5 columns
     fn render_suit_row(&self, layer: &mut LayerBuilder, x: f32, y: f32) {
         let suits = [
-            ("♠", false), // Spades - black
-            ("♥", true),  // Hearts - red
-            ("♦", true),  // Diamonds - red
-            ("♣", false), // Clubs - black
+            ("♠", Suit::Spades),
+            ("♥", Suit::Hearts),
+            ("♦", Suit::Diamonds),
+            ("♣", Suit::Clubs),
         ];
 
         let width = self.col_width * 5.0;
@@ -239,7 +232,7 @@ impl<'a> LosersTableRenderer<'a> {
 
         let measurer = text_metrics::get_helvetica_measurer(); // Sans for symbols
 
-        for (i, (symbol, is_red)) in suits.iter().enumerate() {
+        for (i, (symbol, suit)) in suits.iter().enumerate() {
             let cell_x = x + (i as f32 * self.col_width);
 
             // Draw column separator
@@ -255,11 +248,7 @@ impl<'a> LosersTableRenderer<'a> {
             let symbol_y =
                 y - self.row_height + (self.row_height - self.label_font_size * 0.35) / 2.0;
 
-            if *is_red {
-                layer.set_fill_color(Color::Rgb(self.colors.hearts.clone()));
-            } else {
-                layer.set_fill_color(Color::Rgb(BLACK));
-            }
+            layer.set_fill_color(Color::Rgb(self.colors.for_suit(suit)));
             layer.use_text(
                 *symbol,
                 self.label_font_size,
@@ -6,7 +6,11 @@ pub mod declarers_plan_small;
 pub mod dummy;
 pub mod fan;
 pub mod hand_diagram;
+pub mod header_footer;
 pub mod losers_table;
+pub mod qr_code;
+pub mod ranking;
+pub mod title_block;
 pub mod winners_table;
 
 pub use bidding_table::BiddingTableRenderer;
@@ -15,5 +19,9 @@ pub use declarers_plan_small::DeclarersPlanSmallRenderer;
 pub use dummy::DummyRenderer;
 pub use fan::FanRenderer;
 pub use hand_diagram::{DiagramDisplayOptions, HandDiagramRenderer};
+pub use header_footer::add_header_footer;
 pub use losers_table::LosersTableRenderer;
+pub use qr_code::QrCodeRenderer;
+pub use ranking::ranking_pages;
+pub use title_block::add_title_block;
 pub use winners_table::WinnersTableRenderer;
@@ -0,0 +1,295 @@
+//! QR code renderer for BBO handviewer links
+//!
+//! Builds the Bridge Base Online handviewer URL for a board (hands, dealer,
+//! vulnerability, board number and auction) and draws it as a QR code, so a
+//! printed sheet can be scanned to replay the deal online. Hands the board
+//! hides are left out of the link too.
+
+use printpdf::{Color, Mm, PaintMode};
+use qrcode::{Color as QrColor, QrCode};
+
+use crate::model::{
+    Board, Call, Direction, Hand, Rank, Strain, Suit, Vulnerability, SUITS_DISPLAY_ORDER,
+};
+use crate::render::helpers::colors::BLACK;
+use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::links::{Link, LinkTarget};
+
+/// Base address of the BBO handviewer
+const HANDVIEWER_URL: &str = "https://www.bridgebase.com/tools/handviewer.html";
+
+/// Quiet zone around the code, in modules (the QR spec asks for 4)
+const QUIET_ZONE_MODULES: usize = 4;
+
+/// Build the BBO handviewer URL for a board
+pub fn handviewer_url(board: &Board) -> String {
+    let mut params: Vec<String> = Vec::new();
+
+    // Ranks held anywhere in the deal, so each spot card becomes a low card
+    // no other hand holds
+    let mut taken: Vec<Vec<Rank>> = SUITS_DISPLAY_ORDER
+        .iter()
+        .map(|&suit| {
+            let deal = &board.deal;
+            [&deal.north, &deal.east, &deal.south, &deal.west]
+                .into_iter()
+                .flat_map(|hand| hand.holding(suit).ranks.iter().copied())
+                .collect()
+        })
+        .collect();
+
+    for (key, direction) in [
+        ('n', Direction::North),
+        ('e', Direction::East),
+        ('s', Direction::South),
+        ('w', Direction::West),
+    ] {
+        let hand = board.deal.hand(direction);
+        if hand.card_count() > 0 && !board.hidden.is_hidden(direction) {
+            params.push(param(key, &hand_param(hand, &mut taken)));
+        }
+    }
+
+    if let Some(dealer) = board.dealer {
+        params.push(param('d', &direction_char(dealer).to_string()));
+    }
+
+    let vul = match board.vulnerable {
+        Vulnerability::None => '-',
+        Vulnerability::NorthSouth => 'n',
+        Vulnerability::EastWest => 'e',
+        Vulnerability::Both => 'b',
+    };
+    params.push(param('v', &vul.to_string()));
+
+    if let Some(ref board_id) = board.board_id {
+        params.push(param('b', board_id));
+    }
+
+    if let Some(ref auction) = board.auction {
+        let calls: Vec<String> = auction
+            .calls
+            .iter()
+            .filter_map(|annotated| call_param(&annotated.call))
+            .collect();
+        if !calls.is_empty() {
+            params.push(param('a', &calls.join(",")));
+        }
+    }
+
+    format!("{}?{}", HANDVIEWER_URL, params.join("&"))
+}
+
+/// A `key=value` query parameter, the value percent-encoded
+fn param(key: char, value: &str) -> String {
+    let mut s = format!("{}=", key);
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~,".contains(&byte) {
+            s.push(byte as char);
+        } else {
+            s.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    s
+}
+
+/// Hand in handviewer format: "s" + spades + "h" + hearts + ...
+///
+/// The handviewer needs every rank, so spot cards are given the lowest ranks
+/// not in `taken` (one list per suit), which are then added to it.
+fn hand_param(hand: &Hand, taken: &mut [Vec<Rank>]) -> String {
+    let mut s = String::new();
+    for (suit, taken) in SUITS_DISPLAY_ORDER.into_iter().zip(taken) {
+        s.push(match suit {
+            Suit::Spades => 's',
+            Suit::Hearts => 'h',
+            Suit::Diamonds => 'd',
+            Suit::Clubs => 'c',
+        });
+        let holding = hand.holding(suit);
+        let ranks = holding.card_ranks(taken);
+        taken.extend(&ranks[holding.ranks.len()..]);
+        for rank in ranks {
+            s.push(rank.to_char().to_ascii_lowercase());
+        }
+    }
+    s
+}
+
+fn strain_char(strain: Strain) -> char {
+    match strain {
+        Strain::Clubs => 'c',
+        Strain::Diamonds => 'd',
+        Strain::Hearts => 'h',
+        Strain::Spades => 's',
+        Strain::NoTrump => 'n',
+    }
+}
+
+fn direction_char(direction: Direction) -> char {
+    match direction {
+        Direction::North => 'n',
+        Direction::East => 'e',
+        Direction::South => 's',
+        Direction::West => 'w',
+    }
+}
+
+/// A call in handviewer format, or None for calls the handviewer can't show
+fn call_param(call: &Call) -> Option<String> {
+    match call {
+        Call::Pass => Some("p".to_string()),
+        Call::Double => Some("d".to_string()),
+        Call::Redouble => Some("r".to_string()),
+        Call::Bid { level, strain } => Some(format!("{}{}", level, strain_char(*strain))),
+        _ => None,
+    }
+}
+
+/// Renderer for a board's handviewer QR code
+pub struct QrCodeRenderer {
+    /// Side length of the code in mm, including the quiet zone
+    size: f32,
+}
+
+impl QrCodeRenderer {
+    pub fn new(size: f32) -> Self {
+        Self { size }
+    }
+
+    /// Render the QR code for a board with its top-left corner at origin.
+    ///
+    /// The code is also a clickable link, for readers of the PDF on screen.
+    /// Returns the height used (0 if the URL could not be encoded).
+    pub fn render(&self, layer: &mut LayerBuilder, board: &Board, origin: (Mm, Mm)) -> f32 {
+        let url = handviewer_url(board);
+        let Ok(code) = QrCode::new(url.as_bytes()) else {
+            log::warn!("Could not encode handviewer URL as a QR code: {}", url);
+            return 0.0;
+        };
+
+        let (ox, oy) = origin;
+        let width = code.width();
+        let module = self.size / (width + 2 * QUIET_ZONE_MODULES) as f32;
+        let inset = QUIET_ZONE_MODULES as f32 * module;
+
+        layer.set_fill_color(Color::Rgb(BLACK));
+        for (i, color) in code.to_colors().into_iter().enumerate() {
+            if color != QrColor::Dark {
+                continue;
+            }
+            let x = ox.0 + inset + (i % width) as f32 * module;
+            let y = oy.0 - inset - (i / width) as f32 * module;
+            layer.add_rect(
+                Mm(x),
+                Mm(y - module),
+                Mm(x + module),
+                Mm(y),
+                PaintMode::Fill,
+            );
+        }
+
+        layer.add_link(Link {
+            x: ox.0,
+            y: oy.0 - self.size,
+            width: self.size,
+            height: self.size,
+            target: LinkTarget::Uri(url),
+        });
+
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_pbn;
+
+    fn parse_board(pbn: &str) -> Board {
+        parse_pbn(pbn).unwrap().boards.remove(0)
+    }
+
+    #[test]
+    fn test_handviewer_url_full_board() {
+        let board = parse_board(
+            r#"[Board "7"]
+[Dealer "S"]
+[Vulnerable "Both"]
+[Deal "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765"]
+[Auction "S"]
+1D 1S X Pass
+2D Pass Pass Pass
+"#,
+        );
+        let url = handviewer_url(&board);
+        assert!(url.starts_with("https://www.bridgebase.com/tools/handviewer.html?"));
+        assert!(url.contains("n=sakq2hj32dt98c432"));
+        assert!(url.contains("w=s43h7654d7654c765"));
+        assert!(url.contains("d=s"));
+        assert!(url.contains("v=b"));
+        assert!(url.contains("b=7"));
+        assert!(url.contains("a=1d,1s,d,p,2d,p,p,p"));
+    }
+
+    #[test]
+    fn test_handviewer_url_omits_missing_hands_and_auction() {
+        let board = parse_board(
+            r#"[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ2.J32.T98.432 - - -"]
+"#,
+        );
+        let url = handviewer_url(&board);
+        assert!(url.contains("n=sakq2hj32dt98c432"));
+        assert!(!url.contains("e="));
+        assert!(!url.contains("a="));
+        assert!(url.contains("v=-"));
+    }
+
+    #[test]
+    fn test_handviewer_url_fills_spot_cards() {
+        let board = parse_board(
+            r#"[Board "1"]
+[Deal "N:AKxx... 2... Qx... -"]
+"#,
+        );
+        let url = handviewer_url(&board);
+        // North's spots take the lowest free ranks, then South's the next
+        assert!(url.contains("n=sak43hdc"), "{}", url);
+        assert!(url.contains("e=s2hdc"));
+        assert!(url.contains("s=sq5hdc"));
+    }
+
+    #[test]
+    fn test_handviewer_url_encodes_values() {
+        let board = parse_board(
+            r#"[Board "1"]
+[Deal "N:AKQ2.J32.T98.432 - - -"]
+"#,
+        );
+        let board = Board {
+            board_id: Some("3a & b=c#1 ü".to_string()),
+            ..board
+        };
+        let url = handviewer_url(&board);
+        assert!(url.contains("b=3a%20%26%20b%3Dc%231%20%C3%BC"), "{}", url);
+        assert!(!url.contains(' ') && !url.contains("&b=c"));
+    }
+
+    #[test]
+    fn test_handviewer_url_leaves_out_hidden_hands() {
+        let board = parse_board(
+            r#"[Board "1"]
+[Hidden "EW"]
+[Deal "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765"]
+"#,
+        );
+        let url = handviewer_url(&board);
+        assert!(url.contains("n=sakq2hj32dt98c432"));
+        assert!(url.contains("s=st765ht98dakqct98"));
+        assert!(!url.contains("e="));
+        assert!(!url.contains("w="));
+    }
+}
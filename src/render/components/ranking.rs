@@ -0,0 +1,156 @@
+//! Event ranking page (`[TotalScoreTable]`)
+//!
+//! When a PBN file carries the event's final standings, the Analysis layout
+//! adds a page after the hand records listing each pair's rank, score and
+//! percentage, so the post-game PDF has both the hands and the results. A
+//! long ranking runs on to further pages, repeating the column headings.
+
+use printpdf::{BuiltinFont, Color, Mm, PdfPage};
+
+use crate::config::Settings;
+use crate::model::Standing;
+use crate::render::helpers::colors::BLACK;
+use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::text_metrics::get_builtin_measurer;
+
+/// Font size of the page title in points
+const TITLE_FONT_SIZE: f32 = 14.0;
+
+/// Line spacing as a multiple of the font size
+const LINE_SPACING: f32 = 1.4;
+
+/// Width of the rank column in mm
+const RANK_WIDTH: f32 = 10.0;
+
+/// Width of the score and percent columns in mm
+const NUMBER_WIDTH: f32 = 20.0;
+
+/// Gap between columns in mm
+const COLUMN_GAP: f32 = 4.0;
+
+/// Pages listing `standings`, with as many pairs on each as fit
+pub fn ranking_pages(standings: &[Standing], settings: &Settings) -> Vec<PdfPage> {
+    let font_size = settings.body_font_size;
+    let line_height = font_size * LINE_SPACING * 0.3528;
+    let title_height =
+        get_builtin_measurer(BuiltinFont::TimesBold).line_height_mm(TITLE_FONT_SIZE) * LINE_SPACING;
+    let content_height = settings.page_height - settings.margin_top - settings.margin_bottom;
+    // The title and the column headings come first on every page
+    let per_page = ((content_height - title_height) / line_height - 1.0).max(1.0) as usize;
+
+    standings
+        .chunks(per_page)
+        .map(|page_standings| {
+            let mut layer = LayerBuilder::new();
+            render_ranking(&mut layer, page_standings, settings, line_height);
+            PdfPage::new(
+                Mm(settings.page_width),
+                Mm(settings.page_height),
+                layer.into_ops(),
+            )
+        })
+        .collect()
+}
+
+/// Draw the title, the column headings and a page of standings
+fn render_ranking(
+    layer: &mut LayerBuilder,
+    standings: &[Standing],
+    settings: &Settings,
+    line_height: f32,
+) {
+    let locale = settings.locale();
+    let font_size = settings.body_font_size;
+    let left = settings.margin_left;
+    let right = settings.page_width - settings.margin_right;
+    let top = settings.page_height - settings.margin_top;
+
+    layer.set_fill_color(Color::Rgb(BLACK));
+    let title_measurer = get_builtin_measurer(BuiltinFont::TimesBold);
+    let mut y = top - title_measurer.cap_height_mm(TITLE_FONT_SIZE);
+    let title_width = title_measurer.measure_width_mm(locale.ranking, TITLE_FONT_SIZE);
+    layer.use_text_builtin(
+        locale.ranking,
+        TITLE_FONT_SIZE,
+        Mm((left + right - title_width) / 2.0),
+        Mm(y),
+        BuiltinFont::TimesBold,
+    );
+    y -= title_measurer.line_height_mm(TITLE_FONT_SIZE) * LINE_SPACING;
+
+    render_row(
+        layer,
+        locale.ranking_columns,
+        (left, right),
+        y,
+        font_size,
+        BuiltinFont::TimesBold,
+    );
+    let rule_y = y - line_height * 0.3;
+    layer.set_outline_color(Color::Rgb(BLACK));
+    layer.set_outline_thickness(0.5);
+    layer.add_line(Mm(left), Mm(rule_y), Mm(right), Mm(rule_y));
+
+    for standing in standings {
+        y -= line_height;
+        render_row(
+            layer,
+            [
+                standing.rank.as_deref().unwrap_or(""),
+                &standing.pair,
+                standing.score.as_deref().unwrap_or(""),
+                standing.percent.as_deref().unwrap_or(""),
+            ],
+            (left, right),
+            y,
+            font_size,
+            BuiltinFont::TimesRoman,
+        );
+    }
+}
+
+/// Draw one row: rank, score and percent right-aligned, the pair on the left
+fn render_row(
+    layer: &mut LayerBuilder,
+    [rank, pair, score, percent]: [&str; 4],
+    (left, right): (f32, f32),
+    y: f32,
+    font_size: f32,
+    font: BuiltinFont,
+) {
+    let measurer = get_builtin_measurer(font);
+    let mut right_aligned = |text: &str, column_right: f32| {
+        let width = measurer.measure_width_mm(text, font_size);
+        layer.use_text_builtin(text, font_size, Mm(column_right - width), Mm(y), font);
+    };
+    right_aligned(rank, left + RANK_WIDTH);
+    right_aligned(percent, right);
+    right_aligned(score, right - NUMBER_WIDTH - COLUMN_GAP);
+    layer.use_text_builtin(
+        pair,
+        font_size,
+        Mm(left + RANK_WIDTH + COLUMN_GAP),
+        Mm(y),
+        font,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_ranking_runs_on() {
+        let standings: Vec<Standing> = (1..=200)
+            .map(|n| Standing {
+                rank: Some(n.to_string()),
+                pair: format!("Pair {}", n),
+                score: None,
+                percent: None,
+            })
+            .collect();
+        let settings = Settings::default();
+        assert_eq!(ranking_pages(&standings[..3], &settings).len(), 1);
+        assert!(ranking_pages(&standings, &settings).len() > 1);
+    }
+}
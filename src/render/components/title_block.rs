@@ -0,0 +1,93 @@
+//! %HRTitle block renderer (`--title-block`)
+//!
+//! Bridge Composer prints a hand record's title block at the top of the page:
+//! the event from `%HRTitleEvent` in large type, with the club, site and date
+//! (`%HRTitleClub`, `%HRTitleSite`, `%HRTitleDate`) centered on a line below.
+//! `--title` and `--subtitle` replace either line.
+//! The block sits in the top margin, which `Settings` grows by
+//! `TITLE_BLOCK_HEIGHT`, and like the header and footer it's added once all
+//! pages are laid out.
+
+use printpdf::{BuiltinFont, Color, Mm, PdfPage};
+
+use crate::cli::TitleBlock;
+use crate::config::defaults::TITLE_BLOCK_HEIGHT;
+use crate::config::Settings;
+use crate::render::helpers::colors::BLACK;
+use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::text_metrics::get_builtin_measurer;
+
+/// Font size of the event line in points
+const EVENT_FONT_SIZE: f32 = 14.0;
+
+/// Font size of the club/site/date line in points
+const DETAILS_FONT_SIZE: f32 = 10.0;
+
+/// Gap between the event and details lines in mm
+const LINE_GAP: f32 = 2.0;
+
+/// Add the title block to the first page or every page
+pub fn add_title_block(pages: &mut [PdfPage], settings: &Settings) {
+    let count = match settings.title_block {
+        TitleBlock::None => return,
+        // Only the chunk holding the document's first page has it
+        TitleBlock::First if settings.first_page() > 1 => return,
+        TitleBlock::First => 1,
+        TitleBlock::Every => pages.len(),
+    };
+    let lines = title_lines(settings);
+    if lines.is_empty() {
+        return;
+    }
+
+    for page in pages.iter_mut().take(count) {
+        let mut layer = LayerBuilder::new();
+        render_title_block(&mut layer, &lines, settings);
+        page.ops.extend(layer.into_ops());
+    }
+}
+
+/// Lines of the block with their font and size, top to bottom
+fn title_lines(settings: &Settings) -> Vec<(String, BuiltinFont, f32)> {
+    let mut lines = Vec::new();
+    if let Some(title) = settings.effective_title().filter(|t| !t.is_empty()) {
+        lines.push((title.to_string(), BuiltinFont::TimesBold, EVENT_FONT_SIZE));
+    }
+    if let Some(subtitle) = settings.effective_subtitle() {
+        lines.push((subtitle, BuiltinFont::TimesRoman, DETAILS_FONT_SIZE));
+    }
+    lines
+}
+
+/// Render the block centered in the space reserved above the content
+fn render_title_block(
+    layer: &mut LayerBuilder,
+    lines: &[(String, BuiltinFont, f32)],
+    settings: &Settings,
+) {
+    let left_x = settings.margin_left;
+    let right_x = settings.page_width - settings.margin_right;
+    let block_bottom = settings.page_height - settings.margin_top;
+
+    // Total height from the first cap line to the last baseline
+    let heights: Vec<f32> = lines
+        .iter()
+        .map(|(_, font, size)| get_builtin_measurer(*font).cap_height_mm(*size))
+        .collect();
+    let total = heights.iter().sum::<f32>() + LINE_GAP * (lines.len() - 1) as f32;
+    let mut top = block_bottom + (TITLE_BLOCK_HEIGHT + total) / 2.0;
+
+    layer.set_fill_color(Color::Rgb(BLACK));
+    for ((text, font, size), cap_height) in lines.iter().zip(heights) {
+        let baseline = top - cap_height;
+        let width = get_builtin_measurer(*font).measure_width_mm(text, *size);
+        layer.use_text_builtin(
+            text.clone(),
+            *size,
+            Mm((left_x + right_x - width) / 2.0),
+            Mm(baseline),
+            *font,
+        );
+        top = baseline - LINE_GAP;
+    }
+}
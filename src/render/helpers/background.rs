@@ -0,0 +1,298 @@
+//! Page backgrounds ("letterhead", `--background`)
+//!
+//! An image background is drawn first on every page, stretched to the page
+//! size. A PDF background is stamped in after the document is saved, with
+//! lopdf: each background page becomes a form XObject drawn beneath the page
+//! content. Output page N uses background page N, and the last background
+//! page repeats, so a letterhead can have a separate continuation page.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream};
+use printpdf::{PdfDocument, PdfPage};
+
+use crate::config::Settings;
+use crate::error::RenderError;
+use crate::render::helpers::image_file::ImageFile;
+use crate::render::helpers::layer::LayerBuilder;
+
+/// Resource name of the background form XObject on each page
+const BACKGROUND_NAME: &str = "PbnBackground";
+
+/// Whether a background file is a PDF (stamped after saving) rather than an image
+pub fn is_pdf_background(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+/// Draw the `--background` image beneath the content of every page.
+///
+/// Does nothing for PDF backgrounds; see `add_pdf_background`.
+pub fn add_image_background(
+    doc: &mut PdfDocument,
+    pages: &mut [PdfPage],
+    settings: &Settings,
+) -> Result<(), RenderError> {
+    let Some(path) = settings.background.as_deref() else {
+        return Ok(());
+    };
+    if is_pdf_background(path) {
+        return Ok(());
+    }
+    let page = (settings.page_width, settings.page_height);
+    let image = ImageFile::load(doc, path, page, settings.image_dpi)?;
+
+    for page in pages {
+        let mut layer = LayerBuilder::new();
+        layer.use_xobject(
+            image.id(),
+            image.transform_fill(0.0, 0.0, settings.page_width, settings.page_height),
+        );
+        page.ops.splice(0..0, layer.into_ops());
+    }
+    Ok(())
+}
+
+/// Stamp the `--background` PDF beneath the pages of an already-saved PDF.
+///
+/// Does nothing for image backgrounds; see `add_image_background`.
+pub fn add_pdf_background(pdf: Vec<u8>, settings: &Settings) -> Result<Vec<u8>, RenderError> {
+    match settings.background.as_deref() {
+        Some(path) if is_pdf_background(path) => {
+            stamp_pdf_background(pdf, path).map_err(RenderError::PdfGeneration)
+        }
+        _ => Ok(pdf),
+    }
+}
+
+/// Stamp the pages of a background PDF beneath the pages of a PDF
+fn stamp_pdf_background(pdf: Vec<u8>, path: &Path) -> Result<Vec<u8>, String> {
+    let mut doc = Document::load_mem(&pdf)
+        .map_err(|e| format!("Failed to parse PDF for background: {}", e))?;
+    let mut background = Document::load(path)
+        .map_err(|e| format!("Failed to read background {}: {}", path.display(), e))?;
+
+    // Give the background's objects ids that don't clash with the output's
+    background.renumber_objects_with(doc.max_id + 1);
+    doc.max_id = background.max_id;
+
+    let mut forms = Vec::new();
+    for &page_id in background.get_pages().values() {
+        let content = background
+            .get_page_content(page_id)
+            .map_err(|e| format!("Failed to read background page: {}", e))?;
+        let resources = inherited(&background, page_id, b"Resources")
+            .cloned()
+            .unwrap_or_else(|| Object::Dictionary(Dictionary::new()));
+        let media_box = media_box(&background, page_id);
+        forms.push((content, resources, media_box));
+    }
+    if forms.is_empty() {
+        return Err(format!("Background {} has no pages", path.display()));
+    }
+
+    // Bring over everything except the background's own page tree
+    for (id, object) in background.objects {
+        let is_page_tree = object.as_dict().is_ok_and(|dict| {
+            dict.get(b"Type")
+                .and_then(Object::as_name)
+                .is_ok_and(|name| name == b"Catalog" || name == b"Pages" || name == b"Page")
+        });
+        if !is_page_tree {
+            doc.objects.insert(id, object);
+        }
+    }
+
+    let pages: Vec<ObjectId> = doc.get_pages().values().copied().collect();
+    let mut form_ids: Vec<Option<ObjectId>> = vec![None; forms.len()];
+    for (page_idx, &page_id) in pages.iter().enumerate() {
+        let form_idx = page_idx.min(forms.len() - 1);
+        let target_box = media_box(&doc, page_id);
+
+        // One form per background page, scaled to the output page size
+        let form_id = match form_ids[form_idx] {
+            Some(id) => id,
+            None => {
+                let (content, resources, source_box) = &forms[form_idx];
+                let id = doc.add_object(Stream::new(
+                    dictionary! {
+                        "Type" => "XObject",
+                        "Subtype" => "Form",
+                        "BBox" => box_object(source_box),
+                        "Matrix" => fit_matrix(source_box, &target_box),
+                        "Resources" => resources.clone(),
+                    },
+                    content.clone(),
+                ));
+                form_ids[form_idx] = Some(id);
+                id
+            }
+        };
+
+        add_xobject_resource(&mut doc, page_id, form_id)?;
+        let stamp = doc.add_object(Stream::new(
+            Dictionary::new(),
+            format!("q /{} Do Q\n", BACKGROUND_NAME).into_bytes(),
+        ));
+        prepend_content(&mut doc, page_id, stamp)?;
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    doc.save_to(&mut output)
+        .map_err(|e| format!("Failed to save PDF with background: {}", e))?;
+
+    Ok(output.into_inner())
+}
+
+/// A page attribute, looked up through the page tree if the page inherits it
+fn inherited<'a>(doc: &'a Document, page_id: ObjectId, key: &[u8]) -> Option<&'a Object> {
+    let mut current = doc.get_dictionary(page_id).ok();
+    while let Some(dict) = current {
+        if let Ok(value) = dict.get(key) {
+            return Some(value);
+        }
+        current = dict
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .ok();
+    }
+    None
+}
+
+/// A page's MediaBox as [x0, y0, x1, y1] (Letter if missing)
+fn media_box(doc: &Document, page_id: ObjectId) -> [f32; 4] {
+    let values = inherited(doc, page_id, b"MediaBox").and_then(|obj| match obj {
+        Object::Reference(id) => doc.get_object(*id).and_then(Object::as_array).ok(),
+        other => other.as_array().ok(),
+    });
+    let numbers: Vec<f32> = values
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_float().ok())
+        .collect();
+    match numbers[..] {
+        [x0, y0, x1, y1] => [x0, y0, x1, y1],
+        _ => [0.0, 0.0, 612.0, 792.0],
+    }
+}
+
+fn box_object(rect: &[f32; 4]) -> Vec<Object> {
+    rect.iter().map(|&v| Object::Real(v)).collect()
+}
+
+/// Form matrix stretching the `source` box over the `target` box
+fn fit_matrix(source: &[f32; 4], target: &[f32; 4]) -> Vec<Object> {
+    let sx = (target[2] - target[0]) / (source[2] - source[0]);
+    let sy = (target[3] - target[1]) / (source[3] - source[1]);
+    vec![
+        Object::Real(sx),
+        Object::Real(0.0),
+        Object::Real(0.0),
+        Object::Real(sy),
+        Object::Real(target[0] - source[0] * sx),
+        Object::Real(target[1] - source[1] * sy),
+    ]
+}
+
+/// Register the background form in a page's XObject resources
+fn add_xobject_resource(
+    doc: &mut Document,
+    page_id: ObjectId,
+    form_id: ObjectId,
+) -> Result<(), String> {
+    let error = |e: lopdf::Error| format!("Failed to access page resources: {}", e);
+
+    // Resources and the XObject dictionary in them may be inline or shared by reference
+    let resources_id = match doc
+        .get_dictionary(page_id)
+        .map_err(error)?
+        .get(b"Resources")
+    {
+        Ok(Object::Reference(id)) => Some(*id),
+        _ => None,
+    };
+    let resources = match resources_id {
+        Some(id) => doc.get_dictionary(id).ok(),
+        None => doc
+            .get_dictionary(page_id)
+            .and_then(|page| page.get(b"Resources"))
+            .and_then(Object::as_dict)
+            .ok(),
+    };
+    if let Some(Ok(Object::Reference(id))) = resources.map(|r| r.get(b"XObject")) {
+        let id = *id;
+        doc.get_object_mut(id)
+            .and_then(Object::as_dict_mut)
+            .map_err(error)?
+            .set(BACKGROUND_NAME, form_id);
+        return Ok(());
+    }
+
+    let resources = match resources_id {
+        Some(id) => doc.get_object_mut(id).and_then(Object::as_dict_mut),
+        None => {
+            let page = doc
+                .get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(error)?;
+            if !page.has(b"Resources") {
+                page.set("Resources", Dictionary::new());
+            }
+            page.get_mut(b"Resources").and_then(Object::as_dict_mut)
+        }
+    }
+    .map_err(error)?;
+
+    match resources.get_mut(b"XObject") {
+        Ok(Object::Dictionary(xobjects)) => xobjects.set(BACKGROUND_NAME, form_id),
+        _ => resources.set("XObject", dictionary! { BACKGROUND_NAME => form_id }),
+    }
+    Ok(())
+}
+
+/// Put a content stream in front of a page's existing contents
+fn prepend_content(
+    doc: &mut Document,
+    page_id: ObjectId,
+    stream_id: ObjectId,
+) -> Result<(), String> {
+    let page = doc
+        .get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|e| format!("Failed to access page for background: {}", e))?;
+    let mut contents = vec![Object::Reference(stream_id)];
+    match page.get(b"Contents") {
+        Ok(Object::Array(existing)) => contents.extend(existing.iter().cloned()),
+        Ok(existing @ Object::Reference(_)) => contents.push(existing.clone()),
+        _ => {}
+    }
+    page.set("Contents", contents);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_matrix_scales_letter_to_a4() {
+        let letter = [0.0, 0.0, 612.0, 792.0];
+        let a4 = [0.0, 0.0, 595.0, 842.0];
+        let matrix: Vec<f32> = fit_matrix(&letter, &a4)
+            .iter()
+            .map(|v| v.as_float().unwrap())
+            .collect();
+        assert!((matrix[0] - 595.0 / 612.0).abs() < 1e-5);
+        assert!((matrix[3] - 842.0 / 792.0).abs() < 1e-5);
+        assert_eq!(matrix[4], 0.0);
+        assert_eq!(matrix[5], 0.0);
+    }
+
+    #[test]
+    fn test_pdf_background_detected_by_extension() {
+        assert!(is_pdf_background(Path::new("letterhead.PDF")));
+        assert!(!is_pdf_background(Path::new("letterhead.png")));
+    }
+}
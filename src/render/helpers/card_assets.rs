@@ -14,6 +14,9 @@ use crate::model::{Rank, Suit, RANKS_DISPLAY_ORDER, SUITS_DISPLAY_ORDER};
 pub const CARD_WIDTH_MM: f32 = 58.94;
 pub const CARD_HEIGHT_MM: f32 = 85.61;
 
+/// Face-down card back, same size as the faces
+const CARD_BACK_SVG: &str = include_str!("../../../assets/cards/back.svg");
+
 /// DPI used for SVG parsing (matches printpdf default)
 const SVG_DPI: f32 = 300.0;
 
@@ -27,33 +30,72 @@ impl CardAssets {
     ///
     /// SVGs are embedded at compile time from assets/cards/
     pub fn load(doc: &mut PdfDocument) -> Result<Self, CardLoadError> {
-        let mut cards = HashMap::new();
+        let all = SUITS_DISPLAY_ORDER.into_iter().flat_map(|suit| {
+            RANKS_DISPLAY_ORDER
+                .into_iter()
+                .map(move |rank| (suit, rank))
+        });
+        Self::load_cards(doc, all)
+    }
+
+    /// Load only the given cards, each once, so a document embeds just the
+    /// faces it draws
+    pub fn load_cards(
+        doc: &mut PdfDocument,
+        cards: impl IntoIterator<Item = (Suit, Rank)>,
+    ) -> Result<Self, CardLoadError> {
+        let mut loaded = HashMap::new();
         let mut warnings: Vec<PdfWarnMsg> = Vec::new();
 
-        // Load all 52 cards
-        for suit in SUITS_DISPLAY_ORDER {
-            for rank in RANKS_DISPLAY_ORDER {
-                let svg_content = get_card_svg(suit, rank)?;
-                let xobject = Svg::parse(svg_content, &mut warnings).map_err(|e| {
-                    CardLoadError::SvgParseError {
-                        suit,
-                        rank,
-                        message: e,
-                    }
-                })?;
-                let id = doc.add_xobject(&xobject);
-                cards.insert((suit, rank), id);
+        for (suit, rank) in cards {
+            if loaded.contains_key(&(suit, rank)) {
+                continue;
             }
+            let svg_content = get_card_svg(suit, rank)?;
+            let xobject = Svg::parse(svg_content, &mut warnings).map_err(|e| {
+                CardLoadError::SvgParseError {
+                    suit,
+                    rank,
+                    message: e,
+                }
+            })?;
+            let id = doc.add_xobject(&xobject);
+            loaded.insert((suit, rank), id);
         }
 
-        Ok(Self { cards })
+        Ok(Self { cards: loaded })
     }
 
-    /// Get the XObjectId for a specific card
+    /// Load the face-down card back as an XObject
+    ///
+    /// Separate from `load` so layouts that only need backs (hidden hands in
+    /// text diagrams) don't embed all 52 faces.
+    pub fn load_back(doc: &mut PdfDocument) -> Result<XObjectId, CardLoadError> {
+        let mut warnings: Vec<PdfWarnMsg> = Vec::new();
+        let xobject =
+            Svg::parse(CARD_BACK_SVG, &mut warnings).map_err(CardLoadError::BackParseError)?;
+        Ok(doc.add_xobject(&xobject))
+    }
+
+    /// Get the XObjectId for a specific card, which must have been loaded
     pub fn get(&self, suit: Suit, rank: Rank) -> &XObjectId {
         self.cards
             .get(&(suit, rank))
-            .expect("All cards should be loaded")
+            .expect("Card drawn should have been loaded")
+    }
+
+    /// Whether the card's image was loaded
+    pub fn contains(&self, suit: Suit, rank: Rank) -> bool {
+        self.cards.contains_key(&(suit, rank))
+    }
+
+    /// Number of card faces loaded
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
     }
 
     /// Get card dimensions in mm at a given scale factor
@@ -79,27 +121,35 @@ impl CardAssets {
         scale: f32,
         rotate_degrees: f32,
     ) -> XObjectTransform {
-        // Convert mm to points (1 mm = 2.834645669 pt)
-        let mm_to_pt = 2.834_645_7;
-
-        let rotate = if rotate_degrees.abs() < 0.001 {
-            None
-        } else {
-            Some(printpdf::XObjectRotation {
-                angle_ccw_degrees: rotate_degrees,
-                rotation_center_x: Px(0),
-                rotation_center_y: Px(0),
-            })
-        };
-
-        XObjectTransform {
-            translate_x: Some(Pt(x_mm * mm_to_pt)),
-            translate_y: Some(Pt(y_mm * mm_to_pt)),
-            scale_x: Some(scale),
-            scale_y: Some(scale),
-            rotate,
-            dpi: Some(SVG_DPI),
-        }
+        card_transform(x_mm, y_mm, scale, rotate_degrees)
+    }
+}
+
+/// Create an XObjectTransform for placing any card image (face or back)
+///
+/// Position is the bottom-left corner of the card in mm.
+/// Rotation is in degrees, counter-clockwise around the card's bottom-left corner.
+pub fn card_transform(x_mm: f32, y_mm: f32, scale: f32, rotate_degrees: f32) -> XObjectTransform {
+    // Convert mm to points (1 mm = 2.834645669 pt)
+    let mm_to_pt = 2.834_645_7;
+
+    let rotate = if rotate_degrees.abs() < 0.001 {
+        None
+    } else {
+        Some(printpdf::XObjectRotation {
+            angle_ccw_degrees: rotate_degrees,
+            rotation_center_x: Px(0),
+            rotation_center_y: Px(0),
+        })
+    };
+
+    XObjectTransform {
+        translate_x: Some(Pt(x_mm * mm_to_pt)),
+        translate_y: Some(Pt(y_mm * mm_to_pt)),
+        scale_x: Some(scale),
+        scale_y: Some(scale),
+        rotate,
+        dpi: Some(SVG_DPI),
     }
 }
 
@@ -115,6 +165,7 @@ pub enum CardLoadError {
         rank: Rank,
         message: String,
     },
+    BackParseError(String),
 }
 
 impl std::fmt::Display for CardLoadError {
@@ -136,6 +187,9 @@ impl std::fmt::Display for CardLoadError {
                     message
                 )
             }
+            CardLoadError::BackParseError(message) => {
+                write!(f, "Failed to parse card back SVG: {}", message)
+            }
         }
     }
 }
@@ -209,3 +263,22 @@ fn get_card_svg(suit: Suit, rank: Rank) -> Result<&'static str, CardLoadError> {
     };
     Ok(svg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_only_requested_cards() {
+        let mut doc = PdfDocument::new("Test");
+        let cards = [
+            (Suit::Spades, Rank::Ace),
+            (Suit::Hearts, Rank::Two),
+            (Suit::Spades, Rank::Ace),
+        ];
+        let assets = CardAssets::load_cards(&mut doc, cards).unwrap();
+        assert_eq!(assets.len(), 2);
+        assert!(assets.contains(Suit::Hearts, Rank::Two));
+        assert!(!assets.contains(Suit::Clubs, Rank::King));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::model::Suit;
+use crate::cli::ColorTheme;
+use crate::config::Settings;
+use crate::model::{BidSuit, Suit};
 use printpdf::Rgb;
 
 /// Color provider for suit symbols
@@ -31,6 +33,39 @@ impl SuitColors {
         }
     }
 
+    /// Suit colors from settings, including four-color deck colors
+    pub fn from_settings(settings: &Settings) -> Self {
+        let rgb = |(r, g, b): (f32, f32, f32)| Rgb::new(r, g, b, None);
+        Self {
+            spades: rgb(settings.black_color),
+            hearts: rgb(settings.red_color),
+            diamonds: rgb(settings.diamond_color),
+            clubs: rgb(settings.club_color),
+        }
+    }
+
+    /// Color for a bid strain (no-trump is black)
+    pub fn for_bid_suit(&self, suit: &BidSuit) -> Rgb {
+        match suit {
+            BidSuit::Spades => self.spades.clone(),
+            BidSuit::Hearts => self.hearts.clone(),
+            BidSuit::Diamonds => self.diamonds.clone(),
+            BidSuit::Clubs => self.clubs.clone(),
+            BidSuit::NoTrump => BLACK,
+        }
+    }
+
+    /// Color for a suit symbol character (other characters are black)
+    pub fn for_symbol(&self, symbol: char) -> Rgb {
+        match symbol {
+            '♠' => self.spades.clone(),
+            '♥' => self.hearts.clone(),
+            '♦' => self.diamonds.clone(),
+            '♣' => self.clubs.clone(),
+            _ => BLACK,
+        }
+    }
+
     pub fn for_suit(&self, suit: &Suit) -> Rgb {
         match suit {
             Suit::Spades => self.spades.clone(),
@@ -41,6 +76,118 @@ impl SuitColors {
     }
 }
 
+/// Colors for page furniture, chosen with `--theme`
+///
+/// Suit colors are not part of the theme here: a theme's suit colors are
+/// folded into `Settings` so `--suit-colors` can override them.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Compass rose fill in hand diagrams
+    pub compass: Rgb,
+    /// Vulnerable arms of the compass (--vulnerable-compass)
+    pub vulnerable: Rgb,
+    /// Lines between boards
+    pub separator: Rgb,
+    /// Thick lines between declarer's plan quadrants
+    pub heavy_separator: Rgb,
+    /// Cell borders in dealer summaries
+    pub border: Rgb,
+    /// Bidding sheet banners for North, South, other players and the answers page
+    pub banner_north: Rgb,
+    pub banner_south: Rgb,
+    pub banner_other: Rgb,
+    pub banner_answers: Rgb,
+    /// Header fills in the winners/losers tables
+    pub table_header: Rgb,
+    pub table_subheader: Rgb,
+    /// Opening lead box background
+    pub lead_box: Rgb,
+    /// Debug outlines for components, boards and page margins
+    pub debug_box: Rgb,
+    pub debug_board_box: Rgb,
+    pub debug_margin: Rgb,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::for_theme(ColorTheme::Classic)
+    }
+}
+
+impl Theme {
+    /// Colors for a named theme
+    pub fn for_theme(theme: ColorTheme) -> Self {
+        let rgb = |r, g, b| Rgb::new(r, g, b, None);
+        let classic = Self {
+            compass: GREEN,
+            vulnerable: rgb(0.85, 0.0, 0.0),
+            separator: rgb(0.4, 0.4, 0.4),
+            heavy_separator: rgb(0.3, 0.3, 0.3),
+            border: BLACK,
+            banner_north: rgb(0.12, 0.56, 1.0), // DodgerBlue
+            banner_south: rgb(1.0, 0.65, 0.0),  // Orange
+            banner_other: GRAY,
+            banner_answers: rgb(0.3, 0.3, 0.3),
+            table_header: rgb(0.678, 0.784, 0.902), // #ADC8E6 approximately
+            table_subheader: rgb(0.745, 0.847, 0.745), // #BED8BE approximately
+            lead_box: rgb(1.0, 1.0, 0.7),
+            debug_box: rgb(0.7, 0.7, 0.7),
+            debug_board_box: rgb(1.0, 0.5, 0.0),
+            debug_margin: BLUE,
+        };
+
+        match theme {
+            ColorTheme::Classic => classic,
+            ColorTheme::HighContrast => Self {
+                compass: BLACK,
+                separator: BLACK,
+                heavy_separator: BLACK,
+                banner_north: rgb(0.0, 0.0, 0.6),
+                banner_south: rgb(0.6, 0.2, 0.0),
+                banner_other: rgb(0.2, 0.2, 0.2),
+                banner_answers: BLACK,
+                table_header: rgb(0.8, 0.8, 0.8),
+                table_subheader: rgb(0.8, 0.8, 0.8),
+                lead_box: rgb(1.0, 1.0, 0.5),
+                ..classic
+            },
+            ColorTheme::Pastel => Self {
+                compass: rgb(0.45, 0.7, 0.55),
+                vulnerable: rgb(0.9, 0.5, 0.5),
+                separator: rgb(0.6, 0.6, 0.7),
+                heavy_separator: rgb(0.55, 0.55, 0.65),
+                border: rgb(0.4, 0.4, 0.5),
+                banner_north: rgb(0.55, 0.7, 0.95),
+                banner_south: rgb(0.95, 0.7, 0.5),
+                banner_other: rgb(0.7, 0.7, 0.75),
+                banner_answers: rgb(0.55, 0.55, 0.65),
+                table_header: rgb(0.85, 0.9, 1.0),
+                table_subheader: rgb(0.88, 0.95, 0.88),
+                lead_box: rgb(1.0, 0.97, 0.85),
+                ..classic
+            },
+            ColorTheme::ClubBranded => Self {
+                compass: rgb(0.0, 0.2, 0.4),
+                separator: rgb(0.0, 0.2, 0.4),
+                heavy_separator: rgb(0.0, 0.2, 0.4),
+                banner_north: rgb(0.0, 0.2, 0.4),
+                banner_south: rgb(0.75, 0.6, 0.2),
+                banner_other: rgb(0.35, 0.45, 0.55),
+                banner_answers: rgb(0.0, 0.2, 0.4),
+                table_header: rgb(0.8, 0.85, 0.92),
+                table_subheader: rgb(0.95, 0.9, 0.75),
+                lead_box: rgb(0.98, 0.94, 0.8),
+                ..classic
+            },
+        }
+    }
+
+    /// Theme selected in settings
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self::for_theme(settings.theme)
+    }
+}
+
 /// Standard colors
 pub const BLACK: Rgb = Rgb {
     r: 0.0,
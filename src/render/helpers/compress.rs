@@ -1,21 +1,40 @@
 //! PDF compression helper
 //!
-//! Uses lopdf to compress PDF streams after printpdf generates uncompressed output.
+//! Uses lopdf to compress PDF streams after printpdf generates uncompressed output,
+//! at the `--compress` level, and to fold repeated copies of the same image or
+//! font into one shared object.
 
-use std::io::Cursor;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::{Cursor, Write};
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+
+use crate::cli::CompressionLevel;
 
 /// Compress PDF streams to reduce file size.
 ///
 /// This is a post-processing step needed because printpdf doesn't compress
 /// its output streams. We parse the PDF bytes with lopdf, compress all
-/// streams, and re-save.
-pub fn compress_pdf(uncompressed: Vec<u8>) -> Result<Vec<u8>, String> {
+/// streams, and re-save. Card images and logos the pages repeat are
+/// embedded once; see [dedup_xobjects]. With [CompressionLevel::None] only
+/// that sharing is done and the streams are left as they are.
+pub fn compress_pdf(uncompressed: Vec<u8>, level: CompressionLevel) -> Result<Vec<u8>, String> {
     // Parse the uncompressed PDF
-    let mut doc = lopdf::Document::load_mem(&uncompressed)
+    let mut doc = Document::load_mem(&uncompressed)
         .map_err(|e| format!("Failed to parse PDF for compression: {}", e))?;
 
+    dedup_xobjects(&mut doc);
+
     // Compress all streams
-    doc.compress();
+    match level {
+        CompressionLevel::None => {}
+        CompressionLevel::Fast => compress_streams(&mut doc, Compression::fast()),
+        CompressionLevel::Best => compress_streams(&mut doc, Compression::best()),
+    }
 
     // Save to bytes
     let mut output = Cursor::new(Vec::new());
@@ -24,3 +43,276 @@ pub fn compress_pdf(uncompressed: Vec<u8>) -> Result<Vec<u8>, String> {
 
     Ok(output.into_inner())
 }
+
+/// Flate-compress every stream that isn't already filtered, where that
+/// makes it smaller
+fn compress_streams(doc: &mut Document, level: Compression) {
+    for object in doc.objects.values_mut() {
+        let Object::Stream(stream) = object else {
+            continue;
+        };
+        if !stream.allows_compression || stream.dict.has(b"Filter") {
+            continue;
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), level);
+        let compressed = encoder
+            .write_all(&stream.content)
+            .and_then(|_| encoder.finish());
+        if let Ok(compressed) = compressed {
+            if compressed.len() < stream.content.len() {
+                stream.dict.set("Filter", "FlateDecode");
+                stream.set_content(compressed);
+            }
+        }
+    }
+}
+
+/// Replace identical image and form XObjects with one shared copy, pointing
+/// every page's resources at it. Returns the number of copies removed.
+///
+/// Repeats until nothing changes, since forms become identical once the
+/// images inside them are shared.
+pub fn dedup_xobjects(doc: &mut Document) -> usize {
+    dedup(
+        doc,
+        |_, object| matches!(object, Object::Stream(stream) if is_xobject(stream)),
+    )
+}
+
+/// Keys of a font or font descriptor dictionary that refer to streams
+const FONT_STREAM_KEYS: [&[u8]; 5] = [
+    b"FontFile",
+    b"FontFile2",
+    b"FontFile3",
+    b"ToUnicode",
+    b"CIDToGIDMap",
+];
+
+/// Replace identical fonts (dictionaries, descriptors, font files and
+/// ToUnicode maps) with one shared copy, as [dedup_xobjects] does for images.
+/// Returns the number of copies removed.
+///
+/// PDFs joined from chunks each embed the fonts they use; a font embedded in
+/// full, or subset to the same glyphs, is then kept once.
+pub fn dedup_fonts(doc: &mut Document) -> usize {
+    let font_streams: HashSet<ObjectId> = doc
+        .objects
+        .values()
+        .filter_map(|object| object.as_dict().ok().filter(|dict| is_font(dict)))
+        .flat_map(|dict| {
+            FONT_STREAM_KEYS
+                .iter()
+                .filter_map(|key| dict.get(key).and_then(Object::as_reference).ok())
+        })
+        .collect();
+    dedup(doc, |id, object| match object {
+        Object::Stream(_) => font_streams.contains(&id),
+        Object::Dictionary(dict) => is_font(dict),
+        _ => false,
+    })
+}
+
+fn is_font(dict: &Dictionary) -> bool {
+    matches!(
+        dict.get(b"Type").and_then(Object::as_name),
+        Ok(b"Font") | Ok(b"FontDescriptor")
+    )
+}
+
+/// Replace objects `candidate` accepts that repeat an earlier one with it,
+/// until nothing changes
+fn dedup(doc: &mut Document, candidate: impl Fn(ObjectId, &Object) -> bool) -> usize {
+    let mut removed = 0;
+    loop {
+        let duplicates = find_duplicates(doc, &candidate);
+        if duplicates.is_empty() {
+            return removed;
+        }
+        for id in duplicates.keys() {
+            doc.objects.remove(id);
+        }
+        for object in doc.objects.values_mut() {
+            replace_references(object, &duplicates);
+        }
+        replace_references_in_dict(&mut doc.trailer, &duplicates);
+        removed += duplicates.len();
+    }
+}
+
+/// Each candidate object that repeats an earlier one, mapped to the earlier one
+fn find_duplicates(
+    doc: &Document,
+    candidate: &impl Fn(ObjectId, &Object) -> bool,
+) -> BTreeMap<ObjectId, ObjectId> {
+    let mut seen: HashMap<u64, Vec<ObjectId>> = HashMap::new();
+    let mut duplicates = BTreeMap::new();
+    for (&id, object) in &doc.objects {
+        if !candidate(id, object) {
+            continue;
+        }
+        let candidates = seen.entry(object_hash(object)).or_default();
+        let original = candidates.iter().copied().find(|other| {
+            doc.objects
+                .get(other)
+                .is_some_and(|o| same_object(o, object))
+        });
+        match original {
+            Some(original) => {
+                duplicates.insert(id, original);
+            }
+            None => candidates.push(id),
+        }
+    }
+    duplicates
+}
+
+fn is_xobject(stream: &Stream) -> bool {
+    let name = |key: &[u8]| stream.dict.get(key).and_then(Object::as_name).ok();
+    name(b"Type") == Some(b"XObject".as_slice())
+        || matches!(name(b"Subtype"), Some(b"Image") | Some(b"Form"))
+}
+
+fn object_hash(object: &Object) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    match object {
+        Object::Stream(stream) => {
+            format!("{:?}", stream.dict).hash(&mut hasher);
+            stream.content.hash(&mut hasher);
+        }
+        other => format!("{:?}", other).hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+fn same_object(a: &Object, b: &Object) -> bool {
+    match (a, b) {
+        (Object::Stream(a), Object::Stream(b)) => {
+            a.content == b.content && format!("{:?}", a.dict) == format!("{:?}", b.dict)
+        }
+        (Object::Stream(_), _) | (_, Object::Stream(_)) => false,
+        (a, b) => format!("{:?}", a) == format!("{:?}", b),
+    }
+}
+
+fn replace_references(object: &mut Object, map: &BTreeMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(&original) = map.get(id) {
+                *id = original;
+            }
+        }
+        Object::Array(items) => {
+            for item in items {
+                replace_references(item, map);
+            }
+        }
+        Object::Dictionary(dict) => replace_references_in_dict(dict, map),
+        Object::Stream(stream) => replace_references_in_dict(&mut stream.dict, map),
+        _ => {}
+    }
+}
+
+fn replace_references_in_dict(dict: &mut Dictionary, map: &BTreeMap<ObjectId, ObjectId>) {
+    for (_, value) in dict.iter_mut() {
+        replace_references(value, map);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    fn image(doc: &mut Document, pixel: u8) -> ObjectId {
+        let dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => 1,
+            "Height" => 1,
+            "ColorSpace" => "DeviceGray",
+            "BitsPerComponent" => 8,
+        };
+        doc.add_object(Stream::new(dict, vec![pixel]))
+    }
+
+    #[test]
+    fn test_compression_levels() {
+        let mut doc = Document::with_version("1.7");
+        let content = doc.add_object(Stream::new(
+            Dictionary::new(),
+            b"0 0 m 10 10 l S\n".repeat(100),
+        ));
+
+        compress_streams(&mut doc, Compression::best());
+        let stream = doc.get_object(content).unwrap().as_stream().unwrap();
+        assert_eq!(
+            stream.dict.get(b"Filter").unwrap().as_name().unwrap(),
+            b"FlateDecode"
+        );
+        assert!(stream.content.len() < 1600);
+
+        // Already-compressed streams are left alone
+        let before = stream.content.clone();
+        compress_streams(&mut doc, Compression::fast());
+        let stream = doc.get_object(content).unwrap().as_stream().unwrap();
+        assert_eq!(stream.content, before);
+    }
+
+    #[test]
+    fn test_dedup_xobjects() {
+        let mut doc = Document::with_version("1.7");
+        let first = image(&mut doc, 0);
+        let copy = image(&mut doc, 0);
+        let other = image(&mut doc, 255);
+        let resources = doc.add_object(dictionary! {
+            "XObject" => dictionary! { "A" => first, "B" => copy, "C" => other },
+        });
+
+        assert_eq!(dedup_xobjects(&mut doc), 1);
+        assert!(doc.get_object(copy).is_err());
+        let xobjects = doc
+            .get_dictionary(resources)
+            .unwrap()
+            .get(b"XObject")
+            .and_then(Object::as_dict)
+            .unwrap();
+        assert_eq!(xobjects.get(b"B").unwrap().as_reference().unwrap(), first);
+        assert_eq!(xobjects.get(b"C").unwrap().as_reference().unwrap(), other);
+    }
+
+    #[test]
+    fn test_dedup_fonts() {
+        let mut doc = Document::with_version("1.7");
+        let font = |doc: &mut Document| {
+            let file = doc.add_object(Stream::new(
+                dictionary! { "Length1" => 4 },
+                vec![1, 2, 3, 4],
+            ));
+            let descriptor = doc.add_object(dictionary! {
+                "Type" => "FontDescriptor",
+                "FontName" => "DejaVuSans",
+                "FontFile2" => file,
+            });
+            doc.add_object(dictionary! {
+                "Type" => "Font",
+                "Subtype" => "TrueType",
+                "FontDescriptor" => descriptor,
+            })
+        };
+        let first = font(&mut doc);
+        let copy = font(&mut doc);
+        let resources = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => first, "F2" => copy },
+        });
+
+        // The font file, then its descriptor, then the font itself
+        assert_eq!(dedup_fonts(&mut doc), 3);
+        let fonts = doc
+            .get_dictionary(resources)
+            .unwrap()
+            .get(b"Font")
+            .and_then(Object::as_dict)
+            .unwrap();
+        assert_eq!(fonts.get(b"F2").unwrap().as_reference().unwrap(), first);
+    }
+}
@@ -0,0 +1,63 @@
+//! Double-sided printing (`--duplex`)
+//!
+//! Every layout's output is padded with a blank page to an even page count, so
+//! the next document printed starts on a new sheet. With `short`, the printer
+//! turns the sheet over on its short edge, so back pages (2, 4, ...) are given
+//! a 180° /Rotate with lopdf after saving; that way a back reads the right way
+//! up when the sheet is turned over like a book page, and form fields and links
+//! turn with the page.
+
+use std::io::Cursor;
+
+use lopdf::{Document, Object};
+use printpdf::{Mm, PdfPage};
+
+use crate::cli::Duplex;
+use crate::config::Settings;
+use crate::error::RenderError;
+
+/// Add a blank page if needed so a duplex document ends on the back of a sheet
+pub fn pad_for_duplex(pages: &mut Vec<PdfPage>, settings: &Settings) {
+    // Only the end of the whole document is padded, not each chunk of it
+    if settings.chunk.is_some_and(|chunk| !chunk.last) {
+        return;
+    }
+    let page_count = settings.first_page() - 1 + pages.len();
+    if settings.duplex.is_some_and(|duplex| duplex != Duplex::None) && page_count % 2 == 1 {
+        pages.push(PdfPage::new(
+            Mm(settings.page_width),
+            Mm(settings.page_height),
+            Vec::new(),
+        ));
+    }
+}
+
+/// Turn the back pages of a saved PDF upside down for short-edge duplex
+pub fn rotate_back_pages(pdf: Vec<u8>, settings: &Settings) -> Result<Vec<u8>, RenderError> {
+    if settings.duplex != Some(Duplex::Short) {
+        return Ok(pdf);
+    }
+    rotate_even_pages(pdf, settings.first_page()).map_err(RenderError::PdfGeneration)
+}
+
+/// Rotate the pages that are even-numbered in the whole document, whose
+/// first page is `first_page` in it
+fn rotate_even_pages(pdf: Vec<u8>, first_page: usize) -> Result<Vec<u8>, String> {
+    let mut doc =
+        Document::load_mem(&pdf).map_err(|e| format!("Failed to parse PDF for duplex: {}", e))?;
+
+    // get_pages is keyed by page number, starting at 1
+    for (&number, &page_id) in doc.get_pages().iter() {
+        if (number as usize + first_page - 1) % 2 == 0 {
+            doc.get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("Failed to access page for duplex: {}", e))?
+                .set("Rotate", 180);
+        }
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    doc.save_to(&mut output)
+        .map_err(|e| format!("Failed to save duplex PDF: {}", e))?;
+    Ok(output.into_inner())
+}
@@ -1,5 +1,14 @@
-use crate::error::RenderError;
-use printpdf::{BuiltinFont, FontId, ParsedFont, PdfDocument};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::cli::FontEmbedding;
+use crate::config::settings::CustomFonts;
+use crate::config::Settings;
+use crate::error::{RenderError, RenderWarning};
+use crate::render::helpers::text_metrics::{
+    get_builtin_measurer, BuiltinFontMeasurer, EmbeddedFontMeasurer, TextMeasure,
+};
+use printpdf::{BuiltinFont, FontId, ParsedFont, PdfDocument, PdfSaveOptions};
 
 // Only embed a minimal DejaVu Sans subset for suit symbols (♠♥♦♣)
 // Regular text uses PDF builtin fonts (Times-Roman, Helvetica)
@@ -62,6 +71,115 @@ impl BuiltinFontSet {
     }
 }
 
+/// A font for drawing text: a PDF builtin font or an embedded font file
+#[derive(Debug, Clone)]
+pub enum TextFont {
+    Builtin(BuiltinFont),
+    Embedded(EmbeddedFont),
+}
+
+/// A TrueType/OpenType font embedded in the document, with its metrics
+#[derive(Debug, Clone)]
+pub struct EmbeddedFont {
+    pub id: FontId,
+    pub metrics: Arc<EmbeddedFontMeasurer>,
+}
+
+impl TextFont {
+    /// Measurer using this font's own metrics
+    pub fn measurer(&self) -> &dyn TextMeasure {
+        match self {
+            TextFont::Builtin(font) => get_builtin_measurer(*font),
+            TextFont::Embedded(font) => font.metrics.as_ref(),
+        }
+    }
+
+    /// Measurer for an embedded font, or the given builtin metrics otherwise.
+    ///
+    /// Components whose layout was tuned against specific builtin metrics use
+    /// this so builtin output is unchanged.
+    pub fn measurer_or(&self, builtin: &'static BuiltinFontMeasurer) -> &dyn TextMeasure {
+        match self {
+            TextFont::Builtin(_) => builtin,
+            TextFont::Embedded(font) => font.metrics.as_ref(),
+        }
+    }
+}
+
+impl From<BuiltinFont> for TextFont {
+    fn from(font: BuiltinFont) -> Self {
+        TextFont::Builtin(font)
+    }
+}
+
+/// Regular, bold, italic and bold-italic fonts for one kind of text
+#[derive(Debug, Clone)]
+pub struct TextFontSet {
+    pub regular: TextFont,
+    pub bold: TextFont,
+    pub italic: TextFont,
+    pub bold_italic: TextFont,
+}
+
+impl From<BuiltinFontSet> for TextFontSet {
+    fn from(set: BuiltinFontSet) -> Self {
+        Self {
+            regular: set.regular.into(),
+            bold: set.bold.into(),
+            italic: set.italic.into(),
+            bold_italic: set.bold_italic.into(),
+        }
+    }
+}
+
+/// Kinds of text that can use a custom font file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontRole {
+    Diagram,
+    Commentary,
+    HandRecord,
+}
+
+impl FontRole {
+    /// All roles, in option order
+    pub const ALL: [FontRole; 3] = [
+        FontRole::Diagram,
+        FontRole::Commentary,
+        FontRole::HandRecord,
+    ];
+
+    /// The command-line option that sets this role's font
+    pub fn option(self) -> &'static str {
+        match self {
+            FontRole::Diagram => "--font-diagram",
+            FontRole::Commentary => "--font-commentary",
+            FontRole::HandRecord => "--font-hand-record",
+        }
+    }
+}
+
+/// Warnings for custom fonts the settings give that a layout drawing only
+/// the `used` kinds of text in them would leave out
+pub fn unused_custom_fonts(settings: &Settings, used: &[FontRole]) -> Vec<RenderWarning> {
+    if settings.font_embedding == FontEmbedding::Builtin {
+        return Vec::new();
+    }
+    let custom = &settings.custom_fonts;
+    FontRole::ALL
+        .into_iter()
+        .filter(|role| !used.contains(role))
+        .filter(|role| {
+            let paths = match role {
+                FontRole::Diagram => &custom.diagram,
+                FontRole::Commentary => &custom.commentary,
+                FontRole::HandRecord => &custom.hand_record,
+            };
+            !paths.is_empty()
+        })
+        .map(|role| RenderWarning::UnusedFont(role.option().to_string()))
+        .collect()
+}
+
 /// Legacy: A set of external fonts (for backwards compatibility during migration)
 #[derive(Clone)]
 pub struct FontSet {
@@ -75,6 +193,8 @@ pub struct FontSet {
 ///
 /// Uses PDF builtin fonts (Times-Roman, Helvetica) for regular text
 /// and an embedded DejaVu Sans font for suit symbols (♠♥♦♣).
+/// Custom font files can replace the builtin fonts for diagram,
+/// commentary and hand record text.
 #[derive(Clone)]
 pub struct FontManager {
     /// Suit symbol font (DejaVu Sans with Unicode suit symbols)
@@ -83,6 +203,12 @@ pub struct FontManager {
     pub sans: BuiltinFontSet,
     /// Builtin serif fonts (Times family)
     pub serif: BuiltinFontSet,
+    /// Custom diagram fonts (--font-diagram)
+    pub diagram: Option<TextFontSet>,
+    /// Custom commentary fonts (--font-commentary)
+    pub commentary: Option<TextFontSet>,
+    /// Custom hand record fonts (--font-hand-record)
+    pub hand_record: Option<TextFontSet>,
 }
 
 impl FontManager {
@@ -101,9 +227,52 @@ impl FontManager {
             symbol_font,
             sans: BuiltinFontSet::helvetica(),
             serif: BuiltinFontSet::times(),
+            diagram: None,
+            commentary: None,
+            hand_record: None,
         })
     }
 
+    /// Load fonts, embedding any custom font files from the settings
+    pub fn with_custom_fonts(
+        doc: &mut PdfDocument,
+        custom: &CustomFonts,
+    ) -> Result<Self, RenderError> {
+        let mut fonts = Self::new(doc)?;
+        fonts.diagram = load_font_set(doc, &custom.diagram)?;
+        fonts.commentary = load_font_set(doc, &custom.commentary)?;
+        fonts.hand_record = load_font_set(doc, &custom.hand_record)?;
+        Ok(fonts)
+    }
+
+    /// Load the fonts the settings ask for: their custom font files, unless
+    /// only builtin fonts are wanted
+    pub fn for_settings(doc: &mut PdfDocument, settings: &Settings) -> Result<Self, RenderError> {
+        match settings.font_embedding {
+            FontEmbedding::Builtin => Self::new(doc),
+            FontEmbedding::Subset | FontEmbedding::Full => {
+                Self::with_custom_fonts(doc, &settings.custom_fonts)
+            }
+        }
+    }
+
+    /// Get the fonts for a kind of text: its custom fonts if loaded,
+    /// otherwise the builtin set for the PBN font specification
+    pub fn text_set_for_role(
+        &self,
+        role: FontRole,
+        spec: Option<&crate::model::FontSpec>,
+    ) -> TextFontSet {
+        let custom = match role {
+            FontRole::Diagram => &self.diagram,
+            FontRole::Commentary => &self.commentary,
+            FontRole::HandRecord => &self.hand_record,
+        };
+        custom
+            .clone()
+            .unwrap_or_else(|| self.builtin_set_for_spec(spec).into())
+    }
+
     /// Get the builtin font set for a given family
     pub fn builtin_family(&self, family: FontFamily) -> BuiltinFontSet {
         match family {
@@ -142,6 +311,77 @@ impl FontManager {
     }
 }
 
+/// Options for saving a document with the given font embedding.
+///
+/// The builtin fonts are never embedded; subsetting applies to the suit
+/// symbol font and any custom font files.
+pub fn save_options(embedding: FontEmbedding) -> PdfSaveOptions {
+    PdfSaveOptions {
+        subset_fonts: embedding != FontEmbedding::Full,
+        ..Default::default()
+    }
+}
+
+/// Load a font set from up to four files (regular, bold, italic, bold-italic).
+///
+/// Missing styles fall back to regular, and bold-italic to bold.
+fn load_font_set(
+    doc: &mut PdfDocument,
+    paths: &[PathBuf],
+) -> Result<Option<TextFontSet>, RenderError> {
+    let fonts = paths
+        .iter()
+        .take(4)
+        .map(|path| load_font_file(doc, path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let Some(regular) = fonts.first().cloned() else {
+        return Ok(None);
+    };
+    let bold = fonts.get(1).cloned().unwrap_or_else(|| regular.clone());
+    let italic = fonts.get(2).cloned().unwrap_or_else(|| regular.clone());
+    let bold_italic = fonts.get(3).cloned().unwrap_or_else(|| bold.clone());
+
+    Ok(Some(TextFontSet {
+        regular,
+        bold,
+        italic,
+        bold_italic,
+    }))
+}
+
+/// Embed a TrueType/OpenType font file in the document
+fn load_font_file(doc: &mut PdfDocument, path: &Path) -> Result<TextFont, RenderError> {
+    let data = read_font_file(path)?;
+
+    let metrics = EmbeddedFontMeasurer::from_bytes(&data).ok_or_else(|| {
+        RenderError::FontLoad(format!("{}: not a TrueType/OpenType font", path.display()))
+    })?;
+
+    let mut warnings = Vec::new();
+    let parsed = ParsedFont::from_bytes(&data, 0, &mut warnings)
+        .ok_or_else(|| RenderError::FontLoad(format!("Failed to parse {}", path.display())))?;
+
+    Ok(TextFont::Embedded(EmbeddedFont {
+        id: doc.add_font(&parsed),
+        metrics: Arc::new(metrics),
+    }))
+}
+
+#[cfg(feature = "fs")]
+fn read_font_file(path: &Path) -> Result<Vec<u8>, RenderError> {
+    std::fs::read(path).map_err(|e| RenderError::FontLoad(format!("{}: {}", path.display(), e)))
+}
+
+/// Without the `fs` feature only the builtin and embedded fonts are available
+#[cfg(not(feature = "fs"))]
+fn read_font_file(path: &Path) -> Result<Vec<u8>, RenderError> {
+    Err(RenderError::FontLoad(format!(
+        "{}: font files can't be read in this build",
+        path.display()
+    )))
+}
+
 /// Convert a suit to its display character
 pub fn suit_char(suit: &crate::model::Suit) -> char {
     match suit {
@@ -161,3 +401,27 @@ pub fn suit_text(suit: &crate::model::Suit) -> &'static str {
         crate::model::Suit::Clubs => "C",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unused_custom_fonts() {
+        let mut settings = Settings {
+            font_embedding: FontEmbedding::Subset,
+            ..Default::default()
+        };
+        settings.custom_fonts.diagram = vec![PathBuf::from("diagram.ttf")];
+        settings.custom_fonts.commentary = vec![PathBuf::from("notes.ttf")];
+
+        assert_eq!(
+            unused_custom_fonts(&settings, &[FontRole::Commentary]),
+            [RenderWarning::UnusedFont("--font-diagram".to_string())]
+        );
+        assert!(unused_custom_fonts(&settings, &FontRole::ALL).is_empty());
+
+        settings.font_embedding = FontEmbedding::Builtin;
+        assert!(unused_custom_fonts(&settings, &[]).is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! Interactive PDF form fields
+//!
+//! printpdf has no AcroForm support, so fill-in fields are collected while
+//! rendering (see `LayerBuilder::add_text_field`) and injected afterwards with
+//! lopdf, the same way `compress_pdf` post-processes the saved document.
+
+use std::io::Cursor;
+
+use lopdf::{dictionary, Document, Object, ObjectId};
+
+use super::links::existing_annotations;
+
+/// Conversion factor from millimeters to PDF points
+const MM_TO_PT: f32 = 72.0 / 25.4;
+
+/// A single-line text field placed over a fill-in area of the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextField {
+    /// Base name for the field; made unique per document when injected
+    pub name: String,
+    /// Lower-left x coordinate in mm
+    pub x: f32,
+    /// Lower-left y coordinate in mm
+    pub y: f32,
+    /// Field width in mm
+    pub width: f32,
+    /// Field height in mm
+    pub height: f32,
+    /// Font size in points for text typed into the field
+    pub font_size: f32,
+}
+
+impl TextField {
+    pub fn new(name: impl Into<String>, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            name: name.into(),
+            x,
+            y,
+            width,
+            height,
+            font_size: 0.0, // 0 = auto-size to the field height
+        }
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Field rectangle in PDF points: [llx, lly, urx, ury]
+    fn rect_pt(&self) -> Vec<Object> {
+        vec![
+            Object::Real(self.x * MM_TO_PT),
+            Object::Real(self.y * MM_TO_PT),
+            Object::Real((self.x + self.width) * MM_TO_PT),
+            Object::Real((self.y + self.height) * MM_TO_PT),
+        ]
+    }
+}
+
+/// Add AcroForm text fields to an already-saved PDF.
+///
+/// `page_fields[i]` holds the fields for page `i` (0-based). Field names are
+/// suffixed with the page number and position so they are unique within the
+/// document, otherwise viewers would mirror typed text between fields; the
+/// first page is numbered `first_page`, so the chunks of a long document
+/// don't repeat names.
+pub fn add_form_fields(
+    pdf: Vec<u8>,
+    page_fields: &[Vec<TextField>],
+    first_page: usize,
+) -> Result<Vec<u8>, String> {
+    if page_fields.iter().all(|fields| fields.is_empty()) {
+        return Ok(pdf);
+    }
+
+    let mut doc = Document::load_mem(&pdf)
+        .map_err(|e| format!("Failed to parse PDF for form fields: {}", e))?;
+
+    let pages: Vec<ObjectId> = doc.get_pages().values().copied().collect();
+    let mut field_refs = Vec::new();
+
+    for (page_idx, fields) in page_fields.iter().enumerate() {
+        let Some(&page_id) = pages.get(page_idx) else {
+            break;
+        };
+        if fields.is_empty() {
+            continue;
+        }
+
+        let mut annots = existing_annotations(&doc, page_id);
+
+        for (field_idx, field) in fields.iter().enumerate() {
+            let name = format!(
+                "{}_p{}_{}",
+                field.name,
+                first_page + page_idx,
+                field_idx + 1
+            );
+            let widget = dictionary! {
+                "Type" => "Annot",
+                "Subtype" => "Widget",
+                "FT" => "Tx",
+                "T" => Object::string_literal(name),
+                "Rect" => field.rect_pt(),
+                "F" => 4, // Print
+                "P" => page_id,
+                "DA" => Object::string_literal(format!("/Helv {} Tf 0 g", field.font_size)),
+            };
+            let widget_id = doc.add_object(widget);
+            annots.push(Object::Reference(widget_id));
+            field_refs.push(Object::Reference(widget_id));
+        }
+
+        let page = doc
+            .get_object_mut(page_id)
+            .and_then(Object::as_dict_mut)
+            .map_err(|e| format!("Failed to access page for form fields: {}", e))?;
+        page.set("Annots", annots);
+    }
+
+    let helv = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+        "Encoding" => "WinAnsiEncoding",
+    });
+    let acroform = doc.add_object(dictionary! {
+        "Fields" => field_refs,
+        "NeedAppearances" => true,
+        "DA" => Object::string_literal("/Helv 0 Tf 0 g"),
+        "DR" => dictionary! {
+            "Font" => dictionary! { "Helv" => helv },
+        },
+    });
+
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|e| format!("Failed to find PDF catalog: {}", e))?;
+    doc.get_object_mut(catalog_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|e| format!("Failed to access PDF catalog: {}", e))?
+        .set("AcroForm", acroform);
+
+    let mut output = Cursor::new(Vec::new());
+    doc.save_to(&mut output)
+        .map_err(|e| format!("Failed to save PDF with form fields: {}", e))?;
+
+    Ok(output.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_page_pdf() -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![
+                Object::Integer(0),
+                Object::Integer(0),
+                Object::Integer(612),
+                Object::Integer(792),
+            ],
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![Object::Reference(page_id)],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut output = Cursor::new(Vec::new());
+        doc.save_to(&mut output).unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn test_no_fields_returns_input_unchanged() {
+        let pdf = single_page_pdf();
+        let result = add_form_fields(pdf.clone(), &[Vec::new()], 1).unwrap();
+        assert_eq!(result, pdf);
+    }
+
+    #[test]
+    fn test_fields_added_to_acroform_and_page() {
+        let pdf = single_page_pdf();
+        let fields = vec![vec![
+            TextField::new("blank", 10.0, 20.0, 8.0, 5.0),
+            TextField::new("blank", 30.0, 20.0, 8.0, 5.0),
+        ]];
+        let result = add_form_fields(pdf, &fields, 1).unwrap();
+
+        let doc = Document::load_mem(&result).unwrap();
+        let catalog = doc.catalog().unwrap();
+        let acroform_id = catalog.get(b"AcroForm").unwrap().as_reference().unwrap();
+        let acroform = doc.get_dictionary(acroform_id).unwrap();
+        assert_eq!(
+            acroform.get(b"Fields").unwrap().as_array().unwrap().len(),
+            2
+        );
+
+        let page_id = *doc.get_pages().values().next().unwrap();
+        let page = doc.get_dictionary(page_id).unwrap();
+        let annots = page.get(b"Annots").unwrap().as_array().unwrap();
+        assert_eq!(annots.len(), 2);
+
+        let first = doc
+            .get_dictionary(annots[0].as_reference().unwrap())
+            .unwrap();
+        assert_eq!(first.get(b"T").unwrap().as_str().unwrap(), b"blank_p1_1");
+    }
+}
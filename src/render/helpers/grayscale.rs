@@ -0,0 +1,261 @@
+//! Grayscale (ink-saver) output
+//!
+//! Rather than teaching every renderer about `--grayscale`, finished pages are
+//! rewritten in one pass: every fill and stroke color is replaced by the gray of
+//! the same luminance, and mid-to-dark filled shapes (banners, the compass box)
+//! are lightened so they cost less toner. Pure black and white are kept, so text,
+//! QR codes and white lettering on banners still read cleanly.
+//!
+//! Card faces and backs, SVG logos and PDF backgrounds are form XObjects
+//! with content streams of their own, which the pages only refer to; once the
+//! PDF is saved, [grayscale_xobjects] converts their colors the same way
+//! (without lightening, so suit symbols keep their weight). Raster images
+//! keep their own colors.
+
+use std::io::Cursor;
+
+use lopdf::content::{Content, Operation};
+use lopdf::{Document, Object};
+use printpdf::{Cmyk, Color, Op, PdfPage, Rgb};
+
+/// Shapes darker than this are lightened; lighter ones are left alone
+const THIN_FILL_LIMIT: f32 = 0.6;
+
+/// How far a dark fill moves towards `THIN_FILL_LIMIT` (0 = unchanged, 1 = all the way)
+const THIN_FILL_AMOUNT: f32 = 0.5;
+
+/// Convert every page to grayscale
+pub fn apply_grayscale(pages: &mut [PdfPage]) {
+    for page in pages {
+        grayscale_ops(&mut page.ops);
+    }
+}
+
+/// Convert the colors in an operation stream to grayscale
+pub fn grayscale_ops(ops: &mut [Op]) {
+    for i in 0..ops.len() {
+        let (done, rest) = ops.split_at_mut(i + 1);
+        match &mut done[i] {
+            Op::SetFillColor { col } => {
+                let gray = luminance(col);
+                let gray = if fills_shape(rest) {
+                    thin_fill(gray)
+                } else {
+                    gray
+                };
+                *col = gray_color(gray);
+            }
+            Op::SetOutlineColor { col } => {
+                *col = gray_color(luminance(col));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert the colors in every form XObject of a saved PDF to grayscale
+pub fn grayscale_xobjects(pdf: Vec<u8>) -> Result<Vec<u8>, String> {
+    let mut doc = Document::load_mem(&pdf)
+        .map_err(|e| format!("Failed to parse PDF for grayscale: {}", e))?;
+
+    grayscale_forms(&mut doc);
+
+    let mut output = Cursor::new(Vec::new());
+    doc.save_to(&mut output)
+        .map_err(|e| format!("Failed to save grayscale PDF: {}", e))?;
+    Ok(output.into_inner())
+}
+
+/// Rewrite the content stream of every form XObject with gray colors;
+/// streams that can't be decoded are left as they are
+fn grayscale_forms(doc: &mut Document) {
+    for object in doc.objects.values_mut() {
+        let Object::Stream(stream) = object else {
+            continue;
+        };
+        let subtype = stream.dict.get(b"Subtype").and_then(Object::as_name).ok();
+        if !matches!(subtype, Some(b"Form")) {
+            continue;
+        }
+        let filtered = stream.dict.has(b"Filter");
+        let content = if filtered {
+            stream.decompressed_content()
+        } else {
+            Ok(stream.content.clone())
+        };
+        let Ok(mut content) = content.and_then(|bytes| Content::decode(&bytes)) else {
+            continue;
+        };
+        content.operations.iter_mut().for_each(gray_operation);
+        let Ok(bytes) = content.encode() else {
+            continue;
+        };
+        if filtered {
+            stream.dict.remove(b"Filter");
+            stream.dict.remove(b"DecodeParms");
+        }
+        stream.set_content(bytes);
+    }
+}
+
+/// Replace a content stream color operator with its gray: `rg` and `k`
+/// become `g` (strokes likewise), and three-component colors in other color
+/// spaces (`sc`, `scn`) get three equal components
+fn gray_operation(op: &mut Operation) {
+    let components: Option<Vec<f32>> = op.operands.iter().map(number).collect();
+    let Some(components) = components else {
+        // Pattern names and the like
+        return;
+    };
+    let gray = match (op.operator.as_str(), components.as_slice()) {
+        ("rg" | "RG" | "sc" | "scn" | "SC" | "SCN", &[r, g, b]) => {
+            luminance(&Color::Rgb(Rgb::new(r, g, b, None)))
+        }
+        ("k" | "K", &[c, m, y, k]) => luminance(&Color::Cmyk(Cmyk::new(c, m, y, k, None))),
+        _ => return,
+    };
+    let gray = gray.clamp(0.0, 1.0);
+    match op.operator.as_str() {
+        "rg" | "k" => *op = Operation::new("g", vec![Object::Real(gray)]),
+        "RG" | "K" => *op = Operation::new("G", vec![Object::Real(gray)]),
+        _ => op.operands = vec![Object::Real(gray); 3],
+    }
+}
+
+fn number(object: &Object) -> Option<f32> {
+    match object {
+        Object::Integer(n) => Some(*n as f32),
+        Object::Real(n) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Whether a fill color is next used for a shape rather than for text
+fn fills_shape(rest: &[Op]) -> bool {
+    for op in rest {
+        match op {
+            Op::DrawPolygon { .. } => return true,
+            Op::StartTextSection | Op::SetFillColor { .. } => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Perceived brightness of a color, 0.0 (black) to 1.0 (white)
+fn luminance(color: &Color) -> f32 {
+    match color {
+        Color::Rgb(rgb) => 0.299 * rgb.r + 0.587 * rgb.g + 0.114 * rgb.b,
+        Color::Greyscale(gray) => gray.percent,
+        Color::Cmyk(cmyk) => (1.0 - cmyk.k) * (1.0 - (cmyk.c + cmyk.m + cmyk.y) / 3.0),
+        _ => 0.0,
+    }
+}
+
+/// Lighten a dark shape fill, keeping pure black (QR codes, card outlines)
+fn thin_fill(gray: f32) -> f32 {
+    if gray > 0.0 && gray < THIN_FILL_LIMIT {
+        gray + (THIN_FILL_LIMIT - gray) * THIN_FILL_AMOUNT
+    } else {
+        gray
+    }
+}
+
+fn gray_color(gray: f32) -> Color {
+    let gray = gray.clamp(0.0, 1.0);
+    Color::Rgb(Rgb::new(gray, gray, gray, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::helpers::colors::{BLACK, GREEN, WHITE};
+    use crate::render::helpers::layer::LayerBuilder;
+    use printpdf::{BuiltinFont, Mm, PaintMode};
+
+    fn fill_grays(ops: &[Op]) -> Vec<f32> {
+        ops.iter()
+            .filter_map(|op| match op {
+                Op::SetFillColor {
+                    col: Color::Rgb(rgb),
+                } => {
+                    assert_eq!(rgb.r, rgb.g);
+                    assert_eq!(rgb.g, rgb.b);
+                    Some(rgb.r)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_banner_fill_is_thinned_but_text_is_not() {
+        let mut layer = LayerBuilder::new();
+        layer.set_fill_color(Color::Rgb(GREEN));
+        layer.add_rect(Mm(0.0), Mm(0.0), Mm(10.0), Mm(10.0), PaintMode::Fill);
+        layer.set_fill_color(Color::Rgb(GREEN));
+        layer.use_text_builtin("N", 10.0, Mm(1.0), Mm(1.0), BuiltinFont::Helvetica);
+        let mut ops = layer.into_ops();
+        grayscale_ops(&mut ops);
+
+        let grays = fill_grays(&ops);
+        let text_gray = 0.587 * 0.5;
+        assert!((grays[1] - text_gray).abs() < 1e-5);
+        assert!(grays[0] > text_gray);
+    }
+
+    #[test]
+    fn test_black_and_white_are_kept() {
+        let mut layer = LayerBuilder::new();
+        layer.set_fill_color(Color::Rgb(BLACK));
+        layer.add_rect(Mm(0.0), Mm(0.0), Mm(1.0), Mm(1.0), PaintMode::Fill);
+        layer.set_fill_color(Color::Rgb(WHITE));
+        layer.add_rect(Mm(0.0), Mm(0.0), Mm(1.0), Mm(1.0), PaintMode::Fill);
+        let mut ops = layer.into_ops();
+        grayscale_ops(&mut ops);
+
+        assert_eq!(fill_grays(&ops), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_form_xobject_colors_turn_gray() {
+        use lopdf::{dictionary, Stream};
+
+        let mut doc = Document::with_version("1.7");
+        let card = "1 0 0 rg 0 0 10 10 re f 0 0 1 RG 0 1 0 scn /P0 scn 0 0 0 1 k";
+        let form = doc.add_object(Stream::new(
+            dictionary! { "Type" => "XObject", "Subtype" => "Form" },
+            card.as_bytes().to_vec(),
+        ));
+        let other = doc.add_object(Stream::new(
+            lopdf::Dictionary::new(),
+            card.as_bytes().to_vec(),
+        ));
+        grayscale_forms(&mut doc);
+
+        let ops = |id| -> Vec<(String, Vec<f32>)> {
+            let stream = doc.get_object(id).unwrap().as_stream().unwrap();
+            Content::decode(&stream.content)
+                .unwrap()
+                .operations
+                .into_iter()
+                .map(|op| (op.operator, op.operands.iter().filter_map(number).collect()))
+                .collect()
+        };
+        let op = |name: &str, operands: &[f32]| (name.to_string(), operands.to_vec());
+        assert_eq!(
+            ops(form),
+            [
+                op("g", &[0.299]),
+                op("re", &[0.0, 0.0, 10.0, 10.0]),
+                op("f", &[]),
+                op("G", &[0.114]),
+                op("scn", &[0.587, 0.587, 0.587]),
+                op("scn", &[]),
+                op("g", &[0.0]),
+            ]
+        );
+        // Only forms are touched
+        assert_eq!(ops(other)[0], op("rg", &[1.0, 0.0, 0.0]));
+    }
+}
@@ -0,0 +1,124 @@
+//! Hyphenation for commentary wrapping (`--hyphenate`)
+//!
+//! Long words that don't fit at the end of a line are split using the
+//! Knuth-Liang patterns of the `--language`, so narrow columns don't end up
+//! with wide gaps. Only the letters of a word are hyphenated; punctuation stays
+//! attached, and short words are never split.
+
+use std::sync::OnceLock;
+
+use hyphenation::{Hyphenator, Load, Standard};
+
+use crate::cli::Language;
+
+/// Words with fewer letters than this are left whole
+const MIN_WORD_LETTERS: usize = 6;
+
+/// Hyphenation patterns for a language, loaded on first use
+fn dictionary(language: Language) -> Option<&'static Standard> {
+    let (patterns, pattern_language) = match language {
+        Language::English => {
+            static PATTERNS: OnceLock<Option<Standard>> = OnceLock::new();
+            (&PATTERNS, hyphenation::Language::EnglishUS)
+        }
+        Language::French => {
+            static PATTERNS: OnceLock<Option<Standard>> = OnceLock::new();
+            (&PATTERNS, hyphenation::Language::French)
+        }
+        Language::German => {
+            static PATTERNS: OnceLock<Option<Standard>> = OnceLock::new();
+            (&PATTERNS, hyphenation::Language::German1996)
+        }
+        Language::Dutch => {
+            static PATTERNS: OnceLock<Option<Standard>> = OnceLock::new();
+            (&PATTERNS, hyphenation::Language::Dutch)
+        }
+        Language::Polish => {
+            static PATTERNS: OnceLock<Option<Standard>> = OnceLock::new();
+            (&PATTERNS, hyphenation::Language::Polish)
+        }
+    };
+    patterns
+        .get_or_init(|| Standard::from_embedded(pattern_language).ok())
+        .as_ref()
+}
+
+/// Byte offsets in `word` where it may be hyphenated
+pub fn break_points(word: &str, language: Language) -> Vec<usize> {
+    let Some(start) = word.find(char::is_alphabetic) else {
+        return Vec::new();
+    };
+    let end = word
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphabetic())
+        .map_or(start, |(i, c)| i + c.len_utf8());
+    let letters = &word[start..end];
+    if letters.chars().count() < MIN_WORD_LETTERS || !letters.chars().all(char::is_alphabetic) {
+        return Vec::new();
+    }
+    let Some(patterns) = dictionary(language) else {
+        return Vec::new();
+    };
+
+    // Patterns are lowercase; keep the original if lowercasing changes byte offsets
+    let lower = letters.to_lowercase();
+    let lookup = if lower.len() == letters.len() {
+        lower.as_str()
+    } else {
+        letters
+    };
+    patterns
+        .hyphenate(lookup)
+        .breaks
+        .into_iter()
+        .map(|offset| start + offset)
+        .collect()
+}
+
+/// Split `word` at the last break point where the first part and a hyphen
+/// still fit in `max_width`, returning ("first-", "rest")
+pub fn split_to_fit(
+    word: &str,
+    language: Language,
+    max_width: f32,
+    width: impl Fn(&str) -> f32,
+) -> Option<(String, String)> {
+    break_points(word, language)
+        .into_iter()
+        .rev()
+        .find_map(|offset| {
+            let first = format!("{}-", &word[..offset]);
+            (width(&first) <= max_width).then(|| (first, word[offset..].to_string()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_word_has_break_points() {
+        let breaks = break_points("hyphenation", Language::English);
+        assert!(!breaks.is_empty());
+        assert!(breaks.iter().all(|&b| b > 0 && b < "hyphenation".len()));
+    }
+
+    #[test]
+    fn test_short_words_and_numbers_are_not_split() {
+        assert!(break_points("trump", Language::English).is_empty());
+        assert!(break_points("1NT-2C-2S", Language::English).is_empty());
+    }
+
+    #[test]
+    fn test_split_keeps_punctuation_with_the_rest() {
+        let (first, rest) =
+            split_to_fit("(overtaking),", Language::English, 6.0, |s| s.len() as f32).unwrap();
+        assert!(first.ends_with('-') && first.len() <= 6);
+        assert_eq!(
+            format!("{}{}", first.trim_end_matches('-'), rest),
+            "(overtaking),"
+        );
+        assert!(rest.ends_with("),"));
+    }
+}
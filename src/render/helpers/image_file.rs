@@ -0,0 +1,243 @@
+//! Image files embedded in the output (`--logo`, `--background`)
+//!
+//! The image is embedded once as an XObject and placed on every page. SVG files
+//! go through the same parser as the card images; PNG and JPEG files are
+//! decoded by printpdf, and can be downsampled (`--image-dpi`) to the
+//! resolution they're drawn at.
+
+use std::path::Path;
+
+use printpdf::{
+    PdfDocument, PdfWarnMsg, Pt, RawImage, RawImageData, RawImageFormat, Svg, XObjectId,
+    XObjectTransform,
+};
+
+use crate::error::RenderError;
+
+/// DPI used when parsing SVG files (same as the card images)
+const SVG_DPI: f32 = 300.0;
+
+/// DPI assumed for bitmaps; only the aspect ratio matters after scaling
+const IMAGE_DPI: f32 = 300.0;
+
+/// An image registered in the document
+#[derive(Debug, Clone)]
+pub struct ImageFile {
+    id: XObjectId,
+    /// Natural size in mm at `dpi`
+    width: f32,
+    height: f32,
+    dpi: f32,
+}
+
+impl ImageFile {
+    /// Load an image file and register it in the document.
+    ///
+    /// `drawn` is the largest width and height in mm the image is drawn at
+    /// (infinite for no limit); with `dpi`, bitmaps with more pixels than
+    /// that needs are downsampled. SVG files are left as they are.
+    pub fn load(
+        doc: &mut PdfDocument,
+        path: &Path,
+        drawn: (f32, f32),
+        dpi: Option<u32>,
+    ) -> Result<Self, RenderError> {
+        let bytes = read_image_file(path)?;
+        let mut warnings: Vec<PdfWarnMsg> = Vec::new();
+        let is_svg = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+
+        // Pixels as decoded, the resolution they're measured at, and the
+        // resolution of what's embedded
+        let (id, width_px, height_px, natural_dpi, embedded_dpi) = if is_svg {
+            let svg = String::from_utf8_lossy(&bytes);
+            let xobject = Svg::parse(&svg, &mut warnings)
+                .map_err(|e| RenderError::Image(format!("{}: {}", path.display(), e)))?;
+            let (width, height) = match (xobject.width, xobject.height) {
+                (Some(w), Some(h)) => (w.0, h.0),
+                _ => {
+                    return Err(RenderError::Image(format!(
+                        "{}: SVG has no size",
+                        path.display()
+                    )))
+                }
+            };
+            (doc.add_xobject(&xobject), width, height, SVG_DPI, SVG_DPI)
+        } else {
+            let mut image = RawImage::decode_from_bytes(&bytes, &mut warnings)
+                .map_err(|e| RenderError::Image(format!("{}: {}", path.display(), e)))?;
+            let (width, height) = (image.width, image.height);
+            if let Some(dpi) = dpi {
+                downsample(&mut image, drawn, dpi);
+            }
+            // Fewer pixels over the same size
+            let embedded_dpi = IMAGE_DPI * image.width as f32 / width.max(1) as f32;
+            (
+                doc.add_image(&image),
+                width,
+                height,
+                IMAGE_DPI,
+                embedded_dpi,
+            )
+        };
+        if width_px == 0 || height_px == 0 {
+            return Err(RenderError::Image(format!(
+                "{}: empty image",
+                path.display()
+            )));
+        }
+
+        let px_to_mm = 25.4 / natural_dpi;
+        Ok(Self {
+            id,
+            width: width_px as f32 * px_to_mm,
+            height: height_px as f32 * px_to_mm,
+            dpi: embedded_dpi,
+        })
+    }
+
+    /// Width in mm when drawn `height` mm tall
+    pub fn width_at(&self, height: f32) -> f32 {
+        self.width * height / self.height
+    }
+
+    /// XObject to place
+    pub fn id(&self) -> XObjectId {
+        self.id.clone()
+    }
+
+    /// Transform drawing the image `height` mm tall with its bottom-left corner at (x, y)
+    pub fn transform(&self, x: f32, y: f32, height: f32) -> XObjectTransform {
+        let scale = height / self.height;
+        self.transform_scaled(x, y, scale, scale)
+    }
+
+    /// Transform stretching the image over a `width` x `height` mm area at (x, y)
+    pub fn transform_fill(&self, x: f32, y: f32, width: f32, height: f32) -> XObjectTransform {
+        self.transform_scaled(x, y, width / self.width, height / self.height)
+    }
+
+    fn transform_scaled(&self, x: f32, y: f32, scale_x: f32, scale_y: f32) -> XObjectTransform {
+        let mm_to_pt = 72.0 / 25.4;
+        XObjectTransform {
+            translate_x: Some(Pt(x * mm_to_pt)),
+            translate_y: Some(Pt(y * mm_to_pt)),
+            scale_x: Some(scale_x),
+            scale_y: Some(scale_y),
+            rotate: None,
+            dpi: Some(self.dpi),
+        }
+    }
+}
+
+/// Shrink a bitmap to at most `dpi` pixels per inch when drawn `drawn` mm
+/// wide and tall, keeping its aspect ratio. Formats other than 8-bit
+/// channels are left as they are.
+fn downsample(image: &mut RawImage, drawn: (f32, f32), dpi: u32) {
+    let channels = match image.data_format {
+        RawImageFormat::R8 => 1,
+        RawImageFormat::RG8 => 2,
+        RawImageFormat::RGB8 | RawImageFormat::BGR8 => 3,
+        RawImageFormat::RGBA8 | RawImageFormat::BGRA8 => 4,
+        _ => return,
+    };
+    let RawImageData::U8(ref pixels) = image.pixels else {
+        return;
+    };
+    let (width, height) = (image.width, image.height);
+    let Some((new_width, new_height)) = target_size(width, height, drawn, dpi) else {
+        return;
+    };
+    let resized = box_filter(pixels, (width, height), channels, (new_width, new_height));
+    image.pixels = RawImageData::U8(resized);
+    image.width = new_width;
+    image.height = new_height;
+}
+
+/// Pixel size for `dpi` at the drawn size, or None if the image is no
+/// bigger than that already
+fn target_size(width: usize, height: usize, drawn: (f32, f32), dpi: u32) -> Option<(usize, usize)> {
+    let needed = |mm: f32| mm / 25.4 * dpi as f32;
+    let scale = (needed(drawn.0) / width as f32).min(needed(drawn.1) / height as f32);
+    if width == 0 || height == 0 || !scale.is_finite() || scale >= 1.0 {
+        return None;
+    }
+    let resize = |px: usize| ((px as f32 * scale).round() as usize).max(1);
+    Some((resize(width), resize(height)))
+}
+
+/// Average each block of source pixels into one pixel of the smaller image
+fn box_filter(
+    pixels: &[u8],
+    (width, height): (usize, usize),
+    channels: usize,
+    (new_width, new_height): (usize, usize),
+) -> Vec<u8> {
+    // Source rows or columns covered by output row or column `i` of `new`
+    let span = |i: usize, old: usize, new: usize| {
+        let start = i * old / new;
+        (start, ((i + 1) * old / new).max(start + 1))
+    };
+    let mut resized = Vec::with_capacity(new_width * new_height * channels);
+    for y in 0..new_height {
+        let (y0, y1) = span(y, height, new_height);
+        for x in 0..new_width {
+            let (x0, x1) = span(x, width, new_width);
+            let count = ((y1 - y0) * (x1 - x0)) as u32;
+            for channel in 0..channels {
+                let mut sum = 0u32;
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        sum += pixels[(sy * width + sx) * channels + channel] as u32;
+                    }
+                }
+                resized.push((sum / count) as u8);
+            }
+        }
+    }
+    resized
+}
+
+#[cfg(feature = "fs")]
+fn read_image_file(path: &Path) -> Result<Vec<u8>, RenderError> {
+    std::fs::read(path).map_err(|e| RenderError::Image(format!("{}: {}", path.display(), e)))
+}
+
+/// Without the `fs` feature there is no disk to read logos and backgrounds from
+#[cfg(not(feature = "fs"))]
+fn read_image_file(path: &Path) -> Result<Vec<u8>, RenderError> {
+    Err(RenderError::Image(format!(
+        "{}: image files can't be read in this build",
+        path.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_size() {
+        // 1200 px over 50.8 mm (2 in) is 600 dpi; 150 dpi needs 300 px
+        assert_eq!(
+            target_size(1200, 600, (50.8, f32::INFINITY), 150),
+            Some((300, 150))
+        );
+        // Already small enough
+        assert_eq!(target_size(200, 100, (50.8, 25.4), 150), None);
+        assert_eq!(
+            target_size(200, 100, (f32::INFINITY, f32::INFINITY), 150),
+            None
+        );
+    }
+
+    #[test]
+    fn test_box_filter() {
+        // 2x2 gray pixels averaged into one
+        assert_eq!(box_filter(&[0, 100, 200, 100], (2, 2), 1, (1, 1)), [100]);
+        // RGB: each channel averaged separately
+        let pixels = [255, 0, 0, 0, 0, 255];
+        assert_eq!(box_filter(&pixels, (2, 1), 3, (1, 1)), [127, 0, 127]);
+    }
+}
@@ -6,10 +6,16 @@
 use std::borrow::Cow;
 
 use printpdf::{
-    BuiltinFont, Color, CurTransMat, FontId, LinePoint, Mm, Op, PaintMode, PdfFontHandle, Point,
-    Polygon, PolygonRing, Pt, TextItem, WindingOrder, XObjectId, XObjectTransform,
+    BuiltinFont, Color, CurTransMat, FontId, Layer, LayerInternalId, LinePoint, Mm, Op, PaintMode,
+    PdfDocument, PdfFontHandle, Point, Polygon, PolygonRing, Pt, TextItem, WindingOrder, XObjectId,
+    XObjectTransform,
 };
 
+use super::fonts::TextFont;
+use super::forms::TextField;
+use super::links::{resolve_card_links, CardAnchor, CardLink, Link};
+use crate::model::{Rank, Suit};
+
 /// A builder that collects PDF operations
 ///
 /// This mimics the old `PdfLayerReference` API from printpdf 0.7
@@ -17,11 +23,52 @@ use printpdf::{
 #[derive(Default)]
 pub struct LayerBuilder {
     ops: Vec<Op>,
+    text_fields: Vec<TextField>,
+    answers_layer: Option<LayerInternalId>,
+    link_scope: u32,
+    card_anchors: Vec<CardAnchor>,
+    card_links: Vec<CardLink>,
+    links: Vec<Link>,
 }
 
 impl LayerBuilder {
     pub fn new() -> Self {
-        Self { ops: Vec::new() }
+        Self {
+            ops: Vec::new(),
+            text_fields: Vec::new(),
+            answers_layer: None,
+            link_scope: 0,
+            card_anchors: Vec::new(),
+            card_links: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Route answer content to an optional content group (PDF layer)
+    ///
+    /// When set, content drawn between `begin_answers()` and `end_answers()`
+    /// lands on this layer so viewers can toggle it.
+    pub fn with_answers_layer(mut self, layer: Option<LayerInternalId>) -> Self {
+        self.answers_layer = layer;
+        self
+    }
+
+    /// Start drawing answer content (no-op without an answers layer)
+    pub fn begin_answers(&mut self) {
+        if let Some(layer_id) = &self.answers_layer {
+            self.ops.push(Op::BeginLayer {
+                layer_id: layer_id.clone(),
+            });
+        }
+    }
+
+    /// Finish drawing answer content (no-op without an answers layer)
+    pub fn end_answers(&mut self) {
+        if let Some(layer_id) = &self.answers_layer {
+            self.ops.push(Op::EndLayer {
+                layer_id: layer_id.clone(),
+            });
+        }
     }
 
     /// Get the collected operations
@@ -37,6 +84,91 @@ impl LayerBuilder {
     /// Extend with operations from another builder
     pub fn extend(&mut self, other: LayerBuilder) {
         self.ops.extend(other.ops);
+        self.text_fields.extend(other.text_fields);
+        self.card_anchors.extend(other.card_anchors);
+        self.card_links.extend(other.card_links);
+        self.links.extend(other.links);
+    }
+
+    /// Register a fillable text field for this page
+    ///
+    /// Fields are not part of the content stream; renderers take them with
+    /// `take_text_fields()` and inject them after saving via `add_form_fields()`.
+    pub fn add_text_field(&mut self, field: TextField) {
+        self.text_fields.push(field);
+    }
+
+    /// Take the text fields registered on this page
+    pub fn take_text_fields(&mut self) -> Vec<TextField> {
+        std::mem::take(&mut self.text_fields)
+    }
+
+    /// Register a clickable link area on this page
+    pub fn add_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Start a new board so card links only resolve within it
+    ///
+    /// Several boards can share a page, each with its own diagram.
+    pub fn next_link_scope(&mut self) {
+        self.link_scope += 1;
+    }
+
+    /// Record where a card is drawn in a hand diagram (x, top y in mm)
+    pub fn add_card_anchor(&mut self, suit: Suit, rank: Rank, x: f32, y: f32) {
+        self.card_anchors.push(CardAnchor {
+            scope: self.link_scope,
+            suit,
+            rank,
+            x,
+            y,
+        });
+    }
+
+    /// Number of card anchors recorded so far
+    pub fn card_anchor_count(&self) -> usize {
+        self.card_anchors.len()
+    }
+
+    /// Move the card anchors recorded since `start` to page coordinates, for
+    /// cards drawn under a transformation matrix
+    pub fn map_card_anchors_from(&mut self, start: usize, map: impl Fn(f32, f32) -> (f32, f32)) {
+        for anchor in &mut self.card_anchors[start..] {
+            (anchor.x, anchor.y) = map(anchor.x, anchor.y);
+        }
+    }
+
+    /// Make a card reference clickable, jumping to the card in the diagram
+    ///
+    /// The rectangle is given by its lower-left corner, width and height in mm.
+    pub fn add_card_link(
+        &mut self,
+        suit: Suit,
+        rank: Rank,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.card_links.push(CardLink {
+            scope: self.link_scope,
+            suit,
+            rank,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    /// Take the links for this page, matching card references to diagram cards
+    pub fn take_links(&mut self) -> Vec<Link> {
+        let mut links = std::mem::take(&mut self.links);
+        links.extend(resolve_card_links(&self.card_anchors, &self.card_links));
+        self.card_anchors.clear();
+        self.card_links.clear();
+        links
     }
 
     /// Set the fill color
@@ -88,6 +220,21 @@ impl LayerBuilder {
         self.use_text_with_handle(sanitized, font_size, x, y, PdfFontHandle::Builtin(font));
     }
 
+    /// Draw text at a specific position using a builtin or embedded font
+    pub fn use_text_font<S: Into<String>>(
+        &mut self,
+        text: S,
+        font_size: f32,
+        x: Mm,
+        y: Mm,
+        font: &TextFont,
+    ) {
+        match font {
+            TextFont::Builtin(builtin) => self.use_text_builtin(text, font_size, x, y, *builtin),
+            TextFont::Embedded(embedded) => self.use_text(text, font_size, x, y, &embedded.id),
+        }
+    }
+
     /// Draw text at a specific position using any font handle
     fn use_text_with_handle<S: Into<String>>(
         &mut self,
@@ -616,6 +763,15 @@ impl LayerBuilder {
     }
 }
 
+/// Register the "Answers" optional content group on the document when enabled
+pub fn add_answers_layer(doc: &mut PdfDocument, enabled: bool) -> Option<LayerInternalId> {
+    enabled.then(|| doc.add_layer(&Layer::new("Answers")))
+}
+
+/// Base letters of U+0100..=U+017F, in code point order ("Ą" → 'A', "ł" → 'l')
+const LATIN_EXTENDED_A_BASE: &str = "AaAaAaCcCcCcCcDdDdEeEeEeEeEeGgGgGgGgHhHhIiIiIiIiIiIiJjKkk\
+LlLlLlLlLlNnNnNnnNnOoOoOoOoRrRrRrSsSsSsSsTtTtTtUuUuUuUuUuUuWwYyYZzZzZzs";
+
 /// Sanitize text for WinAnsiEncoding (Windows-1252) used by PDF builtin fonts.
 ///
 /// Converts Unicode characters to their Windows-1252 equivalents where possible,
@@ -703,6 +859,12 @@ fn sanitize_for_winansi(text: &str) -> Cow<'_, str> {
                 // Common Latin-1 supplement characters (most are in Windows-1252)
                 c if ('\u{00A1}'..='\u{00FF}').contains(&c) => c, // Keep Latin-1 supplement
 
+                // Latin Extended-A (Polish, Czech, Hungarian, ...) → base letter
+                c if ('\u{0100}'..='\u{017F}').contains(&c) => LATIN_EXTENDED_A_BASE
+                    .chars()
+                    .nth(c as usize - 0x100)
+                    .unwrap_or('?'),
+
                 // Skip suit symbols (these should use the symbol font, not builtin)
                 '\u{2660}' | '\u{2663}' | '\u{2665}' | '\u{2666}' => {
                     // Spade, club, heart, diamond - skip if they somehow get here
@@ -718,3 +880,39 @@ fn sanitize_for_winansi(text: &str) -> Cow<'_, str> {
 
     Cow::Owned(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Language;
+    use crate::config::Locale;
+
+    #[test]
+    fn test_latin_extended_a_table() {
+        assert_eq!(LATIN_EXTENDED_A_BASE.chars().count(), 0x80);
+        assert_eq!(sanitize_for_winansi("Łódź żółć"), "Lódz zólc");
+    }
+
+    #[test]
+    fn test_polish_labels_have_no_replacement_marks() {
+        let mut layer = LayerBuilder::new();
+        for label in Locale::for_language(Language::Polish).strings() {
+            layer.use_text_builtin(label, 10.0, Mm(0.0), Mm(0.0), BuiltinFont::Helvetica);
+        }
+        let texts: Vec<String> = layer
+            .into_ops()
+            .into_iter()
+            .filter_map(|op| match op {
+                Op::ShowText { items } => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                TextItem::Text(text) => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Pólnoc".to_string()));
+        assert!(texts.iter().all(|text| !text.contains('?')), "{:?}", texts);
+    }
+}
@@ -0,0 +1,220 @@
+//! PDF link annotations
+//!
+//! Like form fields, links are collected while rendering (see
+//! `LayerBuilder::add_link` and `LayerBuilder::add_card_link`) and written
+//! into the saved PDF with lopdf.
+
+use std::io::Cursor;
+
+use lopdf::{dictionary, Document, Object, ObjectId};
+
+use crate::model::{Rank, Suit};
+
+/// Conversion factor from millimeters to PDF points
+const MM_TO_PT: f32 = 72.0 / 25.4;
+
+/// Where a link goes when clicked
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    /// A position on the same page (x, y in mm, y is the top of the view)
+    Position { x: f32, y: f32 },
+    /// An external web address
+    Uri(String),
+}
+
+/// A clickable rectangle on a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    /// Lower-left x coordinate in mm
+    pub x: f32,
+    /// Lower-left y coordinate in mm
+    pub y: f32,
+    /// Width in mm
+    pub width: f32,
+    /// Height in mm
+    pub height: f32,
+    pub target: LinkTarget,
+}
+
+/// Position of a card as drawn in a hand diagram
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardAnchor {
+    pub scope: u32,
+    pub suit: Suit,
+    pub rank: Rank,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A card reference in commentary, waiting to be matched with its anchor
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardLink {
+    pub scope: u32,
+    pub suit: Suit,
+    pub rank: Rank,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Match card references to the diagram cards drawn for the same board.
+///
+/// References to cards that were not drawn (hidden hands, fragments) are dropped.
+pub fn resolve_card_links(anchors: &[CardAnchor], card_links: &[CardLink]) -> Vec<Link> {
+    card_links
+        .iter()
+        .filter_map(|link| {
+            anchors
+                .iter()
+                .find(|a| a.scope == link.scope && a.suit == link.suit && a.rank == link.rank)
+                .map(|anchor| Link {
+                    x: link.x,
+                    y: link.y,
+                    width: link.width,
+                    height: link.height,
+                    target: LinkTarget::Position {
+                        x: anchor.x,
+                        y: anchor.y,
+                    },
+                })
+        })
+        .collect()
+}
+
+/// Add link annotations to an already-saved PDF.
+///
+/// `page_links[i]` holds the links for page `i` (0-based).
+pub fn add_links(pdf: Vec<u8>, page_links: &[Vec<Link>]) -> Result<Vec<u8>, String> {
+    if page_links.iter().all(|links| links.is_empty()) {
+        return Ok(pdf);
+    }
+
+    let mut doc =
+        Document::load_mem(&pdf).map_err(|e| format!("Failed to parse PDF for links: {}", e))?;
+
+    let pages: Vec<ObjectId> = doc.get_pages().values().copied().collect();
+
+    for (page_idx, links) in page_links.iter().enumerate() {
+        let Some(&page_id) = pages.get(page_idx) else {
+            break;
+        };
+        if links.is_empty() {
+            continue;
+        }
+
+        let mut annots = existing_annotations(&doc, page_id);
+
+        for link in links {
+            let mut annot = dictionary! {
+                "Type" => "Annot",
+                "Subtype" => "Link",
+                "Rect" => vec![
+                    Object::Real(link.x * MM_TO_PT),
+                    Object::Real(link.y * MM_TO_PT),
+                    Object::Real((link.x + link.width) * MM_TO_PT),
+                    Object::Real((link.y + link.height) * MM_TO_PT),
+                ],
+                "Border" => vec![Object::Integer(0), Object::Integer(0), Object::Integer(0)],
+            };
+            match &link.target {
+                LinkTarget::Position { x, y } => annot.set(
+                    "Dest",
+                    vec![
+                        Object::Reference(page_id),
+                        "XYZ".into(),
+                        Object::Real(x * MM_TO_PT),
+                        Object::Real(y * MM_TO_PT),
+                        Object::Integer(0), // Keep current zoom
+                    ],
+                ),
+                LinkTarget::Uri(uri) => annot.set(
+                    "A",
+                    dictionary! {
+                        "S" => "URI",
+                        "URI" => Object::string_literal(uri.as_str()),
+                    },
+                ),
+            }
+            annots.push(Object::Reference(doc.add_object(annot)));
+        }
+
+        doc.get_object_mut(page_id)
+            .and_then(Object::as_dict_mut)
+            .map_err(|e| format!("Failed to access page for links: {}", e))?
+            .set("Annots", annots);
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    doc.save_to(&mut output)
+        .map_err(|e| format!("Failed to save PDF with links: {}", e))?;
+
+    Ok(output.into_inner())
+}
+
+/// Collect a page's existing annotations so new ones are appended, not substituted
+pub(crate) fn existing_annotations(doc: &Document, page_id: ObjectId) -> Vec<Object> {
+    let Ok(page) = doc.get_dictionary(page_id) else {
+        return Vec::new();
+    };
+    match page.get(b"Annots") {
+        Ok(Object::Array(annots)) => annots.clone(),
+        Ok(Object::Reference(id)) => doc
+            .get_object(*id)
+            .and_then(Object::as_array)
+            .cloned()
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(scope: u32, suit: Suit, rank: Rank) -> CardAnchor {
+        CardAnchor {
+            scope,
+            suit,
+            rank,
+            x: 50.0,
+            y: 200.0,
+        }
+    }
+
+    fn card_link(scope: u32, suit: Suit, rank: Rank) -> CardLink {
+        CardLink {
+            scope,
+            suit,
+            rank,
+            x: 10.0,
+            y: 20.0,
+            width: 5.0,
+            height: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_resolve_matches_same_board() {
+        let anchors = vec![
+            anchor(1, Suit::Spades, Rank::Ace),
+            anchor(2, Suit::Spades, Rank::Ace),
+        ];
+        let links = resolve_card_links(&anchors, &[card_link(1, Suit::Spades, Rank::Ace)]);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, LinkTarget::Position { x: 50.0, y: 200.0 });
+    }
+
+    #[test]
+    fn test_resolve_drops_unknown_cards() {
+        let anchors = vec![anchor(1, Suit::Spades, Rank::Ace)];
+        let links = resolve_card_links(
+            &anchors,
+            &[
+                card_link(1, Suit::Hearts, Rank::Ace),
+                card_link(2, Suit::Spades, Rank::Ace),
+            ],
+        );
+        assert!(links.is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+//! Joining PDFs rendered in chunks
+//!
+//! A long document can be rendered a chunk of boards at a time (`--chunk-size`)
+//! so only one chunk's drawing operations are in memory at once; each chunk's
+//! saved PDF is added to a [PdfMerger] as soon as it is rendered and its bytes
+//! are dropped. Every chunk's pages keep their resources, and its form fields
+//! are added to the document's. Layers are matched by name, so the answers
+//! layer of every chunk is the document's one answers layer.
+//!
+//! The joined document itself is held in memory until it is saved, so peak
+//! memory still grows with the size of the output PDF; chunking bounds what
+//! printpdf holds, not what the finished file holds. To keep that small, the
+//! fonts each chunk embeds and the card images and logos the chunks repeat are
+//! folded into one shared copy after every chunk is added.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+use super::compress::{dedup_fonts, dedup_xobjects};
+
+/// Page attributes a page can inherit from the page tree
+const INHERITED: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
+/// Arrays in the catalog that collect something from every chunk
+const MERGED_ARRAYS: [&[&[u8]]; 5] = [
+    &[b"AcroForm", b"Fields"],
+    &[b"OCProperties", b"OCGs"],
+    &[b"OCProperties", b"D", b"ON"],
+    &[b"OCProperties", b"D", b"OFF"],
+    &[b"OCProperties", b"D", b"Order"],
+];
+
+/// Joins PDFs one at a time, their pages in the order they are added
+#[derive(Default)]
+pub struct PdfMerger {
+    doc: Option<Document>,
+}
+
+impl PdfMerger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the pages of `pdf` after those already added
+    pub fn append(&mut self, pdf: &[u8]) -> Result<(), String> {
+        let mut other =
+            Document::load_mem(pdf).map_err(|e| format!("Failed to parse PDF to merge: {}", e))?;
+        let Some(doc) = self.doc.as_mut() else {
+            self.doc = Some(other);
+            return Ok(());
+        };
+        let catalog_id = root_id(doc)?;
+        let pages_id = pages_id(doc, catalog_id)?;
+
+        other.renumber_objects_with(doc.max_id + 1);
+        doc.max_id = other.max_id;
+
+        let other_catalog_id = root_id(&other)?;
+        let other_pages_id = pages_id(&other, other_catalog_id)?;
+        let mut other_catalog = Object::Dictionary(dictionary(&other, other_catalog_id)?.clone());
+        let inherited: Vec<(&[u8], Object)> = {
+            let tree = dictionary(&other, other_pages_id)?;
+            INHERITED
+                .iter()
+                .filter_map(|key| tree.get(key).ok().map(|value| (*key, value.clone())))
+                .collect()
+        };
+        let other_pages: Vec<ObjectId> = other.get_pages().into_values().collect();
+
+        // Everything but the catalog and the root of the page tree moves over
+        let mut moved = Vec::new();
+        for (id, object) in std::mem::take(&mut other.objects) {
+            if id != other_catalog_id && id != other_pages_id {
+                doc.objects.insert(id, object);
+                moved.push(id);
+            }
+        }
+
+        // Layers the document already has are shared rather than repeated
+        let layers = shared_layers(doc, catalog_id, &other_catalog);
+        if !layers.is_empty() {
+            for id in &moved {
+                if let Some(object) = doc.objects.get_mut(id) {
+                    replace_references(object, &layers);
+                }
+            }
+            replace_references(&mut other_catalog, &layers);
+            for id in layers.keys() {
+                doc.objects.remove(id);
+            }
+        }
+        let other_catalog = other_catalog
+            .as_dict()
+            .map_err(|e| format!("Failed to access PDF catalog: {}", e))?;
+
+        for &page_id in &other_pages {
+            let page = doc
+                .get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("Failed to access page to merge: {}", e))?;
+            page.set("Parent", pages_id);
+            for (key, value) in &inherited {
+                if !page.has(key) {
+                    page.set(key.to_vec(), value.clone());
+                }
+            }
+        }
+
+        let tree = doc
+            .get_object_mut(pages_id)
+            .and_then(Object::as_dict_mut)
+            .map_err(|e| format!("Failed to access page tree: {}", e))?;
+        let mut kids = tree
+            .get(b"Kids")
+            .and_then(Object::as_array)
+            .cloned()
+            .unwrap_or_default();
+        kids.extend(other_pages.into_iter().map(Object::Reference));
+        tree.set("Count", kids.len() as i64);
+        tree.set("Kids", kids);
+
+        merge_catalog(doc, catalog_id, other_catalog)?;
+
+        // Drop this chunk's copies of fonts and images the document already has
+        dedup_fonts(doc);
+        dedup_xobjects(doc);
+        Ok(())
+    }
+
+    /// Save the joined document
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        let mut doc = self.doc.ok_or_else(|| "No PDFs to merge".to_string())?;
+        let mut output = Cursor::new(Vec::new());
+        doc.save_to(&mut output)
+            .map_err(|e| format!("Failed to save merged PDF: {}", e))?;
+        Ok(output.into_inner())
+    }
+}
+
+/// Join PDFs into one, their pages in order
+pub fn merge_pdfs(parts: Vec<Vec<u8>>) -> Result<Vec<u8>, String> {
+    let mut merger = PdfMerger::new();
+    for part in parts {
+        merger.append(&part)?;
+    }
+    merger.finish()
+}
+
+/// Each optional content group listed in `other` (a catalog whose objects
+/// are already in `doc`) that has the name of one of the document's groups,
+/// mapped to the document's group
+fn shared_layers(
+    doc: &Document,
+    catalog_id: ObjectId,
+    other: &Object,
+) -> HashMap<ObjectId, ObjectId> {
+    const OCGS: [&[u8]; 2] = [b"OCProperties", b"OCGs"];
+    let (Ok(catalog), Ok(other)) = (dictionary(doc, catalog_id), other.as_dict()) else {
+        return HashMap::new();
+    };
+    let name = |group: &Object| -> Option<(ObjectId, Object)> {
+        let id = group.as_reference().ok()?;
+        let name = doc.get_dictionary(id).ok()?.get(b"Name").ok()?.clone();
+        Some((id, name))
+    };
+    let groups: Vec<(ObjectId, Object)> = array_at(doc, catalog, &OCGS)
+        .iter()
+        .filter_map(name)
+        .collect();
+    array_at(doc, other, &OCGS)
+        .iter()
+        .filter_map(name)
+        .filter_map(|(id, other_name)| {
+            let (kept, _) = groups.iter().find(|(_, name)| *name == other_name)?;
+            Some((id, *kept))
+        })
+        .collect()
+}
+
+/// Point every reference in `object` to a key of `ids` at its value instead
+fn replace_references(object: &mut Object, ids: &HashMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(kept) = ids.get(id) {
+                *id = *kept;
+            }
+        }
+        Object::Array(items) => {
+            for item in items {
+                replace_references(item, ids);
+            }
+        }
+        Object::Dictionary(dict) => {
+            for (_, value) in dict.iter_mut() {
+                replace_references(value, ids);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, value) in stream.dict.iter_mut() {
+                replace_references(value, ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Add the form fields and layers in `other` (a catalog whose objects are
+/// already in `doc`) to the document's catalog. Items the document's arrays
+/// already hold, such as a shared layer, are not added again.
+fn merge_catalog(
+    doc: &mut Document,
+    catalog_id: ObjectId,
+    other: &Dictionary,
+) -> Result<(), String> {
+    let mut catalog = dictionary(doc, catalog_id)?.clone();
+    let mut copied: Vec<&[u8]> = Vec::new();
+    for path in MERGED_ARRAYS {
+        let key = path[0];
+        let Ok(value) = other.get(key) else {
+            continue;
+        };
+        if copied.contains(&key) {
+            continue;
+        }
+        if !catalog.has(key) {
+            // The first chunk with fields or layers brings their settings
+            catalog.set(key.to_vec(), resolve(doc, value));
+            copied.push(key);
+            continue;
+        }
+        let items = array_at(doc, other, path);
+        if !items.is_empty() {
+            append(doc, &mut catalog, path, items);
+        }
+    }
+    doc.objects.insert(catalog_id, Object::Dictionary(catalog));
+    Ok(())
+}
+
+/// The array at `path` under `dict`, following references
+fn array_at(doc: &Document, dict: &Dictionary, path: &[&[u8]]) -> Vec<Object> {
+    let Some((key, rest)) = path.split_first() else {
+        return Vec::new();
+    };
+    let Ok(value) = dict.get(key) else {
+        return Vec::new();
+    };
+    match (resolve(doc, value), rest.is_empty()) {
+        (Object::Array(items), true) => items,
+        (Object::Dictionary(child), false) => array_at(doc, &child, rest),
+        _ => Vec::new(),
+    }
+}
+
+/// Add `items` to the array at `path` under `dict`, copying referenced
+/// dictionaries along the path into `dict`
+fn append(doc: &Document, dict: &mut Dictionary, path: &[&[u8]], items: Vec<Object>) {
+    let Some((key, rest)) = path.split_first() else {
+        return;
+    };
+    let value = dict.get(key).ok().map(|value| resolve(doc, value));
+    let value = if rest.is_empty() {
+        let mut array = match value {
+            Some(Object::Array(array)) => array,
+            _ => Vec::new(),
+        };
+        for item in items {
+            if !array.contains(&item) {
+                array.push(item);
+            }
+        }
+        Object::Array(array)
+    } else {
+        let mut child = match value {
+            Some(Object::Dictionary(child)) => child,
+            _ => Dictionary::new(),
+        };
+        append(doc, &mut child, rest, items);
+        Object::Dictionary(child)
+    };
+    dict.set(key.to_vec(), value);
+}
+
+/// The object `object` refers to, or `object` itself
+fn resolve(doc: &Document, object: &Object) -> Object {
+    match object {
+        Object::Reference(id) => doc.get_object(*id).cloned().unwrap_or(Object::Null),
+        other => other.clone(),
+    }
+}
+
+fn root_id(doc: &Document) -> Result<ObjectId, String> {
+    doc.trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|e| format!("Failed to find PDF catalog: {}", e))
+}
+
+fn pages_id(doc: &Document, catalog_id: ObjectId) -> Result<ObjectId, String> {
+    dictionary(doc, catalog_id)?
+        .get(b"Pages")
+        .and_then(Object::as_reference)
+        .map_err(|e| format!("Failed to find page tree: {}", e))
+}
+
+fn dictionary(doc: &Document, id: ObjectId) -> Result<&Dictionary, String> {
+    doc.get_dictionary(id)
+        .map_err(|e| format!("Failed to access PDF object: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use printpdf::{Mm, PdfDocument, PdfPage, PdfSaveOptions};
+
+    fn pdf(pages: usize) -> Vec<u8> {
+        let mut doc = PdfDocument::new("Test");
+        doc.with_pages(
+            (0..pages)
+                .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), Vec::new()))
+                .collect(),
+        );
+        doc.save(&PdfSaveOptions::default(), &mut Vec::new())
+    }
+
+    #[test]
+    fn test_merge_pages() {
+        let merged = merge_pdfs(vec![pdf(2), pdf(1), pdf(3)]).unwrap();
+        let doc = Document::load_mem(&merged).unwrap();
+        assert_eq!(doc.get_pages().len(), 6);
+    }
+
+    #[test]
+    fn test_merge_form_fields() {
+        use crate::render::helpers::forms::{add_form_fields, TextField};
+
+        let field = TextField::new("answer", 20.0, 200.0, 40.0, 8.0);
+        let first = add_form_fields(pdf(1), &[vec![field.clone()]], 1).unwrap();
+        let second = add_form_fields(pdf(1), &[vec![field]], 2).unwrap();
+        let merged = merge_pdfs(vec![first, second]).unwrap();
+
+        let doc = Document::load_mem(&merged).unwrap();
+        let catalog = doc.catalog().unwrap();
+        assert_eq!(array_at(&doc, catalog, &[b"AcroForm", b"Fields"]).len(), 2);
+    }
+
+    #[test]
+    fn test_merger_keeps_layers() {
+        use crate::render::helpers::layer::add_answers_layer;
+
+        let layered = |pages: usize| {
+            let mut doc = PdfDocument::new("Test");
+            add_answers_layer(&mut doc, true);
+            doc.with_pages(
+                (0..pages)
+                    .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), Vec::new()))
+                    .collect(),
+            );
+            doc.save(&PdfSaveOptions::default(), &mut Vec::new())
+        };
+        let mut merger = PdfMerger::new();
+        for pages in [2, 1, 3] {
+            merger.append(&layered(pages)).unwrap();
+        }
+        let merged = merger.finish().unwrap();
+
+        let doc = Document::load_mem(&merged).unwrap();
+        assert_eq!(doc.get_pages().len(), 6);
+        // One answers layer, toggling every chunk's answers
+        let catalog = doc.catalog().unwrap();
+        let groups = array_at(&doc, catalog, &[b"OCProperties", b"OCGs"]);
+        assert_eq!(groups.len(), 1);
+        for key in [&b"ON"[..], b"OFF", b"Order"] {
+            let items = array_at(&doc, catalog, &[b"OCProperties", b"D", key]);
+            assert!(items.iter().all(|item| groups.contains(item)));
+            assert!(items.len() <= 1);
+        }
+        for page in doc.get_pages().into_values() {
+            let page = dictionary(&doc, page).unwrap();
+            let resources = resolve(&doc, page.get(b"Resources").unwrap());
+            let Ok(properties) = resources.as_dict().unwrap().get(b"Properties") else {
+                continue;
+            };
+            let properties = resolve(&doc, properties);
+            for (_, layer) in properties.as_dict().unwrap().iter() {
+                assert!(groups.contains(layer));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merger_needs_a_pdf() {
+        assert!(PdfMerger::new().finish().is_err());
+    }
+}
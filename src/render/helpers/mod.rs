@@ -1,21 +1,41 @@
 //! Helper utilities for PDF rendering
 
+pub mod background;
 pub mod card_assets;
 pub mod colors;
 pub mod compress;
+pub mod duplex;
 pub mod fonts;
+pub mod forms;
+pub mod grayscale;
+pub mod hyphenate;
+pub mod image_file;
 pub mod layer;
 pub mod layout;
+pub mod links;
+pub mod merge;
+pub mod parallel;
+pub mod progress;
+pub mod report;
+pub mod suit_letters;
 pub mod text_metrics;
+pub mod timings;
 
 pub use card_assets::{CardAssets, CardLoadError, CARD_HEIGHT_MM, CARD_WIDTH_MM};
 pub use colors::{SuitColors, BLACK};
 pub use compress::compress_pdf;
-pub use fonts::{BuiltinFontSet, FontFamily, FontManager};
+pub use fonts::{
+    BuiltinFontSet, EmbeddedFont, FontFamily, FontManager, FontRole, TextFont, TextFontSet,
+};
+pub use forms::{add_form_fields, TextField};
 pub use layer::LayerBuilder;
 pub use layout::LayoutEngine;
+pub use links::{add_links, Link, LinkTarget};
+pub use merge::{merge_pdfs, PdfMerger};
+pub use progress::{Progress, ProgressCallback, RenderProgress, RenderStage};
+pub use report::{RenderReport, WarningLog};
 pub use text_metrics::{
     get_builtin_measurer, get_helvetica_bold_measurer, get_helvetica_measurer,
     get_times_bold_italic_measurer, get_times_bold_measurer, get_times_italic_measurer,
-    get_times_measurer, BuiltinFontMeasurer, FontMetrics,
+    get_times_measurer, BuiltinFontMeasurer, EmbeddedFontMeasurer, FontMetrics,
 };
@@ -0,0 +1,31 @@
+//! Work spread over boards
+//!
+//! With the `parallel` feature (on by default) boards are handed to rayon's
+//! thread pool. Without it, e.g. for `wasm32-unknown-unknown`, which has no
+//! threads, they're done one after another on the calling thread. Results come
+//! back in the order of the boards either way.
+
+/// Apply `f` to each item, in parallel when the `parallel` feature is on
+#[cfg(feature = "parallel")]
+pub fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    use rayon::prelude::*;
+    items.par_iter().map(f).collect()
+}
+
+/// Apply `f` to each item, in order
+#[cfg(not(feature = "parallel"))]
+pub fn par_map<T, R>(items: &[T], f: impl Fn(&T) -> R) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_map_keeps_order() {
+        let items: Vec<u32> = (0..100).collect();
+        let squares = par_map(&items, |n| n * n);
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+}
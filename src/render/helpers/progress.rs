@@ -0,0 +1,97 @@
+//! Progress reports from the renderers, for callers showing a progress bar,
+//! and cancellation of a render in progress
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::RenderError;
+
+/// What a renderer is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStage {
+    /// Laying out boards on pages
+    Boards,
+    /// Saving and compressing the finished PDF
+    Saving,
+}
+
+/// One progress report: the stage, and how many of the boards are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderProgress {
+    pub stage: RenderStage,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Receives progress reports, on the thread doing the rendering
+pub type ProgressCallback = Arc<dyn Fn(RenderProgress) + Send + Sync>;
+
+/// A renderer's progress reporter and cancellation check; reports go nowhere
+/// without a callback, and a render can't be cancelled without a flag
+#[derive(Clone, Default)]
+pub struct Progress {
+    callback: Option<ProgressCallback>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Progress {
+    pub fn set_callback(&mut self, callback: ProgressCallback) {
+        self.callback = Some(callback);
+    }
+
+    /// Stop rendering once `cancel` is set, from any thread
+    pub fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// `RenderError::Cancelled` once the render has been cancelled
+    pub fn check_cancelled(&self) -> Result<(), RenderError> {
+        if self.is_cancelled() {
+            Err(RenderError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Report `done` of `total` boards laid out
+    pub fn boards(&self, done: usize, total: usize) {
+        self.report(RenderStage::Boards, done, total);
+    }
+
+    /// Report that every board is laid out and the PDF is being saved
+    pub fn saving(&self, total: usize) {
+        self.report(RenderStage::Saving, total, total);
+    }
+
+    fn report(&self, stage: RenderStage, done: usize, total: usize) {
+        if let Some(ref callback) = self.callback {
+            callback(RenderProgress { stage, done, total });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let mut progress = Progress::default();
+        assert!(progress.check_cancelled().is_ok());
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        progress.set_cancel(cancel.clone());
+        assert!(!progress.is_cancelled());
+        cancel.store(true, Ordering::Relaxed);
+        assert!(matches!(
+            progress.check_cancelled(),
+            Err(RenderError::Cancelled)
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Warnings gathered while rendering, returned with the PDF
+
+use std::sync::Mutex;
+
+use crate::error::RenderWarning;
+use crate::model::Board;
+
+/// A rendered PDF and what went wrong along the way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderReport {
+    pub pdf: Vec<u8>,
+    pub warnings: Vec<RenderWarning>,
+}
+
+impl RenderReport {
+    /// The PDF, with the warnings sent to the log
+    pub fn into_pdf(self) -> Vec<u8> {
+        for warning in &self.warnings {
+            log::warn!("{}", warning);
+        }
+        self.pdf
+    }
+}
+
+/// A renderer's warnings for the render in progress
+#[derive(Debug, Default)]
+pub struct WarningLog(Mutex<Vec<RenderWarning>>);
+
+impl WarningLog {
+    /// Record a warning, unless it has been recorded already (page banners
+    /// and the like are drawn once per page)
+    pub fn push(&self, warning: RenderWarning) {
+        let mut warnings = self.0.lock().unwrap();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// The warnings so far, leaving the log empty for the next render
+    pub fn take(&self) -> Vec<RenderWarning> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// How warnings name a board: its [Board] tag, or "?" without one
+pub fn board_label(board: &Board) -> String {
+    board
+        .board_id
+        .clone()
+        .or_else(|| board.number.map(|n| n.to_string()))
+        .unwrap_or_else(|| "?".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_log() {
+        let log = WarningLog::default();
+        log.push(RenderWarning::TruncatedTitle("Long".to_string()));
+        log.push(RenderWarning::EmptyBoard("3".to_string()));
+        log.push(RenderWarning::TruncatedTitle("Long".to_string()));
+        assert_eq!(
+            log.take(),
+            [
+                RenderWarning::TruncatedTitle("Long".to_string()),
+                RenderWarning::EmptyBoard("3".to_string())
+            ]
+        );
+        assert!(log.take().is_empty());
+    }
+}
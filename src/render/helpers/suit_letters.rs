@@ -0,0 +1,97 @@
+//! Letter suits (S/H/D/C) instead of suit symbols
+//!
+//! Suit symbols are always drawn in the bundled symbol font, so `--suit-style
+//! letters` is applied to finished pages like `--grayscale`: text set in the
+//! symbol font is switched to Times Bold with each symbol replaced by its letter.
+//! The letters are drawn slightly smaller so they take about the width the
+//! layout reserved for the symbol.
+
+use printpdf::{BuiltinFont, FontId, Op, PdfFontHandle, PdfPage, Pt, TextItem};
+
+/// Font used for suit letters
+const LETTER_FONT: BuiltinFont = BuiltinFont::TimesBold;
+
+/// Letter size relative to the symbol size it replaces
+const LETTER_SCALE: f32 = 0.85;
+
+/// Replace suit symbols with letters on every page
+pub fn apply_suit_letters(pages: &mut [PdfPage], symbol_font: &FontId) {
+    for page in pages {
+        suit_letter_ops(&mut page.ops, symbol_font);
+    }
+}
+
+/// Replace suit symbols with letters in an operation stream
+pub fn suit_letter_ops(ops: &mut [Op], symbol_font: &FontId) {
+    let mut in_symbol_font = false;
+    for op in ops.iter_mut() {
+        match op {
+            Op::SetFont { size, font } => {
+                in_symbol_font = matches!(font, PdfFontHandle::External(id) if id == symbol_font);
+                if in_symbol_font {
+                    *size = Pt(size.0 * LETTER_SCALE);
+                    *font = PdfFontHandle::Builtin(LETTER_FONT);
+                }
+            }
+            Op::ShowText { items } if in_symbol_font => {
+                for item in items.iter_mut() {
+                    if let TextItem::Text(text) = item {
+                        *text = text.chars().map(suit_letter).collect();
+                    }
+                }
+            }
+            Op::EndTextSection => in_symbol_font = false,
+            _ => {}
+        }
+    }
+}
+
+/// Letter for a suit symbol (other characters are unchanged)
+pub fn suit_letter(c: char) -> char {
+    match c {
+        '♠' => 'S',
+        '♥' => 'H',
+        '♦' => 'D',
+        '♣' => 'C',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::helpers::layer::LayerBuilder;
+    use printpdf::Mm;
+
+    fn shown_text(ops: &[Op]) -> Vec<String> {
+        ops.iter()
+            .filter_map(|op| match op {
+                Op::ShowText { items } => items.iter().find_map(|item| match item {
+                    TextItem::Text(text) => Some(text.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_symbol_font_text_is_replaced() {
+        let symbol_font = FontId::new();
+        let mut layer = LayerBuilder::new();
+        layer.use_text("♠", 10.0, Mm(0.0), Mm(0.0), &symbol_font);
+        layer.use_text_builtin("AKQ", 10.0, Mm(5.0), Mm(0.0), BuiltinFont::TimesRoman);
+        layer.use_text("♥♦♣", 10.0, Mm(0.0), Mm(5.0), &symbol_font);
+        let mut ops = layer.into_ops();
+        suit_letter_ops(&mut ops, &symbol_font);
+
+        assert_eq!(shown_text(&ops), vec!["S", "AKQ", "HDC"]);
+        assert!(!ops.iter().any(|op| matches!(
+            op,
+            Op::SetFont {
+                font: PdfFontHandle::External(_),
+                ..
+            }
+        )));
+    }
+}
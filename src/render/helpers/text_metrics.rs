@@ -1,7 +1,16 @@
 //! Text measurement utilities for PDF builtin fonts
 //!
 //! This module provides functions to measure text dimensions before rendering,
-//! allowing for precise layout calculations using PDF builtin font metrics.
+//! allowing for precise layout calculations using PDF builtin font metrics, or
+//! the metrics of an embedded TrueType/OpenType font.
+//!
+//! Layout measures the same words many times over, so each measurer keeps
+//! its advance widths in a table indexed by character code, and words in an
+//! embedded font that fall outside the table have their widths memoized.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use printpdf::BuiltinFont;
 
@@ -77,15 +86,25 @@ impl FontMetrics {
 /// Uses hardcoded Adobe AFM metrics for accurate text measurement.
 pub struct BuiltinFontMeasurer {
     font: BuiltinFont,
+    /// Width of each ASCII character, looked up once when created
+    widths: [u16; 128],
 }
 
 impl BuiltinFontMeasurer {
     pub fn new(font: BuiltinFont) -> Self {
-        Self { font }
+        let mut widths = [0; 128];
+        for (code, width) in widths.iter_mut().enumerate() {
+            *width = Self::ascii_width(font, code as u8);
+        }
+        Self { font, widths }
     }
 
     /// Get character width in 1000 units per em
     fn char_width(&self, c: char) -> u16 {
+        if let Some(&width) = self.widths.get(c as usize) {
+            return width;
+        }
+
         // Special handling for suit symbols (rendered with embedded DejaVu Sans)
         // DejaVu Sans has 2048 units per em, suit symbols are 1836 units wide
         // Scaled to 1000 units: 1836 * 1000 / 2048 = 896
@@ -93,13 +112,13 @@ impl BuiltinFontMeasurer {
             return 896;
         }
 
-        // ASCII printable range only - builtin fonts are Win-1252
-        if !c.is_ascii() {
-            return 500; // Default width for non-ASCII
-        }
+        // Builtin fonts are Win-1252; only ASCII widths are tabulated
+        500 // Default width for non-ASCII
+    }
 
-        let code = c as u8;
-        match self.font {
+    /// Width of an ASCII character from the font's AFM table
+    fn ascii_width(font: BuiltinFont, code: u8) -> u16 {
+        match font {
             BuiltinFont::TimesRoman => TIMES_ROMAN_WIDTHS
                 .get(code as usize)
                 .copied()
@@ -282,6 +301,158 @@ pub fn get_builtin_measurer(font: BuiltinFont) -> &'static BuiltinFontMeasurer {
     }
 }
 
+// =============================================================================
+// Embedded Font Metrics
+// =============================================================================
+//
+// Fonts loaded from TrueType/OpenType files are measured with the advance
+// widths and vertical metrics read from the font itself.
+
+/// Source of [EmbeddedFontMeasurer] ids
+static NEXT_FONT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Longest text, in bytes, whose width is memoized
+const MAX_CACHED_WORD: usize = 64;
+
+/// Memoized widths the cache holds before it starts over
+const MAX_CACHED_WORDS: usize = 16_384;
+
+thread_local! {
+    /// Widths in font units of words outside the Latin-1 table, by font id.
+    /// Per thread, so boards measured in parallel don't contend for it.
+    static WORD_WIDTHS: RefCell<HashMap<u64, HashMap<String, u32>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Text measurer for an embedded TrueType/OpenType font
+#[derive(Debug, Clone)]
+pub struct EmbeddedFontMeasurer {
+    /// Identifies this font's entries in the word width cache
+    id: u64,
+    metrics: FontMetrics,
+    /// Advance width in font units for each character the font maps
+    widths: HashMap<char, u16>,
+    /// Advance widths of U+0000 to U+00FF, from `widths`
+    latin1: [u16; 256],
+    /// Advance width of the missing-glyph (.notdef) glyph
+    missing_width: u16,
+}
+
+impl EmbeddedFontMeasurer {
+    /// Read metrics from font file data (face 0). Returns None if the data
+    /// isn't a usable TrueType/OpenType font.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let face = ttf_parser::Face::parse(data, 0).ok()?;
+
+        let mut widths = HashMap::new();
+        if let Some(cmap) = face.tables().cmap {
+            for subtable in cmap.subtables.into_iter().filter(|t| t.is_unicode()) {
+                subtable.codepoints(|cp| {
+                    let width = char::from_u32(cp)
+                        .zip(subtable.glyph_index(cp))
+                        .and_then(|(c, gid)| Some((c, face.glyph_hor_advance(gid)?)));
+                    if let Some((c, w)) = width {
+                        widths.entry(c).or_insert(w);
+                    }
+                });
+            }
+        }
+
+        let ascender = face.ascender();
+        let metrics = FontMetrics {
+            units_per_em: face.units_per_em() as i32,
+            ascender,
+            descender: face.descender(),
+            line_gap: face.line_gap(),
+            // Fonts without an OS/2 cap height: approximate from the ascender
+            cap_height: face
+                .capital_height()
+                .unwrap_or((ascender as f32 * 0.7) as i16),
+        };
+
+        let missing_width = face.glyph_hor_advance(ttf_parser::GlyphId(0)).unwrap_or(0);
+        let mut latin1 = [missing_width; 256];
+        for (code, width) in latin1.iter_mut().enumerate() {
+            if let Some(&w) = widths.get(&(code as u8 as char)) {
+                *width = w;
+            }
+        }
+
+        Some(Self {
+            id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+            metrics,
+            widths,
+            latin1,
+            missing_width,
+        })
+    }
+
+    /// Whether the font has a glyph for the character
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.widths.contains_key(&c)
+    }
+
+    /// Measure text width in mm
+    pub fn measure_width_mm(&self, text: &str, font_size: f32) -> f32 {
+        self.text_units(text) as f32 / self.metrics.units_per_em as f32 * font_size * 0.3528
+    }
+
+    /// Advance width of `text` in font units
+    fn text_units(&self, text: &str) -> u32 {
+        if text.len() > MAX_CACHED_WORD || text.chars().all(|c| (c as usize) < 256) {
+            return self.sum_units(text);
+        }
+        WORD_WIDTHS.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if let Some(&units) = cache.get(&self.id).and_then(|words| words.get(text)) {
+                return units;
+            }
+            if cache.values().map(HashMap::len).sum::<usize>() >= MAX_CACHED_WORDS {
+                cache.clear();
+            }
+            let units = self.sum_units(text);
+            cache
+                .entry(self.id)
+                .or_default()
+                .insert(text.to_string(), units);
+            units
+        })
+    }
+
+    fn sum_units(&self, text: &str) -> u32 {
+        text.chars()
+            .map(|c| match self.latin1.get(c as usize) {
+                Some(&w) => w as u32,
+                None => *self.widths.get(&c).unwrap_or(&self.missing_width) as u32,
+            })
+            .sum()
+    }
+
+    /// Get ascender height in mm
+    pub fn ascender_mm(&self, font_size: f32) -> f32 {
+        self.metrics.ascender_mm(font_size)
+    }
+
+    /// Get recommended line height in mm
+    pub fn line_height_mm(&self, font_size: f32) -> f32 {
+        self.metrics.line_height_mm(font_size)
+    }
+}
+
+impl TextMeasure for EmbeddedFontMeasurer {
+    fn measure_text(&self, text: &str, font_size: f32) -> f32 {
+        self.measure_width_mm(text, font_size)
+    }
+
+    fn cap_height_mm(&self, font_size: f32) -> f32 {
+        self.metrics.cap_height_mm(font_size)
+    }
+
+    fn descender_mm(&self, font_size: f32) -> f32 {
+        self.metrics.descender_mm(font_size)
+    }
+}
+
 // =============================================================================
 // Adobe AFM Character Width Tables (ASCII subset, in 1000 units per em)
 // =============================================================================
@@ -412,6 +583,47 @@ mod tests {
         assert!(cap_height > 1.5 && cap_height < 4.0);
     }
 
+    #[test]
+    fn test_embedded_font_measurement() {
+        let data = include_bytes!("../../../assets/fonts/DejaVuSans-Suits.ttf");
+        let measurer = EmbeddedFontMeasurer::from_bytes(data).unwrap();
+
+        // DejaVu Sans suit symbols are 1836 of 2048 units wide
+        assert!(measurer.has_glyph('♠'));
+        let width = measurer.measure_width_mm("♠", 10.0);
+        let expected = 1836.0 / 2048.0 * 10.0 * 0.3528;
+        assert!((width - expected).abs() < 0.01);
+        assert!(measurer.cap_height_mm(10.0) > 0.0);
+    }
+
+    #[test]
+    fn test_embedded_font_word_cache() {
+        let data = include_bytes!("../../../assets/fonts/DejaVuSans-Suits.ttf");
+        let measurer = EmbeddedFontMeasurer::from_bytes(data).unwrap();
+
+        // Latin-1 text is summed from the table; other words are memoized
+        assert_eq!(measurer.text_units("AK"), measurer.sum_units("AK"));
+        let first = measurer.measure_width_mm("♠♥♦♣", 10.0);
+        let cached = WORD_WIDTHS.with(|cache| cache.borrow()[&measurer.id].get("♠♥♦♣").copied());
+        assert_eq!(cached, Some(measurer.sum_units("♠♥♦♣")));
+        assert_eq!(measurer.measure_width_mm("♠♥♦♣", 10.0), first);
+        assert!((first - 4.0 * measurer.measure_width_mm("♠", 10.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_builtin_advance_table() {
+        let measurer = get_times_measurer();
+        assert_eq!(measurer.char_width('W'), 944);
+        assert_eq!(measurer.char_width(' '), 250);
+        assert_eq!(measurer.char_width('♠'), 896);
+        assert_eq!(measurer.char_width('é'), 500);
+    }
+
+    #[test]
+    fn test_embedded_font_rejects_invalid_data() {
+        assert!(EmbeddedFontMeasurer::from_bytes(b"not a font").is_none());
+    }
+
     #[test]
     fn test_helvetica_vs_times() {
         let times = get_times_measurer();
@@ -0,0 +1,158 @@
+//! Time spent per stage and per board (`--timings`)
+//!
+//! Off unless [enable] is called, in which case the stages of every render
+//! in the process (parsing, font loading, measuring, drawing, saving,
+//! compressing) and the time spent on each board are added up until
+//! [take] collects them. Boards drawn in parallel each count their own time,
+//! so board times can add up to more than the stage they were drawn in.
+//!
+//! Stage times are also logged at debug level under [LOG_TARGET] (`-vv`).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Log target for stage times
+pub const LOG_TARGET: &str = "pbn_to_pdf::timings";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LOG: Mutex<TimingLog> = Mutex::new(TimingLog::new());
+
+/// Times collected since timing was enabled
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimingLog {
+    /// Stages in the order first seen, with their total time
+    pub stages: Vec<(String, Duration)>,
+    /// Boards by label, with the time spent measuring and drawing each
+    pub boards: Vec<(String, Duration)>,
+}
+
+impl TimingLog {
+    const fn new() -> Self {
+        Self {
+            stages: Vec::new(),
+            boards: Vec::new(),
+        }
+    }
+
+    /// The stages, then the `slowest` slowest boards, one per line
+    pub fn report(&self, slowest: usize) -> String {
+        let mut lines = vec!["Timings:".to_string()];
+        for (stage, time) in &self.stages {
+            lines.push(format!("  {:<24} {:>9.1} ms", stage, millis(*time)));
+        }
+        if !self.boards.is_empty() {
+            let mut boards = self.boards.clone();
+            boards.sort_by(|a, b| b.1.cmp(&a.1));
+            lines.push(format!(
+                "Slowest boards ({} of {}):",
+                slowest.min(boards.len()),
+                boards.len()
+            ));
+            for (label, time) in boards.iter().take(slowest) {
+                lines.push(format!("  board {:<18} {:>9.1} ms", label, millis(*time)));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Start collecting times, discarding any collected before
+pub fn enable() {
+    *collected() = TimingLog::new();
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop collecting and return what was collected, if timing was on
+pub fn take() -> Option<TimingLog> {
+    ENABLED
+        .swap(false, Ordering::Relaxed)
+        .then(|| std::mem::take(&mut *collected()))
+}
+
+/// Run `f`, adding its time to `stage` and logging it
+pub fn time<T>(stage: &str, f: impl FnOnce() -> T) -> T {
+    // Instant isn't available everywhere (wasm32), so only touch it when
+    // someone is listening
+    let logging = log::log_enabled!(target: LOG_TARGET, log::Level::Debug);
+    if !logging && !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    log::debug!(target: LOG_TARGET, "{}: {:.1} ms", stage, millis(elapsed));
+    if is_enabled() {
+        add(&mut collected().stages, stage, elapsed);
+    }
+    result
+}
+
+/// Run `f`, adding its time to the board labelled `label`
+pub fn time_board<T>(label: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    add(&mut collected().boards, &label(), elapsed);
+    result
+}
+
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn add(entries: &mut Vec<(String, Duration)>, name: &str, elapsed: Duration) {
+    match entries.iter_mut().find(|(entry, _)| entry == name) {
+        Some((_, total)) => *total += elapsed,
+        None => entries.push((name.to_string(), elapsed)),
+    }
+}
+
+fn collected() -> MutexGuard<'static, TimingLog> {
+    LOG.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_accumulates() {
+        let mut entries = Vec::new();
+        add(&mut entries, "save", Duration::from_millis(5));
+        add(&mut entries, "compress", Duration::from_millis(2));
+        add(&mut entries, "save", Duration::from_millis(3));
+        assert_eq!(
+            entries,
+            [
+                ("save".to_string(), Duration::from_millis(8)),
+                ("compress".to_string(), Duration::from_millis(2))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_lists_slowest_boards() {
+        let log = TimingLog {
+            stages: vec![("parse".to_string(), Duration::from_micros(1500))],
+            boards: vec![
+                ("1".to_string(), Duration::from_millis(2)),
+                ("2".to_string(), Duration::from_millis(9)),
+                ("3".to_string(), Duration::from_millis(4)),
+            ],
+        };
+        let report = log.report(2);
+        assert!(report.contains("parse"));
+        assert!(report.contains("1.5 ms"));
+        assert!(report.contains("Slowest boards (2 of 3)"));
+        assert!(report.find("board 2").unwrap() < report.find("board 3").unwrap());
+        assert!(!report.contains("board 1 "));
+    }
+}
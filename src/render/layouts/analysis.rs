@@ -1,48 +1,57 @@
-use crate::config::Settings;
-use crate::error::RenderError;
+use crate::cli::{HiddenHandStyle, SuitStyle};
+use crate::config::{DiagramSide, Locale, Settings};
+use crate::error::{RenderError, RenderWarning};
 use crate::model::card::RankExt;
-use crate::model::{BidSuit, Board, Direction, Suit, SUITS_DISPLAY_ORDER};
-use printpdf::{
-    BuiltinFont, Color, FontId, Mm, PaintMode, PdfDocument, PdfPage, PdfSaveOptions, Rgb,
+use crate::model::{
+    Auction, BidSuit, Board, CommentaryPlacement, Direction, PlaySequence, PlayerNames, Standing,
+    Suit, TableResult, SUITS_DISPLAY_ORDER,
 };
+use printpdf::{Color, FontId, LayerInternalId, Mm, PaintMode, PdfDocument, PdfPage, XObjectId};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use crate::render::components::bidding_table::BiddingTableRenderer;
-use crate::render::components::commentary::{CommentaryRenderer, FloatLayout};
+use crate::render::components::commentary::{contains_cjk, CommentaryRenderer, FloatLayout};
 use crate::render::components::hand_diagram::{DiagramDisplayOptions, HandDiagramRenderer};
-use crate::render::helpers::colors::{SuitColors, BLACK};
+use crate::render::components::header_footer::add_header_footer;
+use crate::render::components::qr_code::QrCodeRenderer;
+use crate::render::components::ranking::ranking_pages;
+use crate::render::components::title_block::add_title_block;
+use crate::render::helpers::background::{add_image_background, add_pdf_background};
+use crate::render::helpers::card_assets::CardAssets;
+use crate::render::helpers::colors::{SuitColors, Theme, BLACK};
 use crate::render::helpers::compress::compress_pdf;
-use crate::render::helpers::fonts::FontManager;
-use crate::render::helpers::layer::LayerBuilder;
-use crate::render::helpers::text_metrics::{self, get_times_measurer};
-
-/// Light gray color for debug boxes (component level)
-const DEBUG_BOX_COLOR: Rgb = Rgb {
-    r: 0.7,
-    g: 0.7,
-    b: 0.7,
-    icc_profile: None,
-};
+use crate::render::helpers::duplex::{pad_for_duplex, rotate_back_pages};
+use crate::render::helpers::fonts::{save_options, FontManager, FontRole, TextFont};
+use crate::render::helpers::forms::{add_form_fields, TextField};
+use crate::render::helpers::grayscale::{apply_grayscale, grayscale_xobjects};
+use crate::render::helpers::layer::{add_answers_layer, LayerBuilder};
+use crate::render::helpers::links::{add_links, Link};
+use crate::render::helpers::parallel::par_map;
+use crate::render::helpers::progress::{Progress, ProgressCallback};
+use crate::render::helpers::report::{board_label, RenderReport, WarningLog};
+
+use super::renderer::LayoutRenderer;
+use crate::render::helpers::suit_letters::apply_suit_letters;
+use crate::render::helpers::text_metrics::get_times_measurer;
+use crate::render::helpers::timings;
 
-/// Orange color for board-level debug boxes
-const DEBUG_BOARD_BOX_COLOR: Rgb = Rgb {
-    r: 1.0,
-    g: 0.5,
-    b: 0.0,
-    icc_profile: None,
-};
 // Debug boxes are now controlled via settings.debug_boxes
 
-/// Dark gray color for separator lines
-const SEPARATOR_COLOR: Rgb = Rgb {
-    r: 0.4,
-    g: 0.4,
-    b: 0.4,
-    icc_profile: None,
-};
-
 /// Separator line thickness
 const SEPARATOR_THICKNESS: f32 = 0.5;
 
+/// Space between columns in the multi-column layout, in mm
+const COLUMN_GUTTER: f32 = 5.0;
+
+/// Boards drawn in parallel between progress reports and cancellation checks
+const BOARD_BATCH: usize = 16;
+
+/// Space between the bidding tables of a board's rooms, in mm
+const ROOM_GAP: f32 = 6.0;
+
 /// Special board name that triggers a column break
 const COLUMN_BREAK_NAME: &str = "column-break";
 /// Special board name that triggers a page break
@@ -81,6 +90,53 @@ fn is_page_break(board: &Board) -> bool {
         .unwrap_or(false)
 }
 
+/// Display options for a board's hand diagram
+fn diagram_options(board: &Board, settings: &Settings) -> DiagramDisplayOptions {
+    let mut options = DiagramDisplayOptions::from_deal(&board.deal, &board.hidden)
+        .with_hidden_style(settings.hidden_hand_style)
+        .with_hand_hcp(
+            settings.show_hcp && settings.hand_hcp,
+            settings.distribution_points,
+        );
+    options = options
+        .with_rotated_east_west(settings.rotate_east_west)
+        .with_double_dummy(board.double_dummy.filter(|_| settings.show_dd_tricks));
+    if settings.vulnerable_compass {
+        options = options.with_vulnerability(board.vulnerable);
+    }
+    if settings.lead_marker {
+        let lead = board
+            .play
+            .as_ref()
+            .and_then(|play| Some((play.opening_leader, play.tricks.first()?.cards[0]?)));
+        if let Some((leader, card)) = lead {
+            options = options.with_lead(leader, card);
+        }
+    }
+    if !settings.board_in_compass {
+        return options;
+    }
+    let flags = board.bc_flags;
+    options.with_board_in_compass(
+        board
+            .board_id
+            .clone()
+            .filter(|_| flags.map(|f| !f.hide_board()).unwrap_or(true)),
+        board
+            .dealer
+            .filter(|_| flags.map(|f| !f.hide_dealer()).unwrap_or(true)),
+    )
+}
+
+/// Whether the board number and dealer go in the compass instead of the title.
+/// Diagrams without a compass (single visible hand or single card) keep the title.
+fn board_in_compass(board: &Board, settings: &Settings, show_diagram: bool) -> bool {
+    show_diagram
+        && settings.board_in_compass
+        && board.deal.get_single_visible_card(&board.hidden).is_none()
+        && !diagram_options(board, settings).hide_compass
+}
+
 /// Visibility flags for a board, computed once and reused
 struct BoardVisibility {
     show_board: bool,
@@ -104,22 +160,26 @@ impl BoardVisibility {
         let has_content = !deal_is_empty || has_auction;
         // Board label is tied to diagram - if BCFlags says no diagram, no board label either
         let show_diagram_flag = flags.map(|f| f.show_diagram()).unwrap_or(true);
+        let show_diagram = !deal_is_empty && show_diagram_flag && !board.hidden.all_hidden();
+        let in_compass = board_in_compass(board, settings, show_diagram);
         Self {
             show_board: has_content
                 && flags.map(|f| !f.hide_board()).unwrap_or(true)
-                && show_diagram_flag,
+                && show_diagram_flag
+                && !in_compass,
             show_dealer: has_content
                 && flags.map(|f| !f.hide_dealer()).unwrap_or(true)
-                && show_diagram_flag,
+                && show_diagram_flag
+                && !in_compass,
             show_vulnerable: has_content
                 && flags.map(|f| !f.hide_vulnerable()).unwrap_or(true)
                 && show_diagram_flag,
-            show_diagram: !deal_is_empty && show_diagram_flag && !board.hidden.all_hidden(),
+            show_diagram,
             show_auction: has_auction
                 && flags.map(|f| f.show_auction()).unwrap_or(true)
                 && settings.show_bidding,
             show_commentary: settings.show_commentary
-                && board.commentary.iter().any(|c| !c.is_blank())
+                && board.general_commentary().next().is_some()
                 && flags
                     .map(|f| f.show_event_commentary() || f.show_final_commentary())
                     .unwrap_or(true),
@@ -136,14 +196,146 @@ impl BoardVisibility {
     }
 }
 
+/// Where the columns go in the multi-column layout
+struct ColumnLayout {
+    margin_left: f32,
+    margin_right: f32,
+    /// Column pitch, gutter included
+    column_width: f32,
+    /// Width boards are drawn in
+    usable_column_width: f32,
+}
+
 /// Main document renderer
 pub struct DocumentRenderer {
     settings: Settings,
+    theme: Theme,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 impl DocumentRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let theme = Theme::from_settings(&settings);
+        Self {
+            settings,
+            theme,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
+    }
+
+    /// A renderer for `board` alone, if it has settings overrides; its
+    /// progress and warnings stay with this renderer
+    fn board_renderer(&self, board: &Board) -> Option<DocumentRenderer> {
+        match self.settings.for_board(board) {
+            Cow::Owned(settings) => Some(DocumentRenderer::new(settings)),
+            Cow::Borrowed(_) => None,
+        }
+    }
+
+    /// Column geometry for the multi-column layout
+    fn column_layout(&self) -> ColumnLayout {
+        // Minimum column width for readable content (approx 60mm per column)
+        const MIN_COLUMN_WIDTH: f32 = 60.0;
+        const DEFAULT_MARGIN: f32 = 15.0;
+        let num_columns = self.settings.column_count.max(1) as usize;
+        let gutter = COLUMN_GUTTER;
+
+        // Calculate minimum content width needed for N columns
+        let min_content_width =
+            num_columns as f32 * MIN_COLUMN_WIDTH + (num_columns - 1) as f32 * gutter;
+
+        // Check if specified margins leave enough room for columns
+        let (margin_left, margin_right) = if self.settings.content_width() < min_content_width {
+            // Margins too large for multi-column layout, use defaults
+            (DEFAULT_MARGIN, DEFAULT_MARGIN)
+        } else {
+            (self.settings.margin_left, self.settings.margin_right)
+        };
+
+        let content_width = self.settings.page_width - margin_left - margin_right;
+        let column_width = content_width / num_columns as f32;
+        ColumnLayout {
+            margin_left,
+            margin_right,
+            column_width,
+            usable_column_width: column_width
+                - gutter * (num_columns - 1) as f32 / num_columns as f32,
+        }
+    }
+
+    /// Width in mm a board is drawn in: one column of the multi-column
+    /// layout, or the whole page width between the margins
+    pub fn column_width(&self) -> f32 {
+        if self.settings.column_count >= 2 {
+            self.column_layout().usable_column_width
+        } else {
+            self.settings.content_width()
+        }
+    }
+
+    /// Height in mm of a page's columns, between the margins
+    pub fn column_height(&self) -> f32 {
+        self.settings.content_height()
+    }
+
+    /// Height in mm `board` takes in a column `column_width` wide, its
+    /// overrides applied; 0.0 for break markers and boards with nothing to show
+    pub fn board_height(&self, board: &Board, column_width: f32) -> f32 {
+        let board_renderer = self.board_renderer(board);
+        let renderer = board_renderer.as_ref().unwrap_or(self);
+        renderer.measure_board_height(board, column_width)
+    }
+
+    /// Height in mm of `board`'s bidding table in a column `column_width`
+    /// wide; 0.0 when it has no auction or the auction is hidden
+    pub fn auction_height(&self, board: &Board, column_width: f32) -> f32 {
+        let board_renderer = self.board_renderer(board);
+        let renderer = board_renderer.as_ref().unwrap_or(self);
+        let visibility = BoardVisibility::from_board(board, &renderer.settings);
+        match board.auction {
+            Some(ref auction) if visibility.show_auction => {
+                let bid_settings = renderer.auction_settings(&board.players, Some(column_width));
+                renderer.measure_auctions_height(board, auction, Some(column_width), &bid_settings)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Height in mm of `board`'s commentary in a column `column_width` wide,
+    /// first baseline to last; 0.0 when it has none or it is hidden
+    pub fn commentary_height(&self, board: &Board, column_width: f32) -> f32 {
+        let board_renderer = self.board_renderer(board);
+        let renderer = board_renderer.as_ref().unwrap_or(self);
+        if !BoardVisibility::from_board(board, &renderer.settings).show_commentary {
+            return 0.0;
+        }
+        let blocks: Vec<_> = board.general_commentary().collect();
+        let spacing = blocks.len().saturating_sub(1) as f32 * renderer.settings.line_height;
+        blocks
+            .iter()
+            .map(|block| renderer.measure_commentary_height(block, column_width))
+            .sum::<f32>()
+            + spacing
+    }
+
+    /// Whether `board` fits in one column; boards that don't run off the
+    /// bottom of the page
+    pub fn board_fits(&self, board: &Board) -> bool {
+        self.board_height(board, self.column_width()) <= self.column_height()
     }
 
     /// Measure the height a board would use in a column without rendering
@@ -213,7 +405,7 @@ impl DocumentRenderer {
 
         // Diagram height
         if visibility.show_diagram {
-            let diagram_options = DiagramDisplayOptions::from_deal(&board.deal, &board.hidden);
+            let diagram_options = diagram_options(board, &self.settings);
 
             // Check for single-card deal - renders just a rank number, not a full diagram
             let is_single_card = board.deal.get_single_visible_card(&board.hidden).is_some();
@@ -252,25 +444,9 @@ impl DocumentRenderer {
         if visibility.show_auction {
             if let Some(ref auction) = board.auction {
                 // Use narrowed bid column width if 4 columns don't fit
-                let effective_bid_col_width =
-                    self.settings.bid_column_width.min(column_width / 4.0);
-                let narrowed_settings;
-                let bid_settings = if effective_bid_col_width < self.settings.bid_column_width {
-                    narrowed_settings = {
-                        let mut s = self.settings.clone();
-                        s.bid_column_width = effective_bid_col_width;
-                        s
-                    };
-                    Some(&narrowed_settings as &Settings)
-                } else {
-                    None
-                };
-                let mut auction_height = self.measure_auction_height(
-                    auction,
-                    &board.players,
-                    Some(column_width),
-                    bid_settings,
-                );
+                let bid_settings = self.auction_settings(&board.players, Some(column_width));
+                let mut auction_height =
+                    self.measure_auctions_height(board, auction, Some(column_width), &bid_settings);
 
                 // For 2-column inline board labels, we skip the spacing row before the header
                 let is_two_col =
@@ -287,23 +463,36 @@ impl DocumentRenderer {
                     .and_then(|p| p.tricks.first())
                     .and_then(|t| t.cards[0])
                     .is_some();
+                let has_best_leads = self.settings.show_best_leads && !board.best_leads.is_empty();
                 let has_more_below = visibility.show_commentary && !board.commentary.is_empty();
 
                 // Spacing after auction (only if there's contract or lead)
-                if has_contract || has_lead {
+                if has_contract || has_best_leads || has_lead {
                     height += line_height;
                 }
 
                 // Contract line
                 if has_contract {
                     // Only add spacing if there's more content below
-                    if has_lead || has_more_below {
+                    if has_best_leads || has_lead || has_more_below {
                         height += line_height;
                     }
                 }
 
-                // Opening lead line
+                // Best leads line
+                if has_best_leads && (has_lead || has_more_below) {
+                    height += line_height;
+                }
+
+                // Opening lead line, with the play's notes below it
                 if has_lead {
+                    if let Some(ref play) = board.play {
+                        height += BiddingTableRenderer::measure_footnotes_static(
+                            &self.play_notes(play),
+                            &bid_settings,
+                            Some(column_width),
+                        );
+                    }
                     // Only add spacing if there's more content below
                     if has_more_below {
                         height += line_height;
@@ -314,8 +503,7 @@ impl DocumentRenderer {
 
         // Commentary height (skip blank blocks)
         if visibility.show_commentary {
-            let non_blank_blocks: Vec<_> =
-                board.commentary.iter().filter(|c| !c.is_blank()).collect();
+            let non_blank_blocks: Vec<_> = board.general_commentary().collect();
             let block_count = non_blank_blocks.len();
 
             // In centered mode, add spacing between event commentary and diagram/auction
@@ -362,16 +550,169 @@ impl DocumentRenderer {
         // Calculate compass size (same logic as HandDiagramRenderer::compass_box_size)
         let compass_size = measurer.cap_height_mm(self.settings.body_font_size) * 3.5;
 
+        // The North and South rows are left out when their hands are
+        // (an East-West pair on its own)
+        let north_row = if options.omits(Direction::North) {
+            0.0
+        } else {
+            hand_h
+        };
         if options.is_fragment {
             // Fragment: 3 rows with compass centering offset
             let compass_center_offset = (compass_size - hand_h) / 2.0;
-            3.0 * hand_h + 2.0 * compass_center_offset
+            let south_row = if options.omits(Direction::South) {
+                0.0
+            } else {
+                hand_h
+            };
+            north_row + hand_h + 2.0 * compass_center_offset + south_row
         } else {
-            // Full deal: 3 rows of hands
-            3.0 * hand_h + 2.0
+            // Full deal: 3 rows of hands (see HandDiagramRenderer::middle_row_height)
+            let middle_row = if options.rotate_east_west {
+                self.settings.hand_width.max(compass_size)
+            } else {
+                hand_h
+            };
+            // South shares its row with the HCP box and double-dummy table
+            let south_row = if options.omits(Direction::South)
+                && !self.settings.show_hcp
+                && options.double_dummy.is_none()
+            {
+                0.0
+            } else {
+                hand_h + 2.0
+            };
+            north_row + middle_row + south_row
         }
     }
 
+    /// Settings for a board's bidding table: columns widened to fit the column
+    /// headers (player names) and narrowed so 4 columns fit in `max_width`
+    fn auction_settings(&self, players: &PlayerNames, max_width: Option<f32>) -> Cow<'_, Settings> {
+        let mut width = BiddingTableRenderer::column_width_static(Some(players), &self.settings);
+        if let Some(max_width) = max_width {
+            width = width.min(max_width / 4.0);
+        }
+        if width == self.settings.bid_column_width {
+            Cow::Borrowed(&self.settings)
+        } else {
+            let mut settings = self.settings.clone();
+            settings.bid_column_width = width;
+            Cow::Owned(settings)
+        }
+    }
+
+    /// The auction and players at each table `board` was played at, with the
+    /// table's label: just `auction` usually, or one per room once the rooms
+    /// of a vugraph file are merged
+    fn table_auctions<'b>(
+        &self,
+        board: &'b Board,
+        auction: &'b Auction,
+    ) -> Vec<(&'b Auction, &'b PlayerNames, Option<String>)> {
+        let rooms = self.settings.locale().rooms;
+        let own = TableResult {
+            room: board.room,
+            table: board.table.clone(),
+            ..Default::default()
+        };
+        let mut tables = vec![(auction, &board.players, own.label(rooms))];
+        tables.extend(board.other_tables.iter().filter_map(|table| {
+            let auction = table.auction.as_ref()?;
+            Some((auction, &table.players, table.label(rooms)))
+        }));
+        tables
+    }
+
+    /// Width in mm of the bidding tables of every room, side by side
+    fn auctions_width(&self, board: &Board, auction: &Auction, settings: &Settings) -> f32 {
+        let tables = self.table_auctions(board, auction);
+        let gaps = (tables.len() - 1) as f32 * ROOM_GAP;
+        tables
+            .iter()
+            .map(|(auction, players, _)| {
+                BiddingTableRenderer::table_width_static(auction, Some(players), settings)
+            })
+            .sum::<f32>()
+            + gaps
+    }
+
+    /// Height in mm of the tallest room's bidding table. With several rooms,
+    /// notes wrap within their own table.
+    fn measure_auctions_height(
+        &self,
+        board: &Board,
+        auction: &Auction,
+        notes_max_width: Option<f32>,
+        settings: &Settings,
+    ) -> f32 {
+        let tables = self.table_auctions(board, auction);
+        let side_by_side = tables.len() > 1;
+        tables
+            .iter()
+            .map(|(auction, players, _)| {
+                let notes_max_width = if side_by_side {
+                    Some(BiddingTableRenderer::table_width_static(
+                        auction,
+                        Some(players),
+                        settings,
+                    ))
+                } else {
+                    notes_max_width
+                };
+                self.measure_auction_height(auction, players, notes_max_width, Some(settings))
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Draw `auction` at `origin` with the other rooms' tables to its right,
+    /// each named above its header when `labels` is set; returns the height
+    /// of the tallest
+    #[allow(clippy::too_many_arguments)]
+    fn render_auctions(
+        &self,
+        layer: &mut LayerBuilder,
+        bidding_renderer: &BiddingTableRenderer,
+        board: &Board,
+        auction: &Auction,
+        origin: (f32, f32),
+        notes_max_width: Option<f32>,
+        settings: &Settings,
+        label_font: &TextFont,
+        labels: bool,
+    ) -> f32 {
+        let tables = self.table_auctions(board, auction);
+        if tables.len() == 1 {
+            return bidding_renderer.render_with_players_and_notes_width(
+                layer,
+                auction,
+                (Mm(origin.0), Mm(origin.1)),
+                Some(&board.players),
+                notes_max_width,
+            );
+        }
+
+        let (mut x, y) = origin;
+        let mut height: f32 = 0.0;
+        for (auction, players, label) in tables {
+            let width = BiddingTableRenderer::table_width_static(auction, Some(players), settings);
+            // The label sits in the spacing row above the table's header
+            if let Some(label) = label.filter(|_| labels) {
+                layer.use_text_font(label, settings.body_font_size, Mm(x), Mm(y), label_font);
+            }
+            let table_height = bidding_renderer.render_with_players_and_notes_width(
+                layer,
+                auction,
+                (Mm(x), Mm(y)),
+                Some(players),
+                Some(width),
+            );
+            height = height.max(table_height);
+            x += width + ROOM_GAP;
+        }
+        height
+    }
+
     /// Measure auction height without rendering
     fn measure_auction_height(
         &self,
@@ -416,7 +757,8 @@ impl DocumentRenderer {
                 | TextSpan::Italic(text)
                 | TextSpan::BoldItalic(text)
                 | TextSpan::Underline(text)
-                | TextSpan::Colored { text, .. } => {
+                | TextSpan::Colored { text, .. }
+                | TextSpan::Link { text, .. } => {
                     for word in text.split_whitespace() {
                         let word_width = measurer.measure_width_mm(word, font_size);
                         if total_width + word_width + base_space_width > max_width
@@ -427,10 +769,21 @@ impl DocumentRenderer {
                         } else {
                             total_width += word_width + base_space_width;
                         }
+                        // CJK runs have no spaces but wrap between characters
+                        if contains_cjk(word) {
+                            while total_width > max_width {
+                                line_count += 1;
+                                total_width -= max_width;
+                            }
+                        }
                     }
                     // Count newlines in the text
                     line_count += text.matches('\n').count();
                 }
+                TextSpan::Superscript(text) | TextSpan::Subscript(text) => {
+                    // Drawn smaller and attached to the previous word
+                    total_width += measurer.measure_width_mm(text, font_size) * 0.65;
+                }
                 TextSpan::SuitSymbol(_) | TextSpan::CardRef { .. } => {
                     // Suit symbols and card refs are small, just add a bit of width
                     total_width += measurer.measure_width_mm("♠", font_size);
@@ -439,6 +792,9 @@ impl DocumentRenderer {
                     line_count += 1;
                     total_width = 0.0;
                 }
+                TextSpan::Tab => {
+                    total_width += base_space_width;
+                }
             }
         }
 
@@ -446,82 +802,174 @@ impl DocumentRenderer {
         (line_count.max(1) - 1) as f32 * line_height
     }
 
-    /// Generate a PDF from a list of boards
+    /// Generate a PDF from a list of boards, logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF from a list of boards, with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Bridge Hands");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
 
-        // Load fonts - printpdf 0.8 handles subsetting automatically
-        let fonts = FontManager::new(&mut doc)?;
+        // Load fonts - printpdf 0.8 subsets them when saving (see --font-embedding)
+        let fonts = timings::time("fonts", || {
+            FontManager::for_settings(&mut doc, &self.settings)
+        })?;
+        if self.settings.custom_fonts.commentary.is_empty() {
+            for board in boards.iter().filter(|board| has_cjk_commentary(board)) {
+                self.warnings
+                    .push(RenderWarning::MissingGlyphs(board_label(board)));
+            }
+        }
+        let answers_layer = add_answers_layer(&mut doc, self.settings.answers_layer);
+        let card_backs = boards.iter().any(|board| {
+            self.settings.for_board(board).hidden_hand_style == HiddenHandStyle::CardBacks
+        });
+        let card_back = if card_backs {
+            Some(
+                CardAssets::load_back(&mut doc)
+                    .map_err(|e| RenderError::CardAsset(e.to_string()))?,
+            )
+        } else {
+            None
+        };
 
         let mut pages = Vec::new();
+        let mut page_fields = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards = Vec::new();
 
         if self.settings.column_count >= 2 {
             // Multi-column layout: fit multiple boards per page
-            pages = self.render_multi_column(boards, &fonts);
+            (pages, page_fields, page_links, page_boards) =
+                self.render_multi_column(boards, &fonts, card_back.as_ref(), answers_layer);
         } else {
-            // Single board per page (original behavior)
-            for board in boards {
-                let mut layer = LayerBuilder::new();
-                self.render_board(&mut layer, board, &fonts, self.settings.margin_left);
-
-                let page = PdfPage::new(
-                    Mm(self.settings.page_width),
-                    Mm(self.settings.page_height),
-                    layer.into_ops(),
-                );
-                pages.push(page);
+            // Single board per page: boards are drawn in parallel a batch at
+            // a time, so progress and cancellation stay on this thread
+            for batch in boards.chunks(BOARD_BATCH) {
+                self.progress.check_cancelled()?;
+                let layers: Vec<LayerBuilder> = par_map(batch, |board| {
+                    let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
+                    let board_renderer = self.board_renderer(board);
+                    timings::time_board(
+                        || board_label(board),
+                        || {
+                            board_renderer.as_ref().unwrap_or(self).render_board(
+                                &mut layer,
+                                board,
+                                &fonts,
+                                card_back.as_ref(),
+                                self.settings.margin_left,
+                            )
+                        },
+                    );
+                    layer
+                });
+
+                for (board, mut layer) in batch.iter().zip(layers) {
+                    page_fields.push(layer.take_text_fields());
+                    page_links.push(layer.take_links());
+                    page_boards.push(vec![board]);
+                    let page = PdfPage::new(
+                        Mm(self.settings.page_width),
+                        Mm(self.settings.page_height),
+                        layer.into_ops(),
+                    );
+                    pages.push(page);
+                }
+                self.progress.boards(pages.len(), boards.len());
             }
         }
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        // The event's final ranking follows the hand records
+        for page in ranking_pages(&event_ranking(boards), &self.settings) {
+            pages.push(page);
+            page_fields.push(Vec::new());
+            page_links.push(Vec::new());
+            page_boards.push(Vec::new());
+        }
 
+        add_header_footer(&mut doc, &mut pages, &page_boards, &self.settings)?;
+        add_title_block(&mut pages, &self.settings);
+        add_image_background(&mut doc, &mut pages, &self.settings)?;
+        pad_for_duplex(&mut pages, &self.settings);
+        if self.settings.grayscale {
+            apply_grayscale(&mut pages);
+        }
+        if self.settings.suit_style == SuitStyle::Letters {
+            apply_suit_letters(&mut pages, fonts.symbol_font());
+        }
         doc.with_pages(pages);
 
-        // Save with auto-subsetting enabled (default)
+        // Fonts are subset unless --font-embedding full
         let mut warnings = Vec::new();
-        let bytes = doc.save(&PdfSaveOptions::default(), &mut warnings);
+        let bytes = timings::time("save", || {
+            doc.save(&save_options(self.settings.font_embedding), &mut warnings)
+        });
+        let bytes = add_pdf_background(bytes, &self.settings)?;
+        let bytes = if self.settings.grayscale {
+            grayscale_xobjects(bytes).map_err(RenderError::PdfGeneration)?
+        } else {
+            bytes
+        };
+        let bytes = rotate_back_pages(bytes, &self.settings)?;
+        let bytes = add_form_fields(bytes.clone(), &page_fields, self.settings.first_page())
+            .unwrap_or(bytes);
+        let bytes = add_links(bytes.clone(), &page_links).unwrap_or(bytes);
 
         // Compress PDF streams to reduce file size
-        let compressed = compress_pdf(bytes.clone()).unwrap_or(bytes);
-        Ok(compressed)
+        let pdf = timings::time("compress", || {
+            compress_pdf(bytes.clone(), self.settings.compression).unwrap_or(bytes)
+        });
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
     }
 
     /// Render boards in multi-column layout with multiple boards per page
-    fn render_multi_column(&self, boards: &[Board], fonts: &FontManager) -> Vec<PdfPage> {
+    ///
+    /// Returns the pages along with the form fields, links and boards on each page.
+    #[allow(clippy::type_complexity)]
+    fn render_multi_column<'b>(
+        &self,
+        boards: &'b [Board],
+        fonts: &FontManager,
+        card_back: Option<&XObjectId>,
+        answers_layer: Option<LayerInternalId>,
+    ) -> (
+        Vec<PdfPage>,
+        Vec<Vec<TextField>>,
+        Vec<Vec<Link>>,
+        Vec<Vec<&'b Board>>,
+    ) {
         let mut pages = Vec::new();
+        let mut page_fields = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards = Vec::new();
 
         let page_width = self.settings.page_width;
         let page_height = self.settings.page_height;
         let margin_top = self.settings.margin_top;
         let margin_bottom = self.settings.margin_bottom;
         let num_columns = self.settings.column_count as usize;
-
-        // Minimum column width for readable content (approx 60mm per column)
-        const MIN_COLUMN_WIDTH: f32 = 60.0;
-        const DEFAULT_MARGIN: f32 = 15.0;
-        let gutter = 5.0; // Space between columns
-
-        // Calculate minimum content width needed for N columns
-        let min_content_width =
-            num_columns as f32 * MIN_COLUMN_WIDTH + (num_columns - 1) as f32 * gutter;
-
-        // Check if specified margins leave enough room for columns
-        let specified_content = page_width - self.settings.margin_left - self.settings.margin_right;
-        let (margin_left, margin_right) = if specified_content < min_content_width {
-            // Margins too large for multi-column layout, use defaults
-            (DEFAULT_MARGIN, DEFAULT_MARGIN)
-        } else {
-            (self.settings.margin_left, self.settings.margin_right)
-        };
-
-        let content_width = page_width - margin_left - margin_right;
-        let column_width = content_width / num_columns as f32;
-        let usable_column_width =
-            column_width - gutter * (num_columns - 1) as f32 / num_columns as f32;
+        let gutter = COLUMN_GUTTER;
+        let ColumnLayout {
+            margin_left,
+            margin_right,
+            column_width,
+            usable_column_width,
+        } = self.column_layout();
 
         // Calculate column start X positions and separator X positions
         let column_starts: Vec<f32> = (0..num_columns)
@@ -534,14 +982,36 @@ impl DocumentRenderer {
         // Spacing between boards (separator line area)
         let board_spacing = 5.0;
 
+        // Measuring is most of the work of placing boards, so measure them
+        // all in parallel up front; pages are then filled in order
+        let heights: Vec<f32> = timings::time("measure", || {
+            par_map(boards, |board| {
+                let board_renderer = self.board_renderer(board);
+                timings::time_board(
+                    || board_label(board),
+                    || {
+                        board_renderer
+                            .as_ref()
+                            .unwrap_or(self)
+                            .measure_board_height(board, usable_column_width)
+                    },
+                )
+            })
+        });
+
         // Process boards dynamically - fill each column until no more space
         let mut board_iter = boards.iter().peekable();
 
         while board_iter.peek().is_some() {
-            let mut layer = LayerBuilder::new();
+            // render() reports the cancellation once this returns
+            if self.progress.is_cancelled() {
+                break;
+            }
+            let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
+            let mut boards_on_page = Vec::new();
 
             // Draw vertical separator lines
-            layer.set_outline_color(Color::Rgb(SEPARATOR_COLOR));
+            layer.set_outline_color(Color::Rgb(self.theme.separator.clone()));
             layer.set_outline_thickness(SEPARATOR_THICKNESS);
             for sep_x in &separator_positions {
                 layer.add_line(
@@ -603,40 +1073,58 @@ impl DocumentRenderer {
                         }
                     }
 
-                    // Measure the board height to check if it fits
-                    let board_height = self.measure_board_height(next, usable_column_width);
+                    // The board's height, to check if it fits
+                    let board_renderer = self.board_renderer(next);
+                    let renderer = board_renderer.as_ref().unwrap_or(self);
+                    let board_height = heights[boards.len() - board_iter.len()];
 
                     // Skip empty boards (height 0)
                     if board_height == 0.0 {
+                        self.warnings
+                            .push(RenderWarning::EmptyBoard(board_label(next)));
                         board_iter.next(); // Consume and skip
                         continue;
                     }
 
                     // Check if board fits in remaining space
                     let available = column_y[col_idx] - margin_bottom;
-                    if board_height + board_spacing > available && column_board_count[col_idx] > 0 {
-                        // Doesn't fit and we have at least one board - move to next column
-                        break;
+                    if board_height + board_spacing > available {
+                        if column_board_count[col_idx] > 0 {
+                            // Doesn't fit and we have at least one board - move to next column
+                            break;
+                        }
+                        // Too tall even for an empty column; render it anyway
+                        if board_height > available {
+                            self.warnings
+                                .push(RenderWarning::BoardOverflow(board_label(next)));
+                        }
                     }
 
                     // Board fits - consume and render it
                     let board = board_iter.next().unwrap();
+                    boards_on_page.push(board);
 
                     // Draw horizontal separator if not at top
                     if column_board_count[col_idx] > 0 {
                         let sep_y = column_y[col_idx] + board_spacing / 2.0;
-                        layer.set_outline_color(Color::Rgb(SEPARATOR_COLOR));
+                        layer.set_outline_color(Color::Rgb(self.theme.separator.clone()));
                         layer.set_outline_thickness(SEPARATOR_THICKNESS);
                         layer.add_line(Mm(col_x), Mm(sep_y), Mm(col_end_x), Mm(sep_y));
                     }
 
-                    let rendered_height = self.render_board_in_column(
-                        &mut layer,
-                        board,
-                        fonts,
-                        col_x,
-                        column_y[col_idx],
-                        usable_column_width,
+                    let rendered_height = timings::time_board(
+                        || board_label(board),
+                        || {
+                            renderer.render_board_in_column(
+                                &mut layer,
+                                board,
+                                fonts,
+                                card_back,
+                                col_x,
+                                column_y[col_idx],
+                                usable_column_width,
+                            )
+                        },
                     );
 
                     // Draw debug box around the whole board
@@ -653,31 +1141,45 @@ impl DocumentRenderer {
                 }
             }
 
+            page_fields.push(layer.take_text_fields());
+            page_links.push(layer.take_links());
+            page_boards.push(boards_on_page);
             let page = PdfPage::new(Mm(page_width), Mm(page_height), layer.into_ops());
             pages.push(page);
+            self.progress
+                .boards(boards.len() - board_iter.len(), boards.len());
         }
 
-        pages
+        (pages, page_fields, page_links, page_boards)
     }
 
     /// Render a board within a column (for multi-column layout)
+    #[allow(clippy::too_many_arguments)]
     fn render_board_in_column(
         &self,
         layer: &mut LayerBuilder,
         board: &Board,
         fonts: &FontManager,
+        card_back: Option<&XObjectId>,
         column_x: f32,
         start_y: f32,
         column_width: f32,
     ) -> f32 {
+        layer.next_link_scope();
         let line_height = self.settings.line_height;
 
         // Get font sets
-        let diagram_fonts = fonts.builtin_set_for_spec(self.settings.fonts.diagram.as_ref());
+        let diagram_fonts =
+            fonts.text_set_for_role(FontRole::Diagram, self.settings.fonts.diagram.as_ref());
         let card_table_fonts = fonts.builtin_set_for_spec(self.settings.fonts.card_table.as_ref());
-        let hand_record_fonts =
-            fonts.builtin_set_for_spec(self.settings.fonts.hand_record.as_ref());
-        let commentary_fonts = fonts.builtin_set_for_spec(self.settings.fonts.commentary.as_ref());
+        let hand_record_fonts = fonts.text_set_for_role(
+            FontRole::HandRecord,
+            self.settings.fonts.hand_record.as_ref(),
+        );
+        let commentary_fonts = fonts.text_set_for_role(
+            FontRole::Commentary,
+            self.settings.fonts.commentary.as_ref(),
+        );
 
         let measurer = get_times_measurer();
         let cap_height = measurer.cap_height_mm(self.settings.body_font_size);
@@ -696,18 +1198,23 @@ impl DocumentRenderer {
             .unwrap_or(false);
         let has_content = !deal_is_empty || has_auction;
         let show_diagram_flag = flags.map(|f| f.show_diagram()).unwrap_or(true);
-        let show_board =
-            has_content && flags.map(|f| !f.hide_board()).unwrap_or(true) && show_diagram_flag;
-        let show_dealer =
-            has_content && flags.map(|f| !f.hide_dealer()).unwrap_or(true) && show_diagram_flag;
+        let show_diagram = !deal_is_empty && show_diagram_flag && !board.hidden.all_hidden();
+        let in_compass = board_in_compass(board, &self.settings, show_diagram);
+        let show_board = has_content
+            && flags.map(|f| !f.hide_board()).unwrap_or(true)
+            && show_diagram_flag
+            && !in_compass;
+        let show_dealer = has_content
+            && flags.map(|f| !f.hide_dealer()).unwrap_or(true)
+            && show_diagram_flag
+            && !in_compass;
         let show_vulnerable =
             has_content && flags.map(|f| !f.hide_vulnerable()).unwrap_or(true) && show_diagram_flag;
-        let show_diagram = !deal_is_empty && show_diagram_flag && !board.hidden.all_hidden();
         let show_auction = has_auction
             && flags.map(|f| f.show_auction()).unwrap_or(true)
             && self.settings.show_bidding;
         let show_commentary = self.settings.show_commentary
-            && board.commentary.iter().any(|c| !c.is_blank())
+            && board.general_commentary().next().is_some()
             && flags
                 .map(|f| f.show_event_commentary() || f.show_final_commentary())
                 .unwrap_or(true);
@@ -740,6 +1247,7 @@ impl DocumentRenderer {
                 layer,
                 board,
                 fonts,
+                card_back,
                 column_x,
                 start_y,
                 column_width,
@@ -766,18 +1274,28 @@ impl DocumentRenderer {
         // Check for single-card deal - these get special centered rendering
         let is_single_card = board.deal.get_single_visible_card(&board.hidden).is_some();
 
+        // Handviewer QR code in the empty top-right corner of the diagram
+        if self.settings.show_qr_codes && show_diagram && !is_single_card {
+            let size = self.settings.qr_code_size;
+            QrCodeRenderer::new(size).render(
+                layer,
+                board,
+                (Mm(column_x + column_width - size), Mm(start_y)),
+            );
+        }
+
         // Render board number in title section (unless it will be inline with auction or single-card)
         if show_board && !inline_board_label && !is_single_card {
             if let Some(ref board_id) = board.board_id {
                 let y = first_baseline - (title_line as f32 * line_height);
                 // Use board label format from settings (e.g., "Board %" -> "Board 1", "%)" -> "1)")
-                let label = self.settings.board_label_format.replace('%', board_id);
-                layer.use_text_builtin(
+                let label = self.settings.board_label_format().replace('%', board_id);
+                layer.use_text_font(
                     label,
                     font_size,
                     Mm(column_x),
                     Mm(y),
-                    hand_record_fonts.bold_italic,
+                    &hand_record_fonts.bold_italic,
                 );
                 title_line += 1;
             }
@@ -786,12 +1304,12 @@ impl DocumentRenderer {
         if show_dealer && !is_single_card {
             if let Some(dealer) = board.dealer {
                 let y = first_baseline - (title_line as f32 * line_height);
-                layer.use_text_builtin(
-                    format!("{} Deals", dealer),
+                layer.use_text_font(
+                    self.settings.locale().deals_line(dealer),
                     font_size,
                     Mm(column_x),
                     Mm(y),
-                    hand_record_fonts.regular,
+                    &hand_record_fonts.regular,
                 );
                 title_line += 1;
             }
@@ -799,12 +1317,15 @@ impl DocumentRenderer {
 
         if show_vulnerable && !is_single_card {
             let y = first_baseline - (title_line as f32 * line_height);
-            layer.use_text_builtin(
-                board.vulnerable.to_string(),
+            layer.use_text_font(
+                self.settings
+                    .locale()
+                    .vulnerability(board.vulnerable)
+                    .to_string(),
                 font_size,
                 Mm(column_x),
                 Mm(y),
-                hand_record_fonts.regular,
+                &hand_record_fonts.regular,
             );
         }
 
@@ -813,7 +1334,7 @@ impl DocumentRenderer {
             let diagram_x = column_x;
 
             // Compute display options - all visibility decisions are made here
-            let diagram_options = DiagramDisplayOptions::from_deal(&board.deal, &board.hidden);
+            let diagram_options = diagram_options(board, &self.settings);
 
             // Check for single-card deal - render just the rank number instead of a full diagram
             if let Some((_suit, rank)) = board.deal.get_single_visible_card(&board.hidden) {
@@ -829,19 +1350,19 @@ impl DocumentRenderer {
                 // Render board label on the left
                 if show_board {
                     if let Some(ref board_id) = board.board_id {
-                        let label = self.settings.board_label_format.replace('%', board_id);
-                        layer.use_text_builtin(
+                        let label = self.settings.board_label_format().replace('%', board_id);
+                        layer.use_text_font(
                             label,
                             font_size,
                             Mm(column_x),
                             Mm(centered_y),
-                            hand_record_fonts.bold_italic,
+                            &hand_record_fonts.bold_italic,
                         );
                     }
                 }
 
                 // Render rank number centered in the diagram area
-                let rank_text = rank.display_str().to_string();
+                let rank_text = rank.display_str_with(self.settings.ten_as_t).to_string();
                 let rank_font_size = font_size; // Use same font size as board label
 
                 // Calculate x position - center in the diagram area
@@ -851,16 +1372,16 @@ impl DocumentRenderer {
                 let compass_center_x = north_base_x + compass_size / 2.0;
 
                 // Measure text width for centering
-                let text_measurer = text_metrics::BuiltinFontMeasurer::new(diagram_fonts.regular);
-                let text_width = text_measurer.measure_width_mm(&rank_text, rank_font_size);
+                let text_measurer = diagram_fonts.regular.measurer();
+                let text_width = text_measurer.measure_text(&rank_text, rank_font_size);
                 let rank_x = compass_center_x - text_width / 2.0;
 
-                layer.use_text_builtin(
+                layer.use_text_font(
                     rank_text,
                     rank_font_size,
                     Mm(rank_x),
                     Mm(centered_y),
-                    diagram_fonts.regular,
+                    &diagram_fonts.regular,
                 );
 
                 // Debug box for single card area
@@ -878,12 +1399,13 @@ impl DocumentRenderer {
                 };
 
                 let hand_renderer = HandDiagramRenderer::new(
-                    diagram_fonts.regular,
-                    diagram_fonts.bold,
+                    diagram_fonts.regular.clone(),
+                    diagram_fonts.bold.clone(),
                     card_table_fonts.regular,
                     fonts.symbol_font(),
                     &self.settings,
-                );
+                )
+                .card_back(card_back);
                 let diagram_height = hand_renderer.render_deal_with_options(
                     layer,
                     &board.deal,
@@ -914,34 +1436,21 @@ impl DocumentRenderer {
             if let Some(ref auction) = board.auction {
                 // Calculate effective bid column width that fits 4 columns in the column
                 // Use the same width for 2-col and 4-col so columns align vertically
-                let effective_bid_col_width =
-                    self.settings.bid_column_width.min(column_width / 4.0);
+                let bid_settings = self.auction_settings(&board.players, Some(column_width));
                 let num_cols =
                     if self.settings.two_col_auctions && auction.uncontested_pair().is_some() {
                         2
                     } else {
                         4
                     };
-                let table_width = num_cols as f32 * effective_bid_col_width;
-
-                // Use narrowed bid column width if needed to fit
-                let narrowed_settings;
-                let bid_settings = if effective_bid_col_width < self.settings.bid_column_width {
-                    narrowed_settings = {
-                        let mut s = self.settings.clone();
-                        s.bid_column_width = effective_bid_col_width;
-                        s
-                    };
-                    &narrowed_settings
-                } else {
-                    &self.settings
-                };
+                let table_width = self.auctions_width(board, auction, &bid_settings);
+
                 let bidding_renderer = BiddingTableRenderer::new(
-                    hand_record_fonts.regular,
-                    hand_record_fonts.bold,
-                    hand_record_fonts.italic,
+                    hand_record_fonts.regular.clone(),
+                    hand_record_fonts.bold.clone(),
+                    hand_record_fonts.italic.clone(),
                     fonts.symbol_font(),
-                    bid_settings,
+                    &bid_settings,
                 );
 
                 // Center the auction table within the column
@@ -960,26 +1469,31 @@ impl DocumentRenderer {
 
                 if inline_board_label {
                     if let Some(ref board_id) = board.board_id {
-                        let label = self.settings.board_label_format.replace('%', board_id);
+                        let label = self.settings.board_label_format().replace('%', board_id);
                         // Board label at first_baseline (same line as auction header after offset)
-                        layer.use_text_builtin(
+                        layer.use_text_font(
                             label,
                             font_size,
                             Mm(column_x),
                             Mm(current_y),
-                            hand_record_fonts.bold_italic,
+                            &hand_record_fonts.bold_italic,
                         );
                     }
                 }
 
                 // Calculate max width for notes: from table_x to right edge of column
                 let notes_max_width = (column_x + column_width) - table_x;
-                let table_height = bidding_renderer.render_with_players_and_notes_width(
+                let table_height = self.render_auctions(
                     layer,
+                    &bidding_renderer,
+                    board,
                     auction,
-                    (Mm(table_x), Mm(auction_y)),
-                    Some(&board.players),
+                    (table_x, auction_y),
                     Some(notes_max_width),
+                    &bid_settings,
+                    &hand_record_fonts.italic,
+                    // No spacing row to hold labels when moved up beside the board label
+                    !(inline_board_label && num_cols == 2),
                 );
 
                 // Debug box for bidding table: tightly bound visible content
@@ -1005,27 +1519,44 @@ impl DocumentRenderer {
                     .and_then(|p| p.tricks.first())
                     .and_then(|t| t.cards[0])
                     .is_some();
+                let has_best_leads = self.settings.show_best_leads && !board.best_leads.is_empty();
                 let has_more_below = show_commentary && !board.commentary.is_empty();
 
                 // Add spacing after auction before contract/lead (only if there's contract or lead)
-                if has_contract || has_lead {
+                if has_contract || has_best_leads || has_lead {
                     current_y -= line_height;
                 }
 
                 // Render contract (only if explicitly in PBN, not inferred from auction)
                 if let Some(ref contract) = board.contract {
-                    let colors =
-                        SuitColors::new(self.settings.black_color, self.settings.red_color);
+                    let colors = SuitColors::from_settings(&self.settings);
                     self.render_contract(
                         layer,
                         contract,
                         Mm(column_x),
                         Mm(current_y),
-                        hand_record_fonts.regular,
+                        &hand_record_fonts.regular,
                         fonts.symbol_font(),
                         &colors,
                     );
                     // Only add spacing if there's more content below
+                    if has_best_leads || has_lead || has_more_below {
+                        current_y -= line_height;
+                    }
+                }
+
+                // Render double-dummy best leads
+                if has_best_leads {
+                    let colors = SuitColors::from_settings(&self.settings);
+                    self.render_best_leads(
+                        layer,
+                        &board.best_leads,
+                        Mm(column_x),
+                        Mm(current_y),
+                        &hand_record_fonts.regular,
+                        fonts.symbol_font(),
+                        &colors,
+                    );
                     if has_lead || has_more_below {
                         current_y -= line_height;
                     }
@@ -1035,17 +1566,23 @@ impl DocumentRenderer {
                 if let Some(ref play) = board.play {
                     if let Some(first_trick) = play.tricks.first() {
                         if let Some(lead_card) = first_trick.cards[0] {
-                            let colors =
-                                SuitColors::new(self.settings.black_color, self.settings.red_color);
+                            let colors = SuitColors::from_settings(&self.settings);
                             self.render_lead(
                                 layer,
                                 &lead_card,
                                 Mm(column_x),
                                 Mm(current_y),
-                                hand_record_fonts.regular,
+                                &hand_record_fonts.regular,
                                 fonts.symbol_font(),
                                 &colors,
                             );
+                            current_y -= self.render_play_notes(
+                                layer,
+                                &bidding_renderer,
+                                play,
+                                (column_x, current_y),
+                                column_width,
+                            );
                             // Only add spacing if there's more content below
                             if has_more_below {
                                 current_y -= line_height;
@@ -1059,16 +1596,15 @@ impl DocumentRenderer {
         // Render commentary - simplified for column layout (no floating, skip blank blocks)
         if show_commentary {
             let commentary_renderer = CommentaryRenderer::new(
-                commentary_fonts.regular,
-                commentary_fonts.bold,
-                commentary_fonts.italic,
-                commentary_fonts.bold_italic,
+                commentary_fonts.regular.clone(),
+                commentary_fonts.bold.clone(),
+                commentary_fonts.italic.clone(),
+                commentary_fonts.bold_italic.clone(),
                 fonts.symbol_font(),
                 &self.settings,
             );
 
-            let non_blank_blocks: Vec<_> =
-                board.commentary.iter().filter(|c| !c.is_blank()).collect();
+            let non_blank_blocks: Vec<_> = board.general_commentary().collect();
             let block_count = non_blank_blocks.len();
             for (i, block) in non_blank_blocks.iter().enumerate() {
                 let block_start_y = current_y;
@@ -1108,6 +1644,7 @@ impl DocumentRenderer {
         layer: &mut LayerBuilder,
         board: &Board,
         fonts: &FontManager,
+        card_back: Option<&XObjectId>,
         column_x: f32,
         start_y: f32,
         column_width: f32,
@@ -1118,13 +1655,20 @@ impl DocumentRenderer {
             visibility;
 
         // Get font sets
-        let diagram_fonts = fonts.builtin_set_for_spec(self.settings.fonts.diagram.as_ref());
+        let diagram_fonts =
+            fonts.text_set_for_role(FontRole::Diagram, self.settings.fonts.diagram.as_ref());
         let card_table_fonts = fonts.builtin_set_for_spec(self.settings.fonts.card_table.as_ref());
-        let hand_record_fonts =
-            fonts.builtin_set_for_spec(self.settings.fonts.hand_record.as_ref());
-        let commentary_fonts = fonts.builtin_set_for_spec(self.settings.fonts.commentary.as_ref());
+        let hand_record_fonts = fonts.text_set_for_role(
+            FontRole::HandRecord,
+            self.settings.fonts.hand_record.as_ref(),
+        );
+        let commentary_fonts = fonts.text_set_for_role(
+            FontRole::Commentary,
+            self.settings.fonts.commentary.as_ref(),
+        );
 
         let measurer = get_times_measurer();
+        let title_measurer = hand_record_fonts.regular.measurer_or(measurer);
         let cap_height = measurer.cap_height_mm(self.settings.body_font_size);
 
         // Start rendering from the top
@@ -1133,33 +1677,25 @@ impl DocumentRenderer {
         let commentary_ascender = measurer.ascender_mm(self.settings.commentary_font_size);
         let mut current_y = start_y - commentary_ascender;
 
-        // In Center layout: split commentary into event (before diagram) and final (after diagram)
-        // If there are multiple commentary blocks, the last one is "final" (rendered after diagram)
+        // In Center layout: split commentary into event (before diagram) and the rest
+        // (diagram, auction and final commentary, after the diagram and auction)
         let commentary_renderer = CommentaryRenderer::new(
-            commentary_fonts.regular,
-            commentary_fonts.bold,
-            commentary_fonts.italic,
-            commentary_fonts.bold_italic,
+            commentary_fonts.regular.clone(),
+            commentary_fonts.bold.clone(),
+            commentary_fonts.italic.clone(),
+            commentary_fonts.bold_italic.clone(),
             fonts.symbol_font(),
             &self.settings,
         );
 
         // Determine which commentary blocks go before vs after the diagram (skip blank blocks)
-        let non_blank_blocks: Vec<_> = board.commentary.iter().filter(|c| !c.is_blank()).collect();
-        let (event_commentary, final_commentary): (Vec<_>, Vec<_>) =
-            if show_commentary && non_blank_blocks.len() > 1 {
-                // Multiple blocks: all but last are event, last is final
-                let split_point = non_blank_blocks.len() - 1;
-                (
-                    non_blank_blocks.iter().take(split_point).copied().collect(),
-                    non_blank_blocks.iter().skip(split_point).copied().collect(),
-                )
-            } else if show_commentary {
-                // Single block: treat as event commentary (before diagram)
-                (non_blank_blocks, vec![])
-            } else {
-                (vec![], vec![])
-            };
+        let (event_commentary, final_commentary): (Vec<_>, Vec<_>) = if show_commentary {
+            board
+                .general_commentary()
+                .partition(|block| block.placement == CommentaryPlacement::Event)
+        } else {
+            (vec![], vec![])
+        };
 
         // Render event commentary (before diagram)
         for (i, block) in event_commentary.iter().enumerate() {
@@ -1199,14 +1735,15 @@ impl DocumentRenderer {
         // Render diagram centered if enabled
         if show_diagram {
             // Calculate diagram width to center it
-            let diagram_options = DiagramDisplayOptions::from_deal(&board.deal, &board.hidden);
+            let diagram_options = diagram_options(board, &self.settings);
             let hand_renderer = HandDiagramRenderer::new(
-                diagram_fonts.regular,
-                diagram_fonts.bold,
+                diagram_fonts.regular.clone(),
+                diagram_fonts.bold.clone(),
                 card_table_fonts.regular,
                 fonts.symbol_font(),
                 &self.settings,
-            );
+            )
+            .card_back(card_back);
 
             // For single hand (hide_compass), check if we have title to render
             if diagram_options.hide_compass {
@@ -1232,13 +1769,13 @@ impl DocumentRenderer {
                     if show_board {
                         if let Some(ref board_id) = board.board_id {
                             let y = first_baseline - (title_line as f32 * line_height);
-                            let label = self.settings.board_label_format.replace('%', board_id);
-                            layer.use_text_builtin(
+                            let label = self.settings.board_label_format().replace('%', board_id);
+                            layer.use_text_font(
                                 label,
                                 font_size,
                                 Mm(column_x),
                                 Mm(y),
-                                hand_record_fonts.bold_italic,
+                                &hand_record_fonts.bold_italic,
                             );
                             title_line += 1;
                         }
@@ -1247,12 +1784,12 @@ impl DocumentRenderer {
                     if show_dealer {
                         if let Some(dealer) = board.dealer {
                             let y = first_baseline - (title_line as f32 * line_height);
-                            layer.use_text_builtin(
-                                format!("{} Deals", dealer),
+                            layer.use_text_font(
+                                self.settings.locale().deals_line(dealer),
                                 font_size,
                                 Mm(column_x),
                                 Mm(y),
-                                hand_record_fonts.regular,
+                                &hand_record_fonts.regular,
                             );
                             title_line += 1;
                         }
@@ -1260,12 +1797,15 @@ impl DocumentRenderer {
 
                     if show_vulnerable {
                         let y = first_baseline - (title_line as f32 * line_height);
-                        layer.use_text_builtin(
-                            board.vulnerable.to_string(),
+                        layer.use_text_font(
+                            self.settings
+                                .locale()
+                                .vulnerability(board.vulnerable)
+                                .to_string(),
                             font_size,
                             Mm(column_x),
                             Mm(y),
-                            hand_record_fonts.regular,
+                            &hand_record_fonts.regular,
                         );
                     }
 
@@ -1304,23 +1844,20 @@ impl DocumentRenderer {
                     let num_suits = suits_to_show.len();
 
                     // Measure hand width for centering
-                    let hand_measurer = text_metrics::get_times_measurer();
+                    let hand_measurer = diagram_fonts.regular.measurer_or(get_times_measurer());
                     let hand_width = suits_to_show
                         .iter()
                         .map(|suit| {
                             let holding = hand.holding(*suit);
                             let cards_str = holding
-                                .ranks
-                                .iter()
-                                .map(|r| r.display_str().to_string())
+                                .card_texts(self.settings.ten_as_t)
                                 .collect::<Vec<_>>()
                                 .join(" ");
                             if show_suit_symbols {
                                 let line = format!("{} {}", suit.symbol(), cards_str);
-                                hand_measurer.measure_width_mm(&line, self.settings.card_font_size)
+                                hand_measurer.measure_text(&line, self.settings.card_font_size)
                             } else {
-                                hand_measurer
-                                    .measure_width_mm(&cards_str, self.settings.card_font_size)
+                                hand_measurer.measure_text(&cards_str, self.settings.card_font_size)
                             }
                         })
                         .fold(0.0_f32, |max, w| max.max(w));
@@ -1334,8 +1871,7 @@ impl DocumentRenderer {
                     let card_descender = hand_measurer.descender_mm(self.settings.card_font_size);
                     let first_baseline = diagram_y - card_cap_height;
 
-                    let colors =
-                        SuitColors::new(self.settings.black_color, self.settings.red_color);
+                    let colors = SuitColors::from_settings(&self.settings);
 
                     for (i, suit) in suits_to_show.iter().enumerate() {
                         let y = first_baseline - (i as f32 * line_height);
@@ -1361,17 +1897,15 @@ impl DocumentRenderer {
                         // Render cards
                         layer.set_fill_color(Color::Rgb(BLACK));
                         let cards_str = holding
-                            .ranks
-                            .iter()
-                            .map(|r| r.display_str().to_string())
+                            .card_texts(self.settings.ten_as_t)
                             .collect::<Vec<_>>()
                             .join(" ");
-                        layer.use_text_builtin(
+                        layer.use_text_font(
                             &cards_str,
                             self.settings.card_font_size,
                             Mm(current_x),
                             Mm(y),
-                            diagram_fonts.regular,
+                            &diagram_fonts.regular,
                         );
                     }
 
@@ -1411,15 +1945,15 @@ impl DocumentRenderer {
 
                 if show_board {
                     if let Some(ref board_id) = board.board_id {
-                        let label = self.settings.board_label_format.replace('%', board_id);
-                        let label_width = measurer.measure_width_mm(&label, font_size);
+                        let label = self.settings.board_label_format().replace('%', board_id);
+                        let label_width = title_measurer.measure_text(&label, font_size);
                         let x = column_center_x - label_width / 2.0;
-                        layer.use_text_builtin(
+                        layer.use_text_font(
                             label,
                             font_size,
                             Mm(x),
                             Mm(current_y),
-                            hand_record_fonts.bold_italic,
+                            &hand_record_fonts.bold_italic,
                         );
                         current_y -= line_height;
                     }
@@ -1427,30 +1961,34 @@ impl DocumentRenderer {
 
                 if show_dealer {
                     if let Some(dealer) = board.dealer {
-                        let text = format!("{} Deals", dealer);
-                        let text_width = measurer.measure_width_mm(&text, font_size);
+                        let text = self.settings.locale().deals_line(dealer);
+                        let text_width = title_measurer.measure_text(&text, font_size);
                         let x = column_center_x - text_width / 2.0;
-                        layer.use_text_builtin(
+                        layer.use_text_font(
                             text,
                             font_size,
                             Mm(x),
                             Mm(current_y),
-                            hand_record_fonts.regular,
+                            &hand_record_fonts.regular,
                         );
                         current_y -= line_height;
                     }
                 }
 
                 if show_vulnerable {
-                    let text = board.vulnerable.to_string();
-                    let text_width = measurer.measure_width_mm(&text, font_size);
+                    let text = self
+                        .settings
+                        .locale()
+                        .vulnerability(board.vulnerable)
+                        .to_string();
+                    let text_width = title_measurer.measure_text(&text, font_size);
                     let x = column_center_x - text_width / 2.0;
-                    layer.use_text_builtin(
+                    layer.use_text_font(
                         text,
                         font_size,
                         Mm(x),
                         Mm(current_y),
-                        hand_record_fonts.regular,
+                        &hand_record_fonts.regular,
                     );
                     current_y -= line_height;
                 }
@@ -1462,15 +2000,15 @@ impl DocumentRenderer {
 
             if show_board {
                 if let Some(ref board_id) = board.board_id {
-                    let label = self.settings.board_label_format.replace('%', board_id);
-                    let label_width = measurer.measure_width_mm(&label, font_size);
+                    let label = self.settings.board_label_format().replace('%', board_id);
+                    let label_width = title_measurer.measure_text(&label, font_size);
                     let x = column_center_x - label_width / 2.0;
-                    layer.use_text_builtin(
+                    layer.use_text_font(
                         label,
                         font_size,
                         Mm(x),
                         Mm(current_y),
-                        hand_record_fonts.bold_italic,
+                        &hand_record_fonts.bold_italic,
                     );
                     current_y -= line_height;
                 }
@@ -1478,30 +2016,34 @@ impl DocumentRenderer {
 
             if show_dealer {
                 if let Some(dealer) = board.dealer {
-                    let text = format!("{} Deals", dealer);
-                    let text_width = measurer.measure_width_mm(&text, font_size);
+                    let text = self.settings.locale().deals_line(dealer);
+                    let text_width = title_measurer.measure_text(&text, font_size);
                     let x = column_center_x - text_width / 2.0;
-                    layer.use_text_builtin(
+                    layer.use_text_font(
                         text,
                         font_size,
                         Mm(x),
                         Mm(current_y),
-                        hand_record_fonts.regular,
+                        &hand_record_fonts.regular,
                     );
                     current_y -= line_height;
                 }
             }
 
             if show_vulnerable {
-                let text = board.vulnerable.to_string();
-                let text_width = measurer.measure_width_mm(&text, font_size);
+                let text = self
+                    .settings
+                    .locale()
+                    .vulnerability(board.vulnerable)
+                    .to_string();
+                let text_width = title_measurer.measure_text(&text, font_size);
                 let x = column_center_x - text_width / 2.0;
-                layer.use_text_builtin(
+                layer.use_text_font(
                     text,
                     font_size,
                     Mm(x),
                     Mm(current_y),
-                    hand_record_fonts.regular,
+                    &hand_record_fonts.regular,
                 );
                 current_y -= line_height;
             }
@@ -1510,33 +2052,31 @@ impl DocumentRenderer {
         // Render bidding table centered
         if show_auction {
             if let Some(ref auction) = board.auction {
+                let bid_settings = self.auction_settings(&board.players, None);
                 let bidding_renderer = BiddingTableRenderer::new(
-                    hand_record_fonts.regular,
-                    hand_record_fonts.bold,
-                    hand_record_fonts.italic,
+                    hand_record_fonts.regular.clone(),
+                    hand_record_fonts.bold.clone(),
+                    hand_record_fonts.italic.clone(),
                     fonts.symbol_font(),
-                    &self.settings,
+                    &bid_settings,
                 );
 
                 // Calculate bidding table width for centering
-                // Two-column mode uses 2 columns, standard uses 4
-                let num_cols =
-                    if self.settings.two_col_auctions && auction.uncontested_pair().is_some() {
-                        2
-                    } else {
-                        4
-                    };
-                let table_width = num_cols as f32 * self.settings.bid_column_width;
+                let table_width = self.auctions_width(board, auction, &bid_settings);
                 let table_x = column_center_x - table_width / 2.0;
 
                 // Calculate max width for notes: from table_x to right edge of column
                 let notes_max_width = (column_x + column_width) - table_x;
-                let table_height = bidding_renderer.render_with_players_and_notes_width(
+                let table_height = self.render_auctions(
                     layer,
+                    &bidding_renderer,
+                    board,
                     auction,
-                    (Mm(table_x), Mm(current_y)),
-                    Some(&board.players),
+                    (table_x, current_y),
                     Some(notes_max_width),
+                    &bid_settings,
+                    &hand_record_fonts.italic,
+                    true,
                 );
 
                 // Debug box for bidding table
@@ -1551,23 +2091,40 @@ impl DocumentRenderer {
                     .and_then(|p| p.tricks.first())
                     .and_then(|t| t.cards[0])
                     .is_some();
+                let has_best_leads = self.settings.show_best_leads && !board.best_leads.is_empty();
                 let has_more_below = !final_commentary.is_empty();
 
                 // Add spacing after auction before contract/lead
-                if has_contract || has_lead {
+                if has_contract || has_best_leads || has_lead {
                     current_y -= line_height;
                 }
 
                 // Render contract (only if explicitly in PBN, not inferred from auction)
                 if let Some(ref contract) = board.contract {
-                    let colors =
-                        SuitColors::new(self.settings.black_color, self.settings.red_color);
+                    let colors = SuitColors::from_settings(&self.settings);
                     self.render_contract(
                         layer,
                         contract,
                         Mm(table_x),
                         Mm(current_y),
-                        hand_record_fonts.regular,
+                        &hand_record_fonts.regular,
+                        fonts.symbol_font(),
+                        &colors,
+                    );
+                    if has_best_leads || has_lead || has_more_below {
+                        current_y -= line_height;
+                    }
+                }
+
+                // Render double-dummy best leads
+                if has_best_leads {
+                    let colors = SuitColors::from_settings(&self.settings);
+                    self.render_best_leads(
+                        layer,
+                        &board.best_leads,
+                        Mm(table_x),
+                        Mm(current_y),
+                        &hand_record_fonts.regular,
                         fonts.symbol_font(),
                         &colors,
                     );
@@ -1580,17 +2137,23 @@ impl DocumentRenderer {
                 if let Some(ref play) = board.play {
                     if let Some(first_trick) = play.tricks.first() {
                         if let Some(lead_card) = first_trick.cards[0] {
-                            let colors =
-                                SuitColors::new(self.settings.black_color, self.settings.red_color);
+                            let colors = SuitColors::from_settings(&self.settings);
                             self.render_lead(
                                 layer,
                                 &lead_card,
                                 Mm(table_x),
                                 Mm(current_y),
-                                hand_record_fonts.regular,
+                                &hand_record_fonts.regular,
                                 fonts.symbol_font(),
                                 &colors,
                             );
+                            current_y -= self.render_play_notes(
+                                layer,
+                                &bidding_renderer,
+                                play,
+                                (table_x, current_y),
+                                notes_max_width,
+                            );
                             if has_more_below {
                                 current_y -= line_height;
                             }
@@ -1642,7 +2205,7 @@ impl DocumentRenderer {
             return;
         }
         // y is top of box, draw from bottom-left to top-right
-        layer.set_outline_color(Color::Rgb(DEBUG_BOX_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.debug_box.clone()));
         layer.set_outline_thickness(0.25);
         layer.add_rect(Mm(x), Mm(y - h), Mm(x + w), Mm(y), PaintMode::Stroke);
     }
@@ -1653,7 +2216,7 @@ impl DocumentRenderer {
             return;
         }
         // y is top of box, draw from bottom-left to top-right
-        layer.set_outline_color(Color::Rgb(DEBUG_BOARD_BOX_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.debug_board_box.clone()));
         layer.set_outline_thickness(0.5);
         layer.add_rect(Mm(x), Mm(y - h), Mm(x + w), Mm(y), PaintMode::Stroke);
     }
@@ -1664,26 +2227,41 @@ impl DocumentRenderer {
         layer: &mut LayerBuilder,
         board: &Board,
         fonts: &FontManager,
+        card_back: Option<&XObjectId>,
         margin_left: f32,
     ) {
+        layer.next_link_scope();
         let margin_top = self.settings.margin_top;
         let page_top = self.settings.page_height - margin_top;
         let line_height = self.settings.line_height;
 
         // Get font sets based on PBN font specifications
-        let diagram_fonts = fonts.builtin_set_for_spec(self.settings.fonts.diagram.as_ref());
+        let diagram_fonts =
+            fonts.text_set_for_role(FontRole::Diagram, self.settings.fonts.diagram.as_ref());
         let card_table_fonts = fonts.builtin_set_for_spec(self.settings.fonts.card_table.as_ref());
-        let hand_record_fonts =
-            fonts.builtin_set_for_spec(self.settings.fonts.hand_record.as_ref());
-        let commentary_fonts = fonts.builtin_set_for_spec(self.settings.fonts.commentary.as_ref());
+        let hand_record_fonts = fonts.text_set_for_role(
+            FontRole::HandRecord,
+            self.settings.fonts.hand_record.as_ref(),
+        );
+        let commentary_fonts = fonts.text_set_for_role(
+            FontRole::Commentary,
+            self.settings.fonts.commentary.as_ref(),
+        );
 
         // Get font metrics for accurate box heights
         let measurer = get_times_measurer();
         let cap_height = measurer.cap_height_mm(self.settings.body_font_size);
         let descender = measurer.descender_mm(self.settings.body_font_size);
 
+        // The deal (title, diagram, auction) normally takes the left of the page
+        // with commentary floating on the right; a template can swap the sides
+        let deal_left = match self.settings.diagram_side {
+            DiagramSide::Left => margin_left,
+            DiagramSide::Right => margin_left + self.settings.content_width() / 2.0 + 2.0,
+        };
+
         // Title: 3 lines stacked vertically, positioned above West hand area
-        let title_x = margin_left;
+        let title_x = deal_left;
         let title_start_y = page_top;
 
         // Build title lines and measure widths
@@ -1697,17 +2275,27 @@ impl DocumentRenderer {
             .map(|a| !a.calls.is_empty())
             .unwrap_or(false);
         let has_content = !deal_is_empty || has_auction;
+        let in_compass = board_in_compass(
+            board,
+            &self.settings,
+            !deal_is_empty && !board.hidden.all_hidden(),
+        );
 
         if has_content {
-            if let Some(ref board_id) = board.board_id {
+            if let Some(board_id) = board.board_id.as_ref().filter(|_| !in_compass) {
                 // Use board label format from settings (e.g., "Board %" -> "Board 1", "%)" -> "1)")
-                let label = self.settings.board_label_format.replace('%', board_id);
+                let label = self.settings.board_label_format().replace('%', board_id);
                 title_lines.push(label);
             }
-            if let Some(dealer) = board.dealer {
-                title_lines.push(format!("{} Deals", dealer));
+            if let Some(dealer) = board.dealer.filter(|_| !in_compass) {
+                title_lines.push(self.settings.locale().deals_line(dealer));
             }
-            title_lines.push(board.vulnerable.to_string());
+            title_lines.push(
+                self.settings
+                    .locale()
+                    .vulnerability(board.vulnerable)
+                    .to_string(),
+            );
         }
 
         let num_lines = title_lines.len();
@@ -1732,47 +2320,50 @@ impl DocumentRenderer {
 
         if has_content {
             // Line 1: Board label (bold italic) - use hand_record font
-            if let Some(ref board_id) = board.board_id {
+            if let Some(board_id) = board.board_id.as_ref().filter(|_| !in_compass) {
                 let y = first_baseline - (current_line as f32 * line_height);
                 // Use board label format from settings (e.g., "Board %" -> "Board 1", "%)" -> "1)")
-                let label = self.settings.board_label_format.replace('%', board_id);
-                layer.use_text_builtin(
+                let label = self.settings.board_label_format().replace('%', board_id);
+                layer.use_text_font(
                     label,
                     self.settings.body_font_size,
                     Mm(title_x),
                     Mm(y),
-                    hand_record_fonts.bold_italic,
+                    &hand_record_fonts.bold_italic,
                 );
                 current_line += 1;
             }
 
             // Line 2: Dealer - use hand_record font
-            if let Some(dealer) = board.dealer {
+            if let Some(dealer) = board.dealer.filter(|_| !in_compass) {
                 let y = first_baseline - (current_line as f32 * line_height);
-                layer.use_text_builtin(
-                    format!("{} Deals", dealer),
+                layer.use_text_font(
+                    self.settings.locale().deals_line(dealer),
                     self.settings.body_font_size,
                     Mm(title_x),
                     Mm(y),
-                    hand_record_fonts.regular,
+                    &hand_record_fonts.regular,
                 );
                 current_line += 1;
             }
 
             // Line 3: Vulnerability - use hand_record font
             let y = first_baseline - (current_line as f32 * line_height);
-            layer.use_text_builtin(
-                board.vulnerable.to_string(),
+            layer.use_text_font(
+                self.settings
+                    .locale()
+                    .vulnerability(board.vulnerable)
+                    .to_string(),
                 self.settings.body_font_size,
                 Mm(title_x),
                 Mm(y),
-                hand_record_fonts.regular,
+                &hand_record_fonts.regular,
             );
         }
 
         // Diagram origin: same Y as page_top (North aligns with "Board 1")
         // The diagram renderer will place North to the right (after hand_width gap for title)
-        let diagram_x = margin_left;
+        let diagram_x = deal_left;
         let diagram_y = page_top; // Start at same level as title
 
         // Content below diagram (or title if no diagram)
@@ -1780,16 +2371,30 @@ impl DocumentRenderer {
 
         // Only render diagram if deal has cards
         if !deal_is_empty {
+            // Handviewer QR code in the empty top-right corner of the diagram
+            if self.settings.show_qr_codes {
+                let size = self.settings.qr_code_size;
+                QrCodeRenderer::new(size).render(
+                    layer,
+                    board,
+                    (
+                        Mm(margin_left + self.settings.content_width() - size),
+                        Mm(page_top),
+                    ),
+                );
+            }
+
             // Compute display options - all visibility decisions are made here
-            let diagram_options = DiagramDisplayOptions::from_deal(&board.deal, &board.hidden);
+            let diagram_options = diagram_options(board, &self.settings);
 
             let hand_renderer = HandDiagramRenderer::new(
-                diagram_fonts.regular,
-                diagram_fonts.bold,
+                diagram_fonts.regular.clone(),
+                diagram_fonts.bold.clone(),
                 card_table_fonts.regular, // Compass uses CardTable font
                 fonts.symbol_font(),      // DejaVu Sans for suit symbols
                 &self.settings,
-            );
+            )
+            .card_back(card_back);
             let diagram_height = hand_renderer.render_deal_with_options(
                 layer,
                 &board.deal,
@@ -1817,39 +2422,38 @@ impl DocumentRenderer {
         // Render bidding table if present
         if self.settings.show_bidding {
             if let Some(ref auction) = board.auction {
+                let bid_settings = self.auction_settings(&board.players, None);
                 let bidding_renderer = BiddingTableRenderer::new(
-                    hand_record_fonts.regular,
-                    hand_record_fonts.bold,
-                    hand_record_fonts.italic,
+                    hand_record_fonts.regular.clone(),
+                    hand_record_fonts.bold.clone(),
+                    hand_record_fonts.italic.clone(),
                     fonts.symbol_font(), // DejaVu Sans for suit symbols
-                    &self.settings,
+                    &bid_settings,
                 );
                 // Notes wrap to the left half when commentary will float on the right,
                 // otherwise use the full content width.
                 let has_floating_commentary =
-                    self.settings.show_commentary && board.commentary.iter().any(|c| !c.is_blank());
+                    self.settings.show_commentary && board.general_commentary().next().is_some();
                 let notes_max_width = if has_floating_commentary {
                     self.settings.content_width() / 2.0 - 2.0
                 } else {
                     self.settings.content_width()
                 };
-                let num_cols =
-                    if self.settings.two_col_auctions && auction.uncontested_pair().is_some() {
-                        2
-                    } else {
-                        4
-                    };
-                let table_width = num_cols as f32 * self.settings.bid_column_width;
-                let table_height = bidding_renderer.render_with_players_and_notes_width(
+                let table_width = self.auctions_width(board, auction, &bid_settings);
+                let table_height = self.render_auctions(
                     layer,
+                    &bidding_renderer,
+                    board,
                     auction,
-                    (Mm(margin_left), content_y),
-                    Some(&board.players),
+                    (deal_left, content_y.0),
                     Some(notes_max_width),
+                    &bid_settings,
+                    &hand_record_fonts.italic,
+                    true,
                 );
 
                 // Debug box for bidding table
-                self.draw_debug_box(layer, margin_left, content_y.0, table_width, table_height);
+                self.draw_debug_box(layer, deal_left, content_y.0, table_width, table_height);
 
                 content_y = Mm(content_y.0 - table_height);
 
@@ -1860,34 +2464,51 @@ impl DocumentRenderer {
                     .and_then(|p| p.tricks.first())
                     .and_then(|t| t.cards[0])
                     .is_some();
+                let has_best_leads = self.settings.show_best_leads && !board.best_leads.is_empty();
 
                 // Add spacing after auction before contract/lead
-                if has_contract || has_lead {
+                if has_contract || has_best_leads || has_lead {
                     content_y = Mm(content_y.0 - line_height);
                 }
 
                 // Render contract below auction (only if explicitly in PBN)
                 if let Some(ref contract) = board.contract {
-                    let colors =
-                        SuitColors::new(self.settings.black_color, self.settings.red_color);
+                    let colors = SuitColors::from_settings(&self.settings);
                     let x = self.render_contract(
                         layer,
                         contract,
-                        Mm(margin_left),
+                        Mm(deal_left),
                         content_y,
-                        hand_record_fonts.regular,
+                        &hand_record_fonts.regular,
                         fonts.symbol_font(),
                         &colors,
                     );
                     // Debug box for contract line
-                    let contract_width = x - margin_left;
+                    let contract_width = x - deal_left;
                     self.draw_debug_box(
                         layer,
-                        margin_left,
+                        deal_left,
                         content_y.0 + cap_height,
                         contract_width,
                         cap_height + descender,
                     );
+                    if has_best_leads || has_lead {
+                        content_y = Mm(content_y.0 - line_height);
+                    }
+                }
+
+                // Render double-dummy best leads below the contract
+                if has_best_leads {
+                    let colors = SuitColors::from_settings(&self.settings);
+                    self.render_best_leads(
+                        layer,
+                        &board.best_leads,
+                        Mm(deal_left),
+                        content_y,
+                        &hand_record_fonts.regular,
+                        fonts.symbol_font(),
+                        &colors,
+                    );
                     if has_lead {
                         content_y = Mm(content_y.0 - line_height);
                     }
@@ -1897,25 +2518,32 @@ impl DocumentRenderer {
                 if let Some(ref play) = board.play {
                     if let Some(first_trick) = play.tricks.first() {
                         if let Some(lead_card) = first_trick.cards[0] {
-                            let colors =
-                                SuitColors::new(self.settings.black_color, self.settings.red_color);
+                            let colors = SuitColors::from_settings(&self.settings);
                             self.render_lead(
                                 layer,
                                 &lead_card,
-                                Mm(margin_left),
+                                Mm(deal_left),
                                 content_y,
-                                hand_record_fonts.regular,
+                                &hand_record_fonts.regular,
                                 fonts.symbol_font(),
                                 &colors,
                             );
                             // Debug box for lead line
                             self.draw_debug_box(
                                 layer,
-                                margin_left,
+                                deal_left,
                                 content_y.0 + cap_height,
                                 table_width,
                                 cap_height + descender,
                             );
+                            let notes_height = self.render_play_notes(
+                                layer,
+                                &bidding_renderer,
+                                play,
+                                (deal_left, content_y.0),
+                                notes_max_width,
+                            );
+                            content_y = Mm(content_y.0 - notes_height);
                         }
                     }
                 }
@@ -1925,12 +2553,12 @@ impl DocumentRenderer {
         }
 
         // Render commentary if present - using floating layout
-        if self.settings.show_commentary && board.commentary.iter().any(|c| !c.is_blank()) {
+        if self.settings.show_commentary && board.general_commentary().next().is_some() {
             let commentary_renderer = CommentaryRenderer::new(
-                commentary_fonts.regular,
-                commentary_fonts.bold,
-                commentary_fonts.italic,
-                commentary_fonts.bold_italic,
+                commentary_fonts.regular.clone(),
+                commentary_fonts.bold.clone(),
+                commentary_fonts.italic.clone(),
+                commentary_fonts.bold_italic.clone(),
                 fonts.symbol_font(), // DejaVu Sans for suit symbols
                 &self.settings,
             );
@@ -1947,9 +2575,13 @@ impl DocumentRenderer {
             // The float_until_y is where the deal content ends (current content_y)
             let float_until_y = content_y.0;
 
+            let float_left = match self.settings.diagram_side {
+                DiagramSide::Left => page_center + 2.0, // Start just right of center
+                DiagramSide::Right => margin_left,
+            };
             let float_layout = FloatLayout {
                 float_until_y,
-                float_left: page_center + 2.0, // Start just right of center
+                float_left,
                 float_width,
                 full_left: margin_left,
                 full_width,
@@ -1961,8 +2593,7 @@ impl DocumentRenderer {
                 get_times_measurer().cap_height_mm(self.settings.commentary_font_size);
             let mut commentary_y = page_top - commentary_cap;
             let mut first_block = true;
-            let non_blank_blocks: Vec<_> =
-                board.commentary.iter().filter(|c| !c.is_blank()).collect();
+            let non_blank_blocks: Vec<_> = board.general_commentary().collect();
 
             let commentary_asc =
                 get_times_measurer().ascender_mm(self.settings.commentary_font_size);
@@ -2049,19 +2680,22 @@ impl DocumentRenderer {
         contract: &crate::model::Contract,
         x: Mm,
         y: Mm,
-        text_font: BuiltinFont,
+        text_font: &TextFont,
         symbol_font: &FontId,
         colors: &SuitColors,
     ) -> f32 {
-        let measurer = get_times_measurer();
+        let measurer = text_font.measurer_or(get_times_measurer());
+        let symbol_measurer = get_times_measurer();
         let font_size = self.settings.body_font_size;
         let mut current_x = x.0;
 
+        layer.begin_answers();
+
         // Render level
         let level_str = contract.level.to_string();
         layer.set_fill_color(Color::Rgb(BLACK));
-        layer.use_text_builtin(&level_str, font_size, Mm(current_x), y, text_font);
-        current_x += measurer.measure_width_mm(&level_str, font_size);
+        layer.use_text_font(&level_str, font_size, Mm(current_x), y, text_font);
+        current_x += measurer.measure_text(&level_str, font_size);
 
         // Render suit symbol (or NT)
         let (symbol, use_symbol_font) = match contract.suit {
@@ -2072,37 +2706,88 @@ impl DocumentRenderer {
             BidSuit::NoTrump => ("NT", false),
         };
 
-        if contract.suit.is_red() {
-            layer.set_fill_color(Color::Rgb(colors.hearts.clone()));
-        } else {
-            layer.set_fill_color(Color::Rgb(BLACK));
-        }
+        layer.set_fill_color(Color::Rgb(colors.for_bid_suit(&contract.suit)));
 
         if use_symbol_font {
             layer.use_text(symbol, font_size, Mm(current_x), y, symbol_font);
         } else {
-            layer.use_text_builtin(symbol, font_size, Mm(current_x), y, text_font);
+            layer.use_text_font(symbol, font_size, Mm(current_x), y, text_font);
         }
-        current_x += measurer.measure_width_mm(symbol, font_size);
+        current_x += if use_symbol_font {
+            symbol_measurer.measure_width_mm(symbol, font_size)
+        } else {
+            measurer.measure_text(symbol, font_size)
+        };
 
         // Render doubled/redoubled
         layer.set_fill_color(Color::Rgb(BLACK));
         if contract.redoubled {
-            layer.use_text_builtin("XX", font_size, Mm(current_x), y, text_font);
-            current_x += measurer.measure_width_mm("XX", font_size);
+            layer.use_text_font("XX", font_size, Mm(current_x), y, text_font);
+            current_x += measurer.measure_text("XX", font_size);
         } else if contract.doubled {
-            layer.use_text_builtin("X", font_size, Mm(current_x), y, text_font);
-            current_x += measurer.measure_width_mm("X", font_size);
+            layer.use_text_font("X", font_size, Mm(current_x), y, text_font);
+            current_x += measurer.measure_text("X", font_size);
         }
 
         // Render " by [declarer]"
         let by_text = format!(" by {}", contract.declarer);
-        layer.use_text_builtin(&by_text, font_size, Mm(current_x), y, text_font);
-        current_x += measurer.measure_width_mm(&by_text, font_size);
+        layer.use_text_font(&by_text, font_size, Mm(current_x), y, text_font);
+        current_x += measurer.measure_text(&by_text, font_size);
+
+        layer.end_answers();
 
         current_x
     }
 
+    /// The play's notes, numbered as in the PBN, each led by the cards
+    /// that reference it ("♠Q: suit preference")
+    fn play_notes(&self, play: &PlaySequence) -> HashMap<u8, String> {
+        play.footnotes()
+            .into_iter()
+            .map(|(number, cards, text)| {
+                let cards: Vec<String> = cards
+                    .iter()
+                    .map(|card| {
+                        let rank = card.rank.display_str_with(self.settings.ten_as_t);
+                        format!("{}{}", card.suit.symbol(), rank)
+                    })
+                    .collect();
+                let text = if cards.is_empty() {
+                    text.to_string()
+                } else {
+                    format!("{}: {}", cards.join(", "), text)
+                };
+                (number, text)
+            })
+            .collect()
+    }
+
+    /// Render the play's notes as footnotes under the opening lead line at
+    /// `origin`, like the notes under an auction. Returns the distance down
+    /// to the last note's baseline.
+    fn render_play_notes(
+        &self,
+        layer: &mut LayerBuilder,
+        bidding_renderer: &BiddingTableRenderer,
+        play: &PlaySequence,
+        origin: (f32, f32),
+        max_width: f32,
+    ) -> f32 {
+        let notes = self.play_notes(play);
+        if notes.is_empty() {
+            return 0.0;
+        }
+        layer.begin_answers();
+        let height = bidding_renderer.render_footnotes(
+            layer,
+            &notes,
+            (Mm(origin.0), Mm(origin.1)),
+            Some(max_width),
+        );
+        layer.end_answers();
+        height
+    }
+
     /// Render opening lead with proper suit symbol font
     #[allow(clippy::too_many_arguments)]
     fn render_lead(
@@ -2111,31 +2796,145 @@ impl DocumentRenderer {
         card: &crate::model::Card,
         x: Mm,
         y: Mm,
-        text_font: BuiltinFont,
+        text_font: &TextFont,
         symbol_font: &FontId,
         colors: &SuitColors,
     ) {
-        let measurer = get_times_measurer();
+        let measurer = text_font.measurer_or(get_times_measurer());
         let font_size = self.settings.body_font_size;
         let mut current_x = x.0;
 
+        layer.begin_answers();
+
         // Render "Lead: "
-        let prefix = "Lead: ";
+        let prefix = Locale::label(self.settings.locale().lead);
         layer.set_fill_color(Color::Rgb(BLACK));
-        layer.use_text_builtin(prefix, font_size, Mm(current_x), y, text_font);
-        current_x += measurer.measure_width_mm(prefix, font_size);
+        layer.use_text_font(&prefix, font_size, Mm(current_x), y, text_font);
+        current_x += measurer.measure_text(&prefix, font_size);
+
+        self.render_card_text(layer, card, current_x, y, text_font, symbol_font, colors);
+
+        layer.end_answers();
+    }
+
+    /// Render the double-dummy best opening leads line (e.g., "Best leads: ♠4, ♦K")
+    #[allow(clippy::too_many_arguments)]
+    fn render_best_leads(
+        &self,
+        layer: &mut LayerBuilder,
+        cards: &[crate::model::Card],
+        x: Mm,
+        y: Mm,
+        text_font: &TextFont,
+        symbol_font: &FontId,
+        colors: &SuitColors,
+    ) {
+        let measurer = text_font.measurer_or(get_times_measurer());
+        let font_size = self.settings.body_font_size;
+        let mut current_x = x.0;
+
+        layer.begin_answers();
+
+        let prefix = Locale::label(self.settings.locale().best_leads);
+        layer.set_fill_color(Color::Rgb(BLACK));
+        layer.use_text_font(&prefix, font_size, Mm(current_x), y, text_font);
+        current_x += measurer.measure_text(&prefix, font_size);
+
+        for (i, card) in cards.iter().enumerate() {
+            if i > 0 {
+                layer.set_fill_color(Color::Rgb(BLACK));
+                layer.use_text_font(", ", font_size, Mm(current_x), y, text_font);
+                current_x += measurer.measure_text(", ", font_size);
+            }
+            current_x =
+                self.render_card_text(layer, card, current_x, y, text_font, symbol_font, colors);
+        }
+
+        layer.end_answers();
+    }
+
+    /// Render a card as a colored suit symbol and a black rank.
+    /// Returns the x position after the card.
+    #[allow(clippy::too_many_arguments)]
+    fn render_card_text(
+        &self,
+        layer: &mut LayerBuilder,
+        card: &crate::model::Card,
+        x: f32,
+        y: Mm,
+        text_font: &TextFont,
+        symbol_font: &FontId,
+        colors: &SuitColors,
+    ) -> f32 {
+        let measurer = text_font.measurer_or(get_times_measurer());
+        let font_size = self.settings.body_font_size;
+        let mut current_x = x;
 
         // Render suit symbol with color
         let symbol = card.suit.symbol().to_string();
         let suit_color = colors.for_suit(&card.suit);
         layer.set_fill_color(Color::Rgb(suit_color));
         layer.use_text(&symbol, font_size, Mm(current_x), y, symbol_font);
-        current_x += measurer.measure_width_mm(&symbol, font_size);
+        current_x += get_times_measurer().measure_width_mm(&symbol, font_size);
 
         // Render rank in black
-        let rank = card.rank.display_str().to_string();
+        let rank = card
+            .rank
+            .display_str_with(self.settings.ten_as_t)
+            .to_string();
         layer.set_fill_color(Color::Rgb(BLACK));
-        layer.use_text_builtin(&rank, font_size, Mm(current_x), y, text_font);
+        layer.use_text_font(&rank, font_size, Mm(current_x), y, text_font);
+        current_x + measurer.measure_text(&rank, font_size)
+    }
+}
+
+/// Standings from the last `[TotalScoreTable]` among the boards, if any
+fn event_ranking(boards: &[Board]) -> Vec<Standing> {
+    boards
+        .iter()
+        .rev()
+        .find_map(|board| board.total_score_table.as_ref())
+        .map(|table| table.standings())
+        .unwrap_or_default()
+}
+
+/// Check if a board's commentary contains CJK text
+fn has_cjk_commentary(board: &Board) -> bool {
+    board
+        .commentary
+        .iter()
+        .any(|block| contains_cjk(&block.content.to_plain_text()))
+}
+
+impl LayoutRenderer for DocumentRenderer {
+    fn name(&self) -> &str {
+        "analysis"
+    }
+
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        let ranking = ranking_pages(&event_ranking(boards), &self.settings).len();
+        if self.settings.column_count < 2 {
+            return Ok(boards.len() + ranking);
+        }
+        // Columns fill by measured height, so lay them out on a scratch
+        // document, with a renderer that reports no progress
+        let scratch = DocumentRenderer::new(self.settings.clone());
+        let mut doc = PdfDocument::new("");
+        let fonts = FontManager::for_settings(&mut doc, &self.settings)?;
+        let (pages, ..) = scratch.render_multi_column(boards, &fonts, None, None);
+        Ok(pages.len() + ranking)
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        DocumentRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
     }
 }
 
@@ -2144,3 +2943,152 @@ pub fn generate_pdf(boards: &[Board], settings: &Settings) -> Result<Vec<u8>, Re
     let renderer = DocumentRenderer::new(settings.clone());
     renderer.render(boards)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{CompressionLevel, FontEmbedding, Layout};
+    use crate::parser::parse_pbn;
+    use lopdf::content::Content;
+    use lopdf::{Document, Object};
+
+    /// Each page's operators and operands, in page order. Font and image
+    /// names differ from one document to the next, so each page's names are
+    /// numbered in the order they first appear.
+    fn page_ops(pdf: &[u8]) -> Vec<Vec<String>> {
+        let doc = Document::load_mem(pdf).unwrap();
+        doc.get_pages()
+            .into_values()
+            .map(|id| {
+                let content = Content::decode(&doc.get_page_content(id).unwrap()).unwrap();
+                let mut names: Vec<Vec<u8>> = Vec::new();
+                content
+                    .operations
+                    .iter()
+                    .map(|op| {
+                        let operands: Vec<String> = op
+                            .operands
+                            .iter()
+                            .map(|operand| match operand {
+                                Object::Name(name) => {
+                                    let index = names.iter().position(|n| n == name);
+                                    let index = index.unwrap_or_else(|| {
+                                        names.push(name.clone());
+                                        names.len() - 1
+                                    });
+                                    format!("/{}", index)
+                                }
+                                other => format!("{:?}", other),
+                            })
+                            .collect();
+                        format!("{} {}", operands.join(" "), op.operator)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_pages_match_sequential() {
+        // More boards than a batch, so several batches are drawn
+        let pbn: String = (1..=BOARD_BATCH + 4)
+            .map(|n| {
+                format!(
+                    "[Board \"{}\"]\n[Dealer \"N\"]\n[Vulnerable \"None\"]\n\
+                     [Deal \"N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765\"]\n\
+                     {{Board {} commentary.}}\n\n",
+                    n, n
+                )
+            })
+            .collect();
+        let boards = parse_pbn(&pbn).unwrap().boards;
+
+        for column_count in [1, 2] {
+            let mut settings = Settings::for_layout(Layout::Analysis);
+            settings.column_count = column_count;
+            // Whole fonts and plain streams, so pages can be compared op by op
+            settings.font_embedding = FontEmbedding::Full;
+            settings.compression = CompressionLevel::None;
+            let render = |threads: usize| {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap();
+                let pdf = pool.install(|| generate_pdf(&boards, &settings).unwrap());
+                page_ops(&pdf)
+            };
+
+            let sequential = render(1);
+            let parallel = render(4);
+            assert!(sequential.len() > 1);
+            assert_eq!(parallel.len(), sequential.len());
+            assert!(
+                parallel == sequential,
+                "pages differ with {} columns",
+                column_count
+            );
+        }
+    }
+
+    /// Text shown in the document, one string per text operator
+    fn page_text(pdf: &[u8]) -> Vec<String> {
+        let doc = Document::load_mem(pdf).unwrap();
+        let mut text = Vec::new();
+        for id in doc.get_pages().into_values() {
+            let content = Content::decode(&doc.get_page_content(id).unwrap()).unwrap();
+            for op in content.operations {
+                let strings = match op.operator.as_str() {
+                    "Tj" => op.operands,
+                    "TJ" => match op.operands.into_iter().next() {
+                        Some(Object::Array(items)) => items,
+                        _ => Vec::new(),
+                    },
+                    _ => continue,
+                };
+                let shown: Vec<u8> = strings
+                    .into_iter()
+                    .filter_map(|operand| match operand {
+                        Object::String(bytes, _) => Some(bytes),
+                        _ => None,
+                    })
+                    .flatten()
+                    .collect();
+                text.push(String::from_utf8_lossy(&shown).into_owned());
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn test_play_notes_drawn_under_the_lead() {
+        let pbn = r#"[Board "1"]
+[Dealer "N"]
+[Deal "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765"]
+[Auction "N"]
+1NT =1= Pass 3NT AP
+[Note "1:15-17"]
+[Play "E"]
+S9 =1= S5 S3 S2
+HA H8=2= H4 H2
+[Note "1:Top of nothing"]
+[Note "2:Count"]
+"#;
+        let boards = parse_pbn(pbn).unwrap().boards;
+
+        for column_count in [1, 2] {
+            let mut settings = Settings::for_layout(Layout::Analysis);
+            settings.column_count = column_count;
+            settings.compression = CompressionLevel::None;
+            let text = page_text(&generate_pdf(&boards, &settings).unwrap());
+            let shown = |s: &str| text.iter().any(|t| t.contains(s));
+
+            // Each note follows the card that cites it (its suit symbol is
+            // drawn in the symbol font)
+            assert!(shown("9: Top of nothing"), "{:?}", text);
+            assert!(shown("8: Count"), "{:?}", text);
+            // The auction's own note is still drawn
+            assert!(shown("15-17"), "{:?}", text);
+        }
+    }
+}
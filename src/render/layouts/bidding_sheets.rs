@@ -6,35 +6,48 @@
 //! 2. Answers page (shows both hands + auction)
 //! 3. South practice page (shows only South's hand)
 //! 4. Answers page (repeated for duplex printing)
+//!
+//! With `--duplex none` the answers page is printed once, after South.
 
-use printpdf::{
-    BuiltinFont, Color, FontId, Mm, PaintMode, PdfDocument, PdfPage, PdfSaveOptions, Rgb,
-};
+use printpdf::{BuiltinFont, Color, FontId, Mm, PaintMode, PdfDocument, PdfPage, Rgb};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
-use crate::config::Settings;
-use crate::error::RenderError;
+use crate::cli::{Duplex, SuitStyle};
+use crate::config::{Locale, Settings};
+use crate::error::{RenderError, RenderWarning};
 use crate::model::{
     AnnotatedCall, Auction, BidSuit, Board, Call, Direction, DirectionExt, Hand, Suit,
-    Vulnerability,
 };
 
-use crate::render::helpers::colors::{SuitColors, BLACK, WHITE};
+use crate::render::components::commentary::CommentaryRenderer;
+use crate::render::components::header_footer::add_header_footer;
+use crate::render::components::title_block::add_title_block;
+use crate::render::helpers::background::{add_image_background, add_pdf_background};
+use crate::render::helpers::colors::{SuitColors, Theme, BLACK, WHITE};
 use crate::render::helpers::compress::compress_pdf;
-use crate::render::helpers::fonts::FontManager;
-use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::duplex::{pad_for_duplex, rotate_back_pages};
+use crate::render::helpers::fonts::{save_options, unused_custom_fonts, FontManager, FontRole};
+use crate::render::helpers::forms::{add_form_fields, TextField};
+use crate::render::helpers::grayscale::{apply_grayscale, grayscale_xobjects};
+use crate::render::helpers::layer::{add_answers_layer, LayerBuilder};
+use crate::render::helpers::links::add_links;
+use crate::render::helpers::progress::{Progress, ProgressCallback};
+use crate::render::helpers::report::{RenderReport, WarningLog};
+
+use super::renderer::LayoutRenderer;
+use crate::render::helpers::suit_letters::apply_suit_letters;
 use crate::render::helpers::text_metrics::{
     get_helvetica_bold_measurer, get_helvetica_measurer, get_times_measurer, TextMeasure,
 };
+use crate::render::helpers::timings;
 
-/// Light gray color for debug boxes
-const DEBUG_BOX_COLOR: Rgb = Rgb {
-    r: 0.7,
-    g: 0.7,
-    b: 0.7,
-    icc_profile: None,
-};
 // Debug boxes are now controlled via settings.debug_boxes
 
+/// Log target for board measurements and page breaks (`-v` for the page
+/// sets, `-vv` for each board)
+const PAGE_BREAK_LOG: &str = "pbn_to_pdf::page_breaks";
+
 /// Font sizes for bidding sheets
 const PRACTICE_FONT_SIZE: f32 = 16.0;
 const ANSWERS_FONT_SIZE: f32 = 12.0;
@@ -68,6 +81,9 @@ const ANSWERS_SEPARATOR_THICKNESS: f32 = 1.0;
 /// Bidding sheets renderer
 pub struct BiddingSheetsRenderer {
     settings: Settings,
+    theme: Theme,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 /// Measured heights for a board on different page types
@@ -76,14 +92,6 @@ struct BoardHeights {
     answers: f32,
 }
 
-/// Blue color for page margin debug box
-const DEBUG_MARGIN_COLOR: Rgb = Rgb {
-    r: 0.0,
-    g: 0.0,
-    b: 1.0,
-    icc_profile: None,
-};
-
 impl BiddingSheetsRenderer {
     /// Draw a debug outline box
     fn draw_debug_box(&self, layer: &mut LayerBuilder, x: f32, y: f32, w: f32, h: f32) {
@@ -91,7 +99,7 @@ impl BiddingSheetsRenderer {
             return;
         }
         // y is top of box, draw from bottom-left to top-right
-        layer.set_outline_color(Color::Rgb(DEBUG_BOX_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.debug_box.clone()));
         layer.set_outline_thickness(0.25);
         layer.add_rect(Mm(x), Mm(y - h), Mm(x + w), Mm(y), PaintMode::Stroke);
     }
@@ -113,7 +121,7 @@ impl BiddingSheetsRenderer {
         let w = page_width - margin_left - margin_right;
         let h = page_height - margin_top - margin_bottom;
 
-        layer.set_outline_color(Color::Rgb(DEBUG_MARGIN_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.debug_margin.clone()));
         layer.set_outline_thickness(0.5);
         layer.add_rect(Mm(x), Mm(y), Mm(x + w), Mm(y + h), PaintMode::Stroke);
     }
@@ -220,6 +228,8 @@ impl BiddingSheetsRenderer {
         available_width: f32,
         measurer: &dyn TextMeasure,
     ) -> (String, f32) {
+        self.warnings
+            .push(RenderWarning::TruncatedTitle(title.to_string()));
         let ellipsis = "...";
         let ellipsis_width = measurer.measure_text(ellipsis, HEADER_FONT_SIZE);
 
@@ -253,52 +263,103 @@ impl BiddingSheetsRenderer {
 
 impl BiddingSheetsRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let theme = Theme::from_settings(&settings);
+        Self {
+            settings,
+            theme,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
     }
 
-    /// Generate a PDF with bidding practice sheets
+    /// Generate a PDF with bidding practice sheets, logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF with bidding practice sheets, with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Bidding Practice");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
 
-        // Load fonts - printpdf 0.8 handles subsetting automatically
-        let fonts = FontManager::new(&mut doc)?;
+        // Load fonts - printpdf 0.8 subsets them when saving (see --font-embedding)
+        let fonts = timings::time("fonts", || {
+            FontManager::for_settings(&mut doc, &self.settings)
+        })?;
+        for warning in unused_custom_fonts(&self.settings, &[FontRole::Commentary]) {
+            self.warnings.push(warning);
+        }
+
+        let answers_layer = add_answers_layer(&mut doc, self.settings.answers_layer);
 
         let mut pages = Vec::new();
+        let mut page_fields = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards: Vec<Vec<&Board>> = Vec::new();
 
         // Measure actual board heights by doing a dry-run render
-        let board_heights = self.measure_board_heights(boards, &fonts);
+        let board_heights = timings::time("measure", || self.measure_board_heights(boards, &fonts));
 
         // Group boards into sets that fit on a page using actual measured heights
         let board_sets = self.group_boards_with_heights(boards, &board_heights);
 
+        let single_sided = self.settings.duplex == Some(Duplex::None);
+
+        let mut done = 0;
         for board_set in board_sets {
+            self.progress.check_cancelled()?;
             // North practice page
             let mut layer = LayerBuilder::new();
             self.render_practice_page(&mut layer, board_set, Direction::North, &fonts);
+            page_fields.push(layer.take_text_fields());
+            page_links.push(layer.take_links());
+            page_boards.push(board_set.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
 
-            // Answers page (after North)
-            let mut layer = LayerBuilder::new();
-            self.render_answers_page(&mut layer, board_set, &fonts);
-            pages.push(PdfPage::new(
-                Mm(self.settings.page_width),
-                Mm(self.settings.page_height),
-                layer.into_ops(),
-            ));
+            // Answers page (after North, on its back); single-sided output
+            // prints the answers once, after South
+            if !single_sided {
+                let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
+                self.render_answers_page(&mut layer, board_set, &fonts);
+                page_fields.push(layer.take_text_fields());
+                page_links.push(layer.take_links());
+                page_boards.push(board_set.iter().collect());
+                pages.push(PdfPage::new(
+                    Mm(self.settings.page_width),
+                    Mm(self.settings.page_height),
+                    layer.into_ops(),
+                ));
+            }
 
             // South practice page
             let mut layer = LayerBuilder::new();
             self.render_practice_page(&mut layer, board_set, Direction::South, &fonts);
+            page_fields.push(layer.take_text_fields());
+            page_links.push(layer.take_links());
+            page_boards.push(board_set.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
@@ -306,23 +367,57 @@ impl BiddingSheetsRenderer {
             ));
 
             // Answers page (after South, for duplex printing)
-            let mut layer = LayerBuilder::new();
+            let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
             self.render_answers_page(&mut layer, board_set, &fonts);
+            page_fields.push(layer.take_text_fields());
+            page_links.push(layer.take_links());
+            page_boards.push(board_set.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
+            done += board_set.len();
+            self.progress.boards(done, boards.len());
+        }
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        add_header_footer(&mut doc, &mut pages, &page_boards, &self.settings)?;
+        add_title_block(&mut pages, &self.settings);
+        add_image_background(&mut doc, &mut pages, &self.settings)?;
+        pad_for_duplex(&mut pages, &self.settings);
+        if self.settings.grayscale {
+            apply_grayscale(&mut pages);
+        }
+        if self.settings.suit_style == SuitStyle::Letters {
+            apply_suit_letters(&mut pages, fonts.symbol_font());
         }
-
         doc.with_pages(pages);
 
         let mut warnings = Vec::new();
-        let bytes = doc.save(&PdfSaveOptions::default(), &mut warnings);
+        let bytes = timings::time("save", || {
+            doc.save(&save_options(self.settings.font_embedding), &mut warnings)
+        });
+        let bytes = add_pdf_background(bytes, &self.settings)?;
+        let bytes = if self.settings.grayscale {
+            grayscale_xobjects(bytes).map_err(RenderError::PdfGeneration)?
+        } else {
+            bytes
+        };
+        let bytes = rotate_back_pages(bytes, &self.settings)?;
+        let bytes = add_form_fields(bytes.clone(), &page_fields, self.settings.first_page())
+            .unwrap_or(bytes);
+        let bytes = add_links(bytes.clone(), &page_links).unwrap_or(bytes);
 
         // Compress PDF streams to reduce file size
-        let compressed = compress_pdf(bytes.clone()).unwrap_or(bytes);
-        Ok(compressed)
+        let pdf = timings::time("compress", || {
+            compress_pdf(bytes.clone(), self.settings.compression).unwrap_or(bytes)
+        });
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
     }
 
     /// Calculate available content height on a page (after banner and gaps)
@@ -354,7 +449,7 @@ impl BiddingSheetsRenderer {
         let text_font = fonts.serif.regular;
         let bold_font = fonts.serif.bold;
         let symbol_font = fonts.symbol_font();
-        let colors = SuitColors::new(self.settings.black_color, self.settings.red_color);
+        let colors = SuitColors::from_settings(&self.settings);
 
         boards
             .iter()
@@ -372,9 +467,25 @@ impl BiddingSheetsRenderer {
                     self.count_auction_setup_lines(board, Direction::South) as f32;
                 let setup_lines = north_setup_lines.max(south_setup_lines);
 
-                let practice_height =
+                let columns_height =
                     context_lines.max(hand_lines).max(setup_lines) * practice_line_height;
 
+                // Seat-scoped notes: dry-run render for each practice page
+                let notes_height = [Direction::North, Direction::South]
+                    .into_iter()
+                    .map(|player| {
+                        self.render_seat_notes(
+                            &mut LayerBuilder::new(),
+                            board,
+                            player,
+                            (0.0, 0.0),
+                            self.settings.content_width(),
+                            fonts,
+                        )
+                    })
+                    .fold(0.0, f32::max);
+                let practice_height = columns_height + notes_height;
+
                 // Answers page: measure actual auction height
                 let (auction_height, _) = if let Some(ref auction) = board.auction {
                     // Do a dry-run render to get actual height
@@ -401,8 +512,9 @@ impl BiddingSheetsRenderer {
                     .max(answers_hand_height)
                     .max(auction_height);
 
-                eprintln!(
-                    "  measure board {}: practice={:.2} (setup_lines={:.0}), answers={:.2}",
+                log::debug!(
+                    target: PAGE_BREAK_LOG,
+                    "Measured board {}: practice={:.2} (setup_lines={:.0}), answers={:.2}",
                     board.number.unwrap_or(0),
                     practice_height,
                     setup_lines,
@@ -424,19 +536,17 @@ impl BiddingSheetsRenderer {
         heights: &[BoardHeights],
     ) -> Vec<&'a [Board]> {
         let available_height = self.available_content_height();
-        eprintln!("=== Page break calculations ===");
-        eprintln!("Page height: {}", self.settings.page_height);
-        eprintln!(
-            "Margin top: {}, bottom: {}",
-            self.settings.margin_top, self.settings.margin_bottom
+        log::debug!(
+            target: PAGE_BREAK_LOG,
+            "Page height {}, margins top {} bottom {}, banner {} + gap {}: {} available, {} between boards",
+            self.settings.page_height,
+            self.settings.margin_top,
+            self.settings.margin_bottom,
+            BANNER_HEIGHT,
+            AFTER_BANNER_GAP,
+            available_height,
+            ROW_GAP
         );
-        eprintln!(
-            "Banner height: {}, after banner gap: {}",
-            BANNER_HEIGHT, AFTER_BANNER_GAP
-        );
-        eprintln!("Available content height: {}", available_height);
-        eprintln!("ROW_GAP between boards: {}", ROW_GAP);
-        eprintln!();
 
         let mut sets = Vec::new();
         let mut start = 0;
@@ -444,7 +554,6 @@ impl BiddingSheetsRenderer {
         while start < boards.len() {
             let mut current_height = 0.0;
             let mut end = start;
-            eprintln!("--- Starting new page set at board index {} ---", start);
 
             // Add boards until we run out of space
             while end < boards.len() {
@@ -463,7 +572,8 @@ impl BiddingSheetsRenderer {
                 };
 
                 let would_be_height = current_height + height_needed;
-                eprintln!(
+                log::debug!(
+                    target: PAGE_BREAK_LOG,
                     "Board {}: practice_h={:.2}, answers_h={:.2}, board_h={:.2}, height_needed={:.2}, current={:.2}, would_be={:.2}, available={:.2}, fits={}",
                     boards[end].number.unwrap_or(0),
                     practice_height,
@@ -478,8 +588,9 @@ impl BiddingSheetsRenderer {
 
                 if current_height + height_needed > available_height && end > start {
                     // This board won't fit, but we have at least one board
-                    eprintln!(
-                        "  -> Board {} does not fit, breaking page",
+                    log::debug!(
+                        target: PAGE_BREAK_LOG,
+                        "Board {} does not fit, breaking page",
                         boards[end].number.unwrap_or(0)
                     );
                     break;
@@ -494,13 +605,13 @@ impl BiddingSheetsRenderer {
                 end = start + 1;
             }
 
-            eprintln!(
-                "Page set contains boards {} to {} (total height: {:.2})",
+            log::info!(
+                target: PAGE_BREAK_LOG,
+                "Page set holds board indices {} to {} (total height {:.2})",
                 start,
                 end - 1,
                 current_height
             );
-            eprintln!();
             sets.push(&boards[start..end]);
             start = end;
         }
@@ -530,28 +641,29 @@ impl BiddingSheetsRenderer {
         let bold_font = fonts.serif.bold;
         let sans_bold_font = fonts.sans.bold;
         let symbol_font = fonts.symbol_font();
-        let colors = SuitColors::new(self.settings.black_color, self.settings.red_color);
+        let colors = SuitColors::from_settings(&self.settings);
 
         // Color for player identification
         let header_color = match player {
-            Direction::North => Rgb::new(0.12, 0.56, 1.0, None), // DodgerBlue
-            Direction::South => Rgb::new(1.0, 0.65, 0.0, None),  // Orange
-            _ => Rgb::new(0.5, 0.5, 0.5, None),
+            Direction::North => self.theme.banner_north.clone(),
+            Direction::South => self.theme.banner_south.clone(),
+            _ => self.theme.banner_other.clone(),
         };
 
         // Header banner with title
         // Use short version (without "Practice Page") when title is present to make room
-        let title = self.settings.effective_title();
+        let title = self.settings.banner_title();
+        let locale = self.settings.locale();
         let header_text = if title.is_some() {
-            format!("{} hands", player)
+            locale.hands_banner(player)
         } else {
-            format!("{} hands (Practice Page)", player)
+            format!("{} ({})", locale.hands_banner(player), locale.practice_page)
         };
         self.render_banner(
             layer,
             &header_text,
             &header_text, // Same text since we already shortened it when title present
-            title,
+            title.as_deref(),
             header_color.clone(),
             sans_bold_font,
             sans_bold_measurer,
@@ -571,13 +683,13 @@ impl BiddingSheetsRenderer {
             let hand_height = 4.0 * line_height; // 4 suits
             let setup_lines = self.count_auction_setup_lines(board, player) as f32;
             let setup_height = setup_lines * line_height;
-            let row_height = context_height.max(hand_height).max(setup_height);
+            let columns_height = context_height.max(hand_height).max(setup_height);
 
             // Debug boxes for each column
             let cap_height = measurer.cap_height_mm(PRACTICE_FONT_SIZE);
             let descender = measurer.descender_mm(PRACTICE_FONT_SIZE);
             let box_top = current_y + cap_height;
-            let box_height = cap_height + row_height - line_height + descender;
+            let box_height = cap_height + columns_height - line_height + descender;
             let setup_col_width = content_width - CONTEXT_COLUMN_WIDTH - HAND_COLUMN_WIDTH;
 
             self.draw_debug_box(
@@ -643,11 +755,40 @@ impl BiddingSheetsRenderer {
                 &colors,
             );
 
+            // Fillable answer field in the space left below the auction setup
+            if self.settings.fillable_forms {
+                let field_top = current_y - setup_height + cap_height;
+                let field_bottom = box_top - box_height;
+                if field_top - field_bottom >= line_height {
+                    layer.add_text_field(
+                        TextField::new(
+                            "answer",
+                            col3_x,
+                            field_bottom,
+                            setup_col_width,
+                            field_top - field_bottom,
+                        )
+                        .with_font_size(PRACTICE_FONT_SIZE * 0.8),
+                    );
+                }
+            }
+
+            // Notes for this seat, below the columns
+            let row_height = columns_height
+                + self.render_seat_notes(
+                    layer,
+                    board,
+                    player,
+                    (margin_left, current_y - columns_height),
+                    content_width,
+                    fonts,
+                );
+
             // Draw separator line in the middle of the gap (except after the last board)
-            // The visual bottom of content is at box_top - box_height
+            // The visual bottom of content is at the last baseline plus descender
             // The gap runs from visual bottom to the next board's baseline
             if i < board_count - 1 {
-                let visual_bottom = box_top - box_height;
+                let visual_bottom = row_start_y - row_height + line_height - descender;
                 let next_board_top = row_start_y - row_height - ROW_GAP + cap_height;
                 let line_y = (visual_bottom + next_board_top) / 2.0;
                 self.draw_separator_line(
@@ -662,7 +803,53 @@ impl BiddingSheetsRenderer {
         }
     }
 
-    /// Render an answers page (shows both hands + auction)
+    /// Render the commentary meant for `player` (`{N: ...}` blocks) across the
+    /// page below a practice row, with the first baseline at `origin`
+    ///
+    /// Returns the height added to the row (0 when there are no such blocks).
+    fn render_seat_notes(
+        &self,
+        layer: &mut LayerBuilder,
+        board: &Board,
+        player: Direction,
+        origin: (f32, f32),
+        width: f32,
+        fonts: &FontManager,
+    ) -> f32 {
+        let notes: Vec<_> = board
+            .commentary
+            .iter()
+            .filter(|block| block.seat == Some(player) && !block.is_blank())
+            .collect();
+        if notes.is_empty() {
+            return 0.0;
+        }
+
+        let commentary_fonts = fonts.text_set_for_role(FontRole::Commentary, None);
+        let renderer = CommentaryRenderer::new(
+            commentary_fonts.regular,
+            commentary_fonts.bold,
+            commentary_fonts.italic,
+            commentary_fonts.bold_italic,
+            fonts.symbol_font(),
+            &self.settings,
+        );
+        let (x, y) = origin;
+        let mut baseline = y;
+        for (i, block) in notes.iter().enumerate() {
+            if i > 0 {
+                baseline -= self.settings.line_height;
+            }
+            baseline -= renderer.render(layer, block, (Mm(x), Mm(baseline)), width);
+        }
+
+        // From the last row line to the last note line, plus the note's own line
+        let line_height = PRACTICE_FONT_SIZE * LINE_HEIGHT_MULTIPLIER * 0.4;
+        y - baseline + line_height
+    }
+
+    /// Render an answers page (shows both hands + auction); everything below
+    /// the banner is on the answers layer, if there is one
     fn render_answers_page(&self, layer: &mut LayerBuilder, boards: &[Board], fonts: &FontManager) {
         // Draw page margin boundary for debugging
         self.draw_margin_debug_box(layer);
@@ -678,14 +865,15 @@ impl BiddingSheetsRenderer {
         let bold_font = fonts.serif.bold;
         let sans_bold_font = fonts.sans.bold;
         let symbol_font = fonts.symbol_font();
-        let colors = SuitColors::new(self.settings.black_color, self.settings.red_color);
+        let colors = SuitColors::from_settings(&self.settings);
 
         // Header banner - dark gray for answers (no title on answers page since it's the back of practice page)
-        let header_color = Rgb::new(0.3, 0.3, 0.3, None);
+        let header_color = self.theme.banner_answers.clone();
+        let locale = self.settings.locale();
         self.render_banner(
             layer,
-            "Both hands (Answers Page)",
-            "Both hands",
+            &format!("{} ({})", locale.both_hands, locale.answers_page),
+            locale.both_hands,
             None, // No title on answers page
             header_color.clone(),
             sans_bold_font,
@@ -694,6 +882,7 @@ impl BiddingSheetsRenderer {
 
         // Start content below banner
         let mut current_y = page_top - BANNER_HEIGHT - AFTER_BANNER_GAP;
+        layer.begin_answers();
 
         let line_height = ANSWERS_FONT_SIZE * LINE_HEIGHT_MULTIPLIER * 0.4;
 
@@ -844,6 +1033,7 @@ impl BiddingSheetsRenderer {
 
             current_y = row_start_y - row_height - ROW_GAP;
         }
+        layer.end_answers();
     }
 
     /// Render board context (board number, dealer, vulnerability, HCP)
@@ -864,10 +1054,12 @@ impl BiddingSheetsRenderer {
 
         layer.set_fill_color(Color::Rgb(BLACK));
 
+        let locale = self.settings.locale();
+
         // Board number
         if let Some(num) = board.number {
             layer.use_text_builtin(
-                format!("Board: {}", num),
+                format!("{}: {}", locale.board, num),
                 font_size,
                 Mm(x),
                 Mm(current_y),
@@ -879,7 +1071,11 @@ impl BiddingSheetsRenderer {
         // Dealer
         if let Some(dealer) = board.dealer {
             layer.use_text_builtin(
-                format!("Dealer: {}", dealer),
+                format!(
+                    "{}{}",
+                    Locale::label(locale.dealer),
+                    locale.direction(dealer)
+                ),
                 font_size,
                 Mm(x),
                 Mm(current_y),
@@ -889,14 +1085,12 @@ impl BiddingSheetsRenderer {
         }
 
         // Vulnerability
-        let vul_str = match board.vulnerable {
-            Vulnerability::None => "None",
-            Vulnerability::NorthSouth => "N-S",
-            Vulnerability::EastWest => "E-W",
-            Vulnerability::Both => "Both",
-        };
         layer.use_text_builtin(
-            format!("Vul: {}", vul_str),
+            format!(
+                "{}{}",
+                Locale::label(locale.vul),
+                locale.vulnerability(board.vulnerable)
+            ),
             font_size,
             Mm(x),
             Mm(current_y),
@@ -910,9 +1104,9 @@ impl BiddingSheetsRenderer {
             let hcp = hand.total_hcp();
             let length_pts = hand.length_points();
             let hcp_str = if length_pts > 0 {
-                format!("HCP: {}+{}", hcp, length_pts)
+                format!("{}: {}+{}", locale.hcp, hcp, length_pts)
             } else {
-                format!("HCP: {}", hcp)
+                format!("{}: {}", locale.hcp, hcp)
             };
             layer.use_text_builtin(hcp_str, font_size, Mm(x), Mm(current_y), text_font);
         }
@@ -938,10 +1132,12 @@ impl BiddingSheetsRenderer {
 
         layer.set_fill_color(Color::Rgb(BLACK));
 
+        let locale = self.settings.locale();
+
         // Board number
         if let Some(num) = board.number {
             layer.use_text_builtin(
-                format!("Board: {}", num),
+                format!("{}: {}", locale.board, num),
                 font_size,
                 Mm(x),
                 Mm(current_y),
@@ -953,7 +1149,11 @@ impl BiddingSheetsRenderer {
         // Dealer
         if let Some(dealer) = board.dealer {
             layer.use_text_builtin(
-                format!("Dealer: {}", dealer),
+                format!(
+                    "{}{}",
+                    Locale::label(locale.dealer),
+                    locale.direction(dealer)
+                ),
                 font_size,
                 Mm(x),
                 Mm(current_y),
@@ -963,14 +1163,12 @@ impl BiddingSheetsRenderer {
         }
 
         // Vulnerability
-        let vul_str = match board.vulnerable {
-            Vulnerability::None => "None",
-            Vulnerability::NorthSouth => "N-S",
-            Vulnerability::EastWest => "E-W",
-            Vulnerability::Both => "Both",
-        };
         layer.use_text_builtin(
-            format!("Vul: {}", vul_str),
+            format!(
+                "{}{}",
+                Locale::label(locale.vul),
+                locale.vulnerability(board.vulnerable)
+            ),
             font_size,
             Mm(x),
             Mm(current_y),
@@ -982,9 +1180,20 @@ impl BiddingSheetsRenderer {
         let north_hcp = board.deal.north.total_hcp();
         let north_length = board.deal.north.length_points();
         let north_hcp_str = if north_length > 0 {
-            format!("North HCP: {}+{}", north_hcp, north_length)
+            format!(
+                "{} {}: {}+{}",
+                locale.direction(Direction::North),
+                locale.hcp,
+                north_hcp,
+                north_length
+            )
         } else {
-            format!("North HCP: {}", north_hcp)
+            format!(
+                "{} {}: {}",
+                locale.direction(Direction::North),
+                locale.hcp,
+                north_hcp
+            )
         };
         layer.use_text_builtin(north_hcp_str, font_size, Mm(x), Mm(current_y), text_font);
         current_y -= line_height;
@@ -993,9 +1202,20 @@ impl BiddingSheetsRenderer {
         let south_hcp = board.deal.south.total_hcp();
         let south_length = board.deal.south.length_points();
         let south_hcp_str = if south_length > 0 {
-            format!("South HCP: {}+{}", south_hcp, south_length)
+            format!(
+                "{} {}: {}+{}",
+                locale.direction(Direction::South),
+                locale.hcp,
+                south_hcp,
+                south_length
+            )
         } else {
-            format!("South HCP: {}", south_hcp)
+            format!(
+                "{} {}: {}",
+                locale.direction(Direction::South),
+                locale.hcp,
+                south_hcp
+            )
         };
         layer.use_text_builtin(south_hcp_str, font_size, Mm(x), Mm(current_y), text_font);
         current_y -= line_height;
@@ -1003,10 +1223,10 @@ impl BiddingSheetsRenderer {
         // Contract (if available)
         if let Some(ref auction) = board.auction {
             if let Some(contract) = auction.final_contract() {
-                let prefix = "Contract: ";
-                layer.use_text_builtin(prefix, font_size, Mm(x), Mm(current_y), text_font);
+                let prefix = Locale::label(locale.contract);
+                layer.use_text_builtin(&prefix, font_size, Mm(x), Mm(current_y), text_font);
 
-                let prefix_width = measurer.measure_width_mm(prefix, font_size);
+                let prefix_width = measurer.measure_width_mm(&prefix, font_size);
                 let mut contract_x = x + prefix_width;
 
                 // Level
@@ -1029,11 +1249,7 @@ impl BiddingSheetsRenderer {
                     BidSuit::NoTrump => "NT",
                 };
 
-                if contract.suit.is_red() {
-                    layer.set_fill_color(Color::Rgb(colors.hearts.clone()));
-                } else {
-                    layer.set_fill_color(Color::Rgb(BLACK));
-                }
+                layer.set_fill_color(Color::Rgb(colors.for_bid_suit(&contract.suit)));
 
                 if contract.suit == BidSuit::NoTrump {
                     layer.use_text_builtin(
@@ -1438,12 +1654,13 @@ impl BiddingSheetsRenderer {
         };
 
         // Render normal calls
-        for annotated in auction.calls.iter().take(calls_to_render) {
+        for (index, annotated) in auction.calls.iter().enumerate().take(calls_to_render) {
             let col_x = x + col as f32 * col_width;
 
             self.render_annotated_call(
                 layer,
                 annotated,
+                auction.call_marks(index).as_deref(),
                 col_x,
                 row_y,
                 font_size,
@@ -1464,7 +1681,13 @@ impl BiddingSheetsRenderer {
             // Four passes: show "Pass Out" in dealer's column
             let col_x = x + start_col as f32 * col_width;
             layer.set_fill_color(Color::Rgb(BLACK));
-            layer.use_text_builtin("Pass Out", font_size, Mm(col_x), Mm(row_y), text_font);
+            layer.use_text_builtin(
+                self.settings.passed_out_text(),
+                font_size,
+                Mm(col_x),
+                Mm(row_y),
+                text_font,
+            );
             col = start_col + 1;
             if col >= 4 {
                 col = 0;
@@ -1474,7 +1697,13 @@ impl BiddingSheetsRenderer {
             // Show "All Pass" in the position of the first of the three passes
             let col_x = x + col as f32 * col_width;
             layer.set_fill_color(Color::Rgb(BLACK));
-            layer.use_text_builtin("All Pass", font_size, Mm(col_x), Mm(row_y), text_font);
+            layer.use_text_builtin(
+                self.settings.all_pass_text(),
+                font_size,
+                Mm(col_x),
+                Mm(row_y),
+                text_font,
+            );
             col += 1;
             if col >= 4 {
                 col = 0;
@@ -1517,12 +1746,14 @@ impl BiddingSheetsRenderer {
         (y - row_y, last_line_height)
     }
 
-    /// Render an annotated call (call with optional superscript annotation)
+    /// Render an annotated call (call with its alert and annotation as a
+    /// superscript, from [Auction::call_marks])
     #[allow(clippy::too_many_arguments)]
     fn render_annotated_call(
         &self,
         layer: &mut LayerBuilder,
         annotated: &AnnotatedCall,
+        marks: Option<&str>,
         x: f32,
         y: f32,
         font_size: f32,
@@ -1541,8 +1772,8 @@ impl BiddingSheetsRenderer {
             colors,
         );
 
-        // If there's an annotation, render it as superscript
-        if let Some(ref annotation) = annotated.annotation {
+        // If there's an alert or annotation, render it as superscript
+        if let Some(annotation) = marks {
             let sup_x = x + call_width;
             let sup_y = y + (font_size * SUPERSCRIPT_RISE * 0.352778); // Convert pt to mm
             let sup_size = font_size * SUPERSCRIPT_RATIO;
@@ -1570,18 +1801,21 @@ impl BiddingSheetsRenderer {
         match call {
             Call::Pass => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("Pass", font_size, Mm(x), Mm(y), text_font);
-                measurer.measure_width_mm("Pass", font_size)
+                let text = self.settings.pass_text();
+                layer.use_text_builtin(text, font_size, Mm(x), Mm(y), text_font);
+                measurer.measure_width_mm(text, font_size)
             }
             Call::Double => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("X", font_size, Mm(x), Mm(y), text_font);
-                measurer.measure_width_mm("X", font_size)
+                let text = self.settings.call_text.double.as_deref().unwrap_or("X");
+                layer.use_text_builtin(text, font_size, Mm(x), Mm(y), text_font);
+                measurer.measure_width_mm(text, font_size)
             }
             Call::Redouble => {
                 layer.set_fill_color(Color::Rgb(BLACK));
-                layer.use_text_builtin("XX", font_size, Mm(x), Mm(y), text_font);
-                measurer.measure_width_mm("XX", font_size)
+                let text = self.settings.call_text.redouble.as_deref().unwrap_or("XX");
+                layer.use_text_builtin(text, font_size, Mm(x), Mm(y), text_font);
+                measurer.measure_width_mm(text, font_size)
             }
             Call::Bid {
                 level,
@@ -1603,11 +1837,7 @@ impl BiddingSheetsRenderer {
                     BidSuit::NoTrump => "NT",
                 };
 
-                if suit.is_red() {
-                    layer.set_fill_color(Color::Rgb(colors.hearts.clone()));
-                } else {
-                    layer.set_fill_color(Color::Rgb(BLACK));
-                }
+                layer.set_fill_color(Color::Rgb(colors.for_bid_suit(suit)));
 
                 if *suit == BidSuit::NoTrump {
                     layer.use_text_builtin(
@@ -1644,6 +1874,15 @@ impl BiddingSheetsRenderer {
                     Mm(x + line_width),
                     Mm(y - baseline_offset),
                 );
+
+                // Optionally overlay a form field so the blank can be filled in on screen
+                if self.settings.fillable_forms {
+                    let field_height = font_size * 0.352778;
+                    layer.add_text_field(
+                        TextField::new("call", x, y - baseline_offset, line_width, field_height)
+                            .with_font_size(font_size * 0.8),
+                    );
+                }
                 line_width
             }
         }
@@ -1715,16 +1954,34 @@ impl MixedText {
     }
 }
 
-/// Extension trait for SuitColors to handle BidSuit
-trait SuitColorsExt {
-    fn for_bid_suit(&self, suit: &BidSuit) -> Rgb;
-}
+impl LayoutRenderer for BiddingSheetsRenderer {
+    fn name(&self) -> &str {
+        "bidding sheets"
+    }
 
-impl SuitColorsExt for SuitColors {
-    fn for_bid_suit(&self, suit: &BidSuit) -> Rgb {
-        match suit {
-            BidSuit::Hearts | BidSuit::Diamonds => self.hearts.clone(),
-            BidSuit::Spades | BidSuit::Clubs | BidSuit::NoTrump => self.spades.clone(),
-        }
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        let mut doc = PdfDocument::new("");
+        let fonts = FontManager::for_settings(&mut doc, &self.settings)?;
+        let board_heights = self.measure_board_heights(boards, &fonts);
+        let board_sets = self.group_boards_with_heights(boards, &board_heights);
+        // North, South and the answers; single-sided output has one answers page
+        let pages_per_set = if self.settings.duplex == Some(Duplex::None) {
+            3
+        } else {
+            4
+        };
+        Ok(board_sets.len() * pages_per_set)
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        BiddingSheetsRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
     }
 }
@@ -0,0 +1,398 @@
+//! Rendering long documents in chunks (`--chunk-size`)
+//!
+//! printpdf holds every page's drawing operations until the document is
+//! saved, which for a season's archive of a thousand boards is a lot of
+//! memory. A [ChunkedRenderer] renders the boards a chunk at a time with the
+//! layout's own renderer and adds each chunk's saved PDF to the joined
+//! document as soon as it is drawn (see [PdfMerger] for what that still
+//! keeps in memory). The page count is measured first, so headers and footers
+//! number pages within the whole document and duplex padding happens once;
+//! measuring lays each chunk out, so a chunked render lays every board out
+//! twice, trading time for memory.
+//!
+//! Given a [ChunkCache], chunks whose boards and place in the document are
+//! unchanged since the last render are reused rather than drawn again, so
+//! `--watch` only redraws the pages around an edited board. The cache keeps
+//! every chunk's PDF, so it is meant for watching, not for saving memory.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use crate::config::settings::Chunk;
+use crate::config::Settings;
+use crate::error::{RenderError, RenderWarning};
+use crate::model::Board;
+use crate::render::helpers::merge::PdfMerger;
+use crate::render::helpers::progress::{Progress, ProgressCallback, RenderProgress, RenderStage};
+use crate::render::helpers::report::RenderReport;
+
+use super::renderer::{renderer_for, LayoutRenderer};
+
+/// Renders `settings.layout` a chunk of `settings.chunk_size` boards at a time
+pub struct ChunkedRenderer {
+    settings: Settings,
+    chunk_size: usize,
+    /// The layout's own renderer, for its name and measuring chunks
+    layout: Box<dyn LayoutRenderer>,
+    progress: Progress,
+    callback: Option<ProgressCallback>,
+    cancel: Option<Arc<AtomicBool>>,
+    cache: Option<ChunkCache>,
+}
+
+/// Page counts and PDFs of chunks already rendered, shared between renders
+/// of the same document
+#[derive(Clone, Default)]
+pub struct ChunkCache {
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    pages: HashMap<u64, usize>,
+    pdfs: HashMap<u64, Vec<u8>>,
+    /// Keys looked up since the last sweep
+    used: HashSet<u64>,
+}
+
+impl ChunkCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget chunks not used since the last sweep, e.g. boards since edited
+    pub fn sweep(&self) {
+        let mut entries = self.lock();
+        let used = std::mem::take(&mut entries.used);
+        entries.pages.retain(|key, _| used.contains(key));
+        entries.pdfs.retain(|key, _| used.contains(key));
+    }
+
+    /// Number of chunk PDFs held
+    pub fn len(&self) -> usize {
+        self.lock().pdfs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn pages(&self, key: u64) -> Option<usize> {
+        let mut entries = self.lock();
+        entries.used.insert(key);
+        entries.pages.get(&key).copied()
+    }
+
+    fn pdf(&self, key: u64) -> Option<Vec<u8>> {
+        let mut entries = self.lock();
+        entries.used.insert(key);
+        entries.pdfs.get(&key).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        // A panic mid-insert leaves nothing half-written worth refusing
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Key for a chunk's boards drawn with `settings` (which, for a rendered
+/// chunk, include its place in the document)
+fn chunk_key(settings: &Settings, boards: &[Board]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", settings).hash(&mut hasher);
+    format!("{:?}", boards).hash(&mut hasher);
+    hasher.finish()
+}
+
+impl ChunkedRenderer {
+    pub fn new(settings: Settings) -> Self {
+        let chunk_size = settings.chunk_size.unwrap_or(usize::MAX).max(1);
+        let layout = renderer_for(Settings {
+            chunk_size: None,
+            ..settings.clone()
+        });
+        Self {
+            settings,
+            chunk_size,
+            layout,
+            progress: Progress::default(),
+            callback: None,
+            cancel: None,
+            cache: None,
+        }
+    }
+
+    /// Reuse chunks rendered before with the same cache
+    pub fn with_cache(mut self, cache: ChunkCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Pages a chunk takes, from the cache if it has been measured before
+    fn measure_chunk(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        let Some(ref cache) = self.cache else {
+            return self.layout.measure(boards);
+        };
+        let key = chunk_key(&self.settings, boards);
+        if let Some(pages) = cache.pages(key) {
+            return Ok(pages);
+        }
+        let pages = self.layout.measure(boards)?;
+        cache.lock().pages.insert(key, pages);
+        Ok(pages)
+    }
+
+    /// Settings for one chunk, placed at `chunk` in the whole document
+    fn chunk_settings(&self, chunk: Chunk) -> Settings {
+        let mut settings = self.settings.clone();
+        settings.chunk = Some(chunk);
+        settings
+    }
+
+    /// A renderer for one chunk with its settings
+    fn chunk_renderer(&self, settings: Settings) -> Box<dyn LayoutRenderer> {
+        let mut renderer = renderer_for(settings);
+        if let Some(ref cancel) = self.cancel {
+            renderer.set_cancel(cancel.clone());
+        }
+        renderer
+    }
+}
+
+impl LayoutRenderer for ChunkedRenderer {
+    fn name(&self) -> &str {
+        self.layout.name()
+    }
+
+    /// Each chunk starts a new page, so this can be more than the pages the
+    /// boards take in one pass
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        boards
+            .chunks(self.chunk_size)
+            .map(|chunk| self.measure_chunk(chunk))
+            .sum()
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        let mut chunks: Vec<&[Board]> = boards.chunks(self.chunk_size).collect();
+        if chunks.is_empty() {
+            // No boards still makes a document, as it does in one pass
+            chunks.push(boards);
+        }
+        let page_counts = chunks
+            .iter()
+            .map(|chunk| self.measure_chunk(chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+        let total_pages = page_counts.iter().sum();
+
+        let mut merger = PdfMerger::new();
+        let mut warnings: Vec<RenderWarning> = Vec::new();
+        let mut first_page = 1;
+        let mut done = 0;
+        for (idx, (chunk, pages)) in chunks.iter().zip(page_counts).enumerate() {
+            self.progress.check_cancelled()?;
+            let last = idx + 1 == chunks.len();
+            let settings = self.chunk_settings(Chunk {
+                first_page,
+                total_pages,
+                last,
+            });
+            let key = chunk_key(&settings, chunk);
+            if let Some(pdf) = self.cache.as_ref().and_then(|cache| cache.pdf(key)) {
+                // Warnings were reported when the chunk was first rendered
+                merger.append(&pdf).map_err(RenderError::PdfGeneration)?;
+            } else {
+                let mut renderer = self.chunk_renderer(settings);
+                if let Some(ref callback) = self.callback {
+                    renderer.set_progress(chunk_progress(
+                        callback.clone(),
+                        done,
+                        boards.len(),
+                        last,
+                    ));
+                }
+
+                let report = renderer.render_report(chunk)?;
+                if let Some(ref cache) = self.cache {
+                    cache.lock().pdfs.insert(key, report.pdf.clone());
+                }
+                merger
+                    .append(&report.pdf)
+                    .map_err(RenderError::PdfGeneration)?;
+                for warning in report.warnings {
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            }
+            first_page += pages;
+            done += chunk.len();
+        }
+
+        let pdf = merger.finish().map_err(RenderError::PdfGeneration)?;
+        Ok(RenderReport { pdf, warnings })
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.callback = Some(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel.clone());
+        self.cancel = Some(cancel);
+    }
+}
+
+/// Progress from one chunk, reported as progress through the whole document:
+/// only the last chunk's saving is the document's
+fn chunk_progress(
+    callback: ProgressCallback,
+    done_before: usize,
+    total: usize,
+    last: bool,
+) -> ProgressCallback {
+    Arc::new(move |progress: RenderProgress| {
+        let stage = match progress.stage {
+            RenderStage::Saving if last => RenderStage::Saving,
+            _ => RenderStage::Boards,
+        };
+        let done = if stage == RenderStage::Saving {
+            total
+        } else {
+            done_before + progress.done
+        };
+        callback(RenderProgress { stage, done, total });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Layout;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_chunk_progress() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let seen = reports.clone();
+        let callback: ProgressCallback = Arc::new(move |p| seen.lock().unwrap().push(p));
+
+        let first = chunk_progress(callback.clone(), 0, 10, false);
+        first(RenderProgress {
+            stage: RenderStage::Saving,
+            done: 4,
+            total: 4,
+        });
+        let last = chunk_progress(callback, 4, 10, true);
+        last(RenderProgress {
+            stage: RenderStage::Boards,
+            done: 2,
+            total: 6,
+        });
+        last(RenderProgress {
+            stage: RenderStage::Saving,
+            done: 6,
+            total: 6,
+        });
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            reports
+                .iter()
+                .map(|p| (p.stage, p.done, p.total))
+                .collect::<Vec<_>>(),
+            [
+                (RenderStage::Boards, 4, 10),
+                (RenderStage::Boards, 6, 10),
+                (RenderStage::Saving, 10, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_measure_sums_chunks() {
+        let boards: Vec<Board> = (1..=7)
+            .map(|number| Board {
+                number: Some(number),
+                ..Default::default()
+            })
+            .collect();
+        let mut settings = Settings::for_layout(Layout::DealerSummary);
+        settings.chunk_size = Some(3);
+        // Six boards a page: chunks of 3, 3 and 1 boards take a page each
+        assert_eq!(ChunkedRenderer::new(settings).measure(&boards).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_chunks() {
+        let mut boards: Vec<Board> = (1..=3)
+            .map(|number| Board {
+                number: Some(number),
+                ..Default::default()
+            })
+            .collect();
+        let mut settings = Settings::for_layout(Layout::Analysis);
+        settings.chunk_size = Some(1);
+        let cache = ChunkCache::new();
+        let render = |boards: &[Board]| {
+            ChunkedRenderer::new(settings.clone())
+                .with_cache(cache.clone())
+                .render_report(boards)
+                .unwrap()
+        };
+
+        render(&boards);
+        assert_eq!(cache.len(), 3);
+        cache.sweep();
+
+        // One board edited: its chunk is drawn again, the others reused
+        boards[1].event = Some("Edited".to_string());
+        render(&boards);
+        assert_eq!(cache.len(), 4);
+        cache.sweep();
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_chunks_keep_fields_and_layers() {
+        use crate::model::{Auction, Call, Direction};
+        use lopdf::{Document, Object};
+
+        let boards: Vec<Board> = (1..=3)
+            .map(|number| {
+                let mut auction = Auction::new(Direction::North);
+                auction.add_call(Call::Pass);
+                auction.add_call(Call::Blank);
+                Board {
+                    number: Some(number),
+                    auction: Some(auction),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let mut settings = Settings::for_layout(Layout::Analysis);
+        settings.chunk_size = Some(1);
+        settings.fillable_forms = true;
+        settings.answers_layer = true;
+        let renderer = ChunkedRenderer::new(settings);
+        let pdf = renderer.render_report(&boards).unwrap().pdf;
+
+        let doc = Document::load_mem(&pdf).unwrap();
+        assert_eq!(doc.get_pages().len(), renderer.measure(&boards).unwrap());
+        let catalog = doc.catalog().unwrap();
+        let array = |key: &[u8], inner: &[u8]| {
+            let dict = match catalog.get(key).unwrap() {
+                Object::Reference(id) => doc.get_dictionary(*id).unwrap(),
+                object => object.as_dict().unwrap(),
+            };
+            dict.get(inner).and_then(Object::as_array).unwrap().len()
+        };
+        // One blank call field from each chunk, and one answers layer shared
+        // by all of them
+        assert_eq!(array(b"AcroForm", b"Fields"), 3);
+        assert_eq!(array(b"OCProperties", b"OCGs"), 1);
+    }
+}
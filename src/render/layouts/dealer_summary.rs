@@ -5,26 +5,32 @@
 //!
 //! Based on Bridge Composer's DealerSummary.wsf script.
 
-use printpdf::{Color, Mm, PdfDocument, PdfPage, PdfSaveOptions, Rgb};
+use printpdf::{Color, Mm, PdfDocument, PdfPage};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
-use crate::config::Settings;
+use crate::cli::SuitStyle;
+use crate::config::{Locale, Settings};
 use crate::error::RenderError;
 use crate::model::card::RankExt;
 use crate::model::Board;
 
-use crate::render::helpers::colors::{SuitColors, BLACK};
+use crate::render::components::header_footer::add_header_footer;
+use crate::render::components::title_block::add_title_block;
+use crate::render::helpers::background::{add_image_background, add_pdf_background};
+use crate::render::helpers::colors::{SuitColors, Theme, BLACK};
 use crate::render::helpers::compress::compress_pdf;
-use crate::render::helpers::fonts::FontManager;
+use crate::render::helpers::duplex::{pad_for_duplex, rotate_back_pages};
+use crate::render::helpers::fonts::{save_options, unused_custom_fonts, FontManager};
+use crate::render::helpers::grayscale::{apply_grayscale, grayscale_xobjects};
 use crate::render::helpers::layer::LayerBuilder;
-use crate::render::helpers::text_metrics::get_helvetica_measurer;
+use crate::render::helpers::progress::{Progress, ProgressCallback};
+use crate::render::helpers::report::{RenderReport, WarningLog};
 
-/// Border color for cells
-const BORDER_COLOR: Rgb = Rgb {
-    r: 0.0,
-    g: 0.0,
-    b: 0.0,
-    icc_profile: None,
-};
+use super::renderer::LayoutRenderer;
+use crate::render::helpers::suit_letters::apply_suit_letters;
+use crate::render::helpers::text_metrics::get_helvetica_measurer;
+use crate::render::helpers::timings;
 
 /// Border thickness
 const BORDER_THICKNESS: f32 = 0.5;
@@ -55,52 +61,117 @@ const ROWS: usize = 3;
 /// Dealer summary renderer
 pub struct DealerSummaryRenderer {
     settings: Settings,
+    theme: Theme,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 impl DealerSummaryRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let theme = Theme::from_settings(&settings);
+        Self {
+            settings,
+            theme,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
     }
 
-    /// Generate a PDF with dealer summary (6 boards per page)
+    /// Generate a PDF with dealer summary (6 boards per page), logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF with dealer summary (6 boards per page), with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Dealer Summary");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
 
         // Load fonts
-        let fonts = FontManager::new(&mut doc)?;
+        let fonts = timings::time("fonts", || {
+            FontManager::for_settings(&mut doc, &self.settings)
+        })?;
+        for warning in unused_custom_fonts(&self.settings, &[]) {
+            self.warnings.push(warning);
+        }
 
         let mut pages = Vec::new();
+        let mut page_boards = Vec::new();
 
         // Process boards in groups of 6
+        let mut done = 0;
         for chunk in boards.chunks(BOARDS_PER_PAGE) {
+            self.progress.check_cancelled()?;
             let mut layer = LayerBuilder::new();
             self.render_page(&mut layer, chunk, &fonts);
+            page_boards.push(chunk.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
+            done += chunk.len();
+            self.progress.boards(done, boards.len());
+        }
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        add_header_footer(&mut doc, &mut pages, &page_boards, &self.settings)?;
+        add_title_block(&mut pages, &self.settings);
+        add_image_background(&mut doc, &mut pages, &self.settings)?;
+        pad_for_duplex(&mut pages, &self.settings);
+        if self.settings.grayscale {
+            apply_grayscale(&mut pages);
+        }
+        if self.settings.suit_style == SuitStyle::Letters {
+            apply_suit_letters(&mut pages, fonts.symbol_font());
         }
-
         doc.with_pages(pages);
 
         let mut warnings = Vec::new();
-        let bytes = doc.save(&PdfSaveOptions::default(), &mut warnings);
+        let bytes = timings::time("save", || {
+            doc.save(&save_options(self.settings.font_embedding), &mut warnings)
+        });
+        let bytes = add_pdf_background(bytes, &self.settings)?;
+        let bytes = if self.settings.grayscale {
+            grayscale_xobjects(bytes).map_err(RenderError::PdfGeneration)?
+        } else {
+            bytes
+        };
+        let bytes = rotate_back_pages(bytes, &self.settings)?;
 
         // Compress PDF streams to reduce file size
-        let compressed = compress_pdf(bytes.clone()).unwrap_or(bytes);
-        Ok(compressed)
+        let pdf = timings::time("compress", || {
+            compress_pdf(bytes.clone(), self.settings.compression).unwrap_or(bytes)
+        });
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
     }
 
     /// Render a single page with up to 6 boards
     fn render_page(&self, layer: &mut LayerBuilder, boards: &[Board], fonts: &FontManager) {
-        let colors = SuitColors::new(self.settings.black_color, self.settings.red_color);
+        let colors = SuitColors::from_settings(&self.settings);
 
         // Page layout
         let page_width = self.settings.page_width;
@@ -144,7 +215,7 @@ impl DealerSummaryRenderer {
 
     /// Draw border around a cell
     fn draw_cell_border(&self, layer: &mut LayerBuilder, x: f32, y: f32, width: f32, height: f32) {
-        layer.set_outline_color(Color::Rgb(BORDER_COLOR));
+        layer.set_outline_color(Color::Rgb(self.theme.border.clone()));
         layer.set_outline_thickness(BORDER_THICKNESS);
 
         // Draw rectangle (4 lines)
@@ -188,9 +259,10 @@ impl DealerSummaryRenderer {
         // Dealer (bold)
         if let Some(dealer) = board.dealer {
             // "Dealer: " in regular
+            let dealer_label = Locale::label(self.settings.locale().dealer);
             layer.set_fill_color(Color::Rgb(BLACK));
             layer.use_text_builtin(
-                "Dealer: ",
+                &dealer_label,
                 font_size,
                 Mm(x),
                 Mm(current_y),
@@ -198,11 +270,11 @@ impl DealerSummaryRenderer {
             );
 
             // Measure "Dealer: " width to position the bold name
-            let dealer_label_width = measurer.measure_width_mm("Dealer: ", font_size);
+            let dealer_label_width = measurer.measure_width_mm(&dealer_label, font_size);
 
             // Direction name in bold
             layer.use_text_builtin(
-                format!("{}", dealer),
+                self.settings.locale().direction(dealer),
                 font_size,
                 Mm(x + dealer_label_width),
                 Mm(current_y),
@@ -216,16 +288,17 @@ impl DealerSummaryRenderer {
 
         // Contract with suit symbol
         if let Some(ref contract) = board.contract {
+            let contract_label = Locale::label(self.settings.locale().contract);
             layer.set_fill_color(Color::Rgb(BLACK));
             layer.use_text_builtin(
-                "Contract: ",
+                &contract_label,
                 font_size,
                 Mm(x),
                 Mm(current_y),
                 fonts.sans.regular,
             );
 
-            let contract_label_width = measurer.measure_width_mm("Contract: ", font_size);
+            let contract_label_width = measurer.measure_width_mm(&contract_label, font_size);
             let mut contract_x = x + contract_label_width;
 
             // Level
@@ -240,12 +313,7 @@ impl DealerSummaryRenderer {
             contract_x += measurer.measure_width_mm(&level_str, font_size);
 
             // Suit symbol (colored) - use symbol font for suits, builtin for NT
-            let suit_color = if contract.suit.is_red() {
-                colors.hearts.clone()
-            } else {
-                BLACK
-            };
-            layer.set_fill_color(Color::Rgb(suit_color));
+            layer.set_fill_color(Color::Rgb(colors.for_bid_suit(&contract.suit)));
             let suit_str = contract.suit.symbol();
             if contract.suit == crate::model::BidSuit::NoTrump {
                 layer.use_text_builtin(
@@ -306,25 +374,21 @@ impl DealerSummaryRenderer {
         if let Some(ref play) = board.play {
             if let Some(first_trick) = play.tricks.first() {
                 if let Some(lead_card) = first_trick.cards[0] {
+                    let lead_label = Locale::label(self.settings.locale().lead);
                     layer.set_fill_color(Color::Rgb(BLACK));
                     layer.use_text_builtin(
-                        "Lead: ",
+                        &lead_label,
                         font_size,
                         Mm(x),
                         Mm(current_y),
                         fonts.sans.regular,
                     );
 
-                    let lead_label_width = measurer.measure_width_mm("Lead: ", font_size);
+                    let lead_label_width = measurer.measure_width_mm(&lead_label, font_size);
                     let mut lead_x = x + lead_label_width;
 
                     // Suit symbol (colored) - use symbol font
-                    let suit_color = if lead_card.suit.is_red() {
-                        colors.hearts.clone()
-                    } else {
-                        BLACK
-                    };
-                    layer.set_fill_color(Color::Rgb(suit_color));
+                    layer.set_fill_color(Color::Rgb(colors.for_suit(&lead_card.suit)));
                     let suit_str = lead_card.suit.symbol().to_string();
                     layer.use_text(
                         &suit_str,
@@ -338,7 +402,10 @@ impl DealerSummaryRenderer {
                     // Rank
                     layer.set_fill_color(Color::Rgb(BLACK));
                     layer.use_text_builtin(
-                        lead_card.rank.display_str().to_string(),
+                        lead_card
+                            .rank
+                            .display_str_with(self.settings.ten_as_t)
+                            .to_string(),
                         font_size,
                         Mm(lead_x),
                         Mm(current_y),
@@ -349,3 +416,25 @@ impl DealerSummaryRenderer {
         }
     }
 }
+
+impl LayoutRenderer for DealerSummaryRenderer {
+    fn name(&self) -> &str {
+        "dealer summary"
+    }
+
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        Ok(boards.len().div_ceil(BOARDS_PER_PAGE))
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        DealerSummaryRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
+    }
+}
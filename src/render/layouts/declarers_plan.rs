@@ -6,32 +6,39 @@
 //! - **2-up**: Two deals per page, each rotated 90° CW for landscape reading
 //! - **4-up**: Four deals per page in a 2x2 grid (original layout)
 
-use printpdf::{Color, CurTransMat, Mm, PdfDocument, PdfPage, PdfSaveOptions, Rgb};
+use printpdf::{Color, CurTransMat, FontId, Mm, PdfDocument, PdfPage, Rgb};
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
+use crate::cli::SuitStyle;
 use crate::config::Settings;
 use crate::error::RenderError;
 use crate::model::analysis::{find_length_winners, find_promotable_winners, find_sure_winners};
-use crate::model::{BidSuit, Board, Card, Deal, Direction, Hand};
+use crate::model::{BidSuit, Board, Card, Deal, Direction, Hand, SUITS_DISPLAY_ORDER};
 
+use crate::render::components::header_footer::add_header_footer;
+use crate::render::components::title_block::add_title_block;
 use crate::render::components::DeclarersPlanSmallRenderer;
+use crate::render::helpers::background::{add_image_background, add_pdf_background};
 use crate::render::helpers::card_assets::CardAssets;
-use crate::render::helpers::colors::{SuitColors, BLUE, GREEN, RED};
+use crate::render::helpers::colors::{SuitColors, Theme, BLUE, GREEN, RED};
 use crate::render::helpers::compress::compress_pdf;
-use crate::render::helpers::fonts::FontManager;
-use crate::render::helpers::layer::LayerBuilder;
+use crate::render::helpers::duplex::{pad_for_duplex, rotate_back_pages};
+use crate::render::helpers::fonts::{save_options, unused_custom_fonts, FontManager};
+use crate::render::helpers::grayscale::{apply_grayscale, grayscale_xobjects};
+use crate::render::helpers::layer::{add_answers_layer, LayerBuilder};
+use crate::render::helpers::links::{add_links, Link, LinkTarget};
+use crate::render::helpers::progress::{Progress, ProgressCallback};
+use crate::render::helpers::report::{RenderReport, WarningLog};
+
+use super::renderer::LayoutRenderer;
+use crate::render::helpers::suit_letters::apply_suit_letters;
+use crate::render::helpers::timings;
 
 /// Separator line thickness
 const SEPARATOR_THICKNESS: f32 = 2.0;
 
-/// Separator line color (dark gray)
-const SEPARATOR_COLOR: Rgb = Rgb {
-    r: 0.3,
-    g: 0.3,
-    b: 0.3,
-    icc_profile: None,
-};
-
 /// Padding inside each panel
 const PANEL_PADDING: f32 = 5.0;
 
@@ -109,6 +116,25 @@ fn rotate_deal_for_declarer(deal: &Deal, declarer: Direction) -> (Hand, Hand) {
     }
 }
 
+/// Load the faces of the dummy and declarer hands the boards show
+fn load_card_assets(doc: &mut PdfDocument, boards: &[Board]) -> Result<CardAssets, RenderError> {
+    let mut cards = Vec::new();
+    for board in boards {
+        let prep = prepare_board(board);
+        for hand in [&prep.dummy_hand, &prep.declarer_hand] {
+            for suit in SUITS_DISPLAY_ORDER {
+                cards.extend(
+                    hand.holding(suit)
+                        .card_ranks(&[])
+                        .into_iter()
+                        .map(|rank| (suit, rank)),
+                );
+            }
+        }
+    }
+    CardAssets::load_cards(doc, cards).map_err(|e| RenderError::CardAsset(e.to_string()))
+}
+
 /// Baseline card scale (4-up) — layout_scale is relative to this
 const BASELINE_CARD_SCALE: f32 = SCALE_4UP;
 
@@ -119,7 +145,7 @@ fn make_renderer<'a>(
     settings: &Settings,
     card_scale: f32,
 ) -> DeclarersPlanSmallRenderer<'a> {
-    let colors = SuitColors::new(settings.black_color, settings.red_color);
+    let colors = SuitColors::from_settings(settings);
     let layout_scale = card_scale / BASELINE_CARD_SCALE;
     DeclarersPlanSmallRenderer::new(
         card_assets,
@@ -131,6 +157,8 @@ fn make_renderer<'a>(
     .card_scale(card_scale)
     .layout_scale(layout_scale)
     .show_bounds(settings.debug_boxes)
+    .ten_as_t(settings.ten_as_t)
+    .theme(Theme::from_settings(settings))
 }
 
 /// Compute cards to circle for a board based on CLI flags.
@@ -202,7 +230,7 @@ fn render_prepared(
 
 /// Draw a horizontal separator line across the content area
 fn draw_horizontal_separator(layer: &mut LayerBuilder, settings: &Settings, y: f32) {
-    layer.set_outline_color(Color::Rgb(SEPARATOR_COLOR));
+    layer.set_outline_color(Color::Rgb(Theme::from_settings(settings).heavy_separator));
     layer.set_outline_thickness(SEPARATOR_THICKNESS);
     layer.add_line(
         Mm(settings.margin_left),
@@ -212,13 +240,42 @@ fn draw_horizontal_separator(layer: &mut LayerBuilder, settings: &Settings, y: f
     );
 }
 
-/// Generate the final PDF bytes from a document
-fn finalize_pdf(doc: PdfDocument, pages: Vec<PdfPage>) -> Result<Vec<u8>, RenderError> {
+/// Generate the final PDF bytes from a document, with each page's links
+fn finalize_pdf(
+    doc: PdfDocument,
+    mut pages: Vec<PdfPage>,
+    page_links: &[Vec<Link>],
+    page_boards: &[Vec<&Board>],
+    settings: &Settings,
+    symbol_font: &FontId,
+) -> Result<Vec<u8>, RenderError> {
     let mut doc = doc;
+    add_header_footer(&mut doc, &mut pages, page_boards, settings)?;
+    add_title_block(&mut pages, settings);
+    add_image_background(&mut doc, &mut pages, settings)?;
+    pad_for_duplex(&mut pages, settings);
+    if settings.grayscale {
+        apply_grayscale(&mut pages);
+    }
+    if settings.suit_style == SuitStyle::Letters {
+        apply_suit_letters(&mut pages, symbol_font);
+    }
     doc.with_pages(pages);
     let mut warnings = Vec::new();
-    let bytes = doc.save(&PdfSaveOptions::default(), &mut warnings);
-    let compressed = compress_pdf(bytes.clone()).unwrap_or(bytes);
+    let bytes = timings::time("save", || {
+        doc.save(&save_options(settings.font_embedding), &mut warnings)
+    });
+    let bytes = add_pdf_background(bytes, settings)?;
+    let bytes = if settings.grayscale {
+        grayscale_xobjects(bytes).map_err(RenderError::PdfGeneration)?
+    } else {
+        bytes
+    };
+    let bytes = rotate_back_pages(bytes, settings)?;
+    let bytes = add_links(bytes.clone(), page_links).unwrap_or(bytes);
+    let compressed = timings::time("compress", || {
+        compress_pdf(bytes.clone(), settings.compression).unwrap_or(bytes)
+    });
     Ok(compressed)
 }
 
@@ -232,38 +289,71 @@ const SCALE_1UP: f32 = 0.55;
 /// Declarer's plan 1-up renderer — one deal per page
 pub struct DeclarersPlan1UpRenderer {
     settings: Settings,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 impl DeclarersPlan1UpRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
     }
 
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
+    }
+
+    /// Generate a PDF with one declarer's plan per page, logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF with one declarer's plan per page, with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Declarer's Plan");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
-        let fonts = FontManager::new(&mut doc)?;
-        let card_assets =
-            CardAssets::load(&mut doc).map_err(|e| RenderError::CardAsset(e.to_string()))?;
+        let fonts = FontManager::for_settings(&mut doc, &self.settings)?;
+        for warning in unused_custom_fonts(&self.settings, &[]) {
+            self.warnings.push(warning);
+        }
+        let card_assets = load_card_assets(&mut doc, boards)?;
+        let answers_layer = add_answers_layer(&mut doc, self.settings.answers_layer);
 
         let mut pages = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards = Vec::new();
 
         for board in boards {
+            self.progress.check_cancelled()?;
             let prep = prepare_board(board);
+            let settings = self.settings.for_board(board);
             let renderer = renderer_for_board(
                 &card_assets,
                 &fonts,
-                &self.settings,
+                &settings,
                 SCALE_1UP,
                 &prep.dummy_hand,
                 &prep.declarer_hand,
             );
-            let mut layer = LayerBuilder::new();
+            let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
 
             // Center the panel on the page
             let content_width =
@@ -281,14 +371,30 @@ impl DeclarersPlan1UpRenderer {
 
             render_prepared(&renderer, &mut layer, &prep, (Mm(origin_x), Mm(origin_y)));
 
+            page_links.push(layer.take_links());
+            page_boards.push(vec![board]);
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
+            self.progress.boards(pages.len(), boards.len());
         }
-
-        finalize_pdf(doc, pages)
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        let pdf = finalize_pdf(
+            doc,
+            pages,
+            &page_links,
+            &page_boards,
+            &self.settings,
+            fonts.symbol_font(),
+        )?;
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
     }
 }
 
@@ -307,24 +413,53 @@ const SCALE_2UP: f32 = 0.45;
 /// space for the tall declarer's plan layout.
 pub struct DeclarersPlan2UpRenderer {
     settings: Settings,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 impl DeclarersPlan2UpRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
     }
 
+    /// Generate a PDF with two declarer's plans per page, logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF with two declarer's plans per page, with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Declarer's Plan");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
-        let fonts = FontManager::new(&mut doc)?;
-        let card_assets =
-            CardAssets::load(&mut doc).map_err(|e| RenderError::CardAsset(e.to_string()))?;
+        let fonts = FontManager::for_settings(&mut doc, &self.settings)?;
+        for warning in unused_custom_fonts(&self.settings, &[]) {
+            self.warnings.push(warning);
+        }
+        let card_assets = load_card_assets(&mut doc, boards)?;
+        let answers_layer = add_answers_layer(&mut doc, self.settings.answers_layer);
 
         let content_width =
             self.settings.page_width - self.settings.margin_left - self.settings.margin_right;
@@ -341,21 +476,27 @@ impl DeclarersPlan2UpRenderer {
         let bottom_slot_cy = center_y - half_height / 2.0 - center_inset / 2.0;
 
         let mut pages = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards = Vec::new();
 
+        let mut done = 0;
         for chunk in boards.chunks(2) {
-            let mut layer = LayerBuilder::new();
+            self.progress.check_cancelled()?;
+            let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
 
             // Draw horizontal separator between panels
             draw_horizontal_separator(&mut layer, &self.settings, center_y);
+            let mut links = Vec::new();
 
             let slot_centers = [(slot_cx, top_slot_cy), (slot_cx, bottom_slot_cy)];
 
             for (i, board) in chunk.iter().enumerate() {
                 let prep = prepare_board(board);
+                let settings = self.settings.for_board(board);
                 let renderer = renderer_for_board(
                     &card_assets,
                     &fonts,
-                    &self.settings,
+                    &settings,
                     SCALE_2UP,
                     &prep.dummy_hand,
                     &prep.declarer_hand,
@@ -404,16 +545,55 @@ impl DeclarersPlan2UpRenderer {
                 layer.set_transform(CurTransMat::Raw([0.0, -1.0, 1.0, 0.0, e_pt, f_pt]));
                 render_prepared(&renderer, &mut layer, &prep, (Mm(panel_ox), Mm(panel_oy)));
                 layer.restore_graphics_state();
+                links.extend(
+                    layer
+                        .take_links()
+                        .into_iter()
+                        .map(|link| rotate_link(link, e_mm, f_mm)),
+                );
             }
 
+            page_links.push(links);
+            page_boards.push(chunk.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
+            done += chunk.len();
+            self.progress.boards(done, boards.len());
         }
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        let pdf = finalize_pdf(
+            doc,
+            pages,
+            &page_links,
+            &page_boards,
+            &self.settings,
+            fonts.symbol_font(),
+        )?;
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
+    }
+}
 
-        finalize_pdf(doc, pages)
+/// Move a link drawn on an upright 2-up panel to where the panel lands once
+/// rotated 90° CW and translated by (e, f) mm: (x, y) → (y + e, f - x)
+fn rotate_link(link: Link, e: f32, f: f32) -> Link {
+    let target = match link.target {
+        LinkTarget::Position { x, y } => LinkTarget::Position { x: y + e, y: f - x },
+        uri => uri,
+    };
+    Link {
+        x: link.y + e,
+        y: f - link.x - link.width,
+        width: link.height,
+        height: link.width,
+        target,
     }
 }
 
@@ -427,39 +607,88 @@ const SCALE_4UP: f32 = 0.35;
 /// Declarer's plan 4-up renderer — four deals per page in a 2x2 grid
 pub struct DeclarersPlanRenderer {
     settings: Settings,
+    progress: Progress,
+    warnings: WarningLog,
 }
 
 impl DeclarersPlanRenderer {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            progress: Progress::default(),
+            warnings: WarningLog::default(),
+        }
+    }
+
+    /// Report progress to `callback` while rendering
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress.set_cancel(cancel);
+        self
     }
 
-    /// Generate a PDF with declarer's plan practice sheets (4 per page)
+    /// Generate a PDF with declarer's plan practice sheets (4 per page), logging any warnings
     pub fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Generate a PDF with declarer's plan practice sheets (4 per page), with the warnings raised along the way
+    pub fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        self.warnings.take();
         let title = boards
             .first()
             .and_then(|b| b.event.as_ref())
             .map(|s| s.as_str())
             .unwrap_or("Declarer's Plan Practice");
+        self.progress.boards(0, boards.len());
 
         let mut doc = PdfDocument::new(title);
-        let fonts = FontManager::new(&mut doc)?;
-        let card_assets =
-            CardAssets::load(&mut doc).map_err(|e| RenderError::CardAsset(e.to_string()))?;
+        let fonts = FontManager::for_settings(&mut doc, &self.settings)?;
+        for warning in unused_custom_fonts(&self.settings, &[]) {
+            self.warnings.push(warning);
+        }
+        let card_assets = load_card_assets(&mut doc, boards)?;
+        let answers_layer = add_answers_layer(&mut doc, self.settings.answers_layer);
 
         let mut pages = Vec::new();
+        let mut page_links = Vec::new();
+        let mut page_boards = Vec::new();
 
+        let mut done = 0;
         for chunk in boards.chunks(4) {
-            let mut layer = LayerBuilder::new();
+            self.progress.check_cancelled()?;
+            let mut layer = LayerBuilder::new().with_answers_layer(answers_layer.clone());
             self.render_page(&mut layer, chunk, &fonts, &card_assets);
+            page_links.push(layer.take_links());
+            page_boards.push(chunk.iter().collect());
             pages.push(PdfPage::new(
                 Mm(self.settings.page_width),
                 Mm(self.settings.page_height),
                 layer.into_ops(),
             ));
+            done += chunk.len();
+            self.progress.boards(done, boards.len());
         }
-
-        finalize_pdf(doc, pages)
+        self.progress.check_cancelled()?;
+        self.progress.saving(boards.len());
+
+        let pdf = finalize_pdf(
+            doc,
+            pages,
+            &page_links,
+            &page_boards,
+            &self.settings,
+            fonts.symbol_font(),
+        )?;
+        Ok(RenderReport {
+            pdf,
+            warnings: self.warnings.take(),
+        })
     }
 
     /// Render a single page with up to 4 deals
@@ -504,10 +733,11 @@ impl DeclarersPlanRenderer {
 
             let (x, y) = positions[i];
             let prep = prepare_board(board);
+            let settings = self.settings.for_board(board);
             let renderer = renderer_for_board(
                 card_assets,
                 fonts,
-                &self.settings,
+                &settings,
                 SCALE_4UP,
                 &prep.dummy_hand,
                 &prep.declarer_hand,
@@ -525,7 +755,9 @@ impl DeclarersPlanRenderer {
         let page_width = self.settings.page_width;
         let page_height = self.settings.page_height;
 
-        layer.set_outline_color(Color::Rgb(SEPARATOR_COLOR));
+        layer.set_outline_color(Color::Rgb(
+            Theme::from_settings(&self.settings).heavy_separator,
+        ));
         layer.set_outline_thickness(SEPARATOR_THICKNESS);
 
         // Vertical line
@@ -545,3 +777,115 @@ impl DeclarersPlanRenderer {
         );
     }
 }
+
+impl LayoutRenderer for DeclarersPlan1UpRenderer {
+    fn name(&self) -> &str {
+        "declarer's plan 1-up"
+    }
+
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        Ok(boards.len())
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        DeclarersPlan1UpRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
+    }
+}
+
+impl LayoutRenderer for DeclarersPlan2UpRenderer {
+    fn name(&self) -> &str {
+        "declarer's plan 2-up"
+    }
+
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        Ok(boards.len().div_ceil(2))
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        DeclarersPlan2UpRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
+    }
+}
+
+impl LayoutRenderer for DeclarersPlanRenderer {
+    fn name(&self) -> &str {
+        "declarer's plan"
+    }
+
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError> {
+        Ok(boards.len().div_ceil(4))
+    }
+
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError> {
+        DeclarersPlanRenderer::render_report(self, boards)
+    }
+
+    fn set_progress(&mut self, callback: ProgressCallback) {
+        self.progress.set_callback(callback);
+    }
+
+    fn set_cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.progress.set_cancel(cancel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Rank, Suit};
+    use crate::parser::parse_pbn;
+
+    #[test]
+    fn test_spot_cards_load_low_card_faces() {
+        let board = parse_pbn(
+            "[Event \"\"]\n[Board \"1\"]\n[Deal \"N:AKxx.Q.. - T9.A.. -\"]\n\
+             [Declarer \"S\"]\n[Contract \"3NT\"]\n",
+        )
+        .unwrap()
+        .boards
+        .remove(0);
+        let mut doc = PdfDocument::new("Test");
+        let assets = load_card_assets(&mut doc, &[board]).unwrap();
+        assert!(assets.contains(Suit::Spades, Rank::Three));
+        assert!(assets.contains(Suit::Spades, Rank::Two));
+        assert_eq!(assets.len(), 8);
+    }
+
+    #[test]
+    fn test_rotate_link() {
+        let link = Link {
+            x: 10.0,
+            y: 20.0,
+            width: 30.0,
+            height: 5.0,
+            target: LinkTarget::Position { x: 1.0, y: 2.0 },
+        };
+        // The panel's width runs down the page once rotated
+        let rotated = rotate_link(link, 100.0, 200.0);
+        assert_eq!(
+            rotated,
+            Link {
+                x: 120.0,
+                y: 160.0,
+                width: 5.0,
+                height: 30.0,
+                target: LinkTarget::Position { x: 102.0, y: 199.0 },
+            }
+        );
+    }
+}
@@ -2,12 +2,16 @@
 
 pub mod analysis;
 pub mod bidding_sheets;
+pub mod chunked;
 pub mod dealer_summary;
 pub mod declarers_plan;
+pub mod renderer;
 
-pub use analysis::generate_pdf;
+pub use analysis::{generate_pdf, DocumentRenderer};
 pub use bidding_sheets::BiddingSheetsRenderer;
+pub use chunked::{ChunkCache, ChunkedRenderer};
 pub use dealer_summary::DealerSummaryRenderer;
 pub use declarers_plan::{
     DeclarersPlan1UpRenderer, DeclarersPlan2UpRenderer, DeclarersPlanRenderer,
 };
+pub use renderer::{renderer_for, LayoutRenderer};
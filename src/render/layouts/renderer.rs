@@ -0,0 +1,88 @@
+//! The interface every layout implements
+//!
+//! The builtin layouts are chosen by [Layout]; other crates can add their
+//! own by implementing [LayoutRenderer] and handing it to
+//! `render_boards_with_layout`.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use crate::cli::Layout;
+use crate::config::Settings;
+use crate::error::RenderError;
+use crate::model::Board;
+use crate::render::helpers::{ProgressCallback, RenderReport};
+
+use super::{
+    BiddingSheetsRenderer, ChunkedRenderer, DealerSummaryRenderer, DeclarersPlan1UpRenderer,
+    DeclarersPlan2UpRenderer, DeclarersPlanRenderer, DocumentRenderer,
+};
+
+/// A page layout: lays boards out on pages and renders them to a PDF
+///
+/// Implementations are built from [Settings] and draw whatever boards they
+/// are given. Progress reports and cancellation are optional; a layout that
+/// doesn't support them can leave the default no-op methods.
+pub trait LayoutRenderer {
+    /// Name for messages, e.g. "bidding sheets"
+    fn name(&self) -> &str;
+
+    /// How many pages `boards` take, before any blank pages are added for
+    /// duplex printing
+    fn measure(&self, boards: &[Board]) -> Result<usize, RenderError>;
+
+    /// Render `boards` to a PDF, with the warnings raised along the way
+    fn render_report(&self, boards: &[Board]) -> Result<RenderReport, RenderError>;
+
+    /// Render `boards` to a PDF, logging any warnings
+    fn render(&self, boards: &[Board]) -> Result<Vec<u8>, RenderError> {
+        self.render_report(boards).map(RenderReport::into_pdf)
+    }
+
+    /// Report progress to `callback` while rendering
+    fn set_progress(&mut self, _callback: ProgressCallback) {}
+
+    /// Stop rendering with `RenderError::Cancelled` once `cancel` is set
+    fn set_cancel(&mut self, _cancel: Arc<AtomicBool>) {}
+}
+
+/// The builtin renderer for `settings.layout`, rendering a chunk of boards at
+/// a time when `settings.chunk_size` is set
+pub fn renderer_for(settings: Settings) -> Box<dyn LayoutRenderer> {
+    if settings.chunk_size.is_some() && settings.chunk.is_none() {
+        return Box::new(ChunkedRenderer::new(settings));
+    }
+    match settings.layout {
+        Layout::Analysis => Box::new(DocumentRenderer::new(settings)),
+        Layout::BiddingSheets => Box::new(BiddingSheetsRenderer::new(settings)),
+        Layout::DeclarersPlan1up => Box::new(DeclarersPlan1UpRenderer::new(settings)),
+        Layout::DeclarersPlan2up => Box::new(DeclarersPlan2UpRenderer::new(settings)),
+        Layout::DeclarersPlan => Box::new(DeclarersPlanRenderer::new(settings)),
+        Layout::DealerSummary => Box::new(DealerSummaryRenderer::new(settings)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards(count: usize) -> Vec<Board> {
+        (1..=count)
+            .map(|number| Board {
+                number: Some(number as u32),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_renderer_for_layout() {
+        let renderer = renderer_for(Settings::for_layout(Layout::DealerSummary));
+        assert_eq!(renderer.name(), "dealer summary");
+        assert_eq!(renderer.measure(&boards(7)).unwrap(), 2);
+
+        let renderer = renderer_for(Settings::for_layout(Layout::DeclarersPlan2up));
+        assert_eq!(renderer.name(), "declarer's plan 2-up");
+        assert_eq!(renderer.measure(&boards(3)).unwrap(), 2);
+    }
+}
@@ -5,8 +5,12 @@ pub mod helpers;
 pub mod layouts;
 
 // Re-export commonly used items for convenience
-pub use helpers::{get_times_measurer, BuiltinFontMeasurer, FontMetrics, LayerBuilder};
+pub use helpers::{
+    get_times_measurer, BuiltinFontMeasurer, FontMetrics, LayerBuilder, ProgressCallback,
+    RenderProgress, RenderReport, RenderStage,
+};
 pub use layouts::{
-    generate_pdf, BiddingSheetsRenderer, DealerSummaryRenderer, DeclarersPlan1UpRenderer,
-    DeclarersPlan2UpRenderer, DeclarersPlanRenderer,
+    generate_pdf, renderer_for, BiddingSheetsRenderer, ChunkCache, ChunkedRenderer,
+    DealerSummaryRenderer, DeclarersPlan1UpRenderer, DeclarersPlan2UpRenderer,
+    DeclarersPlanRenderer, DocumentRenderer, LayoutRenderer,
 };
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use pbn_to_pdf::config::Settings;
 use pbn_to_pdf::model::analysis::{
@@ -13,10 +15,15 @@ use pbn_to_pdf::render::components::{
     DeclarersPlanSmallRenderer, DummyRenderer, FanRenderer, LosersTableRenderer,
     WinnersTableRenderer,
 };
-use pbn_to_pdf::render::generate_pdf;
 use pbn_to_pdf::render::helpers::colors::{SuitColors, BLUE, RED};
 use pbn_to_pdf::render::helpers::FontManager;
 use pbn_to_pdf::render::helpers::{CardAssets, LayerBuilder};
+use pbn_to_pdf::render::{generate_pdf, DocumentRenderer};
+use pbn_to_pdf::{
+    render_boards_cancellable, render_boards_report, render_boards_with_overrides,
+    render_boards_with_progress, render_layouts, BoardOverrides, BoardSettings, Layout,
+    RenderError, RenderOptions, RenderProgress, RenderStage, RenderWarning,
+};
 use printpdf::{Mm, PdfDocument, PdfPage, PdfSaveOptions, PdfWarnMsg};
 
 fn fixtures_path() -> PathBuf {
@@ -66,6 +73,165 @@ fn test_two_column_layout() {
     println!("Two-column layout test PDF written to: {:?}", output_file);
 }
 
+#[test]
+fn test_render_progress_and_cancel() {
+    let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length practice deals.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let boards = parse_pbn(&content).expect("Failed to parse PBN").boards;
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = reports.clone();
+    render_boards_with_progress(
+        &boards,
+        &[],
+        Layout::Analysis,
+        RenderOptions::default(),
+        move |progress| sink.lock().unwrap().push(progress),
+    )
+    .expect("Failed to generate PDF");
+    let reports = reports.lock().unwrap();
+    assert_eq!(
+        reports.first(),
+        Some(&RenderProgress {
+            stage: RenderStage::Boards,
+            done: 0,
+            total: 4
+        })
+    );
+    assert_eq!(
+        reports.last(),
+        Some(&RenderProgress {
+            stage: RenderStage::Saving,
+            done: 4,
+            total: 4
+        })
+    );
+
+    // Cancel as soon as the first board is laid out
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = cancel.clone();
+    let result = render_boards_cancellable(
+        &boards,
+        &[],
+        Layout::Analysis,
+        RenderOptions::default(),
+        move |progress| {
+            if progress.done > 0 {
+                flag.store(true, Ordering::Relaxed);
+            }
+        },
+        cancel,
+    );
+    assert!(matches!(result, Err(RenderError::Cancelled)));
+}
+
+#[test]
+fn test_render_report_warnings() {
+    let content = "[Event \"\"]\n[Board \"7\"]\n[Dealer \"N\"]\n[Vulnerable \"None\"]\n\
+        [Deal \"N:AKQ.JT9.876.5432 JT9.AKQ.5432.876 876.5432.AKQ.JT9 5432.876.JT9.AKQ\"]\n\
+        {\u{4F60}\u{597D}}\n";
+    let boards = parse_pbn(content).expect("Failed to parse PBN").boards;
+
+    let report = render_boards_report(
+        &boards,
+        &[],
+        Layout::Analysis,
+        RenderOptions::default(),
+        |_| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .expect("Failed to generate PDF");
+    assert!(report.pdf.starts_with(b"%PDF"));
+    assert_eq!(
+        report.warnings,
+        [RenderWarning::MissingGlyphs("7".to_string())]
+    );
+}
+
+#[test]
+fn test_measure_boards() {
+    // Two columns, from %BoardsPerPage fit,2
+    let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length exercises.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let renderer = DocumentRenderer::new(settings.clone());
+
+    let width = renderer.column_width();
+    assert!(width < settings.content_width() / 2.0);
+    for board in &pbn_file.boards {
+        let height = renderer.board_height(board, width);
+        assert!(height >= renderer.auction_height(board, width));
+        assert!(height >= renderer.commentary_height(board, width));
+        assert_eq!(
+            renderer.board_fits(board),
+            height <= renderer.column_height()
+        );
+    }
+
+    // Narrower columns wrap the longest commentary onto more lines
+    let board = pbn_file
+        .boards
+        .iter()
+        .max_by(|a, b| {
+            renderer
+                .commentary_height(a, width)
+                .total_cmp(&renderer.commentary_height(b, width))
+        })
+        .expect("Expected boards");
+    assert!(
+        renderer.commentary_height(board, width / 2.0) > renderer.commentary_height(board, width)
+    );
+}
+
+#[test]
+fn test_render_with_board_overrides() {
+    let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length practice deals.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let boards = parse_pbn(&content).expect("Failed to parse PBN").boards;
+
+    let overrides = BoardOverrides::new().with(
+        "2",
+        BoardSettings {
+            show_bidding: Some(false),
+            show_commentary: Some(false),
+            board_label_format: Some("Quiz %".to_string()),
+            ..Default::default()
+        },
+    );
+    for layout in [Layout::Analysis, Layout::DeclarersPlan] {
+        let pdf = render_boards_with_overrides(
+            &boards,
+            &[],
+            layout,
+            RenderOptions::default(),
+            overrides.clone(),
+        )
+        .expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
+
+#[test]
+fn test_render_layouts() {
+    let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length practice deals.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let boards = parse_pbn(&content).expect("Failed to parse PBN").boards;
+
+    let layouts = [
+        Layout::Analysis,
+        Layout::BiddingSheets,
+        Layout::DeclarersPlan,
+    ];
+    let pdfs = render_layouts(&boards, &[], &layouts, RenderOptions::default())
+        .expect("Failed to generate PDFs");
+    assert_eq!(pdfs.len(), layouts.len());
+    for ((layout, pdf), expected) in pdfs.iter().zip(layouts) {
+        assert_eq!(*layout, expected);
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
+
 #[test]
 fn test_parse_abs2_practice_deals() {
     let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length practice deals.pbn");
@@ -1730,3 +1896,792 @@ fn test_colored_span_in_commentary() {
     fs::write(&output_file, &pdf_bytes).expect("Failed to write test PDF");
     println!("Colored span test PDF written to: {:?}", output_file);
 }
+
+#[test]
+fn test_fillable_blanks_add_acroform() {
+    let pbn_path = fixtures_path().join("ABS4-1 Stayman exercises.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let contains =
+        |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).any(|w| w == needle);
+
+    // Without the flag the output has no form
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let plain = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(!contains(&plain, b"/AcroForm"));
+
+    // With the flag each blank call gets a text field
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.fillable_forms = true;
+    let fillable = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(fillable.starts_with(b"%PDF"));
+    assert!(contains(&fillable, b"/AcroForm"));
+    assert!(contains(&fillable, b"/Widget"));
+}
+
+#[test]
+fn test_answers_layer_adds_optional_content() {
+    use pbn_to_pdf::cli::Layout;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let contains =
+        |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).any(|w| w == needle);
+
+    for layout in [Layout::Analysis, Layout::BiddingSheets] {
+        let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+        settings.layout = layout;
+        settings.answers_layer = true;
+        let pdf_bytes = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf_bytes.starts_with(b"%PDF"), "{:?}", layout);
+        assert!(contains(&pdf_bytes, b"/OCProperties"), "{:?}", layout);
+    }
+}
+
+#[test]
+fn test_bidding_sheet_notes_keep_links() {
+    use pbn_to_pdf::cli::Layout;
+
+    let pbn = r#"[Event "Links"]
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ2.J32.T98.432 J98.AKQ.J32.AKQJ T765.T98.AKQ.T98 43.7654.7654.765"]
+[Auction "N"]
+1NT Pass 2C Pass
+2D Pass 3NT AP
+{N: See https://example.com/stayman for the convention.}
+"#;
+    let pbn_file = parse_pbn(pbn).expect("Failed to parse PBN");
+
+    let contains =
+        |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).any(|w| w == needle);
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.layout = Layout::BiddingSheets;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(contains(&pdf, b"/URI"));
+    assert!(contains(&pdf, b"https://example.com/stayman"));
+}
+
+#[test]
+fn test_header_footer_adds_page_text() {
+    use pbn_to_pdf::config::settings::PageFields;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let plain = Settings::default().with_metadata(&pbn_file.metadata);
+    let mut with_furniture = plain.clone();
+    with_furniture.header = PageFields::parse("{event}||Boards {boards}");
+    with_furniture.footer = PageFields::parse("|Page {page} of {pages}|");
+
+    let plain_pdf = generate_pdf(&pbn_file.boards, &plain).expect("Failed to generate PDF");
+    let pdf = generate_pdf(&pbn_file.boards, &with_furniture).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+    assert!(pdf.len() > plain_pdf.len());
+}
+
+#[test]
+fn test_title_block_on_every_page() {
+    use pbn_to_pdf::cli::TitleBlock;
+
+    let pbn_path = fixtures_path().join("ABS2-2 Promotion and Length exercises.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let plain = Settings::default().with_metadata(&pbn_file.metadata);
+    let mut with_title = plain.clone();
+    with_title.title_block = TitleBlock::Every;
+    with_title.margin_top += with_title.title_block_height();
+    assert_eq!(
+        with_title.title_details,
+        vec!["Stoneridge Creek Pleasanton", "2016.08.05"]
+    );
+
+    let plain_pdf = generate_pdf(&pbn_file.boards, &plain).expect("Failed to generate PDF");
+    let pdf = generate_pdf(&pbn_file.boards, &with_title).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+    assert!(pdf.len() > plain_pdf.len());
+}
+
+#[test]
+fn test_logo_in_page_header() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.logo = Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/cards/back.svg"));
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+
+    settings.logo = Some(PathBuf::from("does-not-exist.png"));
+    assert!(generate_pdf(&pbn_file.boards, &settings).is_err());
+}
+
+#[test]
+fn test_background_image_and_pdf() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let plain_pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+
+    let mut with_image = settings.clone();
+    with_image.background =
+        Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/cards/back.svg"));
+    let pdf = generate_pdf(&pbn_file.boards, &with_image).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+
+    // Any PDF will do as letterhead; use a plain rendering of the same file
+    let letterhead = std::env::temp_dir().join("pbn-to-pdf-test-letterhead.pdf");
+    fs::write(&letterhead, &plain_pdf).expect("Failed to write letterhead");
+    let mut with_pdf = settings.clone();
+    with_pdf.background = Some(letterhead.clone());
+    let pdf = generate_pdf(&pbn_file.boards, &with_pdf).expect("Failed to generate PDF");
+    let _ = fs::remove_file(&letterhead);
+    let contains =
+        |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).any(|w| w == needle);
+    assert!(pdf.starts_with(b"%PDF"));
+    assert!(contains(&pdf, b"/PbnBackground"));
+}
+
+#[test]
+fn test_template_puts_diagram_on_the_right() {
+    use pbn_to_pdf::config::{DiagramSide, LayoutTemplate};
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let template = LayoutTemplate::parse("[diagram]\nside = \"right\"\n[page]\ncolumns = 1")
+        .expect("Failed to parse template");
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    template.apply(&mut settings);
+    assert_eq!(settings.diagram_side, DiagramSide::Right);
+    assert_eq!(settings.column_count, 1);
+
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_copyright_line_adds_page_text() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let plain = Settings::default().with_metadata(&pbn_file.metadata);
+    let mut with_copyright = plain.clone();
+    with_copyright.copyright_override = Some("© 2024 Bridge Teacher".to_string());
+    assert_eq!(
+        with_copyright.effective_copyright(),
+        Some("© 2024 Bridge Teacher")
+    );
+
+    let plain_pdf = generate_pdf(&pbn_file.boards, &plain).expect("Failed to generate PDF");
+    let pdf = generate_pdf(&pbn_file.boards, &with_copyright).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+    assert!(pdf.len() > plain_pdf.len());
+
+    with_copyright.copyright_override = Some(String::new());
+    assert_eq!(with_copyright.effective_copyright(), None);
+}
+
+#[test]
+fn test_custom_commentary_font_is_embedded() {
+    use std::path::PathBuf;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let font_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/fonts/DejaVuSans-Suits.ttf");
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.custom_fonts.commentary = vec![font_path];
+
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+
+    settings.custom_fonts.commentary = vec![PathBuf::from("does-not-exist.ttf")];
+    assert!(generate_pdf(&pbn_file.boards, &settings).is_err());
+}
+
+#[test]
+fn test_language_changes_generated_labels() {
+    use pbn_to_pdf::cli::Language;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.language = Language::German;
+    assert_eq!(settings.locale().all_pass, "Alle passen");
+
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_color_themes_render_all_layouts() {
+    use pbn_to_pdf::cli::{ColorTheme, Layout};
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    for theme in [
+        ColorTheme::HighContrast,
+        ColorTheme::Pastel,
+        ColorTheme::ClubBranded,
+    ] {
+        for layout in [
+            Layout::Analysis,
+            Layout::BiddingSheets,
+            Layout::DealerSummary,
+        ] {
+            let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+            settings.theme = theme;
+            settings.layout = layout;
+            let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+            assert!(pdf.starts_with(b"%PDF"), "{:?} / {:?}", theme, layout);
+        }
+    }
+}
+
+#[test]
+fn test_grayscale_output() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.grayscale = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_suit_letters_output() {
+    use pbn_to_pdf::cli::SuitStyle;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.suit_style = SuitStyle::Letters;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_hidden_hands_placeholder_and_card_backs() {
+    use pbn_to_pdf::cli::HiddenHandStyle;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    assert!(pbn_file
+        .boards
+        .iter()
+        .any(|b| b.hidden.north || b.hidden.east || b.hidden.south || b.hidden.west));
+
+    for style in [HiddenHandStyle::Placeholder, HiddenHandStyle::CardBacks] {
+        let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+        settings.hidden_hand_style = style;
+        let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"), "{:?}", style);
+    }
+}
+
+#[test]
+fn test_per_hand_hcp_in_compass() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.show_hcp = true;
+    settings.hand_hcp = true;
+    settings.distribution_points = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_board_number_in_compass() {
+    use pbn_to_pdf::cli::HiddenHandStyle;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    for boards_per_page in [1, 4] {
+        let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+        settings.board_in_compass = true;
+        settings.hidden_hand_style = HiddenHandStyle::Placeholder;
+        settings.boards_per_page = boards_per_page;
+        let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
+
+#[test]
+fn test_vulnerable_compass() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.vulnerable_compass = true;
+    settings.board_in_compass = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_lead_marker() {
+    let pbn_path = fixtures_path()
+        .join("ABS3-1 Opening Leads against Notrump Contracts practice deals-NS.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.lead_marker = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_rotated_east_west() {
+    let pbn_path = fixtures_path()
+        .join("ABS3-1 Opening Leads against Notrump Contracts practice deals-NS.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.rotate_east_west = true;
+    settings.lead_marker = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_double_dummy_tricks_table() {
+    use pbn_to_pdf::model::DoubleDummyTricks;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let mut pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    for board in &mut pbn_file.boards {
+        board.double_dummy = DoubleDummyTricks::from_pbn("a9a9ba9a9b3434334343");
+    }
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.show_dd_tricks = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_best_leads_line() {
+    let pbn_path = fixtures_path()
+        .join("ABS3-1 Opening Leads against Notrump Contracts practice deals-NS.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn = content.replace("[Play ", "[BestLeads \"S4 DK\"]\n[Play ");
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+    assert!(pbn_file.boards.iter().any(|b| b.best_leads.len() == 2));
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.show_best_leads = true;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_convention_file_footnotes() {
+    use pbn_to_pdf::parser::parse_conventions;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let mut pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let conventions = parse_conventions("1NT = 15-17\n1NT 2♣ = Stayman\n").unwrap();
+    conventions.annotate_boards(&mut pbn_file.boards);
+    assert!(pbn_file
+        .boards
+        .iter()
+        .filter_map(|b| b.auction.as_ref())
+        .any(|a| a.notes.values().any(|n| n == "Stayman")));
+
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_player_names_as_auction_headers() {
+    use pbn_to_pdf::cli::AuctionHeaders;
+    use pbn_to_pdf::render::components::bidding_table::BiddingTableRenderer;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn = content
+        .replace("[West \"\"]", "[West \"Alexandra Longname\"]")
+        .replace("[North \"\"]", "[North \"Bob\"]");
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+    let players = &pbn_file.boards[0].players;
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.auction_headers = AuctionHeaders::Both;
+    assert_eq!(
+        BiddingTableRenderer::header_label(Direction::West, Some(players), &settings),
+        "Alexandra Longname (W)"
+    );
+    assert_eq!(
+        BiddingTableRenderer::header_label(Direction::East, Some(players), &settings),
+        "East"
+    );
+    assert!(
+        BiddingTableRenderer::column_width_static(Some(players), &settings)
+            > settings.bid_column_width
+    );
+
+    for headers in [AuctionHeaders::Names, AuctionHeaders::Both] {
+        settings.auction_headers = headers;
+        let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
+
+#[test]
+fn test_long_auction_splits_into_blocks() {
+    use pbn_to_pdf::parser::auction::parse_auction;
+    use pbn_to_pdf::render::components::bidding_table::BiddingTableRenderer;
+
+    // Competitive auction: 7 rows of calls plus "All Pass"
+    let auction = parse_auction(
+        Direction::North,
+        "1C 1D 1H 1S 2C 2D 2H 2S 3C 3D 3H 3S 4C 4D 4H 4S 5C 5D 5H 5S 6C 6D 6H 6S Pass Pass Pass",
+    )
+    .expect("Failed to parse auction");
+
+    let mut settings = Settings::default();
+    let full_height = BiddingTableRenderer::measure_height_static(&auction, None, &settings, None);
+    let full_width = BiddingTableRenderer::table_width_static(&auction, None, &settings);
+
+    settings.auction_max_rows = Some(4);
+    let split_height = BiddingTableRenderer::measure_height_static(&auction, None, &settings, None);
+    let split_width = BiddingTableRenderer::table_width_static(&auction, None, &settings);
+
+    assert!((full_height - split_height - 4.0 * settings.bid_row_height).abs() < 0.01);
+    assert!(split_width > 2.0 * full_width);
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.auction_max_rows = Some(2);
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_call_text_overrides() {
+    use pbn_to_pdf::cli::Layout;
+
+    let mut settings = Settings::default();
+    assert_eq!(settings.double_text(), "Dbl");
+    assert_eq!(settings.all_pass_text(), "All Pass");
+
+    settings.call_text.pass = Some("P".to_string());
+    settings.call_text.double = Some("X".to_string());
+    settings.call_text.all_pass = Some("AP".to_string());
+    assert_eq!(settings.pass_text(), "P");
+    assert_eq!(settings.double_text(), "X");
+    assert_eq!(settings.redouble_text(), "Rdbl");
+    assert_eq!(settings.all_pass_text(), "AP");
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    for layout in [Layout::Analysis, Layout::BiddingSheets] {
+        let mut settings = settings.clone().with_metadata(&pbn_file.metadata);
+        settings.layout = layout;
+        let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"), "{:?}", layout);
+    }
+}
+
+#[test]
+fn test_auction_prose_below_table() {
+    use pbn_to_pdf::render::components::bidding_table::BiddingTableRenderer;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+    let auction = pbn_file
+        .boards
+        .iter()
+        .find_map(|b| b.auction.as_ref())
+        .expect("Expected an auction");
+    assert!(auction.to_prose().contains("opened"));
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let plain = BiddingTableRenderer::measure_height_static(auction, None, &settings, None);
+    settings.show_auction_prose = true;
+    let with_prose = BiddingTableRenderer::measure_height_static(auction, None, &settings, None);
+    assert!(with_prose > plain);
+
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_commentary_tables() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let table = "{Count your tricks:<table><tr><td><b>Suit</b></td><td><b>Tricks</b></td></tr>\n\
+                 <tr><td>\\S</td><td>5</td></tr><tr><td>\\H</td><td>2</td></tr></table>\
+                 Or as tabs:\\nSuit\tTricks\\n\\D\t4}\n";
+    let pbn = content.replacen("[Auction", &format!("{}[Auction", table), 1);
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+    assert!(pbn_file
+        .boards
+        .iter()
+        .flat_map(|b| &b.commentary)
+        .any(|c| c.content.to_plain_text().contains("Suit\tTricks")));
+
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_justified_commentary() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    // Stayman.pbn sets %BCOptions Justify; add a CJK paragraph, which wraps between characters
+    let pbn = content.replacen(
+        "[Auction",
+        "{東西の守りは難しい。南は一番目のトリックで勝ち、クラブを続けて切り札を引き、最後にダイヤモンドで勝つ。東西の守りは難しい。}\n[Auction",
+        1,
+    );
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    assert!(settings.justify);
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_hyphenated_commentary() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.hyphenate = true;
+    settings.boards_per_page = 4;
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_superscript_and_subscript_commentary() {
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn = content.replacen(
+        "[Auction",
+        "{South bids 2\\C<sup>1</sup>; 4\\S<sub>x</sub> would go one off (4\\S<sup>\\S-1</sup>).}\n[Auction",
+        1,
+    );
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+
+    let settings = Settings::default().with_metadata(&pbn_file.metadata);
+    let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_seat_commentary_on_bidding_sheets() {
+    use pbn_to_pdf::cli::Layout;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn = content.replacen(
+        "[Auction",
+        "{N: With a balanced 15-17 count, open 1NT.}\n{S: Think about Stayman.}\n[Auction",
+        1,
+    );
+    let pbn_file = parse_pbn(&pbn).expect("Failed to parse PBN");
+
+    let board = &pbn_file.boards[0];
+    let seats: Vec<_> = board.commentary.iter().filter_map(|c| c.seat).collect();
+    assert_eq!(seats, vec![Direction::North, Direction::South]);
+    assert!(board
+        .general_commentary()
+        .all(|block| !block.content.to_plain_text().contains("Stayman.")));
+
+    for layout in [Layout::Analysis, Layout::BiddingSheets] {
+        let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+        settings.layout = layout;
+        let pdf = generate_pdf(&pbn_file.boards, &settings).expect("Failed to generate PDF");
+        assert!(pdf.starts_with(b"%PDF"), "{:?}", layout);
+    }
+}
+
+#[test]
+fn test_settings_round_trip_through_serde() {
+    use pbn_to_pdf::cli::{Layout, TitleBlock};
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+    settings.layout = Layout::DeclarersPlan1up;
+    settings.title_block = TitleBlock::Every;
+    settings.header.center = "{event}".to_string();
+
+    let text = toml::to_string(&settings).expect("Failed to serialize settings");
+    assert!(text.contains("layout = \"declarers-plan-1up\""));
+    let restored: Settings = toml::from_str(&text).expect("Failed to deserialize settings");
+    assert_eq!(toml::to_string(&restored).unwrap(), text);
+    assert_eq!(restored.header.center, "{event}");
+
+    // Missing keys keep their defaults
+    let partial: Settings = toml::from_str("boards_per_page = 2\nlayout = \"bidding-sheets\"")
+        .expect("Failed to deserialize partial settings");
+    assert_eq!(partial.boards_per_page, 2);
+    assert_eq!(partial.layout, Layout::BiddingSheets);
+    assert_eq!(partial.page_width, Settings::default().page_width);
+}
+
+#[test]
+fn test_duplex_page_order() {
+    use pbn_to_pdf::cli::Duplex;
+    use pbn_to_pdf::render::BiddingSheetsRenderer;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let page_count = |duplex: Option<Duplex>| {
+        let mut settings = Settings::default().with_metadata(&pbn_file.metadata);
+        settings.duplex = duplex;
+        let pdf = BiddingSheetsRenderer::new(settings)
+            .render(&pbn_file.boards)
+            .expect("Failed to generate bidding sheets");
+        let doc = lopdf::Document::load_mem(&pdf).expect("Failed to parse PDF");
+        let rotated = doc
+            .get_pages()
+            .values()
+            .filter(|&&id| {
+                doc.get_dictionary(id)
+                    .and_then(|page| page.get(b"Rotate"))
+                    .and_then(lopdf::Object::as_i64)
+                    .is_ok_and(|rotate| rotate == 180)
+            })
+            .count();
+        (doc.get_pages().len(), rotated)
+    };
+
+    // Answers behind both practice pages, or once at the end single-sided
+    let (duplex_pages, _) = page_count(None);
+    let (single_pages, _) = page_count(Some(Duplex::None));
+    assert_eq!(single_pages * 4, duplex_pages * 3);
+
+    let (short_pages, rotated) = page_count(Some(Duplex::Short));
+    assert_eq!(short_pages % 2, 0);
+    assert_eq!(rotated, short_pages / 2);
+}
+
+#[test]
+fn test_chunked_rendering() {
+    use pbn_to_pdf::cli::{Duplex, Layout};
+    use pbn_to_pdf::render::renderer_for;
+
+    let pbn_path = fixtures_path().join("Stayman.pbn");
+    let content = fs::read_to_string(&pbn_path).expect("Failed to read PBN file");
+    let pbn_file = parse_pbn(&content).expect("Failed to parse PBN");
+
+    let render = |chunk_size: Option<usize>| {
+        let mut settings = Settings::for_layout(Layout::Analysis).with_metadata(&pbn_file.metadata);
+        settings.column_count = 1;
+        settings.duplex = Some(Duplex::Short);
+        settings.chunk_size = chunk_size;
+        let pdf = renderer_for(settings)
+            .render(&pbn_file.boards)
+            .expect("Failed to generate PDF");
+        let doc = lopdf::Document::load_mem(&pdf).expect("Failed to parse PDF");
+        let rotated: Vec<u32> = doc
+            .get_pages()
+            .iter()
+            .filter(|(_, &id)| {
+                doc.get_dictionary(id)
+                    .and_then(|page| page.get(b"Rotate"))
+                    .and_then(lopdf::Object::as_i64)
+                    .is_ok_and(|rotate| rotate == 180)
+            })
+            .map(|(&number, _)| number)
+            .collect();
+        (doc.get_pages().len(), rotated)
+    };
+
+    // One board a page either way, so chunking changes nothing a reader sees
+    let (pages, rotated) = render(None);
+    assert_eq!(render(Some(1)), (pages, rotated.clone()));
+    assert_eq!(render(Some(3)), (pages, rotated));
+}
+
+#[test]
+fn test_partial_deals() {
+    use pbn_to_pdf::render::components::DiagramDisplayOptions;
+
+    let content = r#"[Event ""]
+[Board "1"]
+[Deal "N:AKQ2.KQ3.A74.Q52 - J43.A52.KQ3.AK64 -"]
+[Event ""]
+[Board "2"]
+[Deal "E:AKQ2.KQ3.A74.Q52 - J43.A52.KQ3.AK64 -"]
+[Event ""]
+[Board "3"]
+[Deal "N:AKQ2.KQ3.A74.Q52"]
+[Event ""]
+[Board "4"]
+[Deal "S:AQxx... - K9x... -"]
+"#;
+    let pbn_file = parse_pbn(content).expect("Failed to parse PBN");
+    let boards = &pbn_file.boards;
+
+    // A pair's hands: the left-out hands are hidden, the compass stays
+    let options = DiagramDisplayOptions::from_deal(&boards[0].deal, &boards[0].hidden);
+    assert!(options.hidden.east && options.hidden.west);
+    assert!(!options.hidden.north && !options.hidden.south);
+    assert!(!options.hide_compass);
+    // A single hand is drawn without the compass
+    let options = DiagramDisplayOptions::from_deal(&boards[2].deal, &boards[2].hidden);
+    assert_eq!(options.single_visible_hand, Some(Direction::North));
+    // A one-suit fragment of a pair's hands
+    let options = DiagramDisplayOptions::from_deal(&boards[3].deal, &boards[3].hidden);
+    assert_eq!(options.suits_present, [Suit::Spades]);
+    assert!(options.hidden.east && options.hidden.west);
+
+    // East-West only leaves out the North and South rows
+    let renderer = DocumentRenderer::new(Settings::default());
+    let width = renderer.column_width();
+    assert!(renderer.board_height(&boards[1], width) < renderer.board_height(&boards[0], width));
+
+    let pdf = generate_pdf(boards, &Settings::default()).expect("Failed to generate PDF");
+    assert!(pdf.starts_with(b"%PDF"));
+}